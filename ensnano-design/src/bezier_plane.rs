@@ -272,6 +272,10 @@ impl BezierPath {
         self.vertices.as_mut_slice()
     }
 
+    pub fn set_vertices(&mut self, vertices: Vec<BezierVertex>) {
+        self.vertices = vertices;
+    }
+
     #[must_use]
     pub fn remove_vertex(&mut self, v_id: usize) -> Option<()> {
         if self.vertices.len() > v_id {
@@ -157,6 +157,24 @@ pub enum SvgImportError {
     BadParameters,
 }
 
+impl std::fmt::Display for SvgImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "Could not read the file: {}", e),
+            Self::SvgParserError(e) => write!(f, "Could not parse the svg file: {}", e),
+            Self::NoPathFound => write!(f, "The svg file contains no path"),
+            Self::AttributeNotFound(attribute) => {
+                write!(f, "Attribute {} is missing in the svg file", attribute)
+            }
+            Self::UnexpectedCommand(command) => {
+                write!(f, "Unexpected command {} in the svg file", command)
+            }
+            Self::CouldNotParseData => write!(f, "Could not parse the data of a path"),
+            Self::BadParameters => write!(f, "Bad parameters in the svg file"),
+        }
+    }
+}
+
 impl From<std::io::Error> for SvgImportError {
     fn from(e: std::io::Error) -> Self {
         Self::IOError(e)
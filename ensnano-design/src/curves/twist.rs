@@ -59,6 +59,44 @@ pub struct Twist {
     pub t_max: Option<f64>,
 }
 
+impl Twist {
+    /// A twist that goes through `point` at t = 0 and turns around the axis going through
+    /// `axis_origin` with direction `axis_direction`.
+    ///
+    /// The curve is oriented so that its tangent at t = 0 points in the same half-space as
+    /// `helix_direction`. Return `None` if the axis direction is the null vector.
+    pub fn around_axis(
+        point: Vec3,
+        helix_direction: Vec3,
+        axis_origin: Vec3,
+        axis_direction: Vec3,
+        omega: f64,
+    ) -> Option<Self> {
+        if axis_direction.mag() < 1e-5 {
+            return None;
+        }
+        let axis_direction = if axis_direction.dot(helix_direction) < 0. {
+            -axis_direction.normalized()
+        } else {
+            axis_direction.normalized()
+        };
+        let orientation = Rotor3::from_rotation_between(Vec3::unit_x(), axis_direction);
+        let position = axis_origin + (point - axis_origin).dot(axis_direction) * axis_direction;
+        let origin_vec = point - position;
+        let y_vec = Vec3::unit_y().rotated_by(orientation);
+        let z_vec = Vec3::unit_z().rotated_by(orientation);
+        Some(Self {
+            theta0: origin_vec.dot(y_vec).atan2(origin_vec.dot(z_vec)) as f64,
+            omega,
+            position,
+            orientation,
+            radius: origin_vec.mag() as f64,
+            t_min: None,
+            t_max: None,
+        })
+    }
+}
+
 impl Curved for Twist {
     fn t_max(&self) -> f64 {
         if let Some(tmax) = self.t_max {
@@ -195,6 +233,19 @@ mod tests {
         assert!((s - expected).abs() < 1e-3);
     }
 
+    #[test]
+    fn twist_around_axis_goes_through_point() {
+        let point = Vec3::new(3., 2., -1.);
+        let axis_origin = Vec3::new(0., -1., 4.);
+        let axis_direction = Vec3::new(1., 1., 0.).normalized();
+        let twist =
+            Twist::around_axis(point, -Vec3::unit_x(), axis_origin, axis_direction, 0.05).unwrap();
+        let at_zero = twist.position(0.0);
+        assert!((at_zero - vec_to_dvec(point)).mag() < 1e-4);
+        // The curve follows the helix direction, not the axis direction
+        assert!(twist.speed(0.0).dot(DVec3::unit_x()) < 0.);
+    }
+
     #[allow(non_snake_case)]
     #[test]
     fn nb_turn_per_100_nt_is_correct() {
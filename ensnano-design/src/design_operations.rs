@@ -19,14 +19,45 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! The functions that apply thes operations take a mutable reference to the design that they are
 //! modifying and may return an `ErrOperation` if the opperation could not be applied.
 
-use super::{bezier_plane::*, grid::*, CurveDescriptor, Design};
+use super::{
+    bezier_plane::*, grid::*, helix_bundles, BundleId, CurveDescriptor, Design, JointBody, JointId,
+    Nucl,
+};
 use std::sync::Arc;
 use ultraviolet::{Rotor3, Vec3};
 
+mod bezier_path_helices;
+mod bezier_path_smoothing;
+mod bundles;
+mod helix_numbering;
+mod ideal_roll;
+mod insertion_placement;
+mod kinematic_joints;
+mod nick_optimization;
+mod random_sequences;
+mod sequence_assignment;
+mod strand_cleanup;
+mod xover_respacing;
+pub use bezier_path_helices::*;
+pub use bezier_path_smoothing::*;
+pub use bundles::*;
+pub use helix_numbering::*;
+pub use ideal_roll::*;
+pub use insertion_placement::*;
+pub use kinematic_joints::*;
+pub use nick_optimization::*;
+pub use random_sequences::*;
+pub use sequence_assignment::*;
+pub use strand_cleanup::*;
+pub use xover_respacing::*;
+
 /// An error that occured when trying to apply an operation.
 #[derive(Debug)]
 pub enum ErrOperation {
-    NotEnoughHelices { actual: usize, needed: usize },
+    NotEnoughHelices {
+        actual: usize,
+        needed: usize,
+    },
     GridPositionAlreadyUsed,
     HelixDoesNotExists(usize),
     GridDoesNotExist(GridId),
@@ -34,7 +65,76 @@ pub enum ErrOperation {
     NotEnoughBezierPoints,
     HelixIsNotPiecewiseBezier,
     CouldNotGetPath(BezierPathId),
+    /// The operation needs all the vertices of the path to be on the same bezier plane
+    PathOnSeveralPlanes(BezierPathId),
     CouldNotGetVertex(BezierVertexId),
+    /// The cross-over with the given 3' end and 5' end is not in the design
+    XoverDoesNotExist((Nucl, Nucl)),
+    StrandDoesNotExist(usize),
+    NuclDoesNotExist(Nucl),
+    /// The operation cannot be applied on a nucleotide at the end of a domain
+    NuclAtDomainEnd(Nucl),
+    /// There is no nick between two strands after the given nucleotide
+    NickDoesNotExist(Nucl),
+    /// The operation needs a scaffold with a sequence
+    NoScaffoldSequence,
+    BundleDoesNotExist(BundleId),
+    /// A bundle must contain at least one helix
+    EmptyBundle,
+    JointDoesNotExist(JointId),
+    JointBodyDoesNotExist(JointBody),
+    /// A joint must link two different bodies
+    JointOnSingleBody,
+}
+
+impl std::fmt::Display for ErrOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughHelices { actual, needed } => write!(
+                f,
+                "At least {} helices are needed, only {} were given",
+                needed, actual
+            ),
+            Self::GridPositionAlreadyUsed => write!(f, "This grid position is already used"),
+            Self::HelixDoesNotExists(h_id) => write!(f, "Helix {} does not exist", h_id),
+            Self::GridDoesNotExist(g_id) => write!(f, "Grid {:?} does not exist", g_id),
+            Self::HelixCollisionDuringTranslation => {
+                write!(f, "The helices would collide with other helices")
+            }
+            Self::NotEnoughBezierPoints => write!(f, "The curve does not have enough points"),
+            Self::HelixIsNotPiecewiseBezier => {
+                write!(f, "The helix is not a piecewise bezier curve")
+            }
+            Self::CouldNotGetPath(path_id) => write!(f, "Bezier path {:?} does not exist", path_id),
+            Self::PathOnSeveralPlanes(path_id) => write!(
+                f,
+                "The vertices of bezier path {:?} are not on the same plane",
+                path_id
+            ),
+            Self::CouldNotGetVertex(vertex_id) => {
+                write!(f, "Bezier vertex {:?} does not exist", vertex_id)
+            }
+            Self::XoverDoesNotExist((prime5, prime3)) => {
+                write!(f, "There is no cross-over from {} to {}", prime5, prime3)
+            }
+            Self::StrandDoesNotExist(s_id) => write!(f, "Strand {} does not exist", s_id),
+            Self::NuclDoesNotExist(nucl) => write!(f, "Nucleotide {} does not exist", nucl),
+            Self::NuclAtDomainEnd(nucl) => write!(
+                f,
+                "This cannot be done on nucleotide {}, which is at the end of a domain",
+                nucl
+            ),
+            Self::NickDoesNotExist(nucl) => write!(f, "There is no nick after nucleotide {}", nucl),
+            Self::NoScaffoldSequence => write!(f, "The scaffold has no sequence"),
+            Self::BundleDoesNotExist(b_id) => write!(f, "Bundle {} does not exist", b_id.0),
+            Self::EmptyBundle => write!(f, "A bundle must contain at least one helix"),
+            Self::JointDoesNotExist(j_id) => write!(f, "Joint {} does not exist", j_id.0),
+            Self::JointBodyDoesNotExist(body) => {
+                write!(f, "The body {:?} of the joint does not exist", body)
+            }
+            Self::JointOnSingleBody => write!(f, "A joint must link two different bodies"),
+        }
+    }
 }
 
 /// The minimum number of helices requiered to infer a grid
@@ -106,6 +206,7 @@ pub fn attach_object_to_grid(
 
 /// Translate helices by a given translation.
 ///
+/// The other helices of the bundles of the helices are translated as well.
 /// If snap is true, the helices are mapped to grid position.
 /// If this translation would cause helices to compete with other helices for a grid position,
 /// an error is returned.
@@ -115,12 +216,18 @@ pub fn translate_helices(
     helices: Vec<usize>,
     translation: Vec3,
 ) -> Result<(), ErrOperation> {
-    let mut helices_translator = HelicesTranslator::from_design(design);
-    helices_translator.translate_helices(snap, helices, translation)
+    let (helices, bundles) = helix_bundles::with_bundle_mates(&design.helix_bundles, helices);
+    {
+        let mut helices_translator = HelicesTranslator::from_design(design);
+        helices_translator.translate_helices(snap, helices, translation)?;
+    }
+    helix_bundles::translate_bundles(&mut design.helix_bundles, &bundles, translation);
+    Ok(())
 }
 
 /// Rotate helices by a given rotation
 ///
+/// The other helices of the bundles of the helices are rotated as well.
 /// If snap is true, the helices are mapped to grid position.
 /// If this rotation would cause helices to compete with other helices for a grid position,
 /// an error is returned.
@@ -131,6 +238,11 @@ pub fn rotate_helices_3d(
     rotation: Rotor3,
     origin: Vec3,
 ) -> Result<(), ErrOperation> {
-    let mut helices_translator = HelicesTranslator::from_design(design);
-    helices_translator.rotate_helices_3d(snap, helices, rotation, origin)
+    let (helices, bundles) = helix_bundles::with_bundle_mates(&design.helix_bundles, helices);
+    {
+        let mut helices_translator = HelicesTranslator::from_design(design);
+        helices_translator.rotate_helices_3d(snap, helices, rotation, origin)?;
+    }
+    helix_bundles::rotate_bundles(&mut design.helix_bundles, &bundles, rotation, origin);
+    Ok(())
 }
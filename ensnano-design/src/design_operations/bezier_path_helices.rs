@@ -0,0 +1,115 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Preparation of the bezier paths along which new helices are built, so that the new helices
+//! continue the existing ones.
+
+use super::ErrOperation;
+use crate::{BezierPathId, Collection, Design, Domain, HelixCollection};
+use std::collections::BTreeMap;
+use ultraviolet::Vec3;
+
+/// The largest distance, in nanometers, between an end of a bezier path and the continuation of
+/// an existing helix for the end to be moved onto that continuation.
+pub const BEZIER_END_SNAPPING_DISTANCE: f32 = 2.;
+
+/// Move the first and last vertices of the path `path_id` onto the positions that continue the
+/// closest existing helices, if these positions are closer than
+/// [`BEZIER_END_SNAPPING_DISTANCE`]. The vertices of a cyclic path are not moved.
+///
+/// The position that continues a helix is the position of the axis one nucleotide beyond the
+/// last, or before the first, nucleotide of the helix that belongs to a strand.
+///
+/// Return the number of vertices that were moved.
+pub fn snap_bezier_path_ends(
+    design: &mut Design,
+    path_id: BezierPathId,
+) -> Result<usize, ErrOperation> {
+    let path = design
+        .bezier_paths
+        .get(&path_id)
+        .ok_or(ErrOperation::CouldNotGetPath(path_id))?;
+    if path.is_cyclic || path.vertices().is_empty() {
+        return Ok(0);
+    }
+    let mut end_vertices = vec![0, path.vertices().len() - 1];
+    end_vertices.dedup();
+    let end_positions: Vec<(usize, Vec3)> = end_vertices
+        .iter()
+        .filter_map(|v_id| {
+            path.vertices()[*v_id]
+                .grid_position(&design.bezier_planes)
+                .map(|p| (*v_id, p))
+        })
+        .collect();
+
+    let targets = helix_continuations(design);
+    let mut translations = Vec::new();
+    for (v_id, position) in end_positions {
+        let closest = targets
+            .iter()
+            .map(|target| (*target - position, (*target - position).mag()))
+            .filter(|(_, distance)| *distance < BEZIER_END_SNAPPING_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((translation, _)) = closest {
+            translations.push((v_id, translation));
+        }
+    }
+
+    let mut paths = design.bezier_paths.make_mut();
+    let path = paths
+        .get_mut(&path_id)
+        .ok_or(ErrOperation::CouldNotGetPath(path_id))?;
+    for (v_id, translation) in translations.iter() {
+        if let Some(vertex) = path.get_vertex_mut(*v_id) {
+            vertex.add_translation(*translation);
+        }
+    }
+    Ok(translations.len())
+}
+
+/// The positions that continue the helices of `design` beyond their first and last nucleotides
+/// that belong to a strand.
+fn helix_continuations(design: &Design) -> Vec<Vec3> {
+    let mut used_intervals: BTreeMap<usize, (isize, isize)> = BTreeMap::new();
+    for strand in design.strands.values() {
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                if interval.start >= interval.end {
+                    continue;
+                }
+                let bounds = used_intervals
+                    .entry(interval.helix)
+                    .or_insert((interval.start, interval.end - 1));
+                bounds.0 = bounds.0.min(interval.start);
+                bounds.1 = bounds.1.max(interval.end - 1);
+            }
+        }
+    }
+    let parameters = design.helix_parameters.unwrap_or_default();
+    used_intervals
+        .iter()
+        .filter_map(|(h_id, (min, max))| {
+            let helix = design.helices.get(h_id)?;
+            Some([
+                helix.axis_position(&parameters, *min - 1, true),
+                helix.axis_position(&parameters, *max + 1, true),
+            ])
+        })
+        .flatten()
+        .collect()
+}
@@ -0,0 +1,220 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Edition of the control points of the bezier paths along which curved helices are built.
+//!
+//! The first and last vertices of a path are never moved by these operations, so that the ends of
+//! the helices built on the path stay anchored where they were.
+
+use super::ErrOperation;
+use crate::{
+    BezierPathId, BezierPlaneId, BezierVertex, Collection, Curved, Design, HelixCollection,
+    HelixParameters,
+};
+use ultraviolet::Vec2;
+
+/// The number of points per segment of a path used to approximate its length.
+const NB_SAMPLES_PER_SEGMENT: usize = 100;
+
+/// The default largest distance, in nanometers, by which the simplification of a path may move
+/// the position of one of its vertices.
+pub const DEFAULT_SIMPLIFICATION_TOLERANCE: f32 = 1.;
+
+/// An edition of the control points of a bezier path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlPointsEdit {
+    /// Replace the vertices of the path by `nb_vertices` vertices evenly spaced along the path.
+    Resample { nb_vertices: usize },
+    /// Move each interior vertex halfway to the middle of its two neighbours, `nb_iterations`
+    /// times. The tangents of the interior vertices are reset to their default value.
+    Smooth { nb_iterations: usize },
+    /// Remove the interior vertices that are closer than `tolerance` nanometers to the path
+    /// going through the remaining vertices.
+    Simplify { tolerance: f32 },
+}
+
+/// Apply `edit` to the vertices of the path `path_id`.
+///
+/// All the vertices of the path must lie on the same bezier plane.
+pub fn edit_bezier_path_control_points(
+    design: &mut Design,
+    path_id: BezierPathId,
+    edit: ControlPointsEdit,
+) -> Result<(), ErrOperation> {
+    let path = design
+        .bezier_paths
+        .get(&path_id)
+        .ok_or(ErrOperation::CouldNotGetPath(path_id))?;
+    let plane_id = path
+        .vertices()
+        .first()
+        .map(|v| v.plane_id)
+        .ok_or(ErrOperation::NotEnoughBezierPoints)?;
+    if path.vertices().iter().any(|v| v.plane_id != plane_id) {
+        return Err(ErrOperation::PathOnSeveralPlanes(path_id));
+    }
+
+    let new_vertices = match edit {
+        ControlPointsEdit::Resample { nb_vertices } => {
+            if nb_vertices < 2 {
+                return Err(ErrOperation::NotEnoughBezierPoints);
+            }
+            resampled_vertices(path, plane_id, nb_vertices)
+        }
+        ControlPointsEdit::Smooth { nb_iterations } => smoothed_vertices(path, nb_iterations),
+        ControlPointsEdit::Simplify { tolerance } => simplified_vertices(path, tolerance),
+    }
+    .ok_or(ErrOperation::NotEnoughBezierPoints)?;
+
+    let mut paths = design.bezier_paths.make_mut();
+    let path = paths
+        .get_mut(&path_id)
+        .ok_or(ErrOperation::CouldNotGetPath(path_id))?;
+    path.set_vertices(new_vertices);
+    Ok(())
+}
+
+fn resampled_vertices(
+    path: &crate::BezierPath,
+    plane_id: BezierPlaneId,
+    nb_vertices: usize,
+) -> Option<Vec<BezierVertex>> {
+    let vertices = path.vertices();
+    if vertices.len() < 2 {
+        return None;
+    }
+    let curve = path.to_instanciated_path_2d()?;
+    let nb_segments = vertices.len() - 1;
+    let nb_samples = nb_segments * NB_SAMPLES_PER_SEGMENT;
+    let samples: Vec<Vec2> = (0..=nb_samples)
+        .map(|i| {
+            let p = curve.position(i as f64 / NB_SAMPLES_PER_SEGMENT as f64);
+            Vec2::new(p.x as f32, p.y as f32)
+        })
+        .collect();
+    let mut lengths = vec![0f32];
+    for w in samples.windows(2) {
+        lengths.push(lengths.last().unwrap() + (w[1] - w[0]).mag());
+    }
+    let total_length = *lengths.last().unwrap();
+
+    let mut ret = Vec::with_capacity(nb_vertices);
+    ret.push(vertices[0]);
+    let mut sample_idx = 0;
+    for i in 1..(nb_vertices - 1) {
+        let target = total_length * i as f32 / (nb_vertices - 1) as f32;
+        while sample_idx + 1 < nb_samples && lengths[sample_idx + 1] < target {
+            sample_idx += 1;
+        }
+        let segment_length = lengths[sample_idx + 1] - lengths[sample_idx];
+        let s = if segment_length > 0. {
+            (target - lengths[sample_idx]) / segment_length
+        } else {
+            0.
+        };
+        let position = samples[sample_idx] * (1. - s) + samples[sample_idx + 1] * s;
+        ret.push(BezierVertex::new(plane_id, position));
+    }
+    ret.push(vertices[vertices.len() - 1]);
+    Some(ret)
+}
+
+fn smoothed_vertices(path: &crate::BezierPath, nb_iterations: usize) -> Option<Vec<BezierVertex>> {
+    let mut vertices = path.vertices().to_vec();
+    if vertices.is_empty() {
+        return None;
+    }
+    let last = vertices.len() - 1;
+    for _ in 0..nb_iterations {
+        let positions: Vec<Vec2> = vertices.iter().map(|v| v.position).collect();
+        for i in 1..last {
+            let middle = (positions[i - 1] + positions[i + 1]) / 2.;
+            vertices[i].position = (positions[i] + middle) / 2.;
+        }
+    }
+    for v in vertices.iter_mut().take(last).skip(1) {
+        v.position_in = None;
+        v.position_out = None;
+    }
+    Some(vertices)
+}
+
+fn simplified_vertices(path: &crate::BezierPath, tolerance: f32) -> Option<Vec<BezierVertex>> {
+    let vertices = path.vertices();
+    if vertices.is_empty() {
+        return None;
+    }
+    let positions: Vec<Vec2> = vertices.iter().map(|v| v.position).collect();
+    let mut kept = vec![false; vertices.len()];
+    kept[0] = true;
+    kept[vertices.len() - 1] = true;
+    // Ramer–Douglas–Peucker: keep the vertex that is the furthest from the chord of each interval
+    // as long as it is further than the tolerance.
+    let mut intervals = vec![(0, vertices.len() - 1)];
+    while let Some((first, last)) = intervals.pop() {
+        let furthest = (first + 1..last)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(positions[i], positions[first], positions[last]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, distance)) = furthest {
+            if distance > tolerance {
+                kept[i] = true;
+                intervals.push((first, i));
+                intervals.push((i, last));
+            }
+        }
+    }
+    Some(
+        vertices
+            .iter()
+            .zip(kept.iter())
+            .filter(|(_, kept)| **kept)
+            .map(|(v, _)| *v)
+            .collect(),
+    )
+}
+
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.mag_sq();
+    if len_sq < f32::EPSILON {
+        return (point - a).mag();
+    }
+    let s = ((point - a).dot(ab) / len_sq).clamp(0., 1.);
+    (point - (a + ab * s)).mag()
+}
+
+/// The smallest radius of curvature that the axis of a helix can have for the helix to be drawn
+/// without its inner side folding onto itself.
+pub fn min_renderable_radius_of_curvature(parameters: &HelixParameters) -> f64 {
+    parameters.helix_radius as f64
+}
+
+/// The smallest radius of curvature, in nanometers, of the helices built on the path `path_id`,
+/// or `None` if no curved helix is built on that path.
+pub fn min_radius_of_curvature_along_path(design: &Design, path_id: BezierPathId) -> Option<f64> {
+    design
+        .helices
+        .values()
+        .filter(|h| h.path_id == Some(path_id))
+        .filter_map(|h| h.min_radius_of_curvature())
+        .min_by(f64::total_cmp)
+}
@@ -0,0 +1,108 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Operations that create, edit and move helix bundles.
+
+use super::ErrOperation;
+use crate::{
+    remove_helices_from_bundles, BundleId, Design, HelixBundle, HelixCollection, JointBody,
+};
+use std::collections::BTreeSet;
+use ultraviolet::{Rotor3, Vec3};
+
+/// Create a bundle made of `helices` and return its identifier. The helices are removed from the
+/// bundles they belonged to, and bundles that become empty are deleted together with their
+/// joints.
+pub fn create_helix_bundle(
+    design: &mut Design,
+    name: String,
+    helices: Vec<usize>,
+) -> Result<BundleId, ErrOperation> {
+    let helices: BTreeSet<usize> = helices
+        .into_iter()
+        .filter(|h| design.helices.contains_key(h))
+        .collect();
+    if helices.is_empty() {
+        return Err(ErrOperation::EmptyBundle);
+    }
+    for bundle_id in remove_helices_from_bundles(&mut design.helix_bundles, &helices) {
+        design
+            .joints
+            .retain(|_, joint| !joint.links_body(JointBody::Bundle(bundle_id)));
+    }
+    let id = design
+        .helix_bundles
+        .keys()
+        .last()
+        .map(|id| BundleId(id.0 + 1))
+        .unwrap_or(BundleId(0));
+    let bundle = HelixBundle::new(design, name, helices);
+    design.helix_bundles.insert(id, bundle);
+    Ok(id)
+}
+
+/// Delete a bundle, together with the joints attached to it.
+pub fn delete_helix_bundle(design: &mut Design, bundle_id: BundleId) -> Result<(), ErrOperation> {
+    design
+        .helix_bundles
+        .remove(&bundle_id)
+        .ok_or(ErrOperation::BundleDoesNotExist(bundle_id))?;
+    design
+        .joints
+        .retain(|_, joint| !joint.links_body(JointBody::Bundle(bundle_id)));
+    Ok(())
+}
+
+pub fn rename_helix_bundle(
+    design: &mut Design,
+    bundle_id: BundleId,
+    name: String,
+) -> Result<(), ErrOperation> {
+    let bundle = design
+        .helix_bundles
+        .get_mut(&bundle_id)
+        .ok_or(ErrOperation::BundleDoesNotExist(bundle_id))?;
+    bundle.name = name;
+    Ok(())
+}
+
+/// Move a bundle so that its pivot gets the given position and orientation. The helices of the
+/// bundle undergo the same rigid transformation as the pivot.
+pub fn set_helix_bundle_pose(
+    design: &mut Design,
+    bundle_id: BundleId,
+    position: Vec3,
+    orientation: Rotor3,
+) -> Result<(), ErrOperation> {
+    let bundle = design
+        .helix_bundles
+        .get_mut(&bundle_id)
+        .ok_or(ErrOperation::BundleDoesNotExist(bundle_id))?;
+    let rotation = orientation * bundle.pivot.orientation.reversed();
+    let old_position = bundle.pivot.position;
+    bundle.pivot.position = position;
+    bundle.pivot.orientation = orientation;
+    let helices = bundle.helices.clone();
+    let mut new_helices = design.helices.make_mut();
+    for h_id in helices.iter() {
+        if let Some(helix) = new_helices.get_mut(h_id) {
+            helix.rotate_arround(rotation, old_position);
+            helix.translate(position - old_position);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Compute the numbers with which helices are displayed, independently of their identifiers.
+
+use crate::{Design, HelixCollection};
+use std::collections::BTreeMap;
+
+/// A scheme used to assign display numbers to the helices of a design.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelixNumbering {
+    /// On each grid, number the helices row by row, from left to right.
+    RowMajor,
+    /// On each grid, number the helices row by row, alternating the direction of the rows.
+    Serpentine,
+    /// Display the identifiers of the helices.
+    Identifiers,
+}
+
+impl HelixNumbering {
+    pub const ALL: &'static [Self] = &[Self::RowMajor, Self::Serpentine, Self::Identifiers];
+}
+
+impl std::fmt::Display for HelixNumbering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::RowMajor => "Row by row",
+            Self::Serpentine => "Serpentine",
+            Self::Identifiers => "Helix identifiers",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Return the display number that `scheme` assigns to each helix of `design`.
+///
+/// The grids are numbered one after the other and the numbers keep increasing from one grid to
+/// the next. Helices that are not on a grid are numbered last, in the order of their
+/// identifiers. With [HelixNumbering::Identifiers], every helix is mapped to `None`.
+pub fn helix_display_numbers(
+    design: &Design,
+    scheme: HelixNumbering,
+) -> BTreeMap<usize, Option<usize>> {
+    if scheme == HelixNumbering::Identifiers {
+        return design.helices.keys().map(|h_id| (*h_id, None)).collect();
+    }
+    let mut on_grid: Vec<_> = Vec::new();
+    let mut off_grid = Vec::new();
+    for (h_id, helix) in design.helices.iter() {
+        if let Some(position) = helix.grid_position {
+            on_grid.push((position.grid, position.y, position.x, *h_id));
+        } else {
+            off_grid.push(*h_id);
+        }
+    }
+    on_grid.sort_by(|a, b| {
+        a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then_with(|| {
+            let serpentine_reversed = scheme == HelixNumbering::Serpentine && a.1 % 2 != 0;
+            if serpentine_reversed {
+                b.2.cmp(&a.2)
+            } else {
+                a.2.cmp(&b.2)
+            }
+        })
+    });
+    on_grid
+        .into_iter()
+        .map(|(_, _, _, h_id)| h_id)
+        .chain(off_grid)
+        .enumerate()
+        .map(|(number, h_id)| (h_id, Some(number)))
+        .collect()
+}
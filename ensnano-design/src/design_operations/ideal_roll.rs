@@ -0,0 +1,74 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Compute the roll of helices that minimizes the length of the cross-overs leaving them.
+
+use crate::{Design, HelixCollection};
+use std::f32::consts::PI;
+
+/// The number of rolls, evenly spaced on a full turn, among which the ideal roll is chosen.
+const NB_ROLL_SAMPLES: usize = 360;
+
+/// Return the roll, in `[-PI, PI)`, at which the sum of the squared lengths of the cross-overs
+/// between helix `h_id` and other helices is the smallest.
+///
+/// The other helices are assumed to keep their current roll. Return `None` if the helix does
+/// not exist or if no cross-over links it to an other helix.
+pub fn ideal_roll(design: &Design, h_id: usize) -> Option<f32> {
+    let helix_parameters = design.helix_parameters.unwrap_or_default();
+    let mut helix = design.helices.get(&h_id)?.clone();
+    let xovers: Vec<_> = design
+        .strands
+        .get_xovers()
+        .into_iter()
+        .filter_map(|(n1, n2)| {
+            if n1.helix == h_id && n2.helix != h_id {
+                Some((n1, n2))
+            } else if n2.helix == h_id && n1.helix != h_id {
+                Some((n2, n1))
+            } else {
+                None
+            }
+        })
+        .filter_map(|(own, other)| {
+            let other_helix = design.helices.get(&other.helix)?;
+            Some((
+                own,
+                other_helix.space_pos(&helix_parameters, other.position, other.forward),
+            ))
+        })
+        .collect();
+    if xovers.is_empty() {
+        return None;
+    }
+    (0..NB_ROLL_SAMPLES)
+        .map(|i| -PI + 2. * PI * i as f32 / NB_ROLL_SAMPLES as f32)
+        .map(|roll| {
+            helix.set_roll(roll);
+            let cost: f32 = xovers
+                .iter()
+                .map(|(own, other_position)| {
+                    (helix.space_pos(&helix_parameters, own.position, own.forward)
+                        - *other_position)
+                        .mag_sq()
+                })
+                .sum();
+            (roll, cost)
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(roll, _)| roll)
+}
@@ -0,0 +1,381 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Compute where insertions and skips must be placed along helices to bend a bundle of helices
+//! or to relax the twist imposed by a lattice, and apply them to the strands.
+//!
+//! An insertion is represented by a one nucleotide long `Domain::Insertion` and a skip by a gap
+//! of one position in the domain that goes over it.
+
+use super::ErrOperation;
+use crate::{
+    grid::GridTypeDescr, Design, Domain, DomainJunction, HelixCollection, HelixInterval, Nucl,
+    Strand,
+};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use ultraviolet::Vec3;
+
+/// The number of positions along a turn of the helices of a square lattice.
+const SQUARE_LATTICE_BASES_PER_TURN: f32 = 32. / 3.;
+/// The number of positions along a turn of the helices of a honeycomb lattice.
+const HONEYCOMB_LATTICE_BASES_PER_TURN: f32 = 21. / 2.;
+
+/// When a planned insertion cannot be placed at its ideal position, it is placed at the closest
+/// valid position at most this many nucleotides away.
+const MAX_INSERTION_PLACEMENT_OFFSET: isize = 3;
+
+/// What the insertions and skips placed along the helices should achieve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InsertionPlacementTarget {
+    /// Relax the twist imposed on the helices by the square or honeycomb lattice on which they
+    /// lie.
+    LatticeTwist,
+    /// Bend the helices into an arc of circle.
+    Curvature {
+        /// The radius of curvature of the axis of the bundle, in nanometers.
+        radius: f32,
+        /// The angle, in degrees, between the direction towards the center of curvature and the
+        /// y axis of the selected helix with the smallest identifier.
+        direction: f32,
+    },
+}
+
+/// An insertion or a skip placed on both strands of an helix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedInsertion {
+    pub helix: usize,
+    pub position: isize,
+    /// The number of inserted nucleotides, or -1 for a skip.
+    pub length: isize,
+}
+
+/// Compute the insertions and skips that must be placed on `helices` to achieve `target`.
+///
+/// The insertions and skips are spread evenly along the part of each helix that is covered by
+/// strands, away from the ends of the domains.
+pub fn plan_insertions(
+    design: &Design,
+    helices: &[usize],
+    target: InsertionPlacementTarget,
+) -> Vec<PlannedInsertion> {
+    let helix_parameters = design.helix_parameters.unwrap_or_default();
+    let ranges: BTreeMap<usize, (isize, isize)> = helices
+        .iter()
+        .filter_map(|h_id| Some((*h_id, covered_range(design, *h_id)?)))
+        .collect();
+
+    let mut deltas: BTreeMap<usize, f32> = BTreeMap::new();
+    match target {
+        InsertionPlacementTarget::LatticeTwist => {
+            for (h_id, (min, max)) in ranges.iter() {
+                let helix = if let Some(helix) = design.helices.get(h_id) {
+                    helix
+                } else {
+                    continue;
+                };
+                if let Some(lattice_bases_per_turn) = lattice_bases_per_turn(design, *h_id) {
+                    let bases_per_turn = helix
+                        .helix_parameters
+                        .unwrap_or(helix_parameters)
+                        .bases_per_turn;
+                    let length = (max - min + 1) as f32;
+                    deltas.insert(
+                        *h_id,
+                        length * (bases_per_turn - lattice_bases_per_turn) / lattice_bases_per_turn,
+                    );
+                }
+            }
+        }
+        InsertionPlacementTarget::Curvature { radius, direction } => {
+            let axes: Vec<(usize, Vec3, Vec3)> = ranges
+                .iter()
+                .filter_map(|(h_id, (min, max))| {
+                    let helix = design.helices.get(h_id)?;
+                    let middle = (min + max) / 2;
+                    let position = helix.axis_position(&helix_parameters, middle, true);
+                    let tangent =
+                        helix.axis_position(&helix_parameters, middle + 1, true) - position;
+                    Some((*h_id, position, tangent.normalized()))
+                })
+                .collect();
+            let first_helix = axes
+                .first()
+                .and_then(|(h_id, _, _)| design.helices.get(h_id));
+            if let Some(first_helix) = first_helix.filter(|_| radius > 0.) {
+                let nb_helices = axes.len() as f32;
+                let center = axes
+                    .iter()
+                    .map(|(_, p, _)| *p)
+                    .fold(Vec3::zero(), |a, b| a + b)
+                    / nb_helices;
+                let axis = axes
+                    .iter()
+                    .map(|(_, _, t)| *t)
+                    .fold(Vec3::zero(), |a, b| a + b)
+                    .normalized();
+                let y = Vec3::unit_y().rotated_by(first_helix.orientation);
+                let y = (y - y.dot(axis) * axis).normalized();
+                let z = axis.cross(y);
+                let angle = direction.to_radians();
+                let towards_center = angle.cos() * y + angle.sin() * z;
+                for (h_id, position, _) in axes.iter() {
+                    let (min, max) = ranges[h_id];
+                    let length = (max - min + 1) as f32;
+                    // helices that are closer to the center of curvature must be shorter
+                    let distance = (*position - center).dot(towards_center);
+                    deltas.insert(*h_id, -length * distance / radius);
+                }
+            }
+        }
+    }
+
+    let mut ret = Vec::new();
+    for (h_id, delta) in deltas.iter() {
+        let count = delta.round() as isize;
+        if count == 0 {
+            continue;
+        }
+        let (min, max) = ranges[h_id];
+        let length = max - min + 1;
+        let mut used_positions = BTreeSet::new();
+        for k in 0..count.abs() {
+            let ideal = min + ((2 * k + 1) * length) / (2 * count.abs());
+            let position = (0..=MAX_INSERTION_PLACEMENT_OFFSET)
+                .flat_map(|offset| [ideal + offset, ideal - offset])
+                .find(|position| {
+                    !used_positions.contains(position)
+                        && (count > 0
+                            || (!used_positions.contains(&(position - 1))
+                                && !used_positions.contains(&(position + 1))))
+                        && can_place_insertion(design, *h_id, *position)
+                });
+            if let Some(position) = position {
+                used_positions.insert(position);
+                ret.push(PlannedInsertion {
+                    helix: *h_id,
+                    position,
+                    length: count.signum(),
+                });
+            }
+        }
+    }
+    ret
+}
+
+/// Apply insertions and skips on the strands going over them.
+pub fn apply_insertions(
+    design: &mut Design,
+    insertions: &[PlannedInsertion],
+) -> Result<(), ErrOperation> {
+    for insertion in insertions.iter() {
+        for forward in [true, false] {
+            let nucl = Nucl {
+                helix: insertion.helix,
+                position: insertion.position,
+                forward,
+            };
+            if let Some(s_id) = design.strands.get_strand_nucl(&nucl) {
+                let strand = design
+                    .strands
+                    .get_mut(&s_id)
+                    .ok_or(ErrOperation::StrandDoesNotExist(s_id))?;
+                if insertion.length > 0 {
+                    insert_after(strand, nucl, insertion.length as usize)?;
+                } else {
+                    skip_nucl(strand, nucl)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Insert `length` nucleotides next to each nucleotide of `nucls`, or skip them if `length` is -1.
+///
+/// The insertions are placed on the side of the increasing positions of the nucleotides, so that
+/// the insertions made on the two nucleotides of a base pair face each other. The nucleotides must
+/// not be at the end of a domain.
+pub fn apply_base_pair_insertions(
+    design: &mut Design,
+    nucls: &[Nucl],
+    length: isize,
+) -> Result<(), ErrOperation> {
+    for nucl in nucls.iter() {
+        let s_id = design
+            .strands
+            .get_strand_nucl(nucl)
+            .ok_or(ErrOperation::NuclDoesNotExist(*nucl))?;
+        let strand = design
+            .strands
+            .get_mut(&s_id)
+            .ok_or(ErrOperation::StrandDoesNotExist(s_id))?;
+        let (d_id, n) =
+            locate_in_interval(strand, *nucl).ok_or(ErrOperation::NuclDoesNotExist(*nucl))?;
+        if n == 0 || n + 1 >= strand.domains[d_id].length() {
+            return Err(ErrOperation::NuclAtDomainEnd(*nucl));
+        }
+        if length > 0 {
+            let anchor = if nucl.forward { *nucl } else { nucl.prime5() };
+            insert_after(strand, anchor, length as usize)?;
+        } else {
+            skip_nucl(strand, *nucl)?;
+        }
+    }
+    Ok(())
+}
+
+/// The number of positions along a turn of helix `h_id` imposed by the square or honeycomb
+/// lattice on which it lies, or `None` if the helix is not on such a lattice.
+pub fn lattice_bases_per_turn(design: &Design, h_id: usize) -> Option<f32> {
+    design
+        .helices
+        .get(&h_id)?
+        .grid_position
+        .as_ref()
+        .and_then(|pos| design.free_grids.get_from_g_id(&pos.grid))
+        .and_then(|grid| match grid.grid_type {
+            GridTypeDescr::Square { twist: None } => Some(SQUARE_LATTICE_BASES_PER_TURN),
+            GridTypeDescr::Honeycomb { twist: None } => Some(HONEYCOMB_LATTICE_BASES_PER_TURN),
+            _ => None,
+        })
+}
+
+/// The smallest and largest positions covered by a strand on an helix.
+fn covered_range(design: &Design, h_id: usize) -> Option<(isize, isize)> {
+    let intervals = design.strands.values().flat_map(|s| {
+        s.domains.iter().filter_map(|d| match d {
+            Domain::HelixDomain(interval) if interval.helix == h_id => {
+                Some((interval.start, interval.end - 1))
+            }
+            _ => None,
+        })
+    });
+    intervals.fold(None, |range, (start, end)| match range {
+        Some((min, max)) => Some((start.min(min), end.max(max))),
+        None => Some((start, end)),
+    })
+}
+
+/// Return true if the nucleotides of both strands at `position` are inside, and not at the end,
+/// of a domain.
+fn can_place_insertion(design: &Design, h_id: usize, position: isize) -> bool {
+    let mut covered = false;
+    for forward in [true, false] {
+        let nucl = Nucl {
+            helix: h_id,
+            position,
+            forward,
+        };
+        if let Some(s_id) = design.strands.get_strand_nucl(&nucl) {
+            covered = true;
+            let inside = design
+                .strands
+                .get(&s_id)
+                .and_then(|s| {
+                    let (d_id, n) = locate_in_interval(s, nucl)?;
+                    Some(n > 0 && n + 1 < s.domains[d_id].length())
+                })
+                .unwrap_or(false);
+            if !inside {
+                return false;
+            }
+        }
+    }
+    covered
+}
+
+/// The index of the domain containing `nucl` and the position of `nucl` in that domain, counted
+/// from its 5' end.
+fn locate_in_interval(strand: &Strand, nucl: Nucl) -> Option<(usize, usize)> {
+    strand
+        .domains
+        .iter()
+        .enumerate()
+        .find_map(|(d_id, d)| Some((d_id, d.has_nucl(&nucl)?)))
+}
+
+/// Split an helix interval into its `n` first nucleotides, starting from its 5' end, and the
+/// remaining ones.
+fn split_interval(interval: &HelixInterval, n: usize) -> (HelixInterval, HelixInterval) {
+    let n = n as isize;
+    let (seq_prime5, seq_prime3) = if let Some(seq) = interval.sequence.as_ref() {
+        let prime5: String = seq.chars().take(n as usize).collect();
+        let prime3: String = seq.chars().skip(n as usize).collect();
+        (Some(Cow::Owned(prime5)), Some(Cow::Owned(prime3)))
+    } else {
+        (None, None)
+    };
+    let (prime5_bounds, prime3_bounds) = if interval.forward {
+        (
+            (interval.start, interval.start + n),
+            (interval.start + n, interval.end),
+        )
+    } else {
+        (
+            (interval.end - n, interval.end),
+            (interval.start, interval.end - n),
+        )
+    };
+    let make = |(start, end), sequence| HelixInterval {
+        helix: interval.helix,
+        start,
+        end,
+        forward: interval.forward,
+        sequence,
+    };
+    (
+        make(prime5_bounds, seq_prime5),
+        make(prime3_bounds, seq_prime3),
+    )
+}
+
+fn insert_after(strand: &mut Strand, nucl: Nucl, length: usize) -> Result<(), ErrOperation> {
+    let (d_id, n) = locate_in_interval(strand, nucl).ok_or(ErrOperation::NuclDoesNotExist(nucl))?;
+    if let Some(Domain::HelixDomain(interval)) = strand.domains.get(d_id) {
+        let (prime5, prime3) = split_interval(interval, n + 1);
+        strand.domains.splice(
+            d_id..=d_id,
+            [
+                Domain::HelixDomain(prime5),
+                Domain::new_insertion(length),
+                Domain::HelixDomain(prime3),
+            ],
+        );
+        strand.junctions.splice(
+            d_id..d_id,
+            [DomainJunction::Adjacent, DomainJunction::Adjacent],
+        );
+    }
+    Ok(())
+}
+
+fn skip_nucl(strand: &mut Strand, nucl: Nucl) -> Result<(), ErrOperation> {
+    let (d_id, n) = locate_in_interval(strand, nucl).ok_or(ErrOperation::NuclDoesNotExist(nucl))?;
+    if let Some(Domain::HelixDomain(interval)) = strand.domains.get(d_id) {
+        let (prime5, rest) = split_interval(interval, n);
+        let (_, prime3) = split_interval(&rest, 1);
+        strand.domains.splice(
+            d_id..=d_id,
+            [Domain::HelixDomain(prime5), Domain::HelixDomain(prime3)],
+        );
+        strand
+            .junctions
+            .insert(d_id, DomainJunction::UnindentifiedXover);
+    }
+    Ok(())
+}
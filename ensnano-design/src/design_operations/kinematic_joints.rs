@@ -0,0 +1,128 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Operations that add and remove kinematic joints.
+
+use super::ErrOperation;
+use crate::{body_frame, Design, HelixCollection, Joint, JointBody, JointId, JointKind, Nucl};
+use ultraviolet::Vec3;
+
+/// Add a joint between two bodies and return its identifier.
+///
+/// The anchor of the joint is placed at the center of the cross-overs linking the two bodies.
+/// The axis of a hinge goes through the two cross-overs that are the furthest apart, which is
+/// where a DNA hinge folds. The axis of a slider is the axis of the helices of the first body.
+pub fn add_joint(
+    design: &mut Design,
+    bodies: (JointBody, JointBody),
+    kind: JointKind,
+) -> Result<JointId, ErrOperation> {
+    if bodies.0 == bodies.1 {
+        return Err(ErrOperation::JointOnSingleBody);
+    }
+    let (position_0, orientation_0) =
+        body_frame(design, bodies.0).ok_or(ErrOperation::JointBodyDoesNotExist(bodies.0))?;
+    let (position_1, _) =
+        body_frame(design, bodies.1).ok_or(ErrOperation::JointBodyDoesNotExist(bodies.1))?;
+
+    let xover_centers = xover_centers_between(design, bodies);
+    let anchor = if xover_centers.is_empty() {
+        (position_0 + position_1) / 2.
+    } else {
+        xover_centers.iter().fold(Vec3::zero(), |a, b| a + *b) / xover_centers.len() as f32
+    };
+    let helix_axis = Vec3::unit_x().rotated_by(orientation_0);
+    let axis = match kind {
+        JointKind::Hinge => furthest_apart(&xover_centers)
+            .map(|(a, b)| (b - a).normalized())
+            .unwrap_or(helix_axis),
+        JointKind::Slider | JointKind::Ball => helix_axis,
+    };
+
+    let joint = Joint::new(design, bodies, kind, anchor, axis)
+        .ok_or(ErrOperation::JointBodyDoesNotExist(bodies.0))?;
+    let id = design
+        .joints
+        .keys()
+        .last()
+        .map(|id| JointId(id.0 + 1))
+        .unwrap_or(JointId(0));
+    design.joints.insert(id, joint);
+    Ok(id)
+}
+
+pub fn delete_joint(design: &mut Design, joint_id: JointId) -> Result<(), ErrOperation> {
+    design
+        .joints
+        .remove(&joint_id)
+        .map(|_| ())
+        .ok_or(ErrOperation::JointDoesNotExist(joint_id))
+}
+
+/// Return true iff `helix` is one of the helices moved with `body`.
+pub fn helix_is_in_body(design: &Design, helix: usize, body: JointBody) -> bool {
+    match body {
+        JointBody::Bundle(b_id) => design
+            .helix_bundles
+            .get(&b_id)
+            .map(|bundle| bundle.helices.contains(&helix))
+            .unwrap_or(false),
+        JointBody::Grid(g_id) => design
+            .helices
+            .get(&helix)
+            .and_then(|h| h.grid_position)
+            .map(|gp| gp.grid == g_id)
+            .unwrap_or(false),
+    }
+}
+
+/// The middle of the cross-overs that link the two bodies
+fn xover_centers_between(design: &Design, bodies: (JointBody, JointBody)) -> Vec<Vec3> {
+    let helix_parameters = design.helix_parameters.unwrap_or_default();
+    let nucl_position = |nucl: &Nucl| {
+        design
+            .helices
+            .get(&nucl.helix)
+            .map(|h| h.space_pos(&helix_parameters, nucl.position, nucl.forward))
+    };
+    let links = |n1: &Nucl, n2: &Nucl| {
+        helix_is_in_body(design, n1.helix, bodies.0) && helix_is_in_body(design, n2.helix, bodies.1)
+    };
+    design
+        .strands
+        .get_xovers()
+        .iter()
+        .filter(|(n1, n2)| links(n1, n2) || links(n2, n1))
+        .filter_map(|(n1, n2)| Some((nucl_position(n1)? + nucl_position(n2)?) / 2.))
+        .collect()
+}
+
+/// Return two points of `points` that are approximately the furthest apart, if they are distinct
+fn furthest_apart(points: &[Vec3]) -> Option<(Vec3, Vec3)> {
+    let further_from = |origin: Vec3| {
+        points.iter().cloned().max_by(|a, b| {
+            (*a - origin)
+                .mag_sq()
+                .partial_cmp(&(*b - origin).mag_sq())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    };
+    let a = further_from(*points.first()?)?;
+    let b = further_from(a)?;
+    Some((a, b)).filter(|(a, b)| (*b - *a).mag() > 1e-3)
+}
@@ -0,0 +1,265 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Move the nicks between consecutive staples along the helices so that, once the scaffold
+//! sequence is known, the seed domains of the staples are as stable as possible and the staples
+//! do not end in weak, AT-rich, regions.
+
+use super::ErrOperation;
+use crate::{Design, Domain, HelixInterval, Nucl, Strand, Strands};
+use std::collections::HashMap;
+
+/// The largest number of nucleotides by which a nick can be moved.
+pub const MAX_NICK_SHIFT: isize = 4;
+
+/// A nick is never moved to a position where one of the domains that end at the nick would be
+/// shorter than this.
+const MIN_END_DOMAIN_LENGTH: isize = 5;
+
+/// Increase, in degrees, of the score of a nick position for each G or C next to the nick.
+const NICK_GC_BONUS: f32 = 2.;
+
+/// The move of the nick between two staples along their helix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NickMove {
+    /// The identifier of the strand whose 3' end is at the nick
+    pub prime3_strand: usize,
+    /// The identifier of the strand whose 5' end is at the nick
+    pub prime5_strand: usize,
+    /// The 3' end of the first strand before the move
+    pub from: Nucl,
+    /// The number of nucleotides by which the nick is moved towards the 3' end of the strands
+    pub shift: isize,
+    /// The lowest melting temperature of the seed domains of the two strands before the move
+    pub seed_tm_before: f32,
+    /// The lowest melting temperature of the seed domains of the two strands after the move
+    pub seed_tm_after: f32,
+}
+
+impl NickMove {
+    /// The 3' end of the first strand after the move
+    pub fn to(&self) -> Nucl {
+        shifted(self.from, self.shift)
+    }
+}
+
+/// An estimation of the melting temperature, in degrees Celsius, of a DNA duplex.
+///
+/// The Wallace rule is used for sequences shorter than 14 nucleotides and the basic GC content
+/// formula for longer sequences.
+pub fn melting_temperature(sequence: &str) -> f32 {
+    let length = sequence.chars().count();
+    let nb_gc = sequence
+        .chars()
+        .filter(|c| matches!(c.to_ascii_uppercase(), 'G' | 'C'))
+        .count() as f32;
+    if length < 14 {
+        2. * (length as f32 - nb_gc) + 4. * nb_gc
+    } else {
+        64.9 + 41. * (nb_gc - 16.4) / length as f32
+    }
+}
+
+/// Compute the moves of the nicks that involve at least one of the strands `strand_ids`, at
+/// most `MAX_NICK_SHIFT` nucleotides away, that maximize the melting temperature of the seed
+/// domains of the staples, i.e. of their longest domains.
+///
+/// `basis_map` gives the basis of each nucleotide of the design. The nicks next to a nucleotide
+/// of unknown basis, next to the scaffold or between domains whose sequence is set explicitly
+/// are not moved. The moves must be applied in the order in which they are returned.
+pub fn plan_nick_optimization(
+    design: &Design,
+    basis_map: &HashMap<Nucl, char>,
+    strand_ids: &[usize],
+) -> Vec<NickMove> {
+    let mut strands = design.strands.clone();
+    let mut ret = Vec::new();
+    let mut nicks: Vec<(usize, usize)> = strand_ids
+        .iter()
+        .flat_map(|s_id| {
+            nick_after(&strands, *s_id)
+                .into_iter()
+                .chain(nick_before(&strands, *s_id))
+        })
+        .filter(|(s3, s5)| design.scaffold_id != Some(*s3) && design.scaffold_id != Some(*s5))
+        .collect();
+    nicks.sort_unstable();
+    nicks.dedup();
+
+    for (prime3_strand, prime5_strand) in nicks {
+        let (strand_3, strand_5) =
+            if let Some(pair) = strands.get(&prime3_strand).zip(strands.get(&prime5_strand)) {
+                pair
+            } else {
+                continue;
+            };
+        let from = if let Some(nucl) = strand_3.get_3prime() {
+            nucl
+        } else {
+            continue;
+        };
+        let score = |shift: isize| -> Option<(f32, f32)> {
+            let (strand_3, strand_5) = shifted_strands(strand_3, strand_5, shift)?;
+            let seed_tm = seed_tm(&strand_3, basis_map)?.min(seed_tm(&strand_5, basis_map)?);
+            let to = shifted(from, shift);
+            let nb_gc = [to, to.prime3()]
+                .iter()
+                .filter(|n| matches!(basis_map.get(n), Some('G' | 'C' | 'g' | 'c')))
+                .count();
+            Some((seed_tm + NICK_GC_BONUS * nb_gc as f32, seed_tm))
+        };
+        let (current_score, seed_tm_before) = if let Some(score) = score(0) {
+            score
+        } else {
+            continue;
+        };
+        let best = (-MAX_NICK_SHIFT..=MAX_NICK_SHIFT)
+            .filter_map(|shift| Some((shift, score(shift)?)))
+            .filter(|(_, (score, _))| *score > current_score)
+            .max_by(|(s1, (a, _)), (s2, (b, _))| {
+                a.partial_cmp(b)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(s2.abs().cmp(&s1.abs()))
+            });
+        if let Some((shift, (_, seed_tm_after))) = best {
+            if let Some((new_3, new_5)) = shifted_strands(strand_3, strand_5, shift) {
+                strands.insert(prime3_strand, new_3);
+                strands.insert(prime5_strand, new_5);
+                ret.push(NickMove {
+                    prime3_strand,
+                    prime5_strand,
+                    from,
+                    shift,
+                    seed_tm_before,
+                    seed_tm_after,
+                });
+            }
+        }
+    }
+    ret
+}
+
+/// Apply moves computed by `plan_nick_optimization`.
+pub fn move_nicks(design: &mut Design, moves: &[NickMove]) -> Result<(), ErrOperation> {
+    for nick_move in moves.iter() {
+        if nick_after(&design.strands, nick_move.prime3_strand)
+            != Some((nick_move.prime3_strand, nick_move.prime5_strand))
+        {
+            return Err(ErrOperation::NickDoesNotExist(nick_move.from));
+        }
+        let (strand_3, strand_5) = design
+            .strands
+            .get(&nick_move.prime3_strand)
+            .zip(design.strands.get(&nick_move.prime5_strand))
+            .filter(|(s, _)| s.get_3prime() == Some(nick_move.from))
+            .and_then(|(s3, s5)| shifted_strands(s3, s5, nick_move.shift))
+            .ok_or(ErrOperation::NickDoesNotExist(nick_move.from))?;
+        design.strands.insert(nick_move.prime3_strand, strand_3);
+        design.strands.insert(nick_move.prime5_strand, strand_5);
+    }
+    Ok(())
+}
+
+fn shifted(nucl: Nucl, shift: isize) -> Nucl {
+    Nucl {
+        position: if nucl.forward {
+            nucl.position + shift
+        } else {
+            nucl.position - shift
+        },
+        ..nucl
+    }
+}
+
+/// The strands whose 3' end and 5' end are on both sides of the nick that follows the 3' end of
+/// strand `s_id`.
+fn nick_after(strands: &Strands, s_id: usize) -> Option<(usize, usize)> {
+    let strand = strands.get(&s_id).filter(|s| !s.is_cyclic)?;
+    let next = strand.get_3prime()?.prime3();
+    let next_id = strands.get_strand_nucl(&next).filter(|id| *id != s_id)?;
+    (strands.get(&next_id)?.get_5prime() == Some(next)).then_some((s_id, next_id))
+}
+
+/// The strands whose 3' end and 5' end are on both sides of the nick that precedes the 5' end
+/// of strand `s_id`.
+fn nick_before(strands: &Strands, s_id: usize) -> Option<(usize, usize)> {
+    let strand = strands.get(&s_id).filter(|s| !s.is_cyclic)?;
+    let previous = strand.get_5prime()?.prime5();
+    let previous_id = strands
+        .get_strand_nucl(&previous)
+        .filter(|id| *id != s_id)?;
+    (strands.get(&previous_id)?.get_3prime() == Some(previous)).then_some((previous_id, s_id))
+}
+
+/// The two strands of a nick once it has been moved by `shift` nucleotides towards their 3'
+/// ends, or `None` if one of their end domains would become too short.
+fn shifted_strands(strand_3: &Strand, strand_5: &Strand, shift: isize) -> Option<(Strand, Strand)> {
+    if strand_3.sequence.is_some() || strand_5.sequence.is_some() {
+        return None;
+    }
+    let mut strand_3 = strand_3.clone();
+    let mut strand_5 = strand_5.clone();
+    let last = end_interval(strand_3.domains.last_mut())?;
+    if last.forward {
+        last.end += shift;
+    } else {
+        last.start -= shift;
+    }
+    let last_length = last.end - last.start;
+    let first = end_interval(strand_5.domains.first_mut())?;
+    if first.forward {
+        first.start += shift;
+    } else {
+        first.end -= shift;
+    }
+    let first_length = first.end - first.start;
+    (last_length.min(first_length) >= MIN_END_DOMAIN_LENGTH).then_some((strand_3, strand_5))
+}
+
+/// The interval of an end domain of a strand, if its sequence is not set explicitly.
+fn end_interval(domain: Option<&mut Domain>) -> Option<&mut HelixInterval> {
+    match domain {
+        Some(Domain::HelixDomain(interval)) if interval.sequence.is_none() => Some(interval),
+        _ => None,
+    }
+}
+
+/// The melting temperature of the longest domain of `strand`, or `None` if the basis of one of
+/// its nucleotides is unknown.
+fn seed_tm(strand: &Strand, basis_map: &HashMap<Nucl, char>) -> Option<f32> {
+    let seed = strand
+        .domains
+        .iter()
+        .filter_map(|d| match d {
+            Domain::HelixDomain(interval) => Some(interval),
+            _ => None,
+        })
+        .max_by_key(|interval| interval.end - interval.start)?;
+    let sequence = seed
+        .iter()
+        .map(|position| {
+            basis_map
+                .get(&Nucl {
+                    helix: seed.helix,
+                    position,
+                    forward: seed.forward,
+                })
+                .copied()
+        })
+        .collect::<Option<String>>()?;
+    Some(melting_temperature(&sequence))
+}
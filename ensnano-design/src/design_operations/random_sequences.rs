@@ -0,0 +1,291 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Fill the nucleotides whose basis is unknown with random bases that satisfy some constraints,
+//! to design staples that are not paired to the scaffold.
+
+use super::ErrOperation;
+use crate::{Design, Domain, Nucl, Strand};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// The number of sequences generated for a strand before giving up satisfying its GC content
+/// constraint.
+const MAX_RANDOM_SEQUENCE_ATTEMPTS: usize = 100;
+
+/// Constraints on the random bases given to the nucleotides whose basis is unknown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomSequenceParameters {
+    /// The smallest proportion of G and C in each filled strand, between 0 and 1
+    pub min_gc: f32,
+    /// The largest proportion of G and C in each filled strand, between 0 and 1
+    pub max_gc: f32,
+    /// The longest run of identical bases
+    pub max_homopolymer: usize,
+    /// Motifs, such as restriction sites, that must not be created by the random bases
+    pub forbidden_motifs: Vec<String>,
+    /// Stretches of this many random bases are not complementary to any stretch of the other
+    /// strands
+    pub complementarity_length: usize,
+}
+
+impl Default for RandomSequenceParameters {
+    fn default() -> Self {
+        Self {
+            min_gc: 0.4,
+            max_gc: 0.6,
+            max_homopolymer: 3,
+            forbidden_motifs: Vec::new(),
+            complementarity_length: 8,
+        }
+    }
+}
+
+/// The sequences of the domains of a strand, in the order of its domains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrandSequences {
+    pub strand_id: usize,
+    pub domain_sequences: Vec<String>,
+}
+
+/// Compute sequences for the strands `strand_ids` that have nucleotides of unknown basis.
+///
+/// `basis_map` gives the basis of the nucleotides that are already known. A nucleotide paired to
+/// a nucleotide of known basis gets the complementary basis, the other ones get random bases.
+/// The scaffold is never filled, its sequence must be set separately.
+pub fn plan_random_sequences(
+    design: &Design,
+    basis_map: &HashMap<Nucl, char>,
+    strand_ids: &[usize],
+    parameters: &RandomSequenceParameters,
+    seed: u64,
+) -> Vec<StrandSequences> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut known = basis_map.clone();
+    let k = parameters.complementarity_length.max(1);
+    let mut kmers: HashSet<String> = HashSet::new();
+    for strand in design.strands.values() {
+        let sequence: String = template(strand, &known)
+            .into_iter()
+            .map(|(_, b)| b.unwrap_or('?'))
+            .collect();
+        add_kmers(&mut kmers, &sequence, k);
+    }
+
+    let mut ret = Vec::new();
+    for s_id in strand_ids.iter() {
+        if design.scaffold_id == Some(*s_id) {
+            continue;
+        }
+        let strand = if let Some(strand) = design.strands.get(s_id) {
+            strand
+        } else {
+            continue;
+        };
+        let template = template(strand, &known);
+        if template.iter().all(|(_, b)| b.is_some()) {
+            continue;
+        }
+        let mut bases = Vec::new();
+        for _ in 0..MAX_RANDOM_SEQUENCE_ATTEMPTS {
+            bases = random_bases(&template, parameters, &kmers, &mut rng);
+            let gc = bases.iter().filter(|b| matches!(b, 'G' | 'C')).count() as f32
+                / bases.len().max(1) as f32;
+            if (parameters.min_gc..=parameters.max_gc).contains(&gc) {
+                break;
+            }
+        }
+        let sequence: String = bases.iter().collect();
+        add_kmers(&mut kmers, &sequence, k);
+        for ((nucl, _), basis) in template.iter().zip(bases.iter()) {
+            if let Some(nucl) = nucl {
+                known.insert(*nucl, *basis);
+            }
+        }
+
+        let mut domain_sequences = Vec::new();
+        let mut bases = bases.into_iter();
+        for domain in strand.domains.iter() {
+            domain_sequences.push(bases.by_ref().take(domain.length()).collect());
+        }
+        ret.push(StrandSequences {
+            strand_id: *s_id,
+            domain_sequences,
+        });
+    }
+    ret
+}
+
+/// Set the sequences of the domains of strands, as computed by `plan_random_sequences`.
+pub fn set_domain_sequences(
+    design: &mut Design,
+    sequences: &[StrandSequences],
+) -> Result<(), ErrOperation> {
+    for strand_sequences in sequences.iter() {
+        let strand = design
+            .strands
+            .get_mut(&strand_sequences.strand_id)
+            .filter(|s| s.domains.len() == strand_sequences.domain_sequences.len())
+            .ok_or(ErrOperation::StrandDoesNotExist(strand_sequences.strand_id))?;
+        for (domain, sequence) in strand
+            .domains
+            .iter_mut()
+            .zip(strand_sequences.domain_sequences.iter())
+        {
+            let sequence = Some(Cow::Owned(sequence.clone()));
+            match domain {
+                Domain::HelixDomain(interval) => interval.sequence = sequence,
+                Domain::Insertion { sequence: s, .. } => *s = sequence,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The nucleotides of `strand`, `None` for the nucleotides of its insertions, and their basis
+/// if it is known.
+fn template(strand: &Strand, known: &HashMap<Nucl, char>) -> Vec<(Option<Nucl>, Option<char>)> {
+    let mut ret = Vec::new();
+    for domain in strand.domains.iter() {
+        match domain {
+            Domain::HelixDomain(interval) => {
+                for position in interval.iter() {
+                    let nucl = Nucl {
+                        helix: interval.helix,
+                        position,
+                        forward: interval.forward,
+                    };
+                    let basis = known
+                        .get(&nucl)
+                        .cloned()
+                        .or_else(|| known.get(&nucl.compl()).and_then(|b| compl(*b)));
+                    ret.push((Some(nucl), basis.map(|b| b.to_ascii_uppercase())));
+                }
+            }
+            Domain::Insertion {
+                nb_nucl, sequence, ..
+            } => {
+                for i in 0..*nb_nucl {
+                    let basis = sequence.as_ref().and_then(|s| s.chars().nth(i));
+                    ret.push((None, basis.map(|b| b.to_ascii_uppercase())));
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// Complete `template` with random bases, avoiding when possible long homopolymers, forbidden
+/// motifs and stretches complementary to `kmers`.
+fn random_bases(
+    template: &[(Option<Nucl>, Option<char>)],
+    parameters: &RandomSequenceParameters,
+    kmers: &HashSet<String>,
+    rng: &mut StdRng,
+) -> Vec<char> {
+    let k = parameters.complementarity_length.max(1);
+    let target_gc = (parameters.min_gc + parameters.max_gc) / 2.;
+    let motifs: Vec<Vec<char>> = parameters
+        .forbidden_motifs
+        .iter()
+        .map(|m| m.trim().to_ascii_uppercase().chars().collect())
+        .filter(|m: &Vec<char>| !m.is_empty())
+        .collect();
+    // The number of bases at the end of the sequence that the constraints look at
+    let tail_length = motifs
+        .iter()
+        .map(|m| m.len())
+        .max()
+        .unwrap_or(0)
+        .max(k)
+        .max(parameters.max_homopolymer + 1);
+    let mut bases: Vec<char> = Vec::with_capacity(template.len());
+    // The number of consecutive random bases at the end of `bases`
+    let mut nb_random = 0;
+    for (_, basis) in template.iter() {
+        if let Some(basis) = basis {
+            bases.push(*basis);
+            nb_random = 0;
+            continue;
+        }
+        let allowed = |candidate: char| {
+            let mut tail = bases[bases.len().saturating_sub(tail_length)..].to_vec();
+            tail.push(candidate);
+            let run = tail.iter().rev().take_while(|b| **b == candidate).count();
+            let creates_motif = motifs.iter().any(|m| tail.ends_with(m));
+            let complementary =
+                nb_random + 1 >= k && kmers.contains(&reverse_complement(&tail[tail.len() - k..]));
+            run <= parameters.max_homopolymer && !creates_motif && !complementary
+        };
+        let candidates: Vec<char> = ['A', 'C', 'G', 'T']
+            .iter()
+            .cloned()
+            .filter(|c| allowed(*c))
+            .collect();
+        let candidates = if candidates.is_empty() {
+            vec!['A', 'C', 'G', 'T']
+        } else {
+            candidates
+        };
+        let weight = |c: &char| {
+            if matches!(c, 'G' | 'C') {
+                target_gc
+            } else {
+                1. - target_gc
+            }
+        };
+        let total: f32 = candidates.iter().map(weight).sum();
+        let mut draw = rng.gen::<f32>() * total;
+        let mut choice = candidates[candidates.len() - 1];
+        for c in candidates.iter() {
+            if draw < weight(c) {
+                choice = *c;
+                break;
+            }
+            draw -= weight(c);
+        }
+        bases.push(choice);
+        nb_random += 1;
+    }
+    bases
+}
+
+fn add_kmers(kmers: &mut HashSet<String>, sequence: &str, k: usize) {
+    let chars: Vec<char> = sequence.chars().collect();
+    for window in chars.windows(k) {
+        if !window.contains(&'?') {
+            kmers.insert(window.iter().collect());
+        }
+    }
+}
+
+fn reverse_complement(bases: &[char]) -> String {
+    bases.iter().rev().filter_map(|b| compl(*b)).collect()
+}
+
+/// The basis complementary to `basis`.
+pub(super) fn compl(basis: char) -> Option<char> {
+    match basis.to_ascii_uppercase() {
+        'A' => Some('T'),
+        'T' | 'U' => Some('A'),
+        'G' => Some('C'),
+        'C' => Some('G'),
+        _ => None,
+    }
+}
@@ -0,0 +1,163 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Remove the sequences of strands, or write the bases given by the scaffold sequence in some
+//! domains only, to correct a wrong sequence assignment without reloading the design.
+
+use super::random_sequences::compl;
+use super::ErrOperation;
+use crate::{Design, Domain, Nucl};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+
+/// The basis written for the nucleotides whose basis is unknown in a domain that is given an
+/// explicit sequence.
+const UNKNOWN_BASIS: char = 'N';
+
+/// Remove the sequences of the strands `strand_ids` and of their domains.
+///
+/// If the scaffold is one of these strands, the scaffold sequence is removed too.
+pub fn clear_sequences(design: &mut Design, strand_ids: &[usize]) -> Result<(), ErrOperation> {
+    for s_id in strand_ids.iter() {
+        let strand = design
+            .strands
+            .get_mut(s_id)
+            .ok_or(ErrOperation::StrandDoesNotExist(*s_id))?;
+        strand.sequence = None;
+        for domain in strand.domains.iter_mut() {
+            match domain {
+                Domain::HelixDomain(interval) => interval.sequence = None,
+                Domain::Insertion { sequence, .. } => *sequence = None,
+            }
+        }
+    }
+    if let Some(scaffold_id) = design.scaffold_id {
+        if strand_ids.contains(&scaffold_id) {
+            design.scaffold_sequence = None;
+            design.scaffold_shift = None;
+        }
+    }
+    Ok(())
+}
+
+/// Write the bases that the scaffold sequence gives to the domains `domain_ids` of the scaffold
+/// in the sequences of these domains, and the complementary bases in the sequences of the
+/// domains paired to them.
+///
+/// The scaffold sequence is then removed, so that the other domains keep their own sequences.
+pub fn assign_scaffold_sequence_to_domains(
+    design: &mut Design,
+    domain_ids: &[usize],
+) -> Result<(), ErrOperation> {
+    let scaffold_id = design.scaffold_id.ok_or(ErrOperation::NoScaffoldSequence)?;
+    let sequence: Vec<char> = design
+        .scaffold_sequence
+        .as_ref()
+        .ok_or(ErrOperation::NoScaffoldSequence)?
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect();
+    if sequence.is_empty() {
+        return Err(ErrOperation::NoScaffoldSequence);
+    }
+    let shift = design.scaffold_shift.unwrap_or(0) % sequence.len();
+    let scaffold = design
+        .strands
+        .get(&scaffold_id)
+        .ok_or(ErrOperation::StrandDoesNotExist(scaffold_id))?;
+
+    // The new basis of each nucleotide of the selected domains and of their partners
+    let mut new_bases: HashMap<Nucl, char> = HashMap::new();
+    let mut position_on_scaffold = 0;
+    for (d_id, domain) in scaffold.domains.iter().enumerate() {
+        if let Domain::HelixDomain(interval) = domain {
+            for position in interval.iter() {
+                let basis = (position_on_scaffold < sequence.len()).then(|| {
+                    sequence[(position_on_scaffold + sequence.len() - shift) % sequence.len()]
+                });
+                position_on_scaffold += 1;
+                if let Some(basis) = basis.filter(|_| domain_ids.contains(&d_id)) {
+                    let nucl = Nucl {
+                        helix: interval.helix,
+                        position,
+                        forward: interval.forward,
+                    };
+                    new_bases.insert(nucl, basis);
+                    if let Some(compl) = compl(basis) {
+                        new_bases.insert(nucl.compl(), compl);
+                    }
+                }
+            }
+        }
+    }
+
+    let strand_ids: BTreeSet<usize> = new_bases
+        .keys()
+        .filter_map(|nucl| design.strands.get_strand_nucl(nucl))
+        .collect();
+    for s_id in strand_ids {
+        let strand = if let Some(strand) = design.strands.get_mut(&s_id) {
+            strand
+        } else {
+            continue;
+        };
+        let strand_sequence: Option<Vec<char>> =
+            strand.sequence.as_ref().map(|s| s.chars().collect());
+        let mut position_on_strand = 0;
+        for domain in strand.domains.iter_mut() {
+            match domain {
+                Domain::HelixDomain(interval) => {
+                    let nucls: Vec<Nucl> = interval
+                        .iter()
+                        .map(|position| Nucl {
+                            helix: interval.helix,
+                            position,
+                            forward: interval.forward,
+                        })
+                        .collect();
+                    if nucls.iter().any(|n| new_bases.contains_key(n)) {
+                        let domain_sequence: Option<Vec<char>> =
+                            interval.sequence.as_ref().map(|s| s.chars().collect());
+                        let new_sequence: String = nucls
+                            .iter()
+                            .enumerate()
+                            .map(|(i, nucl)| {
+                                new_bases
+                                    .get(nucl)
+                                    .cloned()
+                                    .or_else(|| domain_sequence.as_ref()?.get(i).cloned())
+                                    .or_else(|| {
+                                        strand_sequence
+                                            .as_ref()?
+                                            .get(position_on_strand + i)
+                                            .cloned()
+                                    })
+                                    .unwrap_or(UNKNOWN_BASIS)
+                            })
+                            .collect();
+                        interval.sequence = Some(Cow::Owned(new_sequence));
+                    }
+                    position_on_strand += nucls.len();
+                }
+                Domain::Insertion { nb_nucl, .. } => position_on_strand += *nb_nucl,
+            }
+        }
+    }
+    design.scaffold_sequence = None;
+    design.scaffold_shift = None;
+    Ok(())
+}
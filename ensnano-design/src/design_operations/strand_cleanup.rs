@@ -0,0 +1,97 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Find the strands that are most likely leftovers of previous edition steps: empty strands,
+//! strands that are too short to be stable and strands that are not paired to any other strand.
+
+use crate::{Design, Domain, Nucl};
+use std::collections::HashSet;
+
+/// The default minimum length, in nucleotides, of the strands that are kept by a cleanup.
+pub const DEFAULT_CLEANUP_MIN_LENGTH: usize = 7;
+
+/// The reason for which a strand is proposed for removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupReason {
+    /// The strand has no nucleotide
+    Empty,
+    /// The strand has less nucleotides than the cleanup threshold
+    Short,
+    /// None of the nucleotides of the strand is paired with an other nucleotide
+    Unpaired,
+}
+
+/// A strand that is proposed for removal by a cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrandCleanup {
+    pub strand_id: usize,
+    /// The number of nucleotides of the strand
+    pub length: usize,
+    pub reason: CleanupReason,
+}
+
+/// List the strands of `design` that are empty, shorter than `min_length` nucleotides, or
+/// whose nucleotides are all unpaired. The scaffold is never proposed for removal.
+pub fn plan_strand_cleanup(design: &Design, min_length: usize) -> Vec<StrandCleanup> {
+    let used_nucls: HashSet<Nucl> = design
+        .strands
+        .values()
+        .flat_map(|s| strand_nucls(s.domains.as_slice()))
+        .collect();
+    let mut ret = Vec::new();
+    for (s_id, strand) in design.strands.iter() {
+        if design.scaffold_id == Some(*s_id) {
+            continue;
+        }
+        let length = strand.length();
+        let reason = if length == 0 {
+            CleanupReason::Empty
+        } else if length < min_length {
+            CleanupReason::Short
+        } else if !strand_nucls(strand.domains.as_slice()).any(|n| used_nucls.contains(&n.compl()))
+        {
+            CleanupReason::Unpaired
+        } else {
+            continue;
+        };
+        ret.push(StrandCleanup {
+            strand_id: *s_id,
+            length,
+            reason,
+        });
+    }
+    ret
+}
+
+fn strand_nucls(domains: &[Domain]) -> impl Iterator<Item = Nucl> + '_ {
+    domains
+        .iter()
+        .filter_map(|d| {
+            if let Domain::HelixDomain(interval) = d {
+                Some(interval)
+            } else {
+                None
+            }
+        })
+        .flat_map(|interval| {
+            interval.iter().map(move |position| Nucl {
+                helix: interval.helix,
+                position,
+                forward: interval.forward,
+            })
+        })
+}
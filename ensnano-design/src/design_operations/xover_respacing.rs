@@ -0,0 +1,244 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Move cross-overs along the helices that they link to the closest positions at which they are
+//! the shortest, adjusting the lengths of the domains that they join.
+
+use super::ErrOperation;
+use crate::{Design, Domain, HelixCollection, HelixInterval, Nucl, Strand, Strands};
+use std::collections::HashSet;
+
+/// The largest number of nucleotides by which a cross-over can be moved when it is respaced.
+pub const MAX_XOVER_RESPACING_SHIFT: isize = 8;
+
+/// Positions at which a cross-over is at most this much longer, in nanometers, than at its best
+/// position are considered as good as the best position.
+const XOVER_RESPACING_TOLERANCE: f32 = 0.1;
+
+/// The move of a cross-over to a position at which it is shorter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XoverRespacing {
+    /// The identifier of the strand containing the cross-over
+    pub strand_id: usize,
+    /// The 3' end and the 5' end of the cross-over before the move
+    pub from: (Nucl, Nucl),
+    /// The 3' end and the 5' end of the cross-over after the move
+    pub to: (Nucl, Nucl),
+}
+
+impl XoverRespacing {
+    /// The number of nucleotides by which the cross-over is moved along the helices.
+    pub fn shift(&self) -> isize {
+        self.to.0.position - self.from.0.position
+    }
+}
+
+/// Compute the moves that bring each cross-over of `xovers` to the closest position at which it
+/// is the shortest, at most `MAX_XOVER_RESPACING_SHIFT` nucleotides away.
+///
+/// A cross-over is never moved to a position where one of its domains would become empty or
+/// would overlap an other domain. The moves must be applied in the order in which they are
+/// returned.
+pub fn plan_xover_respacing(design: &Design, xovers: &[(Nucl, Nucl)]) -> Vec<XoverRespacing> {
+    let helix_parameters = design.helix_parameters.unwrap_or_default();
+    let mut strands = design.strands.clone();
+    let mut used_nucls = used_nucls(&strands);
+    let mut ret = Vec::new();
+    for xover in xovers.iter() {
+        let (prime3, prime5) = *xover;
+        let (helix_3, helix_5) = if let Some(helices) = design
+            .helices
+            .get(&prime3.helix)
+            .zip(design.helices.get(&prime5.helix))
+        {
+            helices
+        } else {
+            continue;
+        };
+        let strand_id = if let Some(s_id) = strands.get_strand_nucl(&prime3) {
+            s_id
+        } else {
+            continue;
+        };
+        let xover_length = |shift: isize| {
+            let pos_3 =
+                helix_3.space_pos(&helix_parameters, prime3.position + shift, prime3.forward);
+            let pos_5 =
+                helix_5.space_pos(&helix_parameters, prime5.position + shift, prime5.forward);
+            (pos_3 - pos_5).mag()
+        };
+        let candidates: Vec<(isize, f32)> = {
+            let strand = if let Some(strand) = strands.get(&strand_id) {
+                strand
+            } else {
+                continue;
+            };
+            (-MAX_XOVER_RESPACING_SHIFT..=MAX_XOVER_RESPACING_SHIFT)
+                .filter(|shift| can_shift_xover(strand, *xover, *shift, &used_nucls))
+                .map(|shift| (shift, xover_length(shift)))
+                .collect()
+        };
+        let best_length = candidates.iter().map(|c| c.1).fold(f32::INFINITY, f32::min);
+        let shift = candidates
+            .iter()
+            .filter(|c| c.1 <= best_length + XOVER_RESPACING_TOLERANCE)
+            .min_by_key(|c| c.0.abs())
+            .map(|c| c.0)
+            .unwrap_or(0);
+        if shift != 0 {
+            if let Some(strand) = strands.get_mut(&strand_id) {
+                for nucl in domain_nucls(strand, *xover) {
+                    used_nucls.remove(&nucl);
+                }
+                if shift_xover(strand, *xover, shift).is_some() {
+                    let to = (shifted(prime3, shift), shifted(prime5, shift));
+                    used_nucls.extend(domain_nucls(strand, to));
+                    ret.push(XoverRespacing {
+                        strand_id,
+                        from: *xover,
+                        to,
+                    });
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// Apply moves computed by `plan_xover_respacing`.
+pub fn respace_xovers(design: &mut Design, moves: &[XoverRespacing]) -> Result<(), ErrOperation> {
+    for respacing in moves.iter() {
+        let strand = design
+            .strands
+            .get_mut(&respacing.strand_id)
+            .ok_or(ErrOperation::XoverDoesNotExist(respacing.from))?;
+        shift_xover(strand, respacing.from, respacing.shift())
+            .ok_or(ErrOperation::XoverDoesNotExist(respacing.from))?;
+    }
+    Ok(())
+}
+
+fn shifted(nucl: Nucl, shift: isize) -> Nucl {
+    Nucl {
+        position: nucl.position + shift,
+        ..nucl
+    }
+}
+
+fn used_nucls(strands: &Strands) -> HashSet<Nucl> {
+    let mut ret = HashSet::new();
+    for strand in strands.values() {
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                ret.extend(interval_nucls(interval));
+            }
+        }
+    }
+    ret
+}
+
+fn interval_nucls(interval: &HelixInterval) -> impl Iterator<Item = Nucl> + '_ {
+    interval.iter().map(move |position| Nucl {
+        helix: interval.helix,
+        position,
+        forward: interval.forward,
+    })
+}
+
+/// The indices of the domains of `strand` whose 3' end and 5' end are the ends of `xover`.
+fn xover_domains(strand: &Strand, xover: (Nucl, Nucl)) -> Option<(usize, usize)> {
+    let nb_domains = strand.domains.len();
+    let prime3_domain = strand
+        .domains
+        .iter()
+        .position(|d| d.prime3_end() == Some(xover.0))?;
+    let prime5_domain = if prime3_domain + 1 < nb_domains {
+        prime3_domain + 1
+    } else if strand.is_cyclic {
+        0
+    } else {
+        return None;
+    };
+    (strand.domains[prime5_domain].prime5_end() == Some(xover.1))
+        .then_some((prime3_domain, prime5_domain))
+}
+
+/// The nucleotides of the two domains joined by `xover`.
+fn domain_nucls(strand: &Strand, xover: (Nucl, Nucl)) -> Vec<Nucl> {
+    let mut ret = Vec::new();
+    if let Some((d3, d5)) = xover_domains(strand, xover) {
+        for d in [d3, d5] {
+            if let Domain::HelixDomain(interval) = &strand.domains[d] {
+                ret.extend(interval_nucls(interval));
+            }
+        }
+    }
+    ret
+}
+
+/// The intervals of the two domains joined by `xover` once the cross-over has been moved by
+/// `shift` nucleotides, or `None` if one of them would be empty.
+fn shifted_intervals(
+    strand: &Strand,
+    xover: (Nucl, Nucl),
+    shift: isize,
+) -> Option<(HelixInterval, HelixInterval)> {
+    let (d3, d5) = xover_domains(strand, xover)?;
+    let (mut interval_3, mut interval_5) = match (&strand.domains[d3], &strand.domains[d5]) {
+        (Domain::HelixDomain(i3), Domain::HelixDomain(i5)) => (i3.clone(), i5.clone()),
+        _ => return None,
+    };
+    if interval_3.forward {
+        interval_3.end += shift;
+    } else {
+        interval_3.start += shift;
+    }
+    if interval_5.forward {
+        interval_5.start += shift;
+    } else {
+        interval_5.end += shift;
+    }
+    (interval_3.start < interval_3.end && interval_5.start < interval_5.end)
+        .then_some((interval_3, interval_5))
+}
+
+fn can_shift_xover(
+    strand: &Strand,
+    xover: (Nucl, Nucl),
+    shift: isize,
+    used_nucls: &HashSet<Nucl>,
+) -> bool {
+    if shift == 0 {
+        return true;
+    }
+    if let Some((interval_3, interval_5)) = shifted_intervals(strand, xover, shift) {
+        let current = domain_nucls(strand, xover);
+        interval_nucls(&interval_3)
+            .chain(interval_nucls(&interval_5))
+            .all(|nucl| current.contains(&nucl) || !used_nucls.contains(&nucl))
+    } else {
+        false
+    }
+}
+
+fn shift_xover(strand: &mut Strand, xover: (Nucl, Nucl), shift: isize) -> Option<()> {
+    let (interval_3, interval_5) = shifted_intervals(strand, xover, shift)?;
+    let (d3, d5) = xover_domains(strand, xover)?;
+    strand.domains[d3] = Domain::HelixDomain(interval_3);
+    strand.domains[d5] = Domain::HelixDomain(interval_5);
+    Some(())
+}
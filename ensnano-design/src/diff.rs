@@ -0,0 +1,170 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Comparison of two versions of a design.
+
+use super::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The identifiers of the elements that differ between two versions of a design.
+#[derive(Debug, Clone)]
+pub struct ElementsDiff<K: Ord> {
+    /// Elements that are only in the new version
+    pub added: BTreeSet<K>,
+    /// Elements that are only in the old version
+    pub removed: BTreeSet<K>,
+    /// Elements that are in both versions but are not identical
+    pub modified: BTreeSet<K>,
+}
+
+impl<K: Ord> Default for ElementsDiff<K> {
+    fn default() -> Self {
+        Self {
+            added: Default::default(),
+            removed: Default::default(),
+            modified: Default::default(),
+        }
+    }
+}
+
+impl<K: Ord + Copy> ElementsDiff<K> {
+    fn new<T, F: Fn(&T, &T) -> bool>(
+        old: &BTreeMap<K, T>,
+        new: &BTreeMap<K, T>,
+        are_equal: F,
+    ) -> Self {
+        let mut ret = Self::default();
+        for (key, old_value) in old.iter() {
+            match new.get(key) {
+                None => {
+                    ret.removed.insert(*key);
+                }
+                Some(new_value) if !are_equal(old_value, new_value) => {
+                    ret.modified.insert(*key);
+                }
+                Some(_) => (),
+            }
+        }
+        ret.added = new
+            .keys()
+            .filter(|key| !old.contains_key(key))
+            .copied()
+            .collect();
+        ret
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// The differences between two versions of a design.
+#[derive(Debug, Clone, Default)]
+pub struct DesignDiff {
+    pub strands: ElementsDiff<usize>,
+    pub helices: ElementsDiff<usize>,
+    /// Cross-overs are identified by the nucleotides that they join, so they are never
+    /// "modified".
+    pub xovers: ElementsDiff<(Nucl, Nucl)>,
+}
+
+impl DesignDiff {
+    /// Compute the changes that turn `old` into `new`.
+    pub fn new(old: &Design, new: &Design) -> Self {
+        let strands = ElementsDiff::new(
+            &old.strands.iter().map(|(id, s)| (*id, s)).collect(),
+            &new.strands.iter().map(|(id, s)| (*id, s)).collect(),
+            |a, b| same_strand(a, b),
+        );
+        // Helices are not comparable, so their serialized forms are compared instead.
+        let helices = ElementsDiff::new(
+            &old.helices
+                .iter()
+                .map(|(id, h)| (*id, comparable_helix(h)))
+                .collect(),
+            &new.helices
+                .iter()
+                .map(|(id, h)| (*id, comparable_helix(h)))
+                .collect(),
+            |a, b| a.is_some() && a == b,
+        );
+        let old_xovers: BTreeSet<_> = old.strands.get_xovers().into_iter().collect();
+        let new_xovers: BTreeSet<_> = new.strands.get_xovers().into_iter().collect();
+        let xovers = ElementsDiff {
+            added: new_xovers.difference(&old_xovers).copied().collect(),
+            removed: old_xovers.difference(&new_xovers).copied().collect(),
+            modified: BTreeSet::new(),
+        };
+        Self {
+            strands,
+            helices,
+            xovers,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strands.is_empty() && self.helices.is_empty() && self.xovers.is_empty()
+    }
+}
+
+// The stable identifiers of the elements are ignored: a design that is read from a file that
+// predates them receives new identifiers each time it is loaded.
+fn same_strand(a: &Strand, b: &Strand) -> bool {
+    a.domains == b.domains
+        && a.junctions == b.junctions
+        && a.sequence == b.sequence
+        && a.is_cyclic == b.is_cyclic
+        && a.color == b.color
+        && a.name == b.name
+}
+
+fn comparable_helix(helix: &Helix) -> Option<serde_json::Value> {
+    let mut value = serde_json::to_value(helix).ok()?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("uuid");
+    }
+    Some(value)
+}
+
+impl std::fmt::Display for DesignDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "The designs are identical");
+        }
+        writeln!(
+            f,
+            "Strands: {} added, {} removed, {} modified",
+            self.strands.added.len(),
+            self.strands.removed.len(),
+            self.strands.modified.len()
+        )?;
+        writeln!(
+            f,
+            "Helices: {} added, {} removed, {} modified",
+            self.helices.added.len(),
+            self.helices.removed.len(),
+            self.helices.modified.len()
+        )?;
+        write!(
+            f,
+            "Cross-overs: {} added, {} removed",
+            self.xovers.added.len(),
+            self.xovers.removed.len()
+        )
+    }
+}
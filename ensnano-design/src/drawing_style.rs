@@ -26,6 +26,53 @@ pub enum ColorType {
     Rainbow, // IGNORED FOR NOW -> Later you can add an argument to tell which kind of rainbow you want
 }
 
+/// The glyph drawn at the 5' or 3' end of a strand in the 3D view
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EndGlyph {
+    None,
+    /// A cone pointing in the 5' to 3' direction of the strand
+    Arrowhead,
+    Sphere,
+    /// A cylinder as long as it is wide, that appears square when seen from the side
+    Square,
+}
+
+impl Default for EndGlyph {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl EndGlyph {
+    pub const ALL: [Self; 4] = [Self::None, Self::Arrowhead, Self::Sphere, Self::Square];
+}
+
+impl std::fmt::Display for EndGlyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::None => "None",
+            Self::Arrowhead => "Arrowhead",
+            Self::Sphere => "Sphere",
+            Self::Square => "Square",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for EndGlyph {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "arrow" => Ok(Self::Arrowhead),
+            "sphere" => Ok(Self::Sphere),
+            "square" => Ok(Self::Square),
+            _ => Err(ParsePointError),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum DrawingAttribute {
     SphereRadius(f32),
@@ -40,6 +87,10 @@ pub enum DrawingAttribute {
     WithCones(bool),
     OnAxis(bool),
     Curvature(f32, f32),
+    Prime5Glyph(EndGlyph),
+    Prime3Glyph(EndGlyph),
+    /// Draw a direction cone on one bond out of `n`. No cone is drawn if `n` is 0.
+    ConeSpacing(usize),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +112,8 @@ impl FromStr for DrawingAttribute {
     /// - %wc / %noc for WithCones(true / false) - default = true
     /// - %onaxis / %offaxis for OnAxis(true / false) - default = false
     /// - %cv(r_min, r_max) - show the curvature radius using Purple to Blue gradient the helix cylinder for radius within the range r_min..r_max
+    /// - %p5(g) / %p3(g) for Prime5Glyph(g) / Prime3Glyph(g) with g = none, arrow, sphere or square
+    /// - %cd(n) for ConeSpacing(n): a direction cone every n bonds
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parsed = s
             .split(&['%', ' ', ',', ')', '('])
@@ -121,6 +174,20 @@ impl FromStr for DrawingAttribute {
                     _ => (),
                 }
             }
+            "p5" | "p3" if len == 2 => {
+                if let Ok(glyph) = EndGlyph::from_str(parsed[1]) {
+                    if parsed[0] == "p5" {
+                        return Ok(Self::Prime5Glyph(glyph));
+                    } else {
+                        return Ok(Self::Prime3Glyph(glyph));
+                    }
+                }
+            }
+            "cd" if len == 2 => {
+                if let Ok(spacing) = usize::from_str(parsed[1]) {
+                    return Ok(Self::ConeSpacing(spacing));
+                }
+            }
             "cv" if len == 3 => {
                 if let Ok(r_min) = f32::from_str(parsed[1]) {
                     if let Ok(r_max) = f32::from_str(parsed[2]) {
@@ -164,6 +231,13 @@ pub struct DrawingStyle {
     /// (r_min, r_max) display curvature on the helix cylinder with a gradient for radius from r_min to r_max
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub curvature: Option<(f32, f32)>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub prime5_glyph: Option<EndGlyph>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub prime3_glyph: Option<EndGlyph>,
+    /// Draw a direction cone on one bond out of `cone_spacing`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cone_spacing: Option<usize>,
 }
 
 impl std::default::Default for DrawingStyle {
@@ -182,6 +256,9 @@ impl std::default::Default for DrawingStyle {
             with_cones: None,
             on_axis: None,
             curvature: None,
+            prime5_glyph: None,
+            prime3_glyph: None,
+            cone_spacing: None,
         }
     }
 }
@@ -218,6 +295,9 @@ impl From<Vec<DrawingAttribute>> for DrawingStyle {
                 DrawingAttribute::Curvature(r_min, r_max) => {
                     ret.curvature = ret.curvature.or(Some((r_min, r_max)))
                 }
+                DrawingAttribute::Prime5Glyph(g) => ret.prime5_glyph = ret.prime5_glyph.or(Some(g)),
+                DrawingAttribute::Prime3Glyph(g) => ret.prime3_glyph = ret.prime3_glyph.or(Some(g)),
+                DrawingAttribute::ConeSpacing(n) => ret.cone_spacing = ret.cone_spacing.or(Some(n)),
             }
         }
         return ret;
@@ -276,6 +356,18 @@ impl DrawingStyle {
                 curvature: Some((r_min, r_max)),
                 ..*self
             },
+            DrawingAttribute::Prime5Glyph(g) => DrawingStyle {
+                prime5_glyph: Some(g),
+                ..*self
+            },
+            DrawingAttribute::Prime3Glyph(g) => DrawingStyle {
+                prime3_glyph: Some(g),
+                ..*self
+            },
+            DrawingAttribute::ConeSpacing(n) => DrawingStyle {
+                cone_spacing: Some(n),
+                ..*self
+            },
         }
     }
 
@@ -322,6 +414,16 @@ impl DrawingStyle {
             atts.push(DrawingAttribute::Curvature(r_min, r_max))
         }
 
+        if let Some(g) = self.prime5_glyph {
+            atts.push(DrawingAttribute::Prime5Glyph(g))
+        }
+        if let Some(g) = self.prime3_glyph {
+            atts.push(DrawingAttribute::Prime3Glyph(g))
+        }
+        if let Some(n) = self.cone_spacing {
+            atts.push(DrawingAttribute::ConeSpacing(n))
+        }
+
         return atts;
     }
 
@@ -376,6 +478,18 @@ impl DrawingStyle {
                 curvature: self.curvature.or(Some((r_min, r_max))),
                 ..*self
             },
+            DrawingAttribute::Prime5Glyph(g) => DrawingStyle {
+                prime5_glyph: self.prime5_glyph.or(Some(g)),
+                ..*self
+            },
+            DrawingAttribute::Prime3Glyph(g) => DrawingStyle {
+                prime3_glyph: self.prime3_glyph.or(Some(g)),
+                ..*self
+            },
+            DrawingAttribute::ConeSpacing(n) => DrawingStyle {
+                cone_spacing: self.cone_spacing.or(Some(n)),
+                ..*self
+            },
         }
     }
 
@@ -406,6 +520,9 @@ impl DrawingStyle {
             hue_range: self.hue_range.or(other.hue_range),
             on_axis: self.on_axis.or(other.on_axis),
             curvature: self.curvature.or(other.curvature),
+            prime5_glyph: self.prime5_glyph.or(other.prime5_glyph),
+            prime3_glyph: self.prime3_glyph.or(other.prime3_glyph),
+            cone_spacing: self.cone_spacing.or(other.cone_spacing),
         };
     }
 }
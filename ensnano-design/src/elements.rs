@@ -168,6 +168,14 @@ impl OrganizerElement for DesignElement {
         }
     }
 
+    fn size(&self) -> Option<usize> {
+        if let DesignElement::StrandElement { length, .. } = self {
+            Some(*length)
+        } else {
+            None
+        }
+    }
+
     fn attributes(&self) -> Vec<DnaAttribute> {
         match self {
             DesignElement::HelixElement {
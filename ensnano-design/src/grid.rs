@@ -52,6 +52,101 @@ pub enum GridId {
     BezierPathGrid(BezierVertexId),
 }
 
+/// The side of the helices' origin on which their phantom helices are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum PhantomDirection {
+    Both,
+    /// Only draw the phantom nucleotides with a positive position
+    Right,
+    /// Only draw the phantom nucleotides with a negative position
+    Left,
+}
+
+impl PhantomDirection {
+    pub const ALL: &'static [Self] = &[Self::Both, Self::Right, Self::Left];
+}
+
+impl Default for PhantomDirection {
+    fn default() -> Self {
+        Self::Both
+    }
+}
+
+impl std::fmt::Display for PhantomDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Both => "Both sides",
+            Self::Right => "Right",
+            Self::Left => "Left",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The way the persistent phantom helices of a grid are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhantomParameters {
+    /// The number of nucleotides drawn on each side of the helices' origin
+    pub length: usize,
+    pub direction: PhantomDirection,
+    /// Between 0 (transparent) and 1 (opaque)
+    pub opacity: f32,
+}
+
+impl PhantomParameters {
+    pub const DEFAULT_LENGTH: usize = 100;
+    pub const MAX_LENGTH: usize = 1000;
+
+    /// The range of positions at which phantom nucleotides must be drawn
+    pub fn range(&self) -> std::ops::RangeInclusive<isize> {
+        let length = self.length as isize;
+        match self.direction {
+            PhantomDirection::Both => -length..=length,
+            PhantomDirection::Right => 0..=length,
+            PhantomDirection::Left => -length..=0,
+        }
+    }
+
+    /// The alpha byte of the color of the phantom nucleotides
+    pub fn alpha(&self) -> u32 {
+        (self.opacity.clamp(0., 1.) * 255.).round() as u32
+    }
+}
+
+impl Default for PhantomParameters {
+    fn default() -> Self {
+        Self {
+            length: Self::DEFAULT_LENGTH,
+            direction: Default::default(),
+            opacity: 1.,
+        }
+    }
+}
+
+/// Grid identifiers cannot be used as keys of JSON objects, so the map is (de)serialized as a list
+/// of pairs.
+pub(crate) mod phantom_parameters_serde {
+    use super::{GridId, PhantomParameters};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(
+        map: &Arc<BTreeMap<GridId, PhantomParameters>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let pairs: Vec<(&GridId, &PhantomParameters)> = map.iter().collect();
+        pairs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Arc<BTreeMap<GridId, PhantomParameters>>, D::Error> {
+        let pairs: Vec<(GridId, PhantomParameters)> = Vec::deserialize(deserializer)?;
+        Ok(Arc::new(pairs.into_iter().collect()))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Grid {
     pub position: Vec3,
@@ -729,7 +824,9 @@ pub struct GridData {
     pos_to_object: HashMap<GridPosition, GridObject>,
     pub helix_parameters: HelixParameters,
     pub no_phantoms: Arc<HashSet<GridId>>,
+    pub phantom_parameters: Arc<BTreeMap<GridId, PhantomParameters>>,
     pub small_spheres: Arc<HashSet<GridId>>,
+    pub labelled_grids: Arc<HashSet<GridId>>,
     center_of_gravity: HashMap<GridId, CenterOfGravity>,
     paths_data: Option<BezierPathData>,
     path_time_maps: Arc<BTreeMap<BezierPathId, Arc<PathTimeMaps>>>,
@@ -765,7 +862,9 @@ impl GridData {
         Arc::ptr_eq(&self.source_free_grids.0, &design.free_grids.0)
             && Arc::ptr_eq(&self.source_helices.0, &design.helices.0)
             && Arc::ptr_eq(&self.no_phantoms, &design.no_phantoms)
+            && Arc::ptr_eq(&self.phantom_parameters, &design.phantom_parameters)
             && Arc::ptr_eq(&self.small_spheres, &design.small_spheres)
+            && Arc::ptr_eq(&self.labelled_grids, &design.labelled_grids)
             && design
                 .instanciated_paths
                 .as_ref()
@@ -828,7 +927,9 @@ impl GridData {
             pos_to_object,
             helix_parameters: design.helix_parameters.unwrap_or_default(), //ne change rien ???
             no_phantoms: design.no_phantoms.clone(),
+            phantom_parameters: design.phantom_parameters.clone(),
             small_spheres: design.small_spheres.clone(),
+            labelled_grids: design.labelled_grids.clone(),
             center_of_gravity: Default::default(),
             paths_data: Some(paths_data),
             path_time_maps: Default::default(),
@@ -1152,17 +1253,29 @@ impl GridData {
         self.pos_to_object.keys().filter(filter).map(map).collect()
     }
 
-    pub fn get_persistent_phantom_helices_id(&self) -> HashSet<u32> {
+    /// Return a map from the identifiers of the helices whose phantom helices must always be
+    /// drawn to the parameters of their grid's phantom helices.
+    pub fn get_persistent_phantom_helices_id(&self) -> HashMap<u32, PhantomParameters> {
         self.pos_to_object
             .iter()
             .filter(|(k, _)| !self.no_phantoms.contains(&k.grid))
-            .map(|(_, v)| match v {
-                GridObject::Helix(h) => *h as u32,
-                GridObject::BezierPoint { helix_id, .. } => *helix_id as u32,
+            .map(|(k, v)| {
+                let h_id = match v {
+                    GridObject::Helix(h) => *h as u32,
+                    GridObject::BezierPoint { helix_id, .. } => *helix_id as u32,
+                };
+                (h_id, self.get_phantom_parameters(k.grid))
             })
             .collect()
     }
 
+    pub fn get_phantom_parameters(&self, g_id: GridId) -> PhantomParameters {
+        self.phantom_parameters
+            .get(&g_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub fn get_helix_grid_position(&self, h_id: usize) -> Option<HelixGridPosition> {
         self.object_to_pos.get(&GridObject::Helix(h_id)).cloned()
     }
@@ -227,6 +227,10 @@ impl Design {
                 .name
                 .as_ref()
                 .map(|n| Cow::from(format!("{}_copy", n))),
+            uuid: None,
+            pool: source_strand.pool.clone(),
+            note: source_strand.note.clone(),
+            url: source_strand.url.clone(),
         })
     }
 }
@@ -241,3 +245,17 @@ pub enum GridCopyError {
     HelixNotOnGrid(usize),
     NoGridToCopy,
 }
+
+impl std::fmt::Display for GridCopyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GridDoesNotExist(g_id) => write!(f, "Grid {} does not exist", g_id.0),
+            Self::StrandDoesNotExist(s_id) => write!(f, "Strand {} does not exist", s_id),
+            Self::HelixIdNotInNewHelixMap(h_id) => write!(f, "Could not copy helix {}", h_id),
+            Self::GridNotInMap(g_id) => write!(f, "Could not copy grid {}", g_id),
+            Self::HelixDoesNotExist(h_id) => write!(f, "Helix {} does not exist", h_id),
+            Self::HelixNotOnGrid(h_id) => write!(f, "Helix {} is not on a grid", h_id),
+            Self::NoGridToCopy => write!(f, "There is no grid to copy"),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Measures computed at each position of the helices of a design, drawn as heat maps over the 2D
+//! view to spot structurally weak regions.
+
+use super::*;
+use std::collections::BTreeSet;
+
+/// The measure displayed by a heat map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeatMapKind {
+    /// The number of nicks around each position
+    NickDensity,
+    /// The number of cross-over ends around each position
+    XoverDensity,
+    /// The length of the longest domain of the staples covering each position
+    SeedStrength,
+    /// The twist strain of the cross-overs around each position
+    XoverStrain,
+}
+
+pub const ALL_HEAT_MAP_KINDS: [HeatMapKind; 4] = [
+    HeatMapKind::NickDensity,
+    HeatMapKind::XoverDensity,
+    HeatMapKind::SeedStrength,
+    HeatMapKind::XoverStrain,
+];
+
+impl std::fmt::Display for HeatMapKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::NickDensity => "Nick density",
+            Self::XoverDensity => "Cross-over density",
+            Self::SeedStrength => "Seed domain strength",
+            Self::XoverStrain => "Cross-over twist strain",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// Nicks and cross-overs are counted, and cross-over strains are looked for, up to this distance,
+/// in nucleotides, from each position.
+pub const HEAT_MAP_WINDOW: isize = 10;
+
+/// For each helix, the positions covered by at least one strand and the value of the measure at
+/// these positions, normalized between 0 and 1.
+pub fn heat_map(design: &Design, kind: HeatMapKind) -> BTreeMap<usize, Vec<(isize, f32)>> {
+    let mut covered: BTreeMap<usize, BTreeSet<isize>> = BTreeMap::new();
+    for strand in design.strands.values() {
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                covered
+                    .entry(interval.helix)
+                    .or_default()
+                    .extend(interval.start..interval.end);
+            }
+        }
+    }
+
+    let mut ret: BTreeMap<usize, Vec<(isize, f32)>> = match kind {
+        HeatMapKind::NickDensity => {
+            let nicks = design
+                .strands
+                .values()
+                .filter(|s| !s.is_cyclic)
+                .flat_map(|s| s.get_5prime().into_iter().chain(s.get_3prime()));
+            density(&covered, nicks)
+        }
+        HeatMapKind::XoverDensity => {
+            let xover_ends = design
+                .strands
+                .values()
+                .flat_map(|s| s.xovers())
+                .flat_map(|(n1, n2)| vec![n1, n2]);
+            density(&covered, xover_ends)
+        }
+        HeatMapKind::SeedStrength => seed_strength(design),
+        HeatMapKind::XoverStrain => xover_strain(design, &covered),
+    };
+
+    let max = ret
+        .values()
+        .flat_map(|values| values.iter().map(|(_, v)| *v))
+        .fold(0f32, f32::max);
+    if max > 0. {
+        for (_, v) in ret.values_mut().flat_map(|values| values.iter_mut()) {
+            *v /= max;
+        }
+    }
+    ret
+}
+
+/// Count the events that happen in a window around each covered position.
+fn density(
+    covered: &BTreeMap<usize, BTreeSet<isize>>,
+    events: impl Iterator<Item = Nucl>,
+) -> BTreeMap<usize, Vec<(isize, f32)>> {
+    let mut events_per_helix: HashMap<usize, Vec<isize>> = HashMap::new();
+    for nucl in events {
+        events_per_helix
+            .entry(nucl.helix)
+            .or_default()
+            .push(nucl.position);
+    }
+    for positions in events_per_helix.values_mut() {
+        positions.sort_unstable();
+    }
+
+    let mut ret = BTreeMap::new();
+    for (h_id, positions) in covered.iter() {
+        let events = events_per_helix
+            .get(h_id)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        let values = positions
+            .iter()
+            .map(|p| {
+                let start = events.partition_point(|e| *e < p - HEAT_MAP_WINDOW);
+                let end = events.partition_point(|e| *e <= p + HEAT_MAP_WINDOW);
+                (*p, (end - start) as f32)
+            })
+            .collect();
+        ret.insert(*h_id, values);
+    }
+    ret
+}
+
+/// For each covered position, the largest twist strain of the cross-overs around it.
+fn xover_strain(
+    design: &Design,
+    covered: &BTreeMap<usize, BTreeSet<isize>>,
+) -> BTreeMap<usize, Vec<(isize, f32)>> {
+    let mut strains_per_helix: HashMap<usize, Vec<(isize, f32)>> = HashMap::new();
+    for strain in crate::xover_strain::xover_strains(design) {
+        let (n1, n2) = strain.xover;
+        for nucl in [n1, n2] {
+            strains_per_helix
+                .entry(nucl.helix)
+                .or_default()
+                .push((nucl.position, strain.strain.abs()));
+        }
+    }
+
+    let mut ret = BTreeMap::new();
+    for (h_id, positions) in covered.iter() {
+        let strains = strains_per_helix
+            .get(h_id)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        let values = positions
+            .iter()
+            .map(|p| {
+                let value = strains
+                    .iter()
+                    .filter(|(position, _)| (position - p).abs() <= HEAT_MAP_WINDOW)
+                    .map(|(_, strain)| *strain)
+                    .fold(0f32, f32::max);
+                (*p, value)
+            })
+            .collect();
+        ret.insert(*h_id, values);
+    }
+    ret
+}
+
+/// For each position covered by a staple, the length of the longest domain of the staples
+/// covering it. Positions covered only by the scaffold have a strength of 0.
+fn seed_strength(design: &Design) -> BTreeMap<usize, Vec<(isize, f32)>> {
+    let mut strength: BTreeMap<usize, BTreeMap<isize, f32>> = BTreeMap::new();
+    for (s_id, strand) in design.strands.iter() {
+        let is_scaffold = design.scaffold_id == Some(*s_id);
+        let longest_domain = strand
+            .domains
+            .iter()
+            .filter(|d| matches!(d, Domain::HelixDomain(_)))
+            .map(|d| d.length())
+            .max()
+            .unwrap_or(0);
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                let helix_strength = strength.entry(interval.helix).or_default();
+                for position in interval.start..interval.end {
+                    let value = helix_strength.entry(position).or_insert(0.);
+                    if !is_scaffold {
+                        *value = value.max(longest_domain as f32);
+                    }
+                }
+            }
+        }
+    }
+    strength
+        .into_iter()
+        .map(|(h_id, values)| (h_id, values.into_iter().collect()))
+        .collect()
+}
@@ -30,6 +30,7 @@ use super::{
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use ultraviolet::{DRotor3, DVec3, Isometry2, Mat4, Rotor3, Vec2, Vec3};
+use uuid::Uuid;
 
 /// A structure maping helices identifier to `Helix` objects
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -56,6 +57,10 @@ pub trait HelixCollection {
         self.len() == 0
     }
     fn contains_key(&self, id: &usize) -> bool;
+    /// The number with which helix `id` is displayed to the user.
+    fn display_number(&self, id: usize) -> usize {
+        self.get(&id).and_then(|h| h.display_number).unwrap_or(id)
+    }
 }
 
 pub trait HasHelixCollection {
@@ -244,6 +249,16 @@ pub struct Helix {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) path_id: Option<BezierPathId>,
+
+    /// An identifier that is preserved when the design is saved, loaded or modified, unlike the
+    /// key of the helix in the design's collection of helices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<Uuid>,
+
+    /// The number shown for this helix in labels, the 2D view and exports. When `None`, the
+    /// identifier of the helix is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_number: Option<usize>,
 }
 
 impl Helix {
@@ -289,6 +304,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -373,6 +390,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         })
     }
 
@@ -422,6 +441,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -451,6 +472,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -473,6 +496,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -495,6 +520,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -517,6 +544,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
@@ -614,6 +643,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         };
         // we can use a fake cache because we don't need it for bezier curves.
         let mut fake_cache = Default::default();
@@ -658,6 +689,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: Some(path_id),
+            uuid: None,
+            display_number: None,
         };
         let mut fake_cache = Default::default();
         grid_manager.update_curve(&mut ret, &mut fake_cache);
@@ -728,6 +761,17 @@ impl Helix {
             .and_then(|c| c.curve.curvature_at_pos(n))
     }
 
+    /// The smallest radius of curvature of the axis of the helix, if the helix is curved.
+    pub fn min_radius_of_curvature(&self) -> Option<f64> {
+        let curve = &self.instanciated_curve.as_ref()?.curve;
+        let nucl_t0 = curve.nucl_t0() as isize;
+        (0..curve.nb_points() as isize)
+            .filter_map(|idx| self.curvature_at_pos(idx - nucl_t0))
+            .filter(|curvature| *curvature > 0.)
+            .map(|curvature| 1. / curvature)
+            .min_by(f64::total_cmp)
+    }
+
     fn theta_n_to_space_pos(
         &self,
         p: &HelixParameters,
@@ -825,6 +869,8 @@ impl Helix {
             initial_nt_index: 0,
             support_helix: None,
             path_id: None,
+            uuid: None,
+            display_number: None,
         }
     }
 
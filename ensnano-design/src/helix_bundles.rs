@@ -0,0 +1,154 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Helix bundles are sets of helices that are moved as a single rigid object.
+//!
+//! Each bundle has a pivot that undergoes the same rigid transformations as its helices, so that
+//! the pivot can be used as the frame of the bundle.
+
+use super::group_attributes::GroupPivot;
+use super::{Design, HelixCollection};
+use std::collections::{BTreeMap, BTreeSet};
+use ultraviolet::{Rotor3, Vec3};
+
+/// The identifier of a helix bundle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BundleId(pub usize);
+
+/// A named set of helices that are translated and rotated together
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HelixBundle {
+    pub name: String,
+    pub helices: BTreeSet<usize>,
+    pub pivot: GroupPivot,
+}
+
+impl HelixBundle {
+    /// Create a bundle whose pivot is at the center of the helices and oriented like the first
+    /// helix.
+    pub fn new(design: &Design, name: String, helices: BTreeSet<usize>) -> Self {
+        let helix_parameters = design.helix_parameters.unwrap_or_default();
+        let intervals = design.strands.get_intervals();
+        let mut centers = Vec::with_capacity(helices.len());
+        let mut orientation = None;
+        for h_id in helices.iter() {
+            if let Some(helix) = design.helices.get(h_id) {
+                let center = if let Some((min, max)) = intervals.get(h_id) {
+                    (helix.axis_position(&helix_parameters, *min, true)
+                        + helix.axis_position(&helix_parameters, *max, true))
+                        / 2.
+                } else {
+                    helix.position
+                };
+                centers.push(center);
+                orientation.get_or_insert(helix.orientation);
+            }
+        }
+        let position = if centers.is_empty() {
+            Vec3::zero()
+        } else {
+            centers.iter().fold(Vec3::zero(), |a, b| a + *b) / centers.len() as f32
+        };
+        Self {
+            name,
+            helices,
+            pivot: GroupPivot {
+                position,
+                orientation: orientation.unwrap_or_default(),
+            },
+        }
+    }
+
+    fn translate(&mut self, translation: Vec3) {
+        self.pivot.position += translation;
+    }
+
+    fn rotate_arround(&mut self, rotation: Rotor3, origin: Vec3) {
+        self.pivot.position = (self.pivot.position - origin).rotated_by(rotation) + origin;
+        self.pivot.orientation = rotation * self.pivot.orientation;
+    }
+}
+
+/// Return the bundle containing helix `h_id`, if any.
+pub fn bundle_of_helix(bundles: &BTreeMap<BundleId, HelixBundle>, h_id: usize) -> Option<BundleId> {
+    bundles
+        .iter()
+        .find(|(_, bundle)| bundle.helices.contains(&h_id))
+        .map(|(id, _)| *id)
+}
+
+/// Remove `helices` from the bundles that contain them. The bundles that become empty are deleted
+/// and their identifiers are returned.
+pub fn remove_helices_from_bundles(
+    bundles: &mut BTreeMap<BundleId, HelixBundle>,
+    helices: &BTreeSet<usize>,
+) -> Vec<BundleId> {
+    for bundle in bundles.values_mut() {
+        bundle.helices.retain(|h| !helices.contains(h));
+    }
+    let empty_bundles: Vec<BundleId> = bundles
+        .iter()
+        .filter(|(_, bundle)| bundle.helices.is_empty())
+        .map(|(id, _)| *id)
+        .collect();
+    for id in empty_bundles.iter() {
+        bundles.remove(id);
+    }
+    empty_bundles
+}
+
+/// Return `helices` completed with all the other helices of their bundles, and the identifiers
+/// of these bundles.
+pub(crate) fn with_bundle_mates(
+    bundles: &BTreeMap<BundleId, HelixBundle>,
+    helices: Vec<usize>,
+) -> (Vec<usize>, Vec<BundleId>) {
+    let mut moved_bundles = Vec::new();
+    let mut all_helices: BTreeSet<usize> = helices.iter().cloned().collect();
+    for (id, bundle) in bundles.iter() {
+        if helices.iter().any(|h| bundle.helices.contains(h)) {
+            moved_bundles.push(*id);
+            all_helices.extend(bundle.helices.iter().cloned());
+        }
+    }
+    (all_helices.into_iter().collect(), moved_bundles)
+}
+
+pub(crate) fn translate_bundles(
+    bundles: &mut BTreeMap<BundleId, HelixBundle>,
+    ids: &[BundleId],
+    translation: Vec3,
+) {
+    for id in ids.iter() {
+        if let Some(bundle) = bundles.get_mut(id) {
+            bundle.translate(translation);
+        }
+    }
+}
+
+pub(crate) fn rotate_bundles(
+    bundles: &mut BTreeMap<BundleId, HelixBundle>,
+    ids: &[BundleId],
+    rotation: Rotor3,
+    origin: Vec3,
+) {
+    for id in ids.iter() {
+        if let Some(bundle) = bundles.get_mut(id) {
+            bundle.rotate_arround(rotation, origin);
+        }
+    }
+}
@@ -23,6 +23,7 @@ use std::f32::consts::{PI, TAU};
 
 const EPSILON_DESC: f32 = 0.05;
 
+#[derive(PartialEq)]
 struct InsertionDescriptor {
     edge: InsertionEdge,
     nb_nucl: usize,
@@ -131,11 +132,13 @@ fn cord_length(d: f32, h: f32, increasing: bool, nb_nucl: usize) -> f32 {
     let small_angle = total_angle / (nb_nucl as f32 + 1.);
     2. * r * (small_angle / 2.).sin()
 }
+#[derive(PartialEq)]
 struct InsertionEdge {
     prime_5: InsertionEnd,
     prime_3: InsertionEnd,
 }
 
+#[derive(PartialEq)]
 struct InsertionEnd {
     position: Vec3,
     up_vec: Vec3,
@@ -149,6 +152,7 @@ impl InsertionDescriptor {
     }
 }
 
+#[derive(PartialEq)]
 pub struct InstanciatedInsertion {
     descriptor: InsertionDescriptor,
     instanciation: Vec<Vec3>,
@@ -224,11 +228,32 @@ impl InsertionDescriptor {
 }
 
 impl Strand {
+    /// Return true if some insertions of the strand must be instanciated again.
+    pub fn insertions_need_update(
+        &self,
+        helices: &dyn HelixCollection,
+        helix_parameters: &HelixParameters,
+    ) -> bool {
+        !self
+            .outdated_insertions(helices, helix_parameters)
+            .is_empty()
+    }
+
     pub fn update_insertions(
         &mut self,
         helices: &dyn HelixCollection,
         helix_parameters: &HelixParameters,
     ) {
+        for (d_id, descriptor) in self.outdated_insertions(helices, helix_parameters) {
+            self.update_insertion(d_id, descriptor, helix_parameters);
+        }
+    }
+
+    fn outdated_insertions(
+        &self,
+        helices: &dyn HelixCollection,
+        helix_parameters: &HelixParameters,
+    ) -> Vec<(usize, InsertionDescriptor)> {
         let mut to_be_updated = Vec::new();
         let nb_domain = self.domains.len();
         for (d_prev, ((d_id, d), d_next)) in self.domains.iter().cycle().skip(nb_domain - 1).zip(
@@ -238,7 +263,12 @@ impl Strand {
                 .enumerate() // domain index plus domain
                 .zip(self.domains.iter().cycle().skip(1)), // next domain
         ) {
-            if let Domain::Insertion { .. } = d {
+            if let Domain::Insertion {
+                nb_nucl,
+                instanciation,
+                ..
+            } = d
+            {
                 if let Some((prime_5, prime_3)) = d_prev.prime3_end().zip(d_next.prime5_end()) {
                     let prime_5 = helices.get(&prime_5.helix).map(|h| {
                         let position =
@@ -255,7 +285,17 @@ impl Strand {
                         InsertionEnd { position, up_vec }
                     });
                     if let Some((prime_5, prime_3)) = prime_5.zip(prime_3) {
-                        to_be_updated.push((d_id, InsertionEdge { prime_5, prime_3 }));
+                        let descriptor = InsertionDescriptor {
+                            nb_nucl: *nb_nucl,
+                            edge: InsertionEdge { prime_5, prime_3 },
+                        };
+                        let up_to_date = instanciation
+                            .as_ref()
+                            .map(|i| i.descriptor.is_up_to_date(&descriptor))
+                            .unwrap_or(false);
+                        if !up_to_date {
+                            to_be_updated.push((d_id, descriptor));
+                        }
                     } else {
                         log::error!("Could not get space pos for insertion");
                     }
@@ -264,37 +304,20 @@ impl Strand {
                 }
             }
         }
-        for (d_id, edge) in to_be_updated.into_iter() {
-            self.update_insertion(d_id, edge, helix_parameters);
-        }
+        to_be_updated
     }
 
     fn update_insertion(
         &mut self,
         d_id: usize,
-        edge: InsertionEdge,
+        descriptor: InsertionDescriptor,
         helix_parameters: &HelixParameters,
     ) {
-        if let Some(Domain::Insertion {
-            nb_nucl,
-            instanciation,
-            ..
-        }) = self.domains.get_mut(d_id)
-        {
-            let descriptor = InsertionDescriptor {
-                nb_nucl: *nb_nucl,
-                edge,
-            };
-            let up_to_date = instanciation
-                .as_ref()
-                .map(|i| i.descriptor.is_up_to_date(&descriptor))
-                .unwrap_or(false);
-            if !up_to_date {
-                *instanciation = Some(Arc::new(InstanciatedInsertion {
-                    instanciation: descriptor.instanciate(helix_parameters),
-                    descriptor,
-                }))
-            }
+        if let Some(Domain::Insertion { instanciation, .. }) = self.domains.get_mut(d_id) {
+            *instanciation = Some(Arc::new(InstanciatedInsertion {
+                instanciation: descriptor.instanciate(helix_parameters),
+                descriptor,
+            }))
         } else {
             log::error!("Wrong domain id");
         }
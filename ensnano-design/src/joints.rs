@@ -0,0 +1,131 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Kinematic joints between rigid parts of a design.
+//!
+//! A joint links two bodies, which are either free grids or helix bundles, and restricts their
+//! relative motion during rigid body simulations. The anchor and axis of a joint are stored in
+//! the frame of each body so that they follow the bodies when these are moved.
+
+use super::grid::GridId;
+use super::{BundleId, Design};
+use ultraviolet::{Rotor3, Vec3};
+
+/// The identifier of a joint
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct JointId(pub usize);
+
+/// A rigid part of the design that can be linked by a joint
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum JointBody {
+    Grid(GridId),
+    Bundle(BundleId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JointKind {
+    /// The bodies can only rotate around the axis of the joint
+    Hinge,
+    /// The bodies can only translate along the axis of the joint
+    Slider,
+    /// The bodies can rotate freely around the anchor of the joint
+    Ball,
+}
+
+impl JointKind {
+    pub const ALL: [Self; 3] = [Self::Hinge, Self::Slider, Self::Ball];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hinge => "Hinge",
+            Self::Slider => "Slider",
+            Self::Ball => "Ball",
+        }
+    }
+}
+
+impl std::fmt::Display for JointKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Joint {
+    pub bodies: (JointBody, JointBody),
+    pub kind: JointKind,
+    /// The anchor of the joint, in the frame of each body
+    pub anchors: (Vec3, Vec3),
+    /// The unit axis of the joint, in the frame of each body. It is ignored for ball joints.
+    pub axes: (Vec3, Vec3),
+}
+
+impl Joint {
+    /// Create a joint whose anchor and axis are given in world coordinates. Return `None` if one
+    /// of the bodies does not exist.
+    pub fn new(
+        design: &Design,
+        bodies: (JointBody, JointBody),
+        kind: JointKind,
+        anchor: Vec3,
+        axis: Vec3,
+    ) -> Option<Self> {
+        let (position_0, orientation_0) = body_frame(design, bodies.0)?;
+        let (position_1, orientation_1) = body_frame(design, bodies.1)?;
+        let axis = axis.normalized();
+        Some(Self {
+            bodies,
+            kind,
+            anchors: (
+                (anchor - position_0).rotated_by(orientation_0.reversed()),
+                (anchor - position_1).rotated_by(orientation_1.reversed()),
+            ),
+            axes: (
+                axis.rotated_by(orientation_0.reversed()),
+                axis.rotated_by(orientation_1.reversed()),
+            ),
+        })
+    }
+
+    /// The anchor and axis of the joint in world coordinates, as seen from the first body.
+    pub fn world_anchor_and_axis(&self, design: &Design) -> Option<(Vec3, Vec3)> {
+        let (position, orientation) = body_frame(design, self.bodies.0)?;
+        Some((
+            self.anchors.0.rotated_by(orientation) + position,
+            self.axes.0.rotated_by(orientation),
+        ))
+    }
+
+    pub fn links_body(&self, body: JointBody) -> bool {
+        self.bodies.0 == body || self.bodies.1 == body
+    }
+}
+
+/// The position and orientation of the frame of a body in world coordinates
+pub fn body_frame(design: &Design, body: JointBody) -> Option<(Vec3, Rotor3)> {
+    match body {
+        JointBody::Grid(g_id) => design
+            .free_grids
+            .get_from_g_id(&g_id)
+            .map(|grid| (grid.position, grid.orientation)),
+        JointBody::Bundle(b_id) => design
+            .helix_bundles
+            .get(&b_id)
+            .map(|bundle| (bundle.pivot.position, bundle.pivot.orientation)),
+    }
+}
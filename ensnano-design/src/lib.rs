@@ -46,6 +46,16 @@ use elements::DesignElementKey;
 pub type EnsnTree = OrganizerTree<DesignElementKey>;
 pub mod group_attributes;
 use group_attributes::GroupAttribute;
+mod helix_bundles;
+pub use helix_bundles::{bundle_of_helix, remove_helices_from_bundles, BundleId, HelixBundle};
+mod joints;
+pub use joints::{body_frame, Joint, JointBody, JointId, JointKind};
+mod presentation;
+pub use presentation::PresentationStep;
+mod provenance;
+pub use provenance::DesignProvenance;
+mod metadata;
+pub use metadata::DesignMetadata;
 
 mod strands;
 pub use strands::*;
@@ -56,7 +66,11 @@ mod curves;
 pub use curves::*;
 mod collection;
 pub mod design_operations;
+pub mod diff;
+pub mod heat_map;
+pub mod pairing_audit;
 pub mod utils;
+pub mod xover_strain;
 pub use collection::{Collection, HasMap};
 pub mod isometry3_descriptor;
 pub use isometry3_descriptor::Isometry3Descriptor;
@@ -79,6 +93,8 @@ mod tests;
 pub use external_3d_objects::*;
 
 mod isograph;
+mod stable_ids;
+pub use uuid::Uuid;
 
 /// The `ensnano` Design structure.
 #[derive(Serialize, Deserialize, Clone)]
@@ -119,7 +135,7 @@ pub struct Design {
     old_grids: Vec<GridDescriptor>,
 
     /// The cross-over suggestion groups
-    #[serde(skip_serializing_if = "groups_is_empty", default)]
+    #[serde(skip_serializing_if = "arc_map_is_empty", default)]
     pub groups: Arc<BTreeMap<usize, bool>>,
 
     /// The set of identifiers of grids whose helices must not always display their phantom
@@ -127,6 +143,15 @@ pub struct Design {
     #[serde(skip_serializing_if = "HashSet::is_empty", default)]
     pub no_phantoms: Arc<HashSet<GridId>>,
 
+    /// The length, direction and opacity of the persistent phantom helices of each grid. Grids
+    /// that are not in this map use the default parameters.
+    #[serde(
+        skip_serializing_if = "arc_map_is_empty",
+        default,
+        with = "grid::phantom_parameters_serde"
+    )]
+    pub phantom_parameters: Arc<BTreeMap<GridId, grid::PhantomParameters>>,
+
     /// The set of identifiers of grids whose helices are displayed with smaller spheres for the
     /// nucleotides.
     #[serde(
@@ -138,6 +163,11 @@ pub struct Design {
     )]
     pub small_spheres: Arc<HashSet<GridId>>,
 
+    /// The set of identifiers of grids whose lattice coordinates and helix numbers are labelled
+    /// in the 3D view.
+    #[serde(skip_serializing_if = "HashSet::is_empty", default)]
+    pub labelled_grids: Arc<HashSet<GridId>>,
+
     /// The set of nucleotides that must not move during physical simulations
     #[serde(skip_serializing_if = "HashSet::is_empty", default)]
     pub anchors: HashSet<Nucl>,
@@ -151,6 +181,14 @@ pub struct Design {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub group_attributes: HashMap<ensnano_organizer::GroupId, GroupAttribute>,
 
+    /// The sets of helices that are moved as single rigid objects
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub helix_bundles: BTreeMap<BundleId, HelixBundle>,
+
+    /// The kinematic joints between grids and helix bundles
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub joints: BTreeMap<JointId, Joint>,
+
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     cameras: BTreeMap<CameraId, Camera>,
 
@@ -163,6 +201,10 @@ pub struct Design {
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     pub checked_xovers: HashSet<usize>,
 
+    /// The steps of the presentation of the design, in the order in which they are displayed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presentation_steps: Vec<PresentationStep>,
+
     /// True if the colors of the scaffold's nucleotides should make a rainbow
     #[serde(default)]
     pub rainbow_scaffold: bool,
@@ -173,6 +215,9 @@ pub struct Design {
     #[serde(skip, default)]
     cached_curve: Arc<CurveCache>,
 
+    #[serde(skip, default)]
+    stable_ids_watermark: stable_ids::StableIdsWatermark,
+
     #[serde(default)]
     pub bezier_planes: BezierPlanes,
 
@@ -190,6 +235,17 @@ pub struct Design {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub clone_isometries: Option<Vec<Isometry3Descriptor>>,
+
+    /// If the design was saved as a variant of an other design, the origin of the variant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<DesignProvenance>,
+
+    #[serde(default, skip_serializing_if = "DesignMetadata::is_empty")]
+    pub metadata: DesignMetadata,
+
+    /// A small PNG image of the design, encoded in base64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thumbnail: Option<String>,
 }
 
 pub trait AdditionalStructure: Send + Sync {
@@ -319,14 +375,24 @@ pub struct Camera {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde_as(deserialize_as = "DefaultOnError")]
     pub pivot_position: Option<Vec3>,
+    /// The rendering settings to apply when going to this camera, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rendering: Option<CameraRenderingSettings>,
+}
+
+/// The way the 3D scene is rendered when going to a saved camera.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CameraRenderingSettings {
+    pub cartoon: bool,
+    pub white_background: bool,
 }
 
 pub fn ensnano_version() -> String {
     std::env!("CARGO_PKG_VERSION").to_owned()
 }
 
-fn groups_is_empty<K, V>(groups: &Arc<BTreeMap<K, V>>) -> bool {
-    groups.as_ref().is_empty()
+fn arc_map_is_empty<K, V>(map: &Arc<BTreeMap<K, V>>) -> bool {
+    map.as_ref().is_empty()
 }
 
 impl Default for Design {
@@ -371,18 +437,24 @@ impl Design {
             scaffold_shift: None,
             groups: Default::default(),
             small_spheres: Default::default(),
+            labelled_grids: Default::default(),
             no_phantoms: Default::default(),
+            phantom_parameters: Default::default(),
             anchors: Default::default(),
             organizer_tree: None,
             ensnano_version: ensnano_version(),
             group_attributes: Default::default(),
+            helix_bundles: Default::default(),
+            joints: Default::default(),
             cameras: Default::default(),
             favorite_camera: None,
             saved_camera: None,
             checked_xovers: Default::default(),
+            presentation_steps: Vec::new(),
             rainbow_scaffold: false,
             instanciated_grid_data: None,
             cached_curve: Default::default(),
+            stable_ids_watermark: Default::default(),
             bezier_planes: Default::default(),
             bezier_paths: Default::default(),
             old_grids: Vec::new(),
@@ -390,6 +462,9 @@ impl Design {
             external_3d_objects: Default::default(),
             additional_structure: None,
             clone_isometries: Some(Vec::new()),
+            provenance: None,
+            metadata: Default::default(),
+            thumbnail: None,
         }
     }
 
@@ -480,6 +555,7 @@ impl Design {
             name: format!("Camera {}", cam_id.0),
             id: cam_id,
             pivot_position,
+            rendering: None,
         };
         self.cameras.insert(cam_id, new_camera);
     }
@@ -495,6 +571,29 @@ impl Design {
         }
     }
 
+    /// Exchange the position in the list of cameras of the cameras `a` and `b`. Return false if
+    /// one of the two cameras does not exist.
+    pub fn swap_cameras(&mut self, a: CameraId, b: CameraId) -> bool {
+        if !self.cameras.contains_key(&a) || !self.cameras.contains_key(&b) {
+            return false;
+        }
+        if a == b {
+            return true;
+        }
+        let mut camera_a = self.cameras.remove(&a).unwrap();
+        let mut camera_b = self.cameras.remove(&b).unwrap();
+        camera_a.id = b;
+        camera_b.id = a;
+        self.cameras.insert(b, camera_a);
+        self.cameras.insert(a, camera_b);
+        if self.favorite_camera == Some(a) {
+            self.favorite_camera = Some(b);
+        } else if self.favorite_camera == Some(b) {
+            self.favorite_camera = Some(a);
+        }
+        true
+    }
+
     pub fn get_camera_mut(&mut self, cam_id: CameraId) -> Option<&mut Camera> {
         self.cameras.get_mut(&cam_id)
     }
@@ -510,6 +609,11 @@ impl Design {
             .or(self.saved_camera.as_ref())
     }
 
+    /// The camera that was saved with the design, if any.
+    pub fn get_saved_camera(&self) -> Option<&Camera> {
+        self.saved_camera.as_ref()
+    }
+
     pub fn get_favourite_camera_id(&self) -> Option<CameraId> {
         self.favorite_camera
     }
@@ -533,6 +637,19 @@ impl Design {
 
     pub fn prepare_for_save(&mut self, saving_information: SavingInformation) {
         self.saved_camera = saving_information.camera;
+        if let Some(provenance) = saving_information.provenance {
+            self.provenance = Some(provenance);
+        }
+        if let Some(png) = saving_information.thumbnail_png {
+            self.thumbnail = Some(base64::encode(png));
+        }
+    }
+
+    /// The PNG image of the design that was embedded in the file when it was saved
+    pub fn get_thumbnail_png(&self) -> Option<Vec<u8>> {
+        self.thumbnail
+            .as_ref()
+            .and_then(|thumbnail| base64::decode(thumbnail).ok())
     }
 
     pub fn get_nucl_position(&self, nucl: Nucl) -> Option<Vec3> {
@@ -637,6 +754,10 @@ pub struct MutStrandAndData<'a> {
 
 pub struct SavingInformation {
     pub camera: Option<Camera>,
+    /// The provenance to record in the saved design, if it is saved as a variant
+    pub provenance: Option<DesignProvenance>,
+    /// A PNG image of the design. If `None`, the previous thumbnail is kept
+    pub thumbnail_png: Option<Vec<u8>>,
 }
 
 impl Design {
@@ -680,6 +801,7 @@ impl Design {
             helices: Helices(Arc::new(helices)),
             strands: Strands(strands),
             small_spheres: Default::default(),
+            labelled_grids: Default::default(),
             scaffold_id: None, //TODO determine this value
             scaffold_sequence: None,
             scaffold_shift: None,
@@ -690,6 +812,8 @@ impl Design {
             organizer_tree: None,
             ensnano_version: ensnano_version(),
             group_attributes: Default::default(),
+            helix_bundles: Default::default(),
+            joints: Default::default(),
             cameras: Default::default(),
             ..Default::default()
         })
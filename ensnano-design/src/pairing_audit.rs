@@ -0,0 +1,101 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Verification that each nucleotide of the scaffold is paired with exactly one nucleotide of a
+//! staple.
+//!
+//! Two nucleotides are paired when they occupy complementary virtual positions, so that a staple
+//! lying on a helix that shares its support helix with the scaffold helix is taken into account.
+//! A routing mistake can leave a scaffold nucleotide without partner, or give it two partners
+//! when two staples overlap, without any visible sign in the views.
+
+use crate::{Design, Domain, Nucl, VirtualNucl};
+use std::collections::HashMap;
+
+/// The way in which a scaffold nucleotide is wrongly paired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairingDefect {
+    /// No staple nucleotide is at the complementary position
+    Unpaired,
+    /// Several staple nucleotides are at the complementary position
+    MultiplyPaired(Vec<Nucl>),
+}
+
+/// A scaffold nucleotide that is not paired with exactly one staple nucleotide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingIssue {
+    pub scaffold_nucl: Nucl,
+    pub defect: PairingDefect,
+}
+
+/// List the nucleotides of the scaffold of `design` that are not paired with exactly one staple
+/// nucleotide, in the order in which they appear on the scaffold.
+///
+/// Return `None` if the design has no scaffold.
+pub fn audit_scaffold_pairing(design: &Design) -> Option<Vec<PairingIssue>> {
+    let scaffold_id = design.scaffold_id?;
+    let scaffold = design.strands.get(&scaffold_id)?;
+
+    // Unlike the map used to draw the hydrogen bonds, this map keeps all the nucleotides that
+    // occupy a virtual position.
+    let mut virtual_nucl_map: HashMap<VirtualNucl, Vec<Nucl>> = HashMap::new();
+    for (s_id, strand) in design.strands.iter() {
+        if *s_id == scaffold_id {
+            continue;
+        }
+        for nucl in helix_nucls(&strand.domains) {
+            if let Some(virtual_nucl) = Nucl::map_to_virtual_nucl(nucl, &design.helices) {
+                virtual_nucl_map.entry(virtual_nucl).or_default().push(nucl);
+            }
+        }
+    }
+
+    let mut ret = Vec::new();
+    for scaffold_nucl in helix_nucls(&scaffold.domains) {
+        let partners = Nucl::map_to_virtual_nucl(scaffold_nucl, &design.helices)
+            .and_then(|virtual_nucl| virtual_nucl_map.get(&virtual_nucl.compl()));
+        let defect = match partners.map(Vec::as_slice) {
+            None | Some([]) => PairingDefect::Unpaired,
+            Some([_]) => continue,
+            Some(partners) => PairingDefect::MultiplyPaired(partners.to_vec()),
+        };
+        ret.push(PairingIssue {
+            scaffold_nucl,
+            defect,
+        });
+    }
+    Some(ret)
+}
+
+fn helix_nucls(domains: &[Domain]) -> impl Iterator<Item = Nucl> + '_ {
+    domains
+        .iter()
+        .filter_map(|d| {
+            if let Domain::HelixDomain(interval) = d {
+                Some(interval)
+            } else {
+                None
+            }
+        })
+        .flat_map(|interval| {
+            interval.iter().map(move |position| Nucl {
+                helix: interval.helix,
+                position,
+                forward: interval.forward,
+            })
+        })
+}
@@ -0,0 +1,33 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::Nucl;
+use ultraviolet::{Rotor3, Vec3};
+
+/// A named step of a presentation. Each step records a camera position and the set of
+/// nucleotides that are hidden while the step is being displayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresentationStep {
+    pub name: String,
+    pub camera_position: Vec3,
+    pub camera_orientation: Rotor3,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot_position: Option<Vec3>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_nucls: Vec<Nucl>,
+}
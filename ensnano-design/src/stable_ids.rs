@@ -0,0 +1,122 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Stable identifiers of strands and helices.
+//!
+//! The keys of strands and helices in a design change when elements are added, removed or merged.
+//! To allow external data (e.g. a spreadsheet of staples) to refer to the elements of a design,
+//! strands and helices are given a UUID that is kept through edits, saves and loads.
+
+use super::{Design, HelixCollection};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// The smallest keys of the strands and helices that may not have a stable identifier yet.
+///
+/// New strands and helices are always given a key larger than the existing ones, so the elements
+/// below the watermark already went through [Design::assign_stable_ids] or
+/// [Design::assign_new_stable_ids].
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct StableIdsWatermark {
+    strands: usize,
+    helices: usize,
+}
+
+impl Design {
+    /// Give a new UUID to each strand and helix that has none, or whose UUID is already used by
+    /// an element of the same kind with a smaller key.
+    ///
+    /// Return the number of UUIDs that were assigned.
+    pub fn assign_stable_ids(&mut self) -> usize {
+        let strands_to_update = ids_to_assign(self.strands.iter().map(|(id, s)| (*id, s.uuid)));
+        for s_id in strands_to_update.iter() {
+            if let Some(strand) = self.strands.get_mut(s_id) {
+                strand.uuid = Some(Uuid::new_v4());
+            }
+        }
+
+        let helices_to_update = ids_to_assign(self.helices.iter().map(|(id, h)| (*id, h.uuid)));
+        if !helices_to_update.is_empty() {
+            let mut helices_mut = self.helices.make_mut();
+            for h_id in helices_to_update.iter() {
+                if let Some(helix) = helices_mut.get_mut(h_id) {
+                    helix.uuid = Some(Uuid::new_v4());
+                }
+            }
+        }
+        self.update_stable_ids_watermark();
+        strands_to_update.len() + helices_to_update.len()
+    }
+
+    /// Give a new UUID to the strands and helices that were added since the last call to this
+    /// method or to [Self::assign_stable_ids].
+    ///
+    /// Only the elements above the watermark are looked at, so this can be called after each
+    /// operation regardless of the size of the design. Return the number of UUIDs that were
+    /// assigned.
+    pub fn assign_new_stable_ids(&mut self) -> usize {
+        let watermark = self.stable_ids_watermark;
+        let strands_to_update: Vec<usize> = self
+            .strands
+            .0
+            .range(watermark.strands..)
+            .filter(|(_, s)| s.uuid.is_none())
+            .map(|(id, _)| *id)
+            .collect();
+        for s_id in strands_to_update.iter() {
+            if let Some(strand) = self.strands.get_mut(s_id) {
+                strand.uuid = Some(Uuid::new_v4());
+            }
+        }
+
+        let helices_to_update: Vec<usize> = self
+            .helices
+            .0
+            .range(watermark.helices..)
+            .filter(|(_, h)| h.uuid.is_none())
+            .map(|(id, _)| *id)
+            .collect();
+        if !helices_to_update.is_empty() {
+            let mut helices_mut = self.helices.make_mut();
+            for h_id in helices_to_update.iter() {
+                if let Some(helix) = helices_mut.get_mut(h_id) {
+                    helix.uuid = Some(Uuid::new_v4());
+                }
+            }
+        }
+        self.update_stable_ids_watermark();
+        strands_to_update.len() + helices_to_update.len()
+    }
+
+    fn update_stable_ids_watermark(&mut self) {
+        self.stable_ids_watermark = StableIdsWatermark {
+            strands: self.strands.0.keys().next_back().map_or(0, |k| k + 1),
+            helices: self.helices.0.keys().next_back().map_or(0, |k| k + 1),
+        };
+    }
+}
+
+/// Return the keys of the elements that must receive a new UUID, either because they have none
+/// or because their UUID is a duplicate.
+fn ids_to_assign(elements: impl Iterator<Item = (usize, Option<Uuid>)>) -> Vec<usize> {
+    let mut used = HashSet::new();
+    elements
+        .filter(|(_, uuid)| uuid.map_or(true, |uuid| !used.insert(uuid)))
+        .map(|(id, _)| id)
+        .collect()
+}
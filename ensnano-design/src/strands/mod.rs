@@ -22,6 +22,7 @@ use super::{codenano, Helices, HelixCollection, Nucl, VirtualNucl};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use uuid::Uuid;
 mod formating;
 
 extern crate serde_hex;
@@ -31,8 +32,11 @@ use serde_hex::{SerHex, StrictPfx};
 ///
 /// It contains all the information about the "topology of the design".  Information about
 /// cross-over or helix interval are obtained via this structure
+///
+/// Strands are stored behind `Arc`s and copied on write, so that a clone of the collection shares
+/// the strands that are not modified afterwards.
 #[derive(Serialize, Deserialize, Clone, Default)]
-pub struct Strands(pub(super) BTreeMap<usize, Strand>);
+pub struct Strands(pub(super) BTreeMap<usize, Arc<Strand>>);
 
 impl Strands {
     pub fn get_xovers(&self) -> Vec<(Nucl, Nucl)> {
@@ -72,7 +76,9 @@ impl Strands {
 
     pub fn remove_empty_domains(&mut self) {
         for s in self.0.values_mut() {
-            s.remove_empty_domains()
+            if s.domains.iter().any(|d| d.length() == 0) {
+                Arc::make_mut(s).remove_empty_domains()
+            }
         }
     }
 
@@ -164,19 +170,19 @@ impl Strands {
     }
 
     pub fn get(&self, id: &usize) -> Option<&Strand> {
-        self.0.get(id)
+        self.0.get(id).map(Arc::as_ref)
     }
 
     pub fn get_mut(&mut self, id: &usize) -> Option<&mut Strand> {
-        self.0.get_mut(id)
+        self.0.get_mut(id).map(Arc::make_mut)
     }
 
     pub fn insert(&mut self, key: usize, strand: Strand) -> Option<Strand> {
-        self.0.insert(key, strand)
+        self.0.insert(key, Arc::new(strand)).map(unwrap_or_clone)
     }
 
     pub fn remove(&mut self, key: &usize) -> Option<Strand> {
-        self.0.remove(key)
+        self.0.remove(key).map(unwrap_or_clone)
     }
 
     pub fn keys(&self) -> impl Iterator<Item = &usize> {
@@ -184,28 +190,32 @@ impl Strands {
     }
 
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (&usize, &mut Strand)> {
-        self.0.iter_mut()
+        self.0.iter_mut().map(|(id, s)| (id, Arc::make_mut(s)))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&usize, &Strand)> {
-        self.0.iter()
+        self.0.iter().map(|(id, s)| (id, s.as_ref()))
     }
 
     pub fn values(&self) -> impl Iterator<Item = &Strand> {
-        self.0.values()
+        self.0.values().map(Arc::as_ref)
     }
 
     pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Strand> {
-        self.0.values_mut()
+        self.0.values_mut().map(Arc::make_mut)
     }
 
     pub fn push(&mut self, strand: Strand) {
         let id = self.0.keys().max().map(|m| m + 1).unwrap_or(0);
-        self.0.insert(id, strand);
+        self.0.insert(id, Arc::new(strand));
     }
     //============================================================================================
 }
 
+fn unwrap_or_clone(strand: Arc<Strand>) -> Strand {
+    Arc::try_unwrap(strand).unwrap_or_else(|s| s.as_ref().clone())
+}
+
 /// A link between a 5' and a 3' domain.
 ///
 /// For any non cyclic strand, the last domain juction must be DomainJunction::Prime3. For a cyclic
@@ -233,7 +243,7 @@ fn is_false(x: &bool) -> bool {
 }
 
 /// A DNA strand. Strands are represented as sequences of `Domains`.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Strand {
     /// The (ordered) vector of domains, where each domain is a
     /// directed interval of a helix.
@@ -259,6 +269,31 @@ pub struct Strand {
     /// will be given a name corresponding to the position of its 5' nucleotide
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub name: Option<Cow<'static, str>>,
+    /// An identifier that is preserved when the design is saved, loaded or modified, so that
+    /// external data keyed on staples stays valid. Unlike the key of the strand in the design,
+    /// it is kept when other strands are added or removed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub uuid: Option<Uuid>,
+    /// The oligo pool in which the strand is mixed. It is exported with the staples to prepare
+    /// the mixing plan.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub pool: Option<OligoPool>,
+    /// A free text note about the strand. It is exported with the staples.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+    /// A link to an external description of the strand, e.g. an entry of a sequence database.
+    /// It is exported with the staples.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub url: Option<String>,
+}
+
+/// The pool in which a staple is mixed before folding.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct OligoPool {
+    pub name: String,
+    /// The concentration of the staple in the pool, in nanomolars
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub concentration: Option<f32>,
 }
 
 struct InsertionAccumulator {
@@ -736,7 +771,7 @@ impl Strand {
 
 /// A domain can be either an interval of nucleotides on an helix, or an "Insertion" that is a set
 /// of nucleotides that are not on an helix and form an independent loop.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub enum Domain {
     /// An interval of nucleotides on an helix
     HelixDomain(HelixInterval),
@@ -752,7 +787,7 @@ pub enum Domain {
     },
 }
 
-#[derive(Default, Clone, Serialize, Deserialize)]
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HelixInterval {
     /// Index of the helix in the array of helices. Indices start at
     /// 0.
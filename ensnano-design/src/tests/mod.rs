@@ -642,8 +642,859 @@ fn formated_sane_strand_with_insertion() -> &'static str {
     "[H1: 0 -> 3] [@8] [H1: 4 -> 7] [@5] [H2: 0 <- 7]"
 }
 
+/// A design with two parallel helices, helix 1 lying next to helix 0 along the y axis.
+fn two_helices_design() -> Design {
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        helices.insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+        helices.insert(
+            1,
+            Helix::new(
+                HelixParameters::INTER_CENTER_GAP * Vec3::unit_y(),
+                Rotor3::identity(),
+            ),
+        );
+    }
+    design
+}
+
+fn helix_interval(helix: usize, start: isize, end: isize, forward: bool) -> Domain {
+    Domain::HelixDomain(HelixInterval {
+        helix,
+        start,
+        end,
+        forward,
+        sequence: None,
+    })
+}
+
+/// A strand made of `domains`, with the junctions between them.
+fn strand_with_domains(domains: Vec<Domain>) -> Strand {
+    let mut strand = Strand::init(0, 0, true, 0xFF0000);
+    strand.junctions = read_junctions(&domains, false);
+    strand.domains = domains;
+    strand
+}
+
 #[test]
 fn check_formated_strand_with_insertion() {
     let strand = strand_with_insertion();
     assert_good_strand(&strand, formated_strand_with_insertion())
 }
+
+#[test]
+fn phantom_parameters_survive_serialization() {
+    use grid::{PhantomDirection, PhantomParameters};
+    let mut design = Design::new();
+    let parameters = PhantomParameters {
+        length: 42,
+        direction: PhantomDirection::Left,
+        opacity: 0.5,
+    };
+    Arc::make_mut(&mut design.phantom_parameters).insert(GridId::FreeGrid(3), parameters);
+    let json = serde_json::to_string(&design).expect("Could not serialize design");
+    let design: Design = serde_json::from_str(&json).expect("Could not parse design");
+    assert_eq!(
+        design.phantom_parameters.get(&GridId::FreeGrid(3)),
+        Some(&parameters)
+    );
+    assert_eq!(parameters.range(), -42..=0);
+}
+
+#[test]
+fn diff_reports_strand_changes() {
+    use diff::DesignDiff;
+    let mut old = Design::new();
+    old.strands.insert(0, Strand::init(0, 0, true, 0xFF0000));
+    old.strands.insert(1, Strand::init(1, 0, true, 0x00FF00));
+    let mut new = old.clone();
+    new.strands.remove(&0);
+    new.strands.get_mut(&1).unwrap().color = 0x0000FF;
+    new.strands.insert(2, Strand::init(2, 0, true, 0x00FF00));
+
+    let diff = DesignDiff::new(&old, &new);
+    assert_eq!(diff.strands.removed.iter().collect::<Vec<_>>(), vec![&0]);
+    assert_eq!(diff.strands.modified.iter().collect::<Vec<_>>(), vec![&1]);
+    assert_eq!(diff.strands.added.iter().collect::<Vec<_>>(), vec![&2]);
+    assert!(diff.helices.is_empty());
+    assert!(DesignDiff::new(&new, &new).is_empty());
+}
+
+#[test]
+fn stable_ids_are_kept_and_deduplicated() {
+    let mut design = Design::new();
+    design.strands.insert(0, Strand::init(0, 0, true, 0xFF0000));
+    design.strands.insert(1, Strand::init(1, 0, true, 0x00FF00));
+    assert_eq!(design.assign_stable_ids(), 2);
+    assert_eq!(design.assign_stable_ids(), 0);
+
+    let uuid_0 = design.strands.get(&0).unwrap().uuid;
+    let copy = design.strands.get(&0).unwrap().clone();
+    design.strands.insert(2, copy);
+    assert_eq!(design.assign_stable_ids(), 1);
+    assert_eq!(design.strands.get(&0).unwrap().uuid, uuid_0);
+    assert_ne!(design.strands.get(&2).unwrap().uuid, uuid_0);
+
+    let json = serde_json::to_string(&design).unwrap();
+    let loaded: Design = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.strands.get(&0).unwrap().uuid, uuid_0);
+}
+
+#[test]
+fn new_stable_ids_are_only_given_to_added_elements() {
+    let mut design = Design::new();
+    design.strands.insert(0, Strand::init(0, 0, true, 0xFF0000));
+    design.strands.insert(1, Strand::init(1, 0, true, 0x00FF00));
+    assert_eq!(design.assign_new_stable_ids(), 2);
+    let uuid_1 = design.strands.get(&1).unwrap().uuid;
+
+    design.strands.push(Strand::init(2, 0, true, 0x0000FF));
+    assert_eq!(design.assign_new_stable_ids(), 1);
+    assert!(design.strands.get(&2).unwrap().uuid.is_some());
+    assert_eq!(design.strands.get(&1).unwrap().uuid, uuid_1);
+    assert_eq!(design.assign_new_stable_ids(), 0);
+}
+
+#[test]
+fn xover_respacing_moves_xovers_to_shorter_positions() {
+    use design_operations::{plan_xover_respacing, respace_xovers};
+    let parameters = HelixParameters::default();
+    let mut design = two_helices_design();
+    let helix_0 = design.helices.get(&0).unwrap().clone();
+    let helix_1 = design.helices.get(&1).unwrap().clone();
+    let length = |position: isize| {
+        (helix_0.space_pos(&parameters, position, true)
+            - helix_1.space_pos(&parameters, position, false))
+        .mag()
+    };
+    let best = (10..=20)
+        .min_by(|a, b| length(*a).partial_cmp(&length(*b)).unwrap())
+        .unwrap();
+    let shortest_length = length(best);
+    let current = best + 1;
+
+    design.strands.insert(
+        0,
+        strand_with_domains(vec![
+            helix_interval(0, best - 4, current + 1, true),
+            helix_interval(1, best - 4, current + 1, false),
+        ]),
+    );
+    let xover = design.strands.get(&0).unwrap().xovers()[0];
+
+    let moves = plan_xover_respacing(&design, &[xover]);
+    assert_eq!(moves.len(), 1);
+    let respacing = moves[0];
+    assert_eq!(respacing.from, xover);
+    assert_ne!(respacing.shift(), 0);
+    assert!(length(respacing.to.0.position) <= shortest_length + 0.1);
+
+    respace_xovers(&mut design, &moves).unwrap();
+    let strand = design.strands.get(&0).unwrap();
+    assert_eq!(strand.xovers(), vec![respacing.to]);
+    assert_eq!(
+        strand.length(),
+        (2 * (current + 1 - (best - 4) + respacing.shift())) as usize
+    );
+}
+
+#[test]
+fn insertions_bend_helices_towards_the_center_of_curvature() {
+    use design_operations::{apply_insertions, plan_insertions, InsertionPlacementTarget};
+    let mut design = two_helices_design();
+    for (s_id, (helix, forward)) in [(0, true), (0, false), (1, true), (1, false)]
+        .iter()
+        .enumerate()
+    {
+        let strand = strand_with_domains(vec![helix_interval(*helix, 0, 64, *forward)]);
+        design.strands.insert(s_id, strand);
+    }
+
+    let insertions = plan_insertions(
+        &design,
+        &[0, 1],
+        InsertionPlacementTarget::Curvature {
+            radius: 50.,
+            direction: 0.,
+        },
+    );
+    let count = |helix: usize, length: isize| {
+        insertions
+            .iter()
+            .filter(|i| i.helix == helix && i.length == length)
+            .count()
+    };
+    assert!(count(0, 1) > 0);
+    assert_eq!(count(0, 1), count(1, -1));
+    assert_eq!(count(0, 1) + count(1, -1), insertions.len());
+
+    apply_insertions(&mut design, &insertions).unwrap();
+    for s_id in 0..2 {
+        let strand = design.strands.get(&s_id).unwrap();
+        assert_eq!(strand.length(), 64 + count(0, 1));
+        assert_eq!(strand.junctions.len(), strand.domains.len());
+    }
+    for s_id in 2..4 {
+        let strand = design.strands.get(&s_id).unwrap();
+        assert_eq!(strand.length(), 64 - count(1, -1));
+        assert_eq!(strand.junctions.len(), strand.domains.len());
+    }
+}
+
+#[test]
+fn oligo_pool_is_saved_only_when_set() {
+    let mut strand = Strand::init(0, 0, true, 0xFF0000);
+    let json = serde_json::to_string(&strand).unwrap();
+    assert!(!json.contains("pool"));
+
+    strand.pool = Some(OligoPool {
+        name: String::from("Edges"),
+        concentration: Some(500.),
+    });
+    let json = serde_json::to_string(&strand).unwrap();
+    let loaded: Strand = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.pool, strand.pool);
+}
+
+#[test]
+fn heat_maps_locate_nicks_and_short_staples() {
+    use heat_map::{heat_map, HeatMapKind, HEAT_MAP_WINDOW};
+    let mut design = Design::new();
+    design
+        .helices
+        .make_mut()
+        .insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+    for (s_id, (start, end)) in [(0, 40), (40, 48), (48, 100)].iter().enumerate() {
+        let mut strand = Strand::init(0, *start, true, 0xFF0000);
+        strand.domains = vec![Domain::HelixDomain(HelixInterval {
+            helix: 0,
+            start: *start,
+            end: *end,
+            forward: true,
+            sequence: None,
+        })];
+        design.strands.insert(s_id, strand);
+    }
+
+    let value_at = |values: &[(isize, f32)], position: isize| {
+        values.iter().find(|(p, _)| *p == position).unwrap().1
+    };
+
+    let nicks = heat_map(&design, HeatMapKind::NickDensity);
+    let nicks = nicks.get(&0).unwrap();
+    assert_eq!(nicks.len(), 100);
+    assert_eq!(value_at(nicks, 44), 1.);
+    assert_eq!(value_at(nicks, 20), 0.);
+    assert!(value_at(nicks, 40 - HEAT_MAP_WINDOW) > 0.);
+
+    let seeds = heat_map(&design, HeatMapKind::SeedStrength);
+    let seeds = seeds.get(&0).unwrap();
+    assert_eq!(value_at(seeds, 60), 1.);
+    assert!(value_at(seeds, 44) < value_at(seeds, 20));
+}
+
+#[test]
+fn ideal_roll_shortens_xovers() {
+    use design_operations::ideal_roll;
+    let parameters = HelixParameters::default();
+    let mut design = two_helices_design();
+    design
+        .helices
+        .make_mut()
+        .insert(2, Helix::new(-10. * Vec3::unit_z(), Rotor3::identity()));
+    assert_eq!(ideal_roll(&design, 0), None);
+
+    design.strands.insert(
+        0,
+        strand_with_domains(vec![
+            helix_interval(0, 0, 16, true),
+            helix_interval(1, 0, 16, false),
+        ]),
+    );
+    let xover_length = |roll: f32| {
+        let mut helix_0 = design.helices.get(&0).unwrap().clone();
+        helix_0.set_roll(roll);
+        let helix_1 = design.helices.get(&1).unwrap();
+        (helix_0.space_pos(&parameters, 15, true) - helix_1.space_pos(&parameters, 15, false)).mag()
+    };
+
+    let roll = ideal_roll(&design, 0).unwrap();
+    assert!((-std::f32::consts::PI..std::f32::consts::PI).contains(&roll));
+    for i in 0..36 {
+        let other_roll = (i * 10) as f32 * std::f32::consts::PI / 180.;
+        assert!(xover_length(roll) <= xover_length(other_roll) + 1e-3);
+    }
+    assert_eq!(ideal_roll(&design, 2), None);
+}
+
+#[test]
+fn strand_cleanup_finds_short_and_unpaired_strands() {
+    use design_operations::{plan_strand_cleanup, CleanupReason};
+    let interval = |start: isize, end: isize, forward: bool| {
+        Domain::HelixDomain(HelixInterval {
+            helix: 0,
+            start,
+            end,
+            forward,
+            sequence: None,
+        })
+    };
+    let mut design = Design::new();
+    let mut scaffold = Strand::init(0, 0, true, 0xFF0000);
+    scaffold.domains = vec![interval(0, 40, true)];
+    let mut paired = Strand::init(0, 0, false, 0x00FF00);
+    paired.domains = vec![interval(0, 20, false)];
+    let short = Strand::init(0, 30, false, 0x00FF00);
+    let mut unpaired = Strand::init(0, 50, true, 0x00FF00);
+    unpaired.domains = vec![interval(50, 70, true)];
+    let mut empty = Strand::init(0, 0, true, 0x00FF00);
+    empty.domains = vec![];
+    design.strands.insert(0, scaffold);
+    design.strands.insert(1, paired);
+    design.strands.insert(2, short);
+    design.strands.insert(3, unpaired);
+    design.strands.insert(4, empty);
+    design.scaffold_id = Some(0);
+
+    let cleanup: Vec<_> = plan_strand_cleanup(&design, 7)
+        .into_iter()
+        .map(|c| (c.strand_id, c.reason))
+        .collect();
+    assert_eq!(
+        cleanup,
+        vec![
+            (2, CleanupReason::Short),
+            (3, CleanupReason::Unpaired),
+            (4, CleanupReason::Empty)
+        ]
+    );
+}
+
+#[test]
+fn helix_numbering_follows_grid_rows() {
+    use design_operations::{helix_display_numbers, HelixNumbering};
+    use grid::{GridId, HelixGridPosition};
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        // Helices 0 to 3 are on a 2x2 square of the grid, helix 4 is not on a grid
+        for (h_id, (x, y)) in [(1, 0), (0, 0), (0, 1), (1, 1)].iter().enumerate() {
+            let mut helix = Helix::new(Vec3::zero(), Rotor3::identity());
+            helix.grid_position = Some(HelixGridPosition {
+                grid: GridId::FreeGrid(0),
+                x: *x,
+                y: *y,
+                axis_pos: 0,
+                roll: 0.,
+            });
+            helices.insert(h_id, helix);
+        }
+        helices.insert(4, Helix::new(Vec3::zero(), Rotor3::identity()));
+    }
+
+    let row_major = helix_display_numbers(&design, HelixNumbering::RowMajor);
+    let expected: Vec<_> = [1, 0, 2, 3, 4].iter().map(|n| Some(*n)).collect();
+    assert_eq!(row_major.values().cloned().collect::<Vec<_>>(), expected);
+
+    let serpentine = helix_display_numbers(&design, HelixNumbering::Serpentine);
+    let expected: Vec<_> = [1, 0, 3, 2, 4].iter().map(|n| Some(*n)).collect();
+    assert_eq!(serpentine.values().cloned().collect::<Vec<_>>(), expected);
+
+    let identifiers = helix_display_numbers(&design, HelixNumbering::Identifiers);
+    assert!(identifiers.values().all(Option::is_none));
+}
+
+#[test]
+fn helix_bundles_move_as_rigid_objects() {
+    use design_operations::{create_helix_bundle, set_helix_bundle_pose};
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        helices.insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+        helices.insert(1, Helix::new(Vec3::new(0., 0., 2.), Rotor3::identity()));
+        helices.insert(2, Helix::new(Vec3::new(0., 0., 4.), Rotor3::identity()));
+    }
+    let first = create_helix_bundle(&mut design, "first".into(), vec![0, 1]).unwrap();
+    assert_eq!(
+        design.helix_bundles[&first].pivot.position,
+        Vec3::new(0., 0., 1.)
+    );
+
+    // Helix 1 leaves the first bundle, helix 42 does not exist
+    let second = create_helix_bundle(&mut design, "second".into(), vec![1, 2, 42]).unwrap();
+    assert_ne!(first, second);
+    assert_eq!(bundle_of_helix(&design.helix_bundles, 1), Some(second));
+    assert_eq!(
+        design.helix_bundles[&second]
+            .helices
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![&1, &2]
+    );
+    assert!(create_helix_bundle(&mut design, "empty".into(), vec![42]).is_err());
+
+    let orientation = Rotor3::from_rotation_xz(std::f32::consts::FRAC_PI_2);
+    set_helix_bundle_pose(&mut design, second, Vec3::new(10., 0., 0.), orientation).unwrap();
+    let h1 = design.helices.get(&1).unwrap().position;
+    let h2 = design.helices.get(&2).unwrap().position;
+    assert!(((h1 - h2).mag() - 2.).abs() < 1e-5);
+    assert!(((h1 + h2) / 2. - Vec3::new(10., 0., 0.)).mag() < 1e-5);
+    assert_eq!(design.helices.get(&0).unwrap().position, Vec3::zero());
+}
+
+#[test]
+fn deleted_helices_leave_their_bundles() {
+    use design_operations::create_helix_bundle;
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        helices.insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+        helices.insert(1, Helix::new(Vec3::new(0., 0., 2.), Rotor3::identity()));
+        helices.insert(2, Helix::new(Vec3::new(0., 0., 4.), Rotor3::identity()));
+    }
+    let first = create_helix_bundle(&mut design, "first".into(), vec![0, 1]).unwrap();
+    let second = create_helix_bundle(&mut design, "second".into(), vec![2]).unwrap();
+
+    let removed = remove_helices_from_bundles(&mut design.helix_bundles, &[1, 2].into());
+    assert_eq!(removed, vec![second]);
+    assert_eq!(
+        design.helix_bundles.keys().collect::<Vec<_>>(),
+        vec![&first]
+    );
+    assert_eq!(bundle_of_helix(&design.helix_bundles, 1), None);
+    assert_eq!(bundle_of_helix(&design.helix_bundles, 0), Some(first));
+}
+
+#[test]
+fn joints_follow_their_bodies() {
+    use design_operations::{add_joint, create_helix_bundle, delete_helix_bundle};
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        helices.insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+        helices.insert(1, Helix::new(Vec3::new(0., 0., 4.), Rotor3::identity()));
+    }
+    let first = create_helix_bundle(&mut design, "first".into(), vec![0]).unwrap();
+    let second = create_helix_bundle(&mut design, "second".into(), vec![1]).unwrap();
+    let bodies = (JointBody::Bundle(first), JointBody::Bundle(second));
+    assert!(add_joint(&mut design, (bodies.0, bodies.0), JointKind::Ball).is_err());
+    let joint_id = add_joint(&mut design, bodies, JointKind::Hinge).unwrap();
+
+    // Without cross-overs, the anchor is between the two bodies
+    let (anchor, _) = design.joints[&joint_id]
+        .world_anchor_and_axis(&design)
+        .unwrap();
+    assert!((anchor - Vec3::new(0., 0., 2.)).mag() < 1e-5);
+
+    design_operations::translate_helices(&mut design, false, vec![0], Vec3::unit_y()).unwrap();
+    let (anchor, _) = design.joints[&joint_id]
+        .world_anchor_and_axis(&design)
+        .unwrap();
+    assert!((anchor - Vec3::new(0., 1., 2.)).mag() < 1e-5);
+
+    delete_helix_bundle(&mut design, second).unwrap();
+    assert!(design.joints.is_empty());
+
+    // A bundle that loses all its helices disappears with its joints
+    let third = create_helix_bundle(&mut design, "third".into(), vec![1]).unwrap();
+    add_joint(
+        &mut design,
+        (JointBody::Bundle(first), JointBody::Bundle(third)),
+        JointKind::Ball,
+    )
+    .unwrap();
+    create_helix_bundle(&mut design, "fourth".into(), vec![0]).unwrap();
+    assert!(!design.helix_bundles.contains_key(&first));
+    assert!(design.joints.is_empty());
+}
+
+#[test]
+fn xover_strain_is_relaxed_by_skips() {
+    use grid::{FreeGrids, GridDescriptor, GridId, GridTypeDescr, HelixGridPosition};
+    use xover_strain::{helix_strains, xover_strains};
+    let mut design = Design::new();
+    design.helix_parameters = Some(HelixParameters {
+        bases_per_turn: 10.5,
+        ..Default::default()
+    });
+    design.free_grids = FreeGrids::from_vec(vec![GridDescriptor {
+        position: Vec3::zero(),
+        orientation: Rotor3::identity(),
+        helix_parameters: None,
+        grid_type: GridTypeDescr::Square { twist: None },
+        invisible: false,
+        bezier_vertex: None,
+    }]);
+    {
+        let mut helices = design.helices.make_mut();
+        for h_id in 0..2 {
+            let mut helix = Helix::new(Vec3::zero(), Rotor3::identity());
+            helix.grid_position = Some(HelixGridPosition {
+                grid: GridId::FreeGrid(0),
+                x: h_id as isize,
+                y: 0,
+                axis_pos: 0,
+                roll: 0.,
+            });
+            helices.insert(h_id, helix);
+        }
+    }
+    // Two cross-overs between the helices, 64 positions apart. On a square lattice, 64
+    // positions make 6 turns while 64 nucleotides make 6 turns and one nucleotide.
+    let strands = vec![
+        vec![
+            helix_interval(0, 0, 8, true),
+            helix_interval(1, 0, 8, false),
+        ],
+        vec![
+            helix_interval(1, 64, 72, true),
+            helix_interval(0, 64, 72, false),
+        ],
+        vec![
+            helix_interval(0, 8, 40, true),
+            helix_interval(0, 41, 64, true),
+        ],
+    ];
+    for (s_id, domains) in strands.into_iter().enumerate() {
+        design.strands.insert(s_id, strand_with_domains(domains));
+    }
+
+    let strains = helix_strains(&design);
+    assert_eq!(strains.len(), 2);
+    // The skip at position 40 relaxes helix 0
+    assert!(strains[0].twist_mismatch.abs() < 1e-3);
+    assert_eq!(strains[0].suggested_skips, 0);
+    assert!((strains[1].twist_mismatch - 360. / 10.5).abs() < 1e-3);
+    assert_eq!(strains[1].suggested_skips, 1);
+
+    let xovers = xover_strains(&design);
+    assert_eq!(xovers.len(), 2);
+    for xover in xovers {
+        assert!((xover.strain - 180. / 10.5).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn nicks_are_moved_to_strengthen_seed_domains() {
+    use design_operations::{move_nicks, plan_nick_optimization};
+    let mut design = Design::new();
+    design
+        .helices
+        .make_mut()
+        .insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+    for (s_id, (start, end)) in [(0, 20), (20, 40)].iter().enumerate() {
+        let mut strand = Strand::init(0, *start, true, 0xFF0000);
+        strand.domains = vec![Domain::HelixDomain(HelixInterval {
+            helix: 0,
+            start: *start,
+            end: *end,
+            forward: true,
+            sequence: None,
+        })];
+        design.strands.insert(s_id, strand);
+    }
+    // The first staple is AT only while the second one is GC rich
+    let basis_map: HashMap<Nucl, char> = (0..40)
+        .map(|position| {
+            let basis = if (20..24).contains(&position) || position >= 30 {
+                'G'
+            } else {
+                'A'
+            };
+            (Nucl::new(0, position, true), basis)
+        })
+        .collect();
+
+    let moves = plan_nick_optimization(&design, &basis_map, &[0, 1]);
+    assert_eq!(moves.len(), 1);
+    let nick_move = moves[0];
+    assert_eq!(nick_move.from, Nucl::new(0, 19, true));
+    assert_eq!(nick_move.shift, 4);
+    assert!(nick_move.seed_tm_after > nick_move.seed_tm_before);
+
+    move_nicks(&mut design, &moves).unwrap();
+    assert_eq!(
+        design.strands.get(&0).unwrap().get_3prime(),
+        Some(Nucl::new(0, 23, true))
+    );
+    assert_eq!(
+        design.strands.get(&1).unwrap().get_5prime(),
+        Some(Nucl::new(0, 24, true))
+    );
+    assert!(move_nicks(&mut design, &moves).is_err());
+}
+
+#[test]
+fn random_sequences_respect_pairing_and_constraints() {
+    use design_operations::{
+        plan_random_sequences, set_domain_sequences, RandomSequenceParameters,
+    };
+    let mut design = Design::new();
+    for (s_id, (helix, forward)) in [(0, true), (0, false), (1, true)].iter().enumerate() {
+        let mut strand = Strand::init(*helix, 0, *forward, 0xFF0000);
+        strand.domains = vec![Domain::HelixDomain(HelixInterval {
+            helix: *helix,
+            start: 0,
+            end: 30,
+            forward: *forward,
+            sequence: None,
+        })];
+        design.strands.insert(s_id, strand);
+    }
+    let parameters = RandomSequenceParameters {
+        forbidden_motifs: vec![String::from("GAATTC")],
+        ..Default::default()
+    };
+
+    let sequences = plan_random_sequences(&design, &HashMap::new(), &[0, 1, 2], &parameters, 0);
+    assert_eq!(sequences.len(), 3);
+    set_domain_sequences(&mut design, &sequences).unwrap();
+
+    let sequence = |s_id: usize| match &design.strands.get(&s_id).unwrap().domains[0] {
+        Domain::HelixDomain(interval) => interval.sequence.as_ref().unwrap().to_string(),
+        _ => unreachable!(),
+    };
+    let forward: Vec<char> = sequence(0).chars().collect();
+    let backward: Vec<char> = sequence(1).chars().rev().collect();
+    for (a, b) in forward.iter().zip(backward.iter()) {
+        let pair: String = [*a, *b].iter().collect();
+        assert!(["AT", "TA", "GC", "CG"].contains(&pair.as_str()));
+    }
+    let random = sequence(2);
+    assert_eq!(random.len(), 30);
+    for basis in ['A', 'C', 'G', 'T'] {
+        assert!(!random.contains(&basis.to_string().repeat(4)));
+    }
+    assert!(!random.contains("GAATTC"));
+}
+
+#[test]
+fn scaffold_sequence_is_assigned_to_selected_domains_only() {
+    use design_operations::{assign_scaffold_sequence_to_domains, clear_sequences};
+    let interval = |helix: usize, forward: bool, sequence: Option<&str>| {
+        Domain::HelixDomain(HelixInterval {
+            helix,
+            start: 0,
+            end: 4,
+            forward,
+            sequence: sequence.map(|s| std::borrow::Cow::Owned(s.to_string())),
+        })
+    };
+    let mut design = Design::new();
+    let mut scaffold = Strand::init(0, 0, true, 0xFF0000);
+    scaffold.domains = vec![interval(0, true, None), interval(1, true, None)];
+    design.strands.insert(0, scaffold);
+    for (s_id, helix) in [(1, 0), (2, 1)] {
+        let mut staple = Strand::init(helix, 0, false, 0xFF0000);
+        staple.domains = vec![interval(helix, false, Some("GGGG"))];
+        design.strands.insert(s_id, staple);
+    }
+    design.scaffold_id = Some(0);
+    design.scaffold_sequence = Some(String::from("ACGTTTTT"));
+
+    assign_scaffold_sequence_to_domains(&mut design, &[0]).unwrap();
+    let sequence = |design: &Design, s_id: usize, d_id: usize| match &design
+        .strands
+        .get(&s_id)
+        .unwrap()
+        .domains[d_id]
+    {
+        Domain::HelixDomain(interval) => interval.sequence.as_ref().map(|s| s.to_string()),
+        _ => unreachable!(),
+    };
+    assert!(design.scaffold_sequence.is_none());
+    assert_eq!(sequence(&design, 0, 0).as_deref(), Some("ACGT"));
+    assert_eq!(sequence(&design, 0, 1), None);
+    assert_eq!(sequence(&design, 1, 0).as_deref(), Some("ACGT"));
+    assert_eq!(sequence(&design, 2, 0).as_deref(), Some("GGGG"));
+
+    clear_sequences(&mut design, &[0, 1]).unwrap();
+    assert_eq!(sequence(&design, 0, 0), None);
+    assert_eq!(sequence(&design, 1, 0), None);
+    assert_eq!(sequence(&design, 2, 0).as_deref(), Some("GGGG"));
+}
+
+#[test]
+fn pairing_audit_finds_unpaired_and_multiply_paired_nucls() {
+    use pairing_audit::{audit_scaffold_pairing, PairingDefect};
+    let mut design = Design::new();
+    {
+        let mut helices = design.helices.make_mut();
+        helices.insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+        // A helix supported by helix 0 and shifted by two positions
+        let mut helix = Helix::new(Vec3::zero(), Rotor3::identity());
+        helix.support_helix = Some(0);
+        helix.initial_nt_index = 2;
+        helices.insert(1, helix);
+    }
+    let strand = |helix: usize, start: isize, end: isize, forward: bool| {
+        let mut strand = Strand::init(helix, start, forward, 0xFF0000);
+        strand.domains = vec![Domain::HelixDomain(HelixInterval {
+            helix,
+            start,
+            end,
+            forward,
+            sequence: None,
+        })];
+        strand
+    };
+    design.strands.insert(0, strand(0, 0, 10, true));
+    design.strands.insert(1, strand(0, 0, 8, false));
+    // Covers the virtual positions 6 and 7, that are already covered by strand 1
+    design.strands.insert(2, strand(1, 4, 6, false));
+    assert_eq!(audit_scaffold_pairing(&design), None);
+
+    design.scaffold_id = Some(0);
+    let issues = audit_scaffold_pairing(&design).unwrap();
+    let nucl = |helix: usize, position: isize, forward: bool| Nucl {
+        helix,
+        position,
+        forward,
+    };
+    let defect_at = |position: isize| {
+        issues
+            .iter()
+            .find(|i| i.scaffold_nucl == nucl(0, position, true))
+            .map(|i| i.defect.clone())
+    };
+    assert_eq!(issues.len(), 4);
+    assert_eq!(defect_at(5), None);
+    assert_eq!(
+        defect_at(6),
+        Some(PairingDefect::MultiplyPaired(vec![
+            nucl(0, 6, false),
+            nucl(1, 4, false)
+        ]))
+    );
+    assert!(matches!(
+        defect_at(7),
+        Some(PairingDefect::MultiplyPaired(_))
+    ));
+    assert_eq!(defect_at(8), Some(PairingDefect::Unpaired));
+    assert_eq!(defect_at(9), Some(PairingDefect::Unpaired));
+}
+
+#[test]
+fn bezier_path_start_is_snapped_to_helix_continuation() {
+    use design_operations::snap_bezier_path_ends;
+    let mut design = Design::new();
+    design
+        .helices
+        .make_mut()
+        .insert(0, Helix::new(Vec3::zero(), Rotor3::identity()));
+    let mut strand = Strand::init(0, 0, true, 0xFF0000);
+    strand.domains = vec![Domain::HelixDomain(HelixInterval {
+        helix: 0,
+        start: 0,
+        end: 10,
+        forward: true,
+        sequence: None,
+    })];
+    design.strands.insert(0, strand);
+    design.bezier_planes.make_mut().push(BezierPlaneDescriptor {
+        position: Vec3::new(3., 0., 0.),
+        orientation: Rotor3::identity(),
+    });
+    let path_id = {
+        let mut paths = design.bezier_paths.make_mut();
+        let path_id = paths.create_path(BezierVertex::new(BezierPlaneId(0), Vec2::zero()));
+        paths
+            .get_mut(&path_id)
+            .unwrap()
+            .add_vertex(BezierVertex::new(BezierPlaneId(0), Vec2::new(10., 0.)));
+        path_id
+    };
+
+    assert_eq!(snap_bezier_path_ends(&mut design, path_id).unwrap(), 1);
+    let parameters = design.helix_parameters.unwrap_or_default();
+    let continuation = design
+        .helices
+        .get(&0)
+        .unwrap()
+        .axis_position(&parameters, 10, true);
+    let vertices = design.bezier_paths.get(&path_id).unwrap().vertices();
+    let start = vertices[0].grid_position(&design.bezier_planes).unwrap();
+    assert!((start - continuation).mag() < 1e-5);
+    let end = vertices[1].grid_position(&design.bezier_planes).unwrap();
+    assert!((end - Vec3::new(3., 0., 10.)).mag() < 1e-5);
+}
+
+#[test]
+fn bezier_path_simplification_keeps_ends() {
+    use design_operations::{edit_bezier_path_control_points, ControlPointsEdit};
+    let mut design = Design::new();
+    design.bezier_planes.make_mut().push(BezierPlaneDescriptor {
+        position: Vec3::zero(),
+        orientation: Rotor3::identity(),
+    });
+    let positions = [
+        Vec2::zero(),
+        Vec2::new(10., 0.1),
+        Vec2::new(20., 0.),
+        Vec2::new(30., 10.),
+        Vec2::new(40., 0.),
+    ];
+    let path_id = {
+        let mut paths = design.bezier_paths.make_mut();
+        let path_id = paths.create_path(BezierVertex::new(BezierPlaneId(0), positions[0]));
+        for p in positions[1..].iter() {
+            paths
+                .get_mut(&path_id)
+                .unwrap()
+                .add_vertex(BezierVertex::new(BezierPlaneId(0), *p));
+        }
+        path_id
+    };
+
+    edit_bezier_path_control_points(
+        &mut design,
+        path_id,
+        ControlPointsEdit::Simplify { tolerance: 1. },
+    )
+    .unwrap();
+    let kept: Vec<Vec2> = design
+        .bezier_paths
+        .get(&path_id)
+        .unwrap()
+        .vertices()
+        .iter()
+        .map(|v| v.position)
+        .collect();
+    assert_eq!(
+        kept,
+        vec![positions[0], positions[2], positions[3], positions[4]]
+    );
+
+    edit_bezier_path_control_points(
+        &mut design,
+        path_id,
+        ControlPointsEdit::Resample { nb_vertices: 6 },
+    )
+    .unwrap();
+    let vertices = design.bezier_paths.get(&path_id).unwrap().vertices();
+    assert_eq!(vertices.len(), 6);
+    assert_eq!(vertices[0].position, positions[0]);
+    assert_eq!(vertices[5].position, positions[4]);
+}
+
+#[test]
+fn undo_snapshot_shares_unchanged_strands() {
+    let mut design = Design::new();
+    design.strands.push(Strand::init(0, 0, true, 0));
+    design.strands.push(Strand::init(1, 0, true, 0));
+    let snapshot = design.clone();
+
+    design.strands.get_mut(&0).unwrap().color = 1;
+    design.strands.remove_empty_domains();
+
+    assert!(std::sync::Arc::ptr_eq(
+        &design.strands.0[&1],
+        &snapshot.strands.0[&1]
+    ));
+    assert!(!std::sync::Arc::ptr_eq(
+        &design.strands.0[&0],
+        &snapshot.strands.0[&0]
+    ));
+    assert_eq!(snapshot.strands.get(&0).unwrap().color, 0);
+}
@@ -0,0 +1,212 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Estimation of the twist strain that the cross-overs impose on the helices of a square or
+//! honeycomb lattice.
+//!
+//! The positions of two consecutive cross-overs on a helix are fixed by the lattice, which
+//! assumes that the helix makes `1 / lattice_bases_per_turn` turns per position. The double
+//! helix would naturally make `1 / bases_per_turn` turns per nucleotide, and the difference
+//! between the two rotations is the twist strain of the segment of helix between the
+//! cross-overs. Insertions and skips located on the segment change its number of nucleotides.
+
+use crate::design_operations::lattice_bases_per_turn;
+use crate::{Design, Domain, HelixCollection, Nucl};
+use std::collections::BTreeMap;
+
+/// The twist strain around a cross-over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XoverStrain {
+    pub xover: (Nucl, Nucl),
+    /// The mean, in degrees, of the twist strains of the segments of helix that end at the
+    /// cross-over. A positive value means that the lattice underwinds the helices.
+    pub strain: f32,
+}
+
+/// The twist strain accumulated along a helix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HelixStrain {
+    pub helix: usize,
+    /// The sum, in degrees, of the twist strains of the segments between the cross-overs of
+    /// the helix.
+    pub twist_mismatch: f32,
+    /// The number of skips that would relax the helix. A negative value is a number of
+    /// insertions.
+    pub suggested_skips: isize,
+}
+
+struct StrainedHelix {
+    bases_per_turn: f32,
+    /// The positions of two consecutive cross-overs and the twist strain between them.
+    segments: Vec<(isize, isize, f32)>,
+}
+
+impl StrainedHelix {
+    /// The mean strain of the segments that end at `position`.
+    fn strain_at(&self, position: isize) -> Option<f32> {
+        let strains: Vec<f32> = self
+            .segments
+            .iter()
+            .filter(|(start, end, _)| *start == position || *end == position)
+            .map(|(_, _, strain)| *strain)
+            .collect();
+        (!strains.is_empty()).then(|| strains.iter().sum::<f32>() / strains.len() as f32)
+    }
+}
+
+/// The twist strain around each cross-over of `design` that has at least one end on a helix of
+/// a square or honeycomb lattice.
+pub fn xover_strains(design: &Design) -> Vec<XoverStrain> {
+    let helices = strained_helices(design);
+    let strain_at = |nucl: Nucl| {
+        helices
+            .get(&nucl.helix)
+            .and_then(|h| h.strain_at(nucl.position))
+    };
+    design
+        .strands
+        .values()
+        .flat_map(|s| s.xovers())
+        .filter_map(|(n1, n2)| {
+            let strains: Vec<f32> = strain_at(n1).into_iter().chain(strain_at(n2)).collect();
+            (!strains.is_empty()).then(|| XoverStrain {
+                xover: (n1, n2),
+                strain: strains.iter().sum::<f32>() / strains.len() as f32,
+            })
+        })
+        .collect()
+}
+
+/// The twist strain accumulated along each helix of `design` that lies on a square or
+/// honeycomb lattice and has at least two cross-overs.
+pub fn helix_strains(design: &Design) -> Vec<HelixStrain> {
+    strained_helices(design)
+        .into_iter()
+        .filter(|(_, h)| !h.segments.is_empty())
+        .map(|(helix, h)| {
+            let twist_mismatch: f32 = h.segments.iter().map(|(_, _, strain)| strain).sum();
+            HelixStrain {
+                helix,
+                twist_mismatch,
+                suggested_skips: (twist_mismatch * h.bases_per_turn / 360.).round() as isize,
+            }
+        })
+        .collect()
+}
+
+fn strained_helices(design: &Design) -> BTreeMap<usize, StrainedHelix> {
+    let helix_parameters = design.helix_parameters.unwrap_or_default();
+
+    let mut xover_ends: BTreeMap<usize, Vec<isize>> = BTreeMap::new();
+    for (n1, n2) in design.strands.values().flat_map(|s| s.xovers()) {
+        for nucl in [n1, n2] {
+            xover_ends
+                .entry(nucl.helix)
+                .or_default()
+                .push(nucl.position);
+        }
+    }
+
+    // For each strand of each helix, the number of nucleotides inserted, or skipped, after each
+    // position.
+    let mut deltas: BTreeMap<(usize, bool), BTreeMap<isize, isize>> = BTreeMap::new();
+    for strand in design.strands.values() {
+        for pair in strand.domains.windows(2) {
+            let (interval, position, delta) = match pair {
+                [Domain::HelixDomain(interval), Domain::Insertion { nb_nucl, .. }] => {
+                    let position = if interval.forward {
+                        interval.end - 1
+                    } else {
+                        interval.start - 1
+                    };
+                    (interval, position, *nb_nucl as isize)
+                }
+                [Domain::HelixDomain(a), Domain::HelixDomain(b)]
+                    if a.helix == b.helix && a.forward == b.forward =>
+                {
+                    let skipped = if a.forward {
+                        (b.start == a.end + 1).then(|| a.end)
+                    } else {
+                        (a.start == b.end + 1).then(|| b.end)
+                    };
+                    if let Some(skipped) = skipped {
+                        (a, skipped, -1)
+                    } else {
+                        continue;
+                    }
+                }
+                _ => continue,
+            };
+            *deltas
+                .entry((interval.helix, interval.forward))
+                .or_default()
+                .entry(position)
+                .or_default() += delta;
+        }
+    }
+
+    let mut ret = BTreeMap::new();
+    for (h_id, mut positions) in xover_ends.into_iter() {
+        let helix = if let Some(helix) = design.helices.get(&h_id) {
+            helix
+        } else {
+            continue;
+        };
+        let lattice_bases_per_turn = if let Some(b) = lattice_bases_per_turn(design, h_id) {
+            b
+        } else {
+            continue;
+        };
+        let bases_per_turn = helix
+            .helix_parameters
+            .unwrap_or(helix_parameters)
+            .bases_per_turn;
+        positions.sort_unstable();
+        positions.dedup();
+        let segments = positions
+            .windows(2)
+            .map(|w| {
+                let (start, end) = (w[0], w[1]);
+                // Insertions and skips are usually placed on both strands, they are counted on
+                // the strand that has the most of them.
+                let delta = [true, false]
+                    .iter()
+                    .map(|forward| {
+                        deltas
+                            .get(&(h_id, *forward))
+                            .map(|d| d.range(start..end).map(|(_, n)| n).sum::<isize>())
+                            .unwrap_or(0)
+                    })
+                    .max_by_key(|n| n.abs())
+                    .unwrap_or(0);
+                let nb_nucl = (end - start + delta) as f32;
+                let strain = 360.
+                    * (nb_nucl / bases_per_turn - (end - start) as f32 / lattice_bases_per_turn);
+                (start, end, strain)
+            })
+            .collect();
+        ret.insert(
+            h_id,
+            StrainedHelix {
+                bases_per_turn,
+                segments,
+            },
+        );
+    }
+    ret
+}
@@ -19,9 +19,9 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! A good description of the cadnano file format can be found at
 //! <https://github.com/UC-Davis-molecular-computing/scadnano-python-package/blob/main/misc/cadnano-format-specs/v2.txt>
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use ensnano_design::{grid::GridData, Collection, Design, Domain, Nucl};
+use ensnano_design::{grid::GridData, Collection, Design, Domain, HelixCollection, Nucl};
 
 mod parity_graph;
 
@@ -122,6 +122,9 @@ fn init_cadnano_exporter(design: &Design) -> Result<CadnanoExporter, CadnanoErro
     let mut odd = 1;
 
     let mut cadnano_helices = HashMap::with_capacity(bonds.max_helix_idx);
+    // The display numbers of the helices are kept when their parity matches the direction of
+    // the helix in cadnano
+    let mut used_nums = HashSet::new();
 
     for g_id in grids.grids.keys() {
         let mut shift_y = 0;
@@ -144,15 +147,30 @@ fn init_cadnano_exporter(design: &Design) -> Result<CadnanoExporter, CadnanoErro
                 candidate.1 += 1;
             }
 
-            let num = if parity_helix[*h] {
+            let display_number = design
+                .helices
+                .get(h)
+                .and_then(|helix| helix.display_number)
+                .map(|n| n as isize)
+                .filter(|n| (n % 2 == 0) == parity_helix[*h] && !used_nums.contains(n));
+            let num = if let Some(n) = display_number {
+                n
+            } else if parity_helix[*h] {
+                while used_nums.contains(&even) {
+                    even += 2;
+                }
                 let ret = even;
                 even += 2;
                 ret
             } else {
+                while used_nums.contains(&odd) {
+                    odd += 2;
+                }
                 let ret = odd;
                 odd += 2;
                 ret
             };
+            used_nums.insert(num);
 
             let cadnano_helix = CadnanoHelix::new(num, candidate, bonds.max_nt_pos);
             cadnano_helices.insert(*h, cadnano_helix);
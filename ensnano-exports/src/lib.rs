@@ -21,8 +21,10 @@ use strum::Display;
 
 pub mod cadnano;
 pub mod cando;
+pub mod nucleotides;
 pub mod oxdna;
 pub mod pdb;
+pub mod print_2d;
 use cadnano::CadnanoError;
 use cando::CanDoError;
 use ensnano_design::ultraviolet::{Vec3, Vec4};
@@ -38,6 +40,10 @@ pub enum ExportType {
     Cando,
     Pdb,
     Oxdna,
+    NucleotidesCsv,
+    NucleotidesJson,
+    /// A paginated PDF printout of the 2D view
+    Print2d(print_2d::PrintParameters),
 }
 
 /// A value returned by the export functions when exports was successfull.
@@ -51,6 +57,8 @@ pub enum ExportSuccess {
         topology: PathBuf,
         configuration: PathBuf,
     },
+    Nucleotides(PathBuf),
+    Print2d(PathBuf),
 }
 
 const SUCCESSFUL_EXPORT_MSG_PREFIX: &str = "Succussfully exported to";
@@ -63,6 +71,9 @@ impl ExportSuccess {
             Self::Cadnano(p) => format!("{SUCCESSFUL_EXPORT_MSG_PREFIX}\n{}", p.to_string_lossy()),
             Self::Cando(p) => format!("{SUCCESSFUL_EXPORT_MSG_PREFIX}\n{}", p.to_string_lossy()),
             Self::Pdb(p) => format!("{SUCCESSFUL_EXPORT_MSG_PREFIX}\n{}", p.to_string_lossy()),
+            Self::Nucleotides(p) | Self::Print2d(p) => {
+                format!("{SUCCESSFUL_EXPORT_MSG_PREFIX}\n{}", p.to_string_lossy())
+            }
             Self::Oxdna {
                 topology,
                 configuration,
@@ -85,6 +96,18 @@ pub enum ExportError {
     NotImplemented,
 }
 
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CadnanoConversion(e) => write!(f, "Could not convert to cadnano: {e:?}"),
+            Self::CandoConversion(e) => write!(f, "Could not convert to CanDo: {e:?}"),
+            Self::PdbConversion(e) => write!(f, "Could not convert to pdb: {e:?}"),
+            Self::IOError(e) => write!(f, "Could not write file: {e}"),
+            Self::NotImplemented => write!(f, "Export not implemented"),
+        }
+    }
+}
+
 impl From<CadnanoError> for ExportError {
     fn from(e: CadnanoError) -> Self {
         Self::CadnanoConversion(e)
@@ -228,6 +251,9 @@ pub fn export(
             writeln!(&mut out_file, "{cadnano_content}")?;
             Ok(ExportSuccess::Cadnano(export_path.clone()))
         }
+        ExportType::Print2d(parameters) => {
+            print_2d::print_2d_export(design, basis_map, parameters, export_path)
+        }
 
         _ => Err(ExportError::NotImplemented),
     }
@@ -0,0 +1,113 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Export of the per-nucleotide data of a design to flat CSV or JSON tables.
+//!
+//! These tables are meant to be consumed by external scripts that need the geometry of a design
+//! without having to parse `.ens` files.
+
+use super::{ExportError, ExportSuccess, ExportType};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+const CSV_HEADER: &str = "id,strand,helix,position,forward,x,y,z,color,base,paired_with";
+
+/// The exported data of a single nucleotide
+#[derive(Serialize, Clone, Debug)]
+pub struct NucleotideRecord {
+    /// The identifier of the nucleotide in the design content
+    pub id: u32,
+    pub strand: usize,
+    pub helix: usize,
+    pub position: isize,
+    pub forward: bool,
+    /// The position of the nucleotide in the model's coordinates
+    pub space_position: [f32; 3],
+    /// The color of the nucleotide, in the `0xAARRGGBB` format
+    pub color: u32,
+    pub base: Option<char>,
+    /// The identifier of the nucleotide paired with this one, if any
+    pub paired_with: Option<u32>,
+}
+
+impl NucleotideRecord {
+    fn csv_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},#{:06X},{},{}",
+            self.id,
+            self.strand,
+            self.helix,
+            self.position,
+            self.forward,
+            self.space_position[0],
+            self.space_position[1],
+            self.space_position[2],
+            self.color & 0xFF_FF_FF,
+            self.base.map(String::from).unwrap_or_default(),
+            self.paired_with
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// Write `records` to `export_path`. `export_type` must be either
+/// [`ExportType::NucleotidesCsv`] or [`ExportType::NucleotidesJson`].
+pub fn export_nucleotides(
+    records: &[NucleotideRecord],
+    export_type: ExportType,
+    export_path: &PathBuf,
+) -> Result<ExportSuccess, ExportError> {
+    let mut out_file = std::fs::File::create(export_path)?;
+    match export_type {
+        ExportType::NucleotidesCsv => {
+            writeln!(&mut out_file, "{CSV_HEADER}")?;
+            for record in records.iter() {
+                writeln!(&mut out_file, "{}", record.csv_line())?;
+            }
+        }
+        ExportType::NucleotidesJson => {
+            serde_json::to_writer_pretty(&mut out_file, records).map_err(std::io::Error::from)?;
+        }
+        _ => return Err(ExportError::NotImplemented),
+    }
+    Ok(ExportSuccess::Nucleotides(export_path.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_line_has_one_field_per_column() {
+        let record = NucleotideRecord {
+            id: 3,
+            strand: 1,
+            helix: 2,
+            position: -4,
+            forward: false,
+            space_position: [1.0, 2.5, -3.0],
+            color: 0xFF_12_34_56,
+            base: Some('A'),
+            paired_with: None,
+        };
+        let line = record.csv_line();
+        assert_eq!(line, "3,1,2,-4,false,1,2.5,-3,#123456,A,");
+        assert_eq!(line.split(',').count(), CSV_HEADER.split(',').count());
+    }
+}
@@ -0,0 +1,648 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Paginated PDF printouts of the 2D view of a design, meant for lab notebooks and bench
+//! references.
+//!
+//! The helices are drawn with the 2D layout of the design. The drawing is scaled so that a
+//! nucleotide has a fixed width on paper, and split across as many A4 pages as needed. Helices
+//! on which no strand is drawn are not printed.
+
+use super::{BasisMap, ExportError, ExportSuccess};
+use ensnano_design::ultraviolet::{Isometry2, Rotor2, Vec2};
+use ensnano_design::{Collection, Design, Domain, HelixCollection, Nucl};
+use ensnano_utils::full_isometry::FullIsometry;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// The parameters of a printout of the 2D view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintParameters {
+    /// The width, in millimeters, of a nucleotide on paper
+    pub mm_per_nucleotide: f32,
+    /// Print the display number of each helix on its left
+    pub helix_numbers: bool,
+    /// Print the base of each nucleotide inside its lane
+    pub sequences: bool,
+    /// Add pages listing the strands with their color, length and 5' end
+    pub legend: bool,
+}
+
+impl Default for PrintParameters {
+    fn default() -> Self {
+        Self {
+            mm_per_nucleotide: 2.,
+            helix_numbers: true,
+            sequences: true,
+            legend: true,
+        }
+    }
+}
+
+/// The range of widths, in millimeters, that a nucleotide can have on paper
+pub const MM_PER_NUCLEOTIDE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=6.0;
+
+const PAGE_WIDTH: f32 = 595.28;
+const PAGE_HEIGHT: f32 = 841.89;
+const MARGIN: f32 = 36.;
+const HEADER_HEIGHT: f32 = 20.;
+const POINTS_PER_MM: f32 = 72. / 25.4;
+const LEGEND_LINE_HEIGHT: f32 = 14.;
+
+const HELIX_BORDER_COLOR: u32 = 0x555555;
+const HELIX_MIDDLE_COLOR: u32 = 0xBBBBBB;
+const TEXT_COLOR: u32 = 0x000000;
+/// The width of the strands, relative to the width of a nucleotide
+const STRAND_WIDTH: f32 = 0.3;
+/// The radius of the circle in which the helix number is printed, relative to the width of a
+/// nucleotide
+const HELIX_NUMBER_RADIUS: f32 = 1.;
+
+/// Write a PDF printout of the 2D view of `design` to `export_path`.
+pub fn print_2d_export(
+    design: &Design,
+    basis_map: Option<&dyn BasisMap>,
+    parameters: PrintParameters,
+    export_path: &PathBuf,
+) -> Result<ExportSuccess, ExportError> {
+    let title = export_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let layout = Layout::new(design);
+    let mut pages = layout.draw_pages(design, basis_map, &parameters);
+    if parameters.legend {
+        pages.extend(draw_legend(design, &parameters));
+    }
+    let nb_pages = pages.len();
+    for (i, page) in pages.iter_mut().enumerate() {
+        page.header(&format!("{title} - page {}/{nb_pages}", i + 1));
+    }
+    std::fs::write(export_path, pdf_document(&pages))?;
+    Ok(ExportSuccess::Print2d(export_path.clone()))
+}
+
+/// The 2D position of the helices that are printed
+struct Layout {
+    helices: Vec<PrintedHelix>,
+    min: Vec2,
+    max: Vec2,
+}
+
+struct PrintedHelix {
+    id: usize,
+    display_number: usize,
+    left: isize,
+    right: isize,
+    isometry: FullIsometry,
+}
+
+impl PrintedHelix {
+    /// The position of a point given in the helix coordinates, where a nucleotide is a unit
+    /// square and the forward lane is above the backward lane.
+    fn position(&self, local: Vec2) -> Vec2 {
+        self.isometry
+            .into_homogeneous_matrix()
+            .transform_point2(local)
+    }
+
+    fn nucl_center(&self, nucl: &Nucl) -> Vec2 {
+        let y = if nucl.forward { 0.5 } else { 1.5 };
+        self.position(Vec2::new(nucl.position as f32 + 0.5, y))
+    }
+
+    fn number_center(&self) -> Vec2 {
+        self.position(Vec2::new(self.left as f32 - 1.5 * HELIX_NUMBER_RADIUS, 1.))
+    }
+
+    fn corners(&self) -> [Vec2; 4] {
+        let left = self.left as f32 - 3. * HELIX_NUMBER_RADIUS;
+        let right = self.right as f32 + 1.;
+        [
+            self.position(Vec2::new(left, 0.)),
+            self.position(Vec2::new(right, 0.)),
+            self.position(Vec2::new(right, 2.)),
+            self.position(Vec2::new(left, 2.)),
+        ]
+    }
+}
+
+impl Layout {
+    fn new(design: &Design) -> Self {
+        let mut helices = Vec::new();
+        for (i, (h_id, (left, right))) in design.strands.get_intervals().into_iter().enumerate() {
+            let helix = if let Some(helix) = design.helices.get(&h_id) {
+                helix
+            } else {
+                continue;
+            };
+            // Same default position as the one given by the 2D view
+            let isometry = helix.isometry2d.unwrap_or_else(|| {
+                Isometry2::new((5. * i as f32 - 1.) * Vec2::unit_y(), Rotor2::identity())
+            });
+            helices.push(PrintedHelix {
+                id: h_id,
+                display_number: design.helices.display_number(h_id),
+                left,
+                right,
+                isometry: FullIsometry::from_isommetry_symmetry(isometry, helix.symmetry),
+            });
+        }
+        let mut min = Vec2::broadcast(f32::INFINITY);
+        let mut max = Vec2::broadcast(f32::NEG_INFINITY);
+        for corner in helices.iter().flat_map(PrintedHelix::corners) {
+            min = min.min_by_component(corner);
+            max = max.max_by_component(corner);
+        }
+        Self { helices, min, max }
+    }
+
+    fn helix(&self, h_id: usize) -> Option<&PrintedHelix> {
+        self.helices.iter().find(|h| h.id == h_id)
+    }
+
+    fn draw_pages(
+        &self,
+        design: &Design,
+        basis_map: Option<&dyn BasisMap>,
+        parameters: &PrintParameters,
+    ) -> Vec<Page> {
+        if self.helices.is_empty() {
+            return vec![];
+        }
+        let scale = parameters.mm_per_nucleotide * POINTS_PER_MM;
+        let area_width = (PAGE_WIDTH - 2. * MARGIN) / scale;
+        let area_height = (PAGE_HEIGHT - 2. * MARGIN - HEADER_HEIGHT) / scale;
+        let size = self.max - self.min;
+        let nb_columns = (size.x / area_width).ceil().max(1.) as usize;
+        let nb_rows = (size.y / area_height).ceil().max(1.) as usize;
+
+        let mut pages = Vec::new();
+        for row in 0..nb_rows {
+            for column in 0..nb_columns {
+                let window_min =
+                    self.min + Vec2::new(column as f32 * area_width, row as f32 * area_height);
+                let window_max = window_min + Vec2::new(area_width, area_height);
+                let visible = self.helices.iter().any(|h| {
+                    let corners = h.corners();
+                    let h_min = corners
+                        .iter()
+                        .fold(corners[0], |a, b| a.min_by_component(*b));
+                    let h_max = corners
+                        .iter()
+                        .fold(corners[0], |a, b| a.max_by_component(*b));
+                    h_min.x < window_max.x
+                        && h_max.x > window_min.x
+                        && h_min.y < window_max.y
+                        && h_max.y > window_min.y
+                });
+                if !visible {
+                    continue;
+                }
+                let mut page = Page::new(window_min, scale);
+                page.clip_to_drawing_area();
+                self.draw_helices(&mut page, parameters);
+                self.draw_strands(&mut page, design);
+                if parameters.sequences {
+                    self.draw_sequences(&mut page, design, basis_map);
+                }
+                page.end_clip();
+                pages.push(page);
+            }
+        }
+        pages
+    }
+
+    fn draw_helices(&self, page: &mut Page, parameters: &PrintParameters) {
+        page.line_width(0.05);
+        for helix in self.helices.iter() {
+            let left = helix.left as f32;
+            let right = helix.right as f32 + 1.;
+            page.stroke_color(HELIX_BORDER_COLOR);
+            page.polygon(&[
+                helix.position(Vec2::new(left, 0.)),
+                helix.position(Vec2::new(right, 0.)),
+                helix.position(Vec2::new(right, 2.)),
+                helix.position(Vec2::new(left, 2.)),
+            ]);
+            page.stroke_color(HELIX_MIDDLE_COLOR);
+            page.polyline(&[
+                helix.position(Vec2::new(left, 1.)),
+                helix.position(Vec2::new(right, 1.)),
+            ]);
+            if parameters.helix_numbers {
+                page.stroke_color(HELIX_BORDER_COLOR);
+                page.circle(helix.number_center(), HELIX_NUMBER_RADIUS);
+                let text = helix.display_number.to_string();
+                let font_size = 1.4 * HELIX_NUMBER_RADIUS / text.len().max(2) as f32;
+                page.text_centered(helix.number_center(), &text, font_size);
+            }
+        }
+    }
+
+    fn draw_strands(&self, page: &mut Page, design: &Design) {
+        page.line_width(STRAND_WIDTH);
+        for strand in design.strands.values() {
+            let mut points = Vec::new();
+            for domain in strand.domains.iter() {
+                if let Domain::HelixDomain(interval) = domain {
+                    if let Some(helix) = self.helix(interval.helix) {
+                        points.push(helix.nucl_center(&interval.prime5()));
+                        points.push(helix.nucl_center(&interval.prime3()));
+                    }
+                }
+            }
+            if strand.is_cyclic {
+                if let Some(first) = points.first().cloned() {
+                    points.push(first);
+                }
+            }
+            if points.is_empty() {
+                continue;
+            }
+            page.stroke_color(strand.color);
+            page.fill_color(strand.color);
+            page.polyline(&points);
+            if !strand.is_cyclic {
+                self.draw_strand_ends(page, strand.get_5prime(), strand.get_3prime());
+            }
+        }
+    }
+
+    /// Draw a square on the 5' end and a triangle pointing outwards on the 3' end
+    fn draw_strand_ends(&self, page: &mut Page, prime5: Option<Nucl>, prime3: Option<Nucl>) {
+        if let Some((nucl, helix)) = prime5.and_then(|n| Some((n, self.helix(n.helix)?))) {
+            let x = nucl.position as f32;
+            let y = if nucl.forward { 0. } else { 1. };
+            page.fill_polygon(&[
+                helix.position(Vec2::new(x + 0.2, y + 0.2)),
+                helix.position(Vec2::new(x + 0.8, y + 0.2)),
+                helix.position(Vec2::new(x + 0.8, y + 0.8)),
+                helix.position(Vec2::new(x + 0.2, y + 0.8)),
+            ]);
+        }
+        if let Some((nucl, helix)) = prime3.and_then(|n| Some((n, self.helix(n.helix)?))) {
+            let x = nucl.position as f32;
+            let (y, tip, base) = if nucl.forward {
+                (0.5, x + 1., x + 0.3)
+            } else {
+                (1.5, x, x + 0.7)
+            };
+            page.fill_polygon(&[
+                helix.position(Vec2::new(base, y - 0.4)),
+                helix.position(Vec2::new(tip, y)),
+                helix.position(Vec2::new(base, y + 0.4)),
+            ]);
+        }
+    }
+
+    fn draw_sequences(&self, page: &mut Page, design: &Design, basis_map: Option<&dyn BasisMap>) {
+        let basis_map = if let Some(map) = basis_map {
+            map
+        } else {
+            return;
+        };
+        page.fill_color(TEXT_COLOR);
+        for strand in design.strands.values() {
+            for domain in strand.domains.iter() {
+                if let Domain::HelixDomain(interval) = domain {
+                    let helix = if let Some(helix) = self.helix(interval.helix) {
+                        helix
+                    } else {
+                        continue;
+                    };
+                    for position in interval.iter() {
+                        let nucl = Nucl {
+                            helix: interval.helix,
+                            position,
+                            forward: interval.forward,
+                        };
+                        if let Some(base) = basis_map.get(&nucl) {
+                            page.text_centered(helix.nucl_center(&nucl), &base.to_string(), 0.6);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draw pages listing the strands of the design with their color, length and 5' end.
+fn draw_legend(design: &Design, parameters: &PrintParameters) -> Vec<Page> {
+    let mut lines = vec![
+        format!(
+            "Scale: 1 nt = {:.1} mm. Squares mark 5' ends, triangles mark 3' ends.",
+            parameters.mm_per_nucleotide
+        ),
+        String::new(),
+    ];
+    let mut colors = vec![None, None];
+    for (s_id, strand) in design.strands.iter() {
+        let name = strand
+            .name
+            .as_ref()
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("Strand {s_id}"));
+        let prime5 = strand
+            .get_5prime()
+            .map(|n| {
+                format!(
+                    "5': h{}:nt{}",
+                    design.helices.display_number(n.helix),
+                    n.position
+                )
+            })
+            .unwrap_or_default();
+        let scaffold = if design.scaffold_id == Some(*s_id) {
+            " (scaffold)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{name}{scaffold}, {} nt, {prime5}",
+            strand.length()
+        ));
+        colors.push(Some(strand.color));
+    }
+
+    let lines_per_page =
+        ((PAGE_HEIGHT - 2. * MARGIN - HEADER_HEIGHT) / LEGEND_LINE_HEIGHT).floor() as usize;
+    let mut pages = Vec::new();
+    for (chunk, chunk_colors) in lines
+        .chunks(lines_per_page.max(1))
+        .zip(colors.chunks(lines_per_page.max(1)))
+    {
+        // A scale of one point per unit, so that the legend is laid out in points
+        let mut page = Page::new(Vec2::zero(), 1.);
+        for (i, (line, color)) in chunk.iter().zip(chunk_colors.iter()).enumerate() {
+            let y = (i as f32 + 1.) * LEGEND_LINE_HEIGHT;
+            let mut x = 0.;
+            if let Some(color) = color {
+                page.fill_color(*color);
+                page.fill_polygon(&[
+                    Vec2::new(0., y - 10.),
+                    Vec2::new(20., y - 10.),
+                    Vec2::new(20., y),
+                    Vec2::new(0., y),
+                ]);
+                x = 28.;
+            }
+            page.fill_color(TEXT_COLOR);
+            page.text(Vec2::new(x, y - 1.), line, 10.);
+        }
+        pages.push(page);
+    }
+    pages
+}
+
+/// The content stream of a page. Drawing methods take positions in the coordinates of the
+/// design, which are mapped to the page so that `origin` is at the top left corner of the
+/// drawing area.
+struct Page {
+    content: String,
+    origin: Vec2,
+    scale: f32,
+}
+
+impl Page {
+    fn new(origin: Vec2, scale: f32) -> Self {
+        Self {
+            content: String::new(),
+            origin,
+            scale,
+        }
+    }
+
+    fn to_page(&self, point: Vec2) -> (f32, f32) {
+        (
+            MARGIN + (point.x - self.origin.x) * self.scale,
+            PAGE_HEIGHT - MARGIN - HEADER_HEIGHT - (point.y - self.origin.y) * self.scale,
+        )
+    }
+
+    fn op(&mut self, op: std::fmt::Arguments) {
+        let _ = self.content.write_fmt(op);
+        self.content.push('\n');
+    }
+
+    fn clip_to_drawing_area(&mut self) {
+        self.op(format_args!(
+            "q {:.2} {:.2} {:.2} {:.2} re W n",
+            MARGIN,
+            MARGIN,
+            PAGE_WIDTH - 2. * MARGIN,
+            PAGE_HEIGHT - 2. * MARGIN - HEADER_HEIGHT
+        ));
+    }
+
+    fn end_clip(&mut self) {
+        self.op(format_args!("Q"));
+    }
+
+    fn header(&mut self, text: &str) {
+        self.op(format_args!("0 g"));
+        self.op(format_args!(
+            "BT /F1 10 Tf {:.2} {:.2} Td ({}) Tj ET",
+            MARGIN,
+            PAGE_HEIGHT - MARGIN - 10.,
+            pdf_string(text)
+        ));
+    }
+
+    fn stroke_color(&mut self, color: u32) {
+        let (r, g, b) = rgb(color);
+        self.op(format_args!("{r:.3} {g:.3} {b:.3} RG"));
+    }
+
+    fn fill_color(&mut self, color: u32) {
+        let (r, g, b) = rgb(color);
+        self.op(format_args!("{r:.3} {g:.3} {b:.3} rg"));
+    }
+
+    /// Set the width of the lines, in the units of the design
+    fn line_width(&mut self, width: f32) {
+        self.op(format_args!("{:.2} w 1 J 1 j", width * self.scale));
+    }
+
+    fn path(&mut self, points: &[Vec2]) {
+        for (i, point) in points.iter().enumerate() {
+            let (x, y) = self.to_page(*point);
+            let operator = if i == 0 { "m" } else { "l" };
+            self.op(format_args!("{x:.2} {y:.2} {operator}"));
+        }
+    }
+
+    fn polyline(&mut self, points: &[Vec2]) {
+        self.path(points);
+        self.op(format_args!("S"));
+    }
+
+    fn polygon(&mut self, points: &[Vec2]) {
+        self.path(points);
+        self.op(format_args!("s"));
+    }
+
+    fn fill_polygon(&mut self, points: &[Vec2]) {
+        self.path(points);
+        self.op(format_args!("f"));
+    }
+
+    fn circle(&mut self, center: Vec2, radius: f32) {
+        // Approximation of a circle by four cubic Bézier curves
+        const K: f32 = 0.552_284_8;
+        let (cx, cy) = self.to_page(center);
+        let r = radius * self.scale;
+        let k = K * r;
+        self.op(format_args!("{:.2} {:.2} m", cx + r, cy));
+        for (x1, y1, x2, y2, x3, y3) in [
+            (r, k, k, r, 0., r),
+            (-k, r, -r, k, -r, 0.),
+            (-r, -k, -k, -r, 0., -r),
+            (k, -r, r, -k, r, 0.),
+        ] {
+            self.op(format_args!(
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                cx + x1,
+                cy + y1,
+                cx + x2,
+                cy + y2,
+                cx + x3,
+                cy + y3
+            ));
+        }
+        self.op(format_args!("S"));
+    }
+
+    /// Write `text` with its baseline starting at `position`. The font size is given in the
+    /// units of the design.
+    fn text(&mut self, position: Vec2, text: &str, font_size: f32) {
+        let (x, y) = self.to_page(position);
+        self.op(format_args!(
+            "BT /F1 {:.2} Tf {x:.2} {y:.2} Td ({}) Tj ET",
+            font_size * self.scale,
+            pdf_string(text)
+        ));
+    }
+
+    fn text_centered(&mut self, center: Vec2, text: &str, font_size: f32) {
+        // Approximate width of the characters of Helvetica
+        let width = 0.6 * font_size * text.len() as f32;
+        let height = 0.7 * font_size;
+        self.text(
+            center + Vec2::new(-width / 2., height / 2.),
+            text,
+            font_size,
+        );
+    }
+}
+
+fn rgb(color: u32) -> (f32, f32, f32) {
+    (
+        ((color >> 16) & 0xFF) as f32 / 255.,
+        ((color >> 8) & 0xFF) as f32 / 255.,
+        (color & 0xFF) as f32 / 255.,
+    )
+}
+
+/// Escape `text` so that it can be written in a PDF string. Non ASCII characters are replaced
+/// by '?' since the standard fonts only cover ASCII reliably.
+fn pdf_string(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                ret.push('\\');
+                ret.push(c);
+            }
+            c if c.is_ascii() && !c.is_ascii_control() => ret.push(c),
+            _ => ret.push('?'),
+        }
+    }
+    ret
+}
+
+/// Assemble the pages into a PDF document using the standard Helvetica font.
+fn pdf_document(pages: &[Page]) -> Vec<u8> {
+    let mut objects = vec![
+        String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+        String::new(), // the page tree, written once the pages are known
+        String::from("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>"),
+    ];
+    let mut kids = Vec::with_capacity(pages.len());
+    for page in pages.iter() {
+        let page_object = objects.len() + 1;
+        kids.push(format!("{page_object} 0 R"));
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            page_object + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            page.content.len(),
+            page.content
+        ));
+    }
+    objects[1] = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        pages.len()
+    );
+
+    let mut ret = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(ret.len());
+        let _ = write!(ret, "{} 0 obj\n{object}\nendobj\n", i + 1);
+    }
+    let xref_offset = ret.len();
+    let _ = write!(ret, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(ret, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        ret,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+        objects.len() + 1
+    );
+    ret.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xref_offsets_point_to_objects() {
+        let mut page = Page::new(Vec2::zero(), 1.);
+        page.header("design (copy)");
+        let document = String::from_utf8(pdf_document(&[page])).unwrap();
+        assert!(document.contains("(design \\(copy\\)) Tj"));
+
+        let xref = document.find("xref\n").unwrap();
+        let offsets: Vec<usize> = document[xref..]
+            .lines()
+            .skip(3)
+            .take_while(|l| l.ends_with(" n "))
+            .map(|l| l[..10].parse().unwrap())
+            .collect();
+        assert_eq!(offsets.len(), 5);
+        for (i, offset) in offsets.into_iter().enumerate() {
+            assert!(document[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+}
@@ -23,8 +23,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! the scene.
 use super::data::{ClickResult, FreeEnd};
 use super::{
-    ActionMode, AppState, CameraPtr, DataPtr, FlatHelix, FlatNucl, PhySize, PhysicalPosition,
-    Selection, ViewPtr, WindowEvent,
+    ActionMode, AppState, Camera2DState, CameraPtr, DataPtr, FlatHelix, FlatNucl, PhySize,
+    PhysicalPosition, Selection, ViewPtr, WindowEvent,
 };
 
 use ensnano_design::ultraviolet;
@@ -70,6 +70,8 @@ pub enum Consequence {
     FlipGroup(FlatHelix),
     FollowingSuggestion(FlatNucl, bool),
     Centering(FlatNucl, bool),
+    /// Center the other views on a nucleotide
+    CenterOtherView(FlatNucl),
     DrawingSelection(PhysicalPosition<f64>, PhysicalPosition<f64>),
     ReleasedSelection(Option<Vec<Selection>>),
     PasteRequest(Option<FlatNucl>),
@@ -77,6 +79,8 @@ pub enum Consequence {
     SelectionChanged(Vec<Selection>),
     ClearSelection,
     DoubleClick(ClickResult),
+    /// Open a context menu for the clicked element
+    ContextMenu(ClickResult),
     MoveBuilders(isize),
     InitBuilding(FlatNucl),
     Helix2DMvmtEnded,
@@ -95,6 +99,17 @@ pub enum Consequence {
         symmetry: Vec2,
     },
     PngExport(Vec2, Vec2),
+    /// A double strand must be added on an existing helix
+    AddDoubleStrand {
+        helix: usize,
+        start: isize,
+        length: usize,
+    },
+    /// The parameters of the double strands created in helix building mode were adjusted
+    DoubleStrandParametersChanged {
+        position: isize,
+        length: usize,
+    },
 }
 
 impl<S: AppState> Controller<S> {
@@ -134,6 +149,20 @@ impl<S: AppState> Controller<S> {
         self.update_globals();
     }
 
+    pub fn get_camera_2d(&self) -> Camera2DState {
+        let globals = *self.camera_top.borrow().get_globals();
+        Camera2DState {
+            center: globals.scroll_offset,
+            zoom: globals.zoom,
+        }
+    }
+
+    pub fn set_camera_2d(&mut self, camera: Camera2DState) {
+        let mut camera_top = self.camera_top.borrow_mut();
+        camera_top.set_zoom(camera.zoom);
+        camera_top.set_center(camera.center.into());
+    }
+
     pub fn set_splited(&mut self, splited: bool, refit: bool) {
         self.splited = splited;
         let old_rectangle_top = self.camera_top.borrow().get_visible_rectangle();
@@ -243,6 +272,7 @@ impl<S: AppState> Controller<S> {
             None
         };
         self.view.borrow_mut().set_hovered_nucl(nucl);
+        self.data.borrow_mut().set_hovered_nucl(nucl);
     }
 
     pub fn process_keyboard(&self, event: &WindowEvent) {
@@ -102,6 +102,22 @@ impl<S: AppState> ControllerState<S> for NormalState {
                 })),
                 consequences: Consequence::Nothing,
             },
+            WindowEvent::MouseInput {
+                button: MouseButton::Right,
+                state: ElementState::Pressed,
+                ..
+            } if ctrl(&controller.modifiers) => {
+                let (x, y) = controller
+                    .get_camera(position.y)
+                    .borrow()
+                    .screen_to_world(self.mouse_position.x as f32, self.mouse_position.y as f32);
+                let click_result =
+                    controller
+                        .data
+                        .borrow()
+                        .get_click(x, y, &controller.get_camera(position.y));
+                Transition::consequence(Consequence::ContextMenu(click_result))
+            }
             WindowEvent::MouseInput {
                 button: MouseButton::Right,
                 state: ElementState::Pressed,
@@ -173,6 +189,20 @@ impl<S: AppState> ControllerState<S> for NormalState {
                         .borrow()
                         .get_click(x, y, &controller.get_camera(position.y))
                 };
+                if let ClickResult::Nucl(nucl) = click_result {
+                    if let Some((helix, start, length)) = controller
+                        .data
+                        .borrow()
+                        .double_strand_target(nucl)
+                        .filter(|_| !app_state.is_pasting())
+                    {
+                        return Transition::consequence(Consequence::AddDoubleStrand {
+                            helix,
+                            start,
+                            length,
+                        });
+                    }
+                }
                 match click_result {
                     ClickResult::CircleWidget { .. } | ClickResult::Nothing
                         if app_state.is_pasting() =>
@@ -366,6 +396,26 @@ impl<S: AppState> ControllerState<S> for NormalState {
                     },
                 }
             }
+            WindowEvent::MouseInput {
+                button: MouseButton::Middle,
+                state: ElementState::Pressed,
+                ..
+            } if controller.modifiers.shift() => {
+                let (x, y) = controller
+                    .get_camera(position.y)
+                    .borrow()
+                    .screen_to_world(self.mouse_position.x as f32, self.mouse_position.y as f32);
+                let click_result =
+                    controller
+                        .data
+                        .borrow()
+                        .get_click(x, y, &controller.get_camera(position.y));
+                if let ClickResult::Nucl(nucl) = click_result {
+                    Transition::consequence(Consequence::CenterOtherView(nucl))
+                } else {
+                    Transition::nothing()
+                }
+            }
             WindowEvent::MouseInput {
                 button: MouseButton::Middle,
                 state: ElementState::Pressed,
@@ -422,6 +472,36 @@ impl<S: AppState> ControllerState<S> for NormalState {
                     .process_scroll(delta, self.mouse_position);
                 Transition::nothing()
             }
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        virtual_keycode: Some(key),
+                        state: ElementState::Pressed,
+                        ..
+                    },
+                ..
+            } if matches!(
+                *key,
+                VirtualKeyCode::Plus
+                    | VirtualKeyCode::NumpadAdd
+                    | VirtualKeyCode::Equals
+                    | VirtualKeyCode::Minus
+                    | VirtualKeyCode::NumpadSubtract
+            ) && matches!(app_state.get_action_mode(), ActionMode::BuildHelix { .. }) =>
+            {
+                let increase =
+                    !matches!(*key, VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract);
+                app_state
+                    .get_action_mode()
+                    .adjusted_double_strand(increase, ctrl(&controller.modifiers))
+                    .map(|(position, length)| {
+                        Transition::consequence(Consequence::DoubleStrandParametersChanged {
+                            position,
+                            length,
+                        })
+                    })
+                    .unwrap_or_else(Transition::nothing)
+            }
             WindowEvent::KeyboardInput { .. } => {
                 controller.process_keyboard(event);
                 Transition::nothing()
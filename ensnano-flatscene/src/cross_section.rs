@@ -0,0 +1,673 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A 2D view of the intersection of the helices with a plane.
+//!
+//! The plane is orthogonal to the axis of a reference helix and can be moved along this axis with
+//! the mouse wheel or the up and down arrow keys. The helices that cross the plane are drawn as
+//! circles, that can be clicked to be selected.
+//!
+//! Controls:
+//! * mouse wheel/up and down arrows: move the plane (by 10 nucleotides if shift is pressed)
+//! * ctrl + mouse wheel: zoom
+//! * left click and drag: move the camera
+//! * left click on a circle: select the helix (ctrl/shift + click adds it to the selection)
+//! * `A`: align the plane on the selected helix
+//! * `F`: fit the camera to the section
+
+use super::{AppState, CameraPtr, DesignReader, NuclCollection, Requests};
+use crate::camera2d::{Camera2D, FitRectangle, Globals};
+use crate::{DrawArea, PhySize};
+use ensnano_design::{
+    ultraviolet::{Vec2, Vec3},
+    Helix, HelixCollection, HelixParameters,
+};
+use ensnano_interactor::{
+    application::{Application, Duration, Notification},
+    consts::{CIRCLE2D_BLUE, CIRCLE2D_GREY, SAMPLE_COUNT},
+    Selection,
+};
+use ensnano_utils::bindgroup_manager::UniformBindGroup;
+use ensnano_utils::chars2d::{Line, Sentence, TextDrawer};
+use ensnano_utils::circles2d::{CircleDrawer, CircleInstance, CircleKind};
+use ensnano_utils::texture::Texture;
+use ensnano_utils::{wgpu, winit};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use wgpu::{Device, Queue};
+use winit::dpi::PhysicalPosition;
+use winit::event::{
+    ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, VirtualKeyCode,
+    WindowEvent,
+};
+
+/// The number of nucleotides by which the plane is moved at each step
+const SLICE_STEP: f32 = 1.;
+/// The number of nucleotides by which the plane is moved at each step when shift is pressed
+const FAST_SLICE_STEP: f32 = 10.;
+/// The distance, in pixels, that the cursor must travel for a click to become a drag
+const DRAG_THRESHOLD: f64 = 3.;
+/// The radius of the ring around the selected helices, relative to the radius of the helices
+const SELECTION_RING_FACTOR: f32 = 1.3;
+const SELECTION_RING_COLOR: u32 = 0xFF_FF_00_00;
+const SLICE_LABEL_COLOR: [f32; 4] = [0.2, 0.2, 0.2, 1.];
+const SLICE_LABEL_SIZE_PX: f32 = 16.;
+const SLICE_LABEL_MARGIN_PX: f32 = 20.;
+/// The advance of a glyph of the monospace font, relative to the font size
+const GLYPH_ADVANCE: f32 = 0.5;
+
+/// The plane with which the helices are intersected
+#[derive(Clone, Copy, Debug)]
+struct SlicePlane {
+    origin: Vec3,
+    /// The normal of the plane, oriented toward the viewer
+    normal: Vec3,
+    right: Vec3,
+    up: Vec3,
+    /// The signed distance, in nanometers, between `origin` and the plane
+    offset: f32,
+}
+
+impl SlicePlane {
+    /// The plane orthogonal to the axis of a helix, going through its `n`-th nucleotide
+    fn orthogonal_to_helix(helix: &Helix, parameters: &HelixParameters, n: isize) -> Option<Self> {
+        let origin = helix.axis_position(parameters, n, true);
+        let normal = normalized(helix.axis_position(parameters, n + 1, true) - origin)?;
+        let up = Vec3::unit_y().rotated_by(helix.orientation);
+        let up = normalized(up - up.dot(normal) * normal)?;
+        let right = up.cross(normal);
+        Some(Self {
+            origin,
+            normal,
+            right,
+            up,
+            offset: 0.,
+        })
+    }
+
+    fn center(&self) -> Vec3 {
+        self.origin + self.offset * self.normal
+    }
+
+    fn signed_distance(&self, point: Vec3) -> f32 {
+        (point - self.center()).dot(self.normal)
+    }
+
+    /// The 2D world coordinates of a point of the plane. The y axis of the 2D world points down.
+    fn project(&self, point: Vec3) -> Vec2 {
+        let vec = point - self.center();
+        Vec2::new(vec.dot(self.right), -vec.dot(self.up))
+    }
+}
+
+fn normalized(vec: Vec3) -> Option<Vec3> {
+    (vec.mag() > 1e-5).then(|| vec.normalized())
+}
+
+/// The intersection of a helix with the slice plane
+struct HelixSection {
+    helix_id: usize,
+    display_number: usize,
+    center: Vec2,
+    visible: bool,
+}
+
+pub struct CrossSection<S: AppState> {
+    device: Rc<Device>,
+    area: DrawArea,
+    camera: CameraPtr,
+    globals: UniformBindGroup,
+    depth_texture: Texture,
+    circle_drawer: CircleDrawer,
+    text_drawer: TextDrawer,
+    plane: Option<SlicePlane>,
+    sections: Vec<HelixSection>,
+    helix_radius: f32,
+    rise: f32,
+    selected_helices: BTreeSet<usize>,
+    old_state: S,
+    requests: Arc<Mutex<dyn Requests>>,
+    modifiers: ModifiersState,
+    mouse_position: PhysicalPosition<f64>,
+    /// The position of the cursor when the left button was pressed, if it is still pressed
+    clicked_position: Option<PhysicalPosition<f64>>,
+    dragging: bool,
+    /// True if the sections must be recomputed before the next frame
+    sections_need_update: bool,
+    /// True if the instances sent to the GPU must be updated before the next frame
+    instances_need_update: bool,
+    need_redraw: bool,
+}
+
+impl<S: AppState> CrossSection<S> {
+    pub fn new(
+        device: Rc<Device>,
+        queue: Rc<Queue>,
+        area: DrawArea,
+        requests: Arc<Mutex<dyn Requests>>,
+        initial_state: S,
+    ) -> Self {
+        let globals = Globals::from_resolution([area.size.width as f32, area.size.height as f32]);
+        let camera = Rc::new(RefCell::new(Camera2D::new(globals, false)));
+        let globals = UniformBindGroup::new(
+            device.clone(),
+            queue.clone(),
+            camera.borrow().get_globals(),
+            "cross section globals",
+        );
+        let depth_texture =
+            Texture::create_depth_texture(device.as_ref(), &area.size, SAMPLE_COUNT);
+        let circle_drawer = CircleDrawer::new(
+            device.clone(),
+            queue.clone(),
+            globals.get_layout(),
+            CircleKind::FullCircle,
+        );
+        let text_drawer = TextDrawer::new(
+            ensnano_interactor::consts::PRINTABLE_CHARS,
+            device.clone(),
+            queue.clone(),
+            globals.get_layout(),
+        );
+        Self {
+            device,
+            area,
+            camera,
+            globals,
+            depth_texture,
+            circle_drawer,
+            text_drawer,
+            plane: None,
+            sections: Vec::new(),
+            helix_radius: 1.,
+            rise: 1.,
+            selected_helices: BTreeSet::new(),
+            old_state: initial_state,
+            requests,
+            modifiers: ModifiersState::empty(),
+            mouse_position: PhysicalPosition::new(-1., -1.),
+            clicked_position: None,
+            dragging: false,
+            sections_need_update: true,
+            instances_need_update: true,
+            need_redraw: true,
+        }
+    }
+
+    /// Align the plane on a helix. If `helix_id` is None, the helix with the smallest identifier
+    /// that has nucleotides is used.
+    fn align_plane(&mut self, helix_id: Option<usize>) {
+        let reader = self.old_state.get_design_reader();
+        let ranges = helices_ranges(&reader);
+        let helix_id = helix_id.or_else(|| ranges.keys().next().cloned());
+        let helices = reader.get_helices_map();
+        let parameters = reader.get_helix_parameters();
+        let plane = helix_id.and_then(|h_id| {
+            let (min, max) = ranges.get(&h_id).cloned().unwrap_or((0, 0));
+            helices
+                .get(&h_id)
+                .and_then(|h| SlicePlane::orthogonal_to_helix(h, &parameters, (min + max) / 2))
+        });
+        if plane.is_some() {
+            self.plane = plane;
+            self.update_sections();
+            self.fit();
+        }
+    }
+
+    fn update_sections(&mut self) {
+        self.sections_need_update = false;
+        self.instances_need_update = true;
+        let reader = self.old_state.get_design_reader();
+        let parameters = reader.get_helix_parameters();
+        self.helix_radius = parameters.helix_radius;
+        self.rise = parameters.rise;
+        let plane = if let Some(plane) = self.plane {
+            plane
+        } else {
+            self.sections.clear();
+            return;
+        };
+        let helices = reader.get_helices_map();
+        let mut sections = Vec::new();
+        for (h_id, (min, max)) in helices_ranges(&reader) {
+            if let Some(helix) = helices.get(&h_id) {
+                let visible = reader.get_visibility_helix(h_id).unwrap_or(true);
+                let display_number = reader.get_helix_display_number(h_id);
+                // The axis of the helix is sampled at each nucleotide, so that curved helices can
+                // cross the plane several times.
+                let mut previous: Option<(Vec3, f32)> = None;
+                for n in min..=max {
+                    let point = helix.axis_position(&parameters, n, true);
+                    let dist = plane.signed_distance(point);
+                    if let Some((previous_point, previous_dist)) = previous {
+                        if (previous_dist < 0.) != (dist < 0.) {
+                            let t = previous_dist / (previous_dist - dist);
+                            let intersection = previous_point + t * (point - previous_point);
+                            sections.push(HelixSection {
+                                helix_id: h_id,
+                                display_number,
+                                center: plane.project(intersection),
+                                visible,
+                            });
+                        }
+                    }
+                    previous = Some((point, dist));
+                }
+            }
+        }
+        self.sections = sections;
+    }
+
+    /// Move the plane along its normal by `nb_nucl` nucleotides
+    fn move_plane(&mut self, nb_nucl: f32) {
+        if let Some(plane) = self.plane.as_mut() {
+            plane.offset += nb_nucl * self.rise;
+            self.sections_need_update = true;
+        }
+    }
+
+    fn fit(&mut self) {
+        let rectangle = FitRectangle::from_points(
+            self.sections
+                .iter()
+                .map(|s| [s.center.x, s.center.y])
+                .collect::<Vec<_>>(),
+        );
+        if let Some(mut rectangle) = rectangle {
+            rectangle.add_point([
+                rectangle.top_left()[0] - 2. * self.helix_radius,
+                rectangle.top_left()[1] - 2. * self.helix_radius,
+            ]);
+            rectangle.add_point([
+                rectangle.bottom_right()[0] + 2. * self.helix_radius,
+                rectangle.bottom_right()[1] + 2. * self.helix_radius,
+            ]);
+            self.camera.borrow_mut().fit_center(rectangle);
+        }
+    }
+
+    /// Return the helix whose section is under the cursor, if any
+    fn helix_under_cursor(&self, position: PhysicalPosition<f64>) -> Option<usize> {
+        let (x, y) = self
+            .camera
+            .borrow()
+            .screen_to_world(position.x as f32, position.y as f32);
+        let point = Vec2::new(x, y);
+        self.sections
+            .iter()
+            .filter(|s| (s.center - point).mag() <= self.helix_radius)
+            .min_by(|a, b| {
+                (a.center - point)
+                    .mag()
+                    .partial_cmp(&(b.center - point).mag())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|s| s.helix_id)
+    }
+
+    fn click(&mut self, position: PhysicalPosition<f64>) {
+        let add = self.modifiers.ctrl() || self.modifiers.shift();
+        let clicked_helix = self.helix_under_cursor(position);
+        let mut helices: BTreeSet<usize> = if add {
+            self.selected_helices.clone()
+        } else {
+            BTreeSet::new()
+        };
+        if let Some(h_id) = clicked_helix {
+            if add && helices.contains(&h_id) {
+                helices.remove(&h_id);
+            } else {
+                helices.insert(h_id);
+            }
+        } else if add {
+            return;
+        }
+        let selection = helices
+            .into_iter()
+            .map(|helix_id| Selection::Helix {
+                design_id: 0,
+                helix_id,
+                segment_id: 0,
+            })
+            .collect();
+        self.requests.lock().unwrap().new_selection(selection);
+    }
+
+    fn input(&mut self, event: &WindowEvent, position: PhysicalPosition<f64>) {
+        match event {
+            WindowEvent::CursorMoved { .. } => {
+                self.mouse_position = position;
+                if let Some(clicked) = self.clicked_position {
+                    let dx = position.x - clicked.x;
+                    let dy = position.y - clicked.y;
+                    if self.dragging || dx.abs().max(dy.abs()) > DRAG_THRESHOLD {
+                        self.dragging = true;
+                        self.camera.borrow_mut().process_mouse(
+                            dx as f32 / self.area.size.width as f32,
+                            dy as f32 / self.area.size.height as f32,
+                        );
+                    }
+                }
+            }
+            WindowEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+                ..
+            } => {
+                self.clicked_position = Some(self.mouse_position);
+                self.dragging = false;
+            }
+            WindowEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+                ..
+            } => {
+                if self.dragging {
+                    self.camera.borrow_mut().end_movement();
+                } else if self.clicked_position.is_some() {
+                    self.click(self.mouse_position);
+                }
+                self.clicked_position = None;
+                self.dragging = false;
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                if self.modifiers.ctrl() {
+                    self.camera
+                        .borrow_mut()
+                        .process_scroll(delta, self.mouse_position);
+                } else {
+                    let scroll = match delta {
+                        MouseScrollDelta::LineDelta(_, scroll) => *scroll,
+                        MouseScrollDelta::PixelDelta(PhysicalPosition { y, .. }) => {
+                            (*y as f32) / 100.
+                        }
+                    };
+                    self.move_plane(scroll.signum() * self.slice_step());
+                }
+            }
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        virtual_keycode: Some(key),
+                        state: ElementState::Pressed,
+                        ..
+                    },
+                ..
+            } => match key {
+                VirtualKeyCode::Up => self.move_plane(self.slice_step()),
+                VirtualKeyCode::Down => self.move_plane(-self.slice_step()),
+                VirtualKeyCode::A => {
+                    let helix = self.selected_helices.iter().next().cloned();
+                    self.align_plane(helix);
+                }
+                VirtualKeyCode::F => self.fit(),
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+
+    fn slice_step(&self) -> f32 {
+        if self.modifiers.shift() {
+            FAST_SLICE_STEP
+        } else {
+            SLICE_STEP
+        }
+    }
+
+    fn update_instances(&mut self) {
+        self.instances_need_update = false;
+        let mut circles = Vec::with_capacity(self.sections.len());
+        self.text_drawer.clear();
+        let camera = self.camera.borrow();
+        let rotation = camera.rotation().reversed();
+        let symetry = camera.get_globals().symmetry;
+        for section in self.sections.iter() {
+            let color = if section.visible {
+                CIRCLE2D_BLUE
+            } else {
+                CIRCLE2D_GREY
+            };
+            circles.push(CircleInstance::new(
+                section.center,
+                self.helix_radius,
+                1,
+                color,
+            ));
+            if self.selected_helices.contains(&section.helix_id) {
+                circles.push(CircleInstance::new(
+                    section.center,
+                    SELECTION_RING_FACTOR * self.helix_radius,
+                    2,
+                    SELECTION_RING_COLOR,
+                ));
+            }
+            let text = section.display_number.to_string();
+            let sentence = Sentence {
+                text: &text,
+                size: 2. * self.helix_radius / text.len().max(2) as f32,
+                z_index: 0,
+                color: [0., 0., 0., 1.].into(),
+                rotation,
+                symetry,
+            };
+            let line = Line {
+                origin: section.center + self.helix_radius * Vec2::unit_y(),
+                direction: Vec2::unit_x(),
+            };
+            self.text_drawer
+                .add_sentence(sentence, section.center, line);
+        }
+
+        if let Some(plane) = self.plane.as_ref() {
+            let zoom = camera.get_globals().zoom;
+            let length_unit = self.old_state.get_scale_bar_parameters().length_unit;
+            let helix_parameters = self.old_state.get_design_reader().get_helix_parameters();
+            let text = format!(
+                "{} ({:.0} nt)",
+                length_unit.format_nm(plane.offset, &helix_parameters),
+                plane.offset / self.rise
+            );
+            let label_width_px = text.len() as f32 * GLYPH_ADVANCE * SLICE_LABEL_SIZE_PX;
+            let (x, y) = camera.screen_to_world(
+                SLICE_LABEL_MARGIN_PX + label_width_px / 2.,
+                SLICE_LABEL_MARGIN_PX + SLICE_LABEL_SIZE_PX / 2.,
+            );
+            let center = Vec2::new(x, y);
+            let sentence = Sentence {
+                text: &text,
+                size: SLICE_LABEL_SIZE_PX / zoom,
+                // A negative z_index puts the label in front of everything else
+                z_index: -1,
+                color: SLICE_LABEL_COLOR.into(),
+                rotation,
+                symetry,
+            };
+            // The label must not be moved, so the bounding line is chosen far away from it
+            let line = Line {
+                origin: center + 1e6 * Vec2::unit_y(),
+                direction: Vec2::unit_x(),
+            };
+            self.text_drawer.add_sentence(sentence, center, line);
+        }
+        drop(camera);
+        self.circle_drawer.new_instances(Rc::new(circles));
+    }
+
+    fn draw(&mut self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView) {
+        if self.sections_need_update {
+            self.update_sections();
+        }
+        if let Some(globals) = self.camera.borrow_mut().update() {
+            self.globals.update(globals);
+            self.instances_need_update = true;
+        }
+        if self.instances_need_update {
+            self.update_instances();
+        }
+        self.need_redraw = false;
+
+        let msaa_texture = if SAMPLE_COUNT > 1 {
+            Some(Texture::create_msaa_texture(
+                self.device.as_ref(),
+                &self.area.size,
+                SAMPLE_COUNT,
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+            ))
+        } else {
+            None
+        };
+        let attachment = msaa_texture.as_ref().unwrap_or(target);
+        let resolve_target = msaa_texture.as_ref().map(|_| target);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("cross section"),
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: attachment,
+                resolve_target,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: 1.,
+                        g: 1.,
+                        b: 1.,
+                        a: 1.,
+                    }),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.),
+                    store: true,
+                }),
+                stencil_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: true,
+                }),
+            }),
+        });
+        render_pass.set_bind_group(0, self.globals.get_bindgroup(), &[]);
+        self.circle_drawer.draw(&mut render_pass);
+        self.text_drawer.draw(&mut render_pass);
+    }
+
+    fn resize(&mut self, area: DrawArea) {
+        self.area = area;
+        self.depth_texture =
+            Texture::create_depth_texture(self.device.as_ref(), &area.size, SAMPLE_COUNT);
+        self.camera
+            .borrow_mut()
+            .resize(area.size.width as f32, area.size.height as f32);
+        self.need_redraw = true;
+    }
+}
+
+/// Return, for each helix that has nucleotides, the smallest and largest position of its
+/// nucleotides
+fn helices_ranges<R: DesignReader>(reader: &R) -> BTreeMap<usize, (isize, isize)> {
+    let mut ret: BTreeMap<usize, (isize, isize)> = BTreeMap::new();
+    let nucl_collection = reader.get_nucl_collection();
+    for nucl in nucl_collection.iter() {
+        let range = ret
+            .entry(nucl.helix)
+            .or_insert((nucl.position, nucl.position));
+        range.0 = range.0.min(nucl.position);
+        range.1 = range.1.max(nucl.position);
+    }
+    ret
+}
+
+impl<S: AppState> Application for CrossSection<S> {
+    type AppState = S;
+
+    fn on_notify(&mut self, notification: Notification) {
+        match notification {
+            Notification::ModifersChanged(modifiers) => self.modifiers = modifiers,
+            Notification::FitRequest => {
+                self.fit();
+                self.need_redraw = true;
+            }
+            Notification::ClearDesigns => {
+                self.plane = None;
+                self.sections_need_update = true;
+            }
+            _ => (),
+        }
+    }
+
+    fn on_resize(&mut self, _window_size: PhySize, area: DrawArea) {
+        self.resize(area)
+    }
+
+    fn on_event(
+        &mut self,
+        event: &WindowEvent,
+        cursor_position: PhysicalPosition<f64>,
+        _state: &S,
+    ) -> Option<ensnano_interactor::CursorIcon> {
+        self.input(event, cursor_position);
+        self.need_redraw = true;
+        None
+    }
+
+    fn on_redraw_request(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        _dt: Duration,
+    ) {
+        self.draw(encoder, target)
+    }
+
+    fn needs_redraw(&mut self, _: Duration, state: S) -> bool {
+        let design_updated = state.design_was_updated(&self.old_state);
+        if state.selection_was_updated(&self.old_state) {
+            self.selected_helices = state
+                .get_selection()
+                .iter()
+                .filter_map(|s| {
+                    if let Selection::Helix { helix_id, .. } = s {
+                        Some(*helix_id)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            self.instances_need_update = true;
+        }
+        self.old_state = state;
+        if design_updated {
+            if self.plane.is_none() {
+                self.align_plane(None);
+            } else {
+                self.sections_need_update = true;
+            }
+        }
+        self.need_redraw
+            || self.sections_need_update
+            || self.instances_need_update
+            || self.camera.borrow().was_updated()
+    }
+
+    fn is_splited(&self) -> bool {
+        false
+    }
+}
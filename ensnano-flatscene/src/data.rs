@@ -17,25 +17,30 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 use super::{
     flattypes::{FlatPosition, FlatSelection, HelixSegment},
-    view::EditionInfo,
-    AppState, Flat, HelixVec, PhantomElement, Requests, ViewPtr,
+    view::{EditionInfo, ScaleBarUnit},
+    ActionMode, AppState, Flat, HelixVec, PhantomElement, Requests, ViewPtr,
 };
-use ensnano_design::{ultraviolet, Nucl};
-use ensnano_interactor::{Selection, SelectionMode};
+use ensnano_design::{ultraviolet, Domain, Nucl};
+use ensnano_interactor::{Selection, SelectionMode, SuggestionParameters};
 use std::sync::{Arc, Mutex};
 use ultraviolet::Vec2;
 
 pub(crate) mod helix;
-pub use helix::{GpuVertex, Helix, HelixHandle, HelixModel, Shift};
+pub use helix::{GpuVertex, Helix, HelixHandle, HelixModel, Numbering, Shift, XoverGuides};
 mod strand;
-pub use strand::{FreeEnd, Strand, StrandVertex};
+pub use strand::{FreeEnd, Strand, StrandVertex, XoverDrawing};
 mod design;
+mod spatial_index;
 use super::{CameraPtr, FlatHelix, FlatIdx, FlatNucl};
 use crate::FlatHelixMaps;
 use ahash::RandomState;
 use design::{Design2d, Helix2d};
 pub use design::{DesignReader, FlatTorsion, NuclCollection};
+use ensnano_design::heat_map::HeatMapKind;
 use ensnano_interactor::consts::*;
+use ensnano_interactor::graphics::{
+    NucleotideNumbering, NumberingParameters, XoverGuideParameters,
+};
 use ensnano_utils::camera2d::FitRectangle;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
@@ -45,12 +50,23 @@ pub struct Data<R: DesignReader> {
     instance_update: bool,
     instance_reset: bool,
     helices: HelixVec<Helix>,
+    /// Used to find quickly the helices that may be under the cursor
+    spatial_index: spatial_index::HelixSpatialIndex,
     selected_helix: Option<FlatIdx>,
     nb_helices_created: usize,
     suggestions: HashMap<FlatNucl, HashSet<FlatNucl, RandomState>, RandomState>,
     id: u32,
     requests: Arc<Mutex<dyn Requests>>,
     last_click: LastClick,
+    /// If some, a tick is drawn above the helices every `ruler_interval` nucleotides
+    ruler_interval: Option<isize>,
+    xover_guides: XoverGuideParameters,
+    heat_map: Option<HeatMapKind>,
+    suggestion_parameters: SuggestionParameters,
+    hovered_nucl: Option<FlatNucl>,
+    /// The position and length of the double strands added by clicking on a helix in helix
+    /// building mode
+    double_strand_parameters: Option<(isize, usize)>,
 }
 
 impl<R: DesignReader> Data<R> {
@@ -61,18 +77,26 @@ impl<R: DesignReader> Data<R> {
             instance_update: true,
             instance_reset: false,
             helices: HelixVec::new(),
+            spatial_index: Default::default(),
             selected_helix: None,
             nb_helices_created: 0,
             suggestions: Default::default(),
             id,
             requests,
             last_click: Default::default(),
+            ruler_interval: None,
+            xover_guides: Default::default(),
+            heat_map: None,
+            suggestion_parameters: Default::default(),
+            hovered_nucl: None,
+            double_strand_parameters: None,
         }
     }
 
     pub fn clear_design(&mut self) {
         self.design.clear();
         self.helices = HelixVec::new();
+        self.spatial_index = Default::default();
         self.view.borrow_mut().reset();
         self.instance_update = true;
         self.instance_reset = false;
@@ -87,15 +111,47 @@ impl<R: DesignReader> Data<R> {
             self.view.borrow_mut().reset();
             self.instance_reset = false;
         }
+        let scale_bar_parameters = new_state.get_scale_bar_parameters();
         if new_state.design_was_updated(old_state)
             || new_state.selection_was_updated(old_state)
             || new_state.candidate_was_updated(old_state)
+            || scale_bar_parameters != old_state.get_scale_bar_parameters()
+            || new_state.get_numbering_parameters() != old_state.get_numbering_parameters()
+            || new_state.get_xover_drawing_parameters() != old_state.get_xover_drawing_parameters()
+            || new_state.get_xover_guide_parameters() != old_state.get_xover_guide_parameters()
+            || new_state.get_heat_map() != old_state.get_heat_map()
+            || new_state.get_suggestion_parameters() != old_state.get_suggestion_parameters()
+            || new_state.get_color_vision_mode() != old_state.get_color_vision_mode()
             || self.instance_update
             || self.view.borrow().needs_redraw()
         {
             log::trace!("updating 2d data");
+            self.ruler_interval = scale_bar_parameters.ruler.interval();
+            self.xover_guides = new_state.get_xover_guide_parameters();
+            self.heat_map = new_state.get_heat_map();
+            self.suggestion_parameters = new_state.get_suggestion_parameters();
+            self.view
+                .borrow_mut()
+                .set_scale_bar(scale_bar_parameters.show_scale_bar.then(|| {
+                    let helix_parameters = new_state.get_design_reader().get_helix_parameters();
+                    let length_unit = scale_bar_parameters.length_unit;
+                    ScaleBarUnit {
+                        rise: helix_parameters.rise,
+                        length_unit,
+                        nm_per_unit: length_unit.nm_per_unit(&helix_parameters),
+                    }
+                }));
             self.design.update(new_state.get_design_reader());
+            self.design
+                .remap_strand_colors(new_state.get_color_vision_mode());
             self.fetch_helices(new_state.get_design_reader());
+            self.view
+                .borrow_mut()
+                .set_xover_drawing_parameters(new_state.get_xover_drawing_parameters());
+            self.update_numbering(
+                new_state.get_numbering_parameters(),
+                new_state.get_design_reader(),
+            );
             self.view.borrow_mut().update_helices(&self.helices);
             self.view
                 .borrow_mut()
@@ -106,9 +162,95 @@ impl<R: DesignReader> Data<R> {
             self.update_highlight(new_state);
             self.update_strand_building_info(new_state.get_building_state());
         }
+        let double_strand_parameters =
+            if let ActionMode::BuildHelix { position, length } = new_state.get_action_mode() {
+                Some((position, length))
+            } else {
+                None
+            };
+        if double_strand_parameters != self.double_strand_parameters
+            || new_state.design_was_updated(old_state)
+        {
+            self.double_strand_parameters = double_strand_parameters;
+            self.update_ghost_double_strand();
+        }
         self.instance_update = false;
     }
 
+    pub fn set_hovered_nucl(&mut self, nucl: Option<FlatNucl>) {
+        if nucl != self.hovered_nucl {
+            self.hovered_nucl = nucl;
+            self.update_ghost_double_strand();
+        }
+    }
+
+    /// Return the helix, start and length of the double strand that is added by clicking on
+    /// `nucl` in helix building mode, if it fits on the helix.
+    pub fn double_strand_target(&self, nucl: FlatNucl) -> Option<(usize, isize, usize)> {
+        let (start, length) = self.double_strand_parameters?;
+        let helix = nucl.helix.segment.helix_idx;
+        self.design
+            .double_strand_fits(helix, start, length)
+            .then(|| (helix, start, length))
+    }
+
+    fn update_ghost_double_strand(&self) {
+        let segments = self
+            .hovered_nucl
+            .and_then(|nucl| self.double_strand_target(nucl))
+            .map(|(helix, start, length)| self.design.double_strand_segments(helix, start, length))
+            .unwrap_or_default();
+        self.view.borrow_mut().set_ghost_double_strand(&segments);
+    }
+
+    /// Compute the positions of the nucleotides on their strands and the domain boundaries that
+    /// the numbering written above the helices needs.
+    fn update_numbering(&mut self, parameters: NumberingParameters, design: R) {
+        let mut strand_positions = HashMap::default();
+        let mut domain_ends = HashSet::default();
+        let need_positions = parameters.numbering == NucleotideNumbering::Strand;
+        if need_positions || parameters.show_domain_boundaries {
+            for s_id in design.get_all_strand_ids() {
+                let strand = if let Some(strand) = design.get_raw_strand(s_id) {
+                    strand
+                } else {
+                    continue;
+                };
+                let mut idx = 0;
+                for domain in strand.domains.iter() {
+                    match domain {
+                        Domain::HelixDomain(interval) => {
+                            domain_ends.insert(interval.prime5());
+                            domain_ends.insert(interval.prime3());
+                            for position in interval.iter() {
+                                if need_positions {
+                                    strand_positions.insert(
+                                        Nucl {
+                                            helix: interval.helix,
+                                            position,
+                                            forward: interval.forward,
+                                        },
+                                        idx,
+                                    );
+                                }
+                                idx += 1;
+                            }
+                        }
+                        domain => idx += domain.length(),
+                    }
+                }
+            }
+        }
+        if !parameters.show_domain_boundaries {
+            domain_ends.clear();
+        }
+        self.view.borrow_mut().set_numbering(Numbering {
+            parameters,
+            strand_positions: Arc::new(strand_positions),
+            domain_ends: Arc::new(domain_ends),
+        });
+    }
+
     pub fn id_map(&self) -> &FlatHelixMaps {
         self.design.id_map()
     }
@@ -201,24 +343,38 @@ impl<R: DesignReader> Data<R> {
                 _ => (),
             }
         }
+        let color_vision_mode = new_state.get_color_vision_mode();
+        let selected_color = color_vision_mode.selected_color();
+        let candidate_color = color_vision_mode.candidate_color();
         let mut selection_highlight = Vec::new();
         let mut candidate_highlight = Vec::new();
         for s in self.design.get_strands().iter() {
             if selected_strands.contains(&s.id) {
                 selection_highlight
-                    .push(s.highlighted(SELECTED_COLOR, SELECTED_STRAND_HIGHLIGHT_FACTOR_2D));
+                    .push(s.highlighted(selected_color, SELECTED_STRAND_HIGHLIGHT_FACTOR_2D));
             }
             if candidate_strands.contains(&s.id) {
                 candidate_highlight
-                    .push(s.highlighted(CANDIDATE_COLOR, CANDIDATE_STRAND_HIGHLIGHT_FACTOR_2D));
+                    .push(s.highlighted(candidate_color, CANDIDATE_STRAND_HIGHLIGHT_FACTOR_2D));
             }
         }
         for xover in selected_xovers.iter() {
-            selection_highlight.push(self.design.strand_from_xover(xover, SELECTED_COLOR, true));
+            selection_highlight.push(self.design.strand_from_xover(xover, selected_color, true));
         }
         for xover in candidate_xovers.iter() {
-            candidate_highlight.push(self.design.strand_from_xover(xover, CANDIDATE_COLOR, true));
+            candidate_highlight.push(self.design.strand_from_xover(xover, candidate_color, true));
+        }
+        if color_vision_mode.uses_patterns() {
+            // The candidates are dashed so that they can be told apart from the selection
+            // without relying on their color.
+            candidate_highlight = candidate_highlight
+                .iter()
+                .flat_map(Strand::dashed)
+                .collect();
         }
+        self.view
+            .borrow_mut()
+            .set_color_vision_mode(color_vision_mode);
         self.view
             .borrow_mut()
             .update_selection(&selection_highlight, &self.helices);
@@ -264,7 +420,7 @@ impl<R: DesignReader> Data<R> {
                 segment_idx: h.segment_idx,
             };
             if let Some(flat_helix) = FlatHelix::from_real(segment, id_map) {
-                self.helices.push(Helix::new(
+                let mut helix = Helix::new(
                     h.left,
                     h.right,
                     h.isometry,
@@ -272,15 +428,40 @@ impl<R: DesignReader> Data<R> {
                     h.id,
                     h.visible,
                     h.abscissa_converter.clone(),
-                ));
+                );
+                helix.display_number = h.display_number;
+                self.helices.push(helix);
                 self.nb_helices_created += 1;
             }
         }
+        let heat_map = self.heat_map.map(|kind| design.get_heat_map(kind));
+        for helix in self.helices.iter_mut() {
+            helix.ruler_interval = self.ruler_interval;
+            helix.heat_map = heat_map
+                .as_ref()
+                .and_then(|values| values.get(&helix.real_id).cloned())
+                .unwrap_or_default();
+            helix.xover_guides = if self.xover_guides.show_guides {
+                design
+                    .get_helix_grid_type(helix.real_id)
+                    .as_ref()
+                    .and_then(XoverGuideParameters::periods)
+                    .map(|(period, major_period)| XoverGuides {
+                        period,
+                        major_period,
+                        register_offset: self.xover_guides.register_offset,
+                    })
+            } else {
+                None
+            };
+        }
+        self.spatial_index.update(&self.helices);
         let suggestions = self.design.suggestions();
         self.update_suggestion(&suggestions);
-        self.view
-            .borrow_mut()
-            .set_suggestions(self.design.suggestions());
+        self.view.borrow_mut().set_suggestions(
+            self.design.suggestions_with_distance(),
+            self.suggestion_parameters.clone(),
+        );
         self.view
             .borrow_mut()
             .set_torsions(self.design.get_torsions());
@@ -308,13 +489,24 @@ impl<R: DesignReader> Data<R> {
     }
 
     fn get_click_(&self, x: f32, y: f32, camera: &CameraPtr, bounded: bool) -> ClickResult {
-        for h in self.helices.iter() {
+        // An unbounded click can hit a helix arbitrarily far from its nucleotides, so the spatial
+        // index cannot be used in that case.
+        let candidates: Vec<&Helix> = if bounded {
+            self.spatial_index
+                .helices_at(Vec2::new(x, y))
+                .iter()
+                .map(|idx| &self.helices[FlatIdx(*idx)])
+                .collect()
+        } else {
+            self.helices.iter().collect()
+        };
+        for h in candidates.iter() {
             if h.click_on_circle(x, y, camera) {
                 let translation_pivot = h.get_circle_pivot(camera).unwrap();
                 return ClickResult::CircleWidget { translation_pivot };
             }
         }
-        for h in self.helices.iter() {
+        for h in candidates.iter() {
             if let Some(handle) = h.click_on_handle(x, y) {
                 return ClickResult::HelixHandle {
                     h_id: h.flat_id,
@@ -322,7 +514,7 @@ impl<R: DesignReader> Data<R> {
                 };
             }
         }
-        for h in self.helices.iter() {
+        for h in candidates.iter() {
             let ret = h
                 .get_click(x, y, bounded)
                 .map(|(position, forward)| FlatNucl {
@@ -448,12 +640,14 @@ impl<R: DesignReader> Data<R> {
 
     pub fn move_handle(&mut self, helix: FlatHelix, handle: HelixHandle, position: Vec2) {
         let (left, right) = self.helices[helix.flat].move_handle(handle, position);
+        self.spatial_index.update(&self.helices);
         self.design.update_helix(helix, left, right);
         self.instance_update = true;
     }
 
     pub fn auto_redim_helix(&mut self, helix: FlatHelix, handle: HelixHandle) {
         let (left, right) = self.helices[helix.flat].reset_handle(handle);
+        self.spatial_index.update(&self.helices);
         self.design.update_helix(helix, left, right);
     }
 
@@ -489,6 +683,7 @@ impl<R: DesignReader> Data<R> {
                 self.design.update_helix(h.flat_id, left, right);
             }
         }
+        self.spatial_index.update(&self.helices);
         self.notify_update();
     }
 
@@ -704,7 +899,10 @@ impl<R: DesignReader> Data<R> {
             if !new_selection.is_empty() {
                 return GraphicalSelection::selection_only(new_selection);
             }
-        } else if selection_mode == SelectionMode::Nucleotide {
+        } else if matches!(
+            selection_mode,
+            SelectionMode::Nucleotide | SelectionMode::BasePair
+        ) {
             self.select_xovers_rectangle(camera, c1, c2, adding, &mut new_selection);
             if !new_selection.is_empty() {
                 return GraphicalSelection::selection_only(new_selection);
@@ -1069,7 +1267,7 @@ impl<R: DesignReader> Data<R> {
                     .get_strand_from_eid(n_id)
                     .map(|s| Selection::Strand(phantom.design_id, s as u32)),
                 SelectionMode::Design => None,
-                SelectionMode::Nucleotide => {
+                SelectionMode::Nucleotide | SelectionMode::BasePair => {
                     Some(Selection::Nucleotide(phantom.design_id, phantom.to_nucl()))
                 }
             }
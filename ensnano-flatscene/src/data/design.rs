@@ -22,12 +22,13 @@ use super::super::{FlatHelix, FlatIdx, FlatNucl, HelixSegment, Requests};
 use super::{Flat, HelixVec, Nucl, Strand};
 use ahash::RandomState;
 use ensnano_design::{
-    ultraviolet, AbscissaConverter, Extremity, Helix as DesignHelix, HelixCollection,
-    Strand as StrandDesign,
+    grid::GridTypeDescr, heat_map::HeatMapKind, ultraviolet, AbscissaConverter, Extremity,
+    Helix as DesignHelix, HelixCollection, HelixParameters, Strand as StrandDesign,
 };
 use ensnano_interactor::consts::{
     CANDIDATE_STRAND_HIGHLIGHT_FACTOR_2D, SELECTED_STRAND_HIGHLIGHT_FACTOR_2D,
 };
+use ensnano_interactor::graphics::ColorVisionMode;
 use ensnano_interactor::{torsion::Torsion, Referential};
 use ensnano_utils::full_isometry::FullIsometry;
 use ultraviolet::{Isometry2, Rotor2, Vec2, Vec3};
@@ -165,6 +166,29 @@ impl<R: DesignReader> Design2d<R> {
             .collect()
     }
 
+    /// The suggested cross-overs together with the distance, in nanometers, between their
+    /// nucleotides
+    pub fn suggestions_with_distance(&self) -> Vec<(FlatNucl, FlatNucl, f32)> {
+        let suggestions = self.design.get_suggestions();
+        suggestions
+            .iter()
+            .filter_map(|(n1, n2)| {
+                let flat = FlatNucl::from_real(n1, &self.id_map)
+                    .zip(FlatNucl::from_real(n2, &self.id_map))?;
+                let distance = self
+                    .design
+                    .get_position_of_nucl_on_helix(*n1, Referential::Model, false)
+                    .zip(
+                        self.design
+                            .get_position_of_nucl_on_helix(*n2, Referential::Model, false),
+                    )
+                    .map(|(a, b)| (a - b).mag())
+                    .unwrap_or(0.);
+                Some((flat.0, flat.1, distance))
+            })
+            .collect()
+    }
+
     fn rm_deleted_helices(&mut self) {
         let mut to_remove = Vec::new();
         for (segment, h) in self.id_map.iter() {
@@ -298,6 +322,7 @@ impl<R: DesignReader> Design2d<R> {
                     .design
                     .get_visibility_helix(segment.helix_idx)
                     .unwrap_or(false),
+                display_number: self.design.get_helix_display_number(segment.helix_idx),
                 abscissa_converter: Arc::new(self.design.get_abscissa_converter(segment.helix_idx)),
             });
         } else {
@@ -307,6 +332,7 @@ impl<R: DesignReader> Design2d<R> {
             helix2d.isometry = FullIsometry::from_isommetry_symmetry(isometry, symmetry);
             helix2d.abscissa_converter =
                 Arc::new(self.design.get_abscissa_converter(segment.helix_idx));
+            helix2d.display_number = self.design.get_helix_display_number(segment.helix_idx);
         }
     }
 
@@ -318,6 +344,13 @@ impl<R: DesignReader> Design2d<R> {
         &self.strands
     }
 
+    /// Replace the colors of the strands by the ones used in `color_vision_mode`.
+    pub fn remap_strand_colors(&mut self, color_vision_mode: ColorVisionMode) {
+        for strand in self.strands.iter_mut() {
+            strand.color = color_vision_mode.strand_color(strand.color);
+        }
+    }
+
     pub fn get_pasted_strand(&self) -> &[Strand] {
         &self.pasted_strands
     }
@@ -393,6 +426,52 @@ impl<R: DesignReader> Design2d<R> {
         self.design.get_identifier_nucl(&nucl).is_some()
     }
 
+    /// Return true if a double strand going from `start` to `start + length` can be added on
+    /// helix `helix`.
+    pub fn double_strand_fits(&self, helix: usize, start: isize, length: usize) -> bool {
+        length > 0
+            && (start..(start + length as isize)).all(|position| {
+                [false, true].iter().all(|forward| {
+                    !self.has_nucl(Nucl {
+                        helix,
+                        position,
+                        forward: *forward,
+                    })
+                })
+            })
+    }
+
+    /// The portions of the flat helices covered by a double strand going from `start` to
+    /// `start + length` on helix `helix`, given by their leftmost and rightmost nucleotides.
+    pub fn double_strand_segments(
+        &self,
+        helix: usize,
+        start: isize,
+        length: usize,
+    ) -> Vec<(FlatNucl, FlatNucl)> {
+        let mut segments: Vec<(FlatNucl, FlatNucl)> = Vec::new();
+        for forward in [false, true] {
+            for position in start..(start + length as isize) {
+                let nucl = Nucl {
+                    helix,
+                    position,
+                    forward,
+                };
+                if let Some(flat_nucl) = FlatNucl::from_real(&nucl, &self.id_map) {
+                    match segments.last_mut() {
+                        Some((first, last))
+                            if first.helix == flat_nucl.helix && first.forward == forward =>
+                        {
+                            *last = flat_nucl
+                        }
+                        _ => segments.push((flat_nucl, flat_nucl)),
+                    }
+                }
+            }
+        }
+        segments
+    }
+
     pub fn get_strand_id(&self, nucl: Nucl) -> Option<usize> {
         self.design.get_id_of_strand_containing_nucl(&nucl)
     }
@@ -486,6 +565,8 @@ pub struct Helix2d {
     pub min_left: Option<isize>,
     pub isometry: FullIsometry,
     pub visible: bool,
+    /// The number with which the helix is displayed to the user
+    pub display_number: usize,
     pub abscissa_converter: Arc<AbscissaConverter>,
     pub segment_idx: usize,
 }
@@ -529,6 +610,8 @@ pub trait DesignReader: 'static {
     fn get_insertions(&self, s_id: usize) -> Option<Vec<Nucl>>;
     fn get_copy_points(&self) -> Vec<Vec<Nucl>>;
     fn get_visibility_helix(&self, h_id: usize) -> Option<bool>;
+    /// Return the number with which helix `h_id` is displayed to the user.
+    fn get_helix_display_number(&self, h_id: usize) -> usize;
     fn get_suggestions(&self) -> Vec<(Nucl, Nucl)>;
     fn has_helix(&self, h_id: usize) -> bool;
     fn get_isometry(&self, h_id: usize, segment_idx: usize) -> Option<Isometry2>;
@@ -559,6 +642,11 @@ pub trait DesignReader: 'static {
     fn get_strand_ends(&self) -> Vec<Nucl>;
     fn get_nucl_collection(&self) -> Arc<Self::NuclCollection>;
     fn get_abscissa_converter(&self, h_id: usize) -> AbscissaConverter;
+    fn get_helix_parameters(&self) -> HelixParameters;
+    /// The type of the grid on which the helix `h_id` lies, if any
+    fn get_helix_grid_type(&self, h_id: usize) -> Option<GridTypeDescr>;
+    /// For each helix, the values of the heat map `kind` at the positions covered by strands
+    fn get_heat_map(&self, kind: HeatMapKind) -> BTreeMap<usize, Vec<(isize, f32)>>;
 }
 
 pub trait NuclCollection {
@@ -25,6 +25,7 @@ use ahash::RandomState;
 use ensnano_design::ultraviolet;
 use ensnano_design::Nucl;
 use ensnano_interactor::consts::*;
+use ensnano_interactor::graphics::{NucleotideNumbering, NumberingParameters};
 use ensnano_utils::{
     chars2d::{Line, Sentence, TextDrawer},
     full_isometry::FullIsometry,
@@ -37,13 +38,17 @@ use lyon::tessellation;
 use lyon::tessellation::{
     FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor,
 };
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use ultraviolet::{Mat2, Rotor2, Vec2, Vec4};
 
 type Vertices = lyon::tessellation::VertexBuffers<GpuVertex, u16>;
 
 const CIRCLE_WIDGET_RADIUS: f32 = 1.5;
+/// The length of the ruler's ticks drawn above the helices.
+const RULER_TICK_LENGTH: f32 = 0.4;
+/// The length by which the cross-over guides overhang the helices.
+const XOVER_GUIDE_OVERHANG: f32 = 0.3;
 const ZOOM_THRESHOLD: f32 = 7.0;
 
 #[derive(Debug, Clone)]
@@ -60,8 +65,29 @@ pub struct Helix {
     /// The position of self in the Helix vector of the design
     pub flat_id: FlatHelix,
     pub real_id: usize,
+    /// The number with which the helix is displayed to the user
+    pub display_number: usize,
     pub visible: bool,
     abscissa_converter: Arc<AbscissaConverter>,
+    /// If some, a tick is drawn above the helix every `ruler_interval` nucleotides
+    pub ruler_interval: Option<isize>,
+    /// If some, guide lines are drawn across the helix at the positions where cross-overs can be
+    /// made
+    pub xover_guides: Option<XoverGuides>,
+    /// The values, between 0 and 1, of the heat map drawn over the helix at each position
+    pub heat_map: Vec<(isize, f32)>,
+}
+
+/// The positions at which guide lines are drawn across a helix to show where cross-overs can be
+/// made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XoverGuides {
+    /// A guide is drawn every `period` nucleotides
+    pub period: isize,
+    /// Every `major_period` nucleotides, the guide is emphasized
+    pub major_period: isize,
+    /// The position of the first emphasized guide
+    pub register_offset: isize,
 }
 
 impl Flat for Helix {}
@@ -96,11 +122,15 @@ impl Helix {
             stroke_width: 0.01,
             flat_id,
             real_id,
+            display_number: real_id,
             visible,
             abscissa_converter: Arc::new(AbscissaConverter {
                 converter: abscissa_converter_,
                 left: flat_id.segment_left,
             }),
+            ruler_interval: None,
+            xover_guides: None,
+            heat_map: vec![],
         }
     }
 
@@ -109,6 +139,7 @@ impl Helix {
         self.right = self.right.max(helix2d.right);
         self.visible = helix2d.visible;
         self.real_id = helix2d.id;
+        self.display_number = helix2d.display_number;
         let left;
         let segment = HelixSegment {
             helix_idx: helix2d.id,
@@ -198,6 +229,16 @@ impl Helix {
         builder.begin(Point::new(left, 1.));
         builder.line_to(Point::new(right, 1.));
         builder.end(false);
+        if let Some(interval) = self.ruler_interval {
+            for i in (self.left..=self.right).filter(|i| i.rem_euclid(interval) == 0) {
+                let x = self
+                    .abscissa_converter
+                    .nucl_to_x_convertion(FlatPosition::from_real(i, self.flat_id.segment_left));
+                builder.begin(Point::new(x as f32, -RULER_TICK_LENGTH));
+                builder.line_to(Point::new(x as f32, 0.));
+                builder.end(false);
+            }
+        }
         let path = builder.build();
         stroke_tess
             .tessellate_path(
@@ -215,6 +256,53 @@ impl Helix {
         vertices
     }
 
+    /// The end points of the cross-over guides drawn across the helix and whether each guide is
+    /// emphasized.
+    pub fn xover_guide_lines(&self) -> Vec<(Vec2, Vec2, bool)> {
+        let guides = if let Some(guides) = self.xover_guides {
+            guides
+        } else {
+            return vec![];
+        };
+        let transform = self.isometry.into_homogeneous_matrix();
+        let mut ret = Vec::new();
+        for i in self.left..=self.right {
+            let register = i - guides.register_offset;
+            if register.rem_euclid(guides.period) == 0 {
+                let x = self
+                    .abscissa_converter
+                    .nucl_to_x_convertion(FlatPosition::from_real(i, self.flat_id.segment_left))
+                    as f32;
+                let top =
+                    transform.transform_point2(self.scale * Vec2::new(x, -XOVER_GUIDE_OVERHANG));
+                let bottom = transform
+                    .transform_point2(self.scale * Vec2::new(x, 2. + XOVER_GUIDE_OVERHANG));
+                ret.push((top, bottom, register.rem_euclid(guides.major_period) == 0));
+            }
+        }
+        ret
+    }
+
+    /// The segments, along the axis of the helix, that are colored by the heat map and their
+    /// value.
+    pub fn heat_map_segments(&self) -> Vec<(Vec2, Vec2, f32)> {
+        let transform = self.isometry.into_homogeneous_matrix();
+        let x = |i: isize| {
+            self.abscissa_converter
+                .nucl_to_x_convertion(FlatPosition::from_real(i, self.flat_id.segment_left))
+                as f32
+        };
+        self.heat_map
+            .iter()
+            .filter(|(i, _)| (self.left..=self.right).contains(i))
+            .map(|(i, value)| {
+                let start = transform.transform_point2(self.scale * Vec2::new(x(*i), 1.));
+                let end = transform.transform_point2(self.scale * Vec2::new(x(*i + 1), 1.));
+                (start, end, *value)
+            })
+            .collect()
+    }
+
     pub fn model(&self) -> HelixModel {
         let mut rotation = self.isometry.rotation.into_matrix();
         rotation[0] *= self.isometry.symmetry.x;
@@ -526,6 +614,32 @@ impl Helix {
         ]
     }
 
+    /// Return the lower left and upper right corners of an axis aligned rectangle containing
+    /// every clickable part of the helix: its nucleotides, its handles and its circle widget.
+    pub fn clickable_bounding_box(&self) -> (Vec2, Vec2) {
+        let margin = 1. + 4. * CIRCLE_WIDGET_RADIUS;
+        let (left, right) = (self.leftmost_x() - margin, self.rightmost_x() + margin);
+        let (bottom, top) = (
+            1. - 2. * CIRCLE_WIDGET_RADIUS,
+            1. + 2. * CIRCLE_WIDGET_RADIUS,
+        );
+        let transform = self.isometry.into_homogeneous_matrix();
+        let corners = [
+            Vec2::new(left, bottom),
+            Vec2::new(left, top),
+            Vec2::new(right, bottom),
+            Vec2::new(right, top),
+        ]
+        .map(|p| transform.transform_point2(self.scale * p));
+        let min = corners
+            .iter()
+            .fold(corners[0], |acc, p| acc.min_by_component(*p));
+        let max = corners
+            .iter()
+            .fold(corners[0], |acc, p| acc.max_by_component(*p));
+        (min, max)
+    }
+
     fn leftmost_x(&self) -> f32 {
         self.abscissa_converter
             .nucl_to_x_convertion(self.get_flat_left()) as f32
@@ -740,6 +854,54 @@ pub struct CharCollector<'a> {
     pub edition_info: &'a Option<EditionInfo>,
     pub hovered_nucl: &'a Option<FlatNucl>,
     pub nucl_collection: &'a dyn NuclCollection,
+    pub numbering: &'a Numbering,
+}
+
+/// The information needed to write the nucleotide positions above the helices.
+#[derive(Clone, Default, PartialEq)]
+pub struct Numbering {
+    pub parameters: NumberingParameters,
+    /// The index of each nucleotide on its strand, starting from the 5' end.
+    pub strand_positions: Arc<HashMap<Nucl, usize, RandomState>>,
+    /// The first and last nucleotide of each domain.
+    pub domain_ends: Arc<HashSet<Nucl, RandomState>>,
+}
+
+impl Numbering {
+    fn is_domain_end(&self, helix: usize, position: isize) -> bool {
+        self.domain_ends.contains(&Nucl {
+            helix,
+            position,
+            forward: true,
+        }) || self.domain_ends.contains(&Nucl {
+            helix,
+            position,
+            forward: false,
+        })
+    }
+
+    fn should_print(&self, helix: usize, position: isize) -> bool {
+        let interval = self.parameters.interval.max(1);
+        (self.parameters.show_numbers && position.rem_euclid(interval) == 0)
+            || (self.parameters.show_domain_boundaries && self.is_domain_end(helix, position))
+    }
+
+    /// The text to write above `position`, or `None` if nothing should be written there.
+    fn label(&self, helix: usize, position: isize) -> Option<String> {
+        match self.parameters.numbering {
+            NucleotideNumbering::Helix => Some(position.to_string()),
+            NucleotideNumbering::Strand => [true, false]
+                .iter()
+                .find_map(|forward| {
+                    self.strand_positions.get(&Nucl {
+                        helix,
+                        position,
+                        forward: *forward,
+                    })
+                })
+                .map(|idx| (idx + 1).to_string()),
+        }
+    }
 }
 
 impl Helix {
@@ -765,7 +927,7 @@ impl Helix {
         let rotation = camera.borrow().rotation().reversed();
         let symetry = camera.borrow().get_globals().symmetry;
         if let Some(circle) = circle {
-            let text = self.real_id.to_string();
+            let text = self.display_number.to_string();
             let sentence = Sentence {
                 text: &text,
                 size: size_id / text.len() as f32 * zoom_font,
@@ -787,13 +949,13 @@ impl Helix {
             .as_ref()
             .filter(|info| info.nucl.helix == self.flat_id)
             .map(|info| info.nucl.flat_position.to_real(self.flat_id.segment_left));
-        let mut print_pos = |pos: isize| {
+        let numbering = char_collector.numbering;
+        let mut print_pos = |pos: isize, text: String| {
             let color = if Some(pos) == moving_pos || candidate_pos == Some(pos) {
                 [1., 0., 0., 1.].into()
             } else {
                 [0., 0., 0., 1.].into()
             };
-            let text = pos.to_string();
             let flat_pos = FlatPosition::from_real(pos, self.flat_id.segment_left);
             let sentence = Sentence {
                 text: &text,
@@ -815,15 +977,17 @@ impl Helix {
 
         let mut pos = self.left;
         while pos <= self.right {
-            if ((pos >= 0 && pos % 8 == 0) || (pos < 0 && -pos % 8 == 0)) && moving_pos != Some(pos)
-                || candidate_pos == Some(pos)
-            {
-                print_pos(pos);
+            if candidate_pos == Some(pos) {
+                print_pos(pos, pos.to_string());
+            } else if moving_pos != Some(pos) && numbering.should_print(self.real_id, pos) {
+                if let Some(text) = numbering.label(self.real_id, pos) {
+                    print_pos(pos, text);
+                }
             }
             pos += 1;
         }
         if let Some(position) = moving_pos {
-            print_pos(position);
+            print_pos(position, position.to_string());
         }
 
         let mut print_info = |flat_pos: FlatPosition, info: &str| {
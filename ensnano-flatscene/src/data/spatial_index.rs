@@ -0,0 +1,122 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A spatial index of the helices of the 2D view.
+//!
+//! The plane is divided in square cells and each cell knows the helices whose clickable area
+//! intersects it. This allows to find the helices that may be under the cursor without iterating
+//! over all the helices of the design.
+
+use super::Helix;
+use ensnano_design::ultraviolet::Vec2;
+use std::collections::HashMap;
+
+/// The side length of the cells, in world coordinates of the 2D view.
+const CELL_SIZE: f32 = 16.;
+
+type Cell = (i32, i32);
+
+#[derive(Default)]
+pub(super) struct HelixSpatialIndex {
+    /// For each non-empty cell, the sorted list of the flat indices of the helices intersecting it.
+    cells: HashMap<Cell, Vec<usize>>,
+    /// The cells covered by each helix when it was last inserted in the index.
+    covered_cells: Vec<CellRange>,
+}
+
+/// The rectangle of cells covered by a helix, both bounds included.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CellRange {
+    min: Cell,
+    max: Cell,
+}
+
+impl CellRange {
+    fn from_bounding_box((min, max): (Vec2, Vec2)) -> Self {
+        Self {
+            min: cell_of(min),
+            max: cell_of(max),
+        }
+    }
+
+    fn cells(self) -> impl Iterator<Item = Cell> {
+        (self.min.0..=self.max.0).flat_map(move |x| (self.min.1..=self.max.1).map(move |y| (x, y)))
+    }
+}
+
+fn cell_of(point: Vec2) -> Cell {
+    (
+        (point.x / CELL_SIZE).floor() as i32,
+        (point.y / CELL_SIZE).floor() as i32,
+    )
+}
+
+impl HelixSpatialIndex {
+    /// Make the index match `helices`.
+    ///
+    /// Only the helices whose covered cells changed since the last update are moved in the index.
+    pub fn update(&mut self, helices: &[Helix]) {
+        while self.covered_cells.len() > helices.len() {
+            let idx = self.covered_cells.len() - 1;
+            let range = self.covered_cells.pop().unwrap();
+            self.remove(idx, range);
+        }
+        for (idx, h) in helices.iter().enumerate() {
+            let range = CellRange::from_bounding_box(h.clickable_bounding_box());
+            if let Some(old_range) = self.covered_cells.get(idx).cloned() {
+                if old_range != range {
+                    self.remove(idx, old_range);
+                    self.insert(idx, range);
+                    self.covered_cells[idx] = range;
+                }
+            } else {
+                self.insert(idx, range);
+                self.covered_cells.push(range);
+            }
+        }
+    }
+
+    /// Return, in increasing order, the flat indices of the helices that may contain `point`.
+    pub fn helices_at(&self, point: Vec2) -> &[usize] {
+        self.cells
+            .get(&cell_of(point))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn insert(&mut self, idx: usize, range: CellRange) {
+        for cell in range.cells() {
+            let helices = self.cells.entry(cell).or_default();
+            if let Err(pos) = helices.binary_search(&idx) {
+                helices.insert(pos, idx);
+            }
+        }
+    }
+
+    fn remove(&mut self, idx: usize, range: CellRange) {
+        for cell in range.cells() {
+            if let Some(helices) = self.cells.get_mut(&cell) {
+                if let Ok(pos) = helices.binary_search(&idx) {
+                    helices.remove(pos);
+                }
+                if helices.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+}
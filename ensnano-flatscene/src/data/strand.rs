@@ -17,13 +17,16 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 use super::super::view::InsertionInstance;
 use super::helix::{Helix, Shift};
-use super::{CameraPtr, FlatNucl};
-use ensnano_design::ultraviolet::Vec2;
+use super::{CameraPtr, FlatIdx, FlatNucl, FlatPosition};
+use ensnano_design::ultraviolet::{Vec2, Vec4};
+use ensnano_interactor::consts::{GHOST_HELIX_COLOR, SUGGESTION_ARC_NB_DASHES};
+use ensnano_interactor::graphics::XoverDrawingParameters;
 use lyon::math::Point;
 use lyon::path::path::BuilderWithAttributes;
 use lyon::path::Path;
 use lyon::tessellation;
 use lyon::tessellation::{StrokeVertex, StrokeVertexConstructor};
+use std::collections::HashMap;
 
 type Vertices = lyon::tessellation::VertexBuffers<StrandVertex, u16>;
 
@@ -73,6 +76,7 @@ impl Strand {
         free_end: &Option<FreeEnd>,
         my_cam: &CameraPtr,
         other_cam: &CameraPtr,
+        xover_drawing: &XoverDrawing,
     ) -> (Vertices, Vertices) {
         let mut vertices = Vertices::new();
         let mut cross_split_vertices = Vertices::new();
@@ -87,6 +91,7 @@ impl Strand {
             main_camera: my_cam,
             alternative_camera: other_cam,
             free_end: &filtered_free_end,
+            xover_drawing,
         });
         let mut strand_topology_reader = StrandTopologyReader::init(helices);
 
@@ -136,6 +141,32 @@ impl Strand {
         (vertices, cross_split_vertices)
     }
 
+    /// Return the helices linked by each cross-over of the strand, together with the position of
+    /// the extremities of the cross-over.
+    fn xovers(&self, helices: &[Helix]) -> Vec<((FlatIdx, FlatIdx), Vec2, Vec2)> {
+        let mut ret = Vec::new();
+        let mut reader = StrandTopologyReader::init(helices);
+        let mut last_position = None;
+        for nucl in self.points.iter() {
+            match reader.read_nucl(*nucl) {
+                DrawingInstruction::StartAt { position, .. }
+                | DrawingInstruction::LineTo { position, .. } => last_position = Some(position),
+                DrawingInstruction::XoverTo {
+                    to,
+                    helices: linked_helices,
+                    ..
+                } => {
+                    if let Some(from) = last_position {
+                        ret.push((linked_helices, from, to));
+                    }
+                    last_position = Some(to);
+                }
+                DrawingInstruction::FreeEndPrime3(_) => (),
+            }
+        }
+        ret
+    }
+
     pub fn get_insertions(&self, helices: &[Helix]) -> Vec<InsertionInstance> {
         let mut ret = Vec::with_capacity(self.insertions.len());
         for i in self.insertions.iter() {
@@ -146,13 +177,13 @@ impl Strand {
 
     pub fn indication(nucl1: FlatNucl, nucl2: FlatNucl, helices: &[Helix]) -> Vertices {
         let mut vertices = Vertices::new();
-        let mut builder = Path::builder_with_attributes(2);
+        let mut builder = Path::builder_with_attributes(NB_ATTRIBUTES);
         let color = [0.823, 0.525, 0.058, 0.75];
         let start = helices[nucl1.helix].get_nucl_position(&nucl1, Shift::No);
         let end = helices[nucl2.helix].get_nucl_position(&nucl2, Shift::No);
 
-        builder.begin(Point::new(start.x, start.y), &[1e-4, 1.]);
-        builder.line_to(Point::new(end.x, end.y), &[1e-4, 1.]);
+        builder.begin(Point::new(start.x, start.y), &[1e-4, 1., 0.]);
+        builder.line_to(Point::new(end.x, end.y), &[1e-4, 1., 0.]);
         let mut stroke_tess = lyon::tessellation::StrokeTessellator::new();
 
         builder.end(false);
@@ -177,6 +208,185 @@ impl Strand {
         vertices
     }
 
+    /// The suggested cross-overs drawn as dashed arcs between their nucleotides. Each suggestion
+    /// comes with its color.
+    pub fn suggestion_arcs(
+        suggestions: &[(FlatNucl, FlatNucl, u32)],
+        helices: &[Helix],
+    ) -> Vertices {
+        let mut vertices = Vertices::new();
+        let mut stroke_tess = lyon::tessellation::StrokeTessellator::new();
+        for (n1, n2, color) in suggestions.iter() {
+            let (h1, h2) = if let Some(h) = helices.get(n1.helix).zip(helices.get(n2.helix)) {
+                h
+            } else {
+                continue;
+            };
+            if !h1.visible || !h2.visible {
+                continue;
+            }
+            let start = h1.get_nucl_position(n1, Shift::No);
+            let end = h2.get_nucl_position(n2, Shift::No);
+            let dist = (end - start).mag();
+            if dist < f32::EPSILON {
+                continue;
+            }
+            let chord = (end - start) / dist;
+            let control = (start + end) / 2. + 2. * ARC_BULGE * dist * Vec2::new(-chord.y, chord.x);
+            let point =
+                |t: f32| (1. - t) * (1. - t) * start + 2. * t * (1. - t) * control + t * t * end;
+            let nb_pieces = 2 * SUGGESTION_ARC_NB_DASHES - 1;
+            let mut builder = Path::builder_with_attributes(1);
+            for dash in 0..SUGGESTION_ARC_NB_DASHES {
+                let t_start = (2 * dash) as f32 / nb_pieces as f32;
+                let t_end = (2 * dash + 1) as f32 / nb_pieces as f32;
+                let dash_start = point(t_start);
+                builder.begin(point!(dash_start), &[1e-4]);
+                for i in 1..=SUGGESTION_DASH_SUBDIVISIONS {
+                    let t = t_start
+                        + (t_end - t_start) * i as f32 / SUGGESTION_DASH_SUBDIVISIONS as f32;
+                    let position = point(t);
+                    builder.line_to(point!(position), &[1e-4]);
+                }
+                builder.end(false);
+            }
+            let path = builder.build();
+            stroke_tess
+                .tessellate_path(
+                    &path,
+                    &tessellation::StrokeOptions::tolerance(0.01)
+                        .with_start_cap(tessellation::LineCap::Round)
+                        .with_end_cap(tessellation::LineCap::Round),
+                    &mut tessellation::BuffersBuilder::new(
+                        &mut vertices,
+                        GuideAttributes {
+                            color: ensnano_utils::instance::Instance::color_from_au32(*color)
+                                .into(),
+                            width: SUGGESTION_ARC_WIDTH,
+                        },
+                    ),
+                )
+                .expect("Error durring tessellation");
+        }
+        vertices
+    }
+
+    /// The double strand that would be added in helix building mode, given by the leftmost and
+    /// rightmost nucleotides of its portions on each flat helix.
+    pub fn ghost_double_strand(segments: &[(FlatNucl, FlatNucl)], helices: &[Helix]) -> Vertices {
+        let mut vertices = Vertices::new();
+        let mut stroke_tess = lyon::tessellation::StrokeTessellator::new();
+        let mut builder = Path::builder_with_attributes(1);
+        for (first, last) in segments.iter() {
+            let helix = if let Some(helix) = helices.get(first.helix).filter(|h| h.visible) {
+                helix
+            } else {
+                continue;
+            };
+            let start = helix.get_nucl_position(first, Shift::No);
+            let end = helix.get_nucl_position(last, Shift::No);
+            builder.begin(point!(start), &[1e-4]);
+            builder.line_to(point!(end), &[1e-4]);
+            builder.end(false);
+        }
+        let path = builder.build();
+        stroke_tess
+            .tessellate_path(
+                &path,
+                &tessellation::StrokeOptions::tolerance(0.01)
+                    .with_start_cap(tessellation::LineCap::Round)
+                    .with_end_cap(tessellation::LineCap::Round),
+                &mut tessellation::BuffersBuilder::new(
+                    &mut vertices,
+                    GuideAttributes {
+                        color: ensnano_utils::instance::Instance::color_from_au32(
+                            GHOST_HELIX_COLOR,
+                        )
+                        .into(),
+                        width: GHOST_DOUBLE_STRAND_WIDTH,
+                    },
+                ),
+            )
+            .expect("Error durring tessellation");
+        vertices
+    }
+
+    /// The guide lines marking the positions where cross-overs can be made on the helices.
+    pub fn xover_guides(helices: &[Helix]) -> Vertices {
+        let mut vertices = Vertices::new();
+        let mut stroke_tess = lyon::tessellation::StrokeTessellator::new();
+        for major in [false, true] {
+            let mut builder = Path::builder_with_attributes(1);
+            for helix in helices.iter().filter(|h| h.visible) {
+                // The guides are drawn just above the helix, below the strands.
+                let depth = helix.get_depth() + 1e-4;
+                for (top, bottom, _) in helix
+                    .xover_guide_lines()
+                    .into_iter()
+                    .filter(|line| line.2 == major)
+                {
+                    builder.begin(point!(top), &[depth]);
+                    builder.line_to(point!(bottom), &[depth]);
+                    builder.end(false);
+                }
+            }
+            let path = builder.build();
+            let (color, width) = if major {
+                (MAJOR_XOVER_GUIDE_COLOR, MAJOR_XOVER_GUIDE_WIDTH)
+            } else {
+                (XOVER_GUIDE_COLOR, XOVER_GUIDE_WIDTH)
+            };
+            stroke_tess
+                .tessellate_path(
+                    &path,
+                    &tessellation::StrokeOptions::tolerance(0.01),
+                    &mut tessellation::BuffersBuilder::new(
+                        &mut vertices,
+                        GuideAttributes { color, width },
+                    ),
+                )
+                .expect("Error durring tessellation");
+        }
+        vertices
+    }
+
+    /// The heat maps drawn over the helices. The values are grouped in a few color levels.
+    pub fn heat_map(helices: &[Helix]) -> Vertices {
+        let mut vertices = Vertices::new();
+        let mut stroke_tess = lyon::tessellation::StrokeTessellator::new();
+        let mut builders: Vec<_> = (0..NB_HEAT_MAP_LEVELS)
+            .map(|_| Path::builder_with_attributes(1))
+            .collect();
+        for helix in helices.iter().filter(|h| h.visible) {
+            // Like the cross-over guides, the heat map is drawn between the helix and the strands
+            let depth = helix.get_depth() + 1e-4;
+            for (start, end, value) in helix.heat_map_segments() {
+                let level =
+                    ((value * NB_HEAT_MAP_LEVELS as f32) as usize).min(NB_HEAT_MAP_LEVELS - 1);
+                builders[level].begin(point!(start), &[depth]);
+                builders[level].line_to(point!(end), &[depth]);
+                builders[level].end(false);
+            }
+        }
+        for (level, builder) in builders.into_iter().enumerate() {
+            let path = builder.build();
+            stroke_tess
+                .tessellate_path(
+                    &path,
+                    &tessellation::StrokeOptions::tolerance(0.01),
+                    &mut tessellation::BuffersBuilder::new(
+                        &mut vertices,
+                        GuideAttributes {
+                            color: heat_map_color(level),
+                            width: HEAT_MAP_WIDTH,
+                        },
+                    ),
+                )
+                .expect("Error durring tessellation");
+        }
+        vertices
+    }
+
     pub fn highlighted(&self, color: u32, highlight_thickness: f32) -> Self {
         Self {
             color,
@@ -186,8 +396,114 @@ impl Strand {
             ..*self
         }
     }
+
+    /// Split the strand into short pieces so that it is drawn as a dashed line.
+    ///
+    /// The domains of the strand are cut into dashes, the cross-overs are kept in one piece.
+    pub fn dashed(&self) -> Vec<Self> {
+        let mut ret = Vec::new();
+        for (i, pair) in self.points.windows(2).enumerate() {
+            let (start, end) = (pair[0], pair[1]);
+            let is_domain = i % 2 == 0 && start.helix == end.helix && start.forward == end.forward;
+            if !is_domain {
+                ret.push(self.piece(start, end));
+                continue;
+            }
+            let direction = if end.flat_position.0 >= start.flat_position.0 {
+                1
+            } else {
+                -1
+            };
+            let length = (end.flat_position.0 - start.flat_position.0).abs();
+            let mut dash_start = 0;
+            while dash_start <= length {
+                let dash_end = (dash_start + DASH_LENGTH - 1).min(length);
+                let nucl_at = |offset: isize| FlatNucl {
+                    flat_position: FlatPosition(start.flat_position.0 + direction * offset),
+                    ..start
+                };
+                ret.push(self.piece(nucl_at(dash_start), nucl_at(dash_end)));
+                dash_start += DASH_LENGTH + DASH_GAP;
+            }
+        }
+        ret
+    }
+
+    fn piece(&self, start: FlatNucl, end: FlatNucl) -> Self {
+        Self {
+            points: vec![start, end],
+            insertions: Vec::new(),
+            ..*self
+        }
+    }
+}
+
+/// The number of nucleotides in each dash of a dashed strand
+const DASH_LENGTH: isize = 2;
+/// The number of nucleotides between two dashes of a dashed strand
+const DASH_GAP: isize = 2;
+
+/// The parameters used to draw the cross-overs, together with the bundles that they form.
+#[derive(Default)]
+pub struct XoverDrawing {
+    pub parameters: XoverDrawingParameters,
+    /// The mean of the middle of the cross-overs linking each pair of helices
+    bundle_centers: HashMap<(FlatIdx, FlatIdx), Vec2>,
+}
+
+/// The height of the arcs relatively to the distance between their extremities
+const ARC_BULGE: f32 = 0.25;
+/// How much the cross-overs of a bundle are pulled towards the center of the bundle
+const BUNDLING_STRENGTH: f32 = 0.8;
+/// Cross-overs shorter than this are drawn with `SHORT_XOVER_COLOR`
+const SHORT_XOVER_LENGTH: f32 = 3.;
+/// Cross-overs longer than this are drawn with `LONG_XOVER_COLOR`
+const LONG_XOVER_LENGTH: f32 = 40.;
+const SHORT_XOVER_COLOR: [f32; 4] = [0.13, 0.4, 0.85, 1.];
+const LONG_XOVER_COLOR: [f32; 4] = [0.9, 0.15, 0.1, 1.];
+
+impl XoverDrawing {
+    pub fn new(parameters: XoverDrawingParameters, strands: &[Strand], helices: &[Helix]) -> Self {
+        let mut bundle_centers = HashMap::new();
+        if parameters.bundling {
+            let mut sums: HashMap<(FlatIdx, FlatIdx), (Vec2, f32)> = HashMap::new();
+            for strand in strands.iter() {
+                for (linked_helices, from, to) in strand.xovers(helices) {
+                    let sum = sums.entry(linked_helices).or_insert((Vec2::zero(), 0.));
+                    sum.0 += (from + to) / 2.;
+                    sum.1 += 1.;
+                }
+            }
+            bundle_centers = sums
+                .into_iter()
+                .map(|(linked_helices, (sum, nb))| (linked_helices, sum / nb))
+                .collect();
+        }
+        Self {
+            parameters,
+            bundle_centers,
+        }
+    }
+
+    fn bundle_center(&self, helices: (FlatIdx, FlatIdx)) -> Option<Vec2> {
+        self.bundle_centers.get(&helices).cloned()
+    }
 }
 
+/// The color of a cross-over of length `length` when cross-overs are colored according to their
+/// length.
+fn xover_length_color(length: f32) -> [f32; 4] {
+    let t = ((length - SHORT_XOVER_LENGTH) / (LONG_XOVER_LENGTH - SHORT_XOVER_LENGTH))
+        .min(1.)
+        .max(0.);
+    let color = Vec4::from(SHORT_XOVER_COLOR) * (1. - t) + Vec4::from(LONG_XOVER_COLOR) * t;
+    color.into()
+}
+
+/// The number of attributes of the vertices of the paths: the depth, the sign used to compute the
+/// width of the path and the length of the cross-over if it must be colored according to it.
+const NB_ATTRIBUTES: usize = 3;
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct StrandVertex {
@@ -217,7 +533,12 @@ impl StrokeVertexConstructor<StrandVertex> for WithAttributes {
         if let Some(thickness) = self.highlight {
             width *= thickness;
         }
-        let color = self.color;
+        let xover_length = vertex.interpolated_attributes()[2];
+        let color = if self.highlight.is_none() && xover_length > 0. {
+            xover_length_color(xover_length)
+        } else {
+            self.color
+        };
 
         let mut depth = if vertex.interpolated_attributes()[1] > 1.00001 {
             1e-7
@@ -238,6 +559,43 @@ impl StrokeVertexConstructor<StrandVertex> for WithAttributes {
     }
 }
 
+const SUGGESTION_ARC_WIDTH: f32 = 0.3;
+/// The number of segments approximating each dash of a suggested cross-over
+const SUGGESTION_DASH_SUBDIVISIONS: usize = 4;
+const GHOST_DOUBLE_STRAND_WIDTH: f32 = 1.;
+const XOVER_GUIDE_COLOR: [f32; 4] = [0.3, 0.6, 0.3, 0.35];
+const MAJOR_XOVER_GUIDE_COLOR: [f32; 4] = [0.3, 0.6, 0.3, 0.8];
+const XOVER_GUIDE_WIDTH: f32 = 0.25;
+const MAJOR_XOVER_GUIDE_WIDTH: f32 = 0.4;
+
+const NB_HEAT_MAP_LEVELS: usize = 8;
+/// The width of the heat map, chosen so that it covers the two strands of the helices
+const HEAT_MAP_WIDTH: f32 = 8.;
+
+/// The color of a level of the heat map, from blue for the lowest values to red for the highest
+fn heat_map_color(level: usize) -> [f32; 4] {
+    let t = (level as f32 + 0.5) / NB_HEAT_MAP_LEVELS as f32;
+    [t, 0.2, 1. - t, 0.45]
+}
+
+/// Builds the vertices of the cross-over guides. The only attribute of the paths is the depth.
+struct GuideAttributes {
+    color: [f32; 4],
+    width: f32,
+}
+
+impl StrokeVertexConstructor<StrandVertex> for GuideAttributes {
+    fn new_vertex(&mut self, mut vertex: StrokeVertex) -> StrandVertex {
+        StrandVertex {
+            position: vertex.position_on_path().to_array(),
+            normal: vertex.normal().to_array(),
+            color: self.color,
+            depth: vertex.interpolated_attributes()[0],
+            width: self.width,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct FreeEnd {
     pub strand_id: usize,
@@ -326,18 +684,23 @@ struct StrandVertexBuilder<'a> {
     main_builder_is_drawing: bool,
     /// The depth attribute is used to generate the z coordinate of the vertices
     depth: f32,
+    /// The length of the cross-over being drawn if it must be colored according to its length, 0
+    /// otherwise
+    xover_length: f32,
+    xover_drawing: &'a XoverDrawing,
 }
 
 struct StrandVertexBuilderInitializer<'a> {
     main_camera: &'a CameraPtr,
     alternative_camera: &'a CameraPtr,
     free_end: &'a Option<FilteredFreeEnd>,
+    xover_drawing: &'a XoverDrawing,
 }
 
 // We need to use this macro to appease the borrow checker
 macro_rules! attributes {
     ($self: ident) => {
-        &[$self.depth, $self.sign]
+        &[$self.depth, $self.sign, $self.xover_length]
     };
 }
 
@@ -347,13 +710,14 @@ struct MainXoverDescriptor {
     target: Vec2,
     normal_source: Vec2,
     normal_target: Vec2,
+    bundle_center: Option<Vec2>,
 }
 
 impl<'a> StrandVertexBuilder<'a> {
     /// Initialise the builder.
     pub fn init(initializer: StrandVertexBuilderInitializer<'a>) -> Self {
-        let main_path_builder = Path::builder_with_attributes(2);
-        let splited_cross_over_builder = Path::builder_with_attributes(2);
+        let main_path_builder = Path::builder_with_attributes(NB_ATTRIBUTES);
+        let splited_cross_over_builder = Path::builder_with_attributes(NB_ATTRIBUTES);
         let last_point = Self::read_free_end(&initializer);
 
         Self {
@@ -365,6 +729,8 @@ impl<'a> StrandVertexBuilder<'a> {
             alternative_camera: initializer.alternative_camera,
             main_builder_is_drawing: false,
             depth: 0.0,
+            xover_length: 0.0,
+            xover_drawing: initializer.xover_drawing,
         }
     }
 
@@ -406,6 +772,7 @@ impl<'a> StrandVertexBuilder<'a> {
                 normal_target,
                 to,
                 depth_to,
+                helices,
             } => {
                 // We use the smallest depth between the two extremities to be above both helices
                 self.depth = self.depth.min(depth_to);
@@ -414,20 +781,32 @@ impl<'a> StrandVertexBuilder<'a> {
                 {
                     self.stop_drawing();
                     self.splited_cross_over_builder
-                        .begin(Point::new(from.x, from.y), &[self.depth, 5.0]);
+                        .begin(Point::new(from.x, from.y), &[self.depth, 5.0, 0.0]);
                     self.splited_cross_over_builder
-                        .line_to(Point::new(to.x, to.y), &[self.depth, 5.0]);
+                        .line_to(Point::new(to.x, to.y), &[self.depth, 5.0, 0.0]);
                     self.splited_cross_over_builder.end(false);
                 } else {
                     let origin = self.last_point.expect("last point");
                     if self.can_see(to) || self.can_see(origin) {
+                        let color_by_length = self.xover_drawing.parameters.color_by_length;
+                        if color_by_length {
+                            // The cross-over is drawn in its own sub-path so that its color is
+                            // not interpolated with the one of the domains that it links
+                            self.stop_drawing();
+                            self.xover_length = (to - origin).mag().max(f32::EPSILON);
+                        }
                         self.start_drawing_on(origin);
                         self.draw_xover_with_main_builder(MainXoverDescriptor {
                             target: to,
                             origin,
                             normal_source,
                             normal_target,
-                        })
+                            bundle_center: self.xover_drawing.bundle_center(helices),
+                        });
+                        if color_by_length {
+                            self.stop_drawing();
+                            self.xover_length = 0.0;
+                        }
                     } else {
                         // We do not draw cross overs whose extremities are both out of sight
                         self.stop_drawing()
@@ -451,15 +830,38 @@ impl<'a> StrandVertexBuilder<'a> {
         let dist = (xover.target - xover.origin).mag();
         let normal_1 = (xover.normal_source - xover.origin).normalized();
         let normal_2 = (xover.normal_target - xover.target).normalized();
-        let control_1 = xover.origin + (dist.sqrt() / 2.) * normal_1;
-        let control_2 = xover.target + (dist.sqrt() / 2.) * normal_2;
+        let middle = (xover.origin + xover.target) / 2.;
+        // When bundling, all the cross-overs linking the same two helices are pulled towards
+        // the same point
+        let bundle_shift = xover
+            .bundle_center
+            .map(|center| (center - middle) * BUNDLING_STRENGTH)
+            .unwrap_or_else(Vec2::zero);
         let target = xover.target;
-        self.main_path_builder.cubic_bezier_to(
-            point!(control_1),
-            point!(control_2),
-            point!(target),
-            attributes!(self),
-        );
+        if self.xover_drawing.parameters.arcs && dist > 0. {
+            let chord = (xover.target - xover.origin) / dist;
+            let mut bulge = Vec2::new(-chord.y, chord.x);
+            if bulge.dot(normal_1 + normal_2) < 0. {
+                bulge = -bulge;
+            }
+            // The middle of a quadratic bezier curve is half way between its control point and
+            // the middle of its extremities
+            let control = middle + 2. * ARC_BULGE * dist * bulge + 2. * bundle_shift;
+            self.main_path_builder.quadratic_bezier_to(
+                point!(control),
+                point!(target),
+                attributes!(self),
+            );
+        } else {
+            let control_1 = xover.origin + (dist.sqrt() / 2.) * normal_1 + bundle_shift;
+            let control_2 = xover.target + (dist.sqrt() / 2.) * normal_2 + bundle_shift;
+            self.main_path_builder.cubic_bezier_to(
+                point!(control_1),
+                point!(control_2),
+                point!(target),
+                attributes!(self),
+            );
+        }
     }
 
     fn can_see(&self, point: Vec2) -> bool {
@@ -575,11 +977,17 @@ impl<'a> StrandTopologyReader<'a> {
         let normal_target =
             self.helices[nucl.helix].get_nucl_position(&nucl.prime3(), Shift::Prime5Outsided);
         let to = self.helices[nucl.helix].get_nucl_position(&nucl, Shift::Prime5);
+        let helices = if last_nucl.helix.flat <= nucl.helix.flat {
+            (last_nucl.helix.flat, nucl.helix.flat)
+        } else {
+            (nucl.helix.flat, last_nucl.helix.flat)
+        };
         DrawingInstruction::XoverTo {
             normal_source,
             normal_target,
             to,
             depth_to: self.get_depth(nucl),
+            helices,
         }
     }
 
@@ -625,6 +1033,8 @@ enum DrawingInstruction {
         normal_target: Vec2,
         to: Vec2,
         depth_to: f32,
+        /// The helices linked by the cross-over, in increasing order
+        helices: (FlatIdx, FlatIdx),
     },
     /// End the drawing by drawing a free end
     FreeEndPrime3(Vec2),
@@ -33,9 +33,12 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 
 use ensnano_design::{consts::ITERATIVE_AXIS_ALGORITHM, Nucl};
 use ensnano_interactor::{
-    application::{AppId, Application, Duration, Notification},
+    application::{AppId, Application, Camera2DState, Duration, Notification},
     consts::{EXPORT_2D_MARGIN, EXPORT_2D_MAX_SIZE},
-    graphics::DrawArea,
+    graphics::{
+        ColorVisionMode, DrawArea, NumberingParameters, ScaleBarParameters, Theme,
+        XoverDrawingParameters, XoverGuideParameters,
+    },
     operation::*,
     ActionMode, DesignOperation, PhantomElement, Selection, SelectionMode, StrandBuilder,
     StrandBuildingStatus,
@@ -56,11 +59,13 @@ use winit::event::WindowEvent;
 
 use ensnano_utils::camera2d;
 mod controller;
+mod cross_section;
 mod data;
 mod flattypes;
 mod view;
 pub use camera2d::{Camera2D, FitRectangle};
 use controller::Controller;
+pub use cross_section::CrossSection;
 use data::Data;
 pub use data::{DesignReader, NuclCollection};
 use flattypes::*;
@@ -331,10 +336,28 @@ impl<S: AppState> FlatScene<S> {
                 } else {
                     phantom.map(Selection::Phantom)
                 };
-                self.requests
-                    .lock()
-                    .unwrap()
-                    .new_candidates(candidate.iter().cloned().collect())
+                // The hovered nucleotide is always a candidate so that it is highlighted in the
+                // 3D view as well
+                let nucl_candidate = phantom
+                    .and_then(|p| {
+                        self.data[self.selected_design]
+                            .borrow()
+                            .phantom_to_selection(p, SelectionMode::Nucleotide)
+                    })
+                    .filter(|nucl| Some(nucl) != candidate.as_ref());
+                self.requests.lock().unwrap().new_candidates(
+                    candidate
+                        .iter()
+                        .chain(nucl_candidate.iter())
+                        .cloned()
+                        .collect(),
+                )
+            }
+            Consequence::CenterOtherView(nucl) => {
+                self.requests.lock().unwrap().request_center_selection(
+                    Selection::Nucleotide(self.selected_design as u32, nucl.to_real()),
+                    AppId::FlatScene,
+                )
             }
             Consequence::Built => {
                 self.requests.lock().unwrap().suspend_op();
@@ -402,6 +425,20 @@ impl<S: AppState> FlatScene<S> {
             Consequence::SelectionChanged(selection) => {
                 self.requests.lock().unwrap().new_selection(selection);
             }
+            Consequence::ContextMenu(click) => {
+                let mut new_selection = Vec::new();
+                self.data[self.selected_design].borrow_mut().add_selection(
+                    click,
+                    false,
+                    &mut new_selection,
+                    app_state.get_selection_mode(),
+                );
+                if let Some(selection) = new_selection.first().cloned() {
+                    let mut requests = self.requests.lock().unwrap();
+                    requests.new_selection(new_selection);
+                    requests.open_context_menu(selection);
+                }
+            }
             Consequence::ClearSelection => {
                 self.requests.lock().unwrap().new_selection(vec![]);
             }
@@ -511,6 +548,22 @@ impl<S: AppState> FlatScene<S> {
                     .borrow_mut()
                     .clear_rectangle();
             }
+            Consequence::AddDoubleStrand {
+                helix,
+                start,
+                length,
+            } => self.requests.lock().unwrap().apply_design_operation(
+                DesignOperation::AddDoubleStrand {
+                    helix,
+                    start,
+                    length,
+                },
+            ),
+            Consequence::DoubleStrandParametersChanged { position, length } => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_double_strand_parameters(position, length),
             _ => (),
         }
     }
@@ -536,6 +589,7 @@ impl<S: AppState> FlatScene<S> {
             self.data[self.selected_design]
                 .borrow_mut()
                 .perform_update(&new_state, &self.old_state);
+            view.borrow_mut().set_theme(new_state.get_theme());
             self.old_state = new_state;
             let ret = view.borrow().needs_redraw();
             if ret {
@@ -759,6 +813,11 @@ impl<S: AppState> Application for FlatScene<S> {
             Notification::Fog(_) => (),
             Notification::WindowFocusLost => (),
             Notification::TeleportCamera(_) => (),
+            Notification::TeleportCamera2D(camera) => {
+                if let Some(controller) = self.controller.get_mut(self.selected_design) {
+                    controller.set_camera_2d(camera)
+                }
+            }
             Notification::NewStereographicCamera(_) => (),
             Notification::FlipSplitViews => self.controller[0].flip_split_views(),
             Notification::HorizonAligned => (),
@@ -807,6 +866,8 @@ impl<S: AppState> Application for FlatScene<S> {
             Notification::ScreenShot3D(_) => (), // Nothing to do in the flatscene.
             Notification::SaveNucleotidesPositions(_) => (), // Nothing to do in the flatscene.
             Notification::StlExport(_) => (),
+            Notification::CameraThumbnail(_) => (),
+            Notification::TraceStrand(_) => (),
         }
     }
 
@@ -843,6 +904,12 @@ impl<S: AppState> Application for FlatScene<S> {
         }
     }
 
+    fn get_camera_2d(&self) -> Option<Camera2DState> {
+        self.controller
+            .get(self.selected_design)
+            .map(|controller| controller.get_camera_2d())
+    }
+
     fn is_splited(&self) -> bool {
         self.splited
     }
@@ -861,6 +928,15 @@ pub trait AppState: Clone {
     fn is_changing_color(&self) -> bool;
     fn is_pasting(&self) -> bool;
     fn get_building_state(&self) -> Option<StrandBuildingStatus>;
+    fn get_theme(&self) -> Theme;
+    fn get_scale_bar_parameters(&self) -> ScaleBarParameters;
+    fn get_numbering_parameters(&self) -> NumberingParameters;
+    fn get_xover_drawing_parameters(&self) -> XoverDrawingParameters;
+    fn get_xover_guide_parameters(&self) -> XoverGuideParameters;
+    fn get_heat_map(&self) -> Option<ensnano_design::heat_map::HeatMapKind>;
+    fn get_color_vision_mode(&self) -> ColorVisionMode;
+    fn get_suggestion_parameters(&self) -> ensnano_interactor::SuggestionParameters;
+    fn get_action_mode(&self) -> ActionMode;
 }
 
 use ensnano_design::ultraviolet::Isometry2;
@@ -878,4 +954,8 @@ pub trait Requests {
     fn suspend_op(&mut self);
     fn apply_design_operation(&mut self, op: DesignOperation);
     fn set_paste_candidate(&mut self, candidate: Option<Nucl>);
+    /// Open a menu listing the actions that can be performed on `selection`
+    fn open_context_menu(&mut self, selection: Selection);
+    /// Set the position and length of the double strands created in helix building mode
+    fn set_double_strand_parameters(&mut self, position: isize, length: usize);
 }
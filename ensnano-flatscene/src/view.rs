@@ -16,12 +16,12 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use super::data::{
-    helix::CharCollector, FlatTorsion, FreeEnd, GpuVertex, Helix, HelixModel, Shift, Strand,
-    StrandVertex,
+    helix::CharCollector, FlatTorsion, FreeEnd, GpuVertex, Helix, HelixModel, Numbering, Shift,
+    Strand, StrandVertex, XoverDrawing,
 };
 use super::{CameraPtr, FlatIdx, FlatNucl, NuclCollection};
 use crate::{DrawArea, PhySize};
-use ensnano_design::Nucl;
+use ensnano_design::{ultraviolet::Vec2, Nucl};
 use ensnano_utils::bindgroup_manager::{DynamicBindGroup, UniformBindGroup};
 use ensnano_utils::camera2d::Globals;
 use ensnano_utils::texture::Texture;
@@ -39,9 +39,14 @@ use super::FlatSelection;
 use ahash::RandomState;
 use background::Background;
 pub use chars::TextDrawer;
+use chars::{Line, Sentence};
 pub use circles::CircleInstance;
 use circles::{CircleDrawer, CircleKind};
 use ensnano_interactor::consts::SAMPLE_COUNT;
+use ensnano_interactor::graphics::{
+    scale_bar_length, ColorVisionMode, LengthUnit, Theme, XoverDrawingParameters,
+};
+use ensnano_interactor::{SuggestionParameters, SuggestionStyle};
 use ensnano_utils::winit::dpi::PhysicalPosition;
 use ensnano_utils::{chars2d as chars, circles2d as circles};
 use insertion::InsertionDrawer;
@@ -54,6 +59,15 @@ use std::{
 
 const SHOW_SUGGESTION: bool = false;
 
+const SCALE_BAR_COLOR: [f32; 4] = [0.88, 0.44, 0.06, 1.];
+const SCALE_BAR_MAX_WIDTH_PX: f32 = 150.;
+const SCALE_BAR_MARGIN_PX: f32 = 20.;
+const SCALE_BAR_THICKNESS_PX: f32 = 4.;
+const SCALE_BAR_LABEL_SIZE_PX: f32 = 16.;
+const SCALE_BAR_LABEL_SPACING_PX: f32 = 4.;
+/// The advance of a glyph of the monospace font, relative to the font size
+const GLYPH_ADVANCE: f32 = 0.5;
+
 pub struct View {
     device: Rc<Device>,
     queue: Rc<Queue>,
@@ -85,8 +99,17 @@ pub struct View {
     text_drawer_top: TextDrawer,
     text_drawer_bottom: TextDrawer,
     show_sec: bool,
-    suggestions: Vec<(FlatNucl, FlatNucl)>,
+    /// The suggested cross-overs and the distance, in nanometers, between their nucleotides
+    suggestions: Vec<(FlatNucl, FlatNucl, f32)>,
+    suggestion_parameters: SuggestionParameters,
     suggestions_view: Vec<StrandView>,
+    /// The suggested cross-overs, when they are drawn as dashed arcs
+    suggestion_arcs: StrandView,
+    /// The double strand that would be added by clicking on the hovered helix in helix building
+    /// mode
+    ghost_double_strand: StrandView,
+    xover_guides: StrandView,
+    heat_map: StrandView,
     selected_strands: Vec<StrandView>,
     candidate_strands: Vec<StrandView>,
     selected_helices: Vec<FlatIdx>,
@@ -102,6 +125,13 @@ pub struct View {
     nucl_collection: Arc<dyn NuclCollection>,
     edition_info: Option<EditionInfo>,
     hovered_nucl: Option<FlatNucl>,
+    theme: Theme,
+    /// The units of the scale bar if it is visible, None otherwise
+    scale_bar_unit: Option<ScaleBarUnit>,
+    scale_bar: Rectangle,
+    numbering: Numbering,
+    xover_drawing: XoverDrawing,
+    color_vision_mode: ColorVisionMode,
 }
 
 impl NuclCollection for () {
@@ -114,6 +144,16 @@ impl NuclCollection for () {
     }
 }
 
+/// The conversions needed to write the label of the scale bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleBarUnit {
+    /// The distance in nanometers between two consecutive nucleotides
+    pub rise: f32,
+    pub length_unit: LengthUnit,
+    /// The number of nanometers in one `length_unit`
+    pub nm_per_unit: f32,
+}
+
 pub struct EditionInfo {
     pub nt_length: usize,
     pub nm_length: f32,
@@ -205,6 +245,11 @@ impl View {
             CircleKind::RotationWidget,
         );
         let rectangle = Rectangle::new(&device, queue.clone());
+        let scale_bar = Rectangle::with_color(&device, queue.clone(), SCALE_BAR_COLOR);
+        let xover_guides = StrandView::new(device.clone(), queue.clone());
+        let heat_map = StrandView::new(device.clone(), queue.clone());
+        let suggestion_arcs = StrandView::new(device.clone(), queue.clone());
+        let ghost_double_strand = StrandView::new(device.clone(), queue.clone());
 
         let text_drawer_top = TextDrawer::new(
             ensnano_interactor::consts::PRINTABLE_CHARS,
@@ -257,7 +302,12 @@ impl View {
             text_drawer_top,
             show_sec: false,
             suggestions: vec![],
+            suggestion_parameters: Default::default(),
             suggestions_view: vec![],
+            suggestion_arcs,
+            ghost_double_strand,
+            xover_guides,
+            heat_map,
             selected_strands: vec![],
             candidate_strands: vec![],
             selected_helices: vec![],
@@ -274,6 +324,12 @@ impl View {
             selected_nucl: vec![],
             candidate_nucl: vec![],
             hovered_nucl: None,
+            theme: Default::default(),
+            scale_bar_unit: None,
+            scale_bar,
+            numbering: Default::default(),
+            xover_drawing: Default::default(),
+            color_vision_mode: Default::default(),
         }
     }
 
@@ -282,6 +338,42 @@ impl View {
         self.was_updated = true;
     }
 
+    pub fn set_theme(&mut self, theme: Theme) {
+        if self.theme != theme {
+            self.theme = theme;
+            self.was_updated = true;
+        }
+    }
+
+    /// Show a scale bar if `unit` is some.
+    pub fn set_scale_bar(&mut self, unit: Option<ScaleBarUnit>) {
+        if self.scale_bar_unit != unit {
+            self.scale_bar_unit = unit;
+            self.was_updated = true;
+        }
+    }
+
+    pub fn set_numbering(&mut self, numbering: Numbering) {
+        if self.numbering != numbering {
+            self.numbering = numbering;
+            self.was_updated = true;
+        }
+    }
+
+    /// Set the parameters used to draw the cross-overs. They are taken into account at the next
+    /// update of the strands.
+    pub fn set_xover_drawing_parameters(&mut self, parameters: XoverDrawingParameters) {
+        self.xover_drawing.parameters = parameters;
+    }
+
+    /// Set the colors used to draw the selected and candidate nucleotides.
+    pub fn set_color_vision_mode(&mut self, color_vision_mode: ColorVisionMode) {
+        if self.color_vision_mode != color_vision_mode {
+            self.color_vision_mode = color_vision_mode;
+            self.was_updated = true;
+        }
+    }
+
     pub fn set_show_torsion(&mut self, show: bool) {
         self.show_torsion = show;
         self.was_updated = true;
@@ -340,8 +432,35 @@ impl View {
         }
     }
 
-    pub fn set_suggestions(&mut self, suggestions: Vec<(FlatNucl, FlatNucl)>) {
+    pub fn set_suggestions(
+        &mut self,
+        suggestions: Vec<(FlatNucl, FlatNucl, f32)>,
+        parameters: SuggestionParameters,
+    ) {
         self.suggestions = suggestions;
+        self.suggestion_parameters = parameters;
+        self.update_suggestion_arcs();
+    }
+
+    fn update_suggestion_arcs(&mut self) {
+        let arcs: Vec<(FlatNucl, FlatNucl, u32)> =
+            if self.suggestion_parameters.style == SuggestionStyle::DashedArcs {
+                self.suggestions
+                    .iter()
+                    .zip(self.suggestion_colors())
+                    .map(|((n1, n2, _), color)| (*n1, *n2, color))
+                    .collect()
+            } else {
+                vec![]
+            };
+        self.suggestion_arcs
+            .set_suggestion_arcs(&arcs, &self.helices);
+    }
+
+    pub fn set_ghost_double_strand(&mut self, segments: &[(FlatNucl, FlatNucl)]) {
+        self.ghost_double_strand
+            .set_ghost_double_strand(segments, &self.helices);
+        self.was_updated = true;
     }
 
     pub fn set_torsions(&mut self, torsions: HashMap<(FlatNucl, FlatNucl), FlatTorsion>) {
@@ -358,7 +477,10 @@ impl View {
             self.add_helix(helix)
         }
         self.models.update(self.helices_model.as_slice());
+        self.xover_guides.set_xover_guides(helices);
+        self.heat_map.set_heat_map(helices);
         self.helices = helices.to_vec();
+        self.update_suggestion_arcs();
         self.was_updated = true;
     }
 
@@ -376,6 +498,7 @@ impl View {
             &self.free_end,
             &self.camera_top,
             other_cam,
+            &self.xover_drawing,
         );
     }
 
@@ -385,9 +508,14 @@ impl View {
         self.helices_view.clear();
         self.strands.clear();
         self.helices_background.clear();
+        self.xover_guides.set_xover_guides(&[]);
+        self.heat_map.set_heat_map(&[]);
+        self.suggestion_arcs.set_suggestion_arcs(&[], &[]);
+        self.ghost_double_strand.set_ghost_double_strand(&[], &[]);
     }
 
     pub fn update_strands(&mut self, strands: &[Strand], helices: &[Helix]) {
+        self.xover_drawing = XoverDrawing::new(self.xover_drawing.parameters, strands, helices);
         self.strands.truncate(strands.len());
         for (i, s) in self.strands.iter_mut().enumerate() {
             let other_cam = if self.splited {
@@ -402,6 +530,7 @@ impl View {
                     &self.free_end,
                     &self.camera_top,
                     other_cam,
+                    &self.xover_drawing,
                 );
             }
         }
@@ -427,7 +556,14 @@ impl View {
         };
         for s in strands.iter() {
             let mut strand_view = StrandView::new(self.device.clone(), self.queue.clone());
-            strand_view.update(s, helices, &None, &self.camera_top, other_cam);
+            strand_view.update(
+                s,
+                helices,
+                &None,
+                &self.camera_top,
+                other_cam,
+                &self.xover_drawing,
+            );
             self.selected_strands.push(strand_view);
         }
         self.was_updated = true;
@@ -442,7 +578,14 @@ impl View {
         };
         for s in strands.iter() {
             let mut strand_view = StrandView::new(self.device.clone(), self.queue.clone());
-            strand_view.update(s, helices, &None, &self.camera_top, other_cam);
+            strand_view.update(
+                s,
+                helices,
+                &None,
+                &self.camera_top,
+                other_cam,
+                &self.xover_drawing,
+            );
             self.candidate_strands.push(strand_view);
         }
         self.was_updated = true;
@@ -467,6 +610,7 @@ impl View {
                     &None,
                     &self.camera_top,
                     &self.camera_bottom,
+                    &self.xover_drawing,
                 );
                 pasted_strand
             })
@@ -649,11 +793,21 @@ impl View {
                 .new_instances(nucleotide_highliting);
         }
 
-        let clear_color = wgpu::Color {
-            r: 0.,
-            g: 0.,
-            b: 0.,
-            a: 0.,
+        let clear_color = if self.theme == Theme::Dark && !exporting_png {
+            // The dark background is obtained by darkening a white target
+            wgpu::Color {
+                r: 1.,
+                g: 1.,
+                b: 1.,
+                a: 1.,
+            }
+        } else {
+            wgpu::Color {
+                r: 0.,
+                g: 0.,
+                b: 0.,
+                a: 0.,
+            }
         };
 
         let msaa_texture = if SAMPLE_COUNT > 1 {
@@ -720,7 +874,7 @@ impl View {
         );
         render_pass.set_bind_group(1, self.models.get_bindgroup(), &[]);
         if !exporting_png {
-            self.background.draw(&mut render_pass);
+            self.background.draw(&mut render_pass, self.theme);
         }
 
         render_pass.set_pipeline(&self.helices_pipeline);
@@ -784,6 +938,8 @@ impl View {
         self.text_drawer_top.draw(&mut render_pass);
         self.insertion_drawer.draw(&mut render_pass);
         render_pass.set_pipeline(&self.strand_pipeline);
+        self.heat_map.draw(&mut render_pass, bottom);
+        self.xover_guides.draw(&mut render_pass, bottom);
         log::trace!("Draw strands..");
         for strand in self.strands.iter() {
             strand.draw(&mut render_pass, bottom);
@@ -794,7 +950,9 @@ impl View {
             strand.draw(&mut render_pass, bottom);
         }
         log::trace!("..OK");
+        self.ghost_double_strand.draw(&mut render_pass, bottom);
         log::trace!("Draw suggestion..");
+        self.suggestion_arcs.draw(&mut render_pass, bottom);
         for suggestion in self.suggestions_view.iter() {
             suggestion.draw(&mut render_pass, bottom);
         }
@@ -913,7 +1071,7 @@ impl View {
             );
             render_pass.set_bind_group(0, self.globals_bottom.get_bindgroup(), &[]);
             render_pass.set_bind_group(1, self.models.get_bindgroup(), &[]);
-            self.background.draw(&mut render_pass);
+            self.background.draw(&mut render_pass, self.theme);
 
             render_pass.set_pipeline(&self.helices_pipeline);
 
@@ -967,12 +1125,15 @@ impl View {
             self.text_drawer_bottom.draw(&mut render_pass);
             self.insertion_drawer.draw(&mut render_pass);
             render_pass.set_pipeline(&self.strand_pipeline);
+            self.heat_map.draw(&mut render_pass, bottom);
+            self.xover_guides.draw(&mut render_pass, bottom);
             for strand in self.strands.iter() {
                 strand.draw(&mut render_pass, bottom);
             }
             for strand in self.pasted_strands.iter() {
                 strand.draw(&mut render_pass, bottom);
             }
+            self.suggestion_arcs.draw(&mut render_pass, bottom);
             for suggestion in self.suggestions_view.iter() {
                 suggestion.draw(&mut render_pass, bottom);
             }
@@ -1066,6 +1227,7 @@ impl View {
                 }),
             });
             self.rectangle.draw(&mut render_pass);
+            self.scale_bar.draw(&mut render_pass);
         }
         self.was_updated = false;
     }
@@ -1131,11 +1293,16 @@ impl View {
         }
     }
 
-    /// Collect the cross-over suggestions
-    fn collect_suggestions(&self, circles: &mut Vec<CircleInstance>) {
+    /// The color of each suggested cross-over
+    fn suggestion_colors(&self) -> Vec<u32> {
         let mut last_blue = None;
         let mut k = 1000;
-        for (n1, n2) in self.suggestions.iter() {
+        let mut ret = Vec::with_capacity(self.suggestions.len());
+        for (n1, _, distance) in self.suggestions.iter() {
+            if self.suggestion_parameters.color_by_distance {
+                ret.push(self.suggestion_parameters.heat_color(*distance));
+                continue;
+            }
             // Don't change the color if the value of n1 hasn't change, so that all suggested
             // cross-overs for n1 appears with the same color
             if last_blue != Some(n1) {
@@ -1150,6 +1317,14 @@ impl View {
                 let rgb = color_space::Rgb::from(hsv);
                 (0xFF << 24) | ((rgb.r as u32) << 16) | ((rgb.g as u32) << 8) | (rgb.b as u32)
             };
+            ret.push(color);
+        }
+        ret
+    }
+
+    /// Collect the cross-over suggestions
+    fn collect_suggestions(&self, circles: &mut Vec<CircleInstance>) {
+        for ((n1, n2, _), color) in self.suggestions.iter().zip(self.suggestion_colors()) {
             let h1 = &self.helices[n1.helix];
             let h2 = &self.helices[n2.helix];
             circles.push(h1.get_circle_nucl(n1.flat_position, n1.forward, color));
@@ -1160,7 +1335,7 @@ impl View {
     /// Collect the candidate/selection circles
     fn collect_nucl_highlight(&self, circles: &mut Vec<CircleInstance>) {
         for n in self.candidate_nucl.iter() {
-            let candidate_color = ensnano_interactor::consts::CANDIDATE_COLOR;
+            let candidate_color = self.color_vision_mode.candidate_color();
             if let Some(h1) = self.helices.get(n.helix.flat.0) {
                 let mut c = h1.get_circle_nucl(n.flat_position, n.forward, candidate_color);
                 c.set_radius(1. / 2.);
@@ -1171,7 +1346,7 @@ impl View {
         }
 
         for n in self.selected_nucl.iter() {
-            let selected_color = ensnano_interactor::consts::SELECTED_COLOR;
+            let selected_color = self.color_vision_mode.selected_color();
             if let Some(h1) = self.helices.get(n.helix.flat.0) {
                 let mut c = h1.get_circle_nucl(n.flat_position, n.forward, selected_color);
                 c.set_radius(std::f32::consts::FRAC_1_SQRT_2);
@@ -1213,7 +1388,7 @@ impl View {
 
     fn view_suggestion(&mut self) {
         self.suggestions_view.clear();
-        for (n1, n2) in self.suggestions.iter() {
+        for (n1, n2, _) in self.suggestions.iter() {
             let mut view = StrandView::new(self.device.clone(), self.queue.clone());
             view.set_indication(*n1, *n2, &self.helices);
             self.suggestions_view.push(view);
@@ -1254,6 +1429,7 @@ impl View {
                 edition_info: &self.edition_info,
                 hovered_nucl: &self.hovered_nucl,
                 nucl_collection: self.nucl_collection.as_ref(),
+                numbering: &self.numbering,
             });
             h.add_char_instances(CharCollector {
                 camera: &self.camera_bottom,
@@ -1264,8 +1440,82 @@ impl View {
                 edition_info: &self.edition_info,
                 hovered_nucl: &self.hovered_nucl,
                 nucl_collection: self.nucl_collection.as_ref(),
+                numbering: &self.numbering,
             })
         }
+        self.update_scale_bar();
+    }
+
+    /// Update the bar and add the label of the scale bar, in the bottom left corner of the view.
+    fn update_scale_bar(&mut self) {
+        let unit = if let Some(unit) = self.scale_bar_unit {
+            unit
+        } else {
+            self.scale_bar.update_corners(None);
+            return;
+        };
+        let (camera, text_drawer) = if self.splited {
+            (&self.camera_bottom, &mut self.text_drawer_bottom)
+        } else {
+            (&self.camera_top, &mut self.text_drawer_top)
+        };
+        let camera = camera.borrow();
+        let zoom = camera.get_globals().zoom;
+        // In the 2D world, a nucleotide is one unit of length
+        let (nb_nucl, text) = if let LengthUnit::HelixTurn = unit.length_unit {
+            let nucl_per_turn = unit.nm_per_unit / unit.rise;
+            let nb_turns = scale_bar_length(SCALE_BAR_MAX_WIDTH_PX / zoom / nucl_per_turn);
+            let nb_decimals = (-nb_turns.log10().floor()).max(0.) as usize;
+            (
+                nb_turns * nucl_per_turn,
+                format!("{:.*} turns", nb_decimals, nb_turns),
+            )
+        } else {
+            let nb_nucl = scale_bar_length(SCALE_BAR_MAX_WIDTH_PX / zoom)
+                .max(1.)
+                .round();
+            let text = if let LengthUnit::BasePair = unit.length_unit {
+                format!("{:.0} bp", nb_nucl)
+            } else {
+                format!("{:.0} bp / {:.1} nm", nb_nucl, nb_nucl * unit.rise)
+            };
+            (nb_nucl, text)
+        };
+        let length_px = nb_nucl * zoom;
+        let bottom = self.area_size.height as f32 - SCALE_BAR_MARGIN_PX;
+        let top = bottom - SCALE_BAR_THICKNESS_PX;
+        self.scale_bar.update_corners(Some([
+            Ndc::from_physical(
+                PhysicalPosition::new(SCALE_BAR_MARGIN_PX, top),
+                self.area_size,
+            ),
+            Ndc::from_physical(
+                PhysicalPosition::new(SCALE_BAR_MARGIN_PX + length_px, bottom),
+                self.area_size,
+            ),
+        ]));
+
+        let label_width_px = text.len() as f32 * GLYPH_ADVANCE * SCALE_BAR_LABEL_SIZE_PX;
+        let (x, y) = camera.screen_to_world(
+            SCALE_BAR_MARGIN_PX + label_width_px / 2.,
+            top - SCALE_BAR_LABEL_SPACING_PX - SCALE_BAR_LABEL_SIZE_PX / 2.,
+        );
+        let center = Vec2::new(x, y);
+        let sentence = Sentence {
+            text: &text,
+            size: SCALE_BAR_LABEL_SIZE_PX / zoom,
+            // A negative z_index puts the label in front of everything else
+            z_index: -1,
+            color: SCALE_BAR_COLOR.into(),
+            rotation: camera.rotation().reversed(),
+            symetry: camera.get_globals().symmetry,
+        };
+        // The label must not be moved, so the bounding line is chosen far away from it
+        let line = Line {
+            origin: center + 1e6 * Vec2::unit_y(),
+            direction: Vec2::unit_x(),
+        };
+        text_drawer.add_sentence(sentence, center, line);
     }
 
     pub fn set_wheels(&mut self, wheels: Vec<CircleInstance>) {
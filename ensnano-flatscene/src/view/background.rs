@@ -29,6 +29,10 @@ use wgpu::util::DeviceExt;
 
 pub struct Background {
     pipeline: wgpu::RenderPipeline,
+    /// A pipeline that draws the negative of the background, used with the dark theme.
+    ///
+    /// The render target must be cleared in white before using it.
+    dark_pipeline: wgpu::RenderPipeline,
     border_pipeline: wgpu::RenderPipeline,
     vbo: wgpu::Buffer,
     ibo: wgpu::Buffer,
@@ -91,32 +95,52 @@ impl Background {
             ..Default::default()
         };
 
-        let bg_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &bg_vs_module,
-                entry_point: "main",
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<BgPoint>() as u64,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
-                }],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &bg_fs_module,
-                entry_point: "main",
-                targets,
+        // Multiplies the destination by (1 - source color).
+        let negative_blend = wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::Zero,
+            dst_factor: wgpu::BlendFactor::OneMinusSrc,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let dark_targets = &[wgpu::ColorTargetState {
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            blend: Some(wgpu::BlendState {
+                color: negative_blend,
+                alpha: wgpu::BlendComponent::REPLACE,
             }),
-            depth_stencil: depth_stencil.clone(),
-            primitive: primitive.clone(),
-            multisample: wgpu::MultisampleState {
-                count: SAMPLE_COUNT,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            label: Some("2D background pipeline"),
-            multiview: None,
-        });
+            write_mask: wgpu::ColorWrites::ALL,
+        }];
+
+        let bg_pipeline_with_targets =
+            |targets: &[wgpu::ColorTargetState], label: &str| -> wgpu::RenderPipeline {
+                device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    layout: Some(&pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &bg_vs_module,
+                        entry_point: "main",
+                        buffers: &[wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<BgPoint>() as u64,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                        }],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &bg_fs_module,
+                        entry_point: "main",
+                        targets,
+                    }),
+                    depth_stencil: depth_stencil.clone(),
+                    primitive: primitive.clone(),
+                    multisample: wgpu::MultisampleState {
+                        count: SAMPLE_COUNT,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    label: Some(label),
+                    multiview: None,
+                })
+            };
+        let bg_pipeline = bg_pipeline_with_targets(targets, "2D background pipeline");
+        let dark_pipeline = bg_pipeline_with_targets(dark_targets, "2D dark background pipeline");
         let border_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             layout: Some(&pipeline_layout),
             vertex: wgpu::VertexState {
@@ -146,15 +170,19 @@ impl Background {
 
         Self {
             pipeline: bg_pipeline,
+            dark_pipeline,
             border_pipeline,
             vbo: bg_vbo,
             ibo: bg_ibo,
         }
     }
 
-    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, theme: Theme) {
         log::trace!("Draw 2d background..");
-        render_pass.set_pipeline(&self.pipeline);
+        match theme {
+            Theme::Light => render_pass.set_pipeline(&self.pipeline),
+            Theme::Dark => render_pass.set_pipeline(&self.dark_pipeline),
+        }
         render_pass.set_index_buffer(self.ibo.slice(..), wgpu::IndexFormat::Uint16);
         render_pass.set_vertex_buffer(0, self.vbo.slice(..));
         render_pass.draw_indexed(0..6, 0, 0..1);
@@ -15,7 +15,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use super::{CameraPtr, FlatNucl, FreeEnd, Helix, Strand};
+use super::{CameraPtr, FlatNucl, FreeEnd, Helix, Strand, XoverDrawing};
 use ensnano_utils::wgpu;
 use std::rc::Rc;
 use wgpu::{Buffer, Device, Queue, RenderPass};
@@ -149,6 +149,7 @@ impl StrandView {
         free_end: &Option<FreeEnd>,
         top_cam: &CameraPtr,
         bottom_cam: &CameraPtr,
+        xover_drawing: &XoverDrawing,
     ) {
         /*
         let need_update = if self.previous_points.as_ref() != Some(&strand.points) {
@@ -162,7 +163,7 @@ impl StrandView {
 
         if need_update {
             let (vertices_top, split_vertices_top) =
-                strand.to_vertices(helices, free_end, top_cam, bottom_cam);
+                strand.to_vertices(helices, free_end, top_cam, bottom_cam, xover_drawing);
             self.vertex_buffer_top
                 .update(vertices_top.vertices.as_slice());
             self.index_buffer_top
@@ -174,7 +175,7 @@ impl StrandView {
                 .update(split_vertices_top.indices.as_slice());
             self.num_instance_split_top = split_vertices_top.indices.len() as u32;
             let (vertices_bottom, split_vertices_bottom) =
-                strand.to_vertices(helices, free_end, bottom_cam, top_cam);
+                strand.to_vertices(helices, free_end, bottom_cam, top_cam, xover_drawing);
             self.vertex_buffer_bottom
                 .update(vertices_bottom.vertices.as_slice());
             self.index_buffer_bottom
@@ -199,6 +200,58 @@ impl StrandView {
         self.num_instance_bottom = vertices.indices.len() as u32;
     }
 
+    pub fn set_ghost_double_strand(
+        &mut self,
+        segments: &[(FlatNucl, FlatNucl)],
+        helices: &[Helix],
+    ) {
+        let vertices = Strand::ghost_double_strand(segments, helices);
+        self.vertex_buffer_top.update(vertices.vertices.as_slice());
+        self.index_buffer_top.update(vertices.indices.as_slice());
+        self.num_instance_top = vertices.indices.len() as u32;
+        self.vertex_buffer_bottom
+            .update(vertices.vertices.as_slice());
+        self.index_buffer_bottom.update(vertices.indices.as_slice());
+        self.num_instance_bottom = vertices.indices.len() as u32;
+    }
+
+    pub fn set_suggestion_arcs(
+        &mut self,
+        suggestions: &[(FlatNucl, FlatNucl, u32)],
+        helices: &[Helix],
+    ) {
+        let vertices = Strand::suggestion_arcs(suggestions, helices);
+        self.vertex_buffer_top.update(vertices.vertices.as_slice());
+        self.index_buffer_top.update(vertices.indices.as_slice());
+        self.num_instance_top = vertices.indices.len() as u32;
+        self.vertex_buffer_bottom
+            .update(vertices.vertices.as_slice());
+        self.index_buffer_bottom.update(vertices.indices.as_slice());
+        self.num_instance_bottom = vertices.indices.len() as u32;
+    }
+
+    pub fn set_xover_guides(&mut self, helices: &[Helix]) {
+        let vertices = Strand::xover_guides(helices);
+        self.vertex_buffer_top.update(vertices.vertices.as_slice());
+        self.index_buffer_top.update(vertices.indices.as_slice());
+        self.num_instance_top = vertices.indices.len() as u32;
+        self.vertex_buffer_bottom
+            .update(vertices.vertices.as_slice());
+        self.index_buffer_bottom.update(vertices.indices.as_slice());
+        self.num_instance_bottom = vertices.indices.len() as u32;
+    }
+
+    pub fn set_heat_map(&mut self, helices: &[Helix]) {
+        let vertices = Strand::heat_map(helices);
+        self.vertex_buffer_top.update(vertices.vertices.as_slice());
+        self.index_buffer_top.update(vertices.indices.as_slice());
+        self.num_instance_top = vertices.indices.len() as u32;
+        self.vertex_buffer_bottom
+            .update(vertices.vertices.as_slice());
+        self.index_buffer_bottom.update(vertices.indices.as_slice());
+        self.num_instance_bottom = vertices.indices.len() as u32;
+    }
+
     pub fn draw<'a>(&'a self, render_pass: &mut RenderPass<'a>, bottom: bool) {
         if bottom {
             render_pass.set_index_buffer(
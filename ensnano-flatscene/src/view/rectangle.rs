@@ -26,6 +26,7 @@ const SELECT_COLOR: [f32; 4] = [0.26, 0.64, 0.85, 0.6];
 
 pub struct Rectangle {
     corner: Option<Option<[Ndc; 2]>>,
+    color: [f32; 4],
     pipeline: RenderPipeline,
     vbo: wgpu::Buffer,
     ibo: wgpu::Buffer,
@@ -53,6 +54,10 @@ impl Vertex {
 
 impl Rectangle {
     pub fn new(device: &Device, queue: Rc<Queue>) -> Self {
+        Self::with_color(device, queue, SELECT_COLOR)
+    }
+
+    pub fn with_color(device: &Device, queue: Rc<Queue>, color: [f32; 4]) -> Self {
         let vs_module = device.create_shader_module(&wgpu::include_spirv!("rectangle.vert.spv"));
         let fs_module = device.create_shader_module(&wgpu::include_spirv!("rectangle.frag.spv"));
 
@@ -126,6 +131,7 @@ impl Rectangle {
         Self {
             pipeline: render_pipeline,
             corner: None,
+            color,
             ibo: index_buffer,
             vbo: vertex_buffer,
             queue,
@@ -155,19 +161,19 @@ impl Rectangle {
             [
                 Vertex {
                     position: [min_x, min_y],
-                    color: SELECT_COLOR,
+                    color: self.color,
                 },
                 Vertex {
                     position: [min_x, max_y],
-                    color: SELECT_COLOR,
+                    color: self.color,
                 },
                 Vertex {
                     position: [max_x, min_y],
-                    color: SELECT_COLOR,
+                    color: self.color,
                 },
                 Vertex {
                     position: [max_x, max_y],
-                    color: SELECT_COLOR,
+                    color: self.color,
                 },
             ]
         } else {
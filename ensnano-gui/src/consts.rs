@@ -27,3 +27,10 @@ use iced::Color;
 pub const fn innactive_color() -> Color {
     Color::from_rgb(0.6, 0.6, 0.6)
 }
+
+/// The color of the border of the regions highlighted by the interactive tutorial
+pub const TUTORIAL_HIGHLIGHT_COLOR: Color = Color::from_rgb(1.0, 0.65, 0.0);
+pub const TUTORIAL_HIGHLIGHT_WIDTH: f32 = 3.0;
+
+/// The color of the texts that warn about a problem in the design
+pub const WARNING_TEXT_COLOR: Color = Color::from_rgb(1.0, 0.4, 0.0);
@@ -0,0 +1,194 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A floating menu listing the actions that can be performed on the element that was
+//! right-clicked in one of the scenes.
+
+use super::{left_panel::BACKGROUND, OverlayType, Requests, UiSize};
+use ensnano_interactor::{application::AppId, Selection};
+use iced::{button, container, Background, Button, Color, Column, Command, Container};
+use iced::{Element, Length, Text};
+use iced_native::Program;
+use std::sync::{Arc, Mutex};
+
+/// Logical width of the context menu.
+pub const CONTEXT_MENU_WIDTH: u16 = 160;
+
+/// An action that can be triggered from the context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuAction {
+    Color,
+    Delete,
+    Isolate,
+    MakeScaffold,
+    Center2D,
+    Center3D,
+    TraceStrand,
+    RespaceXovers,
+    DeleteBasePair,
+    InsertBasePair,
+}
+
+impl ContextMenuAction {
+    /// The actions that are relevant for a given selected element.
+    pub fn actions_for(selection: &Selection) -> &'static [Self] {
+        use ContextMenuAction::*;
+        match selection {
+            Selection::Strand(_, _) => &[
+                Color,
+                Delete,
+                Isolate,
+                MakeScaffold,
+                Center2D,
+                Center3D,
+                TraceStrand,
+                RespaceXovers,
+            ],
+            Selection::Helix { .. } => &[Delete, Isolate, Center2D, Center3D],
+            Selection::Xover(_, _) | Selection::Bond(_, _, _) => {
+                &[Delete, Center2D, Center3D, RespaceXovers]
+            }
+            Selection::Nucleotide(_, _) => {
+                &[Isolate, Center2D, Center3D, DeleteBasePair, InsertBasePair]
+            }
+            Selection::Grid(_, _) => &[Delete, Isolate, Center3D],
+            _ => &[],
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Color => "Change color",
+            Self::Delete => "Delete",
+            Self::Isolate => "Isolate",
+            Self::MakeScaffold => "Set as scaffold",
+            Self::Center2D => "Center in 2D view",
+            Self::Center3D => "Center in 3D view",
+            Self::TraceStrand => "Trace strand",
+            Self::RespaceXovers => "Respace cross-overs",
+            Self::DeleteBasePair => "Delete base pair",
+            Self::InsertBasePair => "Insert base pair",
+        }
+    }
+}
+
+pub struct ContextMenu<R: Requests> {
+    requests: Arc<Mutex<R>>,
+    selection: Option<Selection>,
+    buttons: Vec<(ContextMenuAction, button::State)>,
+    ui_size: UiSize,
+}
+
+#[derive(Debug, Clone)]
+pub enum ContextMenuMessage {
+    /// Display the actions that can be performed on a new element
+    NewSelection(Selection),
+    ActionPicked(ContextMenuAction),
+}
+
+impl<R: Requests> ContextMenu<R> {
+    pub fn new(requests: Arc<Mutex<R>>, ui_size: UiSize) -> Self {
+        Self {
+            requests,
+            selection: None,
+            buttons: Vec::new(),
+            ui_size,
+        }
+    }
+
+    /// The logical height of the menu when it lists `nb_actions` actions.
+    pub fn height(nb_actions: usize, ui_size: UiSize) -> u16 {
+        nb_actions as u16 * (ui_size.button() + 2) + 10
+    }
+}
+
+impl<R: Requests> Program for ContextMenu<R> {
+    type Renderer = iced_wgpu::Renderer;
+    type Message = ContextMenuMessage;
+
+    fn update(&mut self, message: ContextMenuMessage) -> Command<ContextMenuMessage> {
+        match message {
+            ContextMenuMessage::NewSelection(selection) => {
+                self.selection = Some(selection);
+                self.buttons = ContextMenuAction::actions_for(&selection)
+                    .iter()
+                    .map(|action| (*action, Default::default()))
+                    .collect();
+            }
+            ContextMenuMessage::ActionPicked(action) => {
+                let mut requests = self.requests.lock().unwrap();
+                match action {
+                    ContextMenuAction::Color => requests.open_overlay(OverlayType::Color),
+                    ContextMenuAction::Delete => requests.delete_selection(),
+                    ContextMenuAction::Isolate => requests.toggle_visibility(true),
+                    ContextMenuAction::MakeScaffold => requests.set_scaffold_from_selection(),
+                    ContextMenuAction::Center2D => {
+                        if let Some(selection) = self.selection {
+                            requests.center_selection(selection, AppId::Scene)
+                        }
+                    }
+                    ContextMenuAction::Center3D => {
+                        if let Some(selection) = self.selection {
+                            requests.center_selection(selection, AppId::FlatScene)
+                        }
+                    }
+                    ContextMenuAction::RespaceXovers => requests.respace_xovers(),
+                    ContextMenuAction::DeleteBasePair => requests.delete_base_pairs(),
+                    ContextMenuAction::InsertBasePair => requests.insert_base_pairs(),
+                    ContextMenuAction::TraceStrand => {
+                        if let Some(Selection::Strand(_, s_id)) = self.selection {
+                            requests.trace_strand(s_id as usize)
+                        }
+                    }
+                }
+                requests.close_overlay(OverlayType::ContextMenu);
+            }
+        }
+        Command::none()
+    }
+
+    fn view(&mut self) -> Element<ContextMenuMessage> {
+        let ui_size = self.ui_size;
+        let mut column = Column::new().spacing(2).padding(5);
+        for (action, state) in self.buttons.iter_mut() {
+            column = column.push(
+                Button::new(state, Text::new(action.label()).size(ui_size.main_text()))
+                    .width(Length::Fill)
+                    .height(Length::Units(ui_size.button()))
+                    .on_press(ContextMenuMessage::ActionPicked(*action)),
+            );
+        }
+        Container::new(column)
+            .style(MenuStyle)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+struct MenuStyle;
+impl container::StyleSheet for MenuStyle {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: Some(Background::Color(BACKGROUND)),
+            text_color: Some(Color::WHITE),
+            border_width: 1_f32,
+            border_radius: 3_f32,
+            border_color: Color::BLACK,
+        }
+    }
+}
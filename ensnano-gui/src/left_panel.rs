@@ -15,8 +15,10 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use ensnano_interactor::{graphics::HBondDisplay, EquadiffSolvingMethod};
-use ensnano_organizer::{Organizer, OrganizerMessage, OrganizerTree};
+use ensnano_interactor::{
+    graphics::HBondDisplay, EquadiffSolvingMethod, RevolutionRelaxationProgress,
+};
+use ensnano_organizer::{GroupId, Organizer, OrganizerMessage, OrganizerTree};
 use std::sync::{Arc, Mutex};
 
 use iced::{
@@ -34,15 +36,25 @@ use iced_winit::winit::{
 use ultraviolet::Vec3;
 
 use ensnano_design::{
+    design_operations::{ControlPointsEdit, HelixNumbering, InsertionPlacementTarget},
     elements::{DesignElement, DesignElementKey},
-    BezierPathId, CameraId,
+    grid::{GridId, PhantomParameters},
+    heat_map::HeatMapKind,
+    BezierPathId, BundleId, CameraId, JointId, JointKind, OligoPool,
 };
 use ensnano_interactor::{
-    graphics::{Background3D, RenderingMode},
-    ActionMode, SelectionConversion, SuggestionParameters,
+    graphics::{
+        Background3D, BrushEffect, BrushParameters, ColorVisionMode, ElementType, GpuPreference,
+        GroundParameters, NumberingParameters, RenderQuality, RenderingMode, ScaleBarParameters,
+        StrandEndsParameters, ViewLayer, XoverDrawingParameters, XoverGuideParameters,
+    },
+    i18n::tr_args,
+    plugin::PluginDescription,
+    ActionMode, DesignBrowserChoice, SelectionConversion, SimulationJobInfo, SimulationJobKind,
+    SuggestionParameters, WidgetBasis,
 };
 
-use ensnano_exports::ExportType;
+use ensnano_exports::{print_2d::PrintParameters, ExportType};
 
 use super::{
     icon_btn,
@@ -50,7 +62,8 @@ use super::{
         dark_icon as icon, icon_to_char, LightIcon as MaterialIcon, DARK_ICONFONT as ICONFONT,
     },
     slider_style::DesactivatedSlider,
-    text_btn, AppState, FogParameters as Fog, OverlayType, Requests, UiSize,
+    text_btn, tr, AppState, FogParameters as Fog, Language, OverlayType, Requests, Theme,
+    TutorialHighlight, TutorialStatus, UiSize,
 };
 
 use ensnano_design::{grid::GridTypeDescr, ultraviolet, NamedParameter};
@@ -63,16 +76,24 @@ mod discrete_value;
 use discrete_value::{FactoryId, RequestFactory, Requestable, ValueId};
 mod tabs;
 use crate::{consts::*, left_panel::tabs::RevolutionParameterId};
+mod console;
 mod contextual_panel;
+mod design_browser;
 mod export_menu;
-use contextual_panel::{ContextualPanel, InstanciatedValue, ValueKind};
+mod toasts;
+use console::LogConsole;
+pub use console::{LogRecord, MemoryUsage, MAX_NB_RECORDS};
+use contextual_panel::{ContextualPanel, HelixBlockField, InstanciatedValue, ValueKind};
+use design_browser::DesignBrowser;
 use export_menu::ExportMenu;
+use toasts::ToastStack;
+pub use toasts::{ToastSeverity, TOAST_DURATION};
 
-use ensnano_interactor::{CheckXoversParameter, HyperboloidRequest, Selection};
+use ensnano_interactor::{CheckXoversParameter, GroupOperation, HyperboloidRequest, Selection};
 pub use tabs::revolution_tab::*;
 use tabs::{
-    CameraShortcut, CameraTab, EditionTab, GridTab, ParametersTab, PenTab, SequenceTab,
-    SimulationTab,
+    CameraShortcut, CameraTab, EditionTab, GridTab, MetadataTab, ParametersTab, PenTab,
+    SequenceTab, ShiftOptimizationField, SimulationTab, StapleColumn, StaplesTab,
 };
 
 pub(super) const ENSNANO_FONT: iced::Font = iced::Font::External {
@@ -103,10 +124,17 @@ pub struct LeftPanel<R: Requests, S: AppState> {
     parameters_tab: ParametersTab,
     pen_tab: PenTab,
     revolution_tab: RevolutionTab<S>,
+    staples_tab: StaplesTab,
+    metadata_tab: MetadataTab,
     contextual_panel: ContextualPanel<S>,
     camera_shortcut: CameraShortcut,
     application_state: S,
     exports_menu: ExportMenu,
+    design_browser: DesignBrowser,
+    toasts: ToastStack,
+    console: LogConsole,
+    /// True if the application is in viewer mode, in which case editing tabs are hidden
+    read_only: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -120,6 +148,22 @@ pub enum Message<S: AppState> {
     ColorPicked(Color),
     HsvSatValueChanged(f64, f64),
     StrandNameChanged(usize, String),
+    StrandPoolNameChanged(usize, String),
+    StrandPoolConcentrationInput(usize, String),
+    StrandNoteChanged(usize, String),
+    StrandUrlChanged(usize, String),
+    OpenStrandUrl(String),
+    StapleFilterChanged(String),
+    DesignMetadataChanged(ensnano_design::DesignMetadata),
+    ToggleEditingNotes,
+    StapleSortColumn(StapleColumn),
+    StapleRowSelected(usize, bool),
+    SelectVisibleStaples,
+    StapleColorInput(usize, String),
+    StapleColorSubmitted(usize),
+    WellQueryChanged(String),
+    HideOthersThanWells(bool),
+    HighlightWells,
     FinishChangingColor,
     HueChanged(f64),
     NewGrid(GridTypeDescr),
@@ -128,6 +172,7 @@ pub enum Message<S: AppState> {
     PositionHelicesChanged(String),
     LengthHelicesChanged(String),
     ScaffoldPositionInput(String),
+    ShiftOptimizationInput(ShiftOptimizationField, String),
     #[allow(dead_code)]
     ShowTorsion(bool),
     FogRadius(f32),
@@ -141,14 +186,56 @@ pub enum Message<S: AppState> {
     NewHyperboloid,
     FinalizeHyperboloid,
     RollTargeted(bool),
+    RollOnSelectedXovers(bool),
     RigidGridSimulation(bool),
+    RigidBundleSimulation(bool),
+    AddJoint(JointKind),
+    DeleteJoint(JointId),
     RigidHelicesSimulation(bool),
+    RollJob,
+    TwistJob,
+    RelaxGridsJob,
+    PauseSimulationJob(usize, bool),
+    StopSimulationJob(usize),
+    SimulationJobs(Vec<SimulationJobInfo>),
+    Plugins(Vec<PluginDescription>),
+    /// The value of a parameter of a plugin has been edited
+    PluginInput(usize, usize, String),
+    RunPlugin(usize),
+    CompareWithLastSave,
+    CompareWithDesignFile,
+    StopDesignComparison,
+    Checkpoints(Vec<String>),
+    CheckpointNameChanged(String),
+    CreateCheckpoint,
+    RestoreCheckpoint(usize),
+    CompareWithCheckpoint(usize),
+    DeleteCheckpoint(usize),
     VolumeExclusion(bool),
     TabSelected(usize),
     OrganizerMessage(OrganizerMessage<DesignElement>),
     ModifiersChanged(ModifiersState),
     UiSizeChanged(UiSize),
     UiSizePicked(UiSize),
+    UiScaleSliding(u16),
+    ThemePicked(Theme),
+    LanguagePicked(Language),
+    ColorVisionModePicked(ColorVisionMode),
+    WidgetBasisPicked(WidgetBasis),
+    GizmoSizeChanged(f32),
+    UndoLimitChanged(u32),
+    BinaryDesignFiles(bool),
+    ConfirmLargeDeletions(bool),
+    GpuPreferencePicked(GpuPreference),
+    RenderQualityChanged(RenderQuality),
+    GroundParametersChanged(GroundParameters),
+    ViewLayerChanged(ElementType, ViewLayer),
+    ScaleBarParametersChanged(ScaleBarParameters),
+    NumberingParametersChanged(NumberingParameters),
+    XoverDrawingParametersChanged(XoverDrawingParameters),
+    StrandEndsParametersChanged(StrandEndsParameters),
+    XoverGuideParametersChanged(XoverGuideParameters),
+    HeatMapPicked(Option<HeatMapKind>),
     StaplesRequested,
     OrigamisRequested,
     ToggleText(bool),
@@ -158,17 +245,64 @@ pub enum Message<S: AppState> {
     ToggleVisibility(bool),
     AllVisible,
     Redim2dHelices(bool),
+    PlaceInsertions(InsertionPlacementTarget),
+    SetIdealRoll,
+    HelixNumberingPicked(HelixNumbering),
+    RenumberHelices,
+    HelixDisplayNumberChanged(String),
+    SetHelixDisplayNumber(usize),
+    HelixBundleNameChanged(String),
+    CreateHelixBundle(Vec<usize>),
+    SelectHelixBundle(BundleId),
+    RenameHelixBundle(BundleId),
+    DeleteHelixBundle(BundleId),
+    SetTwistAxisFromSelection,
+    ClearTwistAxis,
+    StartHelicesTwist,
+    BendingRadiusChanged(f32),
+    BendingDirectionChanged(f32),
+    CleanupMinLengthChanged(u32),
+    CleanupStrands,
     InvertScroll(bool),
     BrownianMotion(bool),
     Nothing,
     CancelHyperboloid,
     SelectionValueChanged(usize, String),
     SetSmallSpheres(bool),
+    SetGridLabels(bool),
+    SetPhantomParameters(PhantomParameters),
+    FinishSettingPhantomParameters,
+    HelixBlockInput(HelixBlockField, String),
+    HelixBlockAlternateOrientation(bool),
+    HelixBlockScaffoldRaster(bool),
+    CreateHelixBlock(GridId),
+    GroupOperation(GroupId, GroupOperation),
+    SimulateGroup(GroupId),
+    GroupPivotInput(usize, String),
     ScaffoldIdSet(usize, bool),
     //NewScaffoldInfo(Option<ScaffoldInfo>),
     SelectScaffold,
     ForceHelp,
     ShowTutorial,
+    /// Give the keyboard focus to the input of the length of the selected insertion
+    FocusInsertionLength,
+    TutorialStatus(Option<TutorialStatus>),
+    StartTutorial,
+    SkipTutorialStep,
+    StopTutorial,
+    NewToast(ToastSeverity, String),
+    DismissToast(usize),
+    ExpireToasts(std::time::Instant),
+    ToggleNotificationHistory,
+    ClearNotificationHistory,
+    NewLogRecords(Vec<LogRecord>),
+    SystemInfo(String),
+    MemoryUsage(MemoryUsage),
+    ToggleConsole,
+    ConsoleLevelFilter(log::Level),
+    ConsoleModuleFilter(String),
+    CopyDiagnostics,
+    SetReadOnly(bool),
     RenderingMode(RenderingMode),
     Background3D(Background3D),
     OpenLink(&'static str),
@@ -176,19 +310,39 @@ pub enum Message<S: AppState> {
     FogChoice(tabs::FogChoice),
     SetScaffoldSeqButtonPressed,
     OptimizeScaffoldShiftPressed,
+    OptimizeNicksPressed,
+    RandomSequenceMinGc(u32),
+    RandomSequenceMaxGc(u32),
+    ForbiddenMotifsInput(String),
+    FillRandomSequences,
+    ClearSequencesPressed,
+    AssignScaffoldToSelectionPressed,
+    AuditScaffoldPairing,
     ResetSimulation,
     EditCameraName(String),
     SubmitCameraName,
     StartEditCameraName(CameraId),
+    SwapCameras(CameraId, CameraId),
+    StoreCameraRendering(CameraId, bool),
+    CameraThumbnail(CameraId, ensnano_interactor::graphics::Thumbnail),
     DeleteCamera(CameraId),
     SelectCamera(CameraId),
     NewCustomCamera,
+    AddPresentationStep,
+    DeletePresentationStep(usize),
+    EditPresentationStepName(String),
+    SubmitPresentationStepName,
+    StartEditPresentationStepName(usize),
+    /// Go to the next (true) or previous (false) presentation step
+    PresentationStep(bool),
     NewSuggestionParameters(SuggestionParameters),
     ContextualValueChanged(ValueKind, usize, String),
     ContextualValueSubmitted(ValueKind),
     InstanciatedValueSubmitted(InstanciatedValue),
     CheckXoversParameter(CheckXoversParameter),
     FollowStereographicCamera(bool),
+    InsideOutInspection(bool),
+    BrushParametersChanged(BrushParameters),
     ShowStereographicCamera(bool),
     ShowHBonds(HBondDisplay),
     RainbowScaffold(bool),
@@ -205,7 +359,18 @@ pub enum Message<S: AppState> {
         path_id: BezierPathId,
         grid_type: GridTypeDescr,
     },
+    AddHelicesAlongPath {
+        path_id: BezierPathId,
+        with_strands: bool,
+    },
+    SetHelicesAlongPathWithStrands(bool),
+    EditBezierPathControlPoints {
+        path_id: BezierPathId,
+        edit: ControlPointsEdit,
+    },
+    ResampledVerticesChanged(f32),
     SetShowBezierPaths(bool),
+    SetShowInsertionBadges(bool),
     MakeBezierPathCyclic {
         path_id: BezierPathId,
         cyclic: bool,
@@ -220,12 +385,15 @@ pub enum Message<S: AppState> {
     },
     InitRevolutionRelaxation,
     CancelExport,
+    PrintParametersChanged(PrintParameters),
+    DesignBrowser(DesignBrowserChoice),
     LoadSvgFile,
     ScreenShot2D,
     ScreenShot3D,
     SaveNucleotidesPositions,
     IncrRevolutionShift,
     DecrRevolutionShift,
+    RevolutionRelaxationProgress(RevolutionRelaxationProgress),
 }
 
 impl<S: AppState> contextual_panel::BuilderMessage for Message<S> {
@@ -268,10 +436,16 @@ impl<R: Requests, S: AppState> LeftPanel<R, S> {
             parameters_tab: ParametersTab::new(state),
             pen_tab: Default::default(),
             revolution_tab: Default::default(),
+            staples_tab: Default::default(),
+            metadata_tab: Default::default(),
             contextual_panel: ContextualPanel::new(logical_size.width as u32),
             camera_shortcut: CameraShortcut::new(),
             application_state: state.clone(),
             exports_menu: Default::default(),
+            design_browser: Default::default(),
+            toasts: Default::default(),
+            console: Default::default(),
+            read_only: false,
         }
     }
 
@@ -286,6 +460,37 @@ impl<R: Requests, S: AppState> LeftPanel<R, S> {
         self.organizer.set_width(logical_size.width as u16);
     }
 
+    /// Give the color picked in the edition tab to the selected strands, and to the brush if it
+    /// paints colors.
+    fn change_color(&mut self, color: u32) {
+        let selection_contains_strand = !ensnano_interactor::extract_strands_from_selection(
+            self.application_state.get_selection(),
+        )
+        .is_empty();
+        if selection_contains_strand {
+            self.requests.lock().unwrap().change_strand_color(color);
+        }
+        let brush = self.application_state.get_brush_parameters();
+        if brush.effect == BrushEffect::Color {
+            self.requests
+                .lock()
+                .unwrap()
+                .set_brush_parameters(BrushParameters { color, ..brush });
+        }
+    }
+
+    /// Start a simulation job with the parameters of the rigid body simulations.
+    fn start_simulation_job(&mut self, kind: SimulationJobKind) {
+        let mut request: Option<RigidBodyParametersRequest> = None;
+        self.simulation_tab.make_rigid_body_request(&mut request);
+        if let Some(request) = request {
+            self.requests
+                .lock()
+                .unwrap()
+                .start_simulation_job(kind, request)
+        }
+    }
+
     fn organizer_message(&mut self, m: OrganizerMessage<DesignElement>) -> Option<Message<S>> {
         match m {
             OrganizerMessage::InternalMessage(m) => {
@@ -339,6 +544,10 @@ impl<R: Requests, S: AppState> LeftPanel<R, S> {
         None
     }
 
+    pub fn get_selected_tab(&self) -> usize {
+        self.selected_tab
+    }
+
     pub fn has_keyboard_priority(&self) -> bool {
         self.sequence_input.has_keyboard_priority()
             || self.contextual_panel.has_keyboard_priority()
@@ -346,6 +555,10 @@ impl<R: Requests, S: AppState> LeftPanel<R, S> {
             || self.sequence_tab.has_keyboard_priority()
             || self.camera_shortcut.has_keyboard_priority()
             || self.revolution_tab.has_keyboard_priority()
+            || self.console.has_keyboard_priority()
+            || self.parameters_tab.has_keyboard_priority()
+            || self.camera_tab.has_keyboard_priority()
+            || self.metadata_tab.has_keyboard_priority()
     }
 }
 
@@ -365,6 +578,105 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::StrandNameChanged(s_id, name) => {
                 self.requests.lock().unwrap().set_strand_name(s_id, name)
             }
+            Message::StrandNoteChanged(s_id, note) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_strand_note(s_id, Some(note).filter(|n| !n.is_empty())),
+            Message::StrandUrlChanged(s_id, url) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_strand_url(s_id, Some(url).filter(|u| !u.is_empty())),
+            Message::OpenStrandUrl(url) => {
+                if let Err(e) = open::that(url) {
+                    log::error!("Could not open link: {}", e);
+                }
+            }
+            Message::StapleFilterChanged(filter) => self.staples_tab.filter_changed(filter),
+            Message::DesignMetadataChanged(metadata) => {
+                self.requests.lock().unwrap().set_design_metadata(metadata)
+            }
+            Message::ToggleEditingNotes => self.metadata_tab.toggle_editing_notes(),
+            Message::StapleSortColumn(column) => self.staples_tab.sort_by(column),
+            Message::StapleRowSelected(s_id, selected) => {
+                let mut keys = self.application_state.get_selection_as_designelement();
+                let key = DesignElementKey::Strand(s_id);
+                if selected {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                } else {
+                    keys.retain(|k| k != &key);
+                }
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .set_selected_keys(keys, None, false);
+            }
+            Message::SelectVisibleStaples => {
+                let rows = self
+                    .staples_tab
+                    .visible_rows(self.application_state.get_reader().get_staple_rows());
+                let keys = rows
+                    .iter()
+                    .map(|row| DesignElementKey::Strand(row.s_id))
+                    .collect();
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .set_selected_keys(keys, None, false);
+            }
+            Message::StapleColorInput(s_id, input) => self.staples_tab.color_input(s_id, input),
+            Message::StapleColorSubmitted(s_id) => {
+                if let Some(color) = self.staples_tab.submit_color(s_id) {
+                    self.requests.lock().unwrap().set_strand_color(s_id, color)
+                }
+            }
+            Message::WellQueryChanged(query) => self.staples_tab.well_query_changed(query),
+            Message::HideOthersThanWells(b) => self.staples_tab.set_hide_others(b),
+            Message::HighlightWells => {
+                let wells = self.staples_tab.queried_wells();
+                let strands = self
+                    .application_state
+                    .get_reader()
+                    .get_strands_at_wells(&wells);
+                self.staples_tab.set_nb_highlighted_staples(strands.len());
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .highlight_strands(strands, self.staples_tab.hide_others());
+            }
+            Message::StrandPoolNameChanged(s_id, name) => {
+                let reader = self.application_state.get_reader();
+                let pool = OligoPool {
+                    name,
+                    ..reader.strand_pool(s_id).unwrap_or_default()
+                };
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .set_strand_pool(s_id, Some(pool).filter(|p| p != &OligoPool::default()))
+            }
+            Message::StrandPoolConcentrationInput(s_id, input) => {
+                let concentration = if input.trim().is_empty() {
+                    Some(None)
+                } else {
+                    input.trim().parse::<f32>().ok().map(Some)
+                };
+                self.contextual_panel.update_pool_concentration_input(input);
+                if let Some(concentration) = concentration {
+                    let reader = self.application_state.get_reader();
+                    let pool = OligoPool {
+                        concentration,
+                        ..reader.strand_pool(s_id).unwrap_or_default()
+                    };
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_strand_pool(s_id, Some(pool).filter(|p| p != &OligoPool::default()))
+                }
+            }
             Message::SequenceFileRequested => {
                 let dialog = rfd::AsyncFileDialog::new().pick_file();
                 let requests = self.requests.clone();
@@ -392,22 +704,16 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::HsvSatValueChanged(saturation, value) => {
                 self.edition_tab.change_sat_value(saturation, value);
                 let requested_color = self.edition_tab.strand_color_change();
-                self.requests
-                    .lock()
-                    .unwrap()
-                    .change_strand_color(requested_color);
+                self.change_color(requested_color);
             }
             Message::HueChanged(x) => {
                 self.edition_tab.change_hue(x);
                 let requested_color = self.edition_tab.strand_color_change();
-                self.requests
-                    .lock()
-                    .unwrap()
-                    .change_strand_color(requested_color);
+                self.change_color(requested_color);
             }
             Message::ColorPicked(color) => {
                 let color_u32 = color_to_u32(color);
-                self.requests.lock().unwrap().change_strand_color(color_u32);
+                self.change_color(color_u32);
             }
             Message::Resized(size, position) => self.resize(size, position),
             Message::NewGrid(grid_type) => {
@@ -452,6 +758,18 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                     self.requests.lock().unwrap().set_scaffold_shift(n);
                 }
             }
+            Message::ShiftOptimizationInput(field, input) => {
+                let parameters = self.application_state.get_shift_optimization_parameters();
+                if let Some(parameters) = self
+                    .sequence_tab
+                    .update_shift_optimization_input(field, input, parameters)
+                {
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_shift_optimization_parameters(parameters);
+                }
+            }
             Message::ShowTorsion(b) => {
                 self.requests.lock().unwrap().set_torsion_visibility(b);
                 self.show_torsion = b;
@@ -579,6 +897,26 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::FinalizeHyperboloid => {
                 self.requests.lock().unwrap().finalize_hyperboloid();
             }
+            Message::AddJoint(kind) => self
+                .requests
+                .lock()
+                .unwrap()
+                .add_joint_between_selected_bodies(kind),
+            Message::DeleteJoint(joint_id) => self.requests.lock().unwrap().delete_joint(joint_id),
+            Message::RigidBundleSimulation(start) => {
+                if start {
+                    let mut request: Option<RigidBodyParametersRequest> = None;
+                    self.simulation_tab.make_rigid_body_request(&mut request);
+                    if let Some(request) = request {
+                        self.requests
+                            .lock()
+                            .unwrap()
+                            .update_rigid_bundles_simulation(request);
+                    }
+                } else {
+                    self.requests.lock().unwrap().stop_simulations();
+                }
+            }
             Message::RigidGridSimulation(start) => {
                 if start {
                     let mut request: Option<RigidBodyParametersRequest> = None;
@@ -593,6 +931,65 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                     self.requests.lock().unwrap().stop_simulations();
                 }
             }
+            Message::RollJob => {
+                let selection = self.application_state.get_selection_as_designelement();
+                if let Some(request) = self.edition_tab.get_roll_request(&selection) {
+                    self.start_simulation_job(SimulationJobKind::Roll {
+                        target_helices: request.target_helices,
+                        target_xovers: request.target_xovers,
+                    })
+                }
+            }
+            Message::TwistJob => {
+                if let Some(Selection::Grid(_, grid_id)) =
+                    self.application_state.get_selection().get(0)
+                {
+                    self.start_simulation_job(SimulationJobKind::Twist { grid_id: *grid_id })
+                }
+            }
+            Message::RelaxGridsJob => self.start_simulation_job(SimulationJobKind::RelaxGrids),
+            Message::PauseSimulationJob(id, paused) => self
+                .requests
+                .lock()
+                .unwrap()
+                .pause_simulation_job(id, paused),
+            Message::StopSimulationJob(id) => self.requests.lock().unwrap().stop_simulation_job(id),
+            Message::SimulationJobs(jobs) => self.simulation_tab.set_simulation_jobs(jobs),
+            Message::Plugins(plugins) => self.parameters_tab.set_plugins(plugins),
+            Message::PluginInput(plugin_id, parameter_id, value) => self
+                .parameters_tab
+                .update_plugin_input(plugin_id, parameter_id, value),
+            Message::CompareWithLastSave => self.requests.lock().unwrap().compare_with_last_save(),
+            Message::CompareWithDesignFile => {
+                self.requests.lock().unwrap().compare_with_design_file()
+            }
+            Message::StopDesignComparison => self.requests.lock().unwrap().stop_design_comparison(),
+            Message::Checkpoints(names) => self.camera_tab.set_checkpoints(names),
+            Message::CheckpointNameChanged(name) => self.camera_tab.checkpoint_name = name,
+            Message::CreateCheckpoint => {
+                let name = std::mem::take(&mut self.camera_tab.checkpoint_name);
+                self.requests.lock().unwrap().create_checkpoint(name)
+            }
+            Message::RestoreCheckpoint(checkpoint_id) => self
+                .requests
+                .lock()
+                .unwrap()
+                .restore_checkpoint(checkpoint_id),
+            Message::CompareWithCheckpoint(checkpoint_id) => self
+                .requests
+                .lock()
+                .unwrap()
+                .compare_with_checkpoint(checkpoint_id),
+            Message::DeleteCheckpoint(checkpoint_id) => self
+                .requests
+                .lock()
+                .unwrap()
+                .delete_checkpoint(checkpoint_id),
+            Message::RunPlugin(plugin_id) => {
+                if let Some(values) = self.parameters_tab.plugin_values(plugin_id) {
+                    self.requests.lock().unwrap().run_plugin(plugin_id, values)
+                }
+            }
             Message::RigidHelicesSimulation(start) => {
                 if start {
                     let mut request: Option<RigidBodyParametersRequest> = None;
@@ -654,7 +1051,81 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::ModifiersChanged(modifiers) => self
                 .organizer
                 .new_modifiers(iced_winit::conversion::modifiers(modifiers)),
-            Message::UiSizePicked(ui_size) => self.requests.lock().unwrap().set_ui_size(ui_size),
+            Message::UiSizePicked(ui_size) => {
+                self.parameters_tab.set_ui_scale_preview(None);
+                self.requests.lock().unwrap().set_ui_size(ui_size)
+            }
+            Message::UiScaleSliding(percent) => {
+                self.parameters_tab.set_ui_scale_preview(Some(percent))
+            }
+            Message::ThemePicked(theme) => self.requests.lock().unwrap().set_theme(theme),
+            Message::ColorVisionModePicked(mode) => {
+                self.requests.lock().unwrap().set_color_vision_mode(mode)
+            }
+            Message::LanguagePicked(language) => {
+                self.requests.lock().unwrap().set_language(language)
+            }
+            Message::WidgetBasisPicked(widget_basis) => {
+                self.requests.lock().unwrap().set_widget_basis(widget_basis)
+            }
+            Message::GizmoSizeChanged(size) => self.requests.lock().unwrap().set_gizmo_size(size),
+            Message::UndoLimitChanged(limit) => self.requests.lock().unwrap().set_undo_limit(limit),
+            Message::BinaryDesignFiles(binary) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_binary_design_files(binary),
+            Message::ConfirmLargeDeletions(confirm) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_confirm_large_deletions(confirm),
+            Message::GpuPreferencePicked(gpu_preference) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_gpu_preference(gpu_preference),
+            Message::RenderQualityChanged(render_quality) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_render_quality(render_quality),
+            Message::GroundParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_ground_parameters(parameters),
+            Message::ViewLayerChanged(view, layer) => {
+                self.requests.lock().unwrap().set_view_layer(view, layer)
+            }
+            Message::ScaleBarParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_scale_bar_parameters(parameters),
+            Message::NumberingParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_numbering_parameters(parameters),
+            Message::XoverDrawingParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_xover_drawing_parameters(parameters),
+            Message::StrandEndsParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_strand_ends_parameters(parameters),
+            Message::XoverGuideParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_xover_guide_parameters(parameters),
+            Message::HeatMapPicked(heat_map) => {
+                self.requests.lock().unwrap().set_heat_map(heat_map)
+            }
             Message::UiSizeChanged(ui_size) => self.ui_size = ui_size,
             Message::SetScaffoldSeqButtonPressed => {
                 self.requests
@@ -665,6 +1136,22 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::OptimizeScaffoldShiftPressed => {
                 self.requests.lock().unwrap().optimize_scaffold_shift();
             }
+            Message::OptimizeNicksPressed => self.requests.lock().unwrap().optimize_nicks(),
+            Message::RandomSequenceMinGc(min_gc) => self.sequence_tab.set_random_min_gc(min_gc),
+            Message::RandomSequenceMaxGc(max_gc) => self.sequence_tab.set_random_max_gc(max_gc),
+            Message::ForbiddenMotifsInput(motifs) => self.sequence_tab.set_forbidden_motifs(motifs),
+            Message::FillRandomSequences => self
+                .requests
+                .lock()
+                .unwrap()
+                .fill_random_sequences(self.sequence_tab.get_random_sequence_parameters()),
+            Message::ClearSequencesPressed => self.requests.lock().unwrap().clear_sequences(),
+            Message::AssignScaffoldToSelectionPressed => self
+                .requests
+                .lock()
+                .unwrap()
+                .assign_scaffold_sequence_to_selection(),
+            Message::AuditScaffoldPairing => self.requests.lock().unwrap().audit_scaffold_pairing(),
             Message::StaplesRequested => self.requests.lock().unwrap().download_staples(),
             Message::ToggleText(b) => {
                 self.requests
@@ -685,6 +1172,83 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::ToggleVisibility(b) => self.requests.lock().unwrap().toggle_visibility(b),
             Message::AllVisible => self.requests.lock().unwrap().make_all_elements_visible(),
             Message::Redim2dHelices(b) => self.requests.lock().unwrap().resize_2d_helices(b),
+            Message::PlaceInsertions(target) => {
+                self.requests.lock().unwrap().place_insertions(target)
+            }
+            Message::SetIdealRoll => self.requests.lock().unwrap().set_ideal_roll(),
+            Message::SetTwistAxisFromSelection => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_twist_axis_from_selection(),
+            Message::ClearTwistAxis => self.requests.lock().unwrap().clear_twist_axis(),
+            Message::StartHelicesTwist => self.requests.lock().unwrap().start_helices_twist(),
+            Message::BendingRadiusChanged(radius) => self.edition_tab.set_bending_radius(radius),
+            Message::RollOnSelectedXovers(b) => self.edition_tab.set_roll_on_selected_xovers(b),
+            Message::BendingDirectionChanged(direction) => {
+                self.edition_tab.set_bending_direction(direction)
+            }
+            Message::HelixNumberingPicked(scheme) => self.edition_tab.set_helix_numbering(scheme),
+            Message::RenumberHelices => self
+                .requests
+                .lock()
+                .unwrap()
+                .renumber_helices(self.edition_tab.get_helix_numbering()),
+            Message::HelixDisplayNumberChanged(s) => self.edition_tab.set_helix_display_number(s),
+            Message::SetHelixDisplayNumber(helix) => {
+                if let Some(number) = self.edition_tab.parse_helix_display_number() {
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_helix_display_number(helix, number)
+                }
+            }
+            Message::HelixBundleNameChanged(name) => self.edition_tab.set_helix_bundle_name(name),
+            Message::CreateHelixBundle(helices) => {
+                let mut name = self.edition_tab.get_helix_bundle_name();
+                if name.is_empty() {
+                    let nb_bundles = self
+                        .application_state
+                        .get_reader()
+                        .get_helix_bundles()
+                        .len();
+                    name = tr_args("Bundle {}", &[&(nb_bundles + 1)]);
+                }
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .create_helix_bundle(name, helices)
+            }
+            Message::SelectHelixBundle(bundle_id) => {
+                let bundles = self.application_state.get_reader().get_helix_bundles();
+                if let Some(bundle) = bundles.get(&bundle_id) {
+                    let keys = bundle
+                        .helices
+                        .iter()
+                        .map(|h| DesignElementKey::Helix(*h))
+                        .collect();
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_selected_keys(keys, None, false)
+                }
+            }
+            Message::RenameHelixBundle(bundle_id) => self
+                .requests
+                .lock()
+                .unwrap()
+                .rename_helix_bundle(bundle_id, self.edition_tab.get_helix_bundle_name()),
+            Message::DeleteHelixBundle(bundle_id) => {
+                self.requests.lock().unwrap().delete_helix_bundle(bundle_id)
+            }
+            Message::CleanupMinLengthChanged(length) => {
+                self.edition_tab.set_cleanup_min_length(length)
+            }
+            Message::CleanupStrands => self
+                .requests
+                .lock()
+                .unwrap()
+                .cleanup_strands(self.edition_tab.get_cleanup_min_length()),
             Message::InvertScroll(b) => {
                 self.requests.lock().unwrap().invert_scroll(b);
             }
@@ -699,6 +1263,47 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                 self.contextual_panel
                     .set_small_sphere(b, self.requests.clone());
             }
+            Message::SetGridLabels(b) => self.requests.lock().unwrap().set_grid_labels(b),
+            Message::SetPhantomParameters(parameters) => {
+                self.contextual_panel
+                    .set_phantom_parameters(parameters, self.requests.clone());
+            }
+            Message::FinishSettingPhantomParameters => self
+                .requests
+                .lock()
+                .unwrap()
+                .finish_setting_phantom_parameters(),
+            Message::GroupPivotInput(coordinate, s) => self
+                .contextual_panel
+                .update_group_pivot_input(coordinate, s),
+            Message::HelixBlockInput(field, s) => {
+                self.contextual_panel.update_helix_block_input(field, s)
+            }
+            Message::HelixBlockAlternateOrientation(b) => self
+                .contextual_panel
+                .set_helix_block_alternate_orientation(b),
+            Message::HelixBlockScaffoldRaster(b) => {
+                self.contextual_panel.set_helix_block_scaffold_raster(b)
+            }
+            Message::CreateHelixBlock(grid_id) => {
+                self.contextual_panel
+                    .create_helix_block(grid_id, self.requests.clone());
+            }
+            Message::GroupOperation(group_id, operation) => self
+                .requests
+                .lock()
+                .unwrap()
+                .apply_group_operation(group_id, operation),
+            Message::SimulateGroup(group_id) => {
+                let mut request: Option<RigidBodyParametersRequest> = None;
+                self.simulation_tab.make_rigid_body_request(&mut request);
+                if let Some(request) = request {
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .start_group_simulation(group_id, request);
+                }
+            }
             Message::ScaffoldIdSet(n, b) => {
                 self.contextual_panel
                     .scaffold_id_set(n, b, self.requests.clone());
@@ -720,6 +1325,40 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                 self.contextual_panel.show_tutorial ^= true;
                 self.contextual_panel.force_help = false;
             }
+            Message::FocusInsertionLength => self.contextual_panel.focus_insertion_length(),
+            Message::TutorialStatus(status) => {
+                let new_step = status.as_ref().map(|s| s.step)
+                    != self.contextual_panel.tutorial.as_ref().map(|s| s.step);
+                if let Some(TutorialHighlight::LeftPanelTab(n)) =
+                    status.as_ref().filter(|_| new_step).map(|s| s.highlight)
+                {
+                    self.update(Message::TabSelected(n));
+                }
+                self.contextual_panel.tutorial = status;
+            }
+            Message::StartTutorial => {
+                self.contextual_panel.show_tutorial = false;
+                self.requests.lock().unwrap().start_tutorial();
+            }
+            Message::SkipTutorialStep => self.requests.lock().unwrap().skip_tutorial_step(),
+            Message::StopTutorial => self.requests.lock().unwrap().stop_tutorial(),
+            Message::NewToast(severity, content) => self.toasts.push(severity, content),
+            Message::DismissToast(toast_idx) => self.toasts.dismiss(toast_idx),
+            Message::ExpireToasts(now) => self.toasts.remove_expired(now),
+            Message::ToggleNotificationHistory => self.toasts.toggle_history(),
+            Message::ClearNotificationHistory => self.toasts.clear_history(),
+            Message::NewLogRecords(records) => self.console.add_records(records),
+            Message::SystemInfo(info) => self.console.set_system_info(info),
+            Message::MemoryUsage(usage) => self.console.set_memory_usage(usage),
+            Message::ToggleConsole => self.console.toggle(),
+            Message::ConsoleLevelFilter(level) => self.console.set_level_filter(level),
+            Message::ConsoleModuleFilter(module) => self.console.set_module_filter(module),
+            Message::SetReadOnly(read_only) => self.read_only = read_only,
+            Message::CopyDiagnostics => self
+                .requests
+                .lock()
+                .unwrap()
+                .copy_to_clipboard(self.console.diagnostics()),
             Message::OpenLink(link) => {
                 // ATM we continue even in case of error, later any error will be promted to user
                 let _ = open::that(link);
@@ -744,6 +1383,8 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                 }
                 if state.get_action_mode() != self.application_state.get_action_mode() {
                     self.contextual_panel.state_updated();
+                    self.contextual_panel
+                        .sync_new_strand_parameters(state.get_action_mode());
                 }
                 self.application_state = state;
                 self.revolution_tab.update(&self.application_state);
@@ -766,6 +1407,18 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::DeleteCamera(camera_id) => {
                 self.requests.lock().unwrap().delete_camera(camera_id)
             }
+            Message::SwapCameras(a, b) => {
+                self.requests.lock().unwrap().swap_cameras(a, b);
+                self.camera_shortcut.swap_thumbnails(a, b);
+            }
+            Message::StoreCameraRendering(camera_id, store) => self
+                .requests
+                .lock()
+                .unwrap()
+                .store_camera_rendering(camera_id, store),
+            Message::CameraThumbnail(camera_id, thumbnail) => {
+                self.camera_shortcut.set_thumbnail(camera_id, thumbnail)
+            }
             Message::SelectCamera(camera_id) => {
                 self.requests.lock().unwrap().select_camera(camera_id)
             }
@@ -773,6 +1426,32 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                 self.requests.lock().unwrap().create_new_camera();
                 self.camera_shortcut.scroll_down()
             }
+            Message::AddPresentationStep => {
+                self.requests.lock().unwrap().add_presentation_step();
+                self.camera_shortcut.scroll_down()
+            }
+            Message::DeletePresentationStep(step) => {
+                self.requests.lock().unwrap().delete_presentation_step(step)
+            }
+            Message::SubmitPresentationStepName => {
+                if let Some((step, name)) = self.camera_shortcut.stop_editing_step() {
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_presentation_step_name(step, name);
+                }
+            }
+            Message::EditPresentationStepName(name) => {
+                self.camera_shortcut.set_step_input_name(name)
+            }
+            Message::StartEditPresentationStepName(step) => {
+                self.camera_shortcut.start_editing_step(step)
+            }
+            Message::PresentationStep(forward) => self
+                .requests
+                .lock()
+                .unwrap()
+                .go_to_presentation_step(forward),
             Message::NewSuggestionParameters(param) => {
                 self.requests
                     .lock()
@@ -800,6 +1479,14 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::FollowStereographicCamera(b) => {
                 self.requests.lock().unwrap().follow_stereographic_camera(b)
             }
+            Message::InsideOutInspection(b) => {
+                self.requests.lock().unwrap().set_inside_out_inspection(b)
+            }
+            Message::BrushParametersChanged(parameters) => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_brush_parameters(parameters),
             Message::ShowStereographicCamera(b) => {
                 self.requests
                     .lock()
@@ -860,9 +1547,31 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                     .unwrap()
                     .turn_path_into_grid(path_id, grid_type);
             }
+            Message::AddHelicesAlongPath {
+                path_id,
+                with_strands,
+            } => self
+                .requests
+                .lock()
+                .unwrap()
+                .add_helices_along_path(path_id, with_strands),
+            Message::SetHelicesAlongPathWithStrands(with_strands) => {
+                self.pen_tab.set_helices_with_strands(with_strands)
+            }
+            Message::EditBezierPathControlPoints { path_id, edit } => self
+                .requests
+                .lock()
+                .unwrap()
+                .edit_bezier_path_control_points(path_id, edit),
+            Message::ResampledVerticesChanged(nb_vertices) => {
+                self.pen_tab.set_nb_resampled_vertices(nb_vertices)
+            }
             Message::SetShowBezierPaths(b) => {
                 self.requests.lock().unwrap().set_show_bezier_paths(b)
             }
+            Message::SetShowInsertionBadges(b) => {
+                self.requests.lock().unwrap().set_show_insertion_badges(b)
+            }
             Message::MakeBezierPathCyclic { path_id, cyclic } => {
                 self.requests
                     .lock()
@@ -874,6 +1583,15 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             Message::CancelExport => {
                 self.requests.lock().unwrap().set_exporting(false);
             }
+            Message::PrintParametersChanged(parameters) => {
+                self.exports_menu.print_parameters = parameters
+            }
+            Message::DesignBrowser(choice) => {
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .choose_in_design_browser(choice);
+            }
             Message::CurveBuilderPicked(builder) => {
                 self.revolution_tab.set_builder(builder);
                 let bezier_path_id = self.revolution_tab.get_current_bezier_path_id();
@@ -917,6 +1635,7 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                     .set_unrooted_surface(unrooted_surface);
             }
             Message::InitRevolutionRelaxation => {
+                self.revolution_tab.relaxation_progress = None;
                 if let Some(desc) = self
                     .revolution_tab
                     .get_revolution_system(&self.application_state, true)
@@ -950,60 +1669,84 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             }
             Message::IncrRevolutionShift => self.revolution_tab.shift_idx += 1,
             Message::DecrRevolutionShift => self.revolution_tab.shift_idx -= 1,
+            Message::RevolutionRelaxationProgress(progress) => {
+                self.revolution_tab.relaxation_progress = Some(progress)
+            }
         };
         Command::none()
     }
 
     fn view(&mut self) -> Element<Message<S>> {
         let width = self.logical_size.cast::<u16>().width;
-        let tabs: Tabs<Message<S>, iced_wgpu::Backend> =
-            Tabs::new(self.selected_tab, Message::TabSelected)
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::GridOn))),
-                    self.grid_tab
-                        .view(self.ui_size, width, &self.application_state),
-                )
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Edit))),
-                    self.edition_tab
-                        .view(self.ui_size, width, &self.application_state),
-                )
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Videocam))),
-                    self.camera_tab.view(self.ui_size, &self.application_state),
-                )
-                .push(
-                    TabLabel::Icon(ICON_PHYSICAL_ENGINE),
-                    self.simulation_tab
-                        .view(self.ui_size, &self.application_state),
-                )
-                .push(
-                    TabLabel::Icon(ICON_ATGC),
-                    self.sequence_tab
-                        .view(self.ui_size, &self.application_state),
-                )
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Settings))),
-                    self.parameters_tab
-                        .view(self.ui_size, &self.application_state),
-                )
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Draw))),
-                    self.pen_tab.view(self.ui_size, &self.application_state),
-                )
-                .push(
-                    TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::AutoMode))),
-                    self.revolution_tab
-                        .view(self.ui_size, &self.application_state),
-                )
-                .text_size(self.ui_size.icon())
-                .text_font(ICONFONT)
-                .icon_font(ENSNANO_FONT)
-                .icon_size(self.ui_size.icon())
-                .tab_bar_height(Length::Units(self.ui_size.button()))
-                .tab_bar_style(TabStyle)
-                .width(Length::Units(width))
-                .height(Length::Fill);
+        let first_container = if let Some(content) = self.application_state.get_design_browser() {
+            Container::new(self.design_browser.view(self.ui_size, content))
+                .height(Length::FillPortion(2))
+        } else if self.application_state.is_exporting() {
+            Container::new(self.exports_menu.view()).height(Length::FillPortion(2))
+        } else if self.read_only {
+            // Editing tabs are hidden in viewer mode
+            Container::new(self.camera_tab.view(self.ui_size, &self.application_state))
+                .height(Length::FillPortion(2))
+        } else {
+            let tabs: Tabs<Message<S>, iced_wgpu::Backend> =
+                Tabs::new(self.selected_tab, Message::TabSelected)
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::GridOn))),
+                        self.grid_tab
+                            .view(self.ui_size, width, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Edit))),
+                        self.edition_tab
+                            .view(self.ui_size, width, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Videocam))),
+                        self.camera_tab.view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Icon(ICON_PHYSICAL_ENGINE),
+                        self.simulation_tab
+                            .view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Icon(ICON_ATGC),
+                        self.sequence_tab
+                            .view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Settings))),
+                        self.parameters_tab
+                            .view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Draw))),
+                        self.pen_tab.view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::AutoMode))),
+                        self.revolution_tab
+                            .view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::TableChart))),
+                        self.staples_tab.view(self.ui_size, &self.application_state),
+                    )
+                    .push(
+                        TabLabel::Text(format!("{}", icon_to_char(MaterialIcon::Description))),
+                        self.metadata_tab
+                            .view(self.ui_size, &self.application_state),
+                    )
+                    .text_size(self.ui_size.icon())
+                    .text_font(ICONFONT)
+                    .icon_font(ENSNANO_FONT)
+                    .icon_size(self.ui_size.icon())
+                    .tab_bar_height(Length::Units(self.ui_size.button()))
+                    .tab_bar_style(TabStyle)
+                    .width(Length::Units(width))
+                    .height(Length::Fill);
+            Container::new(tabs).height(Length::FillPortion(2))
+        };
         let camera_shortcut =
             self.camera_shortcut
                 .view(self.ui_size, width, &self.application_state);
@@ -1038,12 +1781,8 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
             .organizer
             .view(selection)
             .map(|m| Message::OrganizerMessage(m));
-
-        let first_container = if self.application_state.is_exporting() {
-            Container::new(self.exports_menu.view()).height(Length::FillPortion(2))
-        } else {
-            Container::new(tabs).height(Length::FillPortion(2))
-        };
+        let organizer = self.console.view(organizer, self.ui_size);
+        let organizer = self.toasts.view(organizer, self.ui_size);
 
         Container::new(
             Column::new()
@@ -1052,34 +1791,61 @@ impl<R: Requests, S: AppState> Program for LeftPanel<R, S> {
                 .push(iced::Rule::horizontal(5))
                 .push(Container::new(camera_shortcut).height(Length::FillPortion(1)))
                 .push(iced::Rule::horizontal(5))
-                .push(Container::new(contextual_menu).height(Length::FillPortion(1)))
+                .push(
+                    Container::new(contextual_menu)
+                        .height(Length::FillPortion(1))
+                        .style(TutorialHighlightStyle(
+                            self.contextual_panel.tutorial.as_ref().map(|t| t.highlight)
+                                == Some(TutorialHighlight::ContextualPanel),
+                        )),
+                )
                 .push(iced::Rule::horizontal(5))
                 .push(Container::new(organizer).height(Length::FillPortion(2)))
                 .padding(3),
         )
-        .style(TopBarStyle)
+        .style(TopBarStyle(self.application_state.get_theme()))
         .height(Length::Units(self.logical_size.height as u16))
         .into()
     }
 }
 
-struct TopBarStyle;
+struct TopBarStyle(Theme);
 impl container::StyleSheet for TopBarStyle {
     fn style(&self) -> container::Style {
+        let palette = crate::theme::palette(self.0);
         container::Style {
-            background: Some(Background::Color(BACKGROUND)),
-            text_color: Some(Color::WHITE),
+            background: Some(Background::Color(palette.left_panel)),
+            text_color: Some(palette.text),
             ..container::Style::default()
         }
     }
 }
 
+/// Draws a border around the regions that the current step of the tutorial refers to.
+struct TutorialHighlightStyle(bool);
+impl container::StyleSheet for TutorialHighlightStyle {
+    fn style(&self) -> container::Style {
+        if self.0 {
+            container::Style {
+                border_color: crate::consts::TUTORIAL_HIGHLIGHT_COLOR,
+                border_width: crate::consts::TUTORIAL_HIGHLIGHT_WIDTH,
+                ..container::Style::default()
+            }
+        } else {
+            container::Style::default()
+        }
+    }
+}
+
 pub const BACKGROUND: Color = Color::from_rgb(
     0x23 as f32 / 255.0,
     0x27 as f32 / 255.0,
     0x2A as f32 / 255.0,
 );
 
+/// Height, in logical pixels, of the title bar by which floating windows can be dragged.
+pub const OVERLAY_TITLE_BAR_HEIGHT: u16 = 24;
+
 pub struct ColorOverlay<R: Requests> {
     logical_size: LogicalSize<f64>,
     color_picker: ColorPicker,
@@ -1106,7 +1872,6 @@ impl<R: Requests> ColorOverlay<R> {
 pub enum ColorMessage {
     HsvSatValueChanged(f64, f64),
     HueChanged(f64),
-    #[allow(dead_code)]
     Resized(LogicalSize<f64>),
     FinishChangingColor,
     Closed,
@@ -1118,7 +1883,12 @@ impl<R: Requests> Program for ColorOverlay<R> {
 
     fn update(&mut self, message: ColorMessage) -> Command<ColorMessage> {
         match message {
-            ColorMessage::HsvSatValueChanged(_sat, _value) => {}
+            ColorMessage::HsvSatValueChanged(saturation, value) => {
+                self.color_picker.set_saturation(saturation);
+                self.color_picker.set_hsv_value(value);
+                let color = color_to_u32(self.color_picker.update_color());
+                self.requests.lock().unwrap().change_strand_color(color);
+            }
             ColorMessage::HueChanged(x) => self.color_picker.change_hue(x as f64),
             ColorMessage::Closed => {
                 self.requests
@@ -1137,14 +1907,20 @@ impl<R: Requests> Program for ColorOverlay<R> {
     fn view(&mut self) -> Element<ColorMessage> {
         let width = self.logical_size.cast::<u16>().width;
 
+        let title_bar = Container::new(Text::new(tr("Strand color")))
+            .width(Length::Fill)
+            .height(Length::Units(OVERLAY_TITLE_BAR_HEIGHT))
+            .center_y();
+
         let widget = Column::new()
             .width(Length::Units(width))
             .height(Length::Fill)
+            .push(title_bar)
             .spacing(5)
             .push(self.color_picker.new_view())
             .spacing(5)
             .push(
-                Button::new(&mut self.close_button, Text::new("Close"))
+                Button::new(&mut self.close_button, Text::new(tr("Close")))
                     .on_press(ColorMessage::Closed),
             );
 
@@ -1638,8 +2414,9 @@ fn right_checkbox<'a, F, S: AppState>(
 where
     F: 'static + Fn(bool) -> Message<S>,
 {
+    let label: String = label.into();
     Row::new()
-        .push(Text::new(label))
+        .push(Text::new(tr(&label)))
         .push(Checkbox::new(is_checked, "", f).size(ui_size.checkbox()))
         .spacing(CHECKBOXSPACING)
         .into()
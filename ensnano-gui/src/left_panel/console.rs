@@ -0,0 +1,227 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A console displaying the warnings and errors that were logged by the application.
+//!
+//! The console is shown in place of the organizer. The records can be filtered by level and by
+//! module, and a diagnostics report can be copied to the clipboard to be attached to bug reports.
+
+use super::*;
+use ensnano_interactor::i18n::tr_args;
+use iced_native::widget::scrollable;
+
+/// The maximum number of log records kept in the console.
+pub const MAX_NB_RECORDS: usize = 1000;
+const LEVELS: &[log::Level] = &[log::Level::Error, log::Level::Warn];
+
+/// A log record captured by the application's logger
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: log::Level,
+    /// The module in which the record was emitted
+    pub module: String,
+    pub message: String,
+}
+
+/// The memory used by the application, displayed in the console
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub undo_entries: usize,
+    pub redo_entries: usize,
+    pub undo_limit: usize,
+    /// The resident memory of the process in bytes, if it could be measured
+    pub resident_bytes: Option<u64>,
+}
+
+impl MemoryUsage {
+    fn lines(&self) -> Vec<String> {
+        let mut ret = vec![tr_args(
+            "Undo history: {}/{} operations, redo: {}",
+            &[&self.undo_entries, &self.undo_limit, &self.redo_entries],
+        )];
+        if let Some(bytes) = self.resident_bytes {
+            ret.push(tr_args("Memory used: {} MiB", &[&(bytes / (1 << 20))]));
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{} {}] {}", self.level, self.module, self.message)
+    }
+}
+
+pub struct LogConsole {
+    records: Vec<LogRecord>,
+    showing: bool,
+    /// The least severe level of the displayed records
+    level_filter: log::Level,
+    level_pick_list: pick_list::State<log::Level>,
+    module_filter: String,
+    module_input: text_input::State,
+    /// Information about the system, included in the diagnostics report
+    system_info: String,
+    memory_usage: MemoryUsage,
+    toggle_button: button::State,
+    copy_button: button::State,
+    scroll: scrollable::State,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self {
+            records: Vec::new(),
+            showing: false,
+            level_filter: log::Level::Warn,
+            level_pick_list: Default::default(),
+            module_filter: String::new(),
+            module_input: Default::default(),
+            system_info: String::new(),
+            memory_usage: Default::default(),
+            toggle_button: Default::default(),
+            copy_button: Default::default(),
+            scroll: Default::default(),
+        }
+    }
+}
+
+impl LogConsole {
+    pub fn add_records(&mut self, records: Vec<LogRecord>) {
+        self.records.extend(records);
+        if self.records.len() > MAX_NB_RECORDS {
+            let nb_removed = self.records.len() - MAX_NB_RECORDS;
+            self.records.drain(..nb_removed);
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.showing ^= true;
+    }
+
+    pub fn set_level_filter(&mut self, level: log::Level) {
+        self.level_filter = level;
+    }
+
+    pub fn set_module_filter(&mut self, module: String) {
+        self.module_filter = module;
+    }
+
+    pub fn set_system_info(&mut self, system_info: String) {
+        self.system_info = system_info;
+    }
+
+    pub fn set_memory_usage(&mut self, memory_usage: MemoryUsage) {
+        self.memory_usage = memory_usage;
+    }
+
+    pub fn has_keyboard_priority(&self) -> bool {
+        self.showing && self.module_input.is_focused()
+    }
+
+    /// A report containing the system information and all the captured records
+    pub fn diagnostics(&self) -> String {
+        let mut ret = self.system_info.clone();
+        for line in self.memory_usage.lines() {
+            ret.push_str(&format!("\n{line}"));
+        }
+        ret.push_str("\n\nCaptured log records:\n");
+        for record in self.records.iter() {
+            ret.push_str(&format!("{record}\n"));
+        }
+        ret
+    }
+
+    /// Display the console in place of `organizer` if it is being shown.
+    pub fn view<'a, S: AppState>(
+        &'a mut self,
+        organizer: Element<'a, Message<S>>,
+        ui_size: UiSize,
+    ) -> Element<'a, Message<S>> {
+        let toggle_label = if self.showing {
+            "Hide console"
+        } else {
+            "Console"
+        };
+        let toggle_button = text_btn(&mut self.toggle_button, toggle_label, ui_size)
+            .on_press(Message::ToggleConsole);
+
+        let main_content: Element<'a, Message<S>> = if self.showing {
+            let mut records = Column::new().spacing(2);
+            for record in self
+                .records
+                .iter()
+                .filter(|r| {
+                    r.level <= self.level_filter && r.module.contains(self.module_filter.as_str())
+                })
+                .rev()
+            {
+                let color = if record.level == log::Level::Error {
+                    Color::from_rgb(0.90, 0.35, 0.35)
+                } else {
+                    Color::from_rgb(0.84, 0.57, 0.20)
+                };
+                records = records.push(
+                    Text::new(record.to_string())
+                        .size(ui_size.main_text())
+                        .color(color),
+                );
+            }
+            let filters = Row::new()
+                .spacing(5)
+                .push(PickList::new(
+                    &mut self.level_pick_list,
+                    LEVELS,
+                    Some(self.level_filter),
+                    Message::ConsoleLevelFilter,
+                ))
+                .push(
+                    TextInput::new(
+                        &mut self.module_input,
+                        "Module",
+                        &self.module_filter,
+                        Message::ConsoleModuleFilter,
+                    )
+                    .size(ui_size.main_text()),
+                )
+                .push(
+                    text_btn(&mut self.copy_button, "Copy diagnostics", ui_size)
+                        .on_press(Message::CopyDiagnostics),
+                );
+            let mut memory = Column::new();
+            for line in self.memory_usage.lines() {
+                memory = memory.push(Text::new(line).size(ui_size.main_text()));
+            }
+            Column::new()
+                .push(filters)
+                .push(memory)
+                .push(
+                    Scrollable::new(&mut self.scroll)
+                        .push(records)
+                        .height(Length::Fill),
+                )
+                .into()
+        } else {
+            organizer
+        };
+
+        Column::new()
+            .push(Container::new(main_content).height(Length::Fill))
+            .push(toggle_button)
+            .into()
+    }
+}
@@ -17,8 +17,14 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 use super::super::DesignReader;
 use super::*;
-use ensnano_design::{grid::GridId, BezierVertexId};
-use ensnano_interactor::{Selection, SimulationState};
+use ensnano_design::{
+    grid::{GridId, PhantomDirection, PhantomParameters},
+    BezierVertexId, HelixParameters,
+};
+use ensnano_interactor::{
+    graphics::LengthUnit, i18n::tr_args, GroupOperation, HelixBlockDescriptor, Selection,
+    SimulationState,
+};
 use iced::{scrollable, Scrollable};
 
 mod value_constructor;
@@ -182,13 +188,26 @@ pub(super) struct ContextualPanel<S: AppState> {
     width: u32,
     pub force_help: bool,
     pub show_tutorial: bool,
+    /// The current step of the interactive tutorial, if one is running
+    pub tutorial: Option<TutorialStatus>,
+    start_tutorial_btn: button::State,
+    skip_tutorial_btn: button::State,
+    stop_tutorial_btn: button::State,
     help_btn: button::State,
     ens_nano_website: button::State,
     add_strand_menu: AddStrandMenu,
     strand_name_state: text_input::State,
+    pool_name_state: text_input::State,
+    pool_concentration_state: PoolConcentrationState,
+    strand_note_state: text_input::State,
+    strand_url_state: text_input::State,
+    open_url_button: button::State,
     builder: Option<InstantiatedBuilder<S>>,
     twist_button: button::State,
     insertion_length_state: InsertionLengthState,
+    phantom_widgets: PhantomWidgets,
+    helix_block_menu: HelixBlockMenu,
+    group_menu: GroupMenu,
 }
 
 impl<S: AppState> ContextualPanel<S> {
@@ -198,13 +217,25 @@ impl<S: AppState> ContextualPanel<S> {
             width,
             force_help: false,
             show_tutorial: false,
+            tutorial: None,
+            start_tutorial_btn: Default::default(),
+            skip_tutorial_btn: Default::default(),
+            stop_tutorial_btn: Default::default(),
             help_btn: Default::default(),
             ens_nano_website: Default::default(),
             add_strand_menu: Default::default(),
             strand_name_state: Default::default(),
+            pool_name_state: Default::default(),
+            pool_concentration_state: Default::default(),
+            strand_note_state: Default::default(),
+            strand_url_state: Default::default(),
+            open_url_button: Default::default(),
             builder: None,
             twist_button: Default::default(),
             insertion_length_state: Default::default(),
+            phantom_widgets: Default::default(),
+            helix_block_menu: Default::default(),
+            group_menu: Default::default(),
         }
     }
 
@@ -259,15 +290,60 @@ impl<S: AppState> ContextualPanel<S> {
             .and_then(|id| app_state.get_reader().xover_length(id));
 
         self.insertion_length_state.update_selection(selection);
-        let info_values = values_of_selection(selection, app_state.get_reader().as_ref());
+        self.pool_concentration_state.update_selection(selection);
+        let length_unit = app_state.get_scale_bar_parameters().length_unit;
+        let helix_parameters = app_state.get_dna_parameters();
+        let info_values = values_of_selection(
+            selection,
+            app_state.get_reader().as_ref(),
+            length_unit,
+            &helix_parameters,
+        );
+        if let Some(tutorial) = self.tutorial.as_ref() {
+            column = column.push(
+                Text::new(tr_args(
+                    "Tutorial: step {}/{}",
+                    &[&(tutorial.step + 1), &tutorial.nb_steps],
+                ))
+                .size(ui_size.head_text()),
+            );
+            column = column.push(Text::new(tr(tutorial.instruction)).size(ui_size.main_text()));
+            column = column.push(
+                Text::new(tr_args("See {}.", &[&tutorial.highlight.description()]))
+                    .size(ui_size.main_text()),
+            );
+            column = column.push(
+                Row::new()
+                    .spacing(5)
+                    .push(
+                        text_btn(&mut self.skip_tutorial_btn, "Skip step", ui_size)
+                            .on_press(Message::SkipTutorialStep),
+                    )
+                    .push(
+                        text_btn(&mut self.stop_tutorial_btn, "Quit tutorial", ui_size)
+                            .on_press(Message::StopTutorial),
+                    ),
+            );
+            column = column.push(iced::Rule::horizontal(5));
+        }
         if self.show_tutorial {
             column = column.push(
-                Text::new("Tutorials")
+                Text::new(tr("Tutorials"))
                     .size(ui_size.head_text())
                     .width(Length::Fill)
                     .horizontal_alignment(iced::alignment::Horizontal::Center),
             );
-            column = column.push(Text::new("ENSnano website"));
+            if self.tutorial.is_none() {
+                column = column.push(
+                    text_btn(
+                        &mut self.start_tutorial_btn,
+                        "Start interactive tutorial",
+                        ui_size,
+                    )
+                    .on_press(Message::StartTutorial),
+                );
+            }
+            column = column.push(Text::new(tr("ENSnano website")));
             column = column.push(link_row(
                 &mut self.ens_nano_website,
                 "http://ens-lyon.fr/ensnano",
@@ -291,7 +367,16 @@ impl<S: AppState> ContextualPanel<S> {
                     .push(Column::new().width(Length::FillPortion(1)).push(help_btn))
                     .push(iced::Space::with_width(Length::FillPortion(1))),
             );
-            column = column.push(Text::new(format!("{} objects selected", nb_selected)));
+            column = column.push(Text::new(tr_args("{} objects selected", &[&nb_selected])));
+            if let Some(group_id) = app_state.get_selected_group() {
+                column = self.group_menu.add_to_column(
+                    column,
+                    group_id,
+                    app_state.get_selected_group_pivot_position(),
+                    app_state.get_simulation_state() == SimulationState::None,
+                    ui_size,
+                );
+            }
         } else {
             let help_btn =
                 text_btn(&mut self.help_btn, "Help", ui_size).on_press(Message::ForceHelp);
@@ -317,18 +402,39 @@ impl<S: AppState> ContextualPanel<S> {
                         SimulationState::None => TwistStatus::CanTwist,
                         _ => TwistStatus::CannotTwist,
                     };
+                    let phantom_parameters =
+                        app_state.get_reader().get_grid_phantom_parameters(*g_id);
+                    let persistent_phantoms =
+                        app_state.get_reader().grid_has_persistent_phantom(*g_id);
                     column = add_grid_content(
                         column,
                         info_values.as_slice(),
+                        app_state.get_reader().grid_has_labels(*g_id),
                         ui_size,
                         &mut self.twist_button,
                         twisting,
-                    )
+                    );
+                    column = self.phantom_widgets.add_to_column(
+                        column,
+                        phantom_parameters,
+                        persistent_phantoms,
+                        ui_size,
+                    );
+                    if let GridId::FreeGrid(_) = g_id {
+                        column = self.helix_block_menu.add_to_column(column, *g_id, ui_size);
+                    }
                 }
                 Selection::Strand(_, _) => {
                     column = add_strand_content(
                         column,
-                        &mut self.strand_name_state,
+                        StrandContentStates {
+                            name: &mut self.strand_name_state,
+                            pool_name: &mut self.pool_name_state,
+                            pool_concentration: &mut self.pool_concentration_state,
+                            note: &mut self.strand_note_state,
+                            url: &mut self.strand_url_state,
+                            open_url: &mut self.open_url_button,
+                        },
                         info_values.as_slice(),
                         ui_size,
                     )
@@ -352,9 +458,20 @@ impl<S: AppState> ContextualPanel<S> {
             if let Some(builder) = &mut self.builder {
                 column = column.push(builder.builder.view(ui_size, selection, app_state))
             }
+            if let Some(group_id) = app_state.get_selected_group() {
+                column = self.group_menu.add_to_column(
+                    column,
+                    group_id,
+                    app_state.get_selected_group_pivot_position(),
+                    app_state.get_simulation_state() == SimulationState::None,
+                    ui_size,
+                );
+            }
         }
 
-        if let Some(info_values) = xover_len.map(|v| fmt_xover_len(Some(v))) {
+        if let Some(info_values) =
+            xover_len.map(|v| fmt_xover_len(Some(v), length_unit, &helix_parameters))
+        {
             if let Some(info) = info_values.get(0) {
                 column = column.push(Text::new(info));
             }
@@ -364,6 +481,7 @@ impl<S: AppState> ContextualPanel<S> {
         }
 
         if let Some(len) = app_state.get_reader().get_insertion_length(selection) {
+            self.insertion_length_state.apply_focus_request();
             let real_len_string = len.to_string();
             let text_input_content = self
                 .insertion_length_state
@@ -371,7 +489,7 @@ impl<S: AppState> ContextualPanel<S> {
                 .as_ref()
                 .unwrap_or(&real_len_string);
             column = column.push(
-                Row::new().push(Text::new("Loopout")).push(
+                Row::new().push(Text::new(tr("Loopout"))).push(
                     TextInput::new(
                         &mut self.insertion_length_state.state,
                         "",
@@ -404,6 +522,38 @@ impl<S: AppState> ContextualPanel<S> {
         requests.lock().unwrap().set_small_sphere(b);
     }
 
+    pub fn set_phantom_parameters<R: Requests>(
+        &mut self,
+        parameters: PhantomParameters,
+        requests: Arc<Mutex<R>>,
+    ) {
+        requests.lock().unwrap().set_phantom_parameters(parameters);
+    }
+
+    pub fn update_group_pivot_input(&mut self, coordinate: usize, input: String) {
+        if let Some(pivot_str) = self.group_menu.pivot_str.get_mut(coordinate) {
+            *pivot_str = input;
+        }
+    }
+
+    pub fn update_helix_block_input(&mut self, field: HelixBlockField, input: String) {
+        self.helix_block_menu.inputs_str[field as usize] = input;
+    }
+
+    pub fn set_helix_block_alternate_orientation(&mut self, alternate_orientation: bool) {
+        self.helix_block_menu.alternate_orientation = alternate_orientation;
+    }
+
+    pub fn set_helix_block_scaffold_raster(&mut self, scaffold_raster: bool) {
+        self.helix_block_menu.scaffold_raster = scaffold_raster;
+    }
+
+    pub fn create_helix_block<R: Requests>(&mut self, grid_id: GridId, requests: Arc<Mutex<R>>) {
+        if let Some(block) = self.helix_block_menu.descriptor(grid_id) {
+            requests.lock().unwrap().add_helix_block(block)
+        }
+    }
+
     pub fn scaffold_id_set<R: Requests>(&mut self, n: usize, b: bool, requests: Arc<Mutex<R>>) {
         if b {
             requests.lock().unwrap().set_scaffold_id(Some(n))
@@ -425,9 +575,20 @@ impl<S: AppState> ContextualPanel<S> {
         self.add_strand_menu.update_length_str(length_str)
     }
 
+    /// Show the double strand parameters that were adjusted from the scenes
+    pub(super) fn sync_new_strand_parameters(&mut self, action_mode: ActionMode) {
+        self.add_strand_menu.sync_with_action_mode(action_mode)
+    }
+
     pub fn has_keyboard_priority(&self) -> bool {
         self.add_strand_menu.has_keyboard_priority()
+            || self.helix_block_menu.has_keyboard_priority()
+            || self.group_menu.has_keyboard_priority()
             || self.strand_name_state.is_focused()
+            || self.pool_name_state.is_focused()
+            || self.pool_concentration_state.state.is_focused()
+            || self.strand_note_state.is_focused()
+            || self.strand_url_state.is_focused()
             || self.builder_has_keyboard_priority()
             || self.insertion_length_state.has_keyboard_priority()
     }
@@ -481,6 +642,17 @@ impl<S: AppState> ContextualPanel<S> {
         }
     }
 
+    pub fn update_pool_concentration_input(&mut self, input: String) {
+        self.pool_concentration_state.input_str = Some(input);
+    }
+
+    /// Give the keyboard focus to the input of the length of the selected insertion
+    pub fn focus_insertion_length(&mut self) {
+        self.insertion_length_state.focus_requested = true;
+        self.force_help = false;
+        self.show_tutorial = false;
+    }
+
     pub fn update_insertion_length_input(&mut self, input: String) {
         self.insertion_length_state.input_str = Some(input);
     }
@@ -498,6 +670,357 @@ impl<S: AppState> ContextualPanel<S> {
     }
 }
 
+#[derive(Default)]
+struct PhantomWidgets {
+    length_slider: slider::State,
+    opacity_slider: slider::State,
+    direction_pick_list: pick_list::State<PhantomDirection>,
+}
+
+impl PhantomWidgets {
+    fn add_to_column<'a, S: AppState>(
+        &'a mut self,
+        mut column: Column<'a, Message<S>>,
+        parameters: PhantomParameters,
+        persistent: bool,
+        ui_size: UiSize,
+    ) -> Column<'a, Message<S>> {
+        let text = |s: String| {
+            if persistent {
+                Text::new(s).size(ui_size.main_text())
+            } else {
+                Text::new(s)
+                    .size(ui_size.main_text())
+                    .color([0.6, 0.6, 0.6])
+            }
+        };
+
+        let length_slider = Slider::new(
+            &mut self.length_slider,
+            0f32..=(PhantomParameters::MAX_LENGTH as f32),
+            parameters.length as f32,
+            move |length| {
+                Message::SetPhantomParameters(PhantomParameters {
+                    length: length.round() as usize,
+                    ..parameters
+                })
+            },
+        )
+        .on_release(Message::FinishSettingPhantomParameters);
+        let opacity_slider = Slider::new(
+            &mut self.opacity_slider,
+            0f32..=1f32,
+            parameters.opacity,
+            move |opacity| {
+                Message::SetPhantomParameters(PhantomParameters {
+                    opacity,
+                    ..parameters
+                })
+            },
+        )
+        .step(0.05)
+        .on_release(Message::FinishSettingPhantomParameters);
+        let (length_slider, opacity_slider) = if persistent {
+            (length_slider, opacity_slider)
+        } else {
+            (
+                length_slider.style(DesactivatedSlider),
+                opacity_slider.style(DesactivatedSlider),
+            )
+        };
+
+        column = column.push(
+            Row::new()
+                .spacing(5)
+                .push(text(format!("Length {}", parameters.length)))
+                .push(length_slider),
+        );
+        column = column.push(
+            Row::new()
+                .spacing(5)
+                .push(text(format!("Opacity {:.2}", parameters.opacity)))
+                .push(opacity_slider),
+        );
+        column = column.push(
+            Row::new()
+                .spacing(5)
+                .push(text("Direction".to_string()))
+                .push(PickList::new(
+                    &mut self.direction_pick_list,
+                    PhantomDirection::ALL,
+                    Some(parameters.direction),
+                    move |direction| {
+                        Message::SetPhantomParameters(PhantomParameters {
+                            direction,
+                            ..parameters
+                        })
+                    },
+                )),
+        );
+        column
+    }
+}
+
+/// The numerical parameters of a block of helices that are typed by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelixBlockField {
+    X = 0,
+    Y = 1,
+    NbRows = 2,
+    NbColumns = 3,
+    Start = 4,
+    Length = 5,
+}
+
+struct HelixBlockMenu {
+    inputs: [text_input::State; 6],
+    inputs_str: [String; 6],
+    alternate_orientation: bool,
+    scaffold_raster: bool,
+    create_btn: button::State,
+}
+
+impl Default for HelixBlockMenu {
+    fn default() -> Self {
+        Self {
+            inputs: Default::default(),
+            inputs_str: [
+                "0".into(),
+                "0".into(),
+                "2".into(),
+                "2".into(),
+                "0".into(),
+                "32".into(),
+            ],
+            alternate_orientation: true,
+            scaffold_raster: false,
+            create_btn: Default::default(),
+        }
+    }
+}
+
+impl HelixBlockMenu {
+    fn has_keyboard_priority(&self) -> bool {
+        self.inputs.iter().any(|s| s.is_focused())
+    }
+
+    fn parse<T: std::str::FromStr>(&self, field: HelixBlockField) -> Option<T> {
+        self.inputs_str[field as usize].parse().ok()
+    }
+
+    /// Return the descriptor of the block, or `None` if one of the inputs is not valid.
+    fn descriptor(&self, grid_id: GridId) -> Option<HelixBlockDescriptor> {
+        Some(HelixBlockDescriptor {
+            grid_id,
+            x: self.parse(HelixBlockField::X)?,
+            y: self.parse(HelixBlockField::Y)?,
+            nb_rows: self.parse(HelixBlockField::NbRows).filter(|n| *n > 0)?,
+            nb_columns: self.parse(HelixBlockField::NbColumns).filter(|n| *n > 0)?,
+            start: self.parse(HelixBlockField::Start)?,
+            length: self.parse(HelixBlockField::Length)?,
+            alternate_orientation: self.alternate_orientation,
+            scaffold_raster: self.scaffold_raster,
+        })
+    }
+
+    fn add_to_column<'a, S: AppState>(
+        &'a mut self,
+        mut column: Column<'a, Message<S>>,
+        grid_id: GridId,
+        ui_size: UiSize,
+    ) -> Column<'a, Message<S>> {
+        let descriptor = self.descriptor(grid_id);
+        column = column.push(Text::new(tr("Helix block")).size(ui_size.intermediate_text()));
+
+        let mut inputs = self.inputs.iter_mut();
+        let mut input_row = |labels: [(&str, HelixBlockField); 2]| {
+            let mut row = Row::new().spacing(5);
+            for (label, field) in labels {
+                let value = &self.inputs_str[field as usize];
+                let valid = match field {
+                    HelixBlockField::X | HelixBlockField::Y | HelixBlockField::Start => {
+                        value.parse::<isize>().is_ok()
+                    }
+                    HelixBlockField::NbRows | HelixBlockField::NbColumns => {
+                        value.parse::<usize>().ok().filter(|n| *n > 0).is_some()
+                    }
+                    HelixBlockField::Length => value.parse::<usize>().is_ok(),
+                };
+                row = row.push(
+                    Column::new()
+                        .push(Text::new(label).size(ui_size.main_text()))
+                        .push(
+                            TextInput::new(inputs.next().unwrap(), label, value, move |s| {
+                                Message::HelixBlockInput(field, s)
+                            })
+                            .style(BadValue(valid)),
+                        ),
+                );
+            }
+            row
+        };
+        let origin_row = input_row([("x", HelixBlockField::X), ("y", HelixBlockField::Y)]);
+        let size_row = input_row([
+            ("Rows", HelixBlockField::NbRows),
+            ("Columns", HelixBlockField::NbColumns),
+        ]);
+        let strand_row = input_row([
+            ("Starting nt", HelixBlockField::Start),
+            ("Length (nt)", HelixBlockField::Length),
+        ]);
+        column = column.push(origin_row).push(size_row).push(strand_row);
+        column = column.push(right_checkbox(
+            self.scaffold_raster,
+            "Scaffold raster",
+            Message::HelixBlockScaffoldRaster,
+            ui_size,
+        ));
+        column = column.push(right_checkbox(
+            self.alternate_orientation,
+            "Alternate orientation",
+            Message::HelixBlockAlternateOrientation,
+            ui_size,
+        ));
+        let mut create_btn = text_btn(&mut self.create_btn, "Create block", ui_size);
+        if let Some(block) = descriptor {
+            create_btn = create_btn.on_press(Message::CreateHelixBlock(block.grid_id));
+        }
+        column.push(create_btn)
+    }
+}
+
+/// Buttons applying an operation to all the members of the selected group
+#[derive(Default)]
+struct GroupMenu {
+    hide_btn: button::State,
+    show_btn: button::State,
+    lock_btn: button::State,
+    unlock_btn: button::State,
+    recolor_btn: button::State,
+    simulate_btn: button::State,
+    pivot_inputs: [text_input::State; 3],
+    pivot_str: [String; 3],
+    set_pivot_btn: button::State,
+    snap_pivot_btn: button::State,
+    reset_pivot_btn: button::State,
+}
+
+impl GroupMenu {
+    fn has_keyboard_priority(&self) -> bool {
+        self.pivot_inputs.iter().any(|s| s.is_focused())
+    }
+
+    /// Return the position entered in the pivot inputs, or `None` if one of them is not valid.
+    fn pivot_position(&self) -> Option<Vec3> {
+        Some(Vec3::new(
+            self.pivot_str[0].parse().ok()?,
+            self.pivot_str[1].parse().ok()?,
+            self.pivot_str[2].parse().ok()?,
+        ))
+    }
+
+    fn add_to_column<'a, S: AppState>(
+        &'a mut self,
+        mut column: Column<'a, Message<S>>,
+        group_id: GroupId,
+        pivot: Option<Vec3>,
+        can_simulate: bool,
+        ui_size: UiSize,
+    ) -> Column<'a, Message<S>> {
+        if !self.has_keyboard_priority() {
+            if let Some(pivot) = pivot {
+                self.pivot_str = [pivot.x, pivot.y, pivot.z].map(|x| format!("{:.2}", x));
+            }
+        }
+        let pivot_position = self.pivot_position();
+        column = column.push(Text::new(tr("Selected group")).size(ui_size.intermediate_text()));
+        let operation_btn = |state: &'a mut button::State,
+                             label: &'static str,
+                             operation: GroupOperation| {
+            text_btn(state, label, ui_size).on_press(Message::GroupOperation(group_id, operation))
+        };
+        column = column
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .push(operation_btn(
+                        &mut self.hide_btn,
+                        "Hide",
+                        GroupOperation::SetVisibility(false),
+                    ))
+                    .push(operation_btn(
+                        &mut self.show_btn,
+                        "Show",
+                        GroupOperation::SetVisibility(true),
+                    )),
+            )
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .push(operation_btn(
+                        &mut self.lock_btn,
+                        "Lock",
+                        GroupOperation::SetLocked(true),
+                    ))
+                    .push(operation_btn(
+                        &mut self.unlock_btn,
+                        "Unlock",
+                        GroupOperation::SetLocked(false),
+                    )),
+            );
+        let mut simulate_btn = text_btn(&mut self.simulate_btn, "Simulate", ui_size);
+        if can_simulate {
+            simulate_btn = simulate_btn.on_press(Message::SimulateGroup(group_id));
+        }
+        column.push(
+            Row::new()
+                .spacing(5)
+                .push(operation_btn(
+                    &mut self.recolor_btn,
+                    "Recolor",
+                    GroupOperation::Recolor,
+                ))
+                .push(simulate_btn),
+        );
+
+        column = column.push(Text::new(tr("Pivot")).size(ui_size.main_text()));
+        let mut pivot_row = Row::new().spacing(5);
+        for (i, (state, value)) in self
+            .pivot_inputs
+            .iter_mut()
+            .zip(self.pivot_str.iter())
+            .enumerate()
+        {
+            let label = ["x", "y", "z"][i];
+            pivot_row = pivot_row.push(
+                TextInput::new(state, label, value, move |s| Message::GroupPivotInput(i, s))
+                    .style(BadValue(value.parse::<f32>().is_ok())),
+            );
+        }
+        let mut set_pivot_btn = text_btn(&mut self.set_pivot_btn, "Set", ui_size);
+        if let Some(position) = pivot_position {
+            set_pivot_btn = set_pivot_btn.on_press(Message::GroupOperation(
+                group_id,
+                GroupOperation::SetPivotPosition(position),
+            ));
+        }
+        column.push(pivot_row.push(set_pivot_btn)).push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    text_btn(&mut self.snap_pivot_btn, "Snap to centroid", ui_size).on_press(
+                        Message::GroupOperation(group_id, GroupOperation::SnapPivotToCentroid),
+                    ),
+                )
+                .push(
+                    text_btn(&mut self.reset_pivot_btn, "Reset to center", ui_size).on_press(
+                        Message::GroupOperation(group_id, GroupOperation::ResetPivot),
+                    ),
+                ),
+        )
+    }
+}
+
 enum TwistStatus {
     CanTwist,
     CannotTwist,
@@ -507,6 +1030,7 @@ enum TwistStatus {
 fn add_grid_content<'a, S: AppState, I: std::ops::Deref<Target = str>>(
     mut column: Column<'a, Message<S>>,
     info_values: &[I],
+    labelled: bool,
     ui_size: UiSize,
     twist_button: &'a mut button::State,
     twisting: TwistStatus,
@@ -537,30 +1061,92 @@ fn add_grid_content<'a, S: AppState, I: std::ops::Deref<Target = str>>(
         .size(ui_size.checkbox())
         .text_size(ui_size.main_text()),
     );
+    column = column.push(
+        Checkbox::new(labelled, "3D labels", Message::SetGridLabels)
+            .size(ui_size.checkbox())
+            .text_size(ui_size.main_text()),
+    );
     column
 }
 
+struct StrandContentStates<'a> {
+    name: &'a mut text_input::State,
+    pool_name: &'a mut text_input::State,
+    pool_concentration: &'a mut PoolConcentrationState,
+    note: &'a mut text_input::State,
+    url: &'a mut text_input::State,
+    open_url: &'a mut button::State,
+}
+
 fn add_strand_content<'a, S: AppState, I: std::ops::Deref<Target = str>>(
     mut column: Column<'a, Message<S>>,
-    strand_name_state: &'a mut text_input::State,
+    states: StrandContentStates<'a>,
     info_values: &[I],
     ui_size: UiSize,
 ) -> Column<'a, Message<S>> {
     let s_id = info_values[2].parse::<usize>().unwrap();
+    let PoolConcentrationState {
+        state: concentration_state,
+        input_str: concentration_input,
+        ..
+    } = states.pool_concentration;
     let name_row = Row::new()
-        .push(Text::new("Name").size(ui_size.main_text()))
+        .push(Text::new(tr("Name")).size(ui_size.main_text()))
         .push(
-            TextInput::new(
-                strand_name_state,
-                "Name",
-                &info_values[4],
-                move |new_name| Message::StrandNameChanged(s_id, new_name),
-            )
+            TextInput::new(states.name, "Name", &info_values[4], move |new_name| {
+                Message::StrandNameChanged(s_id, new_name)
+            })
             .size(ui_size.main_text()),
         );
     column = column.push(name_row);
-    column = column
-        .push(Text::new(format!("length {}", info_values[0].deref())).size(ui_size.main_text()));
+    let pool_row = Row::new()
+        .push(Text::new(tr("Pool")).size(ui_size.main_text()))
+        .push(
+            TextInput::new(states.pool_name, "Pool", &info_values[5], move |name| {
+                Message::StrandPoolNameChanged(s_id, name)
+            })
+            .size(ui_size.main_text()),
+        );
+    column = column.push(pool_row);
+    let concentration = concentration_input
+        .clone()
+        .unwrap_or_else(|| info_values[6].to_string());
+    let concentration_row = Row::new()
+        .push(Text::new(tr("Concentration (nM)")).size(ui_size.main_text()))
+        .push(
+            TextInput::new(concentration_state, "", &concentration, move |input| {
+                Message::StrandPoolConcentrationInput(s_id, input)
+            })
+            .size(ui_size.main_text()),
+        );
+    column = column.push(concentration_row);
+    let note_row = Row::new()
+        .push(Text::new(tr("Note")).size(ui_size.main_text()))
+        .push(
+            TextInput::new(states.note, "", &info_values[7], move |note| {
+                Message::StrandNoteChanged(s_id, note)
+            })
+            .size(ui_size.main_text()),
+        );
+    column = column.push(note_row);
+    let url = info_values[8].to_string();
+    let mut open_url_button = text_btn(states.open_url, "Open", ui_size);
+    if !url.is_empty() {
+        open_url_button = open_url_button.on_press(Message::OpenStrandUrl(url));
+    }
+    let url_row = Row::new()
+        .push(Text::new(tr("Link")).size(ui_size.main_text()))
+        .push(
+            TextInput::new(states.url, "https://", &info_values[8], move |url| {
+                Message::StrandUrlChanged(s_id, url)
+            })
+            .size(ui_size.main_text()),
+        )
+        .push(open_url_button);
+    column = column.push(url_row);
+    column = column.push(
+        Text::new(tr_args("length {}", &[&info_values[0].deref()])).size(ui_size.main_text()),
+    );
     column = column.push(Checkbox::new(
         info_values[1].parse().unwrap(),
         "Scaffold",
@@ -581,17 +1167,17 @@ fn bool_to_string(b: bool) -> String {
 #[allow(clippy::needless_lifetimes)]
 fn add_help_to_column<'a, M: 'static>(
     mut column: Column<'a, M>,
-    help_title: impl Into<String>,
+    help_title: &str,
     help: Vec<(String, String)>,
     ui_size: UiSize,
 ) -> Column<'a, M> {
-    column = column.push(Text::new(help_title).size(ui_size.intermediate_text()));
+    column = column.push(Text::new(tr(help_title)).size(ui_size.intermediate_text()));
     for (l, r) in help {
         if l.is_empty() {
             column = column.push(iced::Space::with_height(Length::Units(10)));
         } else if r.is_empty() {
             column = column.push(
-                Text::new(l)
+                Text::new(tr(&l))
                     .width(Length::Fill)
                     .horizontal_alignment(iced::alignment::Horizontal::Center),
             );
@@ -604,7 +1190,7 @@ fn add_help_to_column<'a, M: 'static>(
                             .horizontal_alignment(iced::alignment::Horizontal::Right),
                     )
                     .push(iced::Space::with_width(Length::FillPortion(1)))
-                    .push(Text::new(r).width(Length::FillPortion(5))),
+                    .push(Text::new(tr(&r)).width(Length::FillPortion(5))),
             );
         }
     }
@@ -617,7 +1203,7 @@ fn turn_into_help_column<'a, M: 'static>(
     ui_size: UiSize,
 ) -> Column<'a, M> {
     column = column.push(
-        Text::new("Help")
+        Text::new(tr("Help"))
             .size(ui_size.head_text())
             .width(Length::Fill)
             .horizontal_alignment(iced::alignment::Horizontal::Center),
@@ -669,6 +1255,10 @@ fn view_3d_help() -> Vec<(String, String)> {
             format!("{}+{} Drag", SHIFT, RCLICK),
             "Tilt camera".to_owned(),
         ),
+        (
+            format!("{} on direction cube", LCLICK),
+            "Look at the design from that side".to_owned(),
+        ),
         (
             "⎵ (with cursor over the 3D scene)".to_owned(),
             "Export the current view in png format".to_owned(),
@@ -804,7 +1394,12 @@ fn link_row<'a, S: AppState>(
         )
 }
 
-fn values_of_selection(selection: &Selection, reader: &dyn DesignReader) -> Vec<String> {
+fn values_of_selection(
+    selection: &Selection,
+    reader: &dyn DesignReader,
+    length_unit: LengthUnit,
+    helix_parameters: &HelixParameters,
+) -> Vec<String> {
     match selection {
         Selection::Grid(_, g_id) => {
             let b1 = reader.grid_has_persistent_phantom(*g_id);
@@ -833,22 +1428,47 @@ fn values_of_selection(selection: &Selection, reader: &dyn DesignReader) -> Vec<
             s_id.to_string(),
             reader.length_decomposition(*s_id as usize),
             reader.strand_name(*s_id as usize),
+            reader
+                .strand_pool(*s_id as usize)
+                .map(|pool| pool.name)
+                .unwrap_or_default(),
+            reader
+                .strand_pool(*s_id as usize)
+                .and_then(|pool| pool.concentration)
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            reader.strand_note(*s_id as usize).unwrap_or_default(),
+            reader.strand_url(*s_id as usize).unwrap_or_default(),
         ],
         Selection::Nucleotide(_, nucl) => {
             vec![format!("{}", reader.nucl_is_anchor(*nucl))]
         }
-        Selection::Xover(_, xover_id) => fmt_xover_len(reader.xover_length(*xover_id)),
+        Selection::Xover(_, xover_id) => fmt_xover_len(
+            reader.xover_length(*xover_id),
+            length_unit,
+            helix_parameters,
+        ),
         _ => Vec::new(),
     }
 }
 
-fn fmt_xover_len(info: Option<(f32, Option<f32>)>) -> Vec<String> {
+fn fmt_xover_len(
+    info: Option<(f32, Option<f32>)>,
+    length_unit: LengthUnit,
+    helix_parameters: &HelixParameters,
+) -> Vec<String> {
     match info {
         Some((len_self, Some(len_neighbour))) => vec![
-            format!("length {:.2} nm", len_self),
-            format!("{:.2} nm", len_neighbour),
+            format!(
+                "length {}",
+                length_unit.format_nm(len_self, helix_parameters)
+            ),
+            length_unit.format_nm(len_neighbour, helix_parameters),
         ],
-        Some((len, None)) => vec![format!("length {:.2} nm", len)],
+        Some((len, None)) => vec![format!(
+            "length {}",
+            length_unit.format_nm(len, helix_parameters)
+        )],
         None => vec![String::from("Error getting length")],
     }
 }
@@ -919,6 +1539,21 @@ impl AddStrandMenu {
         self.text_inputs_are_active = show;
     }
 
+    fn sync_with_action_mode(&mut self, action_mode: ActionMode) {
+        if let ActionMode::BuildHelix { position, length } = action_mode {
+            if length > 0
+                && !self.has_keyboard_priority()
+                && (position, length) != (self.helix_pos, self.helix_length)
+            {
+                self.helix_pos = position;
+                self.helix_length = length;
+                self.pos_str = position.to_string();
+                self.length_str = length.to_string();
+                self.text_inputs_are_active = true;
+            }
+        }
+    }
+
     #[allow(clippy::needless_lifetimes)]
     fn view<'a, S: AppState>(&'a mut self, ui_size: UiSize, width: u16) -> Element<'a, Message<S>> {
         let mut ret = Column::new();
@@ -960,13 +1595,13 @@ impl AddStrandMenu {
         let row = Row::new()
             .push(
                 Column::new()
-                    .push(Text::new("Starting nt").color(color_choose_strand_start_length))
+                    .push(Text::new(tr("Starting nt")).color(color_choose_strand_start_length))
                     .push(position_input)
                     .width(Length::Units(width / 2)),
             )
             .push(
                 Column::new()
-                    .push(Text::new("Length (nt)").color(color_choose_strand_start_length))
+                    .push(Text::new(tr("Length (nt)")).color(color_choose_strand_start_length))
                     .push(length_input),
             );
         ret = ret.push(row);
@@ -978,6 +1613,8 @@ struct InsertionLengthState {
     state: text_input::State,
     selection: Selection,
     input_str: Option<String>,
+    /// True if the input must get the keyboard focus the next time it is displayed
+    focus_requested: bool,
 }
 
 impl Default for InsertionLengthState {
@@ -986,6 +1623,7 @@ impl Default for InsertionLengthState {
             state: Default::default(),
             selection: Selection::Nothing,
             input_str: None,
+            focus_requested: false,
         }
     }
 }
@@ -995,6 +1633,19 @@ impl InsertionLengthState {
         if selection != &self.selection {
             self.input_str = None;
             self.selection = *selection;
+            if !self.focus_requested {
+                // Do not keep the keyboard captured by an input that was focused for another
+                // insertion
+                self.state.unfocus();
+            }
+        }
+    }
+
+    fn apply_focus_request(&mut self) {
+        if self.focus_requested {
+            self.focus_requested = false;
+            self.state.focus();
+            self.state.select_all();
         }
     }
 
@@ -1003,6 +1654,33 @@ impl InsertionLengthState {
     }
 }
 
+/// The text typed in the concentration field of the strand's pool, kept until the selection
+/// changes so that partially typed numbers are not overwritten.
+struct PoolConcentrationState {
+    state: text_input::State,
+    selection: Selection,
+    input_str: Option<String>,
+}
+
+impl Default for PoolConcentrationState {
+    fn default() -> Self {
+        Self {
+            state: Default::default(),
+            selection: Selection::Nothing,
+            input_str: None,
+        }
+    }
+}
+
+impl PoolConcentrationState {
+    fn update_selection(&mut self, selection: &Selection) {
+        if selection != &self.selection {
+            self.input_str = None;
+            self.selection = *selection;
+        }
+    }
+}
+
 pub(super) struct InsertionRequest {
     pub selection: Selection,
     pub length: usize,
@@ -16,7 +16,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use super::{Selection, UiSize};
+use super::{tr, Selection, UiSize};
 
 use iced_native::{
     widget::{slider, text_input, Column, Row, Slider, Text, TextInput},
@@ -286,7 +286,7 @@ impl<S: AppState> Builder<S> for BezierVertexBuilder {
     ) -> Element<'a, super::Message<S>, Renderer> {
         let mut ret = Column::new().width(iced::Length::Fill);
         let position_builder_view = self.position_builder.view();
-        ret = ret.push(Text::new("Position").size(ui_size.intermediate_text()));
+        ret = ret.push(Text::new(tr("Position")).size(ui_size.intermediate_text()));
         ret = ret.push(position_builder_view);
         ret.into()
     }
@@ -377,11 +377,11 @@ impl<S: AppState> Builder<S> for GridBuilder {
         let mut ret = Column::new().width(iced::Length::Fill);
         let position_builder_view = self.position_builder.view();
         let orientation_builder_view = self.orientation_builder.view();
-        ret = ret.push(Text::new("Position").size(ui_size.intermediate_text()));
+        ret = ret.push(Text::new(tr("Position")).size(ui_size.intermediate_text()));
         ret = ret.push(position_builder_view);
-        ret = ret.push(Text::new("Orientation").size(ui_size.intermediate_text()));
+        ret = ret.push(Text::new(tr("Orientation")).size(ui_size.intermediate_text()));
         ret = ret.push(orientation_builder_view);
-        ret = ret.push(Text::new("Twist").size(ui_size.intermediate_text()));
+        ret = ret.push(Text::new(tr("Twist")).size(ui_size.intermediate_text()));
         if let Some(row) = Self::nb_turn_row(&mut self.nb_turn_slider, app_state, selection) {
             ret = ret.push(row)
         }
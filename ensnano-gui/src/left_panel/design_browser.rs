@@ -0,0 +1,147 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The dialog listing the design files of a folder, with their thumbnail, size and description,
+//! in which the user picks the design to open.
+
+use super::*;
+use ensnano_interactor::{DesignBrowserChoice, DesignBrowserContent, DesignFileInfo};
+use iced::{image, Image};
+use iced_native::widget::scrollable;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Width, in pixels, of the thumbnails of the listed designs
+const THUMBNAIL_WIDTH: u16 = 96;
+
+#[derive(Default)]
+pub struct DesignBrowser {
+    scroll: scrollable::State,
+    button_cancel: button::State,
+    button_choose_folder: button::State,
+    button_other_file: button::State,
+    file_buttons: Vec<button::State>,
+    /// The decoded thumbnails of the listed files
+    thumbnails: HashMap<PathBuf, image::Handle>,
+}
+
+impl DesignBrowser {
+    pub fn view<'a, S: AppState>(
+        &'a mut self,
+        ui_size: UiSize,
+        content: &DesignBrowserContent,
+    ) -> Element<'a, Message<S>> {
+        let mut ret = Column::new().spacing(5);
+        ret = ret.push(Text::new(tr("Open design")).size(ui_size.head_text()));
+        ret = ret.push(
+            Text::new(content.directory.to_string_lossy().to_string()).size(ui_size.main_text()),
+        );
+        ret = ret.push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    text_btn(&mut self.button_choose_folder, "Choose folder", ui_size)
+                        .on_press(Message::DesignBrowser(DesignBrowserChoice::ChooseFolder)),
+                )
+                .push(
+                    text_btn(&mut self.button_other_file, "Other file", ui_size)
+                        .on_press(Message::DesignBrowser(DesignBrowserChoice::OtherFile)),
+                )
+                .push(
+                    text_btn(&mut self.button_cancel, "Cancel", ui_size)
+                        .on_press(Message::DesignBrowser(DesignBrowserChoice::Cancel)),
+                ),
+        );
+
+        if content.scanning {
+            ret = ret.push(Text::new(tr("Reading design files")).size(ui_size.main_text()));
+        } else if content.files.is_empty() {
+            ret =
+                ret.push(Text::new(tr("No design file in this folder")).size(ui_size.main_text()));
+        }
+
+        self.thumbnails
+            .retain(|path, _| content.files.iter().any(|f| &f.path == path));
+        for file in content.files.iter() {
+            if let Some(png) = file.thumbnail_png.as_ref() {
+                self.thumbnails
+                    .entry(file.path.clone())
+                    .or_insert_with(|| image::Handle::from_memory(png.clone()));
+            }
+        }
+
+        self.file_buttons
+            .resize_with(content.files.len(), Default::default);
+        for (file, state) in content.files.iter().zip(self.file_buttons.iter_mut()) {
+            let thumbnail = self.thumbnails.get(&file.path).cloned();
+            ret = ret.push(
+                Button::new(state, file_summary(file, thumbnail, ui_size))
+                    .width(Length::Fill)
+                    .on_press(Message::DesignBrowser(DesignBrowserChoice::Open(
+                        file.path.clone(),
+                    ))),
+            );
+        }
+
+        Scrollable::new(&mut self.scroll).push(ret).into()
+    }
+}
+
+fn file_summary<'a, S: AppState>(
+    file: &DesignFileInfo,
+    thumbnail: Option<image::Handle>,
+    ui_size: UiSize,
+) -> Element<'a, Message<S>> {
+    let mut description = Column::new().push(Text::new(file.file_name()).size(ui_size.main_text()));
+    if let Some(modified) = file.modified.as_ref() {
+        description = description.push(Text::new(modified.clone()).size(ui_size.main_text()));
+    }
+    if let Some(error) = file.error.as_ref() {
+        description = description.push(
+            Text::new(error.clone())
+                .size(ui_size.main_text())
+                .color(innactive_color()),
+        );
+    } else {
+        description = description.push(
+            Text::new(format!(
+                "{} helices, {} strands, {} nt",
+                file.nb_helices, file.nb_strands, file.nb_nucleotides
+            ))
+            .size(ui_size.main_text()),
+        );
+    }
+    let metadata = &file.metadata;
+    if !metadata.author.is_empty() {
+        description = description.push(
+            Text::new(format!("{}: {}", tr("Author"), metadata.author)).size(ui_size.main_text()),
+        );
+    }
+    if !metadata.description.is_empty() {
+        description =
+            description.push(Text::new(metadata.description.clone()).size(ui_size.main_text()));
+    }
+
+    let mut row = Row::new().spacing(5);
+    if let Some(thumbnail) = thumbnail {
+        row = row.push(Image::new(thumbnail).width(Length::Units(THUMBNAIL_WIDTH)));
+    } else {
+        row = row.push(iced::Space::with_width(Length::Units(THUMBNAIL_WIDTH)));
+    }
+    row.push(description).into()
+}
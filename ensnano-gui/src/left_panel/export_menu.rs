@@ -17,6 +17,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 
 use super::*;
+use ensnano_exports::print_2d::MM_PER_NUCLEOTIDE_RANGE;
+use ensnano_interactor::i18n::tr_args;
 use iced_native::widget::scrollable;
 
 #[derive(Default)]
@@ -26,13 +28,18 @@ pub struct ExportMenu {
     button_oxdna: button::State,
     button_pdb: button::State,
     button_cadnano: button::State,
+    button_nucleotides_csv: button::State,
+    button_nucleotides_json: button::State,
+    button_print: button::State,
+    print_scale_slider: slider::State,
+    pub print_parameters: PrintParameters,
 }
 
 impl ExportMenu {
     pub fn view<'a, S: AppState>(&'a mut self) -> Element<'a, Message<S>> {
         let ret = Column::new()
             .push(
-                Button::new(&mut self.button_cancel, Text::new("Cancel"))
+                Button::new(&mut self.button_cancel, Text::new(tr("Cancel")))
                     .on_press(Message::CancelExport),
             )
             .push(
@@ -46,6 +53,76 @@ impl ExportMenu {
             .push(
                 Button::new(&mut self.button_cadnano, Text::new("Cadnano"))
                     .on_press(Message::Export(ExportType::Cadnano)),
+            )
+            .push(
+                Button::new(
+                    &mut self.button_nucleotides_csv,
+                    Text::new(tr("Nucleotides (csv)")),
+                )
+                .on_press(Message::Export(ExportType::NucleotidesCsv)),
+            )
+            .push(
+                Button::new(
+                    &mut self.button_nucleotides_json,
+                    Text::new(tr("Nucleotides (json)")),
+                )
+                .on_press(Message::Export(ExportType::NucleotidesJson)),
+            );
+
+        let parameters = self.print_parameters;
+        let ret = ret
+            .push(Text::new(tr("2D printout (PDF)")))
+            .push(Text::new(tr_args(
+                "1 nt = {} mm",
+                &[&format!("{:.1}", parameters.mm_per_nucleotide)],
+            )))
+            .push(
+                Slider::new(
+                    &mut self.print_scale_slider,
+                    MM_PER_NUCLEOTIDE_RANGE,
+                    parameters.mm_per_nucleotide,
+                    move |mm_per_nucleotide| {
+                        Message::PrintParametersChanged(PrintParameters {
+                            mm_per_nucleotide,
+                            ..parameters
+                        })
+                    },
+                )
+                .step(0.1),
+            )
+            .push(Checkbox::new(
+                parameters.helix_numbers,
+                tr("Helix numbers"),
+                move |helix_numbers| {
+                    Message::PrintParametersChanged(PrintParameters {
+                        helix_numbers,
+                        ..parameters
+                    })
+                },
+            ))
+            .push(Checkbox::new(
+                parameters.sequences,
+                tr("Sequences"),
+                move |sequences| {
+                    Message::PrintParametersChanged(PrintParameters {
+                        sequences,
+                        ..parameters
+                    })
+                },
+            ))
+            .push(Checkbox::new(
+                parameters.legend,
+                tr("Legend"),
+                move |legend| {
+                    Message::PrintParametersChanged(PrintParameters {
+                        legend,
+                        ..parameters
+                    })
+                },
+            ))
+            .push(
+                Button::new(&mut self.button_print, Text::new(tr("Print to PDF")))
+                    .on_press(Message::Export(ExportType::Print2d(parameters))),
             );
 
         Scrollable::new(&mut self.scroll).push(ret).into()
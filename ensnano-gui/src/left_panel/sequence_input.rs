@@ -15,7 +15,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use super::{AppState, Message};
+use super::{tr, AppState, Message};
 use iced::{button, text_input, Button, Row, Text, TextInput};
 
 pub struct SequenceInput {
@@ -45,7 +45,7 @@ impl SequenceInput {
                 Message::SequenceChanged,
             ))
             .push(
-                Button::new(&mut self.button_state, Text::new("Load File"))
+                Button::new(&mut self.button_state, Text::new(tr("Load File")))
                     .on_press(Message::SequenceFileRequested),
             );
         sequence_input
@@ -18,7 +18,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use super::color_picker::{ColorSquare, ColorState};
 use super::*;
 use ensnano_design::CameraId;
-use ensnano_interactor::{RollRequest, SimulationState};
+use ensnano_interactor::{RollRequest, SimulationJobInfo, SimulationJobStatus, SimulationState};
 use iced::scrollable;
 use std::collections::VecDeque;
 
@@ -50,12 +50,12 @@ fn light_icon_btn<'a, Message: Clone>(
 
 macro_rules! section {
     ($row:ident, $ui_size:ident, $text:tt) => {
-        $row = $row.push(Text::new($text).size($ui_size.head_text()));
+        $row = $row.push(Text::new(tr(&$text)).size($ui_size.head_text()));
     };
 }
 macro_rules! subsection {
     ($row:ident, $ui_size:ident, $text:tt) => {
-        $row = $row.push(Text::new($text).size($ui_size.intermediate_text()));
+        $row = $row.push(Text::new(tr(&$text)).size($ui_size.intermediate_text()));
     };
 }
 
@@ -81,9 +81,13 @@ pub use simulation_tab::SimulationTab;
 mod parameters_tab;
 pub use parameters_tab::ParametersTab;
 mod sequence_tab;
-pub use sequence_tab::SequenceTab;
+pub use sequence_tab::{SequenceTab, ShiftOptimizationField};
 mod pen_tab;
 pub use pen_tab::PenTab;
+mod staples_tab;
+pub use staples_tab::{StapleColumn, StaplesTab};
+mod metadata_tab;
+pub use metadata_tab::MetadataTab;
 pub(super) mod revolution_tab;
 pub use revolution_tab::*;
 
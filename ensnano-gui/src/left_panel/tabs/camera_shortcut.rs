@@ -16,6 +16,10 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use super::*;
+use ensnano_interactor::graphics::Thumbnail;
+use ensnano_interactor::i18n::tr_args;
+use iced::{image, Image};
+use std::collections::HashMap;
 
 struct TargetShortcut {
     name: &'static str,
@@ -112,7 +116,7 @@ macro_rules! add_rotate_buttons {
             })
             .collect();
 
-        $ret = $ret.push(Text::new("Rotate Camera"));
+        $ret = $ret.push(Text::new(tr("Rotate Camera")));
         while rotate_buttons.len() > 0 {
             let mut row = Row::new();
             row = row.push(rotate_buttons.remove(0)).spacing(5);
@@ -147,7 +151,7 @@ macro_rules! add_screenshot_buttons {
         row = row.push(screenshot_2d_button);
         row = row.spacing(5);
 
-        $ret = $ret.push(Text::new("Screenshot"));
+        $ret = $ret.push(Text::new(tr("Screenshot")));
         $ret = $ret.spacing(5).push(row);
     };
 }
@@ -161,7 +165,7 @@ macro_rules! add_stl_export_button {
         .on_press(Message::StlExport)
         .width(Length::Units(2 * $ui_size.button()));
 
-        $ret = $ret.push(Text::new("STL export"));
+        $ret = $ret.push(Text::new(tr("STL export")));
         $ret = $ret.spacing(5).push(stl_export_button);
     };
 }
@@ -175,7 +179,7 @@ macro_rules! add_nucleotides_positons_export_button {
         .on_press(Message::SaveNucleotidesPositions)
         .width(Length::Units(2 * $ui_size.button()));
 
-        $ret = $ret.push(Text::new("Nucleotides positions export"));
+        $ret = $ret.push(Text::new(tr("Nucleotides positions export")));
         $ret = $ret.spacing(5).push(nucleotides_positions_export_button);
     };
 }
@@ -186,7 +190,7 @@ macro_rules! add_custom_camera_row {
             light_icon_btn(&mut $self.new_camera_button, LightIcon::AddAPhoto, $ui_size)
                 .on_press(Message::NewCustomCamera);
         let custom_cameras_row = Row::new()
-            .push(Text::new("Custom cameras").size($ui_size.head_text()))
+            .push(Text::new(tr("Custom cameras")).size($ui_size.head_text()))
             .push(iced::Space::with_width(Length::Fill))
             .push(new_camera_button);
 
@@ -194,6 +198,85 @@ macro_rules! add_custom_camera_row {
     };
 }
 
+macro_rules! add_presentation_rows {
+    ($ret: ident, $self: ident, $ui_size: ident, $app: ident) => {
+        let step_names: Vec<String> = $app
+            .get_reader()
+            .get_presentation_step_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let nb_steps = step_names.len();
+        $ret = $ret.push(Text::new(tr("Presentation")).size($ui_size.head_text()));
+        $ret = $ret.push(Text::new(tr_args(
+            "{} steps (PageUp/PageDown)",
+            &[&nb_steps],
+        )));
+
+        if $self.presentation_step_states.len() < nb_steps {
+            $self
+                .presentation_step_states
+                .resize(nb_steps, Default::default());
+        }
+        for (i, (name, state)) in step_names
+            .into_iter()
+            .zip($self.presentation_step_states.iter_mut())
+            .enumerate()
+        {
+            let name: Element<Message<S>> = if $self.step_being_edited == Some(i) {
+                TextInput::new(
+                    &mut state.name_input,
+                    tr("Step name"),
+                    $self.step_input_name.as_deref().unwrap_or(&name),
+                    Message::EditPresentationStepName,
+                )
+                .on_submit(Message::SubmitPresentationStepName)
+                .into()
+            } else {
+                Text::new(name).into()
+            };
+            let edit_button = light_icon_btn(&mut state.edit_name_btn, LightIcon::Edit, $ui_size)
+                .on_press(Message::StartEditPresentationStepName(i));
+            $ret = $ret.push(
+                Row::new()
+                    .push(name)
+                    .push(iced::Space::with_width(iced::Length::Units(3)))
+                    .push(edit_button),
+            );
+        }
+
+        let add_step_button =
+            text_btn(&mut $self.add_presentation_step_button, tr("Add"), $ui_size)
+                .on_press(Message::AddPresentationStep);
+        let mut remove_step_button = text_btn(
+            &mut $self.remove_presentation_step_button,
+            tr("Remove last"),
+            $ui_size,
+        );
+        if nb_steps > 0 {
+            remove_step_button =
+                remove_step_button.on_press(Message::DeletePresentationStep(nb_steps - 1));
+        }
+        $ret = $ret.push(
+            Row::new()
+                .spacing(5)
+                .push(add_step_button)
+                .push(remove_step_button),
+        );
+
+        let previous_button = text_btn(&mut $self.previous_step_button, tr("Previous"), $ui_size)
+            .on_press(Message::PresentationStep(false));
+        let next_button = text_btn(&mut $self.next_step_button, tr("Next"), $ui_size)
+            .on_press(Message::PresentationStep(true));
+        $ret = $ret.push(
+            Row::new()
+                .spacing(5)
+                .push(previous_button)
+                .push(next_button),
+        );
+    };
+}
+
 macro_rules! add_camera_widgets {
     ($ret: ident, $self: ident, $ui_size: ident) => {
         if $self.camera_widget_states.len() < $self.camera_widgets.len() {
@@ -224,10 +307,18 @@ pub struct CameraShortcut {
     camera_widgets: Vec<CameraWidget>,
     new_camera_button: button::State,
     camera_widget_states: Vec<CameraWidgetState>,
+    thumbnails: HashMap<CameraId, image::Handle>,
     screenshot_3d_button: button::State,
     screenshot_2d_button: button::State,
     save_nucleotide_positions_button: button::State,
     stl_export_button: button::State,
+    add_presentation_step_button: button::State,
+    remove_presentation_step_button: button::State,
+    previous_step_button: button::State,
+    next_step_button: button::State,
+    presentation_step_states: Vec<PresentationStepWidgetState>,
+    step_input_name: Option<String>,
+    step_being_edited: Option<usize>,
 }
 
 impl CameraShortcut {
@@ -244,10 +335,18 @@ impl CameraShortcut {
             camera_widgets: vec![],
             new_camera_button: Default::default(),
             camera_widget_states: vec![],
+            thumbnails: Default::default(),
             screenshot_3d_button: Default::default(),
             screenshot_2d_button: Default::default(),
             save_nucleotide_positions_button: Default::default(),
             stl_export_button: Default::default(),
+            add_presentation_step_button: Default::default(),
+            remove_presentation_step_button: Default::default(),
+            previous_step_button: Default::default(),
+            next_step_button: Default::default(),
+            presentation_step_states: vec![],
+            step_input_name: None,
+            step_being_edited: None,
         }
     }
 
@@ -290,18 +389,69 @@ impl CameraShortcut {
         }
     }
 
+    pub fn set_step_input_name(&mut self, name: String) {
+        self.step_input_name = Some(name);
+    }
+
+    pub fn stop_editing_step(&mut self) -> Option<(usize, String)> {
+        let name = self.step_input_name.take();
+        let step = self.step_being_edited.take();
+        for s in self.presentation_step_states.iter_mut() {
+            s.name_input.unfocus();
+        }
+        step.zip(name)
+    }
+
+    pub fn start_editing_step(&mut self, step: usize) {
+        if let Some(state) = self.presentation_step_states.get_mut(step) {
+            self.step_being_edited = Some(step);
+            self.step_input_name = None;
+            state.name_input.focus();
+            state.name_input.select_all();
+        }
+    }
+
+    pub fn set_thumbnail(&mut self, camera_id: CameraId, thumbnail: Thumbnail) {
+        // iced expects Bgra pixels
+        let mut pixels = thumbnail.rgba;
+        for pixel in pixels.chunks_mut(4) {
+            pixel.swap(0, 2);
+        }
+        self.thumbnails.insert(
+            camera_id,
+            image::Handle::from_pixels(thumbnail.width, thumbnail.height, pixels),
+        );
+    }
+
+    /// Exchange the thumbnails of two cameras that have been swapped in the design
+    pub fn swap_thumbnails(&mut self, a: CameraId, b: CameraId) {
+        let thumbnail_a = self.thumbnails.remove(&a);
+        let thumbnail_b = self.thumbnails.remove(&b);
+        if let Some(thumbnail) = thumbnail_a {
+            self.thumbnails.insert(b, thumbnail);
+        }
+        if let Some(thumbnail) = thumbnail_b {
+            self.thumbnails.insert(a, thumbnail);
+        }
+    }
+
     pub fn has_keyboard_priority(&self) -> bool {
         self.camera_widget_states
             .iter()
             .any(|s| s.name_input.is_focused())
+            || self
+                .presentation_step_states
+                .iter()
+                .any(|s| s.name_input.is_focused())
     }
 
     fn set_camera_widget<S: AppState>(&mut self, app: &S) {
-        self.camera_widgets = app
-            .get_reader()
-            .get_all_cameras()
+        let reader = app.get_reader();
+        let cameras = reader.get_all_cameras();
+        self.camera_widgets = cameras
             .iter()
-            .map(|cam| {
+            .enumerate()
+            .map(|(i, cam)| {
                 let being_edited = self.camera_being_edited == Some(cam.0);
                 let name = if being_edited {
                     self.camera_input_name
@@ -311,7 +461,15 @@ impl CameraShortcut {
                 } else {
                     cam.1
                 };
-                CameraWidget::new(name.to_string(), being_edited, cam.0)
+                CameraWidget {
+                    name: name.to_string(),
+                    being_edited,
+                    camera_id: cam.0,
+                    previous_camera: i.checked_sub(1).map(|j| cameras[j].0),
+                    next_camera: cameras.get(i + 1).map(|c| c.0),
+                    thumbnail: self.thumbnails.get(&cam.0).cloned(),
+                    rendering_stored: reader.camera_has_rendering_settings(cam.0),
+                }
             })
             .collect();
     }
@@ -339,6 +497,8 @@ impl CameraShortcut {
 
         add_camera_widgets!(ret, self, ui_size);
 
+        add_presentation_rows!(ret, self, ui_size, app);
+
         Scrollable::new(&mut self.scroll)
             .push(ret)
             .width(iced::Length::Units(width))
@@ -354,6 +514,10 @@ struct CameraWidget {
     name: String,
     being_edited: bool,
     camera_id: CameraId,
+    previous_camera: Option<CameraId>,
+    next_camera: Option<CameraId>,
+    thumbnail: Option<image::Handle>,
+    rendering_stored: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -362,17 +526,19 @@ struct CameraWidgetState {
     edit_name_btn: button::State,
     delete_btn: button::State,
     name_input: text_input::State,
+    move_up_btn: button::State,
+    move_down_btn: button::State,
+    rendering_btn: button::State,
+    thumbnail_btn: button::State,
 }
 
-impl CameraWidget {
-    fn new(name: String, being_edited: bool, camera_id: CameraId) -> Self {
-        Self {
-            name,
-            being_edited,
-            camera_id,
-        }
-    }
+#[derive(Debug, Clone, Default)]
+struct PresentationStepWidgetState {
+    edit_name_btn: button::State,
+    name_input: text_input::State,
+}
 
+impl CameraWidget {
     fn view<'a, S: AppState>(
         &self,
         ui_size: UiSize,
@@ -401,13 +567,49 @@ impl CameraWidget {
         let delete_button = light_icon_btn(&mut state.delete_btn, LightIcon::Delete, ui_size)
             .on_press(Message::DeleteCamera(self.camera_id));
 
-        Row::new()
+        let mut move_up_button =
+            light_icon_btn(&mut state.move_up_btn, LightIcon::ArrowUpward, ui_size);
+        if let Some(previous) = self.previous_camera {
+            move_up_button =
+                move_up_button.on_press(Message::SwapCameras(self.camera_id, previous));
+        }
+        let mut move_down_button =
+            light_icon_btn(&mut state.move_down_btn, LightIcon::ArrowDownward, ui_size);
+        if let Some(next) = self.next_camera {
+            move_down_button =
+                move_down_button.on_press(Message::SwapCameras(self.camera_id, next));
+        }
+
+        let rendering_icon = if self.rendering_stored {
+            LightIcon::Palette
+        } else {
+            LightIcon::FormatColorReset
+        };
+        let rendering_button =
+            light_icon_btn(&mut state.rendering_btn, rendering_icon, ui_size).on_press(
+                Message::StoreCameraRendering(self.camera_id, !self.rendering_stored),
+            );
+
+        let name_row = Row::new()
             .push(name)
             .push(iced::Space::with_width(iced::Length::Units(3)))
             .push(edit_button)
             .push(iced::Space::with_width(iced::Length::Fill))
             .push(select_camera_btn)
-            .push(delete_button)
-            .into()
+            .push(delete_button);
+        let actions_row = Row::new()
+            .push(move_up_button)
+            .push(move_down_button)
+            .push(iced::Space::with_width(iced::Length::Fill))
+            .push(rendering_button);
+
+        let mut ret = Column::new().push(name_row).push(actions_row);
+        if let Some(thumbnail) = self.thumbnail.clone() {
+            ret = ret.push(
+                Button::new(&mut state.thumbnail_btn, Image::new(thumbnail))
+                    .on_press(Message::SelectCamera(self.camera_id)),
+            );
+        }
+        ret.into()
     }
 }
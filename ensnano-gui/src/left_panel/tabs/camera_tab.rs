@@ -17,10 +17,124 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 
 use super::*;
+use ensnano_design::drawing_style::EndGlyph;
+use ensnano_design::heat_map::{HeatMapKind, ALL_HEAT_MAP_KINDS};
 use ensnano_interactor::graphics::{
-    Background3D, RenderingMode, ALL_BACKGROUND3D, ALL_RENDERING_MODE,
+    Background3D, ElementType, GroundParameters, NucleotideNumbering, NumberingParameters,
+    RenderingMode, StrandEndsParameters, ViewLayer, XoverDrawingParameters, XoverGuideParameters,
+    ALL_BACKGROUND3D, ALL_NUCLEOTIDE_NUMBERINGS, ALL_RENDERING_MODE,
 };
 
+/// An entry of the list of heat maps that can be drawn over the 2D view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HeatMapChoice(Option<HeatMapKind>);
+
+const HEAT_MAP_CHOICES: [HeatMapChoice; 5] = [
+    HeatMapChoice(None),
+    HeatMapChoice(Some(ALL_HEAT_MAP_KINDS[0])),
+    HeatMapChoice(Some(ALL_HEAT_MAP_KINDS[1])),
+    HeatMapChoice(Some(ALL_HEAT_MAP_KINDS[2])),
+    HeatMapChoice(Some(ALL_HEAT_MAP_KINDS[3])),
+];
+
+impl std::fmt::Display for HeatMapChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(kind) = self.0 {
+            write!(f, "{}", tr(&kind.to_string()))
+        } else {
+            write!(f, "{}", tr("None"))
+        }
+    }
+}
+
+/// An entry of the pick lists of a view layer, `None` meaning that the global setting is used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LayerOverride<T>(Option<T>);
+
+impl<T: std::fmt::Display> std::fmt::Display for LayerOverride<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(value) = self.0.as_ref() {
+            write!(f, "{}", tr(&value.to_string()))
+        } else {
+            write!(f, "{}", tr("Global setting"))
+        }
+    }
+}
+
+fn layer_overrides<T: Copy>(values: &[T]) -> Vec<LayerOverride<T>> {
+    std::iter::once(LayerOverride(None))
+        .chain(values.iter().map(|v| LayerOverride(Some(*v))))
+        .collect()
+}
+
+/// The 3D views that have their own view layer
+const LAYER_VIEWS: [(ElementType, &str); 2] = [
+    (ElementType::Scene, "3D view"),
+    (ElementType::StereographicScene, "Stereographic view"),
+];
+
+/// The widgets editing the view layer of one 3D view
+#[derive(Default)]
+struct ViewLayerWidgets {
+    rendering_mode_picklist: pick_list::State<LayerOverride<RenderingMode>>,
+    background3d_picklist: pick_list::State<LayerOverride<Background3D>>,
+}
+
+impl ViewLayerWidgets {
+    fn view<'a, S: AppState>(
+        &'a mut self,
+        view: ElementType,
+        name: &str,
+        layer: ViewLayer,
+        ui_size: UiSize,
+    ) -> Column<'a, Message<S>> {
+        Column::new()
+            .push(Text::new(tr(name)).size(ui_size.main_text()))
+            .push(PickList::new(
+                &mut self.rendering_mode_picklist,
+                layer_overrides(&ALL_RENDERING_MODE),
+                Some(LayerOverride(layer.rendering_mode)),
+                move |mode: LayerOverride<RenderingMode>| {
+                    Message::ViewLayerChanged(
+                        view,
+                        ViewLayer {
+                            rendering_mode: mode.0,
+                            ..layer
+                        },
+                    )
+                },
+            ))
+            .push(PickList::new(
+                &mut self.background3d_picklist,
+                layer_overrides(&ALL_BACKGROUND3D),
+                Some(LayerOverride(layer.background3d)),
+                move |background: LayerOverride<Background3D>| {
+                    Message::ViewLayerChanged(
+                        view,
+                        ViewLayer {
+                            background3d: background.0,
+                            ..layer
+                        },
+                    )
+                },
+            ))
+            .push(right_checkbox(
+                layer.ignore_visibility_sieve,
+                "Show hidden nucleotides",
+                move |ignore_visibility_sieve| {
+                    Message::ViewLayerChanged(
+                        view,
+                        ViewLayer {
+                            ignore_visibility_sieve,
+                            ..layer
+                        },
+                    )
+                },
+                ui_size,
+            ))
+    }
+}
+
 pub struct CameraTab {
     fog: FogParameters,
     scroll: scrollable::State,
@@ -31,8 +145,65 @@ pub struct CameraTab {
     background3d_picklist: pick_list::State<Background3D>,
     pub rendering_mode: RenderingMode,
     rendering_mode_picklist: pick_list::State<RenderingMode>,
+    view_layer_widgets: [ViewLayerWidgets; 2],
     check_xover_picklist: pick_list::State<CheckXoversParameter>,
     h_bonds_picklist: pick_list::State<HBondDisplay>,
+    ground_height_slider: slider::State,
+    ground_spacing_slider: slider::State,
+    numbering_interval_slider: slider::State,
+    prime5_glyph_picklist: pick_list::State<EndGlyph>,
+    prime3_glyph_picklist: pick_list::State<EndGlyph>,
+    cone_spacing_slider: slider::State,
+    xover_guide_offset_slider: slider::State,
+    numbering_picklist: pick_list::State<NucleotideNumbering>,
+    heat_map_picklist: pick_list::State<HeatMapChoice>,
+    compare_last_save_btn: button::State,
+    compare_file_btn: button::State,
+    stop_comparison_btn: button::State,
+    pub checkpoint_name: String,
+    checkpoint_name_input: text_input::State,
+    create_checkpoint_btn: button::State,
+    checkpoints: Vec<CheckpointButtons>,
+}
+
+/// A checkpoint of the list, with the buttons that act on it.
+struct CheckpointButtons {
+    name: String,
+    restore_btn: button::State,
+    compare_btn: button::State,
+    delete_btn: button::State,
+}
+
+impl CheckpointButtons {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            restore_btn: Default::default(),
+            compare_btn: Default::default(),
+            delete_btn: Default::default(),
+        }
+    }
+
+    fn view<'a, S: AppState>(&'a mut self, id: usize, ui_size: UiSize) -> Column<'a, Message<S>> {
+        Column::new()
+            .push(Text::new(self.name.clone()).size(ui_size.main_text()))
+            .push(
+                Row::new()
+                    .spacing(3)
+                    .push(
+                        text_btn(&mut self.restore_btn, "Restore", ui_size)
+                            .on_press(Message::RestoreCheckpoint(id)),
+                    )
+                    .push(
+                        text_btn(&mut self.compare_btn, "Compare", ui_size)
+                            .on_press(Message::CompareWithCheckpoint(id)),
+                    )
+                    .push(
+                        text_btn(&mut self.delete_btn, "Delete", ui_size)
+                            .on_press(Message::DeleteCheckpoint(id)),
+                    ),
+            )
+    }
 }
 
 impl CameraTab {
@@ -47,8 +218,25 @@ impl CameraTab {
             background3d_picklist: Default::default(),
             rendering_mode: Default::default(),
             rendering_mode_picklist: Default::default(),
+            view_layer_widgets: Default::default(),
             check_xover_picklist: Default::default(),
             h_bonds_picklist: Default::default(),
+            ground_height_slider: Default::default(),
+            ground_spacing_slider: Default::default(),
+            numbering_interval_slider: Default::default(),
+            prime5_glyph_picklist: Default::default(),
+            prime3_glyph_picklist: Default::default(),
+            cone_spacing_slider: Default::default(),
+            xover_guide_offset_slider: Default::default(),
+            numbering_picklist: Default::default(),
+            heat_map_picklist: Default::default(),
+            compare_last_save_btn: Default::default(),
+            compare_file_btn: Default::default(),
+            stop_comparison_btn: Default::default(),
+            checkpoint_name: String::new(),
+            checkpoint_name_input: Default::default(),
+            create_checkpoint_btn: Default::default(),
+            checkpoints: Vec::new(),
         }
     }
 
@@ -87,7 +275,7 @@ impl CameraTab {
         ret = ret.push(self.fog.view(&ui_size));
 
         let h_bond_column = Column::new()
-            .push(Text::new("Show H-Bonds").size(ui_size.intermediate_text()))
+            .push(Text::new(tr("Show H-Bonds")).size(ui_size.intermediate_text()))
             .push(PickList::new(
                 &mut self.h_bonds_picklist,
                 [
@@ -116,6 +304,13 @@ impl CameraTab {
             ui_size,
         ));
 
+        ret = ret.push(right_checkbox(
+            app_state.inside_out_inspection(),
+            "Inspect from inside",
+            Message::InsideOutInspection,
+            ui_size,
+        ));
+
         subsection!(ret, ui_size, "Highlight Xovers");
         ret = ret.push(PickList::new(
             &mut self.check_xover_picklist,
@@ -124,30 +319,369 @@ impl CameraTab {
             Message::CheckXoversParameter,
         ));
 
+        subsection!(ret, ui_size, "Compare designs");
+        ret = ret.push(
+            text_btn(
+                &mut self.compare_last_save_btn,
+                "Compare with last save",
+                ui_size,
+            )
+            .on_press(Message::CompareWithLastSave),
+        );
+        ret = ret.push(
+            text_btn(&mut self.compare_file_btn, "Compare with file", ui_size)
+                .on_press(Message::CompareWithDesignFile),
+        );
+        if let Some(summary) = app_state.get_design_diff_summary() {
+            for line in summary.lines() {
+                ret = ret.push(Text::new(line.to_string()).size(ui_size.main_text()));
+            }
+            ret = ret.push(
+                text_btn(&mut self.stop_comparison_btn, "Stop comparing", ui_size)
+                    .on_press(Message::StopDesignComparison),
+            );
+        }
+
+        subsection!(ret, ui_size, "Checkpoints");
+        ret = ret.push(
+            TextInput::new(
+                &mut self.checkpoint_name_input,
+                tr("Checkpoint name"),
+                &self.checkpoint_name,
+                Message::CheckpointNameChanged,
+            )
+            .on_submit(Message::CreateCheckpoint),
+        );
+        ret = ret.push(
+            text_btn(
+                &mut self.create_checkpoint_btn,
+                "Create checkpoint",
+                ui_size,
+            )
+            .on_press(Message::CreateCheckpoint),
+        );
+        for (id, checkpoint) in self.checkpoints.iter_mut().enumerate() {
+            ret = ret.push(checkpoint.view(id, ui_size));
+        }
+
         subsection!(ret, ui_size, "Rendering");
-        ret = ret.push(Text::new("Style"));
+        ret = ret.push(Text::new(tr("Style")));
         ret = ret.push(PickList::new(
             &mut self.rendering_mode_picklist,
             &ALL_RENDERING_MODE[..],
             Some(self.rendering_mode),
             Message::RenderingMode,
         ));
-        ret = ret.push(Text::new("Background"));
+        ret = ret.push(Text::new(tr("Background")));
         ret = ret.push(PickList::new(
             &mut self.background3d_picklist,
             &ALL_BACKGROUND3D[..],
             Some(self.background3d),
             Message::Background3D,
         ));
+
+        subsection!(ret, ui_size, "View layers");
+        for ((view, name), widgets) in LAYER_VIEWS.iter().zip(self.view_layer_widgets.iter_mut()) {
+            ret = ret.push(widgets.view(*view, name, app_state.get_view_layer(*view), ui_size));
+        }
+
+        subsection!(ret, ui_size, "Ground");
+        let ground = app_state.get_ground_parameters();
+        ret = ret.push(right_checkbox(
+            ground.show_grid,
+            "Ground grid",
+            move |show_grid| {
+                Message::GroundParametersChanged(GroundParameters {
+                    show_grid,
+                    ..ground
+                })
+            },
+            ui_size,
+        ));
+        ret = ret.push(right_checkbox(
+            ground.show_shadow,
+            "Contact shadow",
+            move |show_shadow| {
+                Message::GroundParametersChanged(GroundParameters {
+                    show_shadow,
+                    ..ground
+                })
+            },
+            ui_size,
+        ));
+        if ground.is_visible() {
+            let length_unit = app_state.get_scale_bar_parameters().length_unit;
+            let helix_parameters = app_state.get_dna_parameters();
+            ret = ret.push(Text::new(format!(
+                "Distance below design: {}",
+                length_unit.format_nm(ground.height, &helix_parameters)
+            )));
+            ret = ret.push(
+                Slider::new(
+                    &mut self.ground_height_slider,
+                    0f32..=50.,
+                    ground.height,
+                    move |height| {
+                        Message::GroundParametersChanged(GroundParameters { height, ..ground })
+                    },
+                )
+                .step(1.),
+            );
+            ret = ret.push(Text::new(format!(
+                "Grid spacing: {}",
+                length_unit.format_nm(ground.spacing, &helix_parameters)
+            )));
+            ret = ret.push(
+                Slider::new(
+                    &mut self.ground_spacing_slider,
+                    1f32..=50.,
+                    ground.spacing,
+                    move |spacing| {
+                        Message::GroundParametersChanged(GroundParameters { spacing, ..ground })
+                    },
+                )
+                .step(1.),
+            );
+        }
         ret = ret.push(Checkbox::new(
             app_state.expand_insertions(),
             "Expand insertions",
             Message::SetExpandInsertions,
         ));
+        ret = ret.push(right_checkbox(
+            app_state.get_show_insertion_badges(),
+            "Insertion length badges",
+            Message::SetShowInsertionBadges,
+            ui_size,
+        ));
+
+        subsection!(ret, ui_size, "Strand ends");
+        let strand_ends = app_state.get_strand_ends_parameters();
+        ret = ret.push(
+            Row::new()
+                .spacing(5)
+                .push(Text::new(tr("5' end")).width(iced::Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut self.prime5_glyph_picklist,
+                        EndGlyph::ALL.as_slice(),
+                        Some(strand_ends.prime5_glyph),
+                        move |prime5_glyph| {
+                            Message::StrandEndsParametersChanged(StrandEndsParameters {
+                                prime5_glyph,
+                                ..strand_ends
+                            })
+                        },
+                    )
+                    .width(iced::Length::FillPortion(1)),
+                ),
+        );
+        ret = ret.push(
+            Row::new()
+                .spacing(5)
+                .push(Text::new(tr("3' end")).width(iced::Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut self.prime3_glyph_picklist,
+                        EndGlyph::ALL.as_slice(),
+                        Some(strand_ends.prime3_glyph),
+                        move |prime3_glyph| {
+                            Message::StrandEndsParametersChanged(StrandEndsParameters {
+                                prime3_glyph,
+                                ..strand_ends
+                            })
+                        },
+                    )
+                    .width(iced::Length::FillPortion(1)),
+                ),
+        );
+        if strand_ends.cone_spacing == 0 {
+            ret = ret.push(Text::new(tr("No direction cones")));
+        } else {
+            ret = ret.push(Text::new(tr_args(
+                "Direction cones every {} bonds",
+                &[&strand_ends.cone_spacing],
+            )));
+        }
+        ret = ret.push(
+            Slider::new(
+                &mut self.cone_spacing_slider,
+                0f32..=20.,
+                strand_ends.cone_spacing as f32,
+                move |spacing| {
+                    Message::StrandEndsParametersChanged(StrandEndsParameters {
+                        cone_spacing: spacing.round() as usize,
+                        ..strand_ends
+                    })
+                },
+            )
+            .step(1.),
+        );
+
+        subsection!(ret, ui_size, "2D view");
+        let numbering = app_state.get_numbering_parameters();
+        ret = ret.push(right_checkbox(
+            numbering.show_numbers,
+            "Position numbers",
+            move |show_numbers| {
+                Message::NumberingParametersChanged(NumberingParameters {
+                    show_numbers,
+                    ..numbering
+                })
+            },
+            ui_size,
+        ));
+        if numbering.show_numbers {
+            ret = ret.push(Text::new(format!("Every {} bases", numbering.interval)));
+            ret = ret.push(
+                Slider::new(
+                    &mut self.numbering_interval_slider,
+                    1f32..=32.,
+                    numbering.interval as f32,
+                    move |interval| {
+                        Message::NumberingParametersChanged(NumberingParameters {
+                            interval: interval.round() as isize,
+                            ..numbering
+                        })
+                    },
+                )
+                .step(1.),
+            );
+        }
+        ret = ret.push(right_checkbox(
+            numbering.show_domain_boundaries,
+            "Domain boundaries",
+            move |show_domain_boundaries| {
+                Message::NumberingParametersChanged(NumberingParameters {
+                    show_domain_boundaries,
+                    ..numbering
+                })
+            },
+            ui_size,
+        ));
+        ret = ret.push(PickList::new(
+            &mut self.numbering_picklist,
+            &ALL_NUCLEOTIDE_NUMBERINGS[..],
+            Some(numbering.numbering),
+            move |numbering_kind| {
+                Message::NumberingParametersChanged(NumberingParameters {
+                    numbering: numbering_kind,
+                    ..numbering
+                })
+            },
+        ));
+
+        let xover_drawing = app_state.get_xover_drawing_parameters();
+        ret = ret.push(right_checkbox(
+            xover_drawing.arcs,
+            "Cross-overs as arcs",
+            move |arcs| {
+                Message::XoverDrawingParametersChanged(XoverDrawingParameters {
+                    arcs,
+                    ..xover_drawing
+                })
+            },
+            ui_size,
+        ));
+        ret = ret.push(right_checkbox(
+            xover_drawing.bundling,
+            "Bundle cross-overs",
+            move |bundling| {
+                Message::XoverDrawingParametersChanged(XoverDrawingParameters {
+                    bundling,
+                    ..xover_drawing
+                })
+            },
+            ui_size,
+        ));
+        ret = ret.push(right_checkbox(
+            xover_drawing.color_by_length,
+            "Color cross-overs by length",
+            move |color_by_length| {
+                Message::XoverDrawingParametersChanged(XoverDrawingParameters {
+                    color_by_length,
+                    ..xover_drawing
+                })
+            },
+            ui_size,
+        ));
+
+        let xover_guides = app_state.get_xover_guide_parameters();
+        ret = ret.push(right_checkbox(
+            xover_guides.show_guides,
+            "Cross-over guides",
+            move |show_guides| {
+                Message::XoverGuideParametersChanged(XoverGuideParameters {
+                    show_guides,
+                    ..xover_guides
+                })
+            },
+            ui_size,
+        ));
+        if xover_guides.show_guides {
+            ret = ret.push(Text::new(format!(
+                "Register offset: {}",
+                xover_guides.register_offset
+            )));
+            ret = ret.push(
+                Slider::new(
+                    &mut self.xover_guide_offset_slider,
+                    0f32..=(XoverGuideParameters::MAX_REGISTER_OFFSET as f32),
+                    xover_guides.register_offset as f32,
+                    move |offset| {
+                        Message::XoverGuideParametersChanged(XoverGuideParameters {
+                            register_offset: offset.round() as isize,
+                            ..xover_guides
+                        })
+                    },
+                )
+                .step(1.),
+            );
+        }
+
+        subsection!(ret, ui_size, "Analysis overlay");
+        ret = ret.push(PickList::new(
+            &mut self.heat_map_picklist,
+            &HEAT_MAP_CHOICES[..],
+            Some(HeatMapChoice(app_state.get_heat_map())),
+            |choice| Message::HeatMapPicked(choice.0),
+        ));
+        if app_state.get_heat_map() == Some(HeatMapKind::XoverStrain) {
+            let strains = app_state.get_reader().get_helix_strains();
+            if strains.is_empty() {
+                ret =
+                    ret.push(Text::new(tr("No cross-over on a lattice")).size(ui_size.main_text()));
+            }
+            for strain in strains {
+                let advice = match strain.suggested_skips {
+                    0 => String::new(),
+                    n if n > 0 => format!(", {} {}", n, tr("skip(s)")),
+                    n => format!(", {} {}", -n, tr("insertion(s)")),
+                };
+                ret = ret.push(
+                    Text::new(format!(
+                        "{} {}: {:+.0}°{}",
+                        tr("Helix"),
+                        strain.helix,
+                        strain.twist_mismatch,
+                        advice
+                    ))
+                    .size(ui_size.main_text()),
+                );
+            }
+        }
 
         Scrollable::new(&mut self.scroll).push(ret).into()
     }
 
+    pub fn set_checkpoints(&mut self, names: Vec<String>) {
+        self.checkpoints = names.into_iter().map(CheckpointButtons::new).collect();
+    }
+
+    pub fn has_keyboard_priority(&self) -> bool {
+        self.checkpoint_name_input.is_focused()
+    }
+
     pub fn fog_visible(&mut self, visible: bool) {
         self.fog.visible = visible
     }
@@ -192,7 +726,7 @@ struct FogParameters {
 impl FogParameters {
     fn view<S: AppState>(&mut self, ui_size: &UiSize) -> Column<Message<S>> {
         let mut column = Column::new()
-            .push(Text::new("Fog").size(ui_size.intermediate_text()))
+            .push(Text::new(tr("Fog")).size(ui_size.intermediate_text()))
             .push(PickList::new(
                 &mut self.picklist,
                 &ALL_FOG_CHOICE[..],
@@ -206,15 +740,15 @@ impl FogParameters {
             ));
 
         let radius_text = if self.visible {
-            Text::new("Radius")
+            Text::new(tr("Radius"))
         } else {
-            Text::new("Radius").color([0.6, 0.6, 0.6])
+            Text::new(tr("Radius")).color([0.6, 0.6, 0.6])
         };
 
         let gradient_text = if self.visible {
-            Text::new("Softness")
+            Text::new(tr("Softness"))
         } else {
-            Text::new("Softness").color([0.6, 0.6, 0.6])
+            Text::new(tr("Softness")).color([0.6, 0.6, 0.6])
         };
 
         let length_slider = if self.visible {
@@ -16,6 +16,10 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use super::*;
+use ensnano_design::design_operations::{HelixNumbering, DEFAULT_CLEANUP_MIN_LENGTH};
+use ensnano_interactor::graphics::{BrushShape, ALL_BRUSH_EFFECTS, ALL_BRUSH_SHAPES};
+use ensnano_interactor::i18n::tr_args;
+use ensnano_interactor::{SuggestionStyle, ALL_SUGGESTION_STYLES};
 
 pub struct EditionTab<S: AppState> {
     scroll: iced::scrollable::State,
@@ -24,9 +28,52 @@ pub struct EditionTab<S: AppState> {
     _sequence_input: SequenceInput,
     redim_helices_button: button::State,
     redim_all_helices_button: button::State,
+    floating_color_button: button::State,
     roll_target_btn: GoStop<S>,
+    /// If true, only the selected cross-overs are used as springs by the roll simulation
+    roll_on_selected_xovers: bool,
+    ideal_roll_button: button::State,
+    twist_axis_button: button::State,
+    clear_twist_axis_button: button::State,
+    twist_helices_button: button::State,
     color_square_state: ColorState,
     memory_color_squares: VecDeque<MemoryColorSquare>,
+    brush_shape_pick_list: pick_list::State<BrushShape>,
+    suggestion_style_pick_list: pick_list::State<SuggestionStyle>,
+    suggestion_max_distance_slider: slider::State,
+    brush_effect_pick_list: pick_list::State<BrushEffect>,
+    brush_radius_slider: slider::State,
+    bending_radius: f32,
+    bending_direction: f32,
+    bending_radius_slider: slider::State,
+    bending_direction_slider: slider::State,
+    compensate_twist_button: button::State,
+    bend_helices_button: button::State,
+    /// Strands shorter than this are proposed for removal by the strand cleanup
+    cleanup_min_length: u32,
+    cleanup_min_length_slider: slider::State,
+    cleanup_strands_button: button::State,
+    helix_numbering: HelixNumbering,
+    helix_numbering_pick_list: pick_list::State<HelixNumbering>,
+    renumber_helices_button: button::State,
+    /// The number typed by the user for the selected helix. An empty string resets the number
+    /// to the helix identifier.
+    helix_display_number: String,
+    helix_display_number_input: text_input::State,
+    set_helix_display_number_button: button::State,
+    /// The name given to new bundles and to renamed bundles
+    helix_bundle_name: String,
+    helix_bundle_name_input: text_input::State,
+    create_helix_bundle_button: button::State,
+    helix_bundle_buttons: Vec<HelixBundleButtons>,
+}
+
+/// The buttons acting on a helix bundle of the list of bundles
+#[derive(Default)]
+struct HelixBundleButtons {
+    select_button: button::State,
+    rename_button: button::State,
+    delete_button: button::State,
 }
 
 struct MemoryColorSquare {
@@ -100,14 +147,73 @@ macro_rules! add_roll_slider {
 }
 
 macro_rules! add_autoroll_button {
-    ($ret:ident, $self:ident, $app_state: ident, $roll_target_helices: ident) => {
+    ($ret:ident, $self:ident, $app_state: ident, $selection: ident) => {
         let sim_state = &$app_state.get_simulation_state();
-        let roll_target_active = sim_state.is_rolling() || $roll_target_helices.len() > 0;
+        let roll_target_active =
+            sim_state.is_rolling() || $self.get_roll_request(&$selection).is_some();
         $ret = $ret.push(
             $self
                 .roll_target_btn
                 .view(roll_target_active, sim_state.is_rolling()),
         );
+        $ret = $ret.push(Checkbox::new(
+            $self.roll_on_selected_xovers,
+            "Springs on selected cross-overs only",
+            Message::RollOnSelectedXovers,
+        ));
+    };
+}
+
+macro_rules! add_ideal_roll_button {
+    ($ret: ident, $self: ident, $ui_size: ident, $roll_target_helices: ident) => {
+        let mut ideal_roll_button = text_btn(
+            &mut $self.ideal_roll_button,
+            "Reset roll to ideal",
+            $ui_size.clone(),
+        );
+        if !$roll_target_helices.is_empty() {
+            ideal_roll_button = ideal_roll_button.on_press(Message::SetIdealRoll);
+        }
+        $ret = $ret.push(ideal_roll_button);
+    };
+}
+
+macro_rules! add_twist_helices_buttons {
+    ($ret: ident, $self: ident, $app_state: ident, $ui_size: ident, $roll_target_helices: ident) => {
+        let mut twist_axis_button = text_btn(
+            &mut $self.twist_axis_button,
+            "Set axis from selection",
+            $ui_size.clone(),
+        );
+        if !$roll_target_helices.is_empty() {
+            twist_axis_button = twist_axis_button.on_press(Message::SetTwistAxisFromSelection);
+        }
+        let mut clear_twist_axis_button = text_btn(
+            &mut $self.clear_twist_axis_button,
+            "Remove axis",
+            $ui_size.clone(),
+        );
+        if $app_state.get_twist_axis().is_some() {
+            clear_twist_axis_button = clear_twist_axis_button.on_press(Message::ClearTwistAxis);
+        }
+        let twist_helices_button = match $app_state.get_simulation_state() {
+            SimulationState::TwistingHelices => {
+                text_btn(&mut $self.twist_helices_button, "Stop", $ui_size.clone())
+                    .on_press(Message::StopSimulation)
+            }
+            SimulationState::None if !$roll_target_helices.is_empty() => {
+                text_btn(&mut $self.twist_helices_button, "Twist", $ui_size.clone())
+                    .on_press(Message::StartHelicesTwist)
+            }
+            _ => text_btn(&mut $self.twist_helices_button, "Twist", $ui_size.clone()),
+        };
+        $ret = $ret.push(
+            Row::new()
+                .spacing(5)
+                .push(twist_axis_button)
+                .push(clear_twist_axis_button),
+        );
+        $ret = $ret.push(twist_helices_button);
     };
 }
 
@@ -145,6 +251,202 @@ macro_rules! add_tighten_helices_button {
     };
 }
 
+/// The default radius of curvature, in nanometers, of the helices bent with insertions and skips.
+const DEFAULT_BENDING_RADIUS: f32 = 100.;
+const BENDING_RADIUS_RANGE: std::ops::RangeInclusive<f32> = 20.0..=500.0;
+
+macro_rules! add_insertion_placement_controls {
+    ($ret: ident, $self: ident, $app_state: ident, $ui_size: ident, $roll_target_helices: ident) => {
+        let mut compensate_twist_button = text_btn(
+            &mut $self.compensate_twist_button,
+            "Relax lattice twist",
+            $ui_size.clone(),
+        );
+        if !$roll_target_helices.is_empty() {
+            compensate_twist_button = compensate_twist_button.on_press(Message::PlaceInsertions(
+                InsertionPlacementTarget::LatticeTwist,
+            ));
+        }
+        $ret = $ret.push(compensate_twist_button);
+        $ret = $ret.push(Text::new(format!(
+            "{} {}",
+            tr("Radius of curvature"),
+            $app_state
+                .get_scale_bar_parameters()
+                .length_unit
+                .format_nm($self.bending_radius, &$app_state.get_dna_parameters())
+        )));
+        $ret = $ret.push(
+            Slider::new(
+                &mut $self.bending_radius_slider,
+                BENDING_RADIUS_RANGE,
+                $self.bending_radius,
+                Message::BendingRadiusChanged,
+            )
+            .step(5.),
+        );
+        $ret = $ret.push(Text::new(format!(
+            "{} {:.0}°",
+            tr("Bending direction"),
+            $self.bending_direction
+        )));
+        $ret = $ret.push(
+            Slider::new(
+                &mut $self.bending_direction_slider,
+                0.0..=355.0,
+                $self.bending_direction,
+                Message::BendingDirectionChanged,
+            )
+            .step(5.),
+        );
+        let mut bend_helices_button =
+            text_btn(&mut $self.bend_helices_button, "Bend", $ui_size.clone());
+        if !$roll_target_helices.is_empty() {
+            bend_helices_button = bend_helices_button.on_press(Message::PlaceInsertions(
+                InsertionPlacementTarget::Curvature {
+                    radius: $self.bending_radius,
+                    direction: $self.bending_direction,
+                },
+            ));
+        }
+        $ret = $ret.push(bend_helices_button);
+    };
+}
+
+macro_rules! add_helix_numbering_controls {
+    ($ret: ident, $self: ident, $ui_size: ident, $roll_target_helices: ident) => {
+        $ret = $ret.push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    PickList::new(
+                        &mut $self.helix_numbering_pick_list,
+                        HelixNumbering::ALL,
+                        Some($self.helix_numbering),
+                        Message::HelixNumberingPicked,
+                    )
+                    .width(Length::FillPortion(2)),
+                )
+                .push(
+                    text_btn(
+                        &mut $self.renumber_helices_button,
+                        "Renumber",
+                        $ui_size.clone(),
+                    )
+                    .on_press(Message::RenumberHelices),
+                ),
+        );
+        let single_helix = if $roll_target_helices.len() == 1 {
+            Some($roll_target_helices[0])
+        } else {
+            None
+        };
+        let number_is_valid = $self.parse_helix_display_number().is_some();
+        let mut set_number_button = text_btn(
+            &mut $self.set_helix_display_number_button,
+            "Set number",
+            $ui_size.clone(),
+        );
+        let mut number_input = TextInput::new(
+            &mut $self.helix_display_number_input,
+            tr("Number of the selected helix"),
+            &$self.helix_display_number,
+            Message::HelixDisplayNumberChanged,
+        )
+        .style(BadValue(number_is_valid));
+        if let Some(helix) = single_helix.filter(|_| number_is_valid) {
+            set_number_button = set_number_button.on_press(Message::SetHelixDisplayNumber(helix));
+            number_input = number_input.on_submit(Message::SetHelixDisplayNumber(helix));
+        }
+        $ret = $ret.push(
+            Row::new()
+                .spacing(5)
+                .push(number_input.width(Length::FillPortion(2)))
+                .push(set_number_button),
+        );
+    };
+}
+
+macro_rules! add_helix_bundle_controls {
+    ($ret: ident, $self: ident, $app_state: ident, $ui_size: ident, $roll_target_helices: ident) => {
+        let bundles = $app_state.get_reader().get_helix_bundles();
+        $self
+            .helix_bundle_buttons
+            .resize_with(bundles.len(), Default::default);
+        $ret = $ret.push(TextInput::new(
+            &mut $self.helix_bundle_name_input,
+            tr("Bundle name"),
+            &$self.helix_bundle_name,
+            Message::HelixBundleNameChanged,
+        ));
+        let mut create_button = text_btn(
+            &mut $self.create_helix_bundle_button,
+            "Bundle selected helices",
+            $ui_size.clone(),
+        );
+        if !$roll_target_helices.is_empty() {
+            create_button =
+                create_button.on_press(Message::CreateHelixBundle($roll_target_helices.clone()));
+        }
+        $ret = $ret.push(create_button);
+        let name_is_empty = $self.helix_bundle_name.trim().is_empty();
+        for ((bundle_id, bundle), buttons) in
+            bundles.iter().zip($self.helix_bundle_buttons.iter_mut())
+        {
+            $ret = $ret.push(
+                Text::new(tr_args(
+                    "{} ({} helices)",
+                    &[&bundle.name, &bundle.helices.len()],
+                ))
+                .size($ui_size.main_text()),
+            );
+            let mut rename_button =
+                text_btn(&mut buttons.rename_button, "Rename", $ui_size.clone());
+            if !name_is_empty {
+                rename_button = rename_button.on_press(Message::RenameHelixBundle(*bundle_id));
+            }
+            $ret = $ret.push(
+                Row::new()
+                    .spacing(3)
+                    .push(
+                        text_btn(&mut buttons.select_button, "Select", $ui_size.clone())
+                            .on_press(Message::SelectHelixBundle(*bundle_id)),
+                    )
+                    .push(rename_button)
+                    .push(
+                        text_btn(&mut buttons.delete_button, "Delete", $ui_size.clone())
+                            .on_press(Message::DeleteHelixBundle(*bundle_id)),
+                    ),
+            );
+        }
+    };
+}
+
+const CLEANUP_MIN_LENGTH_RANGE: std::ops::RangeInclusive<u32> = 1..=40;
+
+macro_rules! add_strand_cleanup_controls {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        $ret = $ret.push(Text::new(tr_args(
+            "Remove strands shorter than {} nt",
+            &[&$self.cleanup_min_length],
+        )));
+        $ret = $ret.push(Slider::new(
+            &mut $self.cleanup_min_length_slider,
+            CLEANUP_MIN_LENGTH_RANGE,
+            $self.cleanup_min_length,
+            Message::CleanupMinLengthChanged,
+        ));
+        $ret = $ret.push(
+            text_btn(
+                &mut $self.cleanup_strands_button,
+                "Clean up strands",
+                $ui_size.clone(),
+            )
+            .on_press(Message::CleanupStrands),
+        );
+    };
+}
+
 macro_rules! add_suggestion_parameters_checkboxes {
     ($ret: ident, $self: ident, $app_state: ident, $ui_size: ident) => {
         let suggestion_parameters = $app_state.get_suggestion_parameters().clone();
@@ -177,6 +479,143 @@ macro_rules! add_suggestion_parameters_checkboxes {
             move |b| Message::NewSuggestionParameters(suggestion_parameters.with_ignore_groups(b)),
             $ui_size,
         ));
+        let suggestion_parameters = $app_state.get_suggestion_parameters().clone();
+        $ret = $ret.push(right_checkbox(
+            suggestion_parameters.same_group_only,
+            "Same group only",
+            move |b| {
+                Message::NewSuggestionParameters(suggestion_parameters.with_same_group_only(b))
+            },
+            $ui_size,
+        ));
+        let suggestion_parameters = $app_state.get_suggestion_parameters().clone();
+        $ret = $ret.push(Text::new(format!(
+            "{} {:.2} nm",
+            tr("Maximal distance"),
+            suggestion_parameters.max_distance
+        )));
+        $ret = $ret.push(
+            Slider::new(
+                &mut $self.suggestion_max_distance_slider,
+                SuggestionParameters::MAX_DISTANCE_RANGE,
+                suggestion_parameters.max_distance,
+                move |d| {
+                    Message::NewSuggestionParameters(suggestion_parameters.with_max_distance(d))
+                },
+            )
+            .step(0.05),
+        );
+        let suggestion_parameters = $app_state.get_suggestion_parameters().clone();
+        $ret = $ret.push(
+            Row::new()
+                .push(Text::new(tr("Style")).width(Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut $self.suggestion_style_pick_list,
+                        &ALL_SUGGESTION_STYLES[..],
+                        Some(suggestion_parameters.style),
+                        move |style| {
+                            Message::NewSuggestionParameters(
+                                suggestion_parameters.with_style(style),
+                            )
+                        },
+                    )
+                    .width(Length::FillPortion(1)),
+                ),
+        );
+        let suggestion_parameters = $app_state.get_suggestion_parameters().clone();
+        $ret = $ret.push(right_checkbox(
+            suggestion_parameters.color_by_distance,
+            "Color by distance",
+            move |b| {
+                Message::NewSuggestionParameters(suggestion_parameters.with_color_by_distance(b))
+            },
+            $ui_size,
+        ));
+    };
+}
+
+macro_rules! add_brush_controls {
+    ($ret: ident, $self: ident, $brush: ident, $picked_color: ident, $ui_size: ident) => {
+        $ret = $ret.push(right_checkbox(
+            $brush.active,
+            "Paint with brush",
+            move |active| Message::BrushParametersChanged(BrushParameters { active, ..$brush }),
+            $ui_size.clone(),
+        ));
+        $ret = $ret.push(
+            Row::new()
+                .push(Text::new(tr("Shape")).width(Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut $self.brush_shape_pick_list,
+                        &ALL_BRUSH_SHAPES[..],
+                        Some($brush.shape),
+                        move |shape| {
+                            Message::BrushParametersChanged(BrushParameters {
+                                shape,
+                                radius: shape.default_radius(),
+                                ..$brush
+                            })
+                        },
+                    )
+                    .width(Length::FillPortion(1)),
+                ),
+        );
+        let unit = match $brush.shape {
+            BrushShape::Sphere => "nm",
+            BrushShape::Screen => "px",
+        };
+        $ret = $ret.push(Text::new(format!(
+            "{} {:.1} {}",
+            tr("Radius"),
+            $brush.radius,
+            unit
+        )));
+        $ret = $ret.push(
+            Slider::new(
+                &mut $self.brush_radius_slider,
+                $brush.shape.radius_range(),
+                $brush.radius,
+                move |radius| Message::BrushParametersChanged(BrushParameters { radius, ..$brush }),
+            )
+            .step(0.5),
+        );
+        $ret = $ret.push(
+            Row::new()
+                .push(Text::new(tr("Effect")).width(Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut $self.brush_effect_pick_list,
+                        &ALL_BRUSH_EFFECTS[..],
+                        Some($brush.effect),
+                        move |effect| {
+                            Message::BrushParametersChanged(BrushParameters {
+                                effect,
+                                color: $picked_color,
+                                ..$brush
+                            })
+                        },
+                    )
+                    .width(Length::FillPortion(1)),
+                ),
+        );
+        $ret = $ret.push(right_checkbox(
+            $brush.paint_occluded,
+            "Paint occluded nucleotides",
+            move |paint_occluded| {
+                Message::BrushParametersChanged(BrushParameters {
+                    paint_occluded,
+                    ..$brush
+                })
+            },
+            $ui_size.clone(),
+        ));
+        if $brush.effect == BrushEffect::Color {
+            $ret = $ret.push(Text::new(tr(
+                "The strands are painted with the color of the color picker",
+            )));
+        }
     };
 }
 
@@ -189,12 +628,42 @@ impl<S: AppState> EditionTab<S> {
             _sequence_input: SequenceInput::new(),
             redim_helices_button: Default::default(),
             redim_all_helices_button: Default::default(),
+            floating_color_button: Default::default(),
             roll_target_btn: GoStop::new(
                 "Autoroll selected helices".to_owned(),
                 Message::RollTargeted,
             ),
+            roll_on_selected_xovers: false,
+            ideal_roll_button: Default::default(),
+            twist_axis_button: Default::default(),
+            clear_twist_axis_button: Default::default(),
+            twist_helices_button: Default::default(),
             color_square_state: Default::default(),
             memory_color_squares: VecDeque::new(),
+            brush_shape_pick_list: Default::default(),
+            suggestion_style_pick_list: Default::default(),
+            suggestion_max_distance_slider: Default::default(),
+            brush_effect_pick_list: Default::default(),
+            brush_radius_slider: Default::default(),
+            bending_radius: DEFAULT_BENDING_RADIUS,
+            bending_direction: 0.,
+            bending_radius_slider: Default::default(),
+            bending_direction_slider: Default::default(),
+            compensate_twist_button: Default::default(),
+            bend_helices_button: Default::default(),
+            cleanup_min_length: DEFAULT_CLEANUP_MIN_LENGTH as u32,
+            cleanup_min_length_slider: Default::default(),
+            cleanup_strands_button: Default::default(),
+            helix_numbering: HelixNumbering::RowMajor,
+            helix_numbering_pick_list: Default::default(),
+            renumber_helices_button: Default::default(),
+            helix_display_number: String::new(),
+            helix_display_number_input: Default::default(),
+            set_helix_display_number_button: Default::default(),
+            helix_bundle_name: String::new(),
+            helix_bundle_name_input: Default::default(),
+            create_helix_bundle_button: Default::default(),
+            helix_bundle_buttons: Vec::new(),
         }
     }
 
@@ -209,21 +678,46 @@ impl<S: AppState> EditionTab<S> {
         let roll_target_helices = self.get_roll_target_helices(&selection);
         section!(ret, ui_size, "Edition");
         add_roll_slider!(ret, self, app_state, ui_size);
-        add_autoroll_button!(ret, self, app_state, roll_target_helices);
+        add_autoroll_button!(ret, self, app_state, selection);
+        add_ideal_roll_button!(ret, self, ui_size, roll_target_helices);
+
+        subsection!(ret, ui_size, "Twist free helices");
+        add_twist_helices_buttons!(ret, self, app_state, ui_size, roll_target_helices);
 
         let selection_contains_strand =
             ensnano_interactor::extract_strands_from_selection(app_state.get_selection()).len() > 0;
-        if selection_contains_strand {
+        let brush = app_state.get_brush_parameters();
+        let picked_color = self.strand_color_change();
+        if selection_contains_strand || (brush.active && brush.effect == BrushEffect::Color) {
             let color_square = self.color_picker.color_square(&mut self.color_square_state);
             add_color_square!(ret, self, color_square);
+            ret = ret.push(
+                text_btn(&mut self.floating_color_button, "Floating picker", ui_size)
+                    .on_press(Message::OpenColor),
+            );
         }
 
+        subsection!(ret, ui_size, "Brush");
+        add_brush_controls!(ret, self, brush, picked_color, ui_size);
+
         subsection!(ret, ui_size, "Suggestions Parameters");
         add_suggestion_parameters_checkboxes!(ret, self, app_state, ui_size);
 
         subsection!(ret, ui_size, "Tighten 2D helices");
         add_tighten_helices_button!(ret, self, app_state, ui_size, roll_target_helices);
 
+        subsection!(ret, ui_size, "Insertions and skips");
+        add_insertion_placement_controls!(ret, self, app_state, ui_size, roll_target_helices);
+
+        subsection!(ret, ui_size, "Helix numbering");
+        add_helix_numbering_controls!(ret, self, ui_size, roll_target_helices);
+
+        subsection!(ret, ui_size, "Helix bundles");
+        add_helix_bundle_controls!(ret, self, app_state, ui_size, roll_target_helices);
+
+        subsection!(ret, ui_size, "Strand cleanup");
+        add_strand_cleanup_controls!(ret, self, ui_size);
+
         Scrollable::new(&mut self.scroll).push(ret).into()
     }
 
@@ -247,19 +741,101 @@ impl<S: AppState> EditionTab<S> {
             .update_request(value_id, value, request);
     }
 
-    pub fn get_roll_request(&mut self, selection: &[DesignElementKey]) -> Option<RollRequest> {
+    /// Return the cross-overs of the selection that must be used as springs by the roll
+    /// simulation, or `None` if all cross-overs must be used.
+    fn get_roll_target_xovers(&self, selection: &[DesignElementKey]) -> Option<Vec<usize>> {
+        if self.roll_on_selected_xovers {
+            Some(
+                selection
+                    .iter()
+                    .filter_map(|s| {
+                        if let DesignElementKey::CrossOver { xover_id } = s {
+                            Some(*xover_id)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    pub fn get_roll_request(&self, selection: &[DesignElementKey]) -> Option<RollRequest> {
         let roll_target_helices = self.get_roll_target_helices(selection);
-        if roll_target_helices.len() > 0 {
+        let target_xovers = self.get_roll_target_xovers(selection);
+        if let Some(xovers) = target_xovers {
+            // Without selected helices, every helix attached to one of the cross-overs may roll
+            (!xovers.is_empty()).then(|| RollRequest {
+                roll: true,
+                springs: false,
+                target_helices: Some(roll_target_helices).filter(|helices| !helices.is_empty()),
+                target_xovers: Some(xovers),
+            })
+        } else if roll_target_helices.len() > 0 {
             Some(RollRequest {
                 roll: true,
                 springs: false,
                 target_helices: Some(roll_target_helices.clone()),
+                target_xovers: None,
             })
         } else {
             None
         }
     }
 
+    pub fn set_roll_on_selected_xovers(&mut self, roll_on_selected_xovers: bool) {
+        self.roll_on_selected_xovers = roll_on_selected_xovers;
+    }
+
+    pub fn set_bending_radius(&mut self, radius: f32) {
+        self.bending_radius = radius;
+    }
+
+    pub fn set_bending_direction(&mut self, direction: f32) {
+        self.bending_direction = direction;
+    }
+
+    pub fn set_cleanup_min_length(&mut self, min_length: u32) {
+        self.cleanup_min_length = min_length;
+    }
+
+    pub fn get_cleanup_min_length(&self) -> usize {
+        self.cleanup_min_length as usize
+    }
+
+    pub fn set_helix_numbering(&mut self, scheme: HelixNumbering) {
+        self.helix_numbering = scheme;
+    }
+
+    pub fn get_helix_numbering(&self) -> HelixNumbering {
+        self.helix_numbering
+    }
+
+    pub fn set_helix_display_number(&mut self, number: String) {
+        self.helix_display_number = number;
+    }
+
+    /// Return `Some(None)` if the input is empty, `Some(Some(n))` if it is a valid number and
+    /// `None` otherwise.
+    pub fn parse_helix_display_number(&self) -> Option<Option<usize>> {
+        let number = self.helix_display_number.trim();
+        if number.is_empty() {
+            Some(None)
+        } else {
+            number.parse().ok().map(Some)
+        }
+    }
+
+    pub fn set_helix_bundle_name(&mut self, name: String) {
+        self.helix_bundle_name = name;
+    }
+
+    pub fn get_helix_bundle_name(&self) -> String {
+        self.helix_bundle_name.trim().to_string()
+    }
+
     pub fn strand_color_change(&mut self) -> u32 {
         let color = self.color_picker.update_color();
         super::color_to_u32(color)
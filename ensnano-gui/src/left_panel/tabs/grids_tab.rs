@@ -97,7 +97,7 @@ macro_rules! add_hyperboloid_sliders {
 macro_rules! add_guess_grid_button {
     ($ret: ident, $self: ident, $ui_size: ident, $app_state: ident) => {
         let mut button_make_grid =
-            Button::new(&mut $self.make_grid_btn, iced::Text::new("From Selection"))
+            Button::new(&mut $self.make_grid_btn, iced::Text::new(tr("From Selection")))
                 .height(Length::Units($ui_size.button()));
 
         if $app_state.can_make_grid() {
@@ -105,7 +105,7 @@ macro_rules! add_guess_grid_button {
         }
 
         $ret = $ret.push(button_make_grid);
-        $ret = $ret.push(Text::new("Select ≥4 unattached helices").size($ui_size.main_text()));
+        $ret = $ret.push(Text::new(tr("Select ≥4 unattached helices")).size($ui_size.main_text()));
     };
 }
 
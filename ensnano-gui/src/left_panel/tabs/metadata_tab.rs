@@ -0,0 +1,235 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Edition of the author, description, license and experiment notes that are stored in the
+//! design.
+
+use super::*;
+use ensnano_design::DesignMetadata;
+
+/// A line of Markdown text, as it is displayed in the preview of the notes.
+#[derive(Debug, PartialEq, Eq)]
+enum MarkdownLine {
+    Heading { level: usize, text: String },
+    Bullet(String),
+    Paragraph(String),
+    Blank,
+}
+
+impl MarkdownLine {
+    /// Interpret `line` as a Markdown heading, list item or paragraph. Emphasis and code markers
+    /// are removed because they cannot be rendered by a text widget.
+    fn parse(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return Self::Blank;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            return Self::Heading {
+                level,
+                text: strip_inline_markers(&trimmed[level..]),
+            };
+        }
+        for bullet in ["- ", "* ", "+ "].iter() {
+            if let Some(item) = trimmed.strip_prefix(bullet) {
+                return Self::Bullet(strip_inline_markers(item));
+            }
+        }
+        Self::Paragraph(strip_inline_markers(trimmed))
+    }
+}
+
+fn strip_inline_markers(text: &str) -> String {
+    text.replace("**", "")
+        .replace("__", "")
+        .replace('`', "")
+        .trim()
+        .to_string()
+}
+
+pub struct MetadataTab {
+    scroll: scrollable::State,
+    author_input: text_input::State,
+    date_input: text_input::State,
+    license_input: text_input::State,
+    description_input: text_input::State,
+    /// One input per line of the notes
+    note_inputs: Vec<text_input::State>,
+    /// If true, the notes are edited line by line instead of being rendered
+    editing_notes: bool,
+    edit_notes_button: button::State,
+}
+
+impl Default for MetadataTab {
+    fn default() -> Self {
+        Self {
+            scroll: Default::default(),
+            author_input: Default::default(),
+            date_input: Default::default(),
+            license_input: Default::default(),
+            description_input: Default::default(),
+            note_inputs: Vec::new(),
+            editing_notes: false,
+            edit_notes_button: Default::default(),
+        }
+    }
+}
+
+macro_rules! metadata_input {
+    ($ret: ident, $ui_size: ident, $metadata: ident, $state: expr, $label: tt, $placeholder: tt, $field: ident) => {
+        $ret = $ret.push(Text::new(tr($label)).size($ui_size.main_text()));
+        let metadata = $metadata.clone();
+        $ret = $ret.push(
+            TextInput::new($state, tr($placeholder), &$metadata.$field, move |s| {
+                let mut metadata = metadata.clone();
+                metadata.$field = s;
+                Message::DesignMetadataChanged(metadata)
+            })
+            .size($ui_size.main_text()),
+        );
+    };
+}
+
+impl MetadataTab {
+    pub fn toggle_editing_notes(&mut self) {
+        self.editing_notes ^= true;
+    }
+
+    pub fn has_keyboard_priority(&self) -> bool {
+        self.author_input.is_focused()
+            || self.date_input.is_focused()
+            || self.license_input.is_focused()
+            || self.description_input.is_focused()
+            || self.note_inputs.iter().any(|i| i.is_focused())
+    }
+
+    pub fn view<'a, S: AppState>(
+        &'a mut self,
+        ui_size: UiSize,
+        app_state: &S,
+    ) -> Element<'a, Message<S>> {
+        let metadata = app_state.get_reader().get_design_metadata();
+        let mut ret = Column::new().spacing(5);
+        section!(ret, ui_size, "Design information");
+        metadata_input!(
+            ret,
+            ui_size,
+            metadata,
+            &mut self.author_input,
+            "Author",
+            "Name of the author",
+            author
+        );
+        metadata_input!(
+            ret,
+            ui_size,
+            metadata,
+            &mut self.date_input,
+            "Date",
+            "YYYY-MM-DD",
+            date
+        );
+        metadata_input!(
+            ret,
+            ui_size,
+            metadata,
+            &mut self.license_input,
+            "License",
+            "CC-BY-4.0",
+            license
+        );
+        metadata_input!(
+            ret,
+            ui_size,
+            metadata,
+            &mut self.description_input,
+            "Description",
+            "What the design is made for",
+            description
+        );
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Experiment notes");
+        let button_label = if self.editing_notes {
+            "Preview"
+        } else {
+            "Edit notes"
+        };
+        ret = ret.push(
+            text_btn(&mut self.edit_notes_button, button_label, ui_size)
+                .on_press(Message::ToggleEditingNotes),
+        );
+
+        let lines: Vec<String> = metadata.notes.split('\n').map(String::from).collect();
+        if self.editing_notes {
+            self.note_inputs.resize_with(lines.len(), Default::default);
+            for (i, state) in self.note_inputs.iter_mut().enumerate() {
+                let on_change = {
+                    let metadata = metadata.clone();
+                    let lines = lines.clone();
+                    move |s: String| {
+                        let mut lines = lines.clone();
+                        lines[i] = s;
+                        let mut metadata = metadata.clone();
+                        metadata.notes = lines.join("\n");
+                        Message::DesignMetadataChanged(metadata)
+                    }
+                };
+                // Pressing enter starts a new line after the current one
+                let on_submit = {
+                    let mut lines = lines.clone();
+                    lines.insert(i + 1, String::new());
+                    let mut metadata = metadata.clone();
+                    metadata.notes = lines.join("\n");
+                    Message::DesignMetadataChanged(metadata)
+                };
+                ret = ret.push(
+                    TextInput::new(state, "", &lines[i], on_change)
+                        .on_submit(on_submit)
+                        .size(ui_size.main_text()),
+                );
+            }
+        } else if metadata.notes.trim().is_empty() {
+            ret = ret.push(Text::new(tr("No notes")).size(ui_size.main_text()));
+        } else {
+            for line in lines.iter() {
+                ret = match MarkdownLine::parse(line) {
+                    MarkdownLine::Heading { level, text } => {
+                        let size = if level == 1 {
+                            ui_size.head_text()
+                        } else {
+                            ui_size.intermediate_text()
+                        };
+                        ret.push(Text::new(text).size(size))
+                    }
+                    MarkdownLine::Bullet(text) => {
+                        ret.push(Text::new(format!("• {}", text)).size(ui_size.main_text()))
+                    }
+                    MarkdownLine::Paragraph(text) => {
+                        ret.push(Text::new(text).size(ui_size.main_text()))
+                    }
+                    MarkdownLine::Blank => {
+                        ret.push(iced::Space::with_height(Length::Units(JUMP_SIZE)))
+                    }
+                };
+            }
+        }
+
+        Scrollable::new(&mut self.scroll).push(ret).into()
+    }
+}
@@ -18,19 +18,121 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 
 use super::*;
 use ensnano_design::NamedParameter;
+use ensnano_interactor::{
+    consts::{MAX_GIZMO_SIZE, MAX_UNDO_LIMIT, MIN_GIZMO_SIZE, MIN_UNDO_LIMIT},
+    graphics::{
+        AnisotropicFiltering, GpuPreference, LengthUnit, Multisampling, RenderQuality, Ruler,
+        ScaleBarParameters, TextureResolution, ALL_ANISOTROPIC_FILTERINGS, ALL_COLOR_VISION_MODES,
+        ALL_GPU_PREFERENCES, ALL_LENGTH_UNITS, ALL_MULTISAMPLINGS, ALL_RULERS,
+        ALL_TEXTURE_RESOLUTIONS,
+    },
+    i18n::tr_args,
+    plugin::PluginDescription,
+    ALL_WIDGET_BASES,
+};
 
 pub struct ParametersTab {
-    size_pick_list: pick_list::State<UiSize>,
+    ui_scale_slider: slider::State,
+    /// The scale that is being picked with the slider, applied when the slider is released.
+    ui_scale_preview: Option<u16>,
+    theme_pick_list: pick_list::State<Theme>,
+    language_pick_list: pick_list::State<Language>,
+    color_vision_pick_list: pick_list::State<ColorVisionMode>,
+    gpu_preference_pick_list: pick_list::State<GpuPreference>,
+    multisampling_pick_list: pick_list::State<Multisampling>,
+    anisotropic_filtering_pick_list: pick_list::State<AnisotropicFiltering>,
+    texture_resolution_pick_list: pick_list::State<TextureResolution>,
+    widget_basis_pick_list: pick_list::State<WidgetBasis>,
+    gizmo_size_slider: slider::State,
+    undo_limit_slider: slider::State,
+    ruler_pick_list: pick_list::State<Ruler>,
+    length_unit_pick_list: pick_list::State<LengthUnit>,
     scroll: scrollable::State,
     scroll_sensitivity_factory: RequestFactory<ScrollSentivity>,
     dna_parameters_picklist: pick_list::State<NamedParameter>,
     pub invert_y_scroll: bool,
+    plugins: Vec<PluginInputs>,
+}
+
+/// The section of a plugin, with one input per parameter of the plugin.
+struct PluginInputs {
+    description: PluginDescription,
+    values: Vec<String>,
+    inputs: Vec<text_input::State>,
+    run_button: button::State,
+}
+
+impl PluginInputs {
+    fn new(description: PluginDescription) -> Self {
+        let values = description
+            .parameters
+            .iter()
+            .map(|p| p.default_value.clone())
+            .collect();
+        let inputs = description
+            .parameters
+            .iter()
+            .map(|_| Default::default())
+            .collect();
+        Self {
+            description,
+            values,
+            inputs,
+            run_button: Default::default(),
+        }
+    }
+
+    fn view<'a, S: AppState>(&'a mut self, ui_size: UiSize) -> Column<'a, Message<S>> {
+        let plugin_id = self.description.id;
+        let mut ret = Column::new()
+            .push(Text::new(self.description.name.clone()).size(ui_size.intermediate_text()));
+        if !self.description.description.is_empty() {
+            ret =
+                ret.push(Text::new(self.description.description.clone()).size(ui_size.main_text()));
+        }
+        let parameters = self.description.parameters.iter();
+        for (parameter_id, ((parameter, state), value)) in parameters
+            .zip(self.inputs.iter_mut())
+            .zip(self.values.iter())
+            .enumerate()
+        {
+            let row = Row::new()
+                .push(
+                    Text::new(parameter.name.clone())
+                        .size(ui_size.main_text())
+                        .width(Length::FillPortion(2)),
+                )
+                .push(
+                    TextInput::new(state, &parameter.default_value, value, move |s| {
+                        Message::PluginInput(plugin_id, parameter_id, s)
+                    })
+                    .width(Length::FillPortion(1)),
+                );
+            ret = ret.push(row);
+        }
+        ret.push(
+            text_btn(&mut self.run_button, "Run", ui_size).on_press(Message::RunPlugin(plugin_id)),
+        )
+    }
 }
 
 impl ParametersTab {
     pub fn new<S: AppState>(app_state: &S) -> Self {
         Self {
-            size_pick_list: Default::default(),
+            ui_scale_slider: Default::default(),
+            ui_scale_preview: None,
+            theme_pick_list: Default::default(),
+            language_pick_list: Default::default(),
+            color_vision_pick_list: Default::default(),
+            gpu_preference_pick_list: Default::default(),
+            multisampling_pick_list: Default::default(),
+            anisotropic_filtering_pick_list: Default::default(),
+            texture_resolution_pick_list: Default::default(),
+            widget_basis_pick_list: Default::default(),
+            gizmo_size_slider: Default::default(),
+            undo_limit_slider: Default::default(),
+            ruler_pick_list: Default::default(),
+            length_unit_pick_list: Default::default(),
             scroll: Default::default(),
             scroll_sensitivity_factory: RequestFactory::new(
                 FactoryId::Scroll,
@@ -40,6 +142,7 @@ impl ParametersTab {
             ),
             dna_parameters_picklist: Default::default(),
             invert_y_scroll: false,
+            plugins: Vec::new(),
         }
     }
 
@@ -51,12 +154,110 @@ impl ParametersTab {
         let mut ret = Column::new();
         section!(ret, ui_size, "Parameters");
         extra_jump!(ret);
-        subsection!(ret, ui_size, "Font size");
+        let ui_scale = self.ui_scale_preview.unwrap_or_else(|| ui_size.percent());
+        subsection!(ret, ui_size, (format!("Interface scale: {ui_scale}%")));
+        ret = ret.push(
+            Slider::new(
+                &mut self.ui_scale_slider,
+                crate::MIN_UI_SCALE..=crate::MAX_UI_SCALE,
+                ui_scale,
+                Message::UiScaleSliding,
+            )
+            .step(5)
+            .on_release(Message::UiSizePicked(UiSize::from_percent(ui_scale))),
+        );
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Theme");
+        ret = ret.push(PickList::new(
+            &mut self.theme_pick_list,
+            &crate::theme::ALL_THEMES[..],
+            Some(app_state.get_theme()),
+            Message::ThemePicked,
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Color vision");
         ret = ret.push(PickList::new(
-            &mut self.size_pick_list,
-            &super::super::super::ALL_UI_SIZE[..],
-            Some(ui_size.clone()),
-            Message::UiSizePicked,
+            &mut self.color_vision_pick_list,
+            &ALL_COLOR_VISION_MODES[..],
+            Some(app_state.get_color_vision_mode()),
+            Message::ColorVisionModePicked,
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Language");
+        ret = ret.push(PickList::new(
+            &mut self.language_pick_list,
+            Language::ALL,
+            Some(app_state.get_language()),
+            Message::LanguagePicked,
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Translation/rotation widgets");
+        ret = ret.push(
+            Row::new()
+                .push(Text::new(tr("Orientation")).width(Length::FillPortion(1)))
+                .push(
+                    PickList::new(
+                        &mut self.widget_basis_pick_list,
+                        &ALL_WIDGET_BASES[..],
+                        Some(app_state.get_widget_basis()),
+                        Message::WidgetBasisPicked,
+                    )
+                    .width(Length::FillPortion(1)),
+                ),
+        );
+        let gizmo_size = app_state.get_gizmo_size();
+        ret = ret.push(Text::new(format!("Size: {:.0}%", gizmo_size * 100.)));
+        ret = ret.push(
+            Slider::new(
+                &mut self.gizmo_size_slider,
+                MIN_GIZMO_SIZE..=MAX_GIZMO_SIZE,
+                gizmo_size,
+                Message::GizmoSizeChanged,
+            )
+            .step(0.1),
+        );
+        ret = ret.push(Text::new(
+            "Press X, Y or Z to constrain the widgets to an axis",
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Scale");
+        let scale_bar = app_state.get_scale_bar_parameters();
+        ret = ret.push(right_checkbox(
+            scale_bar.show_scale_bar,
+            "Show scale bar",
+            move |show_scale_bar| {
+                Message::ScaleBarParametersChanged(ScaleBarParameters {
+                    show_scale_bar,
+                    ..scale_bar
+                })
+            },
+            ui_size.clone(),
+        ));
+        ret = ret.push(Text::new(tr("Ruler along 2D helices")));
+        ret = ret.push(PickList::new(
+            &mut self.ruler_pick_list,
+            &ALL_RULERS[..],
+            Some(scale_bar.ruler),
+            move |ruler| {
+                Message::ScaleBarParametersChanged(ScaleBarParameters { ruler, ..scale_bar })
+            },
+        ));
+        ret = ret.push(Text::new(tr("Length unit")));
+        ret = ret.push(PickList::new(
+            &mut self.length_unit_pick_list,
+            &ALL_LENGTH_UNITS[..],
+            Some(scale_bar.length_unit),
+            move |length_unit| {
+                Message::ScaleBarParametersChanged(ScaleBarParameters {
+                    length_unit,
+                    ..scale_bar
+                })
+            },
         ));
 
         extra_jump!(ret);
@@ -76,6 +277,95 @@ impl ParametersTab {
             ui_size.clone(),
         ));
 
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Undo history");
+        let undo_limit = app_state.get_undo_limit();
+        ret = ret.push(Text::new(tr_args(
+            "Keep the last {} operations",
+            &[&undo_limit],
+        )));
+        ret = ret.push(
+            Slider::new(
+                &mut self.undo_limit_slider,
+                MIN_UNDO_LIMIT..=MAX_UNDO_LIMIT,
+                undo_limit,
+                Message::UndoLimitChanged,
+            )
+            .step(10),
+        );
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Design files");
+        ret = ret.push(right_checkbox(
+            app_state.get_binary_design_files(),
+            "Save in binary format",
+            Message::BinaryDesignFiles,
+            ui_size.clone(),
+        ));
+        ret = ret.push(right_checkbox(
+            app_state.get_confirm_large_deletions(),
+            "Confirm large deletions",
+            Message::ConfirmLargeDeletions,
+            ui_size.clone(),
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Graphics card");
+        ret = ret.push(PickList::new(
+            &mut self.gpu_preference_pick_list,
+            &ALL_GPU_PREFERENCES[..],
+            Some(app_state.get_gpu_preference()),
+            Message::GpuPreferencePicked,
+        ));
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Rendering quality");
+        let quality = app_state.get_render_quality();
+        ret = ret.push(PickList::new(
+            &mut self.multisampling_pick_list,
+            &ALL_MULTISAMPLINGS[..],
+            Some(quality.multisampling),
+            move |multisampling| {
+                Message::RenderQualityChanged(RenderQuality {
+                    multisampling,
+                    ..quality
+                })
+            },
+        ));
+        ret = ret.push(PickList::new(
+            &mut self.anisotropic_filtering_pick_list,
+            &ALL_ANISOTROPIC_FILTERINGS[..],
+            Some(quality.anisotropic_filtering),
+            move |anisotropic_filtering| {
+                Message::RenderQualityChanged(RenderQuality {
+                    anisotropic_filtering,
+                    ..quality
+                })
+            },
+        ));
+        ret = ret.push(PickList::new(
+            &mut self.texture_resolution_pick_list,
+            &ALL_TEXTURE_RESOLUTIONS[..],
+            Some(quality.texture_resolution),
+            move |texture_resolution| {
+                Message::RenderQualityChanged(RenderQuality {
+                    texture_resolution,
+                    ..quality
+                })
+            },
+        ));
+        ret = ret
+            .push(Text::new(tr("Takes effect after restarting ENSnano")).size(ui_size.main_text()));
+
+        if !self.plugins.is_empty() {
+            extra_jump!(10, ret);
+            section!(ret, ui_size, "Plugins");
+            for plugin in self.plugins.iter_mut() {
+                ret = ret.push(plugin.view(ui_size));
+                extra_jump!(ret);
+            }
+        }
+
         extra_jump!(10, ret);
         section!(ret, ui_size, "DNA/RNA model");
         ret = ret.push(PickList::new(
@@ -88,7 +378,7 @@ impl ParametersTab {
             ret = ret.push(Text::new(line));
         }
         ret = ret.push(iced::Space::with_height(Length::Units(10)));
-        ret = ret.push(Text::new("About").size(ui_size.head_text()));
+        ret = ret.push(Text::new(tr("About")).size(ui_size.head_text()));
         ret = ret.push(Text::new(format!(
             "Version {}",
             ensnano_design::ensnano_version()
@@ -112,6 +402,35 @@ impl ParametersTab {
         Scrollable::new(&mut self.scroll).push(ret).into()
     }
 
+    pub fn set_plugins(&mut self, plugins: Vec<PluginDescription>) {
+        self.plugins = plugins.into_iter().map(PluginInputs::new).collect();
+    }
+
+    pub fn update_plugin_input(&mut self, plugin_id: usize, parameter_id: usize, value: String) {
+        if let Some(value_ref) = self
+            .plugins
+            .get_mut(plugin_id)
+            .and_then(|p| p.values.get_mut(parameter_id))
+        {
+            *value_ref = value;
+        }
+    }
+
+    /// The values of the parameters of a plugin, in the order expected by the plugin.
+    pub fn plugin_values(&self, plugin_id: usize) -> Option<Vec<String>> {
+        self.plugins.get(plugin_id).map(|p| p.values.clone())
+    }
+
+    pub fn has_keyboard_priority(&self) -> bool {
+        self.plugins
+            .iter()
+            .any(|p| p.inputs.iter().any(|i| i.is_focused()))
+    }
+
+    pub fn set_ui_scale_preview(&mut self, preview: Option<u16>) {
+        self.ui_scale_preview = preview;
+    }
+
     pub fn update_scroll_request(
         &mut self,
         value_id: ValueId,
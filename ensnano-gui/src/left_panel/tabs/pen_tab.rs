@@ -16,17 +16,49 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use super::*;
+use ensnano_design::design_operations::{
+    min_renderable_radius_of_curvature, ControlPointsEdit, DEFAULT_SIMPLIFICATION_TOLERANCE,
+};
 
 const NEW_BEZIER_PLANE_ICON: LightIcon = LightIcon::HistoryEdu;
 const EDIT_BEZIER_PATH_ICON: LightIcon = LightIcon::LinearScale;
 
-#[derive(Default)]
+const DEFAULT_NB_RESAMPLED_VERTICES: f32 = 8.;
+const NB_RESAMPLED_VERTICES_RANGE: std::ops::RangeInclusive<f32> = 2.0..=64.0;
+
 pub struct PenTab {
     add_plane_btn: button::State,
     edit_path_btn: button::State,
     make_square_grid_btn: button::State,
     make_honeycomb_grid_btn: button::State,
     load_svg_btn: button::State,
+    add_helices_btn: button::State,
+    /// True if the helices added along the selected path receive a scaffold and staples
+    helices_with_strands: bool,
+    resample_btn: button::State,
+    smooth_btn: button::State,
+    simplify_btn: button::State,
+    nb_resampled_vertices_slider: slider::State,
+    nb_resampled_vertices: f32,
+}
+
+impl Default for PenTab {
+    fn default() -> Self {
+        Self {
+            add_plane_btn: Default::default(),
+            edit_path_btn: Default::default(),
+            make_square_grid_btn: Default::default(),
+            make_honeycomb_grid_btn: Default::default(),
+            load_svg_btn: Default::default(),
+            add_helices_btn: Default::default(),
+            helices_with_strands: true,
+            resample_btn: Default::default(),
+            smooth_btn: Default::default(),
+            simplify_btn: Default::default(),
+            nb_resampled_vertices_slider: Default::default(),
+            nb_resampled_vertices: DEFAULT_NB_RESAMPLED_VERTICES,
+        }
+    }
 }
 
 macro_rules! add_buttons {
@@ -74,7 +106,105 @@ macro_rules! add_grid_buttons {
     };
 }
 
+macro_rules! add_helices_along_path_buttons {
+    ($ret: ident, $self: ident, $ui_size: ident, $app_state: ident) => {
+        if let Some(path_id) = $app_state.get_selected_bezier_path() {
+            $ret = $ret.push(
+                text_btn(&mut $self.add_helices_btn, "Add helix pair", $ui_size).on_press(
+                    Message::AddHelicesAlongPath {
+                        path_id,
+                        with_strands: $self.helices_with_strands,
+                    },
+                ),
+            );
+            $ret = $ret.push(Checkbox::new(
+                $self.helices_with_strands,
+                "With scaffold and staples",
+                Message::SetHelicesAlongPathWithStrands,
+            ));
+        }
+    };
+}
+
+macro_rules! add_control_points_edition {
+    ($ret: ident, $self: ident, $ui_size: ident, $app_state: ident) => {
+        if let Some(path_id) = $app_state.get_selected_bezier_path() {
+            let nb_vertices = $self.nb_resampled_vertices.round() as usize;
+            $ret = $ret.push(Text::new(format!(
+                "{} {}",
+                tr("Number of vertices"),
+                nb_vertices
+            )));
+            $ret = $ret.push(
+                Slider::new(
+                    &mut $self.nb_resampled_vertices_slider,
+                    NB_RESAMPLED_VERTICES_RANGE,
+                    $self.nb_resampled_vertices,
+                    Message::ResampledVerticesChanged,
+                )
+                .step(1.),
+            );
+            $ret = $ret.push(
+                Row::new()
+                    .push(
+                        text_btn(&mut $self.resample_btn, "Resample", $ui_size).on_press(
+                            Message::EditBezierPathControlPoints {
+                                path_id,
+                                edit: ControlPointsEdit::Resample { nb_vertices },
+                            },
+                        ),
+                    )
+                    .push(
+                        text_btn(&mut $self.smooth_btn, "Smooth", $ui_size).on_press(
+                            Message::EditBezierPathControlPoints {
+                                path_id,
+                                edit: ControlPointsEdit::Smooth { nb_iterations: 1 },
+                            },
+                        ),
+                    )
+                    .push(
+                        text_btn(&mut $self.simplify_btn, "Simplify", $ui_size).on_press(
+                            Message::EditBezierPathControlPoints {
+                                path_id,
+                                edit: ControlPointsEdit::Simplify {
+                                    tolerance: DEFAULT_SIMPLIFICATION_TOLERANCE,
+                                },
+                            },
+                        ),
+                    )
+                    .spacing(5),
+            );
+            let parameters = $app_state.get_dna_parameters();
+            if let Some(radius) = $app_state
+                .get_reader()
+                .get_min_radius_of_curvature_along_path(path_id)
+                .filter(|r| *r < min_renderable_radius_of_curvature(&parameters))
+            {
+                $ret = $ret.push(
+                    Text::new(format!(
+                        "{} {}",
+                        tr("Curvature too high, minimum radius:"),
+                        $app_state
+                            .get_scale_bar_parameters()
+                            .length_unit
+                            .format_nm(radius as f32, &parameters)
+                    ))
+                    .color(WARNING_TEXT_COLOR),
+                );
+            }
+        }
+    };
+}
+
 impl PenTab {
+    pub fn set_helices_with_strands(&mut self, with_strands: bool) {
+        self.helices_with_strands = with_strands;
+    }
+
+    pub fn set_nb_resampled_vertices(&mut self, nb_vertices: f32) {
+        self.nb_resampled_vertices = nb_vertices;
+    }
+
     pub fn view<'a, S: AppState>(
         &'a mut self,
         ui_size: UiSize,
@@ -88,6 +218,8 @@ impl PenTab {
         );
         add_buttons!(ret, self, ui_size);
         add_grid_buttons!(ret, self, ui_size, app_state);
+        add_helices_along_path_buttons!(ret, self, ui_size, app_state);
+        add_control_points_edition!(ret, self, ui_size, app_state);
         let selected_path_id = app_state.get_selected_bezier_path();
         let path_txt = selected_path_id
             .map(|p| format!("{:?}", p))
@@ -19,8 +19,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use super::*;
 use ensnano_design::{ultraviolet::Rotor3, CurveDescriptor2D};
 use ensnano_interactor::{
-    EquadiffSolvingMethod, RevolutionSimulationParameters, RevolutionSurfaceRadius,
-    RevolutionSurfaceSystemDescriptor, RootingParameters, ShiftGenerator,
+    EquadiffSolvingMethod, RevolutionRelaxationProgress, RevolutionSimulationParameters,
+    RevolutionSurfaceRadius, RevolutionSurfaceSystemDescriptor, RootingParameters, ShiftGenerator,
     UnrootedRevolutionSurfaceDescriptor,
 };
 use iced_native::widget::{
@@ -291,6 +291,8 @@ pub(crate) struct RevolutionTab<S: AppState> {
     go_button: button::State,
     abbort_button: button::State,
     finish_button: button::State,
+    /// The advancement of the current relaxation, if any.
+    pub relaxation_progress: Option<RevolutionRelaxationProgress>,
 }
 
 impl<S: AppState> Default for RevolutionTab<S> {
@@ -331,6 +333,7 @@ impl<S: AppState> Default for RevolutionTab<S> {
             go_button: Default::default(),
             abbort_button: Default::default(),
             finish_button: Default::default(),
+            relaxation_progress: None,
         }
     }
 }
@@ -437,7 +440,7 @@ impl<S: AppState> RevolutionTab<S> {
         .placeholder("Pick..");
 
         let pick_curve_row = Row::new()
-            .push(Text::new("Curve type"))
+            .push(Text::new(tr("Curve type")))
             .push(curve_pick_list);
 
         ret = ret.push(pick_curve_row);
@@ -450,7 +453,7 @@ impl<S: AppState> RevolutionTab<S> {
         subsection!(ret, ui_size, "Revolution parameter");
 
         ret = ret.push(
-            Row::new().push(Text::new("Nb Half Turns")).push(
+            Row::new().push(Text::new(tr("Nb Half Turns"))).push(
                 self.half_turn_count
                     .input_view(RevolutionParameterId::HalfTurnCount),
             ),
@@ -464,7 +467,7 @@ impl<S: AppState> RevolutionTab<S> {
         ret = ret.push(Text::new(helix_text));
 
         ret = ret.push(
-            Row::new().push(Text::new("Nb spiral")).push(
+            Row::new().push(Text::new(tr("Nb spiral"))).push(
                 self.nb_sprial_state_input
                     .input_view(RevolutionParameterId::NbSpiral),
             ),
@@ -488,7 +491,7 @@ impl<S: AppState> RevolutionTab<S> {
         );
 
         ret = ret.push(
-            Row::new().push(Text::new("Revolution Radius")).push(
+            Row::new().push(Text::new(tr("Revolution Radius"))).push(
                 self.radius_input
                     .input_view(RevolutionParameterId::RevolutionRadius),
             ),
@@ -497,13 +500,15 @@ impl<S: AppState> RevolutionTab<S> {
         extra_jump!(ret);
         subsection!(ret, ui_size, "Discretization parameters");
         ret = ret.push(
-            Row::new().push(Text::new("Nb section per segments")).push(
-                self.nb_section_per_segment_input
-                    .input_view(RevolutionParameterId::NbSectionPerSegment),
-            ),
+            Row::new()
+                .push(Text::new(tr("Nb section per segments")))
+                .push(
+                    self.nb_section_per_segment_input
+                        .input_view(RevolutionParameterId::NbSectionPerSegment),
+                ),
         );
         ret = ret.push(
-            Row::new().push(Text::new("Target length")).push(
+            Row::new().push(Text::new(tr("Target length"))).push(
                 self.scaffold_len_target
                     .input_view(RevolutionParameterId::ScaffoldLenTarget),
             ),
@@ -512,26 +517,26 @@ impl<S: AppState> RevolutionTab<S> {
         extra_jump!(ret);
         subsection!(ret, ui_size, "Simulation parameters");
         ret = ret.push(
-            Row::new().push(Text::new("Spring Stiffness")).push(
+            Row::new().push(Text::new(tr("Spring Stiffness"))).push(
                 self.spring_stiffness
                     .input_view(RevolutionParameterId::SpringStiffness),
             ),
         );
         ret = ret.push(
-            Row::new().push(Text::new("Torsion Stiffness")).push(
+            Row::new().push(Text::new(tr("Torsion Stiffness"))).push(
                 self.torsion_stiffness
                     .input_view(RevolutionParameterId::TorsionStiffness),
             ),
         );
         ret = ret.push(
-            Row::new().push(Text::new("Fluid Friction")).push(
+            Row::new().push(Text::new(tr("Fluid Friction"))).push(
                 self.fluid_friction
                     .input_view(RevolutionParameterId::FluidFriction),
             ),
         );
         ret = ret.push(
             Row::new()
-                .push(Text::new("Ball Mass"))
+                .push(Text::new(tr("Ball Mass")))
                 .push(self.ball_mass.input_view(RevolutionParameterId::BallMass)),
         );
         let method_pick_list = PickList::new(
@@ -542,18 +547,18 @@ impl<S: AppState> RevolutionTab<S> {
         );
 
         let pick_method_row = Row::new()
-            .push(Text::new("Solving Method"))
+            .push(Text::new(tr("Solving Method")))
             .push(method_pick_list);
 
         ret = ret.push(pick_method_row);
 
         ret = ret.push(
             Row::new()
-                .push(Text::new("Time Span"))
+                .push(Text::new(tr("Time Span")))
                 .push(self.time_span.input_view(RevolutionParameterId::TimeSpan)),
         );
         ret = ret.push(
-            Row::new().push(Text::new("Simulation Step")).push(
+            Row::new().push(Text::new(tr("Simulation Step"))).push(
                 self.simulation_step
                     .input_view(RevolutionParameterId::SimulationStep),
             ),
@@ -562,18 +567,30 @@ impl<S: AppState> RevolutionTab<S> {
         extra_jump!(ret);
         section!(ret, ui_size, "Relaxation computation");
         if let SimulationState::Relaxing = app_state.get_simulation_state() {
-            let button_abbort = Button::new(&mut self.abbort_button, Text::new("Abort"))
+            let button_abbort = Button::new(&mut self.abbort_button, Text::new(tr("Abort")))
                 .on_press(Message::StopSimulation);
             ret = ret.push(button_abbort);
             extra_jump!(2, ret);
+            if let Some(progress) = self.relaxation_progress {
+                ret = ret.push(Text::new(format!(
+                    "{} {}, {} {:.4}",
+                    tr("Iteration"),
+                    progress.iteration,
+                    tr("residual"),
+                    progress.residual
+                )));
+            }
             if let Some(len) = app_state.get_reader().get_current_length_of_relaxed_shape() {
                 ret = ret.push(Text::new(format!("Current total length: {len}")));
             }
-            let button_relaxation = Button::new(&mut self.finish_button, Text::new("Finish"))
-                .on_press(Message::FinishRelaxation);
+            let button_relaxation = Button::new(
+                &mut self.finish_button,
+                Text::new(tr("Accept current shape")),
+            )
+            .on_press(Message::FinishRelaxation);
             ret = ret.push(button_relaxation);
         } else {
-            let mut button = Button::new(&mut self.go_button, Text::new("Start"));
+            let mut button = Button::new(&mut self.go_button, Text::new(tr("Start")));
             if let SimulationState::None = app_state.get_simulation_state() {
                 if desc.is_some() {
                     button = button.on_press(Message::InitRevolutionRelaxation);
@@ -18,6 +18,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use super::*;
+use ensnano_design::design_operations::RandomSequenceParameters;
+use ensnano_interactor::{i18n::tr_args, ShiftOptimizationParameters};
 
 pub struct SequenceTab {
     scroll: scrollable::State,
@@ -28,10 +30,117 @@ pub struct SequenceTab {
     scaffold_position_str: String,
     scaffold_position: usize,
     scaffold_input: text_input::State,
+    scaffold_shift_slider: slider::State,
     button_selection_from_scaffold: button::State,
     button_selection_to_scaffold: button::State,
     button_show_sequence: button::State,
     button_optimize_shift: button::State,
+    button_optimize_nicks: button::State,
+    shift_optimization: ShiftOptimizationInputs,
+    /// The smallest and largest GC content, in percents, of the random sequences
+    random_gc_range: (u32, u32),
+    random_min_gc_slider: slider::State,
+    random_max_gc_slider: slider::State,
+    /// Comma separated motifs that must not appear in the random sequences
+    forbidden_motifs: String,
+    forbidden_motifs_input: text_input::State,
+    button_fill_random_sequences: button::State,
+    button_clear_sequences: button::State,
+    button_assign_scaffold_to_selection: button::State,
+    button_audit_pairing: button::State,
+}
+
+/// The settings of the scaffold shift optimization that can be edited in the sequence tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShiftOptimizationField {
+    NbThreads,
+    RangeStart,
+    RangeEnd,
+}
+
+#[derive(Default)]
+struct ShiftOptimizationInputs {
+    nb_threads_str: String,
+    nb_threads_input: text_input::State,
+    range_start_str: String,
+    range_start_input: text_input::State,
+    range_end_str: String,
+    range_end_input: text_input::State,
+}
+
+impl ShiftOptimizationInputs {
+    fn is_focused(&self) -> bool {
+        self.nb_threads_input.is_focused()
+            || self.range_start_input.is_focused()
+            || self.range_end_input.is_focused()
+    }
+
+    fn update_from_parameters(&mut self, parameters: ShiftOptimizationParameters) {
+        self.nb_threads_str = if parameters.nb_threads == 0 {
+            String::new()
+        } else {
+            parameters.nb_threads.to_string()
+        };
+        self.range_start_str = parameters.range_start.to_string();
+        self.range_end_str = parameters
+            .range_end
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+    }
+
+    fn view<'a, S: AppState>(&'a mut self, ui_size: UiSize) -> Column<'a, Message<S>> {
+        let mut ret = Column::new();
+        let fields = [
+            (
+                ShiftOptimizationField::NbThreads,
+                "Threads",
+                "All cores",
+                &mut self.nb_threads_input,
+                &self.nb_threads_str,
+            ),
+            (
+                ShiftOptimizationField::RangeStart,
+                "Search from",
+                "0",
+                &mut self.range_start_input,
+                &self.range_start_str,
+            ),
+            (
+                ShiftOptimizationField::RangeEnd,
+                "Search to",
+                "End of sequence",
+                &mut self.range_end_input,
+                &self.range_end_str,
+            ),
+        ];
+        for (field, label, placeholder, state, value) in fields {
+            let row = Row::new()
+                .push(
+                    Text::new(tr(label))
+                        .size(ui_size.main_text())
+                        .width(Length::FillPortion(2)),
+                )
+                .push(
+                    TextInput::new(state, tr(placeholder), value, move |s| {
+                        Message::ShiftOptimizationInput(field, s)
+                    })
+                    .style(BadValue(parse_optional_usize(value).is_some()))
+                    .width(Length::FillPortion(1)),
+                );
+            ret = ret.push(row);
+        }
+        ret
+    }
+}
+
+/// Parse a number, or return `Some(None)` if the input is empty.
+fn parse_optional_usize(input: &str) -> Option<Option<usize>> {
+    let input = input.trim();
+    if input.is_empty() {
+        Some(None)
+    } else {
+        input.parse().ok().map(Some)
+    }
 }
 
 macro_rules! add_show_sequence_button {
@@ -112,7 +221,7 @@ macro_rules! add_set_scaffold_sequence_button {
     ($ret: ident, $self: ident, $ui_size: ident) => {
         let button_scaffold = Button::new(
             &mut $self.button_scaffold,
-            iced::Text::new("Set scaffold sequence"),
+            iced::Text::new(tr("Set scaffold sequence")),
         )
         .height(Length::Units($ui_size.button()))
         .on_press(Message::SetScaffoldSeqButtonPressed);
@@ -163,11 +272,65 @@ macro_rules! add_scaffold_position_input_row {
     };
 }
 
+/// The maximum number of selected staples whose sequence is shown under the scaffold shift
+/// slider
+const NB_PREVIEWED_STAPLES: usize = 5;
+
+macro_rules! add_scaffold_shift_slider {
+    ($ret: ident, $self: ident, $app_state: ident) => {
+        let sequence_length = $app_state
+            .get_reader()
+            .get_scaffold_sequence()
+            .map(|s| s.chars().filter(|c| c.is_alphabetic()).count());
+        if let Some(length) = sequence_length.filter(|l| *l > 1) {
+            if $app_state.get_scaffold_info().is_some() {
+                $ret = $ret.push(
+                    Slider::new(
+                        &mut $self.scaffold_shift_slider,
+                        0.0..=(length - 1) as f32,
+                        $self.scaffold_position as f32,
+                        |shift| {
+                            Message::ScaffoldPositionInput((shift.round() as usize).to_string())
+                        },
+                    )
+                    .step(1.),
+                );
+            }
+        }
+    };
+}
+
+macro_rules! add_staple_sequence_preview {
+    ($ret: ident, $ui_size: ident, $app_state: ident) => {
+        let reader = $app_state.get_reader();
+        let staples: Vec<usize> =
+            ensnano_interactor::extract_strands_from_selection($app_state.get_selection())
+                .into_iter()
+                .filter(|s_id| !reader.is_id_of_scaffold(*s_id))
+                .collect();
+        for s_id in staples.iter().take(NB_PREVIEWED_STAPLES) {
+            if let Some(sequence) = reader.get_strand_sequence(*s_id) {
+                $ret = $ret
+                    .push(Text::new(format!("#{}: {}", s_id, sequence)).size($ui_size.main_text()));
+            }
+        }
+        if staples.len() > NB_PREVIEWED_STAPLES {
+            $ret = $ret.push(
+                Text::new(tr_args(
+                    "and {} other staple(s)",
+                    &[&(staples.len() - NB_PREVIEWED_STAPLES)],
+                ))
+                .size($ui_size.main_text()),
+            );
+        }
+    };
+}
+
 macro_rules! add_optimize_scaffold_shift_button {
     ($ret: ident, $self: ident, $ui_size: ident) => {
         let button_scaffold = Button::new(
             &mut $self.button_optimize_shift,
-            iced::Text::new("Optimize starting position"),
+            iced::Text::new(tr("Optimize starting position")),
         )
         .height(Length::Units($ui_size.button()))
         .on_press(Message::OptimizeScaffoldShiftPressed);
@@ -175,6 +338,96 @@ macro_rules! add_optimize_scaffold_shift_button {
     };
 }
 
+macro_rules! add_optimize_nicks_button {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        let button_nicks = Button::new(
+            &mut $self.button_optimize_nicks,
+            iced::Text::new(tr("Optimize staple breakpoints")),
+        )
+        .height(Length::Units($ui_size.button()))
+        .on_press(Message::OptimizeNicksPressed);
+        $ret = $ret.push(button_nicks);
+    };
+}
+
+macro_rules! add_random_sequence_controls {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        let (min_gc, max_gc) = $self.random_gc_range;
+        $ret = $ret.push(
+            Text::new(tr_args("GC content: {}% to {}%", &[&min_gc, &max_gc]))
+                .size($ui_size.main_text()),
+        );
+        $ret = $ret.push(Slider::new(
+            &mut $self.random_min_gc_slider,
+            0..=100,
+            min_gc,
+            Message::RandomSequenceMinGc,
+        ));
+        $ret = $ret.push(Slider::new(
+            &mut $self.random_max_gc_slider,
+            0..=100,
+            max_gc,
+            Message::RandomSequenceMaxGc,
+        ));
+        $ret = $ret.push(
+            TextInput::new(
+                &mut $self.forbidden_motifs_input,
+                tr("Forbidden motifs, e.g. GAATTC, GGATCC"),
+                &$self.forbidden_motifs,
+                Message::ForbiddenMotifsInput,
+            )
+            .size($ui_size.main_text()),
+        );
+        $ret = $ret.push(
+            text_btn(
+                &mut $self.button_fill_random_sequences,
+                "Fill unassigned sequences",
+                $ui_size,
+            )
+            .on_press(Message::FillRandomSequences),
+        );
+    };
+}
+
+macro_rules! add_clear_sequences_button {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        $ret = $ret.push(
+            text_btn(
+                &mut $self.button_clear_sequences,
+                "Clear sequences",
+                $ui_size,
+            )
+            .on_press(Message::ClearSequencesPressed),
+        );
+    };
+}
+
+macro_rules! add_assign_scaffold_to_selection_button {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        $ret = $ret.push(
+            text_btn(
+                &mut $self.button_assign_scaffold_to_selection,
+                "Assign to selected domains",
+                $ui_size,
+            )
+            .on_press(Message::AssignScaffoldToSelectionPressed),
+        );
+    };
+}
+
+macro_rules! add_audit_pairing_button {
+    ($ret: ident, $self: ident, $ui_size: ident) => {
+        $ret = $ret.push(
+            text_btn(
+                &mut $self.button_audit_pairing,
+                "Check scaffold pairing",
+                $ui_size,
+            )
+            .on_press(Message::AuditScaffoldPairing),
+        );
+    };
+}
+
 macro_rules! add_scaffold_start_position {
     ($ret: ident, $ui_size: ident, $app_state: ident) => {
         let starting_nucl = $app_state
@@ -205,13 +458,15 @@ macro_rules! add_scaffold_start_position {
 
 macro_rules! add_download_staples_button {
     ($ret: ident, $self: ident, $ui_size: ident) => {
-        let button_staples =
-            Button::new(&mut $self.button_staples, iced::Text::new("Export Staples"))
-                .height(Length::Units($ui_size.button()))
-                .on_press(Message::StaplesRequested);
+        let button_staples = Button::new(
+            &mut $self.button_staples,
+            iced::Text::new(tr("Export Staples")),
+        )
+        .height(Length::Units($ui_size.button()))
+        .on_press(Message::StaplesRequested);
         let button_origamis = Button::new(
             &mut $self.button_origamis,
-            iced::Text::new("Export Origamis"),
+            iced::Text::new(tr("Export Origamis")),
         )
         .height(Length::Units($ui_size.button()))
         .on_press(Message::OrigamisRequested);
@@ -241,10 +496,22 @@ impl SequenceTab {
             scaffold_position_str: "0".to_string(),
             scaffold_position: 0,
             scaffold_input: Default::default(),
+            scaffold_shift_slider: Default::default(),
             button_selection_from_scaffold: Default::default(),
             button_selection_to_scaffold: Default::default(),
             button_show_sequence: Default::default(),
             button_optimize_shift: Default::default(),
+            button_optimize_nicks: Default::default(),
+            random_gc_range: (40, 60),
+            random_min_gc_slider: Default::default(),
+            random_max_gc_slider: Default::default(),
+            forbidden_motifs: String::new(),
+            forbidden_motifs_input: Default::default(),
+            button_fill_random_sequences: Default::default(),
+            button_clear_sequences: Default::default(),
+            button_assign_scaffold_to_selection: Default::default(),
+            button_audit_pairing: Default::default(),
+            shift_optimization: Default::default(),
         }
     }
 
@@ -263,6 +530,7 @@ impl SequenceTab {
         section!(ret, ui_size, "Sequence");
         extra_jump!(ret);
         add_show_sequence_button!(ret, self, ui_size);
+        add_clear_sequences_button!(ret, self, ui_size);
         extra_jump!(ret);
         section!(ret, ui_size, "Scaffold");
         extra_jump!(ret);
@@ -276,18 +544,55 @@ impl SequenceTab {
 
         add_set_scaffold_sequence_button!(ret, self, ui_size);
         show_current_sequence_name!(ret, self, app_state);
+        add_assign_scaffold_to_selection_button!(ret, self, ui_size);
         extra_jump!(ret);
         add_scaffold_position_input_row!(ret, self);
+        add_scaffold_shift_slider!(ret, self, app_state);
+        add_staple_sequence_preview!(ret, ui_size, app_state);
 
         add_optimize_scaffold_shift_button!(ret, self, ui_size);
+        if !self.shift_optimization.is_focused() {
+            self.shift_optimization
+                .update_from_parameters(app_state.get_shift_optimization_parameters());
+        }
+        ret = ret.push(self.shift_optimization.view(ui_size));
         add_scaffold_start_position!(ret, ui_size, app_state);
+        add_audit_pairing_button!(ret, self, ui_size);
         extra_jump!(ret);
         section!(ret, ui_size, "Staples");
         extra_jump!(ret);
+        add_optimize_nicks_button!(ret, self, ui_size);
+        add_random_sequence_controls!(ret, self, ui_size);
         add_download_staples_button!(ret, self, ui_size);
         Scrollable::new(&mut self.scroll).push(ret).into()
     }
 
+    pub fn set_random_min_gc(&mut self, min_gc: u32) {
+        self.random_gc_range = (min_gc, self.random_gc_range.1.max(min_gc));
+    }
+
+    pub fn set_random_max_gc(&mut self, max_gc: u32) {
+        self.random_gc_range = (self.random_gc_range.0.min(max_gc), max_gc);
+    }
+
+    pub fn set_forbidden_motifs(&mut self, motifs: String) {
+        self.forbidden_motifs = motifs;
+    }
+
+    pub fn get_random_sequence_parameters(&self) -> RandomSequenceParameters {
+        RandomSequenceParameters {
+            min_gc: self.random_gc_range.0 as f32 / 100.,
+            max_gc: self.random_gc_range.1 as f32 / 100.,
+            forbidden_motifs: self
+                .forbidden_motifs
+                .split(',')
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
     pub fn toggle_text_value(&mut self, b: bool) {
         self.toggle_text_value = b;
     }
@@ -303,7 +608,34 @@ impl SequenceTab {
     }
 
     pub fn has_keyboard_priority(&self) -> bool {
-        self.scaffold_input.is_focused()
+        self.scaffold_input.is_focused() || self.shift_optimization.is_focused()
+    }
+
+    /// Update the text of a setting of the scaffold shift optimization and return the new
+    /// settings if the text is valid.
+    pub fn update_shift_optimization_input(
+        &mut self,
+        field: ShiftOptimizationField,
+        input: String,
+        mut parameters: ShiftOptimizationParameters,
+    ) -> Option<ShiftOptimizationParameters> {
+        let value = parse_optional_usize(&input);
+        let inputs = &mut self.shift_optimization;
+        match field {
+            ShiftOptimizationField::NbThreads => {
+                inputs.nb_threads_str = input;
+                parameters.nb_threads = value?.unwrap_or(0);
+            }
+            ShiftOptimizationField::RangeStart => {
+                inputs.range_start_str = input;
+                parameters.range_start = value?.unwrap_or(0);
+            }
+            ShiftOptimizationField::RangeEnd => {
+                inputs.range_end_str = input;
+                parameters.range_end = value?;
+            }
+        }
+        Some(parameters)
     }
 
     fn get_candidate_scaffold(selection: &[DesignElementKey]) -> Option<usize> {
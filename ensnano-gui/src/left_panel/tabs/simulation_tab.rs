@@ -17,15 +17,33 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 
 use super::*;
+use ensnano_design::{grid::GridId, BundleId, HelixBundle, JointBody, JointKind};
+use ensnano_interactor::i18n::tr_args;
+use iced::ProgressBar;
+use std::collections::BTreeMap;
 
 pub struct SimulationTab<S: AppState> {
     rigid_body_factory: RequestFactory<RigidBodyFactory>,
     brownian_factory: RequestFactory<BrownianParametersFactory>,
     rigid_grid_button: GoStop<S>,
     rigid_helices_button: GoStop<S>,
+    rigid_bundles_button: GoStop<S>,
     scroll: scrollable::State,
     physical_simulation: PhysicalSimulation,
     reset_state: button::State,
+    roll_job_button: button::State,
+    twist_job_button: button::State,
+    relax_grids_job_button: button::State,
+    jobs: Vec<JobView>,
+    add_joint_buttons: [button::State; 3],
+    delete_joint_buttons: Vec<button::State>,
+}
+
+/// A simulation job listed in the job manager
+struct JobView {
+    info: SimulationJobInfo,
+    pause_button: button::State,
+    stop_button: button::State,
 }
 
 impl<S: AppState> SimulationTab<S> {
@@ -52,16 +70,139 @@ impl<S: AppState> SimulationTab<S> {
                 String::from("Rigid Grids"),
                 Message::RigidGridSimulation,
             ),
+            rigid_bundles_button: GoStop::new(
+                String::from("Rigid Bundles"),
+                Message::RigidBundleSimulation,
+            ),
             scroll: Default::default(),
             physical_simulation: Default::default(),
             reset_state: Default::default(),
+            roll_job_button: Default::default(),
+            add_joint_buttons: Default::default(),
+            delete_joint_buttons: Vec::new(),
+            twist_job_button: Default::default(),
+            relax_grids_job_button: Default::default(),
+            jobs: Vec::new(),
         }
     }
 
+    pub fn set_simulation_jobs(&mut self, jobs: Vec<SimulationJobInfo>) {
+        let mut old_jobs = std::mem::take(&mut self.jobs);
+        self.jobs = jobs
+            .into_iter()
+            .map(|info| {
+                if let Some(pos) = old_jobs.iter().position(|j| j.info.id == info.id) {
+                    let mut job = old_jobs.swap_remove(pos);
+                    job.info = info;
+                    job
+                } else {
+                    JobView {
+                        info,
+                        pause_button: Default::default(),
+                        stop_button: Default::default(),
+                    }
+                }
+            })
+            .collect();
+    }
+
+    fn jobs_view<'a>(jobs: &'a mut [JobView], ui_size: UiSize) -> Column<'a, Message<S>> {
+        let mut ret = Column::new().spacing(3);
+        if jobs.is_empty() {
+            ret = ret.push(Text::new(tr("No running job")).size(ui_size.main_text()));
+        }
+        for job in jobs.iter_mut() {
+            let status = match job.info.status {
+                SimulationJobStatus::Queued => tr("Queued"),
+                SimulationJobStatus::Running => tr("Running"),
+                SimulationJobStatus::Paused => tr("Paused"),
+            };
+            let mut row = Row::new().spacing(3).push(
+                Text::new(format!(
+                    "{} ({}, {} steps)",
+                    job.info.name, status, job.info.nb_steps
+                ))
+                .size(ui_size.main_text())
+                .width(Length::Fill),
+            );
+            match job.info.status {
+                SimulationJobStatus::Running => {
+                    row = row.push(
+                        text_btn(&mut job.pause_button, "Pause", ui_size)
+                            .on_press(Message::PauseSimulationJob(job.info.id, true)),
+                    )
+                }
+                SimulationJobStatus::Paused => {
+                    row = row.push(
+                        text_btn(&mut job.pause_button, "Resume", ui_size)
+                            .on_press(Message::PauseSimulationJob(job.info.id, false)),
+                    )
+                }
+                SimulationJobStatus::Queued => (),
+            }
+            row = row.push(
+                text_btn(&mut job.stop_button, "Stop", ui_size)
+                    .on_press(Message::StopSimulationJob(job.info.id)),
+            );
+            ret = ret.push(row);
+            if let Some(progress) = job.info.progress {
+                ret = ret.push(ProgressBar::new(0.0..=1.0, progress).height(Length::Units(6)));
+            }
+        }
+        ret
+    }
+
+    fn joints_view<'a>(
+        add_buttons: &'a mut [button::State; 3],
+        delete_buttons: &'a mut Vec<button::State>,
+        app_state: &S,
+        ui_size: UiSize,
+    ) -> Column<'a, Message<S>> {
+        let mut ret = Column::new().spacing(3);
+        let mut add_row = Row::new().spacing(3);
+        for (button, kind) in add_buttons.iter_mut().zip(JointKind::ALL.iter()) {
+            add_row = add_row
+                .push(text_btn(button, kind.name(), ui_size).on_press(Message::AddJoint(*kind)));
+        }
+        ret =
+            ret.push(Text::new(tr("Select two grids or helix bundles")).size(ui_size.main_text()));
+        ret = ret.push(add_row);
+
+        let reader = app_state.get_reader();
+        let joints = reader.get_joints();
+        let bundles = reader.get_helix_bundles();
+        delete_buttons.resize_with(joints.len(), Default::default);
+        for ((joint_id, joint), button) in joints.iter().zip(delete_buttons.iter_mut()) {
+            let description = tr_args(
+                "{} between {} and {}",
+                &[
+                    &tr(joint.kind.name()),
+                    &body_name(joint.bodies.0, &bundles),
+                    &body_name(joint.bodies.1, &bundles),
+                ],
+            );
+            ret = ret.push(
+                Row::new()
+                    .spacing(3)
+                    .push(
+                        Text::new(description)
+                            .size(ui_size.main_text())
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        text_btn(button, "Delete", ui_size)
+                            .on_press(Message::DeleteJoint(*joint_id)),
+                    ),
+            );
+        }
+        ret
+    }
+
     pub fn view<'a>(&'a mut self, ui_size: UiSize, app_state: &S) -> Element<'a, Message<S>> {
         let sim_state = &app_state.get_simulation_state();
         let grid_active = sim_state.is_none() || sim_state.simulating_grid();
         let roll_active = sim_state.is_none() || sim_state.is_rolling();
+        let bundles_active = sim_state.is_none() || sim_state.simulating_bundles();
         let mut ret = Column::new().spacing(5);
         section!(ret, ui_size, "Simulation (Beta)");
         ret = ret.push(self.physical_simulation.view(
@@ -75,6 +216,10 @@ impl<S: AppState> SimulationTab<S> {
                 self.rigid_grid_button
                     .view(grid_active, sim_state.simulating_grid()),
             )
+            .push(
+                self.rigid_bundles_button
+                    .view(bundles_active, sim_state.simulating_bundles()),
+            )
             .push(Self::helix_btns(
                 &mut self.rigid_helices_button,
                 &mut self.reset_state,
@@ -82,6 +227,14 @@ impl<S: AppState> SimulationTab<S> {
                 ui_size.clone(),
             ));
 
+        subsection!(ret, ui_size, "Joints");
+        ret = ret.push(Self::joints_view(
+            &mut self.add_joint_buttons,
+            &mut self.delete_joint_buttons,
+            app_state,
+            ui_size.clone(),
+        ));
+
         let volume_exclusion = self.rigid_body_factory.requestable.volume_exclusion;
         let brownian_motion = self.rigid_body_factory.requestable.brownian_motion;
         subsection!(ret, ui_size, "Parameters for helices simulation");
@@ -112,6 +265,26 @@ impl<S: AppState> SimulationTab<S> {
             ret = ret.push(view);
         }
 
+        extra_jump!(ret);
+        section!(ret, ui_size, "Background jobs");
+        ret = ret.push(
+            Row::new()
+                .spacing(3)
+                .push(
+                    text_btn(&mut self.roll_job_button, "Roll selection", ui_size)
+                        .on_press(Message::RollJob),
+                )
+                .push(
+                    text_btn(&mut self.twist_job_button, "Twist grid", ui_size)
+                        .on_press(Message::TwistJob),
+                )
+                .push(
+                    text_btn(&mut self.relax_grids_job_button, "Relax grids", ui_size)
+                        .on_press(Message::RelaxGridsJob),
+                ),
+        );
+        ret = ret.push(Self::jobs_view(&mut self.jobs, ui_size));
+
         Scrollable::new(&mut self.scroll).push(ret).into()
     }
 
@@ -180,6 +353,8 @@ impl<S: AppState> SimulationTab<S> {
         } else if app_state.get_simulation_state() == SimulationState::RigidHelices {
             self.request_stop_rigid_body_simulation(requests);
             println!("stop helices");
+        } else if app_state.get_simulation_state() == SimulationState::RigidBundles {
+            self.request_stop_rigid_body_simulation(requests);
         }
     }
 
@@ -222,6 +397,21 @@ impl PhysicalSimulation {
             roll: true,
             springs: false,
             target_helices: None,
+            target_xovers: None,
+        }
+    }
+}
+
+/// The name under which a body linked by a joint is displayed
+fn body_name(body: JointBody, bundles: &BTreeMap<BundleId, HelixBundle>) -> String {
+    match body {
+        JointBody::Grid(GridId::FreeGrid(g_id)) => tr_args("grid {}", &[&g_id]),
+        JointBody::Grid(GridId::BezierPathGrid(vertex)) => {
+            tr_args("grid {}:{}", &[&vertex.path_id.0, &vertex.vertex_id])
         }
+        JointBody::Bundle(b_id) => bundles
+            .get(&b_id)
+            .map(|bundle| bundle.name.clone())
+            .unwrap_or_else(|| tr_args("bundle {}", &[&b_id.0])),
     }
 }
@@ -0,0 +1,347 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+use super::*;
+use crate::{StapleRow, WellPosition};
+use ensnano_interactor::i18n::tr_args;
+use std::collections::HashMap;
+
+/// A column by which the rows of the staples table can be sorted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StapleColumn {
+    Id,
+    Name,
+    Length,
+    Color,
+    Pool,
+}
+
+const ALL_STAPLE_COLUMNS: [StapleColumn; 5] = [
+    StapleColumn::Id,
+    StapleColumn::Name,
+    StapleColumn::Length,
+    StapleColumn::Color,
+    StapleColumn::Pool,
+];
+
+impl StapleColumn {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Id => "Id",
+            Self::Name => "Name",
+            Self::Length => "Length",
+            Self::Color => "Color",
+            Self::Pool => "Pool",
+        }
+    }
+}
+
+#[derive(Default)]
+struct StapleRowState {
+    name_input: text_input::State,
+    color_input: text_input::State,
+}
+
+pub struct StaplesTab {
+    scroll: scrollable::State,
+    filter_input: text_input::State,
+    filter: String,
+    sort_column: StapleColumn,
+    ascending: bool,
+    header_buttons: [button::State; 5],
+    select_all_btn: button::State,
+    row_states: HashMap<usize, StapleRowState>,
+    /// The hexadecimal colors being typed, that have not been submitted yet
+    color_inputs: HashMap<usize, String>,
+    well_input: text_input::State,
+    well_query: String,
+    hide_others: bool,
+    highlight_btn: button::State,
+    /// A description of the result of the last well query
+    well_query_result: Option<String>,
+}
+
+impl Default for StaplesTab {
+    fn default() -> Self {
+        Self {
+            scroll: Default::default(),
+            filter_input: Default::default(),
+            filter: String::new(),
+            sort_column: StapleColumn::Id,
+            ascending: true,
+            header_buttons: Default::default(),
+            select_all_btn: Default::default(),
+            row_states: HashMap::new(),
+            color_inputs: HashMap::new(),
+            well_input: Default::default(),
+            well_query: String::new(),
+            hide_others: false,
+            highlight_btn: Default::default(),
+            well_query_result: None,
+        }
+    }
+}
+
+fn color_hex(color: u32) -> String {
+    format!("#{:06X}", color & 0xFF_FF_FF)
+}
+
+fn parse_color_hex(input: &str) -> Option<u32> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(|c| 0xFF_00_00_00 | c)
+}
+
+impl StaplesTab {
+    pub fn filter_changed(&mut self, filter: String) {
+        self.filter = filter;
+    }
+
+    /// Sort the table by `column`. Sorting twice by the same column reverts the order.
+    pub fn sort_by(&mut self, column: StapleColumn) {
+        if self.sort_column == column {
+            self.ascending ^= true;
+        } else {
+            self.sort_column = column;
+            self.ascending = true;
+        }
+    }
+
+    pub fn color_input(&mut self, s_id: usize, input: String) {
+        self.color_inputs.insert(s_id, input);
+    }
+
+    /// Return the color that was being typed for strand `s_id`, if it is a valid one.
+    pub fn submit_color(&mut self, s_id: usize) -> Option<u32> {
+        self.color_inputs
+            .remove(&s_id)
+            .and_then(|input| parse_color_hex(&input))
+    }
+
+    pub fn well_query_changed(&mut self, query: String) {
+        self.well_query = query;
+    }
+
+    pub fn set_hide_others(&mut self, hide_others: bool) {
+        self.hide_others = hide_others;
+    }
+
+    pub fn hide_others(&self) -> bool {
+        self.hide_others
+    }
+
+    /// Read the wells of the query, remembering the parts of the query that could not be read.
+    pub fn queried_wells(&mut self) -> Vec<WellPosition> {
+        let (wells, invalid) = WellPosition::parse_list(&self.well_query);
+        self.well_query_result = if invalid.is_empty() {
+            None
+        } else {
+            Some(tr_args("Unreadable wells: {}", &[&invalid.join(", ")]))
+        };
+        wells
+    }
+
+    pub fn set_nb_highlighted_staples(&mut self, nb_staples: usize) {
+        let found = tr_args("{} staple(s) found", &[&nb_staples]);
+        self.well_query_result = Some(if let Some(invalid) = self.well_query_result.take() {
+            format!("{}. {}", found, invalid)
+        } else {
+            found
+        });
+    }
+
+    /// The rows that match the filter, in the order in which they are displayed.
+    pub fn visible_rows(&self, rows: Vec<StapleRow>) -> Vec<StapleRow> {
+        let filter = self.filter.trim().to_lowercase();
+        let mut ret: Vec<StapleRow> = rows
+            .into_iter()
+            .filter(|row| {
+                filter.is_empty()
+                    || row.s_id.to_string() == filter
+                    || row.name.to_lowercase().contains(&filter)
+                    || color_hex(row.color).to_lowercase().contains(&filter)
+                    || [&row.pool, &row.note, &row.url].iter().any(|field| {
+                        field
+                            .as_ref()
+                            .map(|f| f.to_lowercase().contains(&filter))
+                            .unwrap_or(false)
+                    })
+            })
+            .collect();
+        match self.sort_column {
+            StapleColumn::Id => ret.sort_by_key(|row| row.s_id),
+            StapleColumn::Name => ret.sort_by(|a, b| a.name.cmp(&b.name)),
+            StapleColumn::Length => ret.sort_by_key(|row| row.length),
+            StapleColumn::Color => ret.sort_by_key(|row| row.color & 0xFF_FF_FF),
+            StapleColumn::Pool => ret.sort_by(|a, b| a.pool.cmp(&b.pool)),
+        }
+        if !self.ascending {
+            ret.reverse();
+        }
+        ret
+    }
+
+    pub fn view<'a, S: AppState>(
+        &'a mut self,
+        ui_size: UiSize,
+        app_state: &S,
+    ) -> Element<'a, Message<S>> {
+        let rows = self.visible_rows(app_state.get_reader().get_staple_rows());
+        let selected_strands =
+            ensnano_interactor::extract_strands_from_selection(app_state.get_selection());
+
+        self.row_states
+            .retain(|s_id, _| rows.iter().any(|row| row.s_id == *s_id));
+        for row in rows.iter() {
+            self.row_states.entry(row.s_id).or_default();
+        }
+
+        let mut ret = Column::new().spacing(5);
+        section!(ret, ui_size, "Staples");
+
+        ret = ret.push(
+            TextInput::new(
+                &mut self.filter_input,
+                tr("Filter"),
+                &self.filter,
+                Message::StapleFilterChanged,
+            )
+            .size(ui_size.main_text()),
+        );
+        ret = ret.push(
+            text_btn(&mut self.select_all_btn, "Select all", ui_size)
+                .on_press(Message::SelectVisibleStaples),
+        );
+
+        extra_jump!(ret);
+        subsection!(ret, ui_size, "Find by well");
+        ret = ret.push(
+            TextInput::new(
+                &mut self.well_input,
+                "B7, 2:C12",
+                &self.well_query,
+                Message::WellQueryChanged,
+            )
+            .on_submit(Message::HighlightWells)
+            .size(ui_size.main_text()),
+        );
+        ret = ret.push(
+            Row::new()
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+                .push(
+                    text_btn(&mut self.highlight_btn, "Highlight", ui_size)
+                        .on_press(Message::HighlightWells),
+                )
+                .push(Checkbox::new(
+                    self.hide_others,
+                    tr("Hide everything else"),
+                    Message::HideOthersThanWells,
+                )),
+        );
+        if let Some(result) = self.well_query_result.as_ref() {
+            ret = ret.push(Text::new(result).size(ui_size.main_text()));
+        }
+        extra_jump!(ret);
+
+        let mut header = Row::new().spacing(3);
+        for (column, state) in ALL_STAPLE_COLUMNS
+            .iter()
+            .zip(self.header_buttons.iter_mut())
+        {
+            let label = if *column == self.sort_column {
+                format!(
+                    "{} {}",
+                    tr(column.label()),
+                    if self.ascending { "▲" } else { "▼" }
+                )
+            } else {
+                tr(column.label()).to_string()
+            };
+            header = header.push(
+                Button::new(state, Text::new(label).size(ui_size.main_text()))
+                    .on_press(Message::StapleSortColumn(*column)),
+            );
+        }
+        ret = ret.push(header);
+
+        let color_inputs = &self.color_inputs;
+        let mut row_states: Vec<(usize, &mut StapleRowState)> = self
+            .row_states
+            .iter_mut()
+            .map(|(s_id, state)| (*s_id, state))
+            .collect();
+        row_states.sort_by_key(|(s_id, _)| rows.iter().position(|row| row.s_id == *s_id));
+
+        for (row, (_, state)) in rows.iter().zip(row_states.into_iter()) {
+            let s_id = row.s_id;
+            let color_str = color_inputs
+                .get(&s_id)
+                .cloned()
+                .unwrap_or_else(|| color_hex(row.color));
+            let color_valid = parse_color_hex(&color_str).is_some();
+            let [r, g, b] = [
+                (row.color >> 16) as u8,
+                (row.color >> 8) as u8,
+                row.color as u8,
+            ];
+            let table_row = Row::new()
+                .spacing(3)
+                .align_items(iced::Alignment::Center)
+                .push(Checkbox::new(
+                    selected_strands.contains(&s_id),
+                    format!("{}", s_id),
+                    move |b| Message::StapleRowSelected(s_id, b),
+                ))
+                .push(
+                    TextInput::new(
+                        &mut state.name_input,
+                        tr("Unamed strand"),
+                        &row.name,
+                        move |name| Message::StrandNameChanged(s_id, name),
+                    )
+                    .size(ui_size.main_text())
+                    .width(Length::FillPortion(3)),
+                )
+                .push(Text::new(row.length.to_string()).size(ui_size.main_text()))
+                .push(
+                    Text::new("■")
+                        .size(ui_size.main_text())
+                        .color(Color::from_rgb8(r, g, b)),
+                )
+                .push(
+                    TextInput::new(&mut state.color_input, "", &color_str, move |s| {
+                        Message::StapleColorInput(s_id, s)
+                    })
+                    .on_submit(Message::StapleColorSubmitted(s_id))
+                    .style(BadValue(color_valid))
+                    .size(ui_size.main_text())
+                    .width(Length::FillPortion(2)),
+                )
+                .push(
+                    Text::new(row.pool.clone().unwrap_or_default())
+                        .size(ui_size.main_text())
+                        .width(Length::FillPortion(2)),
+                );
+            ret = ret.push(table_row);
+        }
+
+        Scrollable::new(&mut self.scroll).push(ret).into()
+    }
+}
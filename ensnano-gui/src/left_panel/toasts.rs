@@ -0,0 +1,171 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Timed notifications displayed at the bottom of the left panel.
+//!
+//! Toasts are stacked, the most recent one being at the bottom, and disappear after
+//! [TOAST_DURATION] or when clicked. All notifications are kept in a history that can be browsed
+//! in place of the organizer.
+
+use super::*;
+use iced_native::widget::scrollable;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The duration during which a toast stays visible if it is not dismissed.
+pub const TOAST_DURATION: Duration = Duration::from_secs(6);
+const MAX_NB_TOASTS: usize = 4;
+const MAX_HISTORY_LEN: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastSeverity {
+    fn color(&self) -> Color {
+        match self {
+            Self::Info => Color::from_rgb(0.55, 0.75, 0.95),
+            Self::Warning => Color::from_rgb(0.84, 0.57, 0.20),
+            Self::Error => Color::from_rgb(0.90, 0.35, 0.35),
+        }
+    }
+}
+
+impl std::fmt::Display for ToastSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        };
+        write!(f, "{name}")
+    }
+}
+
+struct Toast {
+    severity: ToastSeverity,
+    content: String,
+    expiration: Instant,
+    button: button::State,
+}
+
+#[derive(Default)]
+pub struct ToastStack {
+    toasts: VecDeque<Toast>,
+    history: Vec<(ToastSeverity, String)>,
+    showing_history: bool,
+    history_button: button::State,
+    clear_history_button: button::State,
+    history_scroll: scrollable::State,
+}
+
+impl ToastStack {
+    pub fn push(&mut self, severity: ToastSeverity, content: String) {
+        self.history.push((severity, content.clone()));
+        if self.history.len() > MAX_HISTORY_LEN {
+            self.history.remove(0);
+        }
+        self.toasts.push_back(Toast {
+            severity,
+            content,
+            expiration: Instant::now() + TOAST_DURATION,
+            button: Default::default(),
+        });
+        while self.toasts.len() > MAX_NB_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    pub fn dismiss(&mut self, toast_idx: usize) {
+        if toast_idx < self.toasts.len() {
+            self.toasts.remove(toast_idx);
+        }
+    }
+
+    pub fn remove_expired(&mut self, now: Instant) {
+        self.toasts.retain(|t| t.expiration > now)
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.showing_history ^= true;
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Display the toasts below `organizer`, or below the notifications history if it is being
+    /// shown.
+    pub fn view<'a, S: AppState>(
+        &'a mut self,
+        organizer: Element<'a, Message<S>>,
+        ui_size: UiSize,
+    ) -> Element<'a, Message<S>> {
+        let main_content: Element<'a, Message<S>> = if self.showing_history {
+            let mut history = Column::new().spacing(2);
+            for (severity, content) in self.history.iter().rev() {
+                history = history.push(
+                    Text::new(format!("[{severity}] {content}"))
+                        .size(ui_size.main_text())
+                        .color(severity.color()),
+                );
+            }
+            Column::new()
+                .push(
+                    text_btn(&mut self.clear_history_button, "Clear", ui_size)
+                        .on_press(Message::ClearNotificationHistory),
+                )
+                .push(
+                    Scrollable::new(&mut self.history_scroll)
+                        .push(history)
+                        .height(Length::Fill),
+                )
+                .into()
+        } else {
+            organizer
+        };
+
+        let mut toasts = Column::new().spacing(3);
+        for (toast_idx, toast) in self.toasts.iter_mut().enumerate() {
+            let text = Text::new(format!("{}: {}", toast.severity, toast.content))
+                .size(ui_size.main_text());
+            toasts = toasts.push(
+                Button::new(&mut toast.button, text)
+                    .width(Length::Fill)
+                    .style(ButtonColor(toast.severity.color()))
+                    .on_press(Message::DismissToast(toast_idx)),
+            );
+        }
+        let history_label = if self.showing_history {
+            "Hide notifications"
+        } else {
+            "Notifications history"
+        };
+        toasts = toasts.push(
+            text_btn(&mut self.history_button, history_label, ui_size)
+                .on_press(Message::ToggleNotificationHistory),
+        );
+
+        Column::new()
+            .push(Container::new(main_content).height(Length::Fill))
+            .push(toasts)
+            .into()
+    }
+}
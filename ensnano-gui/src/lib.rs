@@ -32,14 +32,22 @@ pub use top_bar::TopBar;
 pub mod left_panel;
 pub use left_panel::{
     ColorOverlay, CurveDescriptorBuilder, CurveDescriptorParameter, InstanciatedParameter,
-    LeftPanel, ParameterKind, RevolutionScaling, RigidBodyParametersRequest,
+    LeftPanel, LogRecord, MemoryUsage, ParameterKind, RevolutionScaling,
+    RigidBodyParametersRequest, ToastSeverity, MAX_NB_RECORDS, OVERLAY_TITLE_BAR_HEIGHT,
 };
+mod context_menu;
 pub mod status_bar;
+pub use context_menu::{ContextMenu, ContextMenuAction, ContextMenuMessage, CONTEXT_MENU_WIDTH};
 mod ui_size;
 pub use ui_size::*;
+mod theme;
+pub use theme::Theme;
 mod material_icons_light;
-pub use ensnano_design::{grid::GridId, Camera, CameraId};
-pub use status_bar::{ClipboardContent, CurentOpState, StrandBuildingStatus};
+pub use ensnano_design::{
+    grid::{GridId, PhantomDirection, PhantomParameters},
+    Camera, CameraId,
+};
+pub use status_bar::{ClipboardContent, CurentOpState, PerformanceStats, StrandBuildingStatus};
 mod consts;
 pub use iced;
 pub use iced_graphics;
@@ -53,26 +61,44 @@ extern crate serde_derive;
 use status_bar::StatusBar;
 
 use ensnano_design::{
+    design_operations::{ControlPointsEdit, HelixNumbering, InsertionPlacementTarget},
     elements::{DesignElement, DesignElementKey, DnaAttribute},
     grid::GridTypeDescr,
-    ultraviolet, BezierPathId, BezierVertexId, HelixParameters, Nucl,
+    heat_map::HeatMapKind,
+    ultraviolet, BezierPathId, BezierVertexId, BundleId, HelixBundle, HelixParameters, Joint,
+    JointId, JointKind, Nucl, OligoPool,
 };
 use ensnano_interactor::{
-    graphics::{Background3D, DrawArea, ElementType, RenderingMode, SplitMode},
-    CheckXoversParameter, InsertionPoint, PastingStatus, Selection, SimulationState,
-    SuggestionParameters, UnrootedRevolutionSurfaceDescriptor, WidgetBasis,
+    application::AppId,
+    graphics::{
+        Background3D, BrushParameters, ColorVisionMode, DrawArea, ElementType, GpuPreference,
+        GroundParameters, NumberingParameters, RenderQuality, RenderingMode, ScaleBarParameters,
+        SplitMode, StrandEndsParameters, Thumbnail, ViewLayer, XoverDrawingParameters,
+        XoverGuideParameters,
+    },
+    plugin::PluginDescription,
+    CheckXoversParameter, InsertionPoint, PastingStatus, Selection, ShiftOptimizationParameters,
+    SimulationJobInfo, SimulationJobKind, SimulationState, SuggestionParameters, TwistAxis,
+    UnrootedRevolutionSurfaceDescriptor, WidgetBasis,
 };
 use ensnano_interactor::{
     graphics::{FogParameters, HBondDisplay},
-    RevolutionSurfaceSystemDescriptor,
+    RevolutionRelaxationProgress, RevolutionSurfaceSystemDescriptor,
+};
+use ensnano_interactor::{
+    i18n::{tr, Language},
+    operation::Operation,
+    DesignStatistics, ScaffoldInfo,
+};
+use ensnano_interactor::{
+    ActionMode, DesignBrowserChoice, DesignBrowserContent, DesignTemplate, GroupOperation,
+    HelixBlockDescriptor, HyperboloidRequest, RollRequest, SelectionMode,
 };
-use ensnano_interactor::{operation::Operation, ScaffoldInfo};
-use ensnano_interactor::{ActionMode, HyperboloidRequest, RollRequest, SelectionMode};
 pub use ensnano_organizer::OrganizerTree;
 use iced_native::Event;
 use iced_wgpu::{wgpu, Backend, Renderer, Settings, Viewport};
 use iced_winit::{conversion, program, winit, Debug, Size};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use ultraviolet::{Rotor3, Vec2, Vec3};
@@ -90,6 +116,21 @@ pub trait Requests: 'static + Send {
     fn change_strand_color(&mut self, color: u32);
     /// Change the background of the 3D scene
     fn change_3d_background(&mut self, bg: Background3D);
+    /// Set the parameters of the ground grid and contact shadow of the 3D view
+    fn set_ground_parameters(&mut self, parameters: GroundParameters);
+    /// Set the settings of `view` that override the global rendering settings
+    fn set_view_layer(&mut self, view: ElementType, layer: ViewLayer);
+    /// Set the parameters of the scale bars and of the ruler of the 2D view
+    fn set_scale_bar_parameters(&mut self, parameters: ScaleBarParameters);
+    /// Set the parameters of the positions written above the helices of the 2D view
+    fn set_numbering_parameters(&mut self, parameters: NumberingParameters);
+    /// Set the way cross-overs are drawn in the 2D view
+    fn set_xover_drawing_parameters(&mut self, parameters: XoverDrawingParameters);
+    fn set_strand_ends_parameters(&mut self, parameters: StrandEndsParameters);
+    /// Set the guide lines marking the positions where cross-overs can be made in the 2D view
+    fn set_xover_guide_parameters(&mut self, parameters: XoverGuideParameters);
+    /// Set the heat map drawn over the helices of the 2D view
+    fn set_heat_map(&mut self, heat_map: Option<HeatMapKind>);
     /// Change the rendering mode
     fn change_3d_rendering_mode(&mut self, rendering_mode: RenderingMode);
     /// Set the selected strand as the scaffold
@@ -100,16 +141,65 @@ pub trait Requests: 'static + Send {
     fn invert_scroll(&mut self, invert: bool);
     /// Resize all the 2D helices, or only the selected ones
     fn resize_2d_helices(&mut self, all: bool);
+    /// Place insertions and skips on the selected helices to achieve `target`
+    fn place_insertions(&mut self, target: InsertionPlacementTarget);
+    /// Set the roll of each selected helix to the one that minimizes the length of its
+    /// cross-overs
+    fn set_ideal_roll(&mut self);
+    /// Assign display numbers to all the helices following `scheme`
+    fn renumber_helices(&mut self, scheme: HelixNumbering);
+    /// Set the number with which `helix` is displayed. `None` displays the helix identifier.
+    fn set_helix_display_number(&mut self, helix: usize, number: Option<usize>);
+    /// Group `helices` in a new helix bundle named `name`
+    fn create_helix_bundle(&mut self, name: String, helices: Vec<usize>);
+    fn delete_helix_bundle(&mut self, bundle_id: BundleId);
+    fn rename_helix_bundle(&mut self, bundle_id: BundleId, name: String);
+    /// Add a joint between the two grids or helix bundles that contain the selection
+    fn add_joint_between_selected_bodies(&mut self, kind: JointKind);
+    fn delete_joint(&mut self, joint_id: JointId);
+    /// Set the axis around which free helices are twisted to the mean axis of the selected
+    /// helices
+    fn set_twist_axis_from_selection(&mut self);
+    /// Remove the axis around which free helices are twisted
+    fn clear_twist_axis(&mut self);
+    /// Twist the selected free helices around the current twist axis
+    fn start_helices_twist(&mut self);
     /// Make all elements of the design visible
     fn make_all_elements_visible(&mut self);
+    /// Select the strands `strands`, and hide everything else if `hide_others` is true
+    fn highlight_strands(&mut self, strands: Vec<usize>, hide_others: bool);
     /// Toggle the visibility of the selected elements
     fn toggle_visibility(&mut self, visible: bool);
+    /// Delete the selected elements
+    fn delete_selection(&mut self);
+    /// Center the camera of the views other than `app_id` on `selection`
+    fn center_selection(&mut self, selection: Selection, app_id: AppId);
+    /// Animate a glow moving along a strand from its 5' end to its 3' end in the 3D view
+    fn trace_strand(&mut self, s_id: usize);
     /// Remove empty domains in the design
     fn remove_empty_domains(&mut self);
     fn change_action_mode(&mut self, action_mode: ActionMode);
     fn change_selection_mode(&mut self, selection_mode: SelectionMode);
-    /// Switch widget basis between world and object
+    /// Switch widget basis between world, object and screen
     fn toggle_widget_basis(&mut self);
+    fn set_widget_basis(&mut self, widget_basis: WidgetBasis);
+    /// Set the scale factor applied to the translation and rotation widgets
+    fn set_gizmo_size(&mut self, size: f32);
+    /// Set the maximum number of operations that can be undone
+    fn set_undo_limit(&mut self, limit: u32);
+    /// Stop the loading or saving of the design that is running in the background
+    fn cancel_file_io(&mut self);
+    /// Save designs in binary format instead of json
+    fn set_binary_design_files(&mut self, binary: bool);
+    /// Ask for a confirmation before deleting a large number of nucleotides or cross-overs
+    fn set_confirm_large_deletions(&mut self, confirm: bool);
+    /// Set the graphics adapter that will be requested at the next startup
+    fn set_gpu_preference(&mut self, gpu_preference: GpuPreference);
+    /// Set the quality settings of the 3D scene that will be used at the next startup
+    fn set_render_quality(&mut self, render_quality: RenderQuality);
+    /// Set the number of threads and the range of positions used by the scaffold shift
+    /// optimization
+    fn set_shift_optimization_parameters(&mut self, parameters: ShiftOptimizationParameters);
     /// Show/hide the DNA sequences
     fn set_dna_sequences_visibility(&mut self, visible: bool);
     /// Download the staples as an xlsx file
@@ -129,7 +219,34 @@ pub trait Requests: 'static + Send {
     fn update_rigid_helices_simulation(&mut self, parameters: RigidBodyParametersRequest);
     /// Start of Update the rigid grids simulation
     fn update_rigid_grids_simulation(&mut self, parameters: RigidBodyParametersRequest);
+    /// Start a simulation in which the helix bundles are rigid bodies
+    fn update_rigid_bundles_simulation(&mut self, parameters: RigidBodyParametersRequest);
     fn start_twist_simulation(&mut self, grid_id: GridId);
+    /// Add a simulation to the queue of the simulations that run in the background
+    fn start_simulation_job(
+        &mut self,
+        kind: SimulationJobKind,
+        parameters: RigidBodyParametersRequest,
+    );
+    /// Stop or resume the application of the results of a simulation job to the design
+    fn pause_simulation_job(&mut self, job_id: usize, paused: bool);
+    fn stop_simulation_job(&mut self, job_id: usize);
+    /// Run a plugin with the values given by the user for its parameters
+    fn run_plugin(&mut self, plugin_id: usize, values: Vec<String>);
+    fn compare_with_last_save(&mut self);
+    /// Ask the user for a design file and highlight its differences with the current design
+    fn compare_with_design_file(&mut self);
+    fn stop_design_comparison(&mut self);
+    /// Save a snapshot of the current state of the application under a given name
+    fn create_checkpoint(&mut self, name: String);
+    /// Replace the current design by the one saved in a checkpoint
+    fn restore_checkpoint(&mut self, checkpoint_id: usize);
+    fn compare_with_checkpoint(&mut self, checkpoint_id: usize);
+    fn delete_checkpoint(&mut self, checkpoint_id: usize);
+    /// Hide, lock or recolor all the members of a group
+    fn apply_group_operation(&mut self, group_id: GroupId, operation: GroupOperation);
+    /// Start a rigid helices simulation in which only the helices of a group can move
+    fn start_group_simulation(&mut self, group_id: GroupId, parameters: RigidBodyParametersRequest);
     /// Update the parameters of the current simulation (rigid grids or helices)
     fn update_rigid_body_simulation_parameters(&mut self, parameters: RigidBodyParametersRequest);
     fn create_new_hyperboloid(&mut self, parameters: HyperboloidRequest);
@@ -170,8 +287,28 @@ pub trait Requests: 'static + Send {
     /// Show tutorial in the contextual panel
     fn show_tutorial(&mut self);
     fn new_design(&mut self);
+    /// Start the interactive tutorial from its first step
+    fn start_tutorial(&mut self);
+    /// Go to the next step of the interactive tutorial without completing the current one
+    fn skip_tutorial_step(&mut self);
+    fn stop_tutorial(&mut self);
+    /// Replace the current design by one generated from a template
+    fn new_design_from_template(&mut self, template: DesignTemplate);
     fn save_as(&mut self);
     fn save(&mut self);
+    /// Save the design next to the current file, with an incremented version number.
+    fn save_incremental_copy(&mut self);
+    /// Save the design as the next incremental copy, recording the current file, the date and
+    /// the number of operations applied since it was opened in the saved design.
+    fn save_variant(&mut self);
+    /// Enable or disable the viewer mode, in which the design cannot be modified.
+    fn set_read_only(&mut self, read_only: bool);
+    /// Display the left panel in its own window, or put it back in the main window.
+    fn toggle_detached_left_panel(&mut self);
+    fn set_theme(&mut self, theme: Theme);
+    /// Set the way colors are used to convey information in the 2D and 3D views
+    fn set_color_vision_mode(&mut self, mode: ColorVisionMode);
+    fn set_language(&mut self, language: Language);
     fn open_file(&mut self);
     /// Adjust the 2D and 3D cameras so that the design fit in screen
     fn fit_design_in_scenes(&mut self);
@@ -186,29 +323,58 @@ pub trait Requests: 'static + Send {
     fn toggle_helices_persistance_of_grid(&mut self, persistant: bool);
     /// make the spheres of the currently selected grid large/small
     fn set_small_sphere(&mut self, small: bool);
+    /// Show or hide the labels of the currently selected grids in the 3D view
+    fn set_grid_labels(&mut self, labelled: bool);
+    /// Set the length, direction and opacity of the phantom helices of the currently selected
+    /// grids
+    fn set_phantom_parameters(&mut self, parameters: PhantomParameters);
+    /// Called when the user releases a phantom parameter slider, so that the whole drag is
+    /// undone at once
+    fn finish_setting_phantom_parameters(&mut self);
     fn finish_changing_color(&mut self);
     fn stop_simulations(&mut self);
     fn reset_simulations(&mut self);
     fn reload_file(&mut self);
     fn add_double_strand_on_new_helix(&mut self, parameters: Option<(isize, usize)>);
     fn set_strand_name(&mut self, s_id: usize, name: String);
+    fn set_strand_pool(&mut self, s_id: usize, pool: Option<OligoPool>);
+    fn set_strand_note(&mut self, s_id: usize, note: Option<String>);
+    fn set_strand_url(&mut self, s_id: usize, url: Option<String>);
+    fn set_strand_color(&mut self, s_id: usize, color: u32);
     fn create_new_camera(&mut self);
     fn delete_camera(&mut self, cam_id: CameraId);
     fn select_camera(&mut self, cam_id: CameraId);
     fn set_favourite_camera(&mut self, cam_id: CameraId);
     fn update_camera(&mut self, cam_id: CameraId);
     fn set_camera_name(&mut self, cam_id: CameraId, name: String);
+    /// Exchange the positions of two cameras in the list of custom cameras
+    fn swap_cameras(&mut self, a: CameraId, b: CameraId);
+    /// Store the current rendering mode and background in a camera, or forget the ones stored in
+    /// it
+    fn store_camera_rendering(&mut self, cam_id: CameraId, store: bool);
+    /// Record the current camera and visibility as a new presentation step
+    fn add_presentation_step(&mut self);
+    fn delete_presentation_step(&mut self, step: usize);
+    fn set_presentation_step_name(&mut self, step: usize, name: String);
+    /// Display the next (if `forward` is true) or previous presentation step
+    fn go_to_presentation_step(&mut self, forward: bool);
     fn set_suggestion_parameters(&mut self, param: SuggestionParameters);
     fn set_grid_position(&mut self, grid_id: GridId, position: Vec3);
     fn set_grid_orientation(&mut self, grid_id: GridId, orientation: Rotor3);
     fn toggle_2d(&mut self);
     fn set_nb_turn(&mut self, grid_id: GridId, nb_turn: f32);
+    /// Create a rectangular block of helices on a grid, as a single undoable operation
+    fn add_helix_block(&mut self, block: HelixBlockDescriptor);
     fn set_check_xover_parameters(&mut self, paramters: CheckXoversParameter);
     fn follow_stereographic_camera(&mut self, follow: bool);
+    /// Place the 3D camera inside the design with a wide field of view
+    fn set_inside_out_inspection(&mut self, inside_out: bool);
+    fn set_brush_parameters(&mut self, parameters: BrushParameters);
     fn set_show_stereographic_camera(&mut self, show: bool);
     fn set_show_h_bonds(&mut self, show: HBondDisplay);
     fn flip_split_views(&mut self);
     fn set_rainbow_scaffold(&mut self, rainbow: bool);
+    fn set_design_metadata(&mut self, metadata: ensnano_design::DesignMetadata);
     fn set_all_helices_on_axis(&mut self, thick: bool);
     fn align_horizon(&mut self);
     fn download_origamis(&mut self);
@@ -217,12 +383,45 @@ pub trait Requests: 'static + Send {
     fn set_insertion_length(&mut self, insertion_point: InsertionPoint, length: usize);
     fn create_bezier_plane(&mut self);
     fn turn_path_into_grid(&mut self, path_id: BezierPathId, grid_type: GridTypeDescr);
+    /// Add a pair of helices along a bezier path, whose ends are snapped to the closest helices
+    fn add_helices_along_path(&mut self, path_id: BezierPathId, with_strands: bool);
+    /// Resample, smooth or simplify the vertices of a bezier path
+    fn edit_bezier_path_control_points(&mut self, path_id: BezierPathId, edit: ControlPointsEdit);
     fn set_show_bezier_paths(&mut self, show: bool);
+    fn set_show_insertion_badges(&mut self, show: bool);
     fn make_bezier_path_cyclic(&mut self, path_id: BezierPathId, cyclic: bool);
     fn set_exporting(&mut self, exporting: bool);
+    /// Answer the dialog listing the design files of a folder
+    fn choose_in_design_browser(&mut self, choice: DesignBrowserChoice);
     fn import_3d_object(&mut self);
     fn set_position_of_bezier_vertex(&mut self, vertex_id: BezierVertexId, position: Vec2);
     fn optimize_scaffold_shift(&mut self);
+    /// Propose to move the selected cross-overs to the closest positions at which they are the
+    /// shortest
+    fn respace_xovers(&mut self);
+    /// Delete the base pairs of the selected nucleotides
+    fn delete_base_pairs(&mut self);
+    /// Insert a base pair next to the base pairs of the selected nucleotides
+    fn insert_base_pairs(&mut self);
+    /// Propose to move the nicks between the staples to strengthen their seed domains
+    fn optimize_nicks(&mut self);
+    /// Remove the sequences of the selected strands, or of all the strands if no strand is
+    /// selected
+    fn clear_sequences(&mut self);
+    /// Write the bases given by the scaffold sequence only in the selected domains of the
+    /// scaffold, and remove the scaffold sequence
+    fn assign_scaffold_sequence_to_selection(&mut self);
+    /// Give random bases, satisfying `parameters`, to the nucleotides of unknown basis of the
+    /// selected strands, or of all the strands if no strand is selected
+    fn fill_random_sequences(
+        &mut self,
+        parameters: ensnano_design::design_operations::RandomSequenceParameters,
+    );
+    /// Propose to remove the strands that are empty, shorter than `min_length` nucleotides or
+    /// not paired to any other strand
+    fn cleanup_strands(&mut self, min_length: usize);
+    /// List the scaffold nucleotides that are not paired with exactly one staple nucleotide
+    fn audit_scaffold_pairing(&mut self);
     fn start_revolution_relaxation(&mut self, desc: RevolutionSurfaceSystemDescriptor);
     fn finish_revolutiion_relaxation(&mut self);
     fn load_svg(&mut self);
@@ -234,6 +433,8 @@ pub trait Requests: 'static + Send {
     /// Make a screenshot of the 3D scene.
     fn request_screenshot_3d(&mut self);
     fn request_save_nucleotides_positions(&mut self);
+    /// Write `content` to the system clipboard.
+    fn copy_to_clipboard(&mut self, content: String);
     fn notify_revolution_tab(&mut self);
     fn request_stl_export(&mut self);
 }
@@ -241,6 +442,7 @@ pub trait Requests: 'static + Send {
 #[derive(Clone, Debug, PartialEq)]
 pub enum OverlayType {
     Color,
+    ContextMenu,
 }
 
 enum GuiState<R: Requests, S: AppState> {
@@ -391,6 +593,14 @@ impl<R: Requests, S: AppState> GuiState<R, S> {
             Self::StatusBar(status_bar) => status_bar.program().has_keyboard_priority(),
         }
     }
+
+    fn get_selected_tab(&self) -> Option<usize> {
+        if let Self::LeftPanel(left_panel) = self {
+            Some(left_panel.program().get_selected_tab())
+        } else {
+            None
+        }
+    }
 }
 
 /// A Gui component.
@@ -698,6 +908,13 @@ impl<R: Requests, S: AppState> Gui<R, S> {
         self.elements.values().any(|e| e.has_keyboard_priority())
     }
 
+    /// Return the index of the tab that is currently opened in the left panel.
+    pub fn get_selected_tab(&self) -> Option<usize> {
+        self.elements
+            .get(&ElementType::LeftPanel)
+            .and_then(|e| e.state.get_selected_tab())
+    }
+
     /// Forward a message to the appropriate gui component
     pub fn forward_messages(&mut self, messages: &mut IcedMessages<S>) {
         for m in messages.top_bar.drain(..) {
@@ -881,7 +1098,7 @@ fn text_btn<'a, M: Clone>(
     } else {
         ui_size.icon()
     };
-    Button::new(state, Text::new(text).size(size)).height(Length::Units(ui_size.button()))
+    Button::new(state, Text::new(tr(text)).size(size)).height(Length::Units(ui_size.button()))
 }
 
 #[allow(clippy::needless_lifetimes)]
@@ -942,6 +1159,9 @@ pub struct IcedMessages<S: AppState> {
     application_state: S,
     last_main_state: MainState,
     redraw: bool,
+    /// The time at which the last pushed toast will expire
+    toasts_expiration: Option<std::time::Instant>,
+    last_toasts_check: std::time::Instant,
 }
 
 impl<S: AppState> IcedMessages<S> {
@@ -955,6 +1175,8 @@ impl<S: AppState> IcedMessages<S> {
             application_state: Default::default(),
             last_main_state: Default::default(),
             redraw: false,
+            toasts_expiration: None,
+            last_toasts_check: std::time::Instant::now(),
         }
     }
 
@@ -969,6 +1191,83 @@ impl<S: AppState> IcedMessages<S> {
             .push_back(status_bar::Message::Message(None));
     }
 
+    /// Show a timed notification in the left panel and add it to the notifications history
+    pub fn push_toast(&mut self, severity: ToastSeverity, message: String) {
+        self.left_panel
+            .push_back(left_panel::Message::NewToast(severity, message));
+        self.toasts_expiration = Some(std::time::Instant::now() + left_panel::TOAST_DURATION);
+    }
+
+    /// Remove the toasts that have expired. This must be called regularly for toasts to
+    /// disappear without user interaction.
+    pub fn expire_toasts(&mut self) {
+        const CHECK_PERIOD: std::time::Duration = std::time::Duration::from_millis(250);
+        if let Some(expiration) = self.toasts_expiration {
+            let now = std::time::Instant::now();
+            if now - self.last_toasts_check < CHECK_PERIOD {
+                return;
+            }
+            self.last_toasts_check = now;
+            self.left_panel
+                .push_back(left_panel::Message::ExpireToasts(now));
+            if now > expiration {
+                self.toasts_expiration = None;
+            }
+        }
+    }
+
+    pub fn push_log_records(&mut self, records: Vec<LogRecord>) {
+        self.left_panel
+            .push_back(left_panel::Message::NewLogRecords(records));
+    }
+
+    /// Set the system information that is included in the diagnostics report of the console
+    pub fn set_system_info(&mut self, system_info: String) {
+        self.left_panel
+            .push_back(left_panel::Message::SystemInfo(system_info));
+    }
+
+    pub fn set_revolution_relaxation_progress(&mut self, progress: RevolutionRelaxationProgress) {
+        self.left_panel
+            .push_back(left_panel::Message::RevolutionRelaxationProgress(progress));
+    }
+
+    pub fn set_simulation_jobs(&mut self, jobs: Vec<SimulationJobInfo>) {
+        self.left_panel
+            .push_back(left_panel::Message::SimulationJobs(jobs));
+    }
+
+    pub fn set_plugins(&mut self, plugins: Vec<PluginDescription>) {
+        self.left_panel
+            .push_back(left_panel::Message::Plugins(plugins));
+    }
+
+    /// Update the list of checkpoints displayed in the camera tab
+    pub fn set_checkpoints(&mut self, names: Vec<String>) {
+        self.left_panel
+            .push_back(left_panel::Message::Checkpoints(names));
+    }
+
+    pub fn set_camera_thumbnail(&mut self, camera_id: CameraId, thumbnail: Thumbnail) {
+        self.left_panel
+            .push_back(left_panel::Message::CameraThumbnail(camera_id, thumbnail));
+    }
+
+    /// Change the rendering mode and the background of the 3D scene as if the user had selected
+    /// them in the camera tab
+    pub fn set_3d_rendering(&mut self, rendering_mode: RenderingMode, background: Background3D) {
+        self.left_panel
+            .push_back(left_panel::Message::RenderingMode(rendering_mode));
+        self.left_panel
+            .push_back(left_panel::Message::Background3D(background));
+    }
+
+    /// Update the memory usage displayed in the console
+    pub fn set_memory_usage(&mut self, memory_usage: MemoryUsage) {
+        self.left_panel
+            .push_back(left_panel::Message::MemoryUsage(memory_usage));
+    }
+
     pub fn push_progress(&mut self, progress_name: String, progress: f32) {
         self.status_bar
             .push_back(status_bar::Message::Progress(Some((
@@ -977,6 +1276,21 @@ impl<S: AppState> IcedMessages<S> {
             ))))
     }
 
+    /// Show the performance overlay with the given measures, or hide it if `stats` is `None`
+    pub fn set_performance_stats(&mut self, stats: Option<PerformanceStats>) {
+        self.status_bar
+            .push_back(status_bar::Message::Performance(stats))
+    }
+
+    /// Display the progress of a task that can be cancelled from the status bar
+    pub fn push_cancellable_progress(&mut self, progress_name: String, progress: f32) {
+        self.status_bar
+            .push_back(status_bar::Message::CancellableProgress(Some((
+                progress_name,
+                progress,
+            ))))
+    }
+
     pub fn finish_progess(&mut self) {
         self.status_bar
             .push_back(status_bar::Message::Progress(None))
@@ -1004,6 +1318,16 @@ impl<S: AppState> IcedMessages<S> {
         self.left_panel.push_back(left_panel::Message::ForceHelp);
     }
 
+    pub fn focus_insertion_length(&mut self) {
+        self.left_panel
+            .push_back(left_panel::Message::FocusInsertionLength);
+    }
+
+    pub fn push_selected_tab(&mut self, tab: usize) {
+        self.left_panel
+            .push_back(left_panel::Message::TabSelected(tab));
+    }
+
     pub fn push_application_state(&mut self, state: S, main_state: MainState) {
         log::trace!("Old ptr {:p}, new ptr {:p}", state, self.application_state);
         self.application_state = state.clone();
@@ -1011,12 +1335,22 @@ impl<S: AppState> IcedMessages<S> {
         self.last_main_state = main_state.clone();
         let must_update = self.application_state != state || self.redraw;
         if must_update {
+            self.left_panel
+                .push_back(left_panel::Message::SetReadOnly(main_state.read_only));
+            self.left_panel
+                .push_back(left_panel::Message::TutorialStatus(
+                    main_state.tutorial.clone(),
+                ));
             self.left_panel
                 .push_back(left_panel::Message::NewApplicationState(state.clone()));
             self.top_bar
                 .push_back(top_bar::Message::NewApplicationState(top_bar_main_state(
                     &state, main_state,
                 )));
+            self.status_bar
+                .push_back(status_bar::Message::LastOperation(
+                    self.last_main_state.last_operation.clone(),
+                ));
             self.status_bar
                 .push_back(status_bar::Message::NewApplicationState(state));
         }
@@ -1040,7 +1374,28 @@ pub trait AppState:
     fn get_build_helix_mode(&self) -> ActionMode;
     fn has_double_strand_on_new_helix(&self) -> bool;
     fn get_widget_basis(&self) -> WidgetBasis;
+    fn get_gizmo_size(&self) -> f32;
+    fn get_undo_limit(&self) -> u32;
+    /// True if designs are saved in binary format instead of json
+    fn get_binary_design_files(&self) -> bool;
+    /// True if large deletions must be confirmed
+    fn get_confirm_large_deletions(&self) -> bool;
+    fn get_gpu_preference(&self) -> GpuPreference;
+    fn get_render_quality(&self) -> RenderQuality;
+    fn get_shift_optimization_parameters(&self) -> ShiftOptimizationParameters;
+    /// A description of the differences with the reference design, if designs are being
+    /// compared
+    fn get_design_diff_summary(&self) -> Option<String>;
+    fn get_ground_parameters(&self) -> GroundParameters;
+    fn get_view_layer(&self, view: ElementType) -> ViewLayer;
+    fn get_scale_bar_parameters(&self) -> ScaleBarParameters;
+    fn get_numbering_parameters(&self) -> NumberingParameters;
+    fn get_xover_drawing_parameters(&self) -> XoverDrawingParameters;
+    fn get_strand_ends_parameters(&self) -> StrandEndsParameters;
+    fn get_xover_guide_parameters(&self) -> XoverGuideParameters;
+    fn get_heat_map(&self) -> Option<HeatMapKind>;
     fn get_simulation_state(&self) -> SimulationState;
+    fn get_twist_axis(&self) -> Option<TwistAxis>;
     fn get_dna_parameters(&self) -> HelixParameters;
     fn is_building_hyperboloid(&self) -> bool;
     fn get_scaffold_info(&self) -> Option<ScaffoldInfo>;
@@ -1053,9 +1408,13 @@ pub trait AppState:
     fn get_curent_operation_state(&self) -> Option<CurentOpState>;
     fn get_strand_building_state(&self) -> Option<StrandBuildingStatus>;
     fn get_selected_group(&self) -> Option<GroupId>;
+    /// The position of the pivot of the selected group, if it has one
+    fn get_selected_group_pivot_position(&self) -> Option<Vec3>;
     fn get_suggestion_parameters(&self) -> &SuggestionParameters;
     fn get_checked_xovers_parameters(&self) -> CheckXoversParameter;
     fn follow_stereographic_camera(&self) -> bool;
+    fn inside_out_inspection(&self) -> bool;
+    fn get_brush_parameters(&self) -> BrushParameters;
     fn show_stereographic_camera(&self) -> bool;
     fn get_h_bonds_display(&self) -> HBondDisplay;
     fn get_scroll_sensitivity(&self) -> f32;
@@ -1063,8 +1422,11 @@ pub trait AppState:
     fn want_all_helices_on_axis(&self) -> bool;
     fn expand_insertions(&self) -> bool;
     fn get_show_bezier_paths(&self) -> bool;
+    fn get_show_insertion_badges(&self) -> bool;
     fn get_selected_bezier_path(&self) -> Option<BezierPathId>;
     fn is_exporting(&self) -> bool;
+    /// The content of the dialog listing design files, if it is open
+    fn get_design_browser(&self) -> Option<&DesignBrowserContent>;
     fn is_transitory(&self) -> bool;
     fn get_current_revoultion_radius(&self) -> Option<f64>;
     fn get_recommended_scaling_revolution_surface(
@@ -1073,11 +1435,17 @@ pub trait AppState:
     ) -> Option<RevolutionScaling>;
     fn get_clipboard_content(&self) -> ClipboardContent;
     fn get_pasting_status(&self) -> PastingStatus;
+    fn get_theme(&self) -> Theme;
+    fn get_color_vision_mode(&self) -> ColorVisionMode;
+    fn get_language(&self) -> Language;
 }
 
 pub trait DesignReader: 'static {
     fn grid_has_persistent_phantom(&self, g_id: GridId) -> bool;
     fn grid_has_small_spheres(&self, g_id: GridId) -> bool;
+    /// True if the lattice coordinates and helix numbers of the grid are labelled in the 3D view
+    fn grid_has_labels(&self, g_id: GridId) -> bool;
+    fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters;
     fn get_grid_shift(&self, g_id: GridId) -> Option<f32>;
     fn get_strand_length(&self, s_id: usize) -> Option<usize>;
     fn is_id_of_scaffold(&self, s_id: usize) -> bool;
@@ -1086,19 +1454,107 @@ pub trait DesignReader: 'static {
     fn get_dna_elements(&self) -> &[DesignElement];
     fn get_organizer_tree(&self) -> Option<Arc<ensnano_design::EnsnTree>>;
     fn strand_name(&self, s_id: usize) -> String;
+    fn strand_pool(&self, s_id: usize) -> Option<OligoPool>;
+    fn strand_note(&self, s_id: usize) -> Option<String>;
+    fn strand_url(&self, s_id: usize) -> Option<String>;
+    /// The rows of the staples table, i.e. all the strands except the scaffold
+    fn get_staple_rows(&self) -> Vec<StapleRow>;
+    /// The identifiers of the staples that are placed in the wells `wells` when exporting the
+    /// staples
+    fn get_strands_at_wells(&self, wells: &[WellPosition]) -> Vec<usize>;
     fn get_all_cameras(&self) -> Vec<(CameraId, &str)>;
     fn get_favourite_camera(&self) -> Option<CameraId>;
+    /// Return true if a rendering mode and background are stored in the camera
+    fn camera_has_rendering_settings(&self, cam_id: CameraId) -> bool;
+    fn get_presentation_step_names(&self) -> Vec<&str>;
+    fn get_design_statistics(&self) -> DesignStatistics;
     fn get_grid_position_and_orientation(&self, g_id: GridId) -> Option<(Vec3, Rotor3)>;
     fn get_grid_nb_turn(&self, g_id: GridId) -> Option<f32>;
     fn xover_length(&self, xover_id: usize) -> Option<(f32, Option<f32>)>;
     fn get_id_of_xover_involving_nucl(&self, nucl: Nucl) -> Option<usize>;
     fn rainbow_scaffold(&self) -> bool;
+    /// The author, description, license and notes of the design
+    fn get_design_metadata(&self) -> ensnano_design::DesignMetadata;
     fn get_insertion_length(&self, selection: &Selection) -> Option<usize>;
     fn get_insertion_point(&self, selection: &Selection) -> Option<InsertionPoint>;
     fn is_bezier_path_cyclic(&self, path_id: BezierPathId) -> Option<bool>;
+    /// The smallest radius of curvature, in nanometers, of the helices built on a bezier path
+    fn get_min_radius_of_curvature_along_path(&self, path_id: BezierPathId) -> Option<f64>;
+    fn get_helix_bundles(&self) -> BTreeMap<BundleId, HelixBundle>;
+    fn get_joints(&self) -> BTreeMap<JointId, Joint>;
     fn get_bezier_vertex_position(&self, vertex_id: BezierVertexId) -> Option<Vec2>;
     fn get_scaffold_sequence(&self) -> Option<&str>;
+    /// The sequence of a strand, with '?' for the nucleotides whose basis is not known
+    fn get_strand_sequence(&self, s_id: usize) -> Option<String>;
     fn get_current_length_of_relaxed_shape(&self) -> Option<usize>;
+    /// The twist strain accumulated along the helices that lie on a lattice
+    fn get_helix_strains(&self) -> Vec<ensnano_design::xover_strain::HelixStrain>;
+}
+
+/// A row of the staples table of the left panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StapleRow {
+    pub s_id: usize,
+    /// The name of the strand, empty if the strand has no name
+    pub name: String,
+    pub length: usize,
+    pub color: u32,
+    pub pool: Option<String>,
+    pub note: Option<String>,
+    pub url: Option<String>,
+}
+
+/// The position of a staple in the plates of the staples export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WellPosition {
+    /// The plate number, starting from 1. If `None`, the well is looked for in all plates.
+    pub plate: Option<usize>,
+    /// The well, in the "B7" format
+    pub well: String,
+}
+
+impl WellPosition {
+    /// Read a well position in the "B7", "B07", "2:B7" or "P2:B7" format.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (plate, well) = if let Some((plate, well)) = input.split_once(':') {
+            let plate = plate.trim().trim_start_matches(|c| c == 'P' || c == 'p');
+            (Some(plate.parse::<usize>().ok()?), well.trim())
+        } else {
+            (None, input)
+        };
+        let mut chars = well.chars();
+        let row = chars.next()?.to_ascii_uppercase();
+        if !('A'..='H').contains(&row) {
+            return None;
+        }
+        let column = chars.as_str().parse::<usize>().ok()?;
+        if !(1..=12).contains(&column) {
+            return None;
+        }
+        Some(Self {
+            plate,
+            well: format!("{}{}", row, column),
+        })
+    }
+
+    /// Read a list of well positions separated by spaces, commas, semicolons or new lines.
+    /// Return the positions that could be read and the pieces of the input that could not.
+    pub fn parse_list(input: &str) -> (Vec<Self>, Vec<String>) {
+        let mut wells = Vec::new();
+        let mut invalid = Vec::new();
+        for piece in input
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|s| !s.is_empty())
+        {
+            if let Some(well) = Self::parse(piece) {
+                wells.push(well)
+            } else {
+                invalid.push(piece.to_string())
+            }
+        }
+        (wells, invalid)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -1110,6 +1566,49 @@ pub struct MainState {
     pub can_split2d: bool,
     pub can_toggle_2d: bool,
     pub splited_2d: bool,
+    /// The label of the last operation that can be undone
+    pub last_operation: Option<String>,
+    /// True if the application is in viewer mode
+    pub read_only: bool,
+    /// True if the left panel is displayed in its own window
+    pub left_panel_detached: bool,
+    /// The current step of the interactive tutorial, if one is running
+    pub tutorial: Option<TutorialStatus>,
+}
+
+/// The region of the window that a step of the interactive tutorial refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialHighlight {
+    TopBar,
+    /// A tab of the left panel, identified by its index
+    LeftPanelTab(usize),
+    ContextualPanel,
+    Scene3D,
+    FlatScene,
+}
+
+impl TutorialHighlight {
+    /// A short description of the region, used in the instructions
+    pub fn description(&self) -> &'static str {
+        let ret = match self {
+            Self::TopBar => "the top bar",
+            Self::LeftPanelTab(_) => "the highlighted tab of the left panel",
+            Self::ContextualPanel => "the contextual panel",
+            Self::Scene3D => "the 3D view",
+            Self::FlatScene => "the 2D view",
+        };
+        tr(ret)
+    }
+}
+
+/// The step of the interactive tutorial that is being displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TutorialStatus {
+    /// The index of the step, starting from 0
+    pub step: usize,
+    pub nb_steps: usize,
+    pub instruction: &'static str,
+    pub highlight: TutorialHighlight,
 }
 
 fn top_bar_main_state<S: AppState>(app_state: &S, main_state: MainState) -> top_bar::MainState<S> {
@@ -1122,5 +1621,12 @@ fn top_bar_main_state<S: AppState>(app_state: &S, main_state: MainState) -> top_
         can_split2d: main_state.can_split2d,
         can_toggle_2d: main_state.can_toggle_2d,
         splited_2d: main_state.splited_2d,
+        read_only: main_state.read_only,
+        left_panel_detached: main_state.left_panel_detached,
+        highlighted: main_state
+            .tutorial
+            .as_ref()
+            .map(|t| t.highlight == TutorialHighlight::TopBar)
+            .unwrap_or(false),
     }
 }
@@ -15,10 +15,12 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use super::{AppState, Requests, UiSize};
+use super::{tr, AppState, Requests, Theme, UiSize};
+use ensnano_design::HelixParameters;
+use ensnano_interactor::graphics::LengthUnit;
 use ensnano_interactor::operation::{Operation, ParameterField};
 pub use ensnano_interactor::StrandBuildingStatus;
-use iced::{container, slider, Background, Container, Length};
+use iced::{button, container, slider, Background, Button, Container, Length, ProgressBar};
 use iced_native::{
     widget::{pick_list, text_input, PickList, TextInput},
     Color,
@@ -29,9 +31,56 @@ use iced_winit::{
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use winit::dpi::LogicalSize;
 
 const GOLD_ORANGE: iced::Color = iced::Color::from_rgb(0.84, 0.57, 0.20);
+const SCAFFOLD_TOO_LONG_RED: iced::Color = iced::Color::from_rgb(0.85, 0.2, 0.2);
+/// The fraction of the length of the scaffold sequence above which the scaffold length counter
+/// starts warning the user
+const SCAFFOLD_LENGTH_WARNING_RATIO: f64 = 0.95;
+
+/// Measures of the performances of the application, displayed in the status bar when the
+/// performance overlay is enabled.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PerformanceStats {
+    /// The average time between two frames
+    pub frame_time: Duration,
+    /// The time spent by each application to record its drawing commands
+    pub draw_times: Vec<(String, Duration)>,
+    /// The time spent rebuilding the presenter the last time the design was modified
+    pub presenter_update_time: Option<Duration>,
+    /// The number of mesh instances drawn by the 3D views
+    pub nb_instances: usize,
+    /// The time between the launch of the application and the first frame
+    pub startup_time: Option<Duration>,
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.)
+}
+
+impl PerformanceStats {
+    fn summary(&self) -> String {
+        let fps = if self.frame_time.is_zero() {
+            0.
+        } else {
+            1. / self.frame_time.as_secs_f64()
+        };
+        let mut ret = format!("Frame {} ({:.0} fps)", format_ms(self.frame_time), fps);
+        for (app, time) in self.draw_times.iter() {
+            ret.push_str(&format!(" | {app} {}", format_ms(*time)));
+        }
+        if let Some(time) = self.presenter_update_time {
+            ret.push_str(&format!(" | Rebuild {}", format_ms(time)));
+        }
+        ret.push_str(&format!(" | {} instances", self.nb_instances));
+        if let Some(time) = self.startup_time {
+            ret.push_str(&format!(" | Startup {:.2} s", time.as_secs_f64()));
+        }
+        ret
+    }
+}
 
 #[derive(Debug)]
 enum StatusParameter {
@@ -91,12 +140,18 @@ pub struct StatusBar<R: Requests, S: AppState> {
     operation: Option<OperationInput>,
     requests: Arc<Mutex<R>>,
     progress: Option<(String, f32)>,
+    /// True if the task whose progress is displayed can be cancelled
+    progress_cancellable: bool,
+    cancel_button: button::State,
     #[allow(dead_code)]
     slider_state: slider::State,
     app_state: S,
     ui_size: UiSize,
     message: Option<String>,
     logical_size: LogicalSize<f64>,
+    last_operation: Option<String>,
+    /// The measures displayed by the performance overlay, if it is enabled
+    performance: Option<PerformanceStats>,
 }
 
 impl<R: Requests, S: AppState> StatusBar<R, S> {
@@ -111,11 +166,15 @@ impl<R: Requests, S: AppState> StatusBar<R, S> {
             operation: None,
             requests,
             progress: None,
+            progress_cancellable: false,
+            cancel_button: Default::default(),
             slider_state: Default::default(),
             app_state: state.clone(),
             ui_size,
             message: None,
             logical_size,
+            last_operation: None,
+            performance: None,
         }
     }
 
@@ -136,12 +195,30 @@ impl<R: Requests, S: AppState> StatusBar<R, S> {
     }
 
     fn view_progress(&mut self) -> Row<Message<S>, iced_wgpu::Renderer> {
-        let row = Row::new();
+        let mut row = Row::new()
+            .spacing(5)
+            .align_items(iced_winit::Alignment::Center);
         let progress = self.progress.as_ref().unwrap();
-        row.push(
+        row = row.push(
             Text::new(format!("{}, {:.1}%", progress.0, progress.1 * 100.))
                 .size(self.ui_size.main_text()),
-        )
+        );
+        if self.progress_cancellable {
+            row = row
+                .push(
+                    ProgressBar::new(0.0..=1.0, progress.1)
+                        .width(Length::Units(150))
+                        .height(Length::Units(self.ui_size.main_text())),
+                )
+                .push(
+                    Button::new(
+                        &mut self.cancel_button,
+                        Text::new(tr("Cancel")).size(self.ui_size.main_text()),
+                    )
+                    .on_press(Message::CancelProgress),
+                );
+        }
+        row
     }
 
     /* TODO
@@ -153,6 +230,49 @@ impl<R: Requests, S: AppState> StatusBar<R, S> {
         row.into()
     }*/
 
+    /// A summary of the design and of the interaction state, displayed at the bottom left of the
+    /// status bar.
+    fn statistics_text(&self) -> String {
+        let statistics = self.app_state.get_reader().get_design_statistics();
+        let mut ret = format!(
+            "{statistics} | {} selected | {} / {}",
+            self.app_state.get_selection().len(),
+            self.app_state.get_action_mode(),
+            self.app_state.get_selection_mode(),
+        );
+        if let Some(label) = self.last_operation.as_ref() {
+            ret.push_str(&format!(" | Last operation: {label}"));
+        }
+        ret
+    }
+
+    /// The length of the scaffold compared to the length of the scaffold sequence, and the color
+    /// in which it must be displayed if the scaffold is almost as long or longer than its
+    /// sequence.
+    fn scaffold_length_text(&self) -> Option<(String, Option<Color>)> {
+        let scaffold_length = self.app_state.get_scaffold_info()?.length;
+        let target = self
+            .app_state
+            .get_reader()
+            .get_scaffold_sequence()
+            .map(|s| s.len());
+        if let Some(target) = target {
+            let color = if scaffold_length > target {
+                Some(SCAFFOLD_TOO_LONG_RED)
+            } else if scaffold_length as f64 >= SCAFFOLD_LENGTH_WARNING_RATIO * target as f64 {
+                Some(GOLD_ORANGE)
+            } else {
+                None
+            };
+            Some((
+                format!("Scaffold: {} / {} nt", scaffold_length, target),
+                color,
+            ))
+        } else {
+            Some((format!("Scaffold: {} nt", scaffold_length), None))
+        }
+    }
+
     pub fn has_keyboard_priority(&self) -> bool {
         self.operation
             .as_ref()
@@ -176,6 +296,8 @@ pub enum Message<S: AppState> {
     ValueStrChanged(usize, String),
     ValueSet(usize, String),
     Progress(Option<(String, f32)>),
+    CancellableProgress(Option<(String, f32)>),
+    CancelProgress,
     #[allow(dead_code)]
     SetShift(f32),
     NewApplicationState(S),
@@ -183,6 +305,8 @@ pub enum Message<S: AppState> {
     TabPressed,
     Message(Option<String>),
     Resize(LogicalSize<f64>),
+    LastOperation(Option<String>),
+    Performance(Option<PerformanceStats>),
 }
 
 impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
@@ -206,7 +330,15 @@ impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
                     }
                 }
             }
-            Message::Progress(progress) => self.progress = progress,
+            Message::Progress(progress) => {
+                self.progress = progress;
+                self.progress_cancellable = false;
+            }
+            Message::CancellableProgress(progress) => {
+                self.progress_cancellable = progress.is_some();
+                self.progress = progress;
+            }
+            Message::CancelProgress => self.requests.lock().unwrap().cancel_file_io(),
             Message::SetShift(f) => {
                 self.info_values[2] = f.to_string();
                 self.requests.lock().unwrap().update_hyperboloid_shift(f);
@@ -216,6 +348,8 @@ impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
             Message::TabPressed => self.process_tab(),
             Message::Message(message) => self.message = message,
             Message::Resize(size) => self.logical_size = size,
+            Message::LastOperation(label) => self.last_operation = label,
+            Message::Performance(stats) => self.performance = stats,
         }
         Command::none()
     }
@@ -241,7 +375,11 @@ impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
         } else if let Some(building_info) = self.app_state.get_strand_building_state() {
             self.operation = None;
             self.message = None;
-            Row::new().push(Text::new(building_info.to_info()).size(self.ui_size.main_text()))
+            let info = building_info.to_info(
+                self.app_state.get_scale_bar_parameters().length_unit,
+                &self.app_state.get_dna_parameters(),
+            );
+            Row::new().push(Text::new(info).size(self.ui_size.main_text()))
         } else if let Some(ref message) = self.message {
             self.operation = None;
             Row::new().push(Text::new(message).size(self.ui_size.main_text()))
@@ -260,8 +398,25 @@ impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
             .push(Space::with_width(Length::Units(5)))
             .align_items(iced_winit::Alignment::End);
 
-        let pasting_status_row = Row::new()
-            .push(Space::with_width(Length::Fill))
+        let bottom_left_text = if let Some(stats) = self.performance.as_ref() {
+            stats.summary()
+        } else {
+            self.statistics_text()
+        };
+        let mut pasting_status_row = Row::new()
+            .push(Space::with_width(Length::Units(5)))
+            .push(Text::new(bottom_left_text).size(self.ui_size.main_text()))
+            .push(Space::with_width(Length::Fill));
+        if let Some((scaffold_text, color)) = self.scaffold_length_text() {
+            let mut text = Text::new(scaffold_text).size(self.ui_size.main_text());
+            if let Some(color) = color {
+                text = text.color(color);
+            }
+            pasting_status_row = pasting_status_row
+                .push(text)
+                .push(Space::with_width(Length::Units(10)));
+        }
+        let pasting_status_row = pasting_status_row
             .push(Text::new(pasting_text))
             .push(Space::with_width(Length::Units(5)));
 
@@ -270,19 +425,20 @@ impl<R: Requests, S: AppState> Program for StatusBar<R, S> {
             .push(content)
             .push(pasting_status_row);
         Container::new(column)
-            .style(StatusBarStyle)
+            .style(StatusBarStyle(self.app_state.get_theme()))
             .width(Length::Units(size.width as u16))
             .height(Length::Fill)
             .into()
     }
 }
 
-struct StatusBarStyle;
+struct StatusBarStyle(Theme);
 impl container::StyleSheet for StatusBarStyle {
     fn style(&self) -> container::Style {
+        let palette = crate::theme::palette(self.0);
         container::Style {
-            background: Some(Background::Color(BACKGROUND)),
-            text_color: Some(Color::WHITE),
+            background: Some(Background::Color(palette.status_bar)),
+            text_color: Some(palette.text),
             ..container::Style::default()
         }
     }
@@ -443,7 +599,8 @@ impl OperationInput {
             }
         }
         if need_validation {
-            row = row.push(Text::new("(Press enter to validate change)").size(ui_size.main_text()));
+            row = row
+                .push(Text::new(tr("(Press enter to validate change)")).size(ui_size.main_text()));
         }
         row
     }
@@ -526,14 +683,17 @@ mod input_color {
 }
 
 trait ToInfo {
-    fn to_info(&self) -> String;
+    fn to_info(&self, length_unit: LengthUnit, helix_parameters: &HelixParameters) -> String;
 }
 
 impl ToInfo for StrandBuildingStatus {
-    fn to_info(&self) -> String {
+    fn to_info(&self, length_unit: LengthUnit, helix_parameters: &HelixParameters) -> String {
         format!(
-            "Current domain length: {} nt ({:.2} nm). 5': {}, 3': {}",
-            self.nt_length, self.nm_length, self.prime5.position, self.prime3.position
+            "Current domain length: {} nt ({}). 5': {}, 3': {}",
+            self.nt_length,
+            length_unit.format_nm(self.nm_length, helix_parameters),
+            self.prime5.position,
+            self.prime3.position
         )
     }
 }
@@ -0,0 +1,61 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! The colors used by the GUI components for each [Theme].
+
+pub use ensnano_interactor::graphics::{Theme, ALL_THEMES};
+use iced::Color;
+
+pub struct Palette {
+    pub top_bar: Color,
+    pub left_panel: Color,
+    pub status_bar: Color,
+    pub text: Color,
+}
+
+const DARK_PALETTE: Palette = Palette {
+    top_bar: crate::top_bar::BACKGROUND,
+    left_panel: crate::left_panel::BACKGROUND,
+    status_bar: crate::status_bar::BACKGROUND,
+    text: Color::WHITE,
+};
+
+const LIGHT_PALETTE: Palette = Palette {
+    top_bar: Color::from_rgb(
+        0xDD as f32 / 255.0,
+        0xE0 as f32 / 255.0,
+        0xE5 as f32 / 255.0,
+    ),
+    left_panel: Color::from_rgb(
+        0xF2 as f32 / 255.0,
+        0xF3 as f32 / 255.0,
+        0xF5 as f32 / 255.0,
+    ),
+    status_bar: Color::from_rgb(
+        0xCF as f32 / 255.0,
+        0xD6 as f32 / 255.0,
+        0xE6 as f32 / 255.0,
+    ),
+    text: Color::BLACK,
+};
+
+pub fn palette(theme: Theme) -> &'static Palette {
+    match theme {
+        Theme::Dark => &DARK_PALETTE,
+        Theme::Light => &LIGHT_PALETTE,
+    }
+}
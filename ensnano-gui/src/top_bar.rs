@@ -15,13 +15,13 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use super::{AppState, UiSize};
-use ensnano_interactor::{ActionMode, SelectionMode};
+use super::{tr, AppState, Theme, UiSize};
+use ensnano_interactor::{ActionMode, DesignTemplate, SelectionMode};
 use iced::{container, Background, Container};
 use iced_wgpu;
 use iced_winit::winit::dpi::LogicalSize;
 use iced_winit::{
-    widget::{button, Button, Row},
+    widget::{button, pick_list, Button, PickList, Row},
     Color, Command, Element, Length, Program,
 };
 use std::collections::BTreeMap;
@@ -36,6 +36,8 @@ pub struct TopBar<R: Requests, S: AppState> {
     button_add_file: button::State,
     button_save_as: button::State,
     button_save: button::State,
+    button_incremental_copy: button::State,
+    button_variant: button::State,
     button_undo: button::State,
     button_redo: button::State,
     button_3d: button::State,
@@ -49,9 +51,12 @@ pub struct TopBar<R: Requests, S: AppState> {
     button_reload: button::State,
     button_toggle_2d: button::State,
     button_new_empty_design: button::State,
+    template_pick_list: pick_list::State<DesignTemplate>,
     button_thick_helices: button::State,
     horizon_button: button::State,
     button_3d_object: button::State,
+    button_read_only: button::State,
+    button_detach_panel: button::State,
     requests: Arc<Mutex<R>>,
     logical_size: LogicalSize<f64>,
     action_mode_state: ActionModeState,
@@ -70,6 +75,10 @@ pub struct MainState<S: AppState> {
     pub can_split2d: bool,
     pub can_toggle_2d: bool,
     pub splited_2d: bool,
+    pub read_only: bool,
+    pub left_panel_detached: bool,
+    /// True if the current step of the interactive tutorial refers to the top bar
+    pub highlighted: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +88,8 @@ pub enum Message<S: AppState> {
     OpenFileButtonPressed,
     FileSaveRequested,
     SaveAsRequested,
+    IncrementalCopyRequested,
+    VariantRequested,
     Resize(LogicalSize<f64>),
     ToggleView(SplitMode),
     UiSizeChanged(UiSize),
@@ -90,6 +101,7 @@ pub enum Message<S: AppState> {
     Undo,
     Redo,
     ButtonNewEmptyDesignPressed,
+    NewDesignFromTemplate(DesignTemplate),
     ActionModeChanged(ActionMode),
     SelectionModeChanged(SelectionMode),
     Toggle2D,
@@ -97,6 +109,8 @@ pub enum Message<S: AppState> {
     FlipSplitViews,
     ThickHelices(bool),
     Import3D,
+    ToggleReadOnly,
+    ToggleDetachedPanel,
 }
 
 impl<R: Requests, S: AppState> TopBar<R, S> {
@@ -112,6 +126,8 @@ impl<R: Requests, S: AppState> TopBar<R, S> {
             button_save_as: Default::default(),
             horizon_button: Default::default(),
             button_save: Default::default(),
+            button_incremental_copy: Default::default(),
+            button_variant: Default::default(),
             button_undo: Default::default(),
             button_redo: Default::default(),
             button_2d: Default::default(),
@@ -123,10 +139,13 @@ impl<R: Requests, S: AppState> TopBar<R, S> {
             button_help: Default::default(),
             button_tutorial: Default::default(),
             button_new_empty_design: Default::default(),
+            template_pick_list: Default::default(),
             button_reload: Default::default(),
             button_toggle_2d: Default::default(),
             button_thick_helices: Default::default(),
             button_3d_object: Default::default(),
+            button_read_only: Default::default(),
+            button_detach_panel: Default::default(),
             requests,
             logical_size,
             action_mode_state: Default::default(),
@@ -163,6 +182,12 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             Message::FileSaveRequested => {
                 self.requests.lock().unwrap().save();
             }
+            Message::IncrementalCopyRequested => {
+                self.requests.lock().unwrap().save_incremental_copy();
+            }
+            Message::VariantRequested => {
+                self.requests.lock().unwrap().save_variant();
+            }
             Message::Resize(size) => self.resize(size),
             Message::ToggleView(b) => self.requests.lock().unwrap().change_split_mode(b),
             Message::UiSizeChanged(ui_size) => self.ui_size = ui_size,
@@ -174,6 +199,11 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             Message::ForceHelp => self.requests.lock().unwrap().force_help(),
             Message::ShowTutorial => self.requests.lock().unwrap().show_tutorial(),
             Message::ButtonNewEmptyDesignPressed => self.requests.lock().unwrap().new_design(),
+            Message::NewDesignFromTemplate(template) => self
+                .requests
+                .lock()
+                .unwrap()
+                .new_design_from_template(template),
             Message::Reload => self.requests.lock().unwrap().reload_file(),
             Message::SelectionModeChanged(selection_mode) => {
                 if selection_mode != self.application_state.app_state.get_selection_mode() {
@@ -205,6 +235,14 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             Message::ThickHelices(b) => self.requests.lock().unwrap().set_all_helices_on_axis(b),
             Message::AlignHorizon => self.requests.lock().unwrap().align_horizon(),
             Message::Import3D => self.requests.lock().unwrap().import_3d_object(),
+            Message::ToggleReadOnly => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_read_only(!self.application_state.read_only),
+            Message::ToggleDetachedPanel => {
+                self.requests.lock().unwrap().toggle_detached_left_panel()
+            }
         };
         Command::none()
     }
@@ -237,6 +275,15 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
         )
         .on_press(Message::ButtonNewEmptyDesignPressed);
 
+        let template_pick_list = PickList::new(
+            &mut self.template_pick_list,
+            DesignTemplate::ALL,
+            None,
+            Message::NewDesignFromTemplate,
+        )
+        .placeholder(tr("New from template"))
+        .text_size(self.ui_size.main_text());
+
         let button_add_file = Button::new(
             &mut self.button_add_file,
             light_icon(LightIcon::FolderOpen, self.ui_size),
@@ -289,6 +336,23 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             .on_press(Message::SaveAsRequested)
         };
 
+        let mut button_incremental_copy = Button::new(
+            &mut self.button_incremental_copy,
+            light_icon(LightIcon::FileCopy, self.ui_size),
+        );
+        if self.application_state.can_reload {
+            button_incremental_copy =
+                button_incremental_copy.on_press(Message::IncrementalCopyRequested);
+        }
+
+        let mut button_variant = Button::new(
+            &mut self.button_variant,
+            light_icon(LightIcon::CallSplit, self.ui_size),
+        );
+        if self.application_state.can_reload {
+            button_variant = button_variant.on_press(Message::VariantRequested);
+        }
+
         let mut button_undo = Button::new(
             &mut self.button_undo,
             dark_icon(LightIcon::Undo, self.ui_size.clone()),
@@ -360,7 +424,7 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
         }
 
         let mut button_toggle_2d =
-            Button::new(&mut self.button_toggle_2d, iced::Text::new("Toggle 2D"))
+            Button::new(&mut self.button_toggle_2d, iced::Text::new(tr("Toggle 2D")))
                 .height(Length::Units(self.ui_size.button()));
 
         if self.application_state.can_toggle_2d {
@@ -376,13 +440,38 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             button_flip_split = button_flip_split.on_press(Message::FlipSplitViews);
         }
 
-        let button_help = Button::new(&mut self.button_help, iced::Text::new("Help"))
+        let read_only_text = if self.application_state.read_only {
+            "Viewer mode"
+        } else {
+            "Edit mode"
+        };
+        let button_read_only = Button::new(
+            &mut self.button_read_only,
+            iced::Text::new(tr(read_only_text)),
+        )
+        .height(Length::Units(self.ui_size.button()))
+        .on_press(Message::ToggleReadOnly);
+
+        let detach_panel_text = if self.application_state.left_panel_detached {
+            "Attach panel"
+        } else {
+            "Detach panel"
+        };
+        let button_detach_panel = Button::new(
+            &mut self.button_detach_panel,
+            iced::Text::new(tr(detach_panel_text)),
+        )
+        .height(Length::Units(self.ui_size.button()))
+        .on_press(Message::ToggleDetachedPanel);
+
+        let button_help = Button::new(&mut self.button_help, iced::Text::new(tr("Help")))
             .height(Length::Units(self.ui_size.button()))
             .on_press(Message::ForceHelp);
 
-        let button_tutorial = Button::new(&mut self.button_tutorial, iced::Text::new("Tutorials"))
-            .height(Length::Units(self.ui_size.button()))
-            .on_press(Message::ShowTutorial);
+        let button_tutorial =
+            Button::new(&mut self.button_tutorial, iced::Text::new(tr("Tutorials")))
+                .height(Length::Units(self.ui_size.button()))
+                .on_press(Message::ShowTutorial);
 
         let app_state = &self.application_state.app_state;
         let ui_size = self.ui_size.clone();
@@ -406,6 +495,7 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             SelectionMode::Helix,
             SelectionMode::Strand,
             SelectionMode::Nucleotide,
+            SelectionMode::BasePair,
         ];
 
         let selection_buttons: Vec<_> = self
@@ -427,10 +517,13 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
             .width(Length::Fill)
             .height(Length::Units(height))
             .push(button_new_empty_design)
+            .push(template_pick_list)
             .push(button_add_file)
             .push(button_reload)
             .push(button_save)
             .push(button_save_as)
+            .push(button_incremental_copy)
+            .push(button_variant)
             .push(oxdna_tooltip)
             .push(button_3d_import)
             .push(iced::Space::with_width(Length::Units(10)))
@@ -462,6 +555,10 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
         buttons = buttons.push(iced::Space::with_width(Length::Units(10)));
 
         buttons = buttons
+            .push(button_read_only)
+            .push(iced::Space::with_width(Length::Units(2)))
+            .push(button_detach_panel)
+            .push(iced::Space::with_width(Length::Units(10)))
             .push(button_help)
             .push(iced::Space::with_width(Length::Units(2)))
             .push(button_tutorial)
@@ -475,19 +572,31 @@ impl<R: Requests, S: AppState> Program for TopBar<R, S> {
 
         Container::new(buttons)
             .width(Length::Units(self.logical_size.width as u16))
-            .style(TopBarStyle)
+            .style(TopBarStyle {
+                theme: self.application_state.app_state.get_theme(),
+                highlighted: self.application_state.highlighted,
+            })
             .into()
     }
 }
 
-struct TopBarStyle;
+struct TopBarStyle {
+    theme: Theme,
+    highlighted: bool,
+}
 impl container::StyleSheet for TopBarStyle {
     fn style(&self) -> container::Style {
-        container::Style {
-            background: Some(Background::Color(BACKGROUND)),
-            text_color: Some(Color::WHITE),
+        let palette = crate::theme::palette(self.theme);
+        let mut style = container::Style {
+            background: Some(Background::Color(palette.top_bar)),
+            text_color: Some(palette.text),
             ..container::Style::default()
+        };
+        if self.highlighted {
+            style.border_color = crate::consts::TUTORIAL_HIGHLIGHT_COLOR;
+            style.border_width = crate::consts::TUTORIAL_HIGHLIGHT_WIDTH;
         }
+        style
     }
 }
 
@@ -512,6 +621,7 @@ struct SelectionModeState {
     pub nucleotide: button::State,
     pub strand: button::State,
     pub helix: button::State,
+    pub base_pair: button::State,
 }
 
 impl SelectionModeState {
@@ -520,6 +630,7 @@ impl SelectionModeState {
         ret.insert(SelectionMode::Nucleotide, &mut self.nucleotide);
         ret.insert(SelectionMode::Strand, &mut self.strand);
         ret.insert(SelectionMode::Helix, &mut self.helix);
+        ret.insert(SelectionMode::BasePair, &mut self.base_pair);
         ret
     }
 }
@@ -592,13 +703,16 @@ fn selection_mode_btn<'a, S: AppState>(
     fixed_mode: SelectionMode,
     button_size: u16,
 ) -> Button<'a, Message<S>, iced_wgpu::Renderer> {
-    let icon_path = if fixed_mode == mode {
-        mode.icon_on()
+    // There is no icon for the base pair selection mode
+    let content: Element<'a, Message<S>, iced_wgpu::Renderer> = if mode == SelectionMode::BasePair {
+        iced::Text::new("bp").into()
+    } else if fixed_mode == mode {
+        Image::new(mode.icon_on()).into()
     } else {
-        mode.icon_off()
+        Image::new(mode.icon_off()).into()
     };
 
-    Button::new(state, Image::new(icon_path))
+    Button::new(state, content)
         .on_press(Message::SelectionModeChanged(mode))
         .style(ButtonStyle(fixed_mode == mode))
         .width(Length::Units(button_size))
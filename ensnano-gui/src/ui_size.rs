@@ -15,70 +15,104 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-pub const ALL_UI_SIZE: [UiSize; 3] = [UiSize::Small, UiSize::Medium, UiSize::Large];
-
 use serde_derive::{Deserialize, Serialize};
 
+/// The smallest scale of the user interface, in percent.
+pub const MIN_UI_SCALE: u16 = 75;
+/// The largest scale of the user interface, in percent.
+pub const MAX_UI_SCALE: u16 = 200;
+
+/// The scale of the fonts, icons and panels of the user interface.
+///
+/// Sizes are expressed in logical pixels, so the scale factor of the OS is applied on top of
+/// this one.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Copy)]
-pub enum UiSize {
+#[serde(from = "UiSizeRepr", into = "UiSizeRepr")]
+pub struct UiSize {
+    percent: u16,
+}
+
+impl Default for UiSize {
+    fn default() -> Self {
+        Self { percent: 100 }
+    }
+}
+
+/// The representation of a [UiSize] in the preferences file.
+///
+/// Older versions stored one of three presets instead of a scale.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum UiSizeRepr {
+    Percent(u16),
+    Preset(UiSizePreset),
+}
+
+#[derive(Serialize, Deserialize)]
+enum UiSizePreset {
     Small,
     Medium,
     Large,
 }
 
-impl Default for UiSize {
-    fn default() -> Self {
-        Self::Medium
+impl From<UiSizeRepr> for UiSize {
+    fn from(repr: UiSizeRepr) -> Self {
+        match repr {
+            UiSizeRepr::Percent(percent) => Self::from_percent(percent),
+            UiSizeRepr::Preset(UiSizePreset::Small) => Self::from_percent(75),
+            UiSizeRepr::Preset(UiSizePreset::Medium) => Self::from_percent(100),
+            UiSizeRepr::Preset(UiSizePreset::Large) => Self::from_percent(125),
+        }
+    }
+}
+
+impl From<UiSize> for UiSizeRepr {
+    fn from(ui_size: UiSize) -> Self {
+        Self::Percent(ui_size.percent)
     }
 }
 
 impl UiSize {
-    pub fn smaller_text(&self) -> u16 {
-        match self {
-            Self::Small => 10,
-            Self::Medium => 12,
-            Self::Large => 16,
+    pub fn from_percent(percent: u16) -> Self {
+        Self {
+            percent: percent.clamp(MIN_UI_SCALE, MAX_UI_SCALE),
         }
     }
 
+    pub fn percent(&self) -> u16 {
+        self.percent
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.percent as f64 / 100.
+    }
+
+    fn scaled(&self, size: u16) -> u16 {
+        (size as f64 * self.scale()).round() as u16
+    }
+
+    pub fn smaller_text(&self) -> u16 {
+        self.scaled(12)
+    }
+
     pub fn main_text(&self) -> u16 {
-        match self {
-            Self::Small => 12,
-            Self::Medium => 16,
-            Self::Large => 20,
-        }
+        self.scaled(16)
     }
 
     pub fn head_text(&self) -> u16 {
-        match self {
-            Self::Small => 18,
-            Self::Medium => 24,
-            Self::Large => 30,
-        }
+        self.scaled(24)
     }
 
     pub fn intermediate_text(&self) -> u16 {
-        match self {
-            Self::Small => 15,
-            Self::Medium => 20,
-            Self::Large => 25,
-        }
+        self.scaled(20)
     }
 
     pub fn icon(&self) -> u16 {
-        match self {
-            Self::Small => 14,
-            Self::Medium => 20,
-            Self::Large => 30,
-        }
+        self.scaled(20)
     }
 
     pub fn checkbox(&self) -> u16 {
-        match self {
-            Self::Small => 15,
-            Self::Medium => 15,
-            Self::Large => 15,
-        }
+        15
     }
 
     pub fn button(&self) -> u16 {
@@ -92,11 +126,6 @@ impl UiSize {
 
 impl std::fmt::Display for UiSize {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ret = match self {
-            UiSize::Small => "Small",
-            UiSize::Medium => "Medium",
-            UiSize::Large => "Large",
-        };
-        write!(f, "{}", ret)
+        write!(f, "{}%", self.percent)
     }
 }
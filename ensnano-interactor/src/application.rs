@@ -24,6 +24,7 @@ use ensnano_design::group_attributes::GroupPivot;
 use ensnano_design::Nucl;
 use iced_wgpu::wgpu;
 use iced_winit::winit;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 pub use std::time::Duration;
 use ultraviolet::{Rotor3, Vec3};
@@ -33,7 +34,7 @@ use winit::{
     event::{ModifiersState, WindowEvent},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Camera3D {
     pub position: Vec3,
     pub orientation: Rotor3,
@@ -50,6 +51,13 @@ impl Default for Camera3D {
     }
 }
 
+/// The position of the camera of the 2D view.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Camera2DState {
+    pub center: [f32; 2],
+    pub zoom: f32,
+}
+
 pub trait Application {
     type AppState;
     /// For notification about the data
@@ -78,11 +86,24 @@ pub trait Application {
     fn get_camera(&self) -> Option<Arc<(Camera3D, f32)>> {
         None
     }
+    fn get_camera_2d(&self) -> Option<Camera2DState> {
+        None
+    }
     fn get_current_selection_pivot(&self) -> Option<GroupPivot> {
         None
     }
 
     fn is_splited(&self) -> bool;
+
+    /// The number of mesh instances drawn by the application, if it draws instanced meshes
+    fn get_nb_instances(&self) -> Option<usize> {
+        None
+    }
+
+    /// A small PNG image of the current view, embedded in the design files when they are saved
+    fn render_design_thumbnail(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -96,6 +117,7 @@ pub enum Notification {
     /// The 3d camera must face a given target
     CameraTarget((Vec3, Vec3)),
     TeleportCamera(Camera3D),
+    TeleportCamera2D(Camera2DState),
     CameraRotation(f32, f32, f32),
     Centering(Nucl, usize),
     CenterSelection(Selection, AppId),
@@ -112,6 +134,11 @@ pub enum Notification {
     ScreenShot3D(Option<Arc<Path>>),
     SaveNucleotidesPositions(Option<Arc<Path>>),
     StlExport(Option<Arc<Path>>),
+    /// Render a thumbnail of the current point of view for the custom camera with the given id
+    CameraThumbnail(ensnano_design::CameraId),
+    /// Animate a glow moving along the strand with the given identifier, from its 5' end to its
+    /// 3' end
+    TraceStrand(usize),
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -42,11 +42,29 @@ pub const NB_SECTOR_CIRCLE: u16 = 36;
 pub const CANDIDATE_SCALE_FACTOR: f32 = 1.3;
 pub const SELECT_SCALE_FACTOR: f32 = 1. + 2. * (CANDIDATE_SCALE_FACTOR - 1.);
 pub const MIN_RADIUS_FOR_FAKE_UPSCALING: f32 = 0.5;
+/// The smallest radius of the tubes drawn in the picking textures, so that thin bonds can be
+/// clicked
+pub const MIN_FAKE_TUBE_RADIUS: f32 = 0.15;
+/// The largest distance in pixels between a click and the element that it selects
+pub const PICKING_RADIUS: i32 = 5;
 pub const PIVOT_SCALE_FACTOR: f32 = 1.2 * SELECT_SCALE_FACTOR;
 pub const FREE_XOVER_SCALE_FACTOR: f32 = 1.25 * SELECT_SCALE_FACTOR;
 
 pub const CLONE_OPACITY: f32 = 0.7;
 
+/// The smallest scale factor that can be applied to the translation and rotation widgets.
+pub const MIN_GIZMO_SIZE: f32 = 0.5;
+/// The largest scale factor that can be applied to the translation and rotation widgets.
+pub const MAX_GIZMO_SIZE: f32 = 3.;
+
+/// The default maximum number of operations that can be undone.
+pub const DEFAULT_UNDO_LIMIT: u32 = 200;
+pub const MIN_UNDO_LIMIT: u32 = 10;
+pub const MAX_UNDO_LIMIT: u32 = 1000;
+/// Consecutive operations with the same label that are separated by less than this delay are
+/// undone together.
+pub const UNDO_COALESCING_DELAY: std::time::Duration = std::time::Duration::from_millis(800);
+
 pub const RIGHT_HANDLE_ID: u32 = 0;
 pub const UP_HANDLE_ID: u32 = 1;
 pub const DIR_HANDLE_ID: u32 = 2;
@@ -114,12 +132,66 @@ pub const HELIX_BORDER_COLOR: u32 = 0xFF_101010;
 pub const CANDIDATE_COLOR: u32 = 0xBF_00_FF_00;
 pub const SELECTED_COLOR: u32 = 0xBF_FF_00_00;
 pub const SUGGESTION_COLOR: u32 = 0xBF_FF_00_FF;
+/// The distance, in nanometers, between the middle of a suggested cross-over drawn as an arc and
+/// the middle of its extremities
+pub const SUGGESTION_ARC_BULGE: f32 = 0.6;
+/// The number of dashes of the suggested cross-overs drawn as dashed arcs
+pub const SUGGESTION_ARC_NB_DASHES: usize = 4;
+/// The Okabe-Ito palette ordered by hue, without the yellow and the black that are used for the
+/// selection and the candidates in colorblind-safe mode.
+pub const CVD_SAFE_PALETTE: [u32; 6] = [
+    0xD5_5E_00, // Vermillion
+    0xE6_9F_00, // Orange
+    0x00_9E_73, // Bluish green
+    0x56_B4_E9, // Sky blue
+    0x00_72_B2, // Blue
+    0xCC_79_A7, // Reddish purple
+];
+pub const CVD_SELECTED_COLOR: u32 = 0xFF_F0_E4_42; // Yellow
+pub const CVD_CANDIDATE_COLOR: u32 = 0xFF_00_00_00; // Black
+/// Colors that are less saturated than this are not remapped in colorblind-safe mode
+pub const CVD_MIN_SATURATION: f64 = 0.2;
+/// Colors that are darker than this are not remapped in colorblind-safe mode
+pub const CVD_MIN_VALUE: f64 = 0.15;
 pub const PIVOT_SPHERE_COLOR: u32 = 0xBF_FF_FF_00;
 pub const SURFACE_PIVOT_SPHERE_COLOR: u32 = 0xBF_FF_14_B9; // pinkish
 pub const FREE_XOVER_COLOR: u32 = 0xBF_00_00_FF;
+/// Color of the strands that are not in the reference design when comparing designs
+pub const DIFF_ADDED_COLOR: u32 = 0x2E_CC_40; // Green
+/// Color of the strands that differ from the reference design when comparing designs
+pub const DIFF_MODIFIED_COLOR: u32 = 0xFF_85_1B; // Orange
+pub const DIFF_UNCHANGED_COLOR: u32 = 0xB0_B0_B0; // Light grey
 pub const CHECKED_XOVER_COLOR: u32 = 0xBF_3C_B3_71; //Medium sea green
 pub const UNCHECKED_XOVER_COLOR: u32 = 0xCF_FF_14_93; // Deep pink
 pub const STEREOGRAPHIC_SPHERE_COLOR: u32 = 0xDD_2F_4F_4F; // Slate grey
+pub const GHOST_HELIX_COLOR: u32 = 0x80_4C_AF_F0;
+pub const HELIX_LENGTH_HANDLE_COLOR: u32 = 0xFF_A5_00; // Orange
+pub const HELIX_LENGTH_HANDLE_RADIUS: f32 = 2.5 * SPHERE_RADIUS;
+pub const HELIX_ROLL_DIAL_COLOR: u32 = 0x1E_90_FF; // Dodger blue
+pub const HELIX_ROLL_DIAL_KNOB_RADIUS: f32 = 2. * SPHERE_RADIUS;
+pub const HELIX_ROLL_DIAL_WIDTH: f32 = 0.3 * BOND_RADIUS;
+pub const TWIST_AXIS_COLOR: u32 = 0x9A_CD_32; // Yellow green
+pub const TWIST_AXIS_HANDLE_RADIUS: f32 = 2.5 * SPHERE_RADIUS;
+pub const TWIST_AXIS_WIDTH: f32 = 0.5 * BOND_RADIUS;
+pub const JOINT_COLOR: u32 = 0xFF_8C_00; // Dark orange
+pub const JOINT_RADIUS: f32 = 3. * SPHERE_RADIUS;
+pub const JOINT_AXIS_WIDTH: f32 = 1.5 * BOND_RADIUS;
+/// Length of the axis drawn for hinge joints, sliders are drawn with a rail twice as long
+pub const JOINT_AXIS_LENGTH: f32 = 4.;
+/// Ratio between the radius of the strand end glyphs and the radius of the nucleotides
+pub const STRAND_END_GLYPH_SCALE: f32 = 1.6;
+pub const INSERTION_BADGE_COLOR: u32 = 0xFF_FF_FF;
+pub const INSERTION_BADGE_RADIUS: f32 = 1.2 * SPHERE_RADIUS;
+/// Distance between an insertion and the badge showing its length
+pub const INSERTION_BADGE_OFFSET: f32 = 0.8;
+/// Color of the glow moving along a traced strand, its alpha fades along the tail of the glow
+pub const STRAND_TRACE_COLOR: u32 = 0xFF_FF_F5_9D; // Light yellow
+/// The number of nucleotides covered by the glow moving along a traced strand
+pub const STRAND_TRACE_TAIL_LENGTH: usize = 12;
+/// The speed, in nucleotides per second, of the glow moving along a traced strand
+pub const STRAND_TRACE_SPEED: f32 = 80.;
+/// The maximum duration, in seconds, of a strand trace. Long strands are traced faster.
+pub const MAX_STRAND_TRACE_DURATION: f32 = 20.;
 pub const STEREOGRAPHIC_SPHERE_RADIUS: f32 = 2.;
 
 pub const MAX_ZOOM_2D: f32 = 50.0;
@@ -189,9 +261,13 @@ pub const CYM_HANDLE_COLORS: [u32; 3] = [0x00FFFF, 0xFF00FF, 0xFFFF00];
 pub const ORIGAMI_EXTENSION: &str = "origami";
 pub const ENS_EXTENSION: &str = "ens";
 pub const ENS_BACKUP_EXTENSION: &str = "ensbackup";
+pub const ENS_SESSION_EXTENSION: &str = "enssession";
 pub const ENS_UNNAMED_FILE_NAME: &str = "Unnamed_design";
 pub const CANNOT_OPEN_DEFAULT_DIR: &str = "Unable to open document or home directory.
 No backup will be saved for this unnamed design";
+pub const SAVE_CANCELLED: &str = "Saving cancelled";
+pub const FILE_OPERATION_IN_PROGRESS: &str =
+    "Another file is being loaded or saved, please wait for it to finish";
 
 pub const NO_DESIGN_TITLE: &str = "New file";
 
@@ -253,7 +329,7 @@ pub const GREY_UNKNOWN_NUCL_VEC4: Vec4 = Vec4 {
 
 pub const PRINTABLE_CHARS: &[char] = &[
     'A', 'T', 'G', 'C', 'N', 'K', 'U', 'X', 'S', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
-    '-', 'n', 't', 'm', '.', '/', ' ', '(', ')', '?',
+    '-', 'n', 't', 'm', '.', '/', ' ', '(', ')', '?', 'b', 'p',
 ];
 pub const NB_PRINTABLE_CHARS: usize = PRINTABLE_CHARS.len();
 
@@ -0,0 +1,68 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The content of the dialog in which the design files of a folder are listed before one of them
+//! is opened.
+
+use ensnano_design::DesignMetadata;
+use std::path::PathBuf;
+
+/// A summary of a design file, read without opening the design.
+#[derive(Clone, Debug)]
+pub struct DesignFileInfo {
+    pub path: PathBuf,
+    /// The date of the last modification of the file, formatted for display
+    pub modified: Option<String>,
+    pub nb_helices: usize,
+    pub nb_strands: usize,
+    pub nb_nucleotides: usize,
+    /// The picture of the 3D view embedded in the file, encoded in png
+    pub thumbnail_png: Option<Vec<u8>>,
+    pub metadata: DesignMetadata,
+    /// The reason why the file could not be read, if any
+    pub error: Option<String>,
+}
+
+impl DesignFileInfo {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// The design files of a folder, most recently modified first.
+#[derive(Clone, Debug, Default)]
+pub struct DesignBrowserContent {
+    pub directory: PathBuf,
+    pub files: Vec<DesignFileInfo>,
+    /// True while the files of the folder are still being read
+    pub scanning: bool,
+}
+
+/// What the user did in the dialog listing design files.
+#[derive(Clone, Debug)]
+pub enum DesignBrowserChoice {
+    Open(PathBuf),
+    /// List the design files of another folder
+    ChooseFolder,
+    /// Pick the file with the system dialog instead, for example to import another file format
+    OtherFile,
+    Cancel,
+}
@@ -0,0 +1,176 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Parametric starter designs that can be generated programmatically in an empty design.
+
+use super::*;
+
+/// Length, in nucleotides, of the helix of the `SingleHelix` template
+const SINGLE_HELIX_LENGTH: usize = 64;
+/// Length, in nucleotides, of the helices of the `SixHelixBundle` template
+const SIX_HELIX_BUNDLE_LENGTH: usize = 84;
+/// Length, in nucleotides, of the helices of the `TwentyFourHelixRectangle` template
+const RECTANGLE_LENGTH: usize = 96;
+/// Length, in nucleotides, of the edges of the `TetrahedronWireframe` template
+const TETRAHEDRON_EDGE_LENGTH: usize = 42;
+/// Number of nucleotides left empty at each vertex of the `TetrahedronWireframe` template so that
+/// the helices of adjacent edges do not overlap
+const TETRAHEDRON_VERTEX_GAP: usize = 4;
+
+/// The grid positions of the helices of a six helix bundle on an honeycomb grid, in the order in
+/// which they go around the bundle
+const SIX_HELIX_BUNDLE_POSITIONS: [(isize, isize); 6] =
+    [(0, 0), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+/// A starter design offered by the "New from template" menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DesignTemplate {
+    SingleHelix,
+    SixHelixBundle,
+    TwentyFourHelixRectangle,
+    TetrahedronWireframe,
+}
+
+impl DesignTemplate {
+    pub const ALL: &'static [Self] = &[
+        Self::SingleHelix,
+        Self::SixHelixBundle,
+        Self::TwentyFourHelixRectangle,
+        Self::TetrahedronWireframe,
+    ];
+
+    /// The operations that generate the template.
+    ///
+    /// The operations must be applied in order to an empty design, because they refer to the
+    /// grids that they create by their position in the list of free grids.
+    pub fn operations(&self) -> Vec<DesignOperation> {
+        match self {
+            Self::SingleHelix => vec![
+                add_grid(Vec3::zero(), Rotor3::identity(), square()),
+                DesignOperation::AddGridHelix {
+                    position: HelixGridPosition::from_grid_id_x_y(GridId::FreeGrid(0), 0, 0),
+                    start: 0,
+                    length: SINGLE_HELIX_LENGTH,
+                },
+            ],
+            Self::SixHelixBundle => {
+                let mut ret = vec![add_grid(Vec3::zero(), Rotor3::identity(), honeycomb())];
+                ret.extend(SIX_HELIX_BUNDLE_POSITIONS.iter().map(|(x, y)| {
+                    DesignOperation::AddGridHelix {
+                        position: HelixGridPosition::from_grid_id_x_y(GridId::FreeGrid(0), *x, *y),
+                        start: 0,
+                        length: SIX_HELIX_BUNDLE_LENGTH,
+                    }
+                }));
+                ret
+            }
+            Self::TwentyFourHelixRectangle => vec![
+                add_grid(Vec3::zero(), Rotor3::identity(), square()),
+                DesignOperation::AddHelixBlock(HelixBlockDescriptor {
+                    grid_id: GridId::FreeGrid(0),
+                    x: 0,
+                    y: 0,
+                    nb_rows: 4,
+                    nb_columns: 6,
+                    start: 0,
+                    length: RECTANGLE_LENGTH,
+                    alternate_orientation: true,
+                    scaffold_raster: true,
+                }),
+            ],
+            Self::TetrahedronWireframe => tetrahedron_operations(&HelixParameters::DEFAULT),
+        }
+    }
+}
+
+impl std::fmt::Display for DesignTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::SingleHelix => "Single helix",
+            Self::SixHelixBundle => "6-helix bundle",
+            Self::TwentyFourHelixRectangle => "24-helix rectangle",
+            Self::TetrahedronWireframe => "Tetrahedron wireframe",
+        };
+        write!(f, "{}", crate::i18n::tr(ret))
+    }
+}
+
+fn square() -> GridTypeDescr {
+    GridTypeDescr::Square { twist: None }
+}
+
+fn honeycomb() -> GridTypeDescr {
+    GridTypeDescr::Honeycomb { twist: None }
+}
+
+fn add_grid(position: Vec3, orientation: Rotor3, grid_type: GridTypeDescr) -> DesignOperation {
+    DesignOperation::AddGrid(grid_descriptor(position, orientation, grid_type))
+}
+
+fn grid_descriptor(
+    position: Vec3,
+    orientation: Rotor3,
+    grid_type: GridTypeDescr,
+) -> GridDescriptor {
+    GridDescriptor {
+        position,
+        orientation,
+        helix_parameters: None,
+        grid_type,
+        invisible: false,
+        bezier_vertex: None,
+    }
+}
+
+/// One helix per edge of a regular tetrahedron. Each helix lies on its own invisible grid whose
+/// normal is the direction of the edge.
+fn tetrahedron_operations(helix_parameters: &HelixParameters) -> Vec<DesignOperation> {
+    let edge_nm =
+        (TETRAHEDRON_EDGE_LENGTH + 2 * TETRAHEDRON_VERTEX_GAP) as f32 * helix_parameters.rise;
+    // The vertices (±1, ±1, ±1) with an even number of minus signs are at distance 2√2 from
+    // each other.
+    let scale = edge_nm / (2. * 2f32.sqrt());
+    let vertices = [
+        Vec3::new(1., 1., 1.),
+        Vec3::new(1., -1., -1.),
+        Vec3::new(-1., 1., -1.),
+        Vec3::new(-1., -1., 1.),
+    ]
+    .map(|v| v * scale);
+    let gap_nm = TETRAHEDRON_VERTEX_GAP as f32 * helix_parameters.rise;
+
+    let mut ret = Vec::with_capacity(12);
+    let mut grid_id = 0;
+    for (i, source) in vertices.iter().enumerate() {
+        for dest in vertices.iter().skip(i + 1) {
+            let direction = (*dest - *source).normalized();
+            let orientation = Rotor3::from_rotation_between(Vec3::unit_x(), direction);
+            ret.push(DesignOperation::AddGrid(GridDescriptor {
+                invisible: true,
+                ..grid_descriptor(*source + direction * gap_nm, orientation, square())
+            }));
+            ret.push(DesignOperation::AddGridHelix {
+                position: HelixGridPosition::from_grid_id_x_y(GridId::FreeGrid(grid_id), 0, 0),
+                start: 0,
+                length: TETRAHEDRON_EDGE_LENGTH,
+            });
+            grid_id += 1;
+        }
+    }
+    ret
+}
@@ -16,6 +16,11 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use crate::consts::{
+    CANDIDATE_COLOR, CVD_CANDIDATE_COLOR, CVD_MIN_SATURATION, CVD_MIN_VALUE, CVD_SAFE_PALETTE,
+    CVD_SELECTED_COLOR, SELECTED_COLOR,
+};
+use ensnano_design::{drawing_style::EndGlyph, HelixParameters};
 use iced_winit::winit;
 use serde::{Deserialize, Serialize};
 use ultraviolet::Vec3;
@@ -48,16 +53,719 @@ impl Default for Background3D {
     }
 }
 
+/// Settings of a 3D view that override the global rendering settings. The default layer
+/// follows all the global settings.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewLayer {
+    /// If some, the rendering mode of the view instead of the global one
+    pub rendering_mode: Option<RenderingMode>,
+    /// If some, the background of the view instead of the global one
+    pub background3d: Option<Background3D>,
+    /// If true, the nucleotides hidden by the visibility sieve are shown in the view
+    pub ignore_visibility_sieve: bool,
+}
+
+impl ViewLayer {
+    pub fn rendering_mode(&self, global: RenderingMode) -> RenderingMode {
+        self.rendering_mode.unwrap_or(global)
+    }
+
+    pub fn background3d(&self, global: Background3D) -> Background3D {
+        self.background3d.unwrap_or(global)
+    }
+}
+
+/// The visual helpers drawn beneath the design in the 3D scene.
+#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
+pub struct GroundParameters {
+    /// Show a grid on the ground plane.
+    pub show_grid: bool,
+    /// Show a soft shadow of the design on the ground plane.
+    pub show_shadow: bool,
+    /// The distance in nanometers between the lowest point of the design and the ground.
+    pub height: f32,
+    /// The distance in nanometers between two lines of the grid.
+    pub spacing: f32,
+}
+
+impl Default for GroundParameters {
+    fn default() -> Self {
+        Self {
+            show_grid: false,
+            show_shadow: false,
+            height: 5.,
+            spacing: 10.,
+        }
+    }
+}
+
+impl GroundParameters {
+    pub fn is_visible(&self) -> bool {
+        self.show_grid || self.show_shadow
+    }
+}
+
+/// The spacing of the ticks drawn along the helices of the 2D view.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum Ruler {
+    Hidden,
+    Every7,
+    Every8,
+    Every16,
+}
+
+pub const ALL_RULERS: [Ruler; 4] = [Ruler::Hidden, Ruler::Every7, Ruler::Every8, Ruler::Every16];
+
+impl Default for Ruler {
+    fn default() -> Self {
+        Self::Hidden
+    }
+}
+
+impl Ruler {
+    /// The number of nucleotides between two ticks, or None if no tick must be drawn.
+    pub fn interval(&self) -> Option<isize> {
+        match self {
+            Self::Hidden => None,
+            Self::Every7 => Some(7),
+            Self::Every8 => Some(8),
+            Self::Every16 => Some(16),
+        }
+    }
+}
+
+impl std::fmt::Display for Ruler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.interval() {
+            None => write!(f, "No ruler"),
+            Some(n) => write!(f, "Every {n} bases"),
+        }
+    }
+}
+
+/// The scale indicators drawn in the 2D and 3D views, and the unit in which lengths are
+/// displayed.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Default, Serialize, Deserialize)]
+pub struct ScaleBarParameters {
+    /// Show a scale bar in the bottom left corner of the views.
+    pub show_scale_bar: bool,
+    pub ruler: Ruler,
+    #[serde(default)]
+    pub length_unit: LengthUnit,
+}
+
+/// The unit in which lengths are displayed.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum LengthUnit {
+    Nanometer,
+    BasePair,
+    /// A full turn of a helix, the length of which depends on the helix parameters of the design
+    HelixTurn,
+}
+
+pub const ALL_LENGTH_UNITS: [LengthUnit; 3] = [
+    LengthUnit::Nanometer,
+    LengthUnit::BasePair,
+    LengthUnit::HelixTurn,
+];
+
+impl Default for LengthUnit {
+    fn default() -> Self {
+        Self::Nanometer
+    }
+}
+
+impl LengthUnit {
+    /// The number of nanometers in one unit
+    pub fn nm_per_unit(&self, parameters: &HelixParameters) -> f32 {
+        match self {
+            Self::Nanometer => 1.,
+            Self::BasePair => parameters.rise,
+            Self::HelixTurn => parameters.rise * parameters.bases_per_turn,
+        }
+    }
+
+    pub fn from_nm(&self, length_nm: f32, parameters: &HelixParameters) -> f32 {
+        length_nm / self.nm_per_unit(parameters)
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Nanometer => "nm",
+            Self::BasePair => "bp",
+            Self::HelixTurn => "turns",
+        }
+    }
+
+    /// Format a length given in nanometers, converted in this unit
+    pub fn format_nm(&self, length_nm: f32, parameters: &HelixParameters) -> String {
+        let nb_decimals = match self {
+            Self::BasePair => 1,
+            Self::Nanometer | Self::HelixTurn => 2,
+        };
+        self.format_value(self.from_nm(length_nm, parameters), nb_decimals)
+    }
+
+    /// Format a `value` already expressed in this unit, with `nb_decimals` decimals
+    pub fn format_value(&self, value: f32, nb_decimals: usize) -> String {
+        format!("{:.*} {}", nb_decimals, value, self.symbol())
+    }
+}
+
+impl std::fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Nanometer => "Nanometers",
+            Self::BasePair => "Base pairs",
+            Self::HelixTurn => "Helix turns",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// The reference from which the positions written above the helices of the 2D view are counted.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum NucleotideNumbering {
+    /// The position of the nucleotides on their helix
+    Helix,
+    /// The position of the nucleotides on their strand, starting at 1 at the 5' end
+    Strand,
+}
+
+pub const ALL_NUCLEOTIDE_NUMBERINGS: [NucleotideNumbering; 2] =
+    [NucleotideNumbering::Helix, NucleotideNumbering::Strand];
+
+impl Default for NucleotideNumbering {
+    fn default() -> Self {
+        Self::Helix
+    }
+}
+
+impl std::fmt::Display for NucleotideNumbering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Helix => "Helix coordinates",
+            Self::Strand => "Strand-relative",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// The positions written above the helices of the 2D view.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub struct NumberingParameters {
+    /// Write the position of every `interval`-th nucleotide.
+    pub show_numbers: bool,
+    pub interval: isize,
+    /// Write the position of the extremities of the domains.
+    pub show_domain_boundaries: bool,
+    pub numbering: NucleotideNumbering,
+}
+
+impl Default for NumberingParameters {
+    fn default() -> Self {
+        Self {
+            show_numbers: true,
+            interval: 8,
+            show_domain_boundaries: false,
+            numbering: Default::default(),
+        }
+    }
+}
+
+/// The glyphs marking the polarity of the strands in the 3D view. These are the default values,
+/// that can be overridden for some strands with drawing styles.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub struct StrandEndsParameters {
+    pub prime5_glyph: EndGlyph,
+    pub prime3_glyph: EndGlyph,
+    /// Draw a direction cone on one bond out of `cone_spacing`. No cone is drawn if it is 0.
+    pub cone_spacing: usize,
+}
+
+impl Default for StrandEndsParameters {
+    fn default() -> Self {
+        Self {
+            prime5_glyph: EndGlyph::None,
+            prime3_glyph: EndGlyph::None,
+            cone_spacing: 1,
+        }
+    }
+}
+
+/// The way cross-overs are drawn in the 2D view.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Default, Serialize, Deserialize)]
+pub struct XoverDrawingParameters {
+    /// Draw the cross-overs as arcs bulging away from the helices that they link.
+    pub arcs: bool,
+    /// Gather the cross-overs linking the same pair of helices into bundles.
+    pub bundling: bool,
+    /// Color the cross-overs according to their length instead of the color of their strand.
+    pub color_by_length: bool,
+}
+
+/// Guide lines drawn across the helices of the 2D view at the positions where cross-overs can be
+/// made, according to the lattice of the helices.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Default, Serialize, Deserialize)]
+pub struct XoverGuideParameters {
+    pub show_guides: bool,
+    /// The shift, in nucleotides, of the guides relatively to position 0 of the helices.
+    pub register_offset: isize,
+}
+
+impl XoverGuideParameters {
+    /// The period of the guides on a square lattice, and the period of the emphasized guides.
+    pub const SQUARE_PERIODS: (isize, isize) = (16, 32);
+    /// The period of the guides on a honeycomb lattice, and the period of the emphasized guides.
+    pub const HONEYCOMB_PERIODS: (isize, isize) = (7, 21);
+    pub const MAX_REGISTER_OFFSET: isize = 31;
+
+    /// The period of the guides on a helix lying on a grid of type `grid_type` and the period of
+    /// the emphasized guides, or `None` if the grid is not a lattice.
+    pub fn periods(grid_type: &ensnano_design::grid::GridTypeDescr) -> Option<(isize, isize)> {
+        use ensnano_design::grid::GridTypeDescr;
+        match grid_type {
+            GridTypeDescr::Square { .. } => Some(Self::SQUARE_PERIODS),
+            GridTypeDescr::Honeycomb { .. } => Some(Self::HONEYCOMB_PERIODS),
+            GridTypeDescr::Hyperboloid { .. } => None,
+        }
+    }
+}
+
+/// The region of the 3D view that is covered by the brush.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum BrushShape {
+    /// The nucleotides that are within `radius` nanometers of the nucleotide under the cursor
+    Sphere,
+    /// The nucleotides that are drawn within `radius` pixels of the cursor
+    Screen,
+}
+
+pub const ALL_BRUSH_SHAPES: [BrushShape; 2] = [BrushShape::Sphere, BrushShape::Screen];
+
+impl BrushShape {
+    /// The values that the radius of a brush of this shape can take.
+    pub fn radius_range(&self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            Self::Sphere => 0.5..=20.,
+            Self::Screen => 5.0..=200.,
+        }
+    }
+
+    pub fn default_radius(&self) -> f32 {
+        match self {
+            Self::Sphere => 2.,
+            Self::Screen => 30.,
+        }
+    }
+}
+
+impl Default for BrushShape {
+    fn default() -> Self {
+        Self::Sphere
+    }
+}
+
+impl std::fmt::Display for BrushShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Sphere => "Sphere",
+            Self::Screen => "Screen",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// What happens to the nucleotides covered by the brush.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum BrushEffect {
+    Hide,
+    Show,
+    /// Give the brush's color to the strands of the nucleotides
+    Color,
+    /// Add the nucleotides to the selected group of the organizer
+    Group,
+}
+
+pub const ALL_BRUSH_EFFECTS: [BrushEffect; 4] = [
+    BrushEffect::Hide,
+    BrushEffect::Show,
+    BrushEffect::Color,
+    BrushEffect::Group,
+];
+
+impl Default for BrushEffect {
+    fn default() -> Self {
+        Self::Hide
+    }
+}
+
+impl std::fmt::Display for BrushEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Hide => "Hide",
+            Self::Show => "Show",
+            Self::Color => "Color",
+            Self::Group => "Add to group",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// The brush with which nucleotides are painted in the 3D view.
+#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
+pub struct BrushParameters {
+    /// When true, dragging the cursor in the 3D view paints the nucleotides instead of moving the
+    /// camera.
+    pub active: bool,
+    pub shape: BrushShape,
+    /// In nanometers for spherical brushes and in pixels for screen-space brushes.
+    pub radius: f32,
+    pub effect: BrushEffect,
+    pub color: u32,
+    /// If false, only the nucleotides that are not hidden behind other objects can be painted.
+    /// Otherwise the brush paints through the structure.
+    pub paint_occluded: bool,
+}
+
+impl Default for BrushParameters {
+    fn default() -> Self {
+        Self {
+            active: false,
+            shape: BrushShape::Sphere,
+            radius: BrushShape::Sphere.default_radius(),
+            effect: Default::default(),
+            color: 0xFF_00_00,
+            paint_occluded: false,
+        }
+    }
+}
+
+/// Return the largest length of the form 1, 2 or 5 times a power of ten that is at most
+/// `max_length`. This is the length represented by the scale bars.
+pub fn scale_bar_length(max_length: f32) -> f32 {
+    let power = 10f32.powf(max_length.log10().floor());
+    let mantissa = max_length / power;
+    if mantissa >= 5. {
+        5. * power
+    } else if mantissa >= 2. {
+        2. * power
+    } else {
+        power
+    }
+}
+
 impl std::fmt::Display for Background3D {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ret = match self {
-            Self::White => "White",
+            Self::White => "Plain",
             Self::Sky => "Sky",
         };
         write!(f, "{}", ret)
     }
 }
 
+/// The color palette used by the GUI and the scenes.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+pub const ALL_THEMES: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::Dark
+    }
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// The graphics adapter requested at startup.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum GpuPreference {
+    /// Favor the adapter that consumes the least power
+    LowPower,
+    /// Favor the most powerful adapter
+    HighPerformance,
+    /// Use the integrated graphics card if there is one
+    Integrated,
+    /// Use the discrete graphics card if there is one
+    Discrete,
+}
+
+pub const ALL_GPU_PREFERENCES: [GpuPreference; 4] = [
+    GpuPreference::LowPower,
+    GpuPreference::HighPerformance,
+    GpuPreference::Integrated,
+    GpuPreference::Discrete,
+];
+
+impl Default for GpuPreference {
+    fn default() -> Self {
+        Self::LowPower
+    }
+}
+
+impl std::fmt::Display for GpuPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::LowPower => "Low power",
+            Self::HighPerformance => "High performance",
+            Self::Integrated => "Integrated GPU",
+            Self::Discrete => "Discrete GPU",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+impl std::str::FromStr for GpuPreference {
+    type Err = String;
+
+    /// Parse the value of the `--gpu` command line argument.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low-power" | "low_power" => Ok(Self::LowPower),
+            "high-performance" | "high_performance" => Ok(Self::HighPerformance),
+            "integrated" => Ok(Self::Integrated),
+            "discrete" => Ok(Self::Discrete),
+            _ => Err(format!(
+                "Unknown gpu preference {}. Expected low-power, high-performance, integrated or discrete",
+                s
+            )),
+        }
+    }
+}
+
+/// Settings that trade the rendering quality of the 3D scene for framerate.
+///
+/// They are read when the rendering pipelines and textures are created, so a modification
+/// takes effect at the next startup.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RenderQuality {
+    pub multisampling: Multisampling,
+    pub anisotropic_filtering: AnisotropicFiltering,
+    pub texture_resolution: TextureResolution,
+}
+
+static RENDER_QUALITY: std::sync::OnceLock<RenderQuality> = std::sync::OnceLock::new();
+
+/// Set the render quality used by the 3D scene. Must be called before the scene is created.
+pub fn set_render_quality(quality: RenderQuality) {
+    if RENDER_QUALITY.set(quality).is_err() {
+        log::error!("Render quality can only be set once");
+    }
+}
+
+/// The render quality used by the 3D scene.
+pub fn render_quality() -> RenderQuality {
+    *RENDER_QUALITY.get_or_init(Default::default)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum Multisampling {
+    Disabled,
+    X4,
+}
+
+pub const ALL_MULTISAMPLINGS: [Multisampling; 2] = [Multisampling::Disabled, Multisampling::X4];
+
+impl Default for Multisampling {
+    fn default() -> Self {
+        Self::X4
+    }
+}
+
+impl Multisampling {
+    pub fn sample_count(&self) -> u32 {
+        match self {
+            Self::Disabled => 1,
+            Self::X4 => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for Multisampling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Disabled => "No antialiasing",
+            Self::X4 => "MSAA x4",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum AnisotropicFiltering {
+    Disabled,
+    X2,
+    X4,
+    X8,
+    X16,
+}
+
+pub const ALL_ANISOTROPIC_FILTERINGS: [AnisotropicFiltering; 5] = [
+    AnisotropicFiltering::Disabled,
+    AnisotropicFiltering::X2,
+    AnisotropicFiltering::X4,
+    AnisotropicFiltering::X8,
+    AnisotropicFiltering::X16,
+];
+
+impl Default for AnisotropicFiltering {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+impl AnisotropicFiltering {
+    /// The value of the `anisotropy_clamp` field of the samplers
+    pub fn clamp(&self) -> Option<std::num::NonZeroU8> {
+        let clamp = match self {
+            Self::Disabled => 0,
+            Self::X2 => 2,
+            Self::X4 => 4,
+            Self::X8 => 8,
+            Self::X16 => 16,
+        };
+        std::num::NonZeroU8::new(clamp)
+    }
+}
+
+impl std::fmt::Display for AnisotropicFiltering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Disabled => "No anisotropic filtering",
+            Self::X2 => "Anisotropic x2",
+            Self::X4 => "Anisotropic x4",
+            Self::X8 => "Anisotropic x8",
+            Self::X16 => "Anisotropic x16",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum TextureResolution {
+    Low,
+    Medium,
+    High,
+}
+
+pub const ALL_TEXTURE_RESOLUTIONS: [TextureResolution; 3] = [
+    TextureResolution::Low,
+    TextureResolution::Medium,
+    TextureResolution::High,
+];
+
+impl Default for TextureResolution {
+    fn default() -> Self {
+        Self::Medium
+    }
+}
+
+impl TextureResolution {
+    /// The width and height of the textures used to draw the grids
+    pub fn grid_texture_size(&self) -> u32 {
+        match self {
+            Self::Low => 256,
+            Self::Medium => 512,
+            Self::High => 1024,
+        }
+    }
+}
+
+impl std::fmt::Display for TextureResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Low => "Low resolution textures",
+            Self::Medium => "Medium resolution textures",
+            Self::High => "High resolution textures",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// The way colors are used to convey information in the 2D and 3D views.
+#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+pub enum ColorVisionMode {
+    Normal,
+    /// Strand colors are remapped to a palette that remains distinguishable with a color vision
+    /// deficiency, selection and candidates are drawn with high-contrast colors, and patterns are
+    /// used in the 2D view where colors alone would make the difference.
+    ColorblindSafe,
+}
+
+pub const ALL_COLOR_VISION_MODES: [ColorVisionMode; 2] =
+    [ColorVisionMode::Normal, ColorVisionMode::ColorblindSafe];
+
+impl Default for ColorVisionMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+impl std::fmt::Display for ColorVisionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Normal => "Normal",
+            Self::ColorblindSafe => "Colorblind-safe",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+impl ColorVisionMode {
+    pub fn selected_color(&self) -> u32 {
+        match self {
+            Self::Normal => SELECTED_COLOR,
+            Self::ColorblindSafe => CVD_SELECTED_COLOR,
+        }
+    }
+
+    pub fn candidate_color(&self) -> u32 {
+        match self {
+            Self::Normal => CANDIDATE_COLOR,
+            Self::ColorblindSafe => CVD_CANDIDATE_COLOR,
+        }
+    }
+
+    /// Return true if candidates must be drawn with a dash pattern in the 2D view.
+    pub fn uses_patterns(&self) -> bool {
+        *self == Self::ColorblindSafe
+    }
+
+    /// The color with which a strand of color `color` is drawn.
+    ///
+    /// In colorblind-safe mode, the hue of the color selects one of the colors of
+    /// `CVD_SAFE_PALETTE`. Greyish and dark colors are kept as they are.
+    pub fn strand_color(&self, color: u32) -> u32 {
+        if *self == Self::Normal {
+            return color;
+        }
+        let (r, g, b) = ((color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF);
+        let hsv = color_space::Hsv::from(color_space::Rgb::new(r as f64, g as f64, b as f64));
+        if hsv.s < CVD_MIN_SATURATION || hsv.v < CVD_MIN_VALUE {
+            color
+        } else {
+            let nb_colors = CVD_SAFE_PALETTE.len();
+            let idx = (hsv.h.rem_euclid(360.) / 360. * nb_colors as f64) as usize % nb_colors;
+            (color & 0xFF_00_00_00) | CVD_SAFE_PALETTE[idx]
+        }
+    }
+}
+
 impl std::fmt::Display for RenderingMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ret = match self {
@@ -135,7 +843,7 @@ impl CutPlaneParameters {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SplitMode {
     Flat,
     Scene3D,
@@ -173,6 +881,8 @@ pub enum ElementType {
     Unattributed,
     /// A stereographic version of the 3D view
     StereographicScene,
+    /// A 2D view of the intersection of the helices with a plane
+    CrossSection,
 }
 
 impl ElementType {
@@ -185,7 +895,10 @@ impl ElementType {
 
     pub fn is_scene(&self) -> bool {
         match self {
-            ElementType::StereographicScene | ElementType::Scene | ElementType::FlatScene => true,
+            ElementType::StereographicScene
+            | ElementType::Scene
+            | ElementType::FlatScene
+            | ElementType::CrossSection => true,
             _ => false,
         }
     }
@@ -208,3 +921,23 @@ pub struct LoopoutBond {
     /// The identifier of the bond representing the whole loopout involving this bond
     pub repr_bond_identifier: u32,
 }
+
+/// The 5' or 3' end of a strand on which a glyph can be drawn
+#[derive(Clone, Debug)]
+pub struct StrandEnd {
+    /// The identifier of the nucleotide at the end of the strand
+    pub nucl_id: u32,
+    /// The identifier of the neighbour of the end nucleotide on the strand
+    pub neighbour_id: u32,
+    pub prime5: bool,
+    /// The glyph required by the drawing style of the strand, if any
+    pub glyph: Option<EndGlyph>,
+}
+
+/// A small image of the 3D view, in Rgba format
+#[derive(Clone, Debug)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
@@ -0,0 +1,125 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A gettext-like translation layer for the strings displayed to the user.
+//!
+//! The strings of the source code are written in English and are used as identifiers to look for
+//! their translation in the catalog of the current language. If a string has no translation, it
+//! is displayed in English.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+mod french;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Language {
+    English = 0,
+    French = 1,
+}
+
+impl Language {
+    pub const ALL: &'static [Self] = &[Self::English, Self::French];
+
+    fn from_u8(n: u8) -> Self {
+        match n {
+            1 => Self::French,
+            _ => Self::English,
+        }
+    }
+
+    fn catalog(&self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        static FRENCH: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        match self {
+            Self::English => None,
+            Self::French => Some(FRENCH.get_or_init(|| french::CATALOG.iter().cloned().collect())),
+        }
+    }
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Self::English
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Languages are always displayed in their own language
+        let ret = match self {
+            Self::English => "English",
+            Self::French => "Français",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+/// Set the language in which the strings are translated by [tr].
+pub fn set_language(language: Language) {
+    CURRENT_LANGUAGE.store(language as u8, Ordering::Relaxed)
+}
+
+pub fn current_language() -> Language {
+    Language::from_u8(CURRENT_LANGUAGE.load(Ordering::Relaxed))
+}
+
+/// Translate `msgid` in the current language.
+pub fn tr(msgid: &str) -> &str {
+    current_language()
+        .catalog()
+        .and_then(|catalog| catalog.get(msgid).cloned())
+        .unwrap_or(msgid)
+}
+
+/// Translate `msgid` in the current language, and replace each occurence of `{}` in the
+/// translation by the next element of `args`.
+pub fn tr_args(msgid: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut pieces = tr(msgid).split("{}");
+    let mut ret = pieces.next().unwrap_or_default().to_string();
+    let mut args = args.iter();
+    for piece in pieces {
+        if let Some(arg) = args.next() {
+            ret.push_str(&arg.to_string());
+        }
+        ret.push_str(piece);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translation_falls_back_to_english() {
+        set_language(Language::French);
+        assert_eq!(tr("Help"), "Aide");
+        assert_eq!(tr("Not a translated string"), "Not a translated string");
+        assert_eq!(
+            tr_args("Tutorial: step {}/{}", &[&1, &7]),
+            "Tutoriel : étape 1/7"
+        );
+        set_language(Language::English);
+        assert_eq!(tr("Help"), "Help");
+    }
+}
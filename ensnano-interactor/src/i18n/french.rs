@@ -0,0 +1,568 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! French translation of the strings displayed to the user.
+
+pub(super) const CATALOG: &[(&str, &str)] = &[
+    // Top bar
+    ("Toggle 2D", "Afficher la 2D"),
+    ("Viewer mode", "Mode lecture"),
+    ("Edit mode", "Mode édition"),
+    ("Attach panel", "Attacher le panneau"),
+    ("Detach panel", "Détacher le panneau"),
+    ("Help", "Aide"),
+    ("Tutorials", "Tutoriels"),
+    ("New from template", "Nouveau depuis un modèle"),
+    // Design templates
+    ("Single helix", "Hélice simple"),
+    ("6-helix bundle", "Faisceau de 6 hélices"),
+    ("24-helix rectangle", "Rectangle de 24 hélices"),
+    ("Tetrahedron wireframe", "Tétraèdre filaire"),
+    // Tutorial
+    ("Tutorial: step {}/{}", "Tutoriel : étape {}/{}"),
+    ("See {}.", "Voir {}."),
+    ("Skip step", "Passer l'étape"),
+    ("Quit tutorial", "Quitter le tutoriel"),
+    (
+        "Start interactive tutorial",
+        "Démarrer le tutoriel interactif",
+    ),
+    ("the top bar", "la barre du haut"),
+    (
+        "the highlighted tab of the left panel",
+        "l'onglet en surbrillance du panneau de gauche",
+    ),
+    ("the contextual panel", "le panneau contextuel"),
+    ("the 3D view", "la vue 3D"),
+    ("the 2D view", "la vue 2D"),
+    (
+        "Add a grid to the design by clicking on one of the grid buttons of the grid tab.",
+        "Ajoutez une grille au design en cliquant sur l'un des boutons de grille de l'onglet \
+        des grilles.",
+    ),
+    (
+        "Select the helix building mode with the corresponding button of the top bar.",
+        "Sélectionnez le mode de construction d'hélices avec le bouton correspondant de la \
+        barre du haut.",
+    ),
+    (
+        "Click on two neighbouring positions of the grid to add two helices.",
+        "Cliquez sur deux positions voisines de la grille pour ajouter deux hélices.",
+    ),
+    (
+        "Drag the end of a strand onto the neighbouring helix to make a cross-over between the \
+        two helices.",
+        "Faites glisser l'extrémité d'un brin sur l'hélice voisine pour créer un cross-over \
+        entre les deux hélices.",
+    ),
+    (
+        "Choose the strand selection mode in the top bar and click on a strand to select it.",
+        "Choisissez le mode de sélection des brins dans la barre du haut et cliquez sur un brin \
+        pour le sélectionner.",
+    ),
+    (
+        "Make the selected strand the scaffold of the design with the \"Scaffold\" checkbox.",
+        "Faites du brin sélectionné le scaffold du design avec la case \"Scaffold\".",
+    ),
+    (
+        "Save your design with the save button of the top bar.",
+        "Enregistrez votre design avec le bouton d'enregistrement de la barre du haut.",
+    ),
+    (
+        "Congratulations, you have completed the tutorial!",
+        "Félicitations, vous avez terminé le tutoriel !",
+    ),
+    // Left panel
+    ("Parameters", "Paramètres"),
+    ("Theme", "Thème"),
+    ("Language", "Langue"),
+    ("Color vision", "Vision des couleurs"),
+    ("Design files", "Fichiers de design"),
+    ("Save in binary format", "Sauvegarder au format binaire"),
+    ("Design information", "Informations sur le design"),
+    ("Author", "Auteur"),
+    ("Name of the author", "Nom de l'auteur"),
+    ("License", "Licence"),
+    ("Description", "Description"),
+    ("What the design is made for", "À quoi sert le design"),
+    ("Experiment notes", "Notes d'expérience"),
+    ("Edit notes", "Modifier les notes"),
+    ("Preview", "Aperçu"),
+    ("No notes", "Aucune note"),
+    ("Strand cleanup", "Nettoyage des brins"),
+    (
+        "Remove strands shorter than {} nt",
+        "Supprimer les brins de moins de {} nt",
+    ),
+    ("Clean up strands", "Nettoyer les brins"),
+    ("Helix numbering", "Numérotation des hélices"),
+    ("2D printout (PDF)", "Impression 2D (PDF)"),
+    ("1 nt = {} mm", "1 nt = {} mm"),
+    ("Helix numbers", "Numéros des hélices"),
+    ("Sequences", "Séquences"),
+    ("Legend", "Légende"),
+    ("Print to PDF", "Imprimer en PDF"),
+    ("Renumber", "Renuméroter"),
+    ("Set number", "Fixer le numéro"),
+    (
+        "Number of the selected helix",
+        "Numéro de l'hélice sélectionnée",
+    ),
+    (
+        "Confirm large deletions",
+        "Confirmer les suppressions importantes",
+    ),
+    ("Graphics card", "Carte graphique"),
+    ("Rendering quality", "Qualité du rendu"),
+    (
+        "Takes effect after restarting ENSnano",
+        "Prend effet au redémarrage d'ENSnano",
+    ),
+    ("Undo history", "Historique d'annulation"),
+    (
+        "Keep the last {} operations",
+        "Conserver les {} dernières opérations",
+    ),
+    ("Camera", "Caméra"),
+    ("Edition", "Édition"),
+    ("Grids", "Grilles"),
+    ("Ground", "Sol"),
+    ("View layers", "Calques de vue"),
+    ("Stereographic view", "Vue stéréographique"),
+    ("Global setting", "Réglage global"),
+    ("Show hidden nucleotides", "Afficher les nucléotides cachés"),
+    ("Guess grid", "Deviner la grille"),
+    ("Highlight Xovers", "Surligner les cross-overs"),
+    ("New Grid", "Nouvelle grille"),
+    ("New nanotube", "Nouveau nanotube"),
+    ("Rendering", "Rendu"),
+    ("Scale", "Échelle"),
+    ("Scrolling", "Défilement"),
+    ("Sequence", "Séquence"),
+    ("Staples", "Agrafes"),
+    ("Visibility", "Visibilité"),
+    ("Simulation (Beta)", "Simulation (bêta)"),
+    ("Simulation parameters", "Paramètres de simulation"),
+    ("Suggestions Parameters", "Paramètres des suggestions"),
+    ("DNA/RNA model", "Modèle ADN/ARN"),
+    ("Discretization parameters", "Paramètres de discrétisation"),
+    (
+        "Parameters for helices simulation",
+        "Paramètres de simulation des hélices",
+    ),
+    ("Relaxation computation", "Calcul de relaxation"),
+    ("Revolution Surfaces", "Surfaces de révolution"),
+    ("Revolution parameter", "Paramètre de révolution"),
+    ("Section parameters", "Paramètres de la section"),
+    ("Bezier Planes", "Plans de Bézier"),
+    ("Tighten 2D helices", "Resserrer les hélices 2D"),
+    (
+        "Translation/rotation widgets",
+        "Widgets de translation/rotation",
+    ),
+    ("Conception:", "Conception :"),
+    ("Development:", "Développement :"),
+    ("License:", "Licence :"),
+    ("About", "À propos"),
+    ("Orientation", "Orientation"),
+    ("Position", "Position"),
+    ("Twist", "Torsion"),
+    ("Style", "Style"),
+    ("Background", "Arrière-plan"),
+    ("Fog", "Brouillard"),
+    ("Radius", "Rayon"),
+    ("Softness", "Douceur"),
+    ("Show H-Bonds", "Afficher les liaisons H"),
+    ("Ruler along 2D helices", "Règle le long des hélices 2D"),
+    ("Rotate Camera", "Tourner la caméra"),
+    ("Screenshot", "Capture d'écran"),
+    ("STL export", "Export STL"),
+    (
+        "Nucleotides positions export",
+        "Export des positions des nucléotides",
+    ),
+    ("Custom cameras", "Caméras personnalisées"),
+    ("Presentation", "Présentation"),
+    ("{} steps (PageUp/PageDown)", "{} étapes (PageUp/PageDown)"),
+    ("Step name", "Nom de l'étape"),
+    ("From Selection", "Depuis la sélection"),
+    ("From selection", "Depuis la sélection"),
+    (
+        "Select ≥4 unattached helices",
+        "Sélectionnez ≥4 hélices libres",
+    ),
+    ("Set scaffold sequence", "Définir la séquence du scaffold"),
+    (
+        "Optimize starting position",
+        "Optimiser la position de départ",
+    ),
+    ("Export Staples", "Exporter les agrafes"),
+    ("Export Origamis", "Exporter les origamis"),
+    ("Load File", "Charger un fichier"),
+    ("Curve type", "Type de courbe"),
+    ("Nb Half Turns", "Nb de demi-tours"),
+    ("Nb spiral", "Nb de spirales"),
+    ("Revolution Radius", "Rayon de révolution"),
+    ("Nb section per segments", "Nb de sections par segment"),
+    ("Target length", "Longueur cible"),
+    ("Spring Stiffness", "Raideur des ressorts"),
+    ("Torsion Stiffness", "Raideur en torsion"),
+    ("Fluid Friction", "Friction du fluide"),
+    ("Ball Mass", "Masse des billes"),
+    ("Solving Method", "Méthode de résolution"),
+    ("Time Span", "Durée"),
+    ("Simulation Step", "Pas de simulation"),
+    ("Abort", "Abandonner"),
+    ("Finish", "Terminer"),
+    ("Start", "Démarrer"),
+    ("Cancel", "Annuler"),
+    ("Nucleotides (csv)", "Nucléotides (csv)"),
+    ("Nucleotides (json)", "Nucléotides (json)"),
+    // Open dialog
+    ("Open design", "Ouvrir un design"),
+    ("Choose folder", "Choisir un dossier"),
+    ("Other file", "Autre fichier"),
+    ("Reading design files", "Lecture des fichiers de design"),
+    (
+        "No design file in this folder",
+        "Aucun fichier de design dans ce dossier",
+    ),
+    ("Strand color", "Couleur du brin"),
+    ("Close", "Fermer"),
+    ("Add", "Ajouter"),
+    ("All", "Tout"),
+    ("Clear", "Effacer"),
+    ("Copy diagnostics", "Copier le diagnostic"),
+    (
+        "Undo history: {}/{} operations, redo: {}",
+        "Historique : {}/{} opérations, rétablir : {}",
+    ),
+    ("Memory used: {} MiB", "Mémoire utilisée : {} Mio"),
+    ("Create block", "Créer le bloc"),
+    ("Floating picker", "Sélecteur flottant"),
+    ("Go", "Aller"),
+    ("Hide Sequences", "Masquer les séquences"),
+    ("Show Sequences", "Afficher les séquences"),
+    ("Next", "Suivant"),
+    ("Previous", "Précédent"),
+    ("Reset", "Réinitialiser"),
+    ("Show", "Afficher"),
+    ("Stop", "Arrêter"),
+    ("Everything visible", "Tout afficher"),
+    ("Remove last", "Retirer le dernier"),
+    ("Selected", "Sélection"),
+    (
+        "Toggle NonSelected Visibility",
+        "Basculer la visibilité du reste",
+    ),
+    (
+        "Toggle Selected Visibility",
+        "Basculer la visibilité de la sélection",
+    ),
+    (
+        "Add double strand on helix",
+        "Ajouter un double brin sur l'hélice",
+    ),
+    ("All helices", "Toutes les hélices"),
+    ("Alternate orientation", "Alterner l'orientation"),
+    ("Bundle cross-overs", "Regrouper les cross-overs"),
+    (
+        "Color cross-overs by length",
+        "Colorer les cross-overs selon leur longueur",
+    ),
+    ("Contact shadow", "Ombre de contact"),
+    ("Cross-overs as arcs", "Cross-overs en arcs"),
+    ("Domain boundaries", "Limites des domaines"),
+    (
+        "Follow stereographic camera",
+        "Suivre la caméra stéréographique",
+    ),
+    (
+        "Show stereographic camera",
+        "Afficher la caméra stéréographique",
+    ),
+    ("Ground grid", "Grille au sol"),
+    (
+        "Include Xover ends",
+        "Inclure les extrémités des cross-overs",
+    ),
+    ("Include scaffold", "Inclure le scaffold"),
+    ("Intra strand suggestions", "Suggestions intra-brin"),
+    ("Inverse direction", "Inverser la direction"),
+    ("Position numbers", "Numéros de position"),
+    ("Rainbow Scaffold", "Scaffold arc-en-ciel"),
+    ("Scaffold raster", "Trame du scaffold"),
+    ("Show scale bar", "Afficher la barre d'échelle"),
+    ("Unmatched nt jiggling", "Agitation des nt non appariés"),
+    ("Volume exclusion", "Exclusion de volume"),
+    // Contextual panel
+    ("ENSnano website", "Site web d'ENSnano"),
+    ("{} objects selected", "{} objets sélectionnés"),
+    ("Loopout", "Boucle"),
+    ("Helix block", "Bloc d'hélices"),
+    ("Name", "Nom"),
+    ("length {}", "longueur {}"),
+    ("Starting nt", "Nt de départ"),
+    ("Length (nt)", "Longueur (nt)"),
+    ("3D view", "Vue 3D"),
+    ("2D/3D view", "Vue 2D/3D"),
+    ("2D view", "Vue 2D"),
+    ("Multiple select", "Sélection multiple"),
+    (
+        "Center selection in 2D view",
+        "Centrer la sélection dans la vue 2D",
+    ),
+    ("Translate camera", "Translater la caméra"),
+    ("Set pivot", "Placer le pivot"),
+    (
+        "Rotate camera around pivot (preserve the XZ plane)",
+        "Tourner la caméra autour du pivot (préserve le plan XZ)",
+    ),
+    (
+        "Rotate camera freely around pivot",
+        "Tourner librement la caméra autour du pivot",
+    ),
+    // Status bar
+    (
+        "(Press enter to validate change)",
+        "(Appuyez sur entrée pour valider)",
+    ),
+    // Dialogs and notifications
+    ("Open canceled", "Ouverture annulée"),
+    ("Save canceled", "Enregistrement annulé"),
+    ("OxDNA export canceled", "Export OxDNA annulé"),
+    ("Scaffold setting canceled", "Choix du scaffold annulé"),
+    ("Staple export canceled", "Export des agrafes annulé"),
+    ("OxDNA export failed", "L'export OxDNA a échoué"),
+    (
+        "Could not save design",
+        "Impossible d'enregistrer le design",
+    ),
+    (
+        "Do you want to save your design before exiting?",
+        "Voulez-vous enregistrer votre design avant de quitter ?",
+    ),
+    (
+        "Do you want to save your design before loading an other one?",
+        "Voulez-vous enregistrer votre design avant d'en ouvrir un autre ?",
+    ),
+    (
+        "Do you want to save your changes in an other file before reloading?",
+        "Voulez-vous enregistrer vos modifications dans un autre fichier avant de recharger ?",
+    ),
+    (
+        "Do you want to save your design before starting a new one?",
+        "Voulez-vous enregistrer votre design avant d'en commencer un nouveau ?",
+    ),
+    (
+        "No design selected, select a design by selecting one of its elements",
+        "Aucun design sélectionné, sélectionnez un design en sélectionnant l'un de ses éléments",
+    ),
+    (
+        "Are you sure that you want to change DNA parameters?",
+        "Êtes-vous sûr de vouloir modifier les paramètres de l'ADN ?",
+    ),
+    (
+        "The design cannot be modified in viewer mode",
+        "Le design ne peut pas être modifié en mode lecture",
+    ),
+    (
+        "The simulation has expired and was stopped",
+        "La simulation a expiré et a été arrêtée",
+    ),
+    ("Background jobs", "Tâches en arrière-plan"),
+    ("Roll selection", "Rouler la sélection"),
+    ("Twist grid", "Tordre la grille"),
+    ("Relax grids", "Relaxer les grilles"),
+    ("No running job", "Aucune tâche en cours"),
+    ("Queued", "En attente"),
+    ("Running", "En cours"),
+    ("Paused", "En pause"),
+    ("Pause", "Pause"),
+    ("Resume", "Reprendre"),
+    ("Iteration", "Itération"),
+    ("residual", "résidu"),
+    ("Accept current shape", "Accepter la forme actuelle"),
+    ("Threads", "Threads"),
+    ("All cores", "Tous les cœurs"),
+    ("Search from", "Chercher à partir de"),
+    ("Search to", "Chercher jusqu'à"),
+    ("End of sequence", "Fin de la séquence"),
+    ("Plugins", "Extensions"),
+    ("Run", "Exécuter"),
+    ("Compare designs", "Comparer des designs"),
+    (
+        "Compare with last save",
+        "Comparer avec la dernière sauvegarde",
+    ),
+    ("Compare with file", "Comparer avec un fichier"),
+    ("Stop comparing", "Arrêter la comparaison"),
+    ("Checkpoints", "Points de sauvegarde"),
+    ("Checkpoint name", "Nom du point de sauvegarde"),
+    ("Create checkpoint", "Créer un point de sauvegarde"),
+    ("Restore", "Restaurer"),
+    ("Compare", "Comparer"),
+    ("Delete", "Supprimer"),
+    ("Selected group", "Groupe sélectionné"),
+    ("Hide", "Cacher"),
+    ("Lock", "Verrouiller"),
+    ("Unlock", "Déverrouiller"),
+    ("Recolor", "Recolorer"),
+    ("Simulate", "Simuler"),
+    ("This group does not exist", "Ce groupe n'existe pas"),
+    (
+        "This group has no strand",
+        "Ce groupe ne contient aucun brin",
+    ),
+    ("Pivot", "Pivot"),
+    ("Set", "Appliquer"),
+    ("Snap to centroid", "Aligner sur un centroïde"),
+    ("Reset to center", "Recentrer"),
+    (
+        "This group has no element with a position",
+        "Ce groupe ne contient aucun élément positionné",
+    ),
+    ("Inspect from inside", "Inspecter de l'intérieur"),
+    ("Brush", "Pinceau"),
+    ("Paint with brush", "Peindre au pinceau"),
+    ("Shape", "Forme"),
+    ("Effect", "Effet"),
+    (
+        "The strands are painted with the color of the color picker",
+        "Les brins sont peints avec la couleur du sélecteur",
+    ),
+    (
+        "Select a group in the organizer to add nucleotides to it",
+        "Sélectionnez un groupe dans l'organiseur pour y ajouter des nucléotides",
+    ),
+    (
+        "Paint occluded nucleotides",
+        "Peindre les nucléotides cachés",
+    ),
+    ("Cross-over guides", "Guides de cross-overs"),
+    ("Insertions and skips", "Insertions et délétions"),
+    ("Relax lattice twist", "Relâcher la torsion du réseau"),
+    ("Reset roll to ideal", "Rétablir le roulis idéal"),
+    ("Twist free helices", "Torsader les hélices libres"),
+    ("Length unit", "Unité de longueur"),
+    ("Set axis from selection", "Axe depuis la sélection"),
+    ("Remove axis", "Supprimer l'axe"),
+    ("Radius of curvature", "Rayon de courbure"),
+    ("Bending direction", "Direction de courbure"),
+    ("Bend", "Courber"),
+    ("Pool", "Pool"),
+    ("Concentration (nM)", "Concentration (nM)"),
+    ("Filter", "Filtrer"),
+    ("Select all", "Tout sélectionner"),
+    ("Length", "Longueur"),
+    ("Color", "Couleur"),
+    ("Unamed strand", "Brin sans nom"),
+    ("Find by well", "Trouver par puits"),
+    ("Highlight", "Mettre en évidence"),
+    ("Hide everything else", "Cacher tout le reste"),
+    ("Unreadable wells: {}", "Puits illisibles : {}"),
+    ("{} staple(s) found", "{} agrafe(s) trouvée(s)"),
+    ("and {} other staple(s)", "et {} autre(s) agrafe(s)"),
+    ("Analysis overlay", "Superposition d'analyse"),
+    ("Nick density", "Densité de coupures"),
+    ("Cross-over density", "Densité de cross-overs"),
+    ("Seed domain strength", "Force des domaines d'ancrage"),
+    ("None", "Aucune"),
+    ("Helix bundles", "Faisceaux d'hélices"),
+    ("Bundle name", "Nom du faisceau"),
+    (
+        "Bundle selected helices",
+        "Regrouper les hélices sélectionnées",
+    ),
+    ("{} ({} helices)", "{} ({} hélices)"),
+    ("Bundle {}", "Faisceau {}"),
+    ("Select", "Sélectionner"),
+    ("Rename", "Renommer"),
+    ("Joints", "Articulations"),
+    (
+        "Select two grids or helix bundles",
+        "Sélectionner deux grilles ou faisceaux d'hélices",
+    ),
+    ("Hinge", "Charnière"),
+    ("Slider", "Glissière"),
+    ("Ball", "Rotule"),
+    ("{} between {} and {}", "{} entre {} et {}"),
+    ("grid {}", "grille {}"),
+    ("grid {}:{}", "grille {}:{}"),
+    ("bundle {}", "faisceau {}"),
+    ("Strand ends", "Extrémités des brins"),
+    ("5' end", "Extrémité 5'"),
+    ("3' end", "Extrémité 3'"),
+    ("No direction cones", "Pas de cônes de direction"),
+    (
+        "Direction cones every {} bonds",
+        "Cônes de direction toutes les {} liaisons",
+    ),
+    (
+        "Insertion length badges",
+        "Badges de longueur des insertions",
+    ),
+    ("Same group only", "Même groupe uniquement"),
+    ("Maximal distance", "Distance maximale"),
+    ("Color by distance", "Couleur selon la distance"),
+    (
+        "Cross-over twist strain",
+        "Contrainte de torsion des cross-overs",
+    ),
+    ("Helix", "Hélice"),
+    (
+        "No cross-over on a lattice",
+        "Aucun cross-over sur une grille",
+    ),
+    ("skip(s)", "saut(s)"),
+    ("insertion(s)", "insertion(s)"),
+    (
+        "Optimize staple breakpoints",
+        "Optimiser les coupures des agrafes",
+    ),
+    ("GC content: {}% to {}%", "Teneur en GC : {}% à {}%"),
+    (
+        "Forbidden motifs, e.g. GAATTC, GGATCC",
+        "Motifs interdits, par ex. GAATTC, GGATCC",
+    ),
+    (
+        "Fill unassigned sequences",
+        "Compléter les séquences manquantes",
+    ),
+    ("Clear sequences", "Effacer les séquences"),
+    (
+        "Assign to selected domains",
+        "Assigner aux domaines sélectionnés",
+    ),
+    ("Note", "Note"),
+    ("Link", "Lien"),
+    ("Open", "Ouvrir"),
+    (
+        "Check scaffold pairing",
+        "Vérifier l'appariement du scaffold",
+    ),
+    ("Add helix pair", "Ajouter une paire d'hélices"),
+    ("Number of vertices", "Nombre de sommets"),
+    ("Resample", "Rééchantillonner"),
+    ("Smooth", "Lisser"),
+    ("Simplify", "Simplifier"),
+    (
+        "Curvature too high, minimum radius:",
+        "Courbure trop forte, rayon minimal :",
+    ),
+];
@@ -22,11 +22,15 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use std::path::PathBuf;
 
 use ensnano_design::{
+    design_operations::{ControlPointsEdit, HelixNumbering},
     elements::{DesignElementKey, DnaAttribute},
-    grid::{GridDescriptor, GridId, GridObject, GridTypeDescr, HelixGridPosition, Hyperboloid},
+    grid::{
+        GridDescriptor, GridId, GridObject, GridTypeDescr, HelixGridPosition, Hyperboloid,
+        PhantomParameters,
+    },
     group_attributes::GroupPivot,
-    BezierPathId, BezierPlaneDescriptor, BezierPlaneId, BezierVertex, BezierVertexId,
-    CurveDescriptor2D, HelixParameters, Isometry3, Nucl,
+    BezierPathId, BezierPlaneDescriptor, BezierPlaneId, BezierVertex, BezierVertexId, BundleId,
+    CurveDescriptor2D, HelixParameters, Isometry3, JointBody, JointId, JointKind, Nucl,
 };
 use serde::{Deserialize, Serialize};
 use ultraviolet::{Isometry2, Rotor3, Vec2, Vec3};
@@ -39,9 +43,15 @@ pub mod operation;
 mod strand_builder;
 pub use strand_builder::*;
 pub mod consts;
+pub mod i18n;
+pub mod plugin;
 pub mod torsion;
 use ensnano_organizer::GroupId;
+mod design_browser;
+mod design_template;
 mod operation_labels;
+pub use design_browser::{DesignBrowserChoice, DesignBrowserContent, DesignFileInfo};
+pub use design_template::DesignTemplate;
 mod surfaces;
 pub use surfaces::*;
 
@@ -132,10 +142,18 @@ pub enum DesignOperation {
         start: isize,
         length: usize,
     },
+    /// Add a double strand going from `start` to `start + length` on an existing helix
+    AddDoubleStrand {
+        helix: usize,
+        start: isize,
+        length: usize,
+    },
     AddTwoPointsBezier {
         start: HelixGridPosition,
         end: HelixGridPosition,
     },
+    /// Add a rectangular block of helices on a grid
+    AddHelixBlock(HelixBlockDescriptor),
     RmHelices {
         h_ids: Vec<usize>,
     },
@@ -210,6 +228,16 @@ pub enum DesignOperation {
         grid_ids: Vec<GridId>,
         small: bool,
     },
+    /// Show or hide the lattice coordinates and helix numbers of grids in the 3D view
+    SetGridLabels {
+        grid_ids: Vec<GridId>,
+        labelled: bool,
+    },
+    /// Set the length, direction and opacity of the persistent phantom helices of grids
+    SetPhantomParameters {
+        grid_ids: Vec<GridId>,
+        parameters: PhantomParameters,
+    },
     /// Apply a translation to the 2d representation of helices holding each pivot
     SnapHelices {
         pivots: Vec<(Nucl, usize)>,
@@ -238,10 +266,21 @@ pub enum DesignOperation {
         helices: Vec<usize>,
         roll: f32,
     },
+    /// Set the roll of each helix to the one that minimizes the length of its cross-overs
+    SetIdealRollHelices {
+        helices: Vec<usize>,
+    },
     SetVisibilityHelix {
         helix: usize,
         visible: bool,
     },
+    /// Assign display numbers to all the helices of the design following a numbering scheme
+    RenumberHelices(HelixNumbering),
+    /// Set the number with which an helix is displayed. `None` displays the helix identifier.
+    SetHelixDisplayNumber {
+        helix: usize,
+        number: Option<usize>,
+    },
     FlipHelixGroup {
         helix: usize,
     },
@@ -259,10 +298,41 @@ pub enum DesignOperation {
         s_id: usize,
         name: String,
     },
+    /// Set the oligo pool in which a strand is mixed
+    SetStrandPool {
+        s_id: usize,
+        pool: Option<ensnano_design::OligoPool>,
+    },
+    /// Set the free text note attached to a strand
+    SetStrandNote {
+        s_id: usize,
+        note: Option<String>,
+    },
+    /// Set the link to an external description of a strand
+    SetStrandUrl {
+        s_id: usize,
+        url: Option<String>,
+    },
     SetGroupPivot {
         group_id: GroupId,
         pivot: GroupPivot,
     },
+    /// Make a bundle of helices that are moved as a single rigid object
+    CreateHelixBundle {
+        name: String,
+        helices: Vec<usize>,
+    },
+    DeleteHelixBundle(BundleId),
+    RenameHelixBundle {
+        bundle_id: BundleId,
+        name: String,
+    },
+    /// Add a kinematic joint between two grids or helix bundles
+    AddJoint {
+        bodies: (JointBody, JointBody),
+        kind: JointKind,
+    },
+    DeleteJoint(JointId),
     DeleteCamera(ensnano_design::CameraId),
     CreateNewCamera {
         position: Vec3,
@@ -279,6 +349,25 @@ pub enum DesignOperation {
         camera_id: ensnano_design::CameraId,
         name: String,
     },
+    /// Exchange the positions of two cameras in the list of cameras
+    SwapCameras(ensnano_design::CameraId, ensnano_design::CameraId),
+    /// Store (or forget) the rendering settings to apply when going to a camera
+    SetCameraRendering {
+        camera_id: ensnano_design::CameraId,
+        rendering: Option<ensnano_design::CameraRenderingSettings>,
+    },
+    /// Append a step to the presentation of the design
+    AddPresentationStep {
+        camera_position: Vec3,
+        camera_orientation: Rotor3,
+        pivot_position: Option<Vec3>,
+        hidden_nucls: Vec<Nucl>,
+    },
+    DeletePresentationStep(usize),
+    SetPresentationStepName {
+        step: usize,
+        name: String,
+    },
     SetGridPosition {
         grid_id: GridId,
         position: Vec3,
@@ -298,7 +387,46 @@ pub enum DesignOperation {
     CheckXovers {
         xovers: Vec<usize>,
     },
+    /// Move cross-overs along their helices, adjusting the lengths of the domains that they join
+    RespaceXovers {
+        moves: Vec<ensnano_design::design_operations::XoverRespacing>,
+    },
+    /// Place insertions and skips along helices to bend them or to relax the twist imposed by
+    /// their lattice
+    PlaceInsertions {
+        helices: Vec<usize>,
+        target: ensnano_design::design_operations::InsertionPlacementTarget,
+    },
+    /// Move nicks between staples along their helices, adjusting the lengths of the domains that
+    /// end at the nicks
+    MoveNicks {
+        moves: Vec<ensnano_design::design_operations::NickMove>,
+    },
+    /// Remove the sequences of a set of strands
+    ClearSequences {
+        strand_ids: Vec<usize>,
+    },
+    /// Write the bases given by the scaffold sequence in the domains `domain_ids` of the scaffold
+    /// and in the domains paired to them, and remove the scaffold sequence
+    AssignScaffoldSequenceToDomains {
+        domain_ids: Vec<usize>,
+    },
+    /// Set the sequences of the domains of strands
+    SetDomainSequences {
+        sequences: Vec<ensnano_design::design_operations::StrandSequences>,
+    },
+    /// Skip the nucleotides `nucls`, that are the two nucleotides of each deleted base pair
+    RmBasePairs {
+        nucls: Vec<Nucl>,
+    },
+    /// Insert a nucleotide next to each nucleotide of `nucls`, that are the two nucleotides of
+    /// each base pair next to which a base pair is inserted
+    InsertBasePairs {
+        nucls: Vec<Nucl>,
+    },
     SetRainbowScaffold(bool),
+    /// Set the author, description, license and notes of the design
+    SetDesignMetadata(ensnano_design::DesignMetadata),
     SetGlobalHelixParameters {
         helix_parameters: HelixParameters,
     },
@@ -329,6 +457,18 @@ pub enum DesignOperation {
         path_id: BezierPathId,
         grid_type: GridTypeDescr,
     },
+    /// Add a pair of helices along a bezier path, with a scaffold and staples if `with_strands`
+    /// is true
+    AddHelicesAlongBezierPath {
+        path_id: BezierPathId,
+        with_strands: bool,
+    },
+    /// Resample, smooth or simplify the vertices of a bezier path, keeping its first and last
+    /// vertices in place
+    EditBezierPathControlPoints {
+        path_id: BezierPathId,
+        edit: ControlPointsEdit,
+    },
     ApplyHomothethyOnBezierPlane {
         homothethy: BezierPlaneHomothethy,
     },
@@ -367,6 +507,23 @@ pub struct InsertionPoint {
     pub nucl_is_prime5_of_insertion: bool,
 }
 
+/// An operation applied to all the members of a group at once
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum GroupOperation {
+    /// Show or hide the helices and grids of the group
+    SetVisibility(bool),
+    /// Lock or unlock the helices of the group for simulations
+    SetLocked(bool),
+    /// Give the same new color to all the strands of the group
+    Recolor,
+    /// Move the pivot of the group to a given position
+    SetPivotPosition(Vec3),
+    /// Move the pivot of the group to the centroid of the member that is the closest to it
+    SnapPivotToCentroid,
+    /// Move the pivot of the group to the center of the group's bounding box
+    ResetPivot,
+}
+
 /// An action performed on the application
 pub enum AppOperation {
     /// Adjust the camera so that the design fit the view
@@ -438,6 +595,53 @@ pub struct GridHelixDescriptor {
     pub y: isize,
 }
 
+/// A rectangular block of helices to be created on a grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HelixBlockDescriptor {
+    pub grid_id: GridId,
+    /// The grid coordinates of the first helix of the block
+    pub x: isize,
+    pub y: isize,
+    pub nb_rows: usize,
+    pub nb_columns: usize,
+    /// The position of the first nucleotide of the strands on each helix
+    pub start: isize,
+    /// The length of the strands on each helix. If 0, the helices are created without strands
+    pub length: usize,
+    /// If true, the direction of the strand linking the helices alternates from one helix to the
+    /// next so that consecutive helices are joined by short cross-overs. This has no effect if
+    /// `scaffold_raster` is false.
+    pub alternate_orientation: bool,
+    /// Link the helices of the block with a single strand going back and forth through them
+    pub scaffold_raster: bool,
+}
+
+impl HelixBlockDescriptor {
+    /// The grid positions of the helices of the block, in the order in which the raster goes
+    /// through them.
+    ///
+    /// The rows are visited one after the other, and the direction in which the columns are
+    /// visited alternates from one row to the next so that consecutive helices are neighbours.
+    pub fn raster_positions(&self) -> Vec<HelixGridPosition> {
+        let mut ret = Vec::with_capacity(self.nb_rows * self.nb_columns);
+        for row in 0..self.nb_rows {
+            for column in 0..self.nb_columns {
+                let column = if row % 2 == 0 {
+                    column
+                } else {
+                    self.nb_columns - 1 - column
+                };
+                ret.push(HelixGridPosition::from_grid_id_x_y(
+                    self.grid_id,
+                    self.x + column as isize,
+                    self.y + row as isize,
+                ));
+            }
+        }
+        ret
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HyperboloidRequest {
     pub radius: usize,
@@ -460,11 +664,44 @@ impl HyperboloidRequest {
     }
 }
 
+/// The axis around which free helices are twisted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TwistAxis {
+    pub origin: Vec3,
+    /// A unit vector giving the direction of the axis
+    pub direction: Vec3,
+}
+
+impl TwistAxis {
+    /// The length of the segment representing the axis in the 3D scene
+    pub const DISPLAY_LENGTH: f32 = 20.;
+
+    /// The position of the handle used to set the direction of the axis
+    pub fn tip(&self) -> Vec3 {
+        self.origin + Self::DISPLAY_LENGTH * self.direction
+    }
+
+    /// The axis going through `origin` and `tip`, or `None` if the two points are too close
+    pub fn from_origin_and_tip(origin: Vec3, tip: Vec3) -> Option<Self> {
+        let direction = tip - origin;
+        if direction.mag() < 1e-3 {
+            None
+        } else {
+            Some(Self {
+                origin,
+                direction: direction.normalized(),
+            })
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RollRequest {
     pub roll: bool,
     pub springs: bool,
     pub target_helices: Option<Vec<usize>>,
+    /// The identifiers of the cross-overs used as springs. If `None`, all cross-overs are used
+    pub target_xovers: Option<Vec<usize>>,
 }
 
 #[derive(Clone, Debug)]
@@ -500,14 +737,40 @@ pub struct ScaffoldInfo {
     pub starting_nucl: Option<Nucl>,
 }
 
+/// Counts of the elements of a design, displayed to the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DesignStatistics {
+    pub nb_helices: usize,
+    pub nb_strands: usize,
+    /// The number of strands that are not the scaffold
+    pub nb_staples: usize,
+    pub nb_nucleotides: usize,
+}
+
+impl std::fmt::Display for DesignStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} helices, {} strands ({} staples), {} nt",
+            self.nb_helices, self.nb_strands, self.nb_staples, self.nb_nucleotides
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SimulationState {
     None,
     Rolling,
     RigidGrid,
     RigidHelices,
+    /// The helix bundles are simulated as rigid bodies
+    RigidBundles,
     Paused,
-    Twisting { grid_id: GridId },
+    Twisting {
+        grid_id: GridId,
+    },
+    /// Free helices are being twisted around a user defined axis
+    TwistingHelices,
     Relaxing,
 }
 
@@ -528,6 +791,10 @@ impl SimulationState {
         matches!(self, Self::RigidHelices)
     }
 
+    pub fn simulating_bundles(&self) -> bool {
+        matches!(self, Self::RigidBundles)
+    }
+
     pub fn is_paused(&self) -> bool {
         matches!(self, Self::Paused)
     }
@@ -543,29 +810,123 @@ impl Default for SimulationState {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// A simulation that can run in the background, simultaneously with other simulations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationJobKind {
+    Roll {
+        target_helices: Option<Vec<usize>>,
+        target_xovers: Option<Vec<usize>>,
+    },
+    Twist {
+        grid_id: GridId,
+    },
+    RelaxGrids,
+}
+
+impl std::fmt::Display for SimulationJobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Roll {
+                target_helices: Some(helices),
+                target_xovers: None,
+            } => write!(f, "Roll {} helices", helices.len()),
+            Self::Roll {
+                target_helices: None,
+                target_xovers: None,
+            } => write!(f, "Roll all helices"),
+            Self::Roll {
+                target_xovers: Some(xovers),
+                ..
+            } => write!(f, "Roll on {} cross-overs", xovers.len()),
+            Self::Twist { grid_id } => write!(f, "Twist grid {:?}", grid_id),
+            Self::RelaxGrids => write!(f, "Relax grids"),
+        }
+    }
+}
+
+/// The state of a simulation job, as displayed in the job manager.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationJobInfo {
+    pub id: usize,
+    pub name: String,
+    pub status: SimulationJobStatus,
+    /// The advancement of the job between 0 and 1, if the simulation has a known end
+    pub progress: Option<f32>,
+    /// The number of simulation steps that have been applied to the design
+    pub nb_steps: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationJobStatus {
+    /// The job waits for another job to finish
+    Queued,
+    Running,
+    /// The job is running but its results are not applied to the design
+    Paused,
+}
+
+/// Settings of the optimization of the starting position of the scaffold
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShiftOptimizationParameters {
+    /// The number of threads used by the optimization, 0 to use all the available cores.
+    pub nb_threads: usize,
+    /// The first position that is tried
+    pub range_start: usize,
+    /// The position at which the search stops (excluded). If `None`, all the positions until the
+    /// end of the scaffold sequence are tried.
+    pub range_end: Option<usize>,
+}
+
+/// The reference frame in which the translation and rotation widgets are oriented.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum WidgetBasis {
     World,
+    /// The basis of the selected helix or grid.
     Object,
+    /// The basis of the camera, the widget's axes follow the screen's axes.
+    Screen,
 }
 
+pub const ALL_WIDGET_BASES: [WidgetBasis; 3] =
+    [WidgetBasis::World, WidgetBasis::Object, WidgetBasis::Screen];
+
 impl WidgetBasis {
     pub fn toggle(&mut self) {
-        if self.is_axis_aligned() {
-            *self = WidgetBasis::Object
-        } else {
-            *self = WidgetBasis::World
+        *self = match self {
+            Self::World => Self::Object,
+            Self::Object => Self::Screen,
+            Self::Screen => Self::World,
         };
     }
 
     pub fn is_axis_aligned(&self) -> bool {
         match self {
             Self::World => true,
-            Self::Object => false,
+            Self::Object | Self::Screen => false,
         }
     }
 }
 
+impl std::fmt::Display for WidgetBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::World => "World",
+            Self::Object => "Local",
+            Self::Screen => "Screen",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
+/// An axis of the widget basis to which translations and rotations are constrained.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GizmoAxis {
+    X,
+    Y,
+    Z,
+}
+
 impl Default for WidgetBasis {
     fn default() -> Self {
         Self::World
@@ -599,12 +960,22 @@ impl PastingStatus {
 }
 
 /// Parameters of strand suggestions
-#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SuggestionParameters {
     pub include_scaffold: bool,
     pub include_intra_strand: bool,
     pub include_xover_ends: bool,
     pub ignore_groups: bool,
+    /// Only suggest cross-overs between helices of the same helix bundle or, for helices that do
+    /// not belong to a bundle, of the same grid.
+    pub same_group_only: bool,
+    /// The maximal distance, in nanometers, between the two nucleotides of a suggested
+    /// cross-over.
+    pub max_distance: f32,
+    pub style: SuggestionStyle,
+    /// Color the suggestions according to the distance between their nucleotides
+    pub color_by_distance: bool,
 }
 
 impl Default for SuggestionParameters {
@@ -614,11 +985,77 @@ impl Default for SuggestionParameters {
             include_scaffold: true,
             include_xover_ends: false,
             ignore_groups: false,
+            same_group_only: false,
+            max_distance: Self::LARGEST_MAX_DISTANCE,
+            style: SuggestionStyle::Straight,
+            color_by_distance: false,
         }
     }
 }
 
+/// The way suggested cross-overs are drawn
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SuggestionStyle {
+    /// Straight tubes in the 3D view, colored circles on the nucleotides in the 2D view
+    Straight,
+    /// Dashed arcs bulging away from the helices that they link, in both views
+    DashedArcs,
+}
+
+pub const ALL_SUGGESTION_STYLES: [SuggestionStyle; 2] =
+    [SuggestionStyle::Straight, SuggestionStyle::DashedArcs];
+
+impl std::fmt::Display for SuggestionStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ret = match self {
+            Self::Straight => "Straight",
+            Self::DashedArcs => "Dashed arcs",
+        };
+        write!(f, "{}", ret)
+    }
+}
+
 impl SuggestionParameters {
+    /// The largest value that `max_distance` can take. Nucleotides further apart than this are
+    /// never considered for suggestions.
+    pub const LARGEST_MAX_DISTANCE: f32 = 1.2;
+    pub const MAX_DISTANCE_RANGE: std::ops::RangeInclusive<f32> = 0.4..=Self::LARGEST_MAX_DISTANCE;
+
+    /// The color of a suggestion whose nucleotides are `distance` nanometers apart, going from
+    /// green for the closest suggestions to red for those at `max_distance`.
+    pub fn heat_color(&self, distance: f32) -> u32 {
+        let t = (distance / self.max_distance.max(f32::EPSILON))
+            .min(1.)
+            .max(0.);
+        let red = (255. * t.min(0.5) * 2.) as u32;
+        let green = (255. * ((1. - t) * 2.).min(1.)) as u32;
+        0xFF_00_00_00 | (red << 16) | (green << 8) | 0x20
+    }
+
+    pub fn with_same_group_only(&self, same_group_only: bool) -> Self {
+        let mut ret = self.clone();
+        ret.same_group_only = same_group_only;
+        ret
+    }
+
+    pub fn with_max_distance(&self, max_distance: f32) -> Self {
+        let mut ret = self.clone();
+        ret.max_distance = max_distance;
+        ret
+    }
+
+    pub fn with_style(&self, style: SuggestionStyle) -> Self {
+        let mut ret = self.clone();
+        ret.style = style;
+        ret
+    }
+
+    pub fn with_color_by_distance(&self, color_by_distance: bool) -> Self {
+        let mut ret = self.clone();
+        ret.color_by_distance = color_by_distance;
+        ret
+    }
+
     pub fn with_include_scaffod(&self, include_scaffold: bool) -> Self {
         let mut ret = self.clone();
         ret.include_scaffold = include_scaffold;
@@ -28,7 +28,21 @@ impl DesignOperation {
                 format!("Translation of {}", translation.target.to_string()).into()
             }
             Self::AddGridHelix { .. } => "Helix creation".into(),
+            Self::AddDoubleStrand { helix, .. } => {
+                format!("Double strand creation on helix {}", helix).into()
+            }
             Self::AddTwoPointsBezier { .. } => "Bezier curve creation".into(),
+            Self::AddHelicesAlongBezierPath { .. } => "Helices along bezier path".into(),
+            Self::EditBezierPathControlPoints { edit, .. } => match edit {
+                ControlPointsEdit::Resample { .. } => "Resample bezier path".into(),
+                ControlPointsEdit::Smooth { .. } => "Smooth bezier path".into(),
+                ControlPointsEdit::Simplify { .. } => "Simplify bezier path".into(),
+            },
+            Self::AddHelixBlock(block) => format!(
+                "Creation of a {}x{} block of helices",
+                block.nb_rows, block.nb_columns
+            )
+            .into(),
             Self::RmHelices { .. } => "Helix deletion".into(),
             Self::RmXovers { .. } => "Xover deletion".into(),
             Self::Cut { nucl, .. } => format!("Cut on {:?}", nucl).into(),
@@ -57,12 +71,27 @@ impl DesignOperation {
             Self::UpdateAttribute { .. } => "Update attribute from organizer".into(),
             Self::SetSmallSpheres { small: true, .. } => "Hide nucleotides".into(),
             Self::SetSmallSpheres { small: false, .. } => "Show nucleotides".into(),
+            Self::SetGridLabels { labelled: true, .. } => "Show grid labels".into(),
+            Self::SetGridLabels {
+                labelled: false, ..
+            } => "Hide grid labels".into(),
+            Self::SetPhantomParameters { .. } => "Set phantom helices parameters".into(),
             Self::SnapHelices { .. } => "Move 2D helices".into(),
             Self::RotateHelices { .. } => "Translate 2D helices".into(),
             Self::SetIsometry { .. } => "Set isometry of helices".into(),
             Self::RequestStrandBuilders { nucls } => format!("Build on {:?}", nucls).into(),
             Self::MoveBuilders(_) => "Move builders".into(),
             Self::SetRollHelices { .. } => "Set roll of helix".into(),
+            Self::SetIdealRollHelices { .. } => "Reset roll of helices to ideal".into(),
+            Self::RenumberHelices(_) => "Renumber helices".into(),
+            Self::SetHelixDisplayNumber { .. } => "Set helix number".into(),
+            Self::CreateHelixBundle { .. } => "Create helix bundle".into(),
+            Self::DeleteHelixBundle(_) => "Delete helix bundle".into(),
+            Self::RenameHelixBundle { .. } => "Rename helix bundle".into(),
+            Self::AddJoint { kind, .. } => {
+                format!("Add {} joint", kind.to_string().to_lowercase()).into()
+            }
+            Self::DeleteJoint(_) => "Delete joint".into(),
             Self::SetVisibilityHelix { visible: true, .. } => "Make helices visible".into(),
             Self::SetVisibilityHelix { visible: false, .. } => "Make helices invisible".into(),
             Self::FlipHelixGroup { .. } => "Change xover group of helices".into(),
@@ -70,12 +99,30 @@ impl DesignOperation {
             Self::AttachObject { .. } => "Move grid object".into(),
             Self::SetOrganizerTree(_) => "Update organizer tree".into(),
             Self::SetStrandName { .. } => "Update name of strand".into(),
+            Self::SetStrandPool { .. } => "Update pool of strand".into(),
+            Self::SetStrandNote { .. } => "Update note of strand".into(),
+            Self::SetStrandUrl { .. } => "Update link of strand".into(),
             Self::SetGroupPivot { .. } => "Set group pivot".into(),
             Self::DeleteCamera(_) => "Delete camera".into(),
             Self::CreateNewCamera { .. } => "Create camera shortcut".into(),
+            Self::AddPresentationStep { .. } => "Add presentation step".into(),
+            Self::DeletePresentationStep(_) => "Delete presentation step".into(),
             Self::SetGridPosition { .. } => "Set grid position".into(),
             Self::SetGridOrientation { .. } => "Set grid orientation".into(),
             Self::MakeSeveralXovers { .. } => "Multiple xovers".into(),
+            Self::RespaceXovers { moves } => format!("Respacing of {} xovers", moves.len()).into(),
+            Self::PlaceInsertions { .. } => "Place insertions and skips".into(),
+            Self::ClearSequences { .. } => "Clear sequences".into(),
+            Self::AssignScaffoldSequenceToDomains { .. } => {
+                "Assign scaffold sequence to domains".into()
+            }
+            Self::SetDomainSequences { sequences } => {
+                format!("Sequences of {} strands", sequences.len()).into()
+            }
+            Self::MoveNicks { moves } => format!("Optimization of {} nicks", moves.len()).into(),
+            Self::RmBasePairs { .. } => "Delete base pairs".into(),
+            Self::InsertBasePairs { .. } => "Insert base pairs".into(),
+            Self::SetDesignMetadata(_) => "Update design metadata".into(),
             _ => "Unamed operation".into(),
         }
     }
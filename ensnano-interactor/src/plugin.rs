@@ -0,0 +1,145 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The interface between ENSnano and third-party tools loaded at runtime.
+//!
+//! A plugin is a dynamic library that depends on this crate and exports its implementation of
+//! [Plugin] with the [declare_plugin] macro. Plugins are loaded at start up from the
+//! `ENSnano/plugins` subdirectory of the user's configuration directory.
+//!
+//! Because the trait objects cross the library boundary, the plugin must be compiled with the
+//! same version of the compiler and of this crate as ENSnano. Both are checked through symbols
+//! with a C ABI before any Rust function of the plugin is called.
+
+use super::*;
+
+/// Incremented each time the [Plugin] trait changes. Plugins built for an other version are not
+/// loaded.
+pub const PLUGIN_API_VERSION: u32 = 2;
+
+/// The version of the compiler that built this crate, as printed by `rustc --version`.
+pub const RUSTC_VERSION: &str = env!("ENSNANO_RUSTC_VERSION");
+
+/// [RUSTC_VERSION] as a nul-terminated string, returned by the function exported by
+/// [declare_plugin].
+#[doc(hidden)]
+pub const RUSTC_VERSION_NUL_TERMINATED: &str = concat!(env!("ENSNANO_RUSTC_VERSION"), "\0");
+
+/// A tool that reads the design and proposes modifications.
+pub trait Plugin {
+    /// The name of the plugin, used as the title of its section in the GUI
+    fn name(&self) -> String;
+
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    /// The values that the user must provide when running the plugin. If it is not empty, the
+    /// plugin gets a section with one input per parameter in the GUI.
+    fn parameters(&self) -> Vec<PluginParameter> {
+        Vec::new()
+    }
+
+    /// Run the plugin. `values` contains the value of each parameter, in the order in which they
+    /// were returned by [Plugin::parameters].
+    fn run(&mut self, context: &dyn PluginContext, values: &[String]) -> PluginOutput;
+}
+
+/// What a plugin can read from ENSnano.
+pub trait PluginContext {
+    fn design(&self) -> &dyn DesignReader;
+    fn selection(&self) -> &[Selection];
+}
+
+/// What a plugin can read from the design.
+pub trait DesignReader {
+    fn get_all_helix_ids(&self) -> Vec<usize>;
+    fn get_all_strand_ids(&self) -> Vec<usize>;
+    fn get_scaffold_id(&self) -> Option<usize>;
+    fn get_strand_name(&self, s_id: usize) -> Option<String>;
+    fn get_strand_color(&self, s_id: usize) -> Option<u32>;
+    /// The nucleotides of a strand, from its 5' end to its 3' end. Insertions are skipped.
+    fn get_strand_nucls(&self, s_id: usize) -> Option<Vec<Nucl>>;
+    /// The base of a nucleotide, if a sequence was assigned to it
+    fn get_base(&self, nucl: Nucl) -> Option<char>;
+    /// The position of a nucleotide in the world coordinates
+    fn get_nucl_position(&self, nucl: Nucl) -> Option<Vec3>;
+    fn get_design_statistics(&self) -> DesignStatistics;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginParameter {
+    pub name: String,
+    pub default_value: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PluginOutput {
+    /// The operations to apply on the design. They are applied in order.
+    pub operations: Vec<DesignOperation>,
+    /// A message displayed to the user when the plugin has finished.
+    pub message: Option<String>,
+}
+
+/// The description of a loaded plugin, used by the GUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginDescription {
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    pub parameters: Vec<PluginParameter>,
+}
+
+/// The signature of the function exported by [declare_plugin].
+pub type PluginConstructor = fn() -> Box<dyn Plugin>;
+
+/// The name of the symbol exported by [declare_plugin].
+pub const PLUGIN_CONSTRUCTOR_SYMBOL: &[u8] = b"ensnano_create_plugin";
+
+/// The name of the symbol containing the [PLUGIN_API_VERSION] the plugin was built for.
+pub const PLUGIN_API_VERSION_SYMBOL: &[u8] = b"ENSNANO_PLUGIN_API_VERSION";
+
+/// The signature of the function returning the [RUSTC_VERSION] the plugin was built with.
+pub type PluginRustcVersion = extern "C" fn() -> *const std::os::raw::c_char;
+
+/// The name of the symbol exported by [declare_plugin] with the [PluginRustcVersion] signature.
+pub const PLUGIN_RUSTC_VERSION_SYMBOL: &[u8] = b"ensnano_plugin_rustc_version";
+
+/// Export the constructor of a plugin.
+///
+/// ```ignore
+/// ensnano_interactor::declare_plugin!(MyPlugin, MyPlugin::default);
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($plugin_type:ty, $constructor:path) => {
+        #[no_mangle]
+        pub static ENSNANO_PLUGIN_API_VERSION: u32 = $crate::plugin::PLUGIN_API_VERSION;
+
+        #[no_mangle]
+        pub extern "C" fn ensnano_plugin_rustc_version() -> *const std::os::raw::c_char {
+            $crate::plugin::RUSTC_VERSION_NUL_TERMINATED.as_ptr() as *const std::os::raw::c_char
+        }
+
+        #[no_mangle]
+        pub fn ensnano_create_plugin() -> Box<dyn $crate::plugin::Plugin> {
+            let plugin: $plugin_type = $constructor();
+            Box::new(plugin)
+        }
+    };
+}
@@ -296,6 +296,31 @@ pub fn list_of_xover_as_nucl_pairs(
     Some((design_id as usize, xovers.into_iter().collect()))
 }
 
+/// Convert a selection of nucleotides into the list of the nucleotides of the base pairs that they
+/// belong to
+pub fn list_of_base_pairs(
+    selection: &[Selection],
+    reader: &dyn DesignReader,
+) -> Option<(usize, Vec<Nucl>)> {
+    let design_id = selection.get(0).and_then(Selection::get_design)?;
+    let mut nucls = BTreeSet::new();
+    for s in selection.iter() {
+        match s {
+            Selection::Nucleotide(d_id, nucl) => {
+                if *d_id != design_id {
+                    return None;
+                }
+                nucls.insert(*nucl);
+                if let Some(paired) = reader.get_paired_nucl(*nucl) {
+                    nucls.insert(paired);
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some((design_id as usize, nucls.into_iter().collect()))
+}
+
 pub fn list_of_helices(selection: &[Selection]) -> Option<(usize, Vec<usize>)> {
     let design_id = selection.get(0).and_then(Selection::get_design)?;
     let mut helices = BTreeSet::new();
@@ -456,6 +481,8 @@ pub enum SelectionMode {
     Strand,
     Helix,
     Design,
+    /// Select nucleotides together with the nucleotide they are paired with
+    BasePair,
 }
 
 impl Default for SelectionMode {
@@ -474,17 +501,19 @@ impl std::fmt::Display for SelectionMode {
                 SelectionMode::Nucleotide => "Nucleotide",
                 SelectionMode::Strand => "Strand",
                 SelectionMode::Helix => "Helix",
+                SelectionMode::BasePair => "Base pair",
             }
         )
     }
 }
 
 impl SelectionMode {
-    pub const ALL: [SelectionMode; 4] = [
+    pub const ALL: [SelectionMode; 5] = [
         SelectionMode::Nucleotide,
         SelectionMode::Design,
         SelectionMode::Strand,
         SelectionMode::Helix,
+        SelectionMode::BasePair,
     ];
 }
 
@@ -537,6 +566,26 @@ impl ActionMode {
     pub fn is_build(&self) -> bool {
         matches!(self, Self::Build(_) | Self::BuildHelix { .. })
     }
+
+    /// In the `BuildHelix` mode, the position and length of the double strand after pressing
+    /// `+` (if `increase` is true) or `-`. The position is shifted if `shift_position` is true,
+    /// otherwise the length is changed.
+    pub fn adjusted_double_strand(
+        &self,
+        increase: bool,
+        shift_position: bool,
+    ) -> Option<(isize, usize)> {
+        if let Self::BuildHelix { position, length } = *self {
+            let delta = if increase { 1 } else { -1 };
+            if shift_position {
+                Some((position + delta, length))
+            } else {
+                Some((position, (length as isize + delta).max(0) as usize))
+            }
+        } else {
+            None
+        }
+    }
 }
 
 //
@@ -627,6 +676,8 @@ pub trait DesignReader {
     fn get_strand_with_id(&self, id: usize) -> Option<&Strand>;
     fn get_helix_grid(&self, h_id: usize) -> Option<GridId>;
     fn get_domain_ends(&self, s_id: usize) -> Option<Vec<Nucl>>;
+    /// Return the nucleotide paired with `nucl`, if it exists
+    fn get_paired_nucl(&self, nucl: Nucl) -> Option<Nucl>;
 }
 
 pub trait SelectionConversion: Sized {
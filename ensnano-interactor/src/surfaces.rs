@@ -401,6 +401,16 @@ impl Default for RevolutionSimulationParameters {
     }
 }
 
+/// The advancement of the relaxation of a revolution surface system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RevolutionRelaxationProgress {
+    /// The number of simulation steps that have been computed
+    pub iteration: usize,
+    /// The largest ratio between the length of a spring and its rest length. The system is
+    /// relaxed when the residual is close to 1.
+    pub residual: f64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EquadiffSolvingMethod {
     Euler,
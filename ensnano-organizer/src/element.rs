@@ -36,6 +36,12 @@ pub trait OrganizerElement: Clone + std::fmt::Debug + 'static {
         vec![self.display_name()]
     }
 
+    /// A size of the element (e.g. the length of a strand) that can be used in searches of the
+    /// form `>60` or `<10`
+    fn size(&self) -> Option<usize> {
+        None
+    }
+
     fn attributes(&self) -> Vec<Self::Attribute>;
 
     fn all_repr() -> &'static [<Self::Attribute as OrganizerAttribute>::Repr] {
@@ -15,6 +15,7 @@ extern crate serde;
 mod drag_drop_target;
 pub mod element;
 mod hoverable_button;
+mod search;
 pub mod theme;
 mod tree;
 
@@ -26,6 +27,7 @@ pub use tree::{GroupId, OrganizerTree};
 use drag_drop_target::*;
 
 use hoverable_button::HoverableContainer;
+use search::SearchFilter;
 
 const LEVEL0_V_SPACING: u16 = 3;
 const LEVELS_V_SPACING: u16 = 2;
@@ -160,6 +162,18 @@ impl<E: OrganizerElement> OrganizerMessage<E> {
             id,
         }))
     }
+
+    fn search_input(query: String) -> Self {
+        Self::InternalMessage(InternalMessage(OrganizerMessage_::SearchInput { query }))
+    }
+
+    fn select_matches() -> Self {
+        Self::InternalMessage(InternalMessage(OrganizerMessage_::SelectMatches))
+    }
+
+    fn group_matches() -> Self {
+        Self::InternalMessage(InternalMessage(OrganizerMessage_::GroupMatches))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -202,6 +216,13 @@ enum OrganizerMessage_<E: OrganizerElement> {
         attribute: E::Attribute,
         id: NodeId<E::AutoGroup>,
     },
+    SearchInput {
+        query: String,
+    },
+    /// Select all the elements that match the search query
+    SelectMatches,
+    /// Create a new group with all the elements that match the search query
+    GroupMatches,
 }
 
 pub struct Organizer<E: OrganizerElement> {
@@ -221,6 +242,11 @@ pub struct Organizer<E: OrganizerElement> {
     last_read_tree: *const OrganizerTree<E::Key>,
     must_update_tree: bool,
     group_to_node: HashMap<GroupId, NodeId<E::AutoGroup>>,
+    search_query: String,
+    search_filter: Option<SearchFilter>,
+    search_input: text_input::State,
+    select_matches_button: button::State,
+    group_matches_button: button::State,
 }
 
 impl<E: OrganizerElement> Organizer<E> {
@@ -253,6 +279,11 @@ impl<E: OrganizerElement> Organizer<E> {
             last_read_tree: std::ptr::null(),
             must_update_tree: false,
             group_to_node: HashMap::new(),
+            search_query: String::new(),
+            search_filter: None,
+            search_input: Default::default(),
+            select_matches_button: Default::default(),
+            group_matches_button: Default::default(),
         }
     }
 
@@ -270,6 +301,7 @@ impl<E: OrganizerElement> Organizer<E> {
 
     pub fn view(&mut self, selection: BTreeSet<E::Key>) -> Element<OrganizerMessage<E>> {
         self.hovered_in = None;
+        let filter = self.search_filter.as_ref();
         let mut ret = Scrollable::new(&mut self.scroll_state)
             .width(self.width)
             .spacing(LEVEL0_V_SPACING);
@@ -281,6 +313,7 @@ impl<E: OrganizerElement> Organizer<E> {
                         &self.sections,
                         &selection,
                         &self.selected_nodes,
+                        filter,
                     )
                     .width(iced::Length::FillPortion(8)),
                 ),
@@ -289,7 +322,7 @@ impl<E: OrganizerElement> Organizer<E> {
         for s in self.sections.iter_mut() {
             ret = ret.push(
                 Row::new().push(tabulation()).push(
-                    s.view(&self.theme, &selection)
+                    s.view(&self.theme, &selection, filter)
                         .width(iced::Length::FillPortion(8)),
                 ),
             )
@@ -297,7 +330,7 @@ impl<E: OrganizerElement> Organizer<E> {
         for s in self.auto_groups.values_mut() {
             ret = ret.push(
                 Row::new().push(tabulation()).push(
-                    s.view(&self.theme, &selection)
+                    s.view(&self.theme, &selection, filter)
                         .width(iced::Length::FillPortion(8)),
                 ),
             )
@@ -312,7 +345,29 @@ impl<E: OrganizerElement> Organizer<E> {
             tooltip::Position::FollowCursor,
         );
         let title_row = Row::new().push(new_group_tooltip);
-        let column = Column::new().push(title_row).push(ret);
+        let search_input = TextInput::new(
+            &mut self.search_input,
+            "Search (name, >60, <10)...",
+            &self.search_query,
+            OrganizerMessage::search_input,
+        );
+        let mut select_matches_button =
+            Button::new(&mut self.select_matches_button, Text::new("Select matches"));
+        let mut group_matches_button =
+            Button::new(&mut self.group_matches_button, Text::new("Group matches"));
+        if self.search_filter.is_some() {
+            select_matches_button =
+                select_matches_button.on_press(OrganizerMessage::select_matches());
+            group_matches_button = group_matches_button.on_press(OrganizerMessage::group_matches());
+        }
+        let search_row = Row::new()
+            .push(select_matches_button)
+            .push(group_matches_button);
+        let column = Column::new()
+            .push(title_row)
+            .push(search_input)
+            .push(search_row)
+            .push(ret);
         Container::new(column).style(self.theme.level(0)).into()
     }
 
@@ -444,10 +499,43 @@ impl<E: OrganizerElement> Organizer<E> {
                 let keys = self.get_keys_below(id);
                 return Some(OrganizerMessage::NewAttribute(attribute.clone(), keys));
             }
+            OrganizerMessage_::SearchInput { query } => {
+                self.search_filter = SearchFilter::parse(query);
+                self.search_query = query.clone();
+            }
+            OrganizerMessage_::SelectMatches => {
+                return Some(OrganizerMessage::Selection(self.search_matches(), None));
+            }
+            OrganizerMessage_::GroupMatches => {
+                let matches = self.search_matches();
+                if !matches.is_empty() {
+                    let group_name = self.search_query.trim().to_string();
+                    let new_group_id = self.push_content(matches.clone(), group_name);
+                    return Some(OrganizerMessage::NewGroup {
+                        new_tree: self.tree(),
+                        group_id: new_group_id,
+                        elements_selected: matches,
+                    });
+                }
+            }
         }
         None
     }
 
+    /// The keys of the elements that match the current search query
+    fn search_matches(&self) -> Vec<E::Key> {
+        if let Some(filter) = self.search_filter.as_ref() {
+            self.sections
+                .iter()
+                .flat_map(|s| s.content.values())
+                .filter(|e| filter.matches(*e))
+                .map(|e| e.key())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
     fn hover(
         &mut self,
         id: &NodeId<E::AutoGroup>,
@@ -590,7 +678,7 @@ impl<E: OrganizerElement> Organizer<E> {
     }
 
     pub fn has_keyboard_priority(&self) -> bool {
-        self.editing.is_some()
+        self.editing.is_some() || self.search_input.is_focused()
     }
 
     fn stop_editing(&mut self) {
@@ -914,25 +1002,27 @@ impl<E: OrganizerElement> Section<E> {
         &mut self,
         theme: &Theme,
         selection: &BTreeSet<E::Key>,
+        filter: Option<&SearchFilter>,
     ) -> Container<OrganizerMessage<E>> {
+        // When searching, the sections are expanded to show the matching elements.
+        let expanded = self.expanded || filter.is_some();
         let title_row = self
             .view
-            .view(theme, &self.name, self.id.clone(), self.expanded, false);
+            .view(theme, &self.name, self.id.clone(), expanded, false);
         let mut ret = Column::new()
             .spacing(LEVELS_V_SPACING)
             .push(Element::new(title_row));
-        if self.expanded {
+        if expanded {
             for (e_id, e) in self.elements.iter_mut() {
+                let element = &self.content[e_id];
+                if filter.map_or(false, |f| !f.matches(element)) {
+                    continue;
+                }
                 ret = ret.push(
                     Row::new().push(tabulation()).push(
-                        Container::new(Element::new(e.view(
-                            theme,
-                            &self.content[e_id],
-                            selection,
-                            None,
-                        )))
-                        .style(theme.level(1))
-                        .width(iced::Length::FillPortion(8)),
+                        Container::new(Element::new(e.view(theme, element, selection, None)))
+                            .style(theme.level(1))
+                            .width(iced::Length::FillPortion(8)),
                     ),
                 )
             }
@@ -1263,6 +1353,7 @@ impl<E: OrganizerElement> GroupContent<E> {
         sections: &[Section<E>],
         selection: &BTreeSet<E::Key>,
         selected_nodes: &BTreeSet<NodeId<E::AutoGroup>>,
+        filter: Option<&SearchFilter>,
     ) -> Container<OrganizerMessage<E>> {
         let level;
         let colummn = match self {
@@ -1288,7 +1379,7 @@ impl<E: OrganizerElement> GroupContent<E> {
                     for c in children.iter_mut() {
                         ret = ret.push(
                             Row::new().push(tabulation()).push(
-                                c.view(theme, sections, selection, selected_nodes)
+                                c.view(theme, sections, selection, selected_nodes, filter)
                                     .width(iced::Length::FillPortion(8)),
                             ),
                         )
@@ -1305,6 +1396,9 @@ impl<E: OrganizerElement> GroupContent<E> {
                     0
                 };
                 if let Some(element) = get_element(sections, element) {
+                    if filter.map_or(false, |f| !f.matches(element)) {
+                        return Container::new(Column::new());
+                    }
                     Column::new()
                         .spacing(LEVELS_V_SPACING)
                         .push(Element::new(view.view(
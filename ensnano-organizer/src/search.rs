@@ -0,0 +1,39 @@
+use super::OrganizerElement;
+
+/// A query typed in the search bar of the organizer.
+///
+/// Queries of the form `>60` or `<10` compare the size of the elements (e.g. the length of a
+/// strand), other queries are matched against the aliases of the elements, ignoring case.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SearchFilter {
+    Text(String),
+    SizeAbove(usize),
+    SizeBelow(usize),
+}
+
+impl SearchFilter {
+    /// Parse a query. Return `None` if the query is empty.
+    pub fn parse(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.is_empty() {
+            None
+        } else if let Some(size) = query.strip_prefix('>').and_then(|n| n.trim().parse().ok()) {
+            Some(Self::SizeAbove(size))
+        } else if let Some(size) = query.strip_prefix('<').and_then(|n| n.trim().parse().ok()) {
+            Some(Self::SizeBelow(size))
+        } else {
+            Some(Self::Text(query.to_lowercase()))
+        }
+    }
+
+    pub fn matches<E: OrganizerElement>(&self, element: &E) -> bool {
+        match self {
+            Self::Text(text) => element
+                .aliases()
+                .iter()
+                .any(|alias| alias.to_lowercase().contains(text.as_str())),
+            Self::SizeAbove(size) => element.size().map_or(false, |s| s > *size),
+            Self::SizeBelow(size) => element.size().map_or(false, |s| s < *size),
+        }
+    }
+}
@@ -167,6 +167,73 @@ impl<K: Eq + Hash + Copy> OrganizerTree<K> {
         }
         return hashmap;
     }
+
+    /// Return all the elements that are in the group with identifier `group_id`, including the
+    /// ones that are in its sub-groups. Return `None` if there is no such group in the tree.
+    pub fn get_elements_of_group(&self, group_id: GroupId) -> Option<Vec<K>> {
+        match self {
+            Self::Leaf(_) => None,
+            Self::Node { children, id, .. } => {
+                if *id == Some(group_id) {
+                    let mut ret = Vec::new();
+                    self.collect_leaves(&mut ret);
+                    Some(ret)
+                } else {
+                    children
+                        .iter()
+                        .find_map(|c| c.get_elements_of_group(group_id))
+                }
+            }
+        }
+    }
+
+    /// Add `elements` to the group with identifier `group_id`. The elements that are already
+    /// directly in the group are not added a second time. Return `false` if there is no such
+    /// group in the tree.
+    pub fn add_elements_to_group(&mut self, group_id: GroupId, elements: &[K]) -> bool {
+        match self {
+            Self::Leaf(_) => false,
+            Self::Node { children, id, .. } => {
+                if *id == Some(group_id) {
+                    let mut present: HashSet<K> = children
+                        .iter()
+                        .filter_map(|c| {
+                            if let Self::Leaf(k) = c {
+                                Some(*k)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
+                    for e in elements {
+                        if present.insert(*e) {
+                            children.push(Self::Leaf(*e))
+                        }
+                    }
+                    true
+                } else {
+                    children
+                        .iter_mut()
+                        .any(|c| c.add_elements_to_group(group_id, elements))
+                }
+            }
+        }
+    }
+
+    fn collect_leaves(&self, leaves: &mut Vec<K>) {
+        match self {
+            Self::Leaf(k) => {
+                if !leaves.contains(k) {
+                    leaves.push(*k)
+                }
+            }
+            Self::Node { children, .. } => {
+                for c in children {
+                    c.collect_leaves(leaves)
+                }
+            }
+        }
+    }
 }
 
 // For compatibility reasons, we need to implement Deserialize ourselved for OrganizerTree.
@@ -121,6 +121,11 @@ impl Projection {
         self.fovy
     }
 
+    /// Set the field of view, in *radiants*
+    pub fn set_fovy(&mut self, fovy: f32) {
+        self.fovy = fovy;
+    }
+
     pub fn get_ratio(&self) -> f32 {
         self.aspect
     }
@@ -17,8 +17,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 use super::view::HandleColors;
 use super::{
-    camera, ultraviolet, Duration, ElementSelector, HandleDir, SceneElement, Stereography, ViewPtr,
-    WidgetRotationMode as RotationMode,
+    camera, ultraviolet, Duration, ElementSelector, HandleDir, HelixEnd, RollDial, SceneElement,
+    Stereography, ViewPtr, WidgetRotationMode as RotationMode,
 };
 use crate::{PhySize, PhysicalPosition, WindowEvent};
 use ensnano_design::grid::{GridId, GridObject, GridPosition, HelixGridPosition};
@@ -26,7 +26,7 @@ use ensnano_design::{
     BezierPathId, BezierPlaneId, BezierVertex, BezierVertexId, Nucl, SurfaceInfo, SurfacePoint,
 };
 use ensnano_interactor::consts::*;
-use ensnano_interactor::Selection;
+use ensnano_interactor::{ActionMode, Selection, TwistAxis};
 use ensnano_utils::winit::event::*;
 use std::cell::RefCell;
 use std::ops::Deref;
@@ -97,6 +97,11 @@ pub enum Consequence {
     Redo,
     Candidate(Option<super::SceneElement>),
     PivotElement(Option<super::SceneElement>),
+    /// A face of the direction cube was clicked. Look at the scene from the side of this face,
+    /// whose outward normal is given.
+    ViewFromAxis(Vec3),
+    /// Open a context menu for the element under the cursor
+    ContextMenu(Option<super::SceneElement>),
     ElementSelected(Option<super::SceneElement>, bool),
     MoveFreeXover(Option<super::SceneElement>, Vec3),
     EndFreeXover,
@@ -111,6 +116,8 @@ pub enum Consequence {
     PasteCandidate(Option<super::SceneElement>),
     Paste(Option<super::SceneElement>),
     DoubleClick(Option<super::SceneElement>),
+    /// Center the other views on the nucleotide under the cursor
+    CenterOtherView(Option<super::SceneElement>),
     InitBuild(Vec<Nucl>),
     ObjectTranslated {
         object: GridObject,
@@ -153,6 +160,31 @@ pub enum Consequence {
     },
     ReverseSurfaceDirection,
     SetRevolutionAxisPosition(f32),
+    /// The nucleotides painted since the beginning of the brush stroke
+    BrushMoved(Vec<Nucl>),
+    /// The brush stroke is over, its effect must be applied to the painted nucleotides
+    BrushReleased(Vec<Nucl>),
+    /// The roll dial of a helix is being dragged
+    HelixRolled {
+        helix_id: usize,
+        roll: f32,
+    },
+    HelixRollReleased,
+    /// One of the handles of the twist axis is being dragged
+    TwistAxisMoved(TwistAxis),
+    /// The badge of an insertion was clicked, its length must be edited
+    EditInsertionLength(super::SceneElement),
+    /// A double strand must be added on an existing helix
+    AddDoubleStrand {
+        helix: usize,
+        start: isize,
+        length: usize,
+    },
+    /// The parameters of the double strands created in helix building mode were adjusted
+    DoubleStrandParametersChanged {
+        position: isize,
+        length: usize,
+    },
 }
 
 enum TransistionConsequence {
@@ -367,6 +399,31 @@ impl<S: AppState> Controller<S> {
                 VirtualKeyCode::W if *state == ElementState::Pressed => {
                     Consequence::ReverseSurfaceDirection
                 }
+                VirtualKeyCode::Plus
+                | VirtualKeyCode::NumpadAdd
+                | VirtualKeyCode::Equals
+                | VirtualKeyCode::Minus
+                | VirtualKeyCode::NumpadSubtract
+                    if *state == ElementState::Pressed
+                        && matches!(
+                            app_state.get_action_mode().0,
+                            ActionMode::BuildHelix { .. }
+                        ) =>
+                {
+                    let increase =
+                        !matches!(*key, VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract);
+                    app_state
+                        .get_action_mode()
+                        .0
+                        .adjusted_double_strand(increase, ctrl(&self.current_modifiers))
+                        .map(
+                            |(position, length)| Consequence::DoubleStrandParametersChanged {
+                                position,
+                                length,
+                            },
+                        )
+                        .unwrap_or(Consequence::Nothing)
+                }
                 _ => {
                     if self.camera_controller.process_keyboard(*key, *state) {
                         Consequence::CameraMoved
@@ -555,13 +612,22 @@ pub(super) trait Data {
         element: &Option<SceneElement>,
         non_phantom: bool,
     ) -> Option<(Nucl, usize)>;
+    fn double_strand_target(
+        &self,
+        element: &Option<SceneElement>,
+        start: isize,
+        length: usize,
+    ) -> Option<usize>;
     fn get_nucl_position(&self, nucl: Nucl, d_id: usize) -> Option<Vec3>;
+    fn get_all_nucls_with_position(&self) -> Vec<(Nucl, Vec3)>;
     fn attempt_xover(
         &self,
         source: &Option<SceneElement>,
         dest: &Option<SceneElement>,
     ) -> Option<(Nucl, Nucl, usize)>;
     fn can_start_builder(&self, element: Option<SceneElement>) -> Option<Nucl>;
+    fn get_helix_end_builder_nucls(&self, helix_id: usize, end: HelixEnd) -> Vec<Nucl>;
+    fn get_helix_roll_dial(&self, helix_id: usize) -> Option<RollDial>;
     fn get_grid_object(&self, position: GridPosition) -> Option<GridObject>;
     fn notify_rotating_pivot(&mut self);
     fn stop_rotating_pivot(&mut self);
@@ -129,6 +129,14 @@ impl<S: AppState> ControllerState<S> for NormalState {
                 let element = context.convert_grid_to_grid_disc(element);
                 Transition::consequence(Consequence::Candidate(element))
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if context.get_direction_cube_face().is_some() => {
+                let face = context.get_direction_cube_face().unwrap();
+                Transition::consequence(Consequence::ViewFromAxis(face))
+            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
@@ -146,6 +154,23 @@ impl<S: AppState> ControllerState<S> for NormalState {
                     consequences: Consequence::Nothing,
                 }
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if context.get_brush_parameters().active => {
+                let click_info = ClickInfo::new(MouseButton::Left, context.cursor_position);
+                let positions = context.get_paintable_nucls_with_position();
+                let painted = context.get_nucls_under_brush(&positions);
+                Transition {
+                    new_state: Some(Box::new(dragging_state::painting_brush(
+                        click_info,
+                        positions,
+                        painted.clone(),
+                    ))),
+                    consequences: Consequence::BrushMoved(painted),
+                }
+            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
@@ -170,6 +195,54 @@ impl<S: AppState> ControllerState<S> for NormalState {
             } => {
                 let element = context.get_element_under_cursor();
                 log::info!("Clicked on {:?}", element);
+                if let Some(SceneElement::HelixLengthHandle { helix_id, end }) = element {
+                    let nucls = context.get_helix_end_builder_nucls(helix_id, end);
+                    if !nucls.is_empty() {
+                        let click_info = ClickInfo::new(MouseButton::Left, context.cursor_position);
+                        return Transition {
+                            new_state: Some(Box::new(dragging_state::resizing_helix(
+                                click_info,
+                                nucls,
+                                context.get_bases_per_turn(),
+                            ))),
+                            consequences: Consequence::Nothing,
+                        };
+                    }
+                }
+                if let Some(SceneElement::HelixRollDial { helix_id }) = element {
+                    if let Some(dial) = context.get_helix_roll_dial(helix_id) {
+                        let click_info = ClickInfo::new(MouseButton::Left, context.cursor_position);
+                        return Transition {
+                            new_state: Some(Box::new(dragging_state::rolling_helix(
+                                click_info, helix_id, dial,
+                            ))),
+                            consequences: Consequence::Nothing,
+                        };
+                    }
+                }
+                if let Some(badge @ SceneElement::InsertionBadge { .. }) = element {
+                    return Transition::consequence(Consequence::EditInsertionLength(badge));
+                }
+                if let ActionMode::BuildHelix { position, length } = context.get_action_mode() {
+                    if let Some(helix) = context.double_strand_target(&element, position, length) {
+                        return Transition::consequence(Consequence::AddDoubleStrand {
+                            helix,
+                            start: position,
+                            length,
+                        });
+                    }
+                }
+                if let Some(SceneElement::TwistAxisHandle { tip }) = element {
+                    if let Some(axis) = context.get_twist_axis() {
+                        let click_info = ClickInfo::new(MouseButton::Left, context.cursor_position);
+                        return Transition {
+                            new_state: Some(Box::new(dragging_state::moving_twist_axis(
+                                click_info, tip, axis,
+                            ))),
+                            consequences: Consequence::Nothing,
+                        };
+                    }
+                }
                 if let Some(SceneElement::PlaneCorner {
                     plane_id,
                     corner_type,
@@ -494,6 +567,13 @@ impl<S: AppState> ControllerState<S> for NormalState {
                     }
                 }
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Middle,
+                ..
+            } if context.get_modifiers().shift() => Transition::consequence(
+                Consequence::CenterOtherView(context.get_element_under_cursor()),
+            ),
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Middle,
@@ -522,6 +602,13 @@ impl<S: AppState> ControllerState<S> for NormalState {
                     consequences: Consequence::Nothing,
                 }
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Right,
+                ..
+            } if ctrl(context.get_modifiers()) => Transition::consequence(
+                Consequence::ContextMenu(context.get_element_under_cursor()),
+            ),
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Right,
@@ -24,6 +24,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! the camera or moving an object.
 
 use ensnano_design::BezierVertexId;
+use std::collections::HashSet;
 
 use super::*;
 
@@ -500,6 +501,254 @@ pub(super) fn building_strands(
     }
 }
 
+/// The user is dragging a helix length handle.
+///
+/// The strands ending at the dragged end of the helix are extended or shrunk by a whole number of
+/// helical turns.
+pub(super) struct ResizingHelix {
+    to_initialize: Option<Vec<Nucl>>,
+    /// The initial position of the end of the helix
+    origin: isize,
+    bases_per_turn: f32,
+}
+
+impl DraggingTransitionTable for ResizingHelix {
+    fn description() -> &'static str {
+        "Resizing helix"
+    }
+
+    fn on_cursor_moved<S: AppState>(
+        &mut self,
+        cursor: DraggedCursor<'_, '_, S>,
+    ) -> Option<Consequence> {
+        if let Some(nucls) = self.to_initialize.take() {
+            Some(Consequence::InitBuild(nucls))
+        } else {
+            let position = cursor.context.get_new_build_position()?;
+            let nb_turns = ((position - self.origin) as f32 / self.bases_per_turn).round();
+            Some(Consequence::Building(
+                self.origin + (nb_turns * self.bases_per_turn).round() as isize,
+            ))
+        }
+    }
+
+    fn on_button_released(&self) -> Option<Consequence> {
+        Some(Consequence::BuildEnded)
+    }
+
+    fn cursor() -> Option<ensnano_interactor::CursorIcon> {
+        Some(CursorIcon::Grabbing)
+    }
+
+    no_csq_leaving_or_entering!();
+}
+
+/// `nucls` must not be empty. The first nucleotide is the one whose position is snapped to full
+/// turns.
+pub(super) fn resizing_helix(
+    click_info: ClickInfo,
+    nucls: Vec<Nucl>,
+    bases_per_turn: f32,
+) -> DraggingState<ResizingHelix> {
+    let origin = nucls.get(0).map(|n| n.position).unwrap_or_default();
+    let transition_table = ResizingHelix {
+        to_initialize: Some(nucls),
+        origin,
+        bases_per_turn,
+    };
+
+    DraggingState {
+        current_cursor_position: click_info.current_position,
+        clicked_position: click_info.current_position,
+        clicked_button: click_info.button,
+        transition_table,
+    }
+}
+
+/// The user is painting nucleotides with the brush.
+///
+/// Cursor movements add the nucleotides covered by the brush to the stroke. The effect of the
+/// brush is applied to all the painted nucleotides at once when the button is released.
+pub(super) struct PaintingBrush {
+    /// The position of all the nucleotides, computed once when the stroke begins
+    positions: Vec<(Nucl, Vec3)>,
+    painted: HashSet<Nucl>,
+}
+
+impl PaintingBrush {
+    fn painted_nucls(&self) -> Vec<Nucl> {
+        self.painted.iter().cloned().collect()
+    }
+}
+
+impl DraggingTransitionTable for PaintingBrush {
+    fn description() -> &'static str {
+        "Painting with brush"
+    }
+
+    fn on_cursor_moved<S: AppState>(
+        &mut self,
+        cursor: DraggedCursor<'_, '_, S>,
+    ) -> Option<Consequence> {
+        let len_before = self.painted.len();
+        self.painted
+            .extend(cursor.context.get_nucls_under_brush(&self.positions));
+        (self.painted.len() > len_before).then(|| Consequence::BrushMoved(self.painted_nucls()))
+    }
+
+    fn on_button_released(&self) -> Option<Consequence> {
+        Some(Consequence::BrushReleased(self.painted_nucls()))
+    }
+
+    fn cursor() -> Option<ensnano_interactor::CursorIcon> {
+        Some(CursorIcon::Crosshair)
+    }
+
+    no_csq_leaving_or_entering!();
+}
+
+/// Start a brush stroke, `painted` are the nucleotides covered by the brush when the button was
+/// pressed.
+pub(super) fn painting_brush(
+    click_info: ClickInfo,
+    positions: Vec<(Nucl, Vec3)>,
+    painted: Vec<Nucl>,
+) -> DraggingState<PaintingBrush> {
+    let transition_table = PaintingBrush {
+        positions,
+        painted: painted.into_iter().collect(),
+    };
+
+    DraggingState {
+        current_cursor_position: click_info.current_position,
+        clicked_position: click_info.clicked_position,
+        clicked_button: click_info.button,
+        transition_table,
+    }
+}
+
+/// The user is dragging the knob of the roll dial of a helix.
+///
+/// The helix is rolled by the angle, around its axis, between the needle of the dial and the
+/// cursor.
+pub(super) struct RollingHelix {
+    helix_id: usize,
+    /// The dial as it was when the mouse button was pressed
+    dial: RollDial,
+}
+
+impl DraggingTransitionTable for RollingHelix {
+    fn description() -> &'static str {
+        "Rolling helix"
+    }
+
+    fn on_cursor_moved<S: AppState>(
+        &mut self,
+        cursor: DraggedCursor<'_, '_, S>,
+    ) -> Option<Consequence> {
+        use std::f32::consts::{PI, TAU};
+        let axis = self.dial.axis;
+        let point = cursor
+            .context
+            .get_cursor_intersection_with_plane(self.dial.center, axis)?;
+        let direction = point - self.dial.center;
+        let direction = direction - direction.dot(axis) * axis;
+        if direction.mag() < 1e-3 {
+            return None;
+        }
+        let angle = self
+            .dial
+            .needle
+            .cross(direction)
+            .dot(axis)
+            .atan2(self.dial.needle.dot(direction));
+        // The nucleotides turn clockwise around the axis of the helix when its roll increases
+        let roll = (self.dial.roll - angle + PI).rem_euclid(TAU) - PI;
+        Some(Consequence::HelixRolled {
+            helix_id: self.helix_id,
+            roll,
+        })
+    }
+
+    fn on_button_released(&self) -> Option<Consequence> {
+        Some(Consequence::HelixRollReleased)
+    }
+
+    fn cursor() -> Option<ensnano_interactor::CursorIcon> {
+        Some(CursorIcon::Grabbing)
+    }
+
+    no_csq_leaving_or_entering!();
+}
+
+pub(super) fn rolling_helix(
+    click_info: ClickInfo,
+    helix_id: usize,
+    dial: RollDial,
+) -> DraggingState<RollingHelix> {
+    DraggingState {
+        current_cursor_position: click_info.current_position,
+        clicked_position: click_info.current_position,
+        clicked_button: click_info.button,
+        transition_table: RollingHelix { helix_id, dial },
+    }
+}
+
+/// The user is dragging one of the handles of the twist axis.
+///
+/// The handle follows the cursor on the plane facing the camera. Moving the origin translates
+/// the axis, moving the tip changes its direction.
+pub(super) struct MovingTwistAxis {
+    tip: bool,
+    /// The axis as it was when the mouse button was pressed
+    axis: TwistAxis,
+}
+
+impl DraggingTransitionTable for MovingTwistAxis {
+    fn description() -> &'static str {
+        "Moving twist axis"
+    }
+
+    fn on_cursor_moved<S: AppState>(
+        &mut self,
+        cursor: DraggedCursor<'_, '_, S>,
+    ) -> Option<Consequence> {
+        let new_axis = if self.tip {
+            let tip = cursor.context.get_projection_on_plane(self.axis.tip());
+            TwistAxis::from_origin_and_tip(self.axis.origin, tip)?
+        } else {
+            TwistAxis {
+                origin: cursor.context.get_projection_on_plane(self.axis.origin),
+                ..self.axis
+            }
+        };
+        Some(Consequence::TwistAxisMoved(new_axis))
+    }
+
+    fn on_button_released(&self) -> Option<Consequence> {
+        None
+    }
+
+    fn cursor() -> Option<ensnano_interactor::CursorIcon> {
+        Some(CursorIcon::Grabbing)
+    }
+
+    no_csq_leaving_or_entering!();
+}
+
+pub(super) fn moving_twist_axis(
+    click_info: ClickInfo,
+    tip: bool,
+    axis: TwistAxis,
+) -> DraggingState<MovingTwistAxis> {
+    DraggingState {
+        current_cursor_position: click_info.current_position,
+        clicked_position: click_info.current_position,
+        clicked_button: click_info.button,
+        transition_table: MovingTwistAxis { tip, axis },
+    }
+}
+
 pub(super) struct TranslatingWidget {
     direction: HandleDir,
     translation_target: WidgetTarget,
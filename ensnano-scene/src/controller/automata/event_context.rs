@@ -20,6 +20,8 @@ use crate::{element_selector::CornerType, view::GridIntersection};
 
 use super::*;
 use ensnano_design::{Axis, BezierPlaneIntersection};
+use ensnano_interactor::graphics::{BrushParameters, BrushShape};
+use std::collections::HashSet;
 
 const REVOLUTION_AXIS_WIDTH: f32 = 1.;
 
@@ -58,6 +60,15 @@ impl<'a, S: AppState> EventContext<'a, S> {
         }
     }
 
+    /// Return the outward normal of the face of the direction cube under the cursor, if any.
+    pub fn get_direction_cube_face(&self) -> Option<Vec3> {
+        self.controller.view.borrow().direction_cube_face(
+            self.cursor_position.x as f32,
+            self.cursor_position.y as f32,
+            self.controller.area_size,
+        )
+    }
+
     pub fn get_projection_on_plane(&self, plane_origin: Vec3) -> Vec3 {
         let normalized_cursor = self.normalized_cursor_position();
         self.controller.camera_controller.get_projection(
@@ -112,10 +123,95 @@ impl<'a, S: AppState> EventContext<'a, S> {
             .map(|(n, _)| n)
     }
 
+    /// Return the identifier of the helix on which `element` is, if a double strand going from
+    /// `start` to `start + length` can be added on it.
+    pub fn double_strand_target(
+        &self,
+        element: &Option<SceneElement>,
+        start: isize,
+        length: usize,
+    ) -> Option<usize> {
+        self.controller
+            .data
+            .borrow()
+            .double_strand_target(element, start, length)
+    }
+
     pub fn get_nucl_position(&self, nucl: Nucl) -> Option<Vec3> {
         self.controller.data.borrow().get_nucl_position(nucl, 0)
     }
 
+    /// Return the nucleotides that can be painted by the brush, with their position.
+    ///
+    /// Unless the brush paints occluded nucleotides, these are the nucleotides that are currently
+    /// visible in at least one pixel of the scene.
+    pub fn get_paintable_nucls_with_position(&mut self) -> Vec<(Nucl, Vec3)> {
+        let positions = self.controller.data.borrow().get_all_nucls_with_position();
+        if self.get_brush_parameters().paint_occluded {
+            positions
+        } else {
+            let visible: HashSet<Nucl> = self
+                .pixel_reader
+                .get_visible_design_elements()
+                .into_iter()
+                .filter_map(|e| self.element_to_nucl(&Some(e), true))
+                .collect();
+            positions
+                .into_iter()
+                .filter(|(n, _)| visible.contains(n))
+                .collect()
+        }
+    }
+
+    pub fn get_brush_parameters(&self) -> BrushParameters {
+        self.app_state.get_brush_parameters()
+    }
+
+    /// Return the nucleotides of `positions` that are covered by the brush at the current cursor
+    /// position.
+    ///
+    /// A screen-space brush covers all the nucleotides of `positions` that are drawn inside of
+    /// it, including the ones that are behind other nucleotides.
+    pub fn get_nucls_under_brush(&mut self, positions: &[(Nucl, Vec3)]) -> Vec<Nucl> {
+        let brush = self.get_brush_parameters();
+        match brush.shape {
+            BrushShape::Sphere => {
+                let element = self.get_element_under_cursor();
+                let center = if let Some(center) = self
+                    .element_to_nucl(&element, true)
+                    .and_then(|n| self.get_nucl_position(n))
+                {
+                    center
+                } else {
+                    return vec![];
+                };
+                positions
+                    .iter()
+                    .filter(|(_, p)| (*p - center).mag() <= brush.radius)
+                    .map(|(n, _)| *n)
+                    .collect()
+            }
+            BrushShape::Screen => {
+                let (origin, direction) = self.shoot_ray(self.cursor_position);
+                let border = PhysicalPosition {
+                    x: self.cursor_position.x + brush.radius as f64,
+                    y: self.cursor_position.y,
+                };
+                let (_, border_direction) = self.shoot_ray(border);
+                let direction = direction.normalized();
+                let min_cos = direction.dot(border_direction.normalized());
+                positions
+                    .iter()
+                    .filter(|(_, p)| {
+                        let to_nucl = *p - origin;
+                        to_nucl.mag() > 0. && to_nucl.normalized().dot(direction) >= min_cos
+                    })
+                    .map(|(n, _)| *n)
+                    .collect()
+            }
+        }
+    }
+
     /// If self is over a possible cross-over origin, return it.
     pub(super) fn get_xover_origin_under_cursor(&mut self) -> Option<XoverOrigin> {
         let element = self.get_element_under_cursor();
@@ -132,6 +228,43 @@ impl<'a, S: AppState> EventContext<'a, S> {
         self.controller.data.borrow().can_start_builder(element)
     }
 
+    /// Return the nucleotides on which strand builders must be started to drag the `end` of a
+    /// helix.
+    pub fn get_helix_end_builder_nucls(&self, helix_id: usize, end: HelixEnd) -> Vec<Nucl> {
+        self.controller
+            .data
+            .borrow()
+            .get_helix_end_builder_nucls(helix_id, end)
+    }
+
+    pub fn get_helix_roll_dial(&self, helix_id: usize) -> Option<RollDial> {
+        self.controller.data.borrow().get_helix_roll_dial(helix_id)
+    }
+
+    /// Return the intersection of the ray under the cursor with the plane going through `origin`
+    /// and orthogonal to `normal`, or None if the ray and the plane are parallel.
+    pub fn get_twist_axis(&self) -> Option<TwistAxis> {
+        self.app_state.get_twist_axis()
+    }
+
+    pub fn get_cursor_intersection_with_plane(&self, origin: Vec3, normal: Vec3) -> Option<Vec3> {
+        let (ray_origin, direction) = self.shoot_ray(self.cursor_position);
+        let denom = direction.dot(normal);
+        if denom.abs() > 1e-3 {
+            let mu = (origin - ray_origin).dot(normal) / denom;
+            Some(ray_origin + mu * direction)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_bases_per_turn(&self) -> f32 {
+        self.app_state
+            .get_design_reader()
+            .get_parameters()
+            .bases_per_turn
+    }
+
     /// Project the current cursor position on an axis
     pub fn get_projection_on_axis(&self, axis: Axis<'_>) -> Option<isize> {
         let normalized_cursor_position = self.normalized_cursor_position();
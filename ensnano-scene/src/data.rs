@@ -21,10 +21,11 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use crate::view::AvailableRotationAxes;
 
 use super::view::{
-    GridDisc, HandleColors, Instanciable, RawDnaInstance, StereographicSphereAndPlane,
+    GridDisc, Ground, HandleColors, Instanciable, RawDnaInstance, ScaleBar,
+    StereographicSphereAndPlane, GRID_LABEL_CHARS,
 };
 use super::{
-    ultraviolet, Camera3D, HandleOrientation, HandlesDescriptor, LetterInstance,
+    ultraviolet, Camera3D, HandleOrientation, HandlesDescriptor, HelixEnd, LetterInstance,
     RotationWidgetDescriptor, RotationWidgetOrientation, SceneElement, View, ViewUpdate,
 };
 use std::cell::RefCell;
@@ -32,21 +33,22 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 // use std::hash::RandomState;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ensnano_design::grid::GridObject;
 use ensnano_design::{BezierVertexId, Collection};
-use ensnano_interactor::graphics::{HBondDisplay, LoopoutNucl};
+use ensnano_interactor::graphics::{ColorVisionMode, HBondDisplay, LoopoutNucl};
 use ultraviolet::{Rotor3, Vec3};
 
 use super::view::Mesh;
 use ensnano_design::{
-    grid::{GridId, GridPosition},
+    grid::{GridId, GridPosition, PhantomParameters},
     Nucl,
 };
 use ensnano_interactor::consts::*;
 use ensnano_interactor::{
-    ActionMode, CenterOfSelection, ObjectType, PhantomElement, Referential, Selection,
-    SelectionMode,
+    ActionMode, CenterOfSelection, ElementType, ObjectType, PhantomElement, Referential, Selection,
+    SelectionMode, WidgetBasis, PHANTOM_RANGE,
 };
 
 use ensnano_utils::StrandNucleotidesPositions;
@@ -58,11 +60,13 @@ type ViewPtr = Rc<RefCell<View>>;
 /// A module that handles the instantiation of designs as 3D geometric objects
 mod design3d;
 use design3d::Design3D;
-pub use design3d::{DesignReader, HBond, HalfHBond, SurfaceInfo, SurfacePoint};
+pub use design3d::{DesignReader, HBond, HalfHBond, RollDial, SurfaceInfo, SurfacePoint};
 use ensnano_design::External3DObjectsStamp;
 
 pub struct Data<R: DesignReader> {
     view: ViewPtr,
+    /// The view whose layer overrides apply to this data.
+    view_element: ElementType,
     /// A `Design3D` is associated to each design.
     designs: Vec<Design3D<R>>,
     /// The set of candidates elements
@@ -89,12 +93,26 @@ pub struct Data<R: DesignReader> {
     stereographic_camera: Arc<(Camera3D, f32)>,
     stereographic_camera_need_update: bool,
     external_3d_objects_stamps: Option<External3DObjectsStamp>,
+    strand_trace: Option<StrandTrace>,
+    /// The instances that were last sent to the view for the meshes that are updated by ranges.
+    sent_instances: HashMap<Mesh, Rc<Vec<RawDnaInstance>>>,
+}
+
+/// A glow moving along a strand from its 5' end to its 3' end
+struct StrandTrace {
+    /// The positions of the nucleotides of the strand, from 5' to 3'
+    positions: Vec<Vec3>,
+    /// The number of nucleotides that the head of the glow has travelled
+    progress: f32,
+    /// The number of nucleotides travelled per second
+    speed: f32,
 }
 
 impl<R: DesignReader> Data<R> {
-    pub fn new(reader: R, view: ViewPtr) -> Self {
+    pub fn new(reader: R, view: ViewPtr, view_element: ElementType) -> Self {
         Self {
             view,
+            view_element,
             designs: vec![Design3D::new(reader, 0)],
             candidate_element: None,
             sub_selection_mode: SelectionMode::Nucleotide,
@@ -112,6 +130,8 @@ impl<R: DesignReader> Data<R> {
             stereographic_camera_need_update: false,
             external_3d_objects_stamps: None,
             surface_pivot_position: None,
+            strand_trace: None,
+            sent_instances: HashMap::default(),
         }
     }
 
@@ -135,6 +155,7 @@ impl<R: DesignReader> Data<R> {
         self.pivot_element = None;
         self.pivot_position = None;
         self.pivot_update = true;
+        self.sent_instances.clear();
         self.view.borrow_mut().clear_design();
     }
 }
@@ -142,18 +163,27 @@ impl<R: DesignReader> Data<R> {
 impl<R: DesignReader> Data<R> {
     /// Forwards all needed update to the view
     pub fn update_view<S: AppState>(&mut self, app_state: &S, older_app_state: &S) {
-        if self.discs_need_update(app_state, older_app_state) {
+        let discs_need_update = self.discs_need_update(app_state, older_app_state);
+        if discs_need_update {
             self.update_discs(app_state);
         }
+        if discs_need_update || app_state.get_action_mode() != older_app_state.get_action_mode() {
+            self.update_ghost_helix(app_state);
+        }
         if app_state.design_was_modified(older_app_state)
             || app_state.suggestion_parameters_were_updated(older_app_state)
             || app_state.draw_options_were_updated(older_app_state)
             || app_state.insertion_bond_display_was_modified(older_app_state)
             || app_state.selection_was_updated(older_app_state)
             || app_state.revolution_bezier_updated(older_app_state)
+            || app_state.get_color_vision_mode() != older_app_state.get_color_vision_mode()
+            || app_state.design_diff_was_updated(older_app_state)
         {
             for d in self.designs.iter_mut() {
                 d.all_helices_on_axis = app_state.get_draw_options().all_helices_on_axis;
+                d.ignore_visibility_sieve = app_state
+                    .get_view_layer(self.view_element)
+                    .ignore_visibility_sieve;
             }
             self.update_instances(app_state);
         }
@@ -170,19 +200,26 @@ impl<R: DesignReader> Data<R> {
             || app_state.design_was_modified(older_app_state)
             || app_state.get_check_xover_parameters()
                 != older_app_state.get_check_xover_parameters()
+            || app_state.get_color_vision_mode() != older_app_state.get_color_vision_mode()
         {
             self.update_selection(app_state.get_selection(), app_state);
         }
         self.handle_need_opdate |= app_state.design_was_modified(older_app_state)
             || app_state.selection_was_updated(older_app_state)
-            || app_state.get_action_mode() != older_app_state.get_action_mode();
+            || app_state.get_action_mode() != older_app_state.get_action_mode()
+            || app_state.get_gizmo_size() != older_app_state.get_gizmo_size()
+            || app_state.get_gizmo_axis_constraint() != older_app_state.get_gizmo_axis_constraint()
+            || app_state.get_twist_axis() != older_app_state.get_twist_axis();
 
         if self.handle_need_opdate {
             self.update_bezier(app_state);
+            self.update_helix_handles(app_state);
             self.update_handle(app_state);
             self.handle_need_opdate = false;
         }
-        if app_state.candidates_set_was_updated(older_app_state) {
+        if app_state.candidates_set_was_updated(older_app_state)
+            || app_state.get_color_vision_mode() != older_app_state.get_color_vision_mode()
+        {
             self.update_candidate(app_state.get_candidates(), app_state);
         }
         if self.pivot_update {
@@ -198,6 +235,18 @@ impl<R: DesignReader> Data<R> {
             self.update_matrices();
         }
 
+        if app_state.design_was_modified(older_app_state)
+            || app_state.get_ground_parameters() != older_app_state.get_ground_parameters()
+        {
+            self.update_ground(app_state);
+        }
+
+        if app_state.design_was_modified(older_app_state)
+            || app_state.get_scale_bar_parameters() != older_app_state.get_scale_bar_parameters()
+        {
+            self.update_scale_bar(app_state);
+        }
+
         self.update_external_3d_objects(app_state);
     }
 
@@ -276,6 +325,111 @@ impl<R: DesignReader> Data<R> {
             .update(ViewUpdate::RawDna(Mesh::BezierSqueleton, Rc::new(tubes)));
     }
 
+    /// Update the length handles and the roll dials of the selected helices
+    fn update_helix_handles<S: AppState>(&mut self, app_state: &S) {
+        let mut spheres = Vec::new();
+        let mut dial_tubes = Vec::new();
+        for s in app_state.get_selection() {
+            if let Selection::Helix { helix_id, .. } = s {
+                spheres.extend(self.designs[0].make_helix_length_handles(*helix_id));
+                let (tubes, knobs) = self.designs[0].make_helix_roll_dial(*helix_id);
+                dial_tubes.extend(tubes);
+                spheres.extend(knobs);
+            }
+        }
+        if let Some(axis) = app_state.get_twist_axis() {
+            let (tube, handles) = design3d::make_twist_axis(&axis);
+            dial_tubes.push(tube);
+            spheres.extend(handles);
+        }
+        self.view.borrow_mut().update(ViewUpdate::RawDna(
+            Mesh::HelixLengthHandle,
+            Rc::new(spheres),
+        ));
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::RawDna(Mesh::HelixRollDial, Rc::new(dial_tubes)));
+    }
+
+    pub fn get_helix_roll_dial(&self, helix_id: usize) -> Option<RollDial> {
+        self.designs
+            .get(0)
+            .and_then(|d| d.get_helix_roll_dial(helix_id))
+    }
+
+    /// Return the nucleotides on which strand builders must be started to drag the `end` of a
+    /// helix.
+    pub fn get_helix_end_builder_nucls(&self, helix_id: usize, end: HelixEnd) -> Vec<Nucl> {
+        self.designs
+            .get(0)
+            .map(|d| d.get_helix_end_builder_nucls(helix_id, end))
+            .unwrap_or_default()
+    }
+
+    /// Return `[min_x, max_x, min_y, max_y, min_z, max_z]`, the bounding box of all the designs,
+    /// or None if the designs are empty.
+    fn designs_boundaries(&self) -> Option<[f32; 6]> {
+        let mut boundaries = [
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        for design in self.designs.iter() {
+            let design_boundaries = design.boundaries();
+            for i in 0..3 {
+                boundaries[2 * i] = boundaries[2 * i].min(design_boundaries[2 * i]);
+                boundaries[2 * i + 1] = boundaries[2 * i + 1].max(design_boundaries[2 * i + 1]);
+            }
+        }
+        (boundaries[2] <= boundaries[3]).then(|| boundaries)
+    }
+
+    /// Return the center of the bounding box of all the designs, or None if the designs are
+    /// empty.
+    pub fn get_designs_center(&self) -> Option<Vec3> {
+        self.designs_boundaries()
+            .map(|b| Vec3::new((b[0] + b[1]) / 2., (b[2] + b[3]) / 2., (b[4] + b[5]) / 2.))
+    }
+
+    fn update_ground<S: AppState>(&self, app_state: &S) {
+        let parameters = app_state.get_ground_parameters();
+        let ground = if parameters.is_visible() {
+            self.designs_boundaries().map(|boundaries| Ground {
+                parameters,
+                design_boundaries: boundaries,
+            })
+        } else {
+            None
+        };
+        self.view.borrow_mut().update(ViewUpdate::Ground(ground));
+    }
+
+    fn update_scale_bar<S: AppState>(&self, app_state: &S) {
+        let scale_bar = if app_state.get_scale_bar_parameters().show_scale_bar {
+            let helix_parameters = self.designs[0].get_helix_parameters();
+            let length_unit = app_state.get_scale_bar_parameters().length_unit;
+            // Without a design, lengths are measured at the origin
+            let reference_point = self
+                .designs_boundaries()
+                .map(|b| Vec3::new(b[0] + b[1], b[2] + b[3], b[4] + b[5]) / 2.)
+                .unwrap_or_else(Vec3::zero);
+            Some(ScaleBar {
+                reference_point,
+                rise: helix_parameters.rise,
+                length_unit,
+                nm_per_unit: length_unit.nm_per_unit(&helix_parameters),
+            })
+        } else {
+            None
+        };
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::ScaleBar(scale_bar));
+    }
+
     fn update_handle<S: AppState>(&self, app_state: &S) {
         log::debug!("updating handle {:?} ", self.selected_element(app_state));
         let pivot = app_state.get_current_group_pivot();
@@ -302,8 +456,9 @@ impl<R: DesignReader> Data<R> {
                 .map(|(origin, orientation)| HandlesDescriptor {
                     origin,
                     orientation: HandleOrientation::Rotor(orientation),
-                    size: 0.25,
+                    size: 0.25 * app_state.get_gizmo_size(),
                     colors,
+                    constraint: app_state.get_gizmo_axis_constraint(),
                 })
         } else {
             None
@@ -324,9 +479,10 @@ impl<R: DesignReader> Data<R> {
                 .map(|(origin, orientation)| RotationWidgetDescriptor {
                     origin,
                     orientation: RotationWidgetOrientation::Rotor(orientation),
-                    size: 0.2,
+                    size: 0.2 * app_state.get_gizmo_size(),
                     available_rotation_axes,
                     colors: self.handle_colors,
+                    constraint: app_state.get_gizmo_axis_constraint(),
                 })
         } else {
             None
@@ -483,7 +639,7 @@ impl<R: DesignReader> Data<R> {
                     SceneElement::DesignElement(d_id, id) => {
                         let instances = self.designs[*d_id as usize].make_instance(
                             *id,
-                            SELECTED_COLOR,
+                            app_state.get_color_vision_mode().selected_color(),
                             SELECT_SCALE_FACTOR
                                 * self.designs[*d_id as usize]
                                     .design_reader
@@ -501,7 +657,7 @@ impl<R: DesignReader> Data<R> {
                             .and_then(|d| {
                                 d.make_instance_phantom(
                                     phantom_element,
-                                    SELECTED_COLOR,
+                                    app_state.get_color_vision_mode().selected_color(),
                                     SELECT_SCALE_FACTOR * SPHERE_RADIUS,
                                 )
                             })
@@ -532,7 +688,7 @@ impl<R: DesignReader> Data<R> {
                     SceneElement::DesignElement(d_id, id) => {
                         let instance = self.designs[*d_id as usize].make_instance(
                             *id,
-                            SELECTED_COLOR,
+                            app_state.get_color_vision_mode().selected_color(),
                             SELECT_SCALE_FACTOR
                                 * self.designs[*d_id as usize]
                                     .design_reader
@@ -550,7 +706,7 @@ impl<R: DesignReader> Data<R> {
                             .and_then(|d| {
                                 d.make_instance_phantom(
                                     phantom_element,
-                                    SELECTED_COLOR,
+                                    app_state.get_color_vision_mode().selected_color(),
                                     SELECT_SCALE_FACTOR * BOND_RADIUS,
                                 )
                             })
@@ -581,7 +737,7 @@ impl<R: DesignReader> Data<R> {
                     SceneElement::DesignElement(d_id, id) => {
                         let instances = self.designs[*d_id as usize].make_instance(
                             *id,
-                            CANDIDATE_COLOR,
+                            app_state.get_color_vision_mode().candidate_color(),
                             CANDIDATE_SCALE_FACTOR
                                 * self.designs[*d_id as usize]
                                     .design_reader
@@ -599,7 +755,7 @@ impl<R: DesignReader> Data<R> {
                             .and_then(|d| {
                                 d.make_instance_phantom(
                                     phantom_element,
-                                    CANDIDATE_COLOR,
+                                    app_state.get_color_vision_mode().candidate_color(),
                                     CANDIDATE_SCALE_FACTOR * SPHERE_RADIUS,
                                 )
                             })
@@ -630,7 +786,7 @@ impl<R: DesignReader> Data<R> {
                     SceneElement::DesignElement(d_id, id) => {
                         let instances = self.designs[*d_id as usize].make_instance(
                             *id,
-                            CANDIDATE_COLOR,
+                            app_state.get_color_vision_mode().candidate_color(),
                             CANDIDATE_SCALE_FACTOR
                                 * self.designs[*d_id as usize]
                                     .design_reader
@@ -648,7 +804,7 @@ impl<R: DesignReader> Data<R> {
                             .and_then(|d| {
                                 d.make_instance_phantom(
                                     phantom_element,
-                                    CANDIDATE_COLOR,
+                                    app_state.get_color_vision_mode().candidate_color(),
                                     CANDIDATE_SCALE_FACTOR * BOND_RADIUS,
                                 )
                             })
@@ -688,7 +844,7 @@ impl<R: DesignReader> Data<R> {
         selection_mode: SelectionMode,
     ) -> Option<u32> {
         match selection_mode {
-            SelectionMode::Nucleotide => Some(element_id),
+            SelectionMode::Nucleotide | SelectionMode::BasePair => Some(element_id),
             SelectionMode::Design => Some(design_id),
             SelectionMode::Strand => self.designs[design_id as usize]
                 .get_strand(element_id)
@@ -716,7 +872,7 @@ impl<R: DesignReader> Data<R> {
         let element_id = self.designs[design_id as usize].get_identifier_nucl(&nucl);
 
         match selection_mode {
-            SelectionMode::Nucleotide => element_id,
+            SelectionMode::Nucleotide | SelectionMode::BasePair => element_id,
             SelectionMode::Design => Some(design_id),
             SelectionMode::Strand => element_id.and_then(|e| {
                 self.designs[design_id as usize]
@@ -791,9 +947,10 @@ impl<R: DesignReader> Data<R> {
                 SelectionMode::Helix => design
                     .get_element_axis_position(element, referential)
                     .or(design.get_element_position(element, referential)),
-                SelectionMode::Nucleotide | SelectionMode::Strand | SelectionMode::Design => {
-                    design.get_element_position(element, referential)
-                }
+                SelectionMode::Nucleotide
+                | SelectionMode::BasePair
+                | SelectionMode::Strand
+                | SelectionMode::Design => design.get_element_position(element, referential),
             }
         }
     }
@@ -1066,18 +1223,22 @@ impl<R: DesignReader> Data<R> {
     }
 
     /// Return a hashmap, mapping designs identifier to the set of helices whose phantom must be
-    /// drawn.
+    /// drawn, together with the parameters of these phantom helices.
     fn get_phantom_helices_set<S: AppState>(
         &self,
         app_state: &S,
-    ) -> HashMap<u32, HashMap<u32, bool>> {
+    ) -> HashMap<u32, HashMap<u32, PhantomParameters>> {
         let mut ret = HashMap::new();
+        let full_range = PhantomParameters {
+            length: PHANTOM_RANGE as usize,
+            ..Default::default()
+        };
 
         for (d_id, design) in self.designs.iter().enumerate() {
             let new_helices = design.get_persistent_phantom_helices();
             let set = ret.entry(d_id as u32).or_insert_with(HashMap::new);
-            for h_id in new_helices.iter() {
-                set.insert(*h_id, true);
+            for (h_id, parameters) in new_helices.into_iter() {
+                set.insert(h_id, parameters);
             }
         }
         if self.must_draw_phantom(app_state) {
@@ -1086,38 +1247,47 @@ impl<R: DesignReader> Data<R> {
                     SceneElement::DesignElement(d_id, elt_id) => {
                         let set = ret.entry(d_id).or_insert_with(HashMap::new);
                         if let Some(h_id) = self.get_helix_identifier(d_id, elt_id) {
-                            set.insert(h_id, false);
+                            set.insert(h_id, full_range);
                         }
                     }
                     SceneElement::PhantomElement(phantom_element) => {
                         let set = ret
                             .entry(phantom_element.design_id)
                             .or_insert_with(HashMap::new);
-                        set.insert(phantom_element.helix_id, false);
+                        set.insert(phantom_element.helix_id, full_range);
                     }
                     SceneElement::Grid(d_id, g_id) => {
                         let new_helices = self.designs[d_id as usize]
                             .get_helices_grid(g_id)
                             .unwrap_or_default();
                         let set = ret.entry(d_id).or_insert_with(HashMap::new);
+                        let parameters =
+                            self.designs[d_id as usize].get_grid_phantom_parameters(g_id);
                         for h_id in new_helices.iter() {
-                            set.insert(*h_id as u32, true);
+                            set.insert(*h_id as u32, parameters);
                         }
                     }
                     SceneElement::GridCircle(d_id, position) => {
                         if let Some(h_id) = self.designs[d_id as usize].get_helix_grid(position) {
                             let set = ret.entry(d_id).or_insert_with(HashMap::new);
-                            set.insert(h_id, false);
+                            set.insert(h_id, full_range);
                         }
                     }
                     SceneElement::WidgetElement(_) => unreachable!(),
                     SceneElement::BezierControl { helix_id, .. } => {
                         let set = ret.entry(0).or_insert_with(HashMap::new);
-                        set.insert(helix_id as u32, false);
+                        set.insert(helix_id as u32, full_range);
                     }
                     SceneElement::BezierVertex { .. } => (),
                     SceneElement::BezierTangent { .. } => (),
                     SceneElement::PlaneCorner { .. } => (),
+                    SceneElement::HelixLengthHandle { helix_id, .. }
+                    | SceneElement::HelixRollDial { helix_id } => {
+                        let set = ret.entry(0).or_insert_with(HashMap::new);
+                        set.insert(helix_id as u32, full_range);
+                    }
+                    SceneElement::TwistAxisHandle { .. } => (),
+                    SceneElement::InsertionBadge { .. } => (),
                 }
             }
         }
@@ -1151,7 +1321,7 @@ impl<R: DesignReader> Data<R> {
                     match selection_mode {
                         SelectionMode::Design => Selection::Design(*design_id),
                         SelectionMode::Strand => Selection::Strand(*design_id, group_id),
-                        SelectionMode::Nucleotide => {
+                        SelectionMode::Nucleotide | SelectionMode::BasePair => {
                             let nucl = self.designs[*design_id as usize].get_nucl(group_id);
                             let bond = self.designs[*design_id as usize].get_bond(group_id);
                             let xover_id = bond.as_ref().and_then(|xover| {
@@ -1214,6 +1384,18 @@ impl<R: DesignReader> Data<R> {
                 helix_id: *helix_id,
             },
             SceneElement::PlaneCorner { .. } => Selection::Nothing,
+            SceneElement::TwistAxisHandle { .. } => Selection::Nothing,
+            // The badge always selects the insertion so that its length can be edited
+            SceneElement::InsertionBadge { bond_id } => self.element_to_selection(
+                &SceneElement::DesignElement(0, *bond_id),
+                SelectionMode::Nucleotide,
+            ),
+            SceneElement::HelixLengthHandle { helix_id, .. }
+            | SceneElement::HelixRollDial { helix_id } => Selection::Helix {
+                design_id: 0,
+                helix_id: *helix_id,
+                segment_id: 0,
+            },
             SceneElement::BezierVertex { path_id, vertex_id } => {
                 Selection::BezierVertex(BezierVertexId {
                     path_id: *path_id,
@@ -1249,28 +1431,33 @@ impl<R: DesignReader> Data<R> {
         }
     }
 
-    /// Set the set of candidates to a given nucleotide
+    /// Set the set of candidates to a given nucleotide.
+    ///
+    /// If the element is a nucleotide that is not selected as such in the current selection mode,
+    /// the nucleotide itself is added to the candidates so that it can be highlighted in the
+    /// other views.
     pub fn set_candidate<S: AppState>(
         &mut self,
         element: Option<SceneElement>,
         app_state: &S,
-    ) -> Option<Selection> {
+    ) -> Vec<Selection> {
         if log::log_enabled!(log::Level::Info) {
             if element.is_some() {
                 log::debug!("candidate {:?}", element);
             }
         }
         self.candidate_element = element;
-        let future_candidates = if let Some(element) = element.as_ref() {
+        let mut future_candidates = Vec::new();
+        if let Some(element) = element.as_ref() {
             let selection = self.element_to_selection(element, app_state.get_selection_mode());
             if selection != Selection::Nothing {
-                Some(selection)
-            } else {
-                None
+                future_candidates.push(selection);
             }
-        } else {
-            None
-        };
+            let nucl = self.element_to_selection(element, SelectionMode::Nucleotide);
+            if matches!(nucl, Selection::Nucleotide(_, _)) && nucl != selection {
+                future_candidates.push(nucl);
+            }
+        }
         future_candidates
     }
 
@@ -1322,9 +1509,12 @@ impl<R: DesignReader> Data<R> {
             for tube in design.get_tubes_raw(show_insertion_representents).iter() {
                 instances.push(*tube);
             }
-            for cone in design.get_cones_raw(show_insertion_representents) {
+            let strand_ends = app_state.get_draw_options().strand_ends;
+            for cone in design.get_cones_raw(show_insertion_representents, strand_ends.cone_spacing)
+            {
                 instances.push(cone);
             }
+            instances.extend(design.get_strand_end_glyphs_raw(&strand_ends));
             if app_state.get_draw_options().h_bonds != HBondDisplay::No {
                 for h_bond in design.get_all_h_bonds().full_h_bonds {
                     instances.push(h_bond);
@@ -1405,6 +1595,59 @@ impl<R: DesignReader> Data<R> {
             .update(ViewUpdate::RawDna(Mesh::PivotSphere, Rc::new(spheres)));
     }
 
+    /// Start animating a glow along the strand with identifier `s_id`
+    pub fn start_strand_trace(&mut self, s_id: usize) {
+        let positions = self.designs[0].get_strand_trace_positions(s_id);
+        if positions.is_empty() {
+            self.stop_strand_trace();
+            return;
+        }
+        // Long strands are travelled faster so that the animation does not last forever.
+        let speed = STRAND_TRACE_SPEED.max(positions.len() as f32 / MAX_STRAND_TRACE_DURATION);
+        self.strand_trace = Some(StrandTrace {
+            positions,
+            progress: 0.,
+            speed,
+        });
+    }
+
+    fn stop_strand_trace(&mut self) {
+        self.strand_trace = None;
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::RawDna(Mesh::TraceSphere, Rc::new(vec![])));
+    }
+
+    /// Move the glow of the strand being traced, if any, forward.
+    pub fn update_strand_trace(&mut self, dt: Duration) {
+        let spheres = if let Some(trace) = self.strand_trace.as_mut() {
+            trace.progress += trace.speed * dt.as_secs_f32();
+            let head = trace.progress as usize;
+            if head >= trace.positions.len() + STRAND_TRACE_TAIL_LENGTH {
+                None
+            } else {
+                let mut spheres = Vec::with_capacity(STRAND_TRACE_TAIL_LENGTH);
+                for i in 0..STRAND_TRACE_TAIL_LENGTH {
+                    if let Some(position) = head.checked_sub(i).and_then(|n| trace.positions.get(n))
+                    {
+                        let intensity = 1. - i as f32 / STRAND_TRACE_TAIL_LENGTH as f32;
+                        spheres.push(Design3D::<R>::strand_trace_sphere(*position, intensity));
+                    }
+                }
+                Some(spheres)
+            }
+        } else {
+            return;
+        };
+        if let Some(spheres) = spheres {
+            self.view
+                .borrow_mut()
+                .update(ViewUpdate::RawDna(Mesh::TraceSphere, Rc::new(spheres)));
+        } else {
+            self.stop_strand_trace();
+        }
+    }
+
     pub fn update_surface_pivot(&mut self, position: Option<Vec3>) {
         self.surface_pivot_position = position;
         self.pivot_update = true;
@@ -1459,6 +1702,22 @@ impl<R: DesignReader> Data<R> {
         }
     }
 
+    /// Send the instances of `mesh` to the view. If their number did not change since the last
+    /// time they were sent, the view is given the ranges of instances that were modified so that
+    /// it only uploads those.
+    fn send_instances(&mut self, mesh: Mesh, instances: Vec<RawDnaInstance>) {
+        let instances = Rc::new(instances);
+        let update = match self.sent_instances.get(&mesh) {
+            Some(previous) if previous.len() == instances.len() => {
+                let dirty = dirty_ranges(previous, &instances);
+                ViewUpdate::RawDnaRanges(mesh, instances.clone(), dirty)
+            }
+            _ => ViewUpdate::RawDna(mesh, instances.clone()),
+        };
+        self.sent_instances.insert(mesh, instances);
+        self.view.borrow_mut().update(update);
+    }
+
     /// Notify the view that the set of instances have been modified.
     fn update_instances<S: AppState>(&mut self, app_state: &S) {
         let mut spheres = Vec::with_capacity(10_000);
@@ -1474,6 +1733,8 @@ impl<R: DesignReader> Data<R> {
         let mut letters = Vec::new();
         let mut grids = BTreeMap::new();
         let mut cones = Vec::new();
+        let mut insertion_badges = Vec::new();
+        let mut insertion_badge_letters = vec![Vec::new(); GRID_LABEL_CHARS.len()];
         for design in self.designs.iter() {
             for sphere in design
                 .get_spheres_raw(app_state.show_insertion_representents())
@@ -1506,10 +1767,11 @@ impl<R: DesignReader> Data<R> {
             for (grid_id, grid) in design.get_grid().iter().filter(|g| g.1.visible) {
                 grids.insert(*grid_id, grid.clone());
             }
-            for sphere in design.get_suggested_spheres() {
+            let suggestion_parameters = app_state.get_suggestion_parameters();
+            for sphere in design.get_suggested_spheres(&suggestion_parameters) {
                 suggested_spheres.push(sphere)
             }
-            for tube in design.get_suggested_tubes() {
+            for tube in design.get_suggested_tubes(&suggestion_parameters) {
                 suggested_tubes.push(tube)
             }
             let (spheres, tubes) = design.get_pasted_strand();
@@ -1519,9 +1781,32 @@ impl<R: DesignReader> Data<R> {
             for tube in tubes {
                 pasted_tubes.push(tube);
             }
-            for cone in design.get_cones_raw(app_state.show_insertion_representents()) {
+            let strand_ends = app_state.get_draw_options().strand_ends;
+            for cone in design.get_cones_raw(
+                app_state.show_insertion_representents(),
+                strand_ends.cone_spacing,
+            ) {
                 cones.push(cone);
             }
+            if app_state.get_draw_options().insertion_badges {
+                let (badges, badge_letters) = design.get_insertion_badges();
+                insertion_badges.extend(badges);
+                for (letters, new_letters) in insertion_badge_letters.iter_mut().zip(badge_letters)
+                {
+                    letters.extend(new_letters);
+                }
+            }
+            for glyph in design.get_strand_end_glyphs_raw(&strand_ends) {
+                if glyph.mesh == Mesh::Sphere as u32 {
+                    spheres.push(glyph);
+                } else if glyph.mesh == Mesh::Tube as u32 {
+                    tubes.push(glyph);
+                } else if glyph.mesh == Mesh::TubeLid as u32 {
+                    tube_lids.push(glyph);
+                } else {
+                    cones.push(glyph);
+                }
+            }
         }
         self.update_free_xover(app_state.get_candidates());
         let (sheet_instances, corner_spheres) = if app_state.show_bezier_paths() {
@@ -1530,25 +1815,38 @@ impl<R: DesignReader> Data<R> {
             (Default::default(), Default::default())
         };
         spheres.extend(corner_spheres);
+        if let Some(diff) = app_state.get_design_diff() {
+            for instances in [
+                &mut spheres,
+                &mut tubes,
+                &mut tube_lids,
+                &mut sliced_tubes,
+                &mut cones,
+            ] {
+                self.designs[0].color_by_diff(instances, diff);
+            }
+        }
+        let color_vision_mode = app_state.get_color_vision_mode();
+        for instances in [
+            &mut spheres,
+            &mut tubes,
+            &mut tube_lids,
+            &mut sliced_tubes,
+            &mut cones,
+        ] {
+            remap_instances_colors(instances, color_vision_mode);
+        }
         self.view
             .borrow_mut()
             .update(ViewUpdate::BezierSheets(sheet_instances));
-        self.view
-            .borrow_mut()
-            .update(ViewUpdate::RawDna(Mesh::Tube, Rc::new(tubes)));
-        self.view
-            .borrow_mut()
-            .update(ViewUpdate::RawDna(Mesh::TubeLid, Rc::new(tube_lids)));
-        self.view
-            .borrow_mut()
-            .update(ViewUpdate::RawDna(Mesh::SlicedTube, Rc::new(sliced_tubes)));
+        self.send_instances(Mesh::Tube, tubes);
+        self.send_instances(Mesh::TubeLid, tube_lids);
+        self.send_instances(Mesh::SlicedTube, sliced_tubes);
         self.view.borrow_mut().update(ViewUpdate::RawDna(
             Mesh::PlainRectangle,
             Rc::new(plain_rectangles),
         ));
-        self.view
-            .borrow_mut()
-            .update(ViewUpdate::RawDna(Mesh::Sphere, Rc::new(spheres)));
+        self.send_instances(Mesh::Sphere, spheres);
         self.view.borrow_mut().update(ViewUpdate::RawDna(
             Mesh::SuggestionSphere,
             Rc::new(suggested_spheres),
@@ -1566,9 +1864,14 @@ impl<R: DesignReader> Data<R> {
             .update(ViewUpdate::RawDna(Mesh::PastedTube, Rc::new(pasted_tubes)));
         self.view.borrow_mut().update(ViewUpdate::Letter(letters));
         self.view.borrow_mut().update(ViewUpdate::Grids(grids));
+        self.send_instances(Mesh::Prime3Cone, cones);
+        self.view.borrow_mut().update(ViewUpdate::RawDna(
+            Mesh::InsertionBadge,
+            Rc::new(insertion_badges),
+        ));
         self.view
             .borrow_mut()
-            .update(ViewUpdate::RawDna(Mesh::Prime3Cone, Rc::new(cones)));
+            .update(ViewUpdate::InsertionBadgeLetter(insertion_badge_letters));
         let bonds = self.designs[0].get_all_h_bonds();
         if app_state.get_draw_options().h_bonds == HBondDisplay::Ellipsoid {
             self.view.borrow_mut().update(ViewUpdate::RawDna(
@@ -1584,11 +1887,53 @@ impl<R: DesignReader> Data<R> {
             Mesh::BaseEllipsoid,
             Rc::new(bonds.ellipsoids),
         ));
+        let (joint_spheres, joint_tubes) = design3d::make_joints(&self.designs[0].design_reader);
+        self.view.borrow_mut().update(ViewUpdate::RawDna(
+            Mesh::JointSphere,
+            Rc::new(joint_spheres),
+        ));
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::RawDna(Mesh::JointTube, Rc::new(joint_tubes)));
+    }
+
+    /// In the `BuildHelix` action mode, show the helix that would be created by clicking on the
+    /// hovered grid disc.
+    fn update_ghost_helix<S: AppState>(&mut self, app_state: &S) {
+        let ghost = match (app_state.get_action_mode().0, self.candidate_element) {
+            (
+                ActionMode::BuildHelix { position, length },
+                Some(SceneElement::GridCircle(d_id, grid_position)),
+            ) => self
+                .designs
+                .get(d_id as usize)
+                .filter(|d| {
+                    d.design_reader
+                        .get_helix_id_at_grid_coord(grid_position)
+                        .is_none()
+                })
+                .map(|d| d.make_ghost_helix_instances(grid_position, position, length)),
+            (ActionMode::BuildHelix { position, length }, element) => self
+                .double_strand_target(&element, position, length)
+                .and_then(|h_id| {
+                    self.designs
+                        .get(0)
+                        .map(|d| d.make_ghost_double_strand_instances(h_id, position, length))
+                }),
+            _ => None,
+        };
+        let (spheres, tubes) = ghost.unwrap_or_default();
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::RawDna(Mesh::GhostSphere, Rc::new(spheres)));
+        self.view
+            .borrow_mut()
+            .update(ViewUpdate::RawDna(Mesh::GhostTube, Rc::new(tubes)));
     }
 
     fn update_discs<S: AppState>(&mut self, app_state: &S) {
         let mut discs = Vec::new();
-        let mut letters: Vec<Vec<LetterInstance>> = vec![vec![]; 10];
+        let mut letters: Vec<Vec<LetterInstance>> = vec![vec![]; GRID_LABEL_CHARS.len()];
         let right = self.view.borrow().get_camera().borrow().right_vec();
         let up = self.view.borrow().get_camera().borrow().up_vec();
         let mut selected_discs: Vec<GridPosition> = Vec::new();
@@ -1629,7 +1974,23 @@ impl<R: DesignReader> Data<R> {
             }
         }
         for design in self.designs.iter() {
-            for grid in design.get_grid().values().filter(|g| g.visible) {
+            let grids = design.get_grid();
+            // The ends of the helices are only needed if a grid is labelled
+            let helices_axis_ends = if grids.values().any(|g| g.visible && g.labelled) {
+                design.get_helices_axis_ends()
+            } else {
+                BTreeMap::new()
+            };
+            for grid in grids.values().filter(|g| g.visible) {
+                if grid.labelled {
+                    grid.coordinates_letter_instances(&mut letters, right, up);
+                    for (_, h_id) in design.get_helices_grid_key_coord(grid.id) {
+                        if let Some(ends) = helices_axis_ends.get(&h_id) {
+                            let number = design.get_helix_display_number(h_id);
+                            grid.helix_end_letter_instances(number, *ends, &mut letters, right);
+                        }
+                    }
+                }
                 for (x, y) in design.get_helices_grid_coord(grid.id) {
                     add_discs(
                         GridPosition {
@@ -1642,6 +2003,7 @@ impl<R: DesignReader> Data<R> {
                     );
                 }
                 for ((x, y), h_id) in design.get_helices_grid_key_coord(grid.id) {
+                    let number = design.get_helix_display_number(h_id);
                     for g_id in design.get_bezier_grid_used_by_helix(h_id) {
                         add_discs(
                             GridPosition { grid: g_id, x, y },
@@ -1649,10 +2011,10 @@ impl<R: DesignReader> Data<R> {
                             DiscLevel::Scene,
                         );
                         if let Some(bezier_grid) = design.get_grid().get(&g_id) {
-                            bezier_grid.letter_instance(x, y, h_id, &mut letters, right, up);
+                            bezier_grid.letter_instance(x, y, number, &mut letters, right, up);
                         }
                     }
-                    grid.letter_instance(x, y, h_id, &mut letters, right, up);
+                    grid.letter_instance(x, y, number, &mut letters, right, up);
                 }
             }
         }
@@ -1689,49 +2051,62 @@ impl<R: DesignReader> Data<R> {
     }
 
     pub fn get_widget_basis<S: AppState>(&self, app_state: &S) -> Option<Rotor3> {
-        self.get_selected_basis(app_state).map(|b| {
-            if app_state.get_widget_basis().is_axis_aligned() {
-                Rotor3::identity()
-            } else {
-                b
-            }
-        })
+        self.get_selected_basis(app_state)
+            .map(|b| match app_state.get_widget_basis() {
+                WidgetBasis::World => Rotor3::identity(),
+                WidgetBasis::Object => b,
+                WidgetBasis::Screen => self.get_screen_basis(),
+            })
     }
 
     fn get_forced_widget_basis<S: AppState>(&self, app_state: &S) -> Option<Rotor3> {
-        if app_state.get_widget_basis().is_axis_aligned()
+        let basis = app_state.get_widget_basis();
+        if basis != WidgetBasis::Object
             && !(self.handle_colors == HandleColors::Cym
                 && app_state.get_action_mode().0 == ActionMode::Rotate)
         {
-            Some(Rotor3::identity())
+            if basis == WidgetBasis::Screen {
+                Some(self.get_screen_basis())
+            } else {
+                Some(Rotor3::identity())
+            }
         } else {
             None
         }
     }
 
+    /// The orientation of the camera, so that widgets' axes are aligned with the screen's axes.
+    fn get_screen_basis(&self) -> Rotor3 {
+        self.view.borrow().get_camera().borrow().rotor.reversed()
+    }
+
     fn get_selected_basis<S: AppState>(&self, app_state: &S) -> Option<Rotor3> {
         let from_selected_element = match self.selected_element(app_state) {
-            Some(SceneElement::DesignElement(d_id, _)) => match self
-                .get_sub_selection_mode(app_state)
-            {
-                SelectionMode::Nucleotide | SelectionMode::Design | SelectionMode::Strand => None,
-                SelectionMode::Helix => {
-                    let h_id = self.get_selected_group(app_state)?;
-                    if let Some(grid_position) =
-                        self.designs[d_id as usize].get_helix_grid_position(h_id)
-                    {
-                        self.designs[d_id as usize].get_grid_basis(grid_position.grid)
-                    } else {
-                        self.designs[d_id as usize].get_helix_basis(h_id)
+            Some(SceneElement::DesignElement(d_id, _)) => {
+                match self.get_sub_selection_mode(app_state) {
+                    SelectionMode::Nucleotide
+                    | SelectionMode::BasePair
+                    | SelectionMode::Design
+                    | SelectionMode::Strand => None,
+                    SelectionMode::Helix => {
+                        let h_id = self.get_selected_group(app_state)?;
+                        if let Some(grid_position) =
+                            self.designs[d_id as usize].get_helix_grid_position(h_id)
+                        {
+                            self.designs[d_id as usize].get_grid_basis(grid_position.grid)
+                        } else {
+                            self.designs[d_id as usize].get_helix_basis(h_id)
+                        }
                     }
                 }
-            },
+            }
             Some(SceneElement::PhantomElement(phantom_element)) => {
                 let d_id = phantom_element.design_id;
                 match self.get_sub_selection_mode(app_state) {
-                    SelectionMode::Nucleotide | SelectionMode::Design | SelectionMode::Strand => {
-                        None
-                    }
+                    SelectionMode::Nucleotide
+                    | SelectionMode::BasePair
+                    | SelectionMode::Design
+                    | SelectionMode::Strand => None,
                     SelectionMode::Helix => {
                         let h_id = phantom_element.helix_id;
                         self.designs[d_id as usize].get_helix_basis(h_id)
@@ -1803,11 +2178,33 @@ impl<R: DesignReader> Data<R> {
         }
     }
 
+    /// Return the identifier of the helix on which `element` is, if a double strand going from
+    /// `start` to `start + length` can be added on it.
+    pub fn double_strand_target(
+        &self,
+        element: &Option<SceneElement>,
+        start: isize,
+        length: usize,
+    ) -> Option<usize> {
+        let (nucl, d_id) = self.element_to_nucl(element, false)?;
+        self.designs
+            .get(d_id)
+            .filter(|d| d.double_strand_fits(nucl.helix, start, length))
+            .map(|_| nucl.helix)
+    }
+
     pub fn get_nucl_position(&self, nucl: Nucl, design_id: usize) -> Option<Vec3> {
         let design = self.designs.get(design_id)?;
         design.get_nucl_position(nucl)
     }
 
+    pub fn get_all_nucls_with_position(&self) -> Vec<(Nucl, Vec3)> {
+        self.designs
+            .iter()
+            .flat_map(|d| d.get_all_nucls_with_position())
+            .collect()
+    }
+
     /*
     /// Set the selection to a given nucleotide if it exists in the design.
     pub fn select_nucl(&mut self, nucl: Nucl, design_id: usize) {
@@ -2027,6 +2424,12 @@ impl<R: DesignReader> Data<R> {
             }
             SceneElement::BezierTangent { .. } => None,
             SceneElement::PlaneCorner { .. } => None,
+            SceneElement::HelixLengthHandle { .. } => None,
+            SceneElement::HelixRollDial { .. } => None,
+            SceneElement::TwistAxisHandle { .. } => None,
+            SceneElement::InsertionBadge { bond_id } => {
+                self.scene_element_to_center_of_selection(SceneElement::DesignElement(0, bond_id))
+            }
         }
     }
 
@@ -2089,6 +2492,38 @@ enum FreeXoverEnd {
     Nucl(Nucl),
 }
 
+/// Replace the colors of the instances by the ones used in `color_vision_mode`.
+fn remap_instances_colors(instances: &mut [RawDnaInstance], color_vision_mode: ColorVisionMode) {
+    if color_vision_mode == ColorVisionMode::Normal {
+        return;
+    }
+    for instance in instances.iter_mut() {
+        let to_byte = |x: f32| (x * 255.).round().max(0.).min(255.) as u32;
+        let color = (to_byte(instance.color.x) << 16)
+            | (to_byte(instance.color.y) << 8)
+            | to_byte(instance.color.z);
+        let alpha = instance.color.w;
+        instance.color = ensnano_utils::instance::Instance::color_from_u32(
+            color_vision_mode.strand_color(color),
+        );
+        instance.color.w = alpha;
+    }
+}
+
+/// The ranges of indices at which `previous` and `new` differ.
+fn dirty_ranges<T: PartialEq>(previous: &[T], new: &[T]) -> Vec<std::ops::Range<usize>> {
+    let mut ret: Vec<std::ops::Range<usize>> = Vec::new();
+    for (i, (p, n)) in previous.iter().zip(new.iter()).enumerate() {
+        if p != n {
+            match ret.last_mut() {
+                Some(range) if range.end == i => range.end = i + 1,
+                _ => ret.push(i..i + 1),
+            }
+        }
+    }
+    ret
+}
+
 fn toggle_selection(mode: SelectionMode) -> SelectionMode {
     match mode {
         SelectionMode::Nucleotide => SelectionMode::Strand,
@@ -2109,10 +2544,23 @@ impl<R: DesignReader> ControllerData for Data<R> {
         self.element_to_nucl(element, non_phantom)
     }
 
+    fn double_strand_target(
+        &self,
+        element: &Option<SceneElement>,
+        start: isize,
+        length: usize,
+    ) -> Option<usize> {
+        self.double_strand_target(element, start, length)
+    }
+
     fn get_nucl_position(&self, nucl: Nucl, design_id: usize) -> Option<Vec3> {
         self.get_nucl_position(nucl, design_id)
     }
 
+    fn get_all_nucls_with_position(&self) -> Vec<(Nucl, Vec3)> {
+        self.get_all_nucls_with_position()
+    }
+
     fn attempt_xover(
         &self,
         source: &Option<SceneElement>,
@@ -2125,6 +2573,14 @@ impl<R: DesignReader> ControllerData for Data<R> {
         self.can_start_builder(element)
     }
 
+    fn get_helix_end_builder_nucls(&self, helix_id: usize, end: HelixEnd) -> Vec<Nucl> {
+        self.get_helix_end_builder_nucls(helix_id, end)
+    }
+
+    fn get_helix_roll_dial(&self, helix_id: usize) -> Option<RollDial> {
+        self.get_helix_roll_dial(helix_id)
+    }
+
     fn get_grid_object(&self, position: GridPosition) -> Option<GridObject> {
         self.designs
             .get(0)
@@ -2242,3 +2698,21 @@ fn candidate_xover(candidates: &[Selection]) -> Option<FreeXover> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::dirty_ranges;
+
+    #[test]
+    fn dirty_ranges_group_consecutive_modifications() {
+        let previous = [0, 1, 2, 3, 4, 5, 6];
+        let new = [0, 9, 9, 3, 4, 9, 6];
+        assert_eq!(dirty_ranges(&previous, &new), vec![1..3, 5..6]);
+    }
+
+    #[test]
+    fn no_dirty_ranges_for_identical_instances() {
+        let previous = [1, 2, 3];
+        assert!(dirty_ranges(&previous, &previous).is_empty());
+    }
+}
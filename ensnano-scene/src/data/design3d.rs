@@ -21,23 +21,25 @@ use super::super::view::{
     SphereInstance, TubeInstance, TubeLidInstance,
 };
 use super::super::GridInstance;
-use super::{ultraviolet, LetterInstance, SceneElement};
+use super::{ultraviolet, HelixEnd, LetterInstance, SceneElement, GRID_LABEL_CHARS};
 use crate::rotor_utils::SafeRotor;
 use crate::sausage_rosary::SausageRosary;
 use crate::view::PlainRectangleInstance;
-use ensnano_design::grid::{GridId, GridObject, GridPosition};
+use ensnano_design::diff::DesignDiff;
+use ensnano_design::drawing_style::EndGlyph;
+use ensnano_design::grid::{GridId, GridObject, GridPosition, PhantomParameters};
 use ensnano_design::{grid::HelixGridPosition, Nucl};
 use ensnano_design::{
     perpendicular_basis, AdditionalStructure, BezierPathId, BezierPlaneDescriptor, BezierPlaneId,
-    BezierVertex, Collection, CubicBezierConstructor, CurveDescriptor, External3DObjects,
-    HelixParameters, InstanciatedPath,
+    BezierVertex, Collection, CubicBezierConstructor, CurveDescriptor, Domain, External3DObjects,
+    HelixParameters, InstanciatedPath, JointKind,
 };
 pub use ensnano_design::{SurfaceInfo, SurfacePoint};
 use ensnano_interactor::consts::*;
 use ensnano_interactor::{
-    graphics::{LoopoutBond, LoopoutNucl},
+    graphics::{LoopoutBond, LoopoutNucl, StrandEnd, StrandEndsParameters},
     phantom_helix_encoder_bond, phantom_helix_encoder_nucl, BezierControlPoint, ObjectType,
-    PhantomElement, Referential, PHANTOM_RANGE,
+    PhantomElement, Referential, SuggestionParameters, SuggestionStyle, TwistAxis,
 };
 use ensnano_utils::colors::{self, new_color, purple_to_blue_gradient_color};
 use ensnano_utils::instance::Instance;
@@ -56,6 +58,24 @@ use serde::{Deserialize, Serialize};
 
 use ensnano_utils::StrandNucleotidesPositions;
 
+/// The number of positions between the leftmost nucleotide of a helix and its roll dial
+const ROLL_DIAL_OFFSET: isize = 3;
+/// The number of tubes drawing the ring of a roll dial
+const ROLL_DIAL_NB_SEGMENTS: usize = 24;
+
+/// The dial showing the roll of a helix in the 3D view
+#[derive(Debug, Clone, Copy)]
+pub struct RollDial {
+    /// The center of the dial, on the axis of the helix
+    pub center: Vec3,
+    /// The unit vector along the axis of the helix, pointing towards increasing positions
+    pub axis: Vec3,
+    /// The vector from the center of the dial to its knob, orthogonal to `axis`
+    pub needle: Vec3,
+    /// The current roll of the helix
+    pub roll: f32,
+}
+
 /// An object that handles the 3d graphical representation of a `Design`
 pub struct Design3D<R: DesignReader> {
     pub design_reader: R,
@@ -63,6 +83,8 @@ pub struct Design3D<R: DesignReader> {
     symbol_map: HashMap<char, usize>,
     /// indicate if all helices must be on axis (helices_off_axis = false)
     pub all_helices_on_axis: bool,
+    /// indicate if nucleotides hidden by the visibility sieve must be shown anyway
+    pub ignore_visibility_sieve: bool,
 }
 
 impl<R: DesignReader> Design3D<R> {
@@ -76,6 +98,22 @@ impl<R: DesignReader> Design3D<R> {
             id,
             symbol_map,
             all_helices_on_axis: false,
+            ignore_visibility_sieve: false,
+        }
+    }
+
+    /// Replace the colors of the instances by colors indicating whether the strand that they
+    /// belong to was added or modified according to `diff`.
+    pub fn color_by_diff(&self, instances: &mut [RawDnaInstance], diff: &DesignDiff) {
+        for instance in instances.iter_mut() {
+            let color = match self.design_reader.get_id_of_strand_containing(instance.id) {
+                Some(s_id) if diff.strands.added.contains(&s_id) => DIFF_ADDED_COLOR,
+                Some(s_id) if diff.strands.modified.contains(&s_id) => DIFF_MODIFIED_COLOR,
+                _ => DIFF_UNCHANGED_COLOR,
+            };
+            let alpha = instance.color.w;
+            instance.color = Instance::color_from_u32(color);
+            instance.color.w = alpha;
         }
     }
 
@@ -92,7 +130,9 @@ impl<R: DesignReader> Design3D<R> {
 
     /// Return the list of raw sphere instances to be displayed to represent the design
     pub fn get_spheres_raw(&self, show_insertion_representents: bool) -> Rc<Vec<RawDnaInstance>> {
-        let visible_nucls_ids = self.design_reader.get_all_visible_nucl_ids();
+        let visible_nucls_ids = self
+            .design_reader
+            .get_all_visible_nucl_ids(self.ignore_visibility_sieve);
         let mut ret = self.id_to_raw_instances(visible_nucls_ids);
         if !show_insertion_representents {
             for loopout_nucl in self.design_reader.get_all_loopout_nucl() {
@@ -216,11 +256,27 @@ impl<R: DesignReader> Design3D<R> {
         vecs
     }
 
-    pub fn get_cones_raw(&self, show_insertion_representents: bool) -> Vec<RawDnaInstance> {
-        let mut ids = self.design_reader.get_all_visible_bond_ids();
+    /// Return the direction cones drawn on the bonds. A cone is drawn on one bond out of
+    /// `cone_spacing`, unless the drawing style of the bond requires another spacing.
+    pub fn get_cones_raw(
+        &self,
+        show_insertion_representents: bool,
+        cone_spacing: usize,
+    ) -> Vec<RawDnaInstance> {
+        let mut ids = self
+            .design_reader
+            .get_all_visible_bond_ids(self.ignore_visibility_sieve);
         if !show_insertion_representents {
             ids.retain(|id| self.design_reader.get_insertion_length(*id) == 0);
         }
+        ids.retain(|id| {
+            let spacing = self
+                .design_reader
+                .get_cone_spacing(*id)
+                .unwrap_or(cone_spacing);
+            let rank = self.design_reader.get_bond_rank(*id).unwrap_or(0);
+            spacing > 0 && rank % spacing == 0
+        });
         let filter = |_n: &Nucl| true;
         let vec: Vec<_> = ids
             .iter()
@@ -229,6 +285,195 @@ impl<R: DesignReader> Design3D<R> {
         vec
     }
 
+    /// Return the glyphs marking the 5' and 3' ends of the strands. The returned instances are
+    /// spheres, cones, tubes and tube lids and must be dispatched according to their mesh.
+    pub fn get_strand_end_glyphs_raw(
+        &self,
+        parameters: &StrandEndsParameters,
+    ) -> Vec<RawDnaInstance> {
+        let mut ret = Vec::new();
+        for end in self
+            .design_reader
+            .get_strand_ends(self.ignore_visibility_sieve)
+        {
+            let default_glyph = if end.prime5 {
+                parameters.prime5_glyph
+            } else {
+                parameters.prime3_glyph
+            };
+            let glyph = end.glyph.unwrap_or(default_glyph);
+            if glyph == EndGlyph::None {
+                continue;
+            }
+            if let Some(instances) = self.make_strand_end_glyph(&end, glyph) {
+                ret.extend(instances);
+            }
+        }
+        ret
+    }
+
+    /// Return the badges showing the length of the insertions, and the digits written on them
+    /// indexed like `GRID_LABEL_CHARS`.
+    pub fn get_insertion_badges(&self) -> (Vec<RawDnaInstance>, Vec<Vec<LetterInstance>>) {
+        let mut badges = Vec::new();
+        let mut letters = vec![Vec::new(); GRID_LABEL_CHARS.len()];
+        let mut loopout_positions: HashMap<u32, Vec<Vec3>> = HashMap::new();
+        for loopout_nucl in self.design_reader.get_all_loopout_nucl() {
+            loopout_positions
+                .entry(loopout_nucl.repr_bond_identifier)
+                .or_default()
+                .push(loopout_nucl.position);
+        }
+        for (id, length) in self
+            .design_reader
+            .get_insertion_lengths(self.ignore_visibility_sieve)
+        {
+            let position =
+                if let Some(p) = self.insertion_badge_position(id, loopout_positions.get(&id)) {
+                    p
+                } else {
+                    continue;
+                };
+            badges.push(
+                SphereInstance {
+                    position,
+                    color: Instance::color_from_u32(INSERTION_BADGE_COLOR),
+                    id: crate::element_selector::insertion_badge_id(id),
+                    radius: INSERTION_BADGE_RADIUS,
+                }
+                .to_raw_instance(),
+            );
+            let label = length.to_string();
+            let nb_chars = label.len() as f32;
+            for (c_idx, c) in label.chars().enumerate() {
+                if let Some(idx) = GRID_LABEL_CHARS.iter().position(|x| *x == c) {
+                    letters[idx].push(LetterInstance {
+                        position,
+                        color: ultraviolet::Vec4::new(0., 0., 0., 1.),
+                        design_id: self.id,
+                        scale: 0.8,
+                        // center the label on the badge
+                        shift: Vec3::new(0.5 * c_idx as f32 - 0.25 * nb_chars - 0.25, -0.5, 0.),
+                    });
+                }
+            }
+        }
+        (badges, letters)
+    }
+
+    /// The position of the badge of an insertion, slightly away from the helices so that it is
+    /// not hidden by the insertion.
+    fn insertion_badge_position(
+        &self,
+        id: u32,
+        loopout_positions: Option<&Vec<Vec3>>,
+    ) -> Option<Vec3> {
+        let end_ids = match self.get_object_type(id)? {
+            ObjectType::Bond(id1, id2) | ObjectType::SlicedBond(_, id1, id2, _) => vec![id1, id2],
+            ObjectType::Nucleotide(id) => vec![id],
+            _ => return None,
+        };
+        let average =
+            |points: &[Vec3]| points.iter().fold(Vec3::zero(), |a, b| a + *b) / points.len() as f32;
+        let end_positions: Vec<Vec3> = end_ids
+            .iter()
+            .filter_map(|e_id| {
+                self.get_graphic_element_position(&SceneElement::DesignElement(self.id, *e_id))
+            })
+            .collect();
+        if end_positions.is_empty() {
+            return None;
+        }
+        let base = loopout_positions
+            .filter(|p| !p.is_empty())
+            .map(|p| average(p))
+            .unwrap_or_else(|| average(&end_positions));
+        let axis_positions: Vec<Vec3> = end_ids
+            .iter()
+            .filter_map(|e_id| {
+                self.get_graphic_element_axis_position(&SceneElement::DesignElement(self.id, *e_id))
+            })
+            .collect();
+        let outward = Some(axis_positions)
+            .filter(|p| !p.is_empty())
+            .map(|p| base - average(&p))
+            .filter(|v| v.mag_sq() > 1e-6)
+            .map(|v| v.normalized())
+            .unwrap_or_else(Vec3::unit_y);
+        Some(base + INSERTION_BADGE_OFFSET * outward)
+    }
+
+    fn make_strand_end_glyph(
+        &self,
+        end: &StrandEnd,
+        glyph: EndGlyph,
+    ) -> Option<Vec<RawDnaInstance>> {
+        let position =
+            self.get_graphic_element_position(&SceneElement::DesignElement(self.id, end.nucl_id))?;
+        let neighbour = self.get_graphic_element_position(&SceneElement::DesignElement(
+            self.id,
+            end.neighbour_id,
+        ))?;
+        // The glyphs are oriented in the 5' -> 3' direction of the strand
+        let direction = if end.prime5 {
+            neighbour - position
+        } else {
+            position - neighbour
+        }
+        .normalized();
+        let rotor = Rotor3::safe_from_rotation_from_unit_x_to(direction);
+        let color = Instance::unclear_color_from_u32(self.get_color(end.nucl_id).unwrap_or(0));
+        let radius = STRAND_END_GLYPH_SCALE * self.get_radius(end.nucl_id).unwrap_or(SPHERE_RADIUS);
+        let id = end.nucl_id | self.id << 24;
+        let instances = match glyph {
+            EndGlyph::None => vec![],
+            EndGlyph::Arrowhead => vec![ConeInstance {
+                position,
+                rotor,
+                color,
+                id,
+                radius,
+                length: 2. * radius,
+            }
+            .to_raw_instance()],
+            EndGlyph::Sphere => vec![SphereInstance {
+                position,
+                color,
+                id,
+                radius,
+            }
+            .to_raw_instance()],
+            EndGlyph::Square => vec![
+                TubeInstance {
+                    position,
+                    color,
+                    rotor,
+                    id,
+                    radius,
+                    length: 2. * radius,
+                }
+                .to_raw_instance(),
+                TubeLidInstance {
+                    position: position - radius * direction,
+                    color,
+                    rotor: Rotor3::safe_from_rotation_from_unit_x_to(-direction),
+                    id,
+                    radius,
+                }
+                .to_raw_instance(),
+                TubeLidInstance {
+                    position: position + radius * direction,
+                    color,
+                    rotor,
+                    id,
+                    radius,
+                }
+                .to_raw_instance(),
+            ],
+        };
+        Some(instances)
+    }
+
     pub fn get_scalebar_plain_rectangles_raw(&self) -> Vec<RawDnaInstance> {
         let n = 1000;
         if let Some((r_min, r_max, gradient)) = self.design_reader.get_scalebar() {
@@ -253,7 +498,9 @@ impl<R: DesignReader> Design3D<R> {
 
     /// Return the list of tube instances to be displayed to represent the design
     pub fn get_tubes_raw(&self, show_insertion_representents: bool) -> Rc<Vec<RawDnaInstance>> {
-        let mut visible_bonds_ids = self.design_reader.get_all_visible_bond_ids();
+        let mut visible_bonds_ids = self
+            .design_reader
+            .get_all_visible_bond_ids(self.ignore_visibility_sieve);
         if !show_insertion_representents {
             visible_bonds_ids.retain(|id| self.design_reader.get_insertion_length(*id) == 0);
         }
@@ -930,33 +1177,43 @@ impl<R: DesignReader> Design3D<R> {
         Some(raw_instances)
     }
 
-    pub fn get_suggested_spheres(&self) -> Vec<RawDnaInstance> {
-        let suggestion = self.design_reader.get_suggestions();
+    /// The positions of the nucleotides of each suggested cross-over, and the color in which the
+    /// suggestion must be drawn.
+    fn get_suggestion_positions(
+        &self,
+        parameters: &SuggestionParameters,
+    ) -> Vec<(Nucl, Nucl, Option<Vec3>, Option<Vec3>, u32)> {
+        self.design_reader
+            .get_suggestions()
+            .into_iter()
+            .map(|(n1, n2)| {
+                let position_1 = self.design_reader.get_position_of_nucl_on_helix(
+                    n1,
+                    Referential::Model,
+                    self.all_helices_on_axis,
+                );
+                let position_2 = self.design_reader.get_position_of_nucl_on_helix(
+                    n2,
+                    Referential::Model,
+                    self.all_helices_on_axis,
+                );
+                let color = match position_1.zip(position_2) {
+                    Some((a, b)) if parameters.color_by_distance => {
+                        parameters.heat_color((a - b).mag())
+                    }
+                    _ => SUGGESTION_COLOR,
+                };
+                (n1, n2, position_1, position_2, color)
+            })
+            .collect()
+    }
+
+    pub fn get_suggested_spheres(&self, parameters: &SuggestionParameters) -> Vec<RawDnaInstance> {
         let mut ret = vec![];
-        for (n1, n2) in suggestion {
-            let nucl_1 = self.design_reader.get_position_of_nucl_on_helix(
-                n1,
-                Referential::Model,
-                self.all_helices_on_axis,
-            );
-            let nucl_2 = self.design_reader.get_position_of_nucl_on_helix(
-                n2,
-                Referential::Model,
-                self.all_helices_on_axis,
-            );
-            if let Some(position) = nucl_1 {
+        for (_, _, nucl_1, nucl_2, color) in self.get_suggestion_positions(parameters) {
+            for position in nucl_1.into_iter().chain(nucl_2) {
                 let instance = SphereInstance {
-                    color: Instance::color_from_au32(SUGGESTION_COLOR),
-                    position,
-                    id: 0,
-                    radius: SELECT_SCALE_FACTOR * SPHERE_RADIUS,
-                }
-                .to_raw_instance();
-                ret.push(instance);
-            }
-            if let Some(position) = nucl_2 {
-                let instance = SphereInstance {
-                    color: Instance::color_from_au32(SUGGESTION_COLOR),
+                    color: Instance::color_from_au32(color),
                     position,
                     id: 0,
                     radius: SELECT_SCALE_FACTOR * SPHERE_RADIUS,
@@ -968,29 +1225,60 @@ impl<R: DesignReader> Design3D<R> {
         ret
     }
 
-    pub fn get_suggested_tubes(&self) -> Vec<RawDnaInstance> {
-        let suggestion = self.design_reader.get_suggestions();
+    pub fn get_suggested_tubes(&self, parameters: &SuggestionParameters) -> Vec<RawDnaInstance> {
         let mut ret = vec![];
-        for (n1, n2) in suggestion {
-            let nucl_1 = self.design_reader.get_position_of_nucl_on_helix(
-                n1,
-                Referential::Model,
-                self.all_helices_on_axis,
-            );
-            let nucl_2 = self.design_reader.get_position_of_nucl_on_helix(
-                n2,
-                Referential::Model,
-                self.all_helices_on_axis,
-            );
+        for (n1, n2, nucl_1, nucl_2, color) in self.get_suggestion_positions(parameters) {
             if let Some((position1, position2)) = nucl_1.zip(nucl_2) {
-                let instance = create_dna_bond(position1, position2, SUGGESTION_COLOR, 0, true)
-                    .to_raw_instance();
-                ret.push(instance);
+                match parameters.style {
+                    SuggestionStyle::Straight => ret.push(
+                        create_dna_bond(position1, position2, color, 0, true).to_raw_instance(),
+                    ),
+                    SuggestionStyle::DashedArcs => ret.extend(self.suggestion_dashed_arc(
+                        (n1, position1),
+                        (n2, position2),
+                        color,
+                    )),
+                }
             }
         }
         ret
     }
 
+    /// The dashes of an arc linking the nucleotides of a suggested cross-over, bulging away from
+    /// the axis of the helices that it links.
+    fn suggestion_dashed_arc(
+        &self,
+        (n1, position1): (Nucl, Vec3),
+        (n2, position2): (Nucl, Vec3),
+        color: u32,
+    ) -> Vec<RawDnaInstance> {
+        let axis_position = |nucl| {
+            self.design_reader
+                .get_position_of_nucl_on_helix(nucl, Referential::Model, true)
+        };
+        let middle = (position1 + position2) / 2.;
+        let bulge = axis_position(n1)
+            .zip(axis_position(n2))
+            .map(|(a1, a2)| middle - (a1 + a2) / 2.)
+            .filter(|v| v.mag() > 1e-3)
+            .map(|v| v.normalized())
+            .unwrap_or_else(Vec3::zero);
+        // The middle of a quadratic bezier curve is half way between its control point and the
+        // middle of its extremities
+        let control = middle + 2. * SUGGESTION_ARC_BULGE * bulge;
+        let point = |t: f32| {
+            (1. - t) * (1. - t) * position1 + 2. * t * (1. - t) * control + t * t * position2
+        };
+        let nb_pieces = (2 * SUGGESTION_ARC_NB_DASHES - 1) as f32;
+        (0..SUGGESTION_ARC_NB_DASHES)
+            .map(|i| {
+                let start = point((2 * i) as f32 / nb_pieces);
+                let end = point((2 * i + 1) as f32 / nb_pieces);
+                create_dna_bond(start, end, color, 0, true).to_raw_instance()
+            })
+            .collect()
+    }
+
     /// Make a instance with the same postion and orientation as a phantom element.
     pub fn make_instance_phantom(
         &self,
@@ -1069,22 +1357,18 @@ impl<R: DesignReader> Design3D<R> {
 
     pub fn make_phantom_helix_instances_raw(
         &self,
-        helix_ids: &HashMap<u32, bool>,
+        helix_ids: &HashMap<u32, PhantomParameters>,
     ) -> (Rc<Vec<RawDnaInstance>>, Rc<Vec<RawDnaInstance>>) {
         let mut spheres = Vec::new();
         let mut tubes = Vec::new();
-        for (helix_id, short) in helix_ids.iter() {
-            let range_phantom = if *short {
-                PHANTOM_RANGE / 10
-            } else {
-                PHANTOM_RANGE
-            } as isize;
+        for (helix_id, parameters) in helix_ids.iter() {
+            let color = 0x00D0D0D0 | (parameters.alpha() << 24);
             for forward in [false, true].iter() {
                 let mut previous_nucl = None;
                 let range = self
                     .design_reader
                     .get_curve_range(*helix_id as usize)
-                    .unwrap_or(-range_phantom..=range_phantom);
+                    .unwrap_or_else(|| parameters.range());
                 for i in range {
                     let i = i as i32;
                     let nucl_coord = self.design_reader.get_position_of_nucl_on_helix(
@@ -1096,7 +1380,6 @@ impl<R: DesignReader> Design3D<R> {
                         Referential::Model,
                         false,
                     );
-                    let color = 0xFFD0D0D0;
                     if nucl_coord.is_none() {
                         continue;
                     }
@@ -1126,6 +1409,271 @@ impl<R: DesignReader> Design3D<R> {
         (Rc::new(spheres), Rc::new(tubes))
     }
 
+    /// Return the spheres and tubes of the helix that would be created on the grid position
+    /// `grid_position`, with its strands going from `start` to `start + length`.
+    pub fn make_ghost_helix_instances(
+        &self,
+        grid_position: GridPosition,
+        start: isize,
+        length: usize,
+    ) -> (Vec<RawDnaInstance>, Vec<RawDnaInstance>) {
+        let mut spheres = Vec::new();
+        let mut tubes = Vec::new();
+        let origin =
+            if let Some(origin) = self.design_reader.get_grid_latice_position(grid_position) {
+                origin
+            } else {
+                return (spheres, tubes);
+            };
+        let orientation = self
+            .design_reader
+            .get_grid_basis(grid_position.grid)
+            .unwrap_or_else(Rotor3::identity);
+        let parameters = self.design_reader.get_parameters();
+        let nucl_position = |n: isize, forward: bool| {
+            // Same as the position of the nucleotides of a straight helix with no roll
+            let shift = if forward { parameters.groove_angle } else { 0. };
+            let theta = -n as f32 * TAU / parameters.bases_per_turn + shift + PI / 2.;
+            let delta_inclination = if forward { 0. } else { parameters.inclination };
+            let local = Vec3::new(
+                n as f32 * parameters.rise + delta_inclination,
+                theta.sin() * parameters.helix_radius,
+                theta.cos() * parameters.helix_radius,
+            );
+            origin + local.rotated_by(orientation)
+        };
+        let axis_point = |n: isize| {
+            origin + (n as f32 * parameters.rise) * Vec3::unit_x().rotated_by(orientation)
+        };
+
+        // The axis spans at least one nucleotide so that the helix is visible even if it is
+        // created without strands.
+        let end = start + length.max(1) as isize;
+        tubes.push(
+            create_dna_bond(
+                axis_point(start),
+                axis_point(end),
+                GHOST_HELIX_COLOR,
+                0,
+                true,
+            )
+            .with_radius(BOND_RADIUS)
+            .to_raw_instance(),
+        );
+        for forward in [false, true] {
+            let mut previous_nucl = None;
+            for n in start..(start + length as isize) {
+                let position = nucl_position(n, forward);
+                spheres.push(
+                    SphereInstance {
+                        position,
+                        color: Instance::color_from_au32(GHOST_HELIX_COLOR),
+                        id: 0,
+                        radius: 0.6 * SPHERE_RADIUS,
+                    }
+                    .to_raw_instance(),
+                );
+                if let Some(previous) = previous_nucl {
+                    tubes.push(
+                        create_dna_bond(position, previous, GHOST_HELIX_COLOR, 0, true)
+                            .with_radius(0.6 * BOND_RADIUS)
+                            .to_raw_instance(),
+                    );
+                }
+                previous_nucl = Some(position);
+            }
+        }
+        (spheres, tubes)
+    }
+
+    /// Return true if a double strand going from `start` to `start + length` can be added on
+    /// helix `h_id`.
+    pub fn double_strand_fits(&self, h_id: usize, start: isize, length: usize) -> bool {
+        length > 0
+            && (start..(start + length as isize)).all(|position| {
+                [false, true].iter().all(|forward| {
+                    !self.has_nucl(&Nucl {
+                        helix: h_id,
+                        position,
+                        forward: *forward,
+                    })
+                })
+            })
+    }
+
+    /// Return the spheres and tubes of the double strand that would be added on helix `h_id`,
+    /// going from `start` to `start + length`.
+    pub fn make_ghost_double_strand_instances(
+        &self,
+        h_id: usize,
+        start: isize,
+        length: usize,
+    ) -> (Vec<RawDnaInstance>, Vec<RawDnaInstance>) {
+        let mut spheres = Vec::new();
+        let mut tubes = Vec::new();
+        for forward in [false, true] {
+            let mut previous_nucl = None;
+            for position in start..(start + length as isize) {
+                let nucl = Nucl {
+                    helix: h_id,
+                    position,
+                    forward,
+                };
+                let position = if let Some(position) = self
+                    .design_reader
+                    .get_position_of_nucl_on_helix(nucl, Referential::Model, false)
+                {
+                    position
+                } else {
+                    continue;
+                };
+                spheres.push(
+                    SphereInstance {
+                        position,
+                        color: Instance::color_from_au32(GHOST_HELIX_COLOR),
+                        id: 0,
+                        radius: 0.6 * SPHERE_RADIUS,
+                    }
+                    .to_raw_instance(),
+                );
+                if let Some(previous) = previous_nucl {
+                    tubes.push(
+                        create_dna_bond(position, previous, GHOST_HELIX_COLOR, 0, true)
+                            .with_radius(0.6 * BOND_RADIUS)
+                            .to_raw_instance(),
+                    );
+                }
+                previous_nucl = Some(position);
+            }
+        }
+        (spheres, tubes)
+    }
+
+    fn get_nucls_of_helix(&self, h_id: usize) -> Vec<Nucl> {
+        self.design_reader
+            .get_ids_of_elements_belonging_to_helix(h_id)
+            .into_iter()
+            .filter_map(|id| self.design_reader.get_nucl_with_id(id))
+            .filter(|n| n.helix == h_id)
+            .collect()
+    }
+
+    /// Return the outermost nucleotides of each strand of helix `h_id` at its `end`, if strand
+    /// builders can be started on them.
+    pub fn get_helix_end_builder_nucls(&self, h_id: usize, end: HelixEnd) -> Vec<Nucl> {
+        let nucls = self.get_nucls_of_helix(h_id);
+        [false, true]
+            .into_iter()
+            .filter_map(|forward| {
+                let on_strand = nucls.iter().filter(|n| n.forward == forward);
+                match end {
+                    HelixEnd::Left => on_strand.min_by_key(|n| n.position),
+                    HelixEnd::Right => on_strand.max_by_key(|n| n.position),
+                }
+                .cloned()
+            })
+            .filter(|n| self.design_reader.can_start_builder_at(n))
+            .collect()
+    }
+
+    /// Return the instances of the two handles that can be dragged to modify the length of helix
+    /// `h_id`. The handles are placed on the helix axis, one step beyond its outermost
+    /// nucleotides.
+    pub fn make_helix_length_handles(&self, h_id: usize) -> Vec<RawDnaInstance> {
+        let nucls = self.get_nucls_of_helix(h_id);
+        let min = nucls.iter().map(|n| n.position).min();
+        let max = nucls.iter().map(|n| n.position).max();
+        let (min, max) = if let Some(extremities) = min.zip(max) {
+            extremities
+        } else {
+            return vec![];
+        };
+        [(HelixEnd::Left, min - 1), (HelixEnd::Right, max + 1)]
+            .into_iter()
+            .filter_map(|(end, position)| {
+                let nucl = Nucl {
+                    helix: h_id,
+                    position,
+                    forward: true,
+                };
+                let position = self.design_reader.get_position_of_nucl_on_helix(
+                    nucl,
+                    Referential::Model,
+                    true,
+                )?;
+                Some(
+                    SphereInstance {
+                        position,
+                        color: Instance::color_from_u32(HELIX_LENGTH_HANDLE_COLOR),
+                        id: crate::element_selector::helix_length_handle_id(h_id, end),
+                        radius: HELIX_LENGTH_HANDLE_RADIUS,
+                    }
+                    .to_raw_instance(),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the dial showing the roll of helix `h_id`. The dial is centered on the helix axis,
+    /// a few steps before its leftmost nucleotide, and its needle points towards the backward
+    /// nucleotide at that position.
+    pub fn get_helix_roll_dial(&self, h_id: usize) -> Option<RollDial> {
+        let min = self
+            .get_nucls_of_helix(h_id)
+            .iter()
+            .map(|n| n.position)
+            .min()?;
+        let position = min - ROLL_DIAL_OFFSET;
+        let position_on_helix = |position: isize, on_axis: bool| {
+            let nucl = Nucl {
+                helix: h_id,
+                position,
+                forward: false,
+            };
+            self.design_reader
+                .get_position_of_nucl_on_helix(nucl, Referential::Model, on_axis)
+        };
+        let center = position_on_helix(position, true)?;
+        let axis = (position_on_helix(position + 1, true)? - center).normalized();
+        let needle = position_on_helix(position, false)? - center;
+        Some(RollDial {
+            center,
+            axis,
+            needle: needle - needle.dot(axis) * axis,
+            roll: self.design_reader.get_helix_roll(h_id)?,
+        })
+    }
+
+    /// Return the instances of the tubes drawing the roll dial of helix `h_id`, and of the knob
+    /// that can be dragged to roll the helix.
+    pub fn make_helix_roll_dial(&self, h_id: usize) -> (Vec<RawDnaInstance>, Vec<RawDnaInstance>) {
+        let dial = if let Some(dial) = self.get_helix_roll_dial(h_id) {
+            dial
+        } else {
+            return (vec![], vec![]);
+        };
+        let id = crate::element_selector::helix_roll_dial_id(h_id);
+        let side = dial.axis.cross(dial.needle);
+        let ring_point = |i: usize| {
+            let angle = TAU * i as f32 / ROLL_DIAL_NB_SEGMENTS as f32;
+            dial.center + angle.cos() * dial.needle + angle.sin() * side
+        };
+        let mut tubes: Vec<RawDnaInstance> = (0..ROLL_DIAL_NB_SEGMENTS)
+            .map(|i| create_roll_dial_tube(ring_point(i), ring_point(i + 1), id).to_raw_instance())
+            .collect();
+        tubes.push(
+            create_roll_dial_tube(dial.center, dial.center + dial.needle, id).to_raw_instance(),
+        );
+        let knob = SphereInstance {
+            position: dial.center + dial.needle,
+            color: Instance::color_from_u32(HELIX_ROLL_DIAL_COLOR),
+            id,
+            radius: HELIX_ROLL_DIAL_KNOB_RADIUS,
+        }
+        .to_raw_instance();
+        (tubes, vec![knob])
+    }
+
     fn get_object_type(&self, id: u32) -> Option<ObjectType> {
         self.design_reader.get_object_type(id)
     }
@@ -1191,6 +1739,10 @@ impl<R: DesignReader> Design3D<R> {
             | SceneElement::BezierVertex { .. }
             | SceneElement::GridCircle(_, _)
             | SceneElement::PlaneCorner { .. }
+            | SceneElement::HelixLengthHandle { .. }
+            | SceneElement::HelixRollDial { .. }
+            | SceneElement::TwistAxisHandle { .. }
+            | SceneElement::InsertionBadge { .. }
             | SceneElement::BezierTangent { .. } => None,
         }
     }
@@ -1213,6 +1765,10 @@ impl<R: DesignReader> Design3D<R> {
             | SceneElement::BezierVertex { .. }
             | SceneElement::GridCircle(_, _)
             | SceneElement::PlaneCorner { .. }
+            | SceneElement::HelixLengthHandle { .. }
+            | SceneElement::HelixRollDial { .. }
+            | SceneElement::TwistAxisHandle { .. }
+            | SceneElement::InsertionBadge { .. }
             | SceneElement::BezierTangent { .. } => None,
         }
     }
@@ -1266,7 +1822,13 @@ impl<R: DesignReader> Design3D<R> {
         self.design_reader.get_model_matrix().transform_vec3(middle)
     }
 
-    fn boundaries(&self) -> [f32; 6] {
+    pub fn get_helix_parameters(&self) -> HelixParameters {
+        self.design_reader.get_parameters()
+    }
+
+    /// Return `[min_x, max_x, min_y, max_y, min_z, max_z]`, the bounding box of `self` in the
+    /// world coordinates.
+    pub fn boundaries(&self) -> [f32; 6] {
         let mut min_x = std::f32::INFINITY;
         let mut min_y = std::f32::INFINITY;
         let mut min_z = std::f32::INFINITY;
@@ -1506,6 +2068,14 @@ impl<R: DesignReader> Design3D<R> {
             .unwrap_or_default()
     }
 
+    pub fn get_helices_axis_ends(&self) -> BTreeMap<usize, (Vec3, Vec3)> {
+        self.design_reader.get_helices_axis_ends()
+    }
+
+    pub fn get_helix_display_number(&self, h_id: usize) -> usize {
+        self.design_reader.get_helix_display_number(h_id)
+    }
+
     pub fn get_helix_grid(&self, position: GridPosition) -> Option<u32> {
         self.design_reader.get_helix_id_at_grid_coord(position)
     }
@@ -1514,10 +2084,14 @@ impl<R: DesignReader> Design3D<R> {
         self.design_reader.get_grid_object(position)
     }
 
-    pub fn get_persistent_phantom_helices(&self) -> HashSet<u32> {
+    pub fn get_persistent_phantom_helices(&self) -> HashMap<u32, PhantomParameters> {
         self.design_reader.get_persistent_phantom_helices_id()
     }
 
+    pub fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters {
+        self.design_reader.get_grid_phantom_parameters(g_id)
+    }
+
     pub fn get_grid_basis(&self, g_id: GridId) -> Option<Rotor3> {
         self.design_reader.get_grid_basis(g_id)
     }
@@ -1539,6 +2113,22 @@ impl<R: DesignReader> Design3D<R> {
             .get_position_of_nucl_on_helix(nucl, Referential::World, false)
     }
 
+    /// Return all the nucleotides of the design, including the invisible ones, with their
+    /// position.
+    pub fn get_all_nucls_with_position(&self) -> Vec<(Nucl, Vec3)> {
+        self.design_reader
+            .get_all_nucl_ids()
+            .into_iter()
+            .filter_map(|id| {
+                let nucl = self.design_reader.get_nucl_with_id(id)?;
+                let position = self
+                    .design_reader
+                    .get_element_position(id, Referential::World)?;
+                Some((nucl, position))
+            })
+            .collect()
+    }
+
     pub fn pivot_sphere(position: Vec3, radius: f32) -> RawDnaInstance {
         SphereInstance {
             position,
@@ -1559,6 +2149,45 @@ impl<R: DesignReader> Design3D<R> {
         .to_raw_instance()
     }
 
+    /// A sphere of the glow moving along a traced strand. `intensity` goes from 1 at the head of
+    /// the glow to 0 at the end of its tail.
+    pub fn strand_trace_sphere(position: Vec3, intensity: f32) -> RawDnaInstance {
+        let alpha = (0xFF as f32 * intensity) as u32;
+        SphereInstance {
+            position,
+            id: 0,
+            radius: (1. + intensity) * SELECT_SCALE_FACTOR * SPHERE_RADIUS,
+            color: Instance::color_from_au32((STRAND_TRACE_COLOR & 0xFF_FF_FF) | (alpha << 24)),
+        }
+        .to_raw_instance()
+    }
+
+    /// Return the position of the nucleotides of the strand `s_id`, from its 5' end to its 3'
+    /// end. The nucleotides of the insertions are skipped.
+    pub fn get_strand_trace_positions(&self, s_id: usize) -> Vec<Vec3> {
+        let strand = if let Some(strand) = self.design_reader.get_strand_with_id(s_id) {
+            strand
+        } else {
+            return vec![];
+        };
+        let mut ret = Vec::with_capacity(strand.length());
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                for position in interval.iter() {
+                    let nucl = Nucl {
+                        helix: interval.helix,
+                        position,
+                        forward: interval.forward,
+                    };
+                    if let Some(position) = self.get_nucl_position(nucl) {
+                        ret.push(position)
+                    }
+                }
+            }
+        }
+        ret
+    }
+
     pub fn free_xover_sphere(position: Vec3) -> RawDnaInstance {
         SphereInstance {
             position,
@@ -1594,7 +2223,9 @@ impl<R: DesignReader> Design3D<R> {
         if self.all_helices_on_axis {
             return vec![];
         }
-        let cones = self.design_reader.get_all_prime3_nucl();
+        let cones = self
+            .design_reader
+            .get_all_prime3_nucl(self.ignore_visibility_sieve);
         let mut ret = Vec::with_capacity(cones.len());
         for c in cones {
             ret.push(create_prime3_cone(c.0, c.1, c.2, 1.));
@@ -1611,6 +2242,92 @@ impl<R: DesignReader> Design3D<R> {
     }
 }
 
+fn create_roll_dial_tube(source: Vec3, dest: Vec3, id: u32) -> TubeInstance {
+    TubeInstance {
+        radius: HELIX_ROLL_DIAL_WIDTH,
+        ..create_dna_bond(source, dest, HELIX_ROLL_DIAL_COLOR, id, false)
+    }
+}
+
+/// The tube representing the axis around which free helices are twisted, and the two handles
+/// used to move it
+/// Return the spheres and tubes representing the joints of the design. Ball joints are drawn as
+/// a sphere, hinges as their axis and sliders as a rail along their axis.
+pub fn make_joints<R: DesignReader>(
+    design_reader: &R,
+) -> (Vec<RawDnaInstance>, Vec<RawDnaInstance>) {
+    let mut spheres = Vec::new();
+    let mut tubes = Vec::new();
+    for (kind, anchor, axis) in design_reader.get_joints() {
+        let (radius, half_length) = match kind {
+            JointKind::Ball => (JOINT_RADIUS, 0.),
+            JointKind::Hinge => (SPHERE_RADIUS * 1.5, JOINT_AXIS_LENGTH / 2.),
+            JointKind::Slider => (SPHERE_RADIUS * 1.5, JOINT_AXIS_LENGTH),
+        };
+        spheres.push(
+            SphereInstance {
+                position: anchor,
+                color: Instance::color_from_u32(JOINT_COLOR),
+                id: 0,
+                radius,
+            }
+            .to_raw_instance(),
+        );
+        if half_length > 0. {
+            let ends = [anchor - axis * half_length, anchor + axis * half_length];
+            tubes.push(
+                TubeInstance {
+                    radius: JOINT_AXIS_WIDTH,
+                    ..create_dna_bond(ends[0], ends[1], JOINT_COLOR, 0, false)
+                }
+                .to_raw_instance(),
+            );
+            if kind == JointKind::Slider {
+                // The stops of the rail
+                for end in ends.iter() {
+                    spheres.push(
+                        SphereInstance {
+                            position: *end,
+                            color: Instance::color_from_u32(JOINT_COLOR),
+                            id: 0,
+                            radius: SPHERE_RADIUS,
+                        }
+                        .to_raw_instance(),
+                    );
+                }
+            }
+        }
+    }
+    (spheres, tubes)
+}
+
+pub fn make_twist_axis(axis: &TwistAxis) -> (RawDnaInstance, Vec<RawDnaInstance>) {
+    let tube = TubeInstance {
+        radius: TWIST_AXIS_WIDTH,
+        ..create_dna_bond(
+            axis.origin,
+            axis.tip(),
+            TWIST_AXIS_COLOR,
+            crate::element_selector::twist_axis_handle_id(false),
+            false,
+        )
+    }
+    .to_raw_instance();
+    let handles = [(axis.origin, false), (axis.tip(), true)]
+        .iter()
+        .map(|(position, tip)| {
+            SphereInstance {
+                position: *position,
+                color: Instance::color_from_u32(TWIST_AXIS_COLOR),
+                id: crate::element_selector::twist_axis_handle_id(*tip),
+                radius: TWIST_AXIS_HANDLE_RADIUS,
+            }
+            .to_raw_instance()
+        })
+        .collect();
+    (tube, handles)
+}
+
 fn create_dna_bond(source: Vec3, dest: Vec3, color: u32, id: u32, use_alpha: bool) -> TubeInstance {
     let color = if use_alpha {
         Instance::color_from_au32(color)
@@ -1734,10 +2451,12 @@ pub(super) enum ExpandWith {
 // Array of (strand number, array of 3D space position of the nucleotides)
 
 pub trait DesignReader: 'static + ensnano_interactor::DesignReader {
-    /// Return the identifier of all the visible nucleotides
-    fn get_all_visible_nucl_ids(&self) -> Vec<u32>;
-    /// Return the identifier of all the visible bounds
-    fn get_all_visible_bond_ids(&self) -> Vec<u32>;
+    /// Return the identifier of all the visible nucleotides. If `ignore_visibility_sieve` is
+    /// true, nucleotides hidden by the visibility sieve are returned as well.
+    fn get_all_visible_nucl_ids(&self, ignore_visibility_sieve: bool) -> Vec<u32>;
+    /// Return the identifier of all the visible bounds. If `ignore_visibility_sieve` is
+    /// true, bounds hidden by the visibility sieve are returned as well.
+    fn get_all_visible_bond_ids(&self, ignore_visibility_sieve: bool) -> Vec<u32>;
     fn get_all_nucl_ids(&self) -> Vec<u32>;
     fn get_all_bond_ids(&self) -> Vec<u32>;
     fn get_pasted_position(&self) -> Vec<(Vec<Vec3>, bool)>;
@@ -1770,12 +2489,20 @@ pub trait DesignReader: 'static + ensnano_interactor::DesignReader {
     fn get_radius(&self, e_id: u32) -> Option<f32>;
     fn get_xover_coloring(&self, e_id: u32) -> Option<bool>;
     fn get_with_cones(&self, e_id: u32) -> Option<bool>;
+    /// Return the cone spacing required by the drawing style of the bond e_id, if any
+    fn get_cone_spacing(&self, e_id: u32) -> Option<usize>;
+    /// Return the rank of the bond e_id along its strand
+    fn get_bond_rank(&self, e_id: u32) -> Option<usize>;
+    /// Return the ends of the visible non-cyclic strands. If `ignore_visibility_sieve` is true,
+    /// the ends hidden by the visibility sieve are returned as well.
+    fn get_strand_ends(&self, ignore_visibility_sieve: bool) -> Vec<StrandEnd>;
     fn get_id_of_strand_containing(&self, e_id: u32) -> Option<usize>;
     fn get_id_of_helix_containing(&self, e_id: u32) -> Option<usize>;
     fn get_ids_of_all_helices(&self) -> Vec<u32>;
     fn get_ids_of_elements_belonging_to_strand(&self, s_id: usize) -> Vec<u32>;
     fn get_ids_of_elements_belonging_to_helix(&self, h_id: usize) -> Vec<u32>;
     fn get_helix_basis(&self, h_id: u32) -> Option<Rotor3>;
+    fn get_helix_roll(&self, h_id: usize) -> Option<f32>;
     fn get_basis(&self) -> Rotor3;
     fn get_identifier_nucl(&self, nucl: &Nucl) -> Option<u32>;
     fn get_identifier_bond(&self, n1: Nucl, n2: Nucl) -> Option<u32>;
@@ -1788,12 +2515,23 @@ pub trait DesignReader: 'static + ensnano_interactor::DesignReader {
     fn get_used_coordinates_on_grid(&self, g_id: GridId) -> Option<Vec<(isize, isize)>>;
     fn get_helices_grid_key_coord(&self, g_id: GridId) -> Option<Vec<((isize, isize), usize)>>;
     fn get_helix_id_at_grid_coord(&self, position: GridPosition) -> Option<u32>;
-    fn get_persistent_phantom_helices_id(&self) -> HashSet<u32>;
+    /// Return a map from the identifiers of the helices to the positions, on their axis and in
+    /// the model's referential, of their first and last nucleotides.
+    fn get_helices_axis_ends(&self) -> BTreeMap<usize, (Vec3, Vec3)>;
+    /// Return the number with which helix `h_id` is displayed to the user.
+    fn get_helix_display_number(&self, h_id: usize) -> usize;
+    /// Return the kind, the anchor and the axis, in the model's referential, of the joints of the
+    /// design.
+    fn get_joints(&self) -> Vec<(JointKind, Vec3, Vec3)>;
+    /// Return a map from the identifiers of the helices whose phantom helices must always be
+    /// drawn to the way these phantom helices must be drawn.
+    fn get_persistent_phantom_helices_id(&self) -> HashMap<u32, PhantomParameters>;
+    fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters;
     fn get_grid_basis(&self, g_id: GridId) -> Option<Rotor3>;
     fn get_helix_grid_position(&self, h_id: u32) -> Option<HelixGridPosition>;
     fn prime5_of_which_strand(&self, nucl: Nucl) -> Option<usize>;
     fn prime3_of_which_strand(&self, nucl: Nucl) -> Option<usize>;
-    fn get_all_prime3_nucl(&self) -> Vec<(Vec3, Vec3, u32)>;
+    fn get_all_prime3_nucl(&self, ignore_visibility_sieve: bool) -> Vec<(Vec3, Vec3, u32)>;
     fn get_curve_range(&self, h_id: usize) -> Option<std::ops::RangeInclusive<isize>>;
     fn get_checked_xovers_ids(&self, checked: bool) -> Vec<u32>;
     fn get_id_of_xover_involving_nucl(&self, nucl: Nucl) -> Option<usize>;
@@ -1810,6 +2548,10 @@ pub trait DesignReader: 'static + ensnano_interactor::DesignReader {
     fn get_all_loopout_nucl(&self) -> &[LoopoutNucl];
     fn get_all_loopout_bonds(&self) -> &[LoopoutBond];
     fn get_insertion_length(&self, bond_id: u32) -> usize;
+    /// Return the identifiers of the elements representing the visible insertions together with
+    /// the length of the insertions. If `ignore_visibility_sieve` is true, insertions hidden by
+    /// the visibility sieve are returned as well.
+    fn get_insertion_lengths(&self, ignore_visibility_sieve: bool) -> Vec<(u32, usize)>;
     fn get_expected_bond_length(&self) -> f32;
     fn get_bezier_planes(
         &self,
@@ -159,11 +159,13 @@ impl<R: DesignReader> Design3D<R> {
                     )
                 }
                 for point in path.get_curve_points().iter() {
+                    // The curve cannot be picked, it must not hide the handles of the
+                    // path in the picking texture.
                     spheres.push(
                         SphereInstance {
                             position: Vec3::new(point.x as f32, point.y as f32, point.z as f32),
                             color: [1., 0., 0., 1.].into(),
-                            id: 0,
+                            id: u32::MAX,
                             radius: 2.0 * SPHERE_RADIUS,
                         }
                         .to_raw_instance(),
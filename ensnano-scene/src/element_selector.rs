@@ -20,10 +20,12 @@ use std::rc::Rc;
 use super::{Device, DrawArea, DrawType, Queue, ViewPtr};
 use ensnano_design::grid::{GridId, GridPosition};
 use ensnano_design::{BezierPathId, BezierPlaneId, BezierVertexId};
+use ensnano_interactor::consts::PICKING_RADIUS;
 use ensnano_interactor::{phantom_helix_decoder, BezierControlPoint, PhantomElement};
 use ensnano_utils as utils;
 use futures::executor;
 use num_enum::IntoPrimitive;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use utils::wgpu;
 use utils::winit::dpi::{PhysicalPosition, PhysicalSize};
@@ -37,6 +39,9 @@ pub struct ElementSelector {
     view: ViewPtr,
     area: DrawArea,
     stereographic: bool,
+    /// The offsets of the pixels that are looked at when the user clicks, sorted by increasing
+    /// distance to the clicked pixel.
+    picking_offsets: Vec<(i32, i32)>,
 }
 
 impl ElementSelector {
@@ -61,6 +66,7 @@ impl ElementSelector {
             view,
             area,
             stereographic: false,
+            picking_offsets: picking_offsets(PICKING_RADIUS),
         }
     }
 
@@ -80,14 +86,46 @@ impl ElementSelector {
         &mut self,
         clicked_pixel: PhysicalPosition<f64>,
     ) -> Option<SceneElement> {
+        self.update_pixels_if_needed();
+        self.get_highest_priority_element(clicked_pixel)
+    }
+
+    /// Return all the design elements that are drawn, and not hidden behind other objects, in at
+    /// least one pixel of the scene's area.
+    pub fn get_visible_design_elements(&mut self) -> Vec<SceneElement> {
+        self.update_pixels_if_needed();
+        let reader = if let Some(reader) = self
+            .readers
+            .iter()
+            .find(|r| matches!(r.draw_type, DrawType::Design))
+        {
+            reader
+        } else {
+            return vec![];
+        };
+        let mut ids = HashSet::new();
+        let x_max = (self.area.position.x + self.area.size.width).min(self.window_size.width);
+        let y_max = (self.area.position.y + self.area.size.height).min(self.window_size.height);
+        for y in self.area.position.y..y_max {
+            for x in self.area.position.x..x_max {
+                let byte0 = (y * self.window_size.width + x) as usize * std::mem::size_of::<u32>();
+                if let Some(SceneElement::DesignElement(d_id, e_id)) = reader.read_pixel(byte0) {
+                    ids.insert((d_id, e_id));
+                }
+            }
+        }
+        ids.into_iter()
+            .map(|(d_id, e_id)| SceneElement::DesignElement(d_id, e_id))
+            .collect()
+    }
+
+    fn update_pixels_if_needed(&mut self) {
         if self.readers[0].pixels.is_none() || self.view.borrow().need_redraw_fake() {
             for i in 0..self.readers.len() {
                 let pixels = self.update_fake_pixels(self.readers[i].draw_type, self.stereographic);
                 self.readers[i].pixels = Some(pixels)
             }
         }
-
-        self.get_highest_priority_element(clicked_pixel)
     }
 
     fn get_highest_priority_element(
@@ -95,23 +133,30 @@ impl ElementSelector {
         clicked_pixel: PhysicalPosition<f64>,
     ) -> Option<SceneElement> {
         let pixel = (
-            clicked_pixel.cast::<u32>().x.min(self.area.size.width - 1) + self.area.position.x,
-            clicked_pixel.cast::<u32>().y.min(self.area.size.height - 1) + self.area.position.y,
+            clicked_pixel.cast::<u32>().x.min(self.area.size.width - 1) as i32,
+            clicked_pixel.cast::<u32>().y.min(self.area.size.height - 1) as i32,
         );
-        for max_delta in 0..=5 {
-            let min_x = pixel.0.max(max_delta) - max_delta;
-            let max_x = (pixel.0 + max_delta).min(self.window_size.width - 1);
-            let min_y = pixel.1.max(max_delta) - max_delta;
-            let max_y = (pixel.1 + max_delta).min(self.window_size.height - 1);
-            for x in min_x..=max_x {
-                for y in min_y..=max_y {
-                    let byte0 =
-                        (y * self.window_size.width + x) as usize * std::mem::size_of::<u32>();
-                    for reader in self.readers.iter() {
-                        if let Some(element) = reader.read_pixel(byte0) {
-                            return Some(element);
-                        }
-                    }
+        // The pixels are looked at from the nearest to the furthest so that the element directly
+        // under the cursor is always preferred to its neighbours. For a given pixel, the readers
+        // are sorted by decreasing priority.
+        for (dx, dy) in self.picking_offsets.iter() {
+            let (x, y) = (pixel.0 + dx, pixel.1 + dy);
+            if x < 0
+                || y < 0
+                || x >= self.area.size.width as i32
+                || y >= self.area.size.height as i32
+            {
+                continue;
+            }
+            let x = x as u32 + self.area.position.x;
+            let y = y as u32 + self.area.position.y;
+            if x >= self.window_size.width || y >= self.window_size.height {
+                continue;
+            }
+            let byte0 = (y * self.window_size.width + x) as usize * std::mem::size_of::<u32>();
+            for reader in self.readers.iter() {
+                if let Some(element) = reader.read_pixel(byte0) {
+                    return Some(element);
                 }
             }
         }
@@ -234,6 +279,17 @@ impl ElementSelector {
     }
 }
 
+/// Return the offsets of the pixels that are at most `radius` pixels away from the origin, sorted
+/// by increasing distance to the origin.
+fn picking_offsets(radius: i32) -> Vec<(i32, i32)> {
+    let mut ret: Vec<(i32, i32)> = (-radius..=radius)
+        .flat_map(|dx| (-radius..=radius).map(move |dy| (dx, dy)))
+        .filter(|(dx, dy)| dx * dx + dy * dy <= radius * radius)
+        .collect();
+    ret.sort_by_key(|(dx, dy)| dx * dx + dy * dy);
+    ret
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SceneElement {
     DesignElement(u32, u32),
@@ -258,6 +314,34 @@ pub enum SceneElement {
         plane_id: BezierPlaneId,
         corner_type: CornerType,
     },
+    /// A handle at one end of a helix, that can be dragged to change the length of the helix
+    HelixLengthHandle {
+        helix_id: usize,
+        end: HelixEnd,
+    },
+    /// The knob of the dial showing the roll of a helix, that can be dragged to roll the helix
+    HelixRollDial {
+        helix_id: usize,
+    },
+    /// One of the two handles of the axis around which free helices are twisted. The origin
+    /// handle translates the axis and the tip handle orients it.
+    TwistAxisHandle {
+        tip: bool,
+    },
+    /// The badge showing the length of an insertion. `bond_id` is the identifier of the element
+    /// that represents the insertion.
+    InsertionBadge {
+        bond_id: u32,
+    },
+}
+
+/// One of the two ends of a helix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelixEnd {
+    /// The end with the smallest nucleotide positions
+    Left,
+    /// The end with the largest nucleotide positions
+    Right,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -309,6 +393,10 @@ impl SceneElement {
             SceneElement::BezierVertex { .. } => Some(0),
             SceneElement::PlaneCorner { .. } => Some(0),
             SceneElement::BezierTangent { .. } => Some(0),
+            SceneElement::HelixLengthHandle { .. } => Some(0),
+            SceneElement::HelixRollDial { .. } => Some(0),
+            SceneElement::TwistAxisHandle { .. } => Some(0),
+            SceneElement::InsertionBadge { .. } => Some(0),
         }
     }
 
@@ -366,6 +454,10 @@ enum ObjType {
     BezierPlaneCorner = 0xFD,
     BezierTangentIn = 0xFC,
     BezierTangentOut = 0xFB,
+    HelixLengthHandle = 0xFA,
+    HelixRollDial = 0xF9,
+    TwistAxisHandle = 0xF8,
+    InsertionBadge = 0xF7,
 }
 
 impl SceneReader {
@@ -436,7 +528,29 @@ impl SceneReader {
                     Some(SceneElement::PhantomElement(phantom_helix_decoder(color)))
                 }
                 DrawType::Widget => {
-                    Some(SceneElement::WidgetElement(color).transform_into_bezier())
+                    if a == u32::from(ObjType::HelixLengthHandle) {
+                        let end = if color & 1 == 0 {
+                            HelixEnd::Left
+                        } else {
+                            HelixEnd::Right
+                        };
+                        Some(SceneElement::HelixLengthHandle {
+                            helix_id: (color >> 1) as usize,
+                            end,
+                        })
+                    } else if a == u32::from(ObjType::HelixRollDial) {
+                        Some(SceneElement::HelixRollDial {
+                            helix_id: color as usize,
+                        })
+                    } else if a == u32::from(ObjType::TwistAxisHandle) {
+                        Some(SceneElement::TwistAxisHandle {
+                            tip: color & 1 == 1,
+                        })
+                    } else if a == u32::from(ObjType::InsertionBadge) {
+                        Some(SceneElement::InsertionBadge { bond_id: color })
+                    } else {
+                        Some(SceneElement::WidgetElement(color).transform_into_bezier())
+                    }
                 }
                 DrawType::Scene => unreachable!(),
                 DrawType::Png { .. } => unreachable!(),
@@ -449,6 +563,26 @@ pub fn bezier_vertex_id(path_id: BezierPathId, vertex_id: usize) -> u32 {
     (u32::from(ObjType::BezierVertex) << 24) | ((path_id.0) << 16) | (vertex_id as u32)
 }
 
+pub fn helix_length_handle_id(helix_id: usize, end: HelixEnd) -> u32 {
+    let end_bit = match end {
+        HelixEnd::Left => 0,
+        HelixEnd::Right => 1,
+    };
+    (u32::from(ObjType::HelixLengthHandle) << 24) | ((helix_id as u32) << 1) | end_bit
+}
+
+pub fn helix_roll_dial_id(helix_id: usize) -> u32 {
+    (u32::from(ObjType::HelixRollDial) << 24) | (helix_id as u32)
+}
+
+pub fn twist_axis_handle_id(tip: bool) -> u32 {
+    (u32::from(ObjType::TwistAxisHandle) << 24) | (tip as u32)
+}
+
+pub fn insertion_badge_id(bond_id: u32) -> u32 {
+    (u32::from(ObjType::InsertionBadge) << 24) | bond_id
+}
+
 pub fn bezier_tangent_id(path_id: BezierPathId, vertex_id: usize, tangent_in: bool) -> u32 {
     let front = if tangent_in {
         u32::from(ObjType::BezierTangentIn)
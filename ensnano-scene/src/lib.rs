@@ -36,10 +36,13 @@ use ensnano_design::{grid::GridPosition, group_attributes::GroupPivot, Nucl};
 use ensnano_interactor::graphics::LoopoutNucl;
 use ensnano_interactor::{
     application::{AppId, Application, Camera3D, Notification},
-    graphics::DrawArea,
+    graphics::{
+        BrushParameters, ColorVisionMode, DrawArea, ElementType, GroundParameters,
+        ScaleBarParameters, Thumbnail, ViewLayer,
+    },
     operation::*,
-    ActionMode, CenterOfSelection, CheckXoversParameter, DesignOperation, Selection, SelectionMode,
-    StrandBuilder, WidgetBasis,
+    ActionMode, CenterOfSelection, CheckXoversParameter, DesignOperation, GizmoAxis, Selection,
+    SelectionMode, StrandBuilder, SuggestionParameters, TwistAxis, WidgetBasis,
 };
 use ensnano_utils::{instance, PhySize};
 use instance::Instance;
@@ -69,9 +72,10 @@ use controller::{Consequence, Controller, WidgetTarget};
 mod data;
 pub use controller::ClickMode;
 use data::Data;
+use data::RollDial;
 pub use data::{DesignReader, HBond, HalfHBond, SurfaceInfo, SurfacePoint};
 mod element_selector;
-use element_selector::{ElementSelector, SceneElement};
+use element_selector::{ElementSelector, HelixEnd, SceneElement};
 mod maths_3d;
 
 type ViewPtr = Rc<RefCell<View>>;
@@ -84,6 +88,9 @@ mod rotor_utils;
 mod sausage_rosary;
 
 const PNG_SIZE: u32 = 256 * 10;
+const THUMBNAIL_WIDTH: u32 = 128;
+/// The field of view of the camera during inside-out inspection, in degrees
+const INSIDE_OUT_FOVY: f32 = 140.;
 
 /// A structure responsible of the 3D display of the designs
 pub struct Scene<S: AppState> {
@@ -102,6 +109,8 @@ pub struct Scene<S: AppState> {
     requests: Arc<Mutex<dyn Requests>>,
     scene_kind: SceneKind,
     current_camera: Arc<(Camera3D, f32)>,
+    /// The camera and field of view to restore when leaving the inside-out inspection mode
+    inside_out_backup: Option<(Vec3, Rotor3, f32)>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -110,6 +119,16 @@ pub enum SceneKind {
     Stereographic,
 }
 
+impl SceneKind {
+    /// The element of the multiplexer in which a scene of this kind is drawn
+    pub fn element_type(&self) -> ElementType {
+        match self {
+            Self::Cartesian => ElementType::Scene,
+            Self::Stereographic => ElementType::StereographicScene,
+        }
+    }
+}
+
 impl<S: AppState> Scene<S> {
     /// Create a new scene.
     /// # Argument
@@ -142,6 +161,7 @@ impl<S: AppState> Scene<S> {
         let data: DataPtr<S::DesignReader> = Rc::new(RefCell::new(Data::new(
             initial_state.get_design_reader(),
             view.clone(),
+            scene_kind.element_type(),
         )));
         let controller: Controller<S> =
             Controller::new(view.clone(), data.clone(), window_size, area.size);
@@ -166,6 +186,7 @@ impl<S: AppState> Scene<S> {
                 Default::default(),
                 area.size.width as f32 / area.size.height as f32,
             )),
+            inside_out_backup: None,
         }
     }
 
@@ -174,6 +195,17 @@ impl<S: AppState> Scene<S> {
         self.data.borrow_mut().clear_designs()
     }
 
+    /// The global draw options, overridden by the view layer of the scene
+    fn draw_options(&self, app_state: &S) -> DrawOptions {
+        let global = app_state.get_draw_options();
+        let layer = app_state.get_view_layer(self.scene_kind.element_type());
+        DrawOptions {
+            rendering_mode: layer.rendering_mode(global.rendering_mode),
+            background3d: layer.background3d(global.background3d),
+            ..global
+        }
+    }
+
     fn is_stereographic(&self) -> bool {
         matches!(self.scene_kind, SceneKind::Stereographic)
     }
@@ -345,6 +377,10 @@ impl<S: AppState> Scene<S> {
                         .get_pivot_position()
                         .and_then(|p| p.try_into().ok());
                 }
+                if self.inside_out_backup.is_some() {
+                    // Look around from the current position of the camera
+                    pivot = None;
+                }
                 self.controller.set_pivot_point(pivot);
                 self.controller.swing(-x, -y);
                 self.notify(SceneNotification::CameraMoved);
@@ -368,6 +404,15 @@ impl<S: AppState> Scene<S> {
                 self.controller.continuous_tilt(angle);
                 self.notify(SceneNotification::CameraMoved);
             }
+            Consequence::ViewFromAxis(normal) => {
+                let up = if normal.y.abs() > 0.5 {
+                    Vec3::unit_z() * -normal.y
+                } else {
+                    Vec3::unit_y()
+                };
+                self.set_camera_target(-normal, up, app_state);
+                self.notify(SceneNotification::CameraMoved);
+            }
             Consequence::ToggleWidget => {
                 self.requests.lock().unwrap().toggle_widget_basis();
             }
@@ -381,11 +426,51 @@ impl<S: AppState> Scene<S> {
                     .update_builder_position(position);
             }
             Consequence::Candidate(element) => self.set_candidate(element, app_state),
+            Consequence::BrushMoved(nucls) => self.requests.lock().unwrap().set_candidate(
+                nucls
+                    .into_iter()
+                    .map(|n| Selection::Nucleotide(0, n))
+                    .collect(),
+            ),
+            Consequence::BrushReleased(nucls) => {
+                let mut requests = self.requests.lock().unwrap();
+                requests.set_candidate(vec![]);
+                requests.apply_brush(nucls, app_state.get_brush_parameters());
+            }
             Consequence::PivotElement(element) => {
                 self.data.borrow_mut().set_pivot_element(element, app_state);
                 let pivot = self.data.borrow().get_pivot_position();
                 self.view.borrow_mut().update(ViewUpdate::FogCenter(pivot));
             }
+            Consequence::ContextMenu(element) => {
+                let (selection, center_of_selection) =
+                    self.data.borrow_mut().set_selection(element, app_state);
+                if let Some(selection) = selection {
+                    let mut requests = self.requests.lock().unwrap();
+                    requests.set_selection(vec![selection], center_of_selection);
+                    requests.open_context_menu(selection);
+                }
+            }
+            Consequence::EditInsertionLength(badge) => {
+                self.select(Some(badge), app_state);
+                self.requests.lock().unwrap().edit_insertion_length();
+            }
+            Consequence::AddDoubleStrand {
+                helix,
+                start,
+                length,
+            } => self.requests.lock().unwrap().apply_design_operation(
+                DesignOperation::AddDoubleStrand {
+                    helix,
+                    start,
+                    length,
+                },
+            ),
+            Consequence::DoubleStrandParametersChanged { position, length } => self
+                .requests
+                .lock()
+                .unwrap()
+                .set_double_strand_parameters(position, length),
             Consequence::ElementSelected(element, adding) => {
                 if adding {
                     self.add_selection(element, app_state.get_selection(), app_state)
@@ -454,6 +539,19 @@ impl<S: AppState> Scene<S> {
                         .request_center_selection(selection, AppId::Scene);
                 }
             }
+            Consequence::CenterOtherView(element) => {
+                let selection = element.map(|element| {
+                    self.data
+                        .borrow()
+                        .element_to_selection(&element, SelectionMode::Nucleotide)
+                });
+                if let Some(selection @ Selection::Nucleotide(_, _)) = selection {
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .request_center_selection(selection, AppId::Scene);
+                }
+            }
             Consequence::InitBuild(nucls) => {
                 if let Some(xover_id) = nucls.get(0).cloned().and_then(|n| {
                     app_state
@@ -553,6 +651,16 @@ impl<S: AppState> Scene<S> {
             )),
             Consequence::ReleaseBezierCorner => self.requests.lock().unwrap().suspend_op(),
             Consequence::ReleaseBezierTangent => self.requests.lock().unwrap().suspend_op(),
+            Consequence::HelixRolled { helix_id, roll } => {
+                self.requests.lock().unwrap().apply_design_operation(
+                    DesignOperation::SetRollHelices {
+                        helices: vec![helix_id],
+                        roll,
+                    },
+                )
+            }
+            Consequence::HelixRollReleased => self.requests.lock().unwrap().suspend_op(),
+            Consequence::TwistAxisMoved(axis) => self.requests.lock().unwrap().set_twist_axis(axis),
             Consequence::MoveBezierTangent {
                 vertex_id,
                 tangent_in,
@@ -673,12 +781,7 @@ impl<S: AppState> Scene<S> {
             None
         };
         self.view.borrow_mut().set_widget_candidate(widget);
-        let selection = if let Some(c) = new_candidates {
-            vec![c]
-        } else {
-            vec![]
-        };
-        self.requests.lock().unwrap().set_candidate(selection);
+        self.requests.lock().unwrap().set_candidate(new_candidates);
     }
 
     fn translate_selected_design(&mut self, translation: Vec3, app_state: &S) {
@@ -833,6 +936,42 @@ impl<S: AppState> Scene<S> {
         self.requests.lock().unwrap().update_opperation(rotation);
     }
 
+    /// Enter or leave the inside-out inspection mode.
+    ///
+    /// When entering the mode, the camera is placed at the pivot point, or at the center of the
+    /// designs if there is no pivot point, and the field of view is widened so that the cavities
+    /// surrounding the camera can be inspected by looking around.
+    fn set_inside_out_inspection(&mut self, inside_out: bool) {
+        if self.is_stereographic() || inside_out == self.inside_out_backup.is_some() {
+            return;
+        }
+        let camera = self.view.borrow().get_camera();
+        let projection = self.view.borrow().get_projection();
+        if inside_out {
+            let center = self
+                .data
+                .borrow()
+                .get_pivot_position()
+                .or_else(|| self.data.borrow().get_designs_center());
+            if let Some(center) = center {
+                let (position, rotor) = {
+                    let camera = camera.borrow();
+                    (camera.position, camera.rotor)
+                };
+                let fovy = projection.borrow().get_fovy();
+                self.inside_out_backup = Some((position, rotor, fovy));
+                projection
+                    .borrow_mut()
+                    .set_fovy(INSIDE_OUT_FOVY.to_radians());
+                self.controller.teleport_camera(center, rotor);
+            }
+        } else if let Some((position, rotor, fovy)) = self.inside_out_backup.take() {
+            projection.borrow_mut().set_fovy(fovy);
+            self.controller.teleport_camera(position, rotor);
+        }
+        self.notify(SceneNotification::CameraMoved);
+    }
+
     /// Adapt the camera, position, orientation and pivot point to a design so that the design fits
     /// the scene, and the pivot point of the camera is the center of the design.
     fn fit_design(&mut self) {
@@ -846,11 +985,18 @@ impl<S: AppState> Scene<S> {
 
     fn need_redraw(&mut self, dt: Duration, new_state: S) -> bool {
         self.check_timers(&new_state);
+        if new_state.inside_out_inspection() != self.older_state.inside_out_inspection() {
+            self.set_inside_out_inspection(new_state.inside_out_inspection());
+        }
         if self.controller.camera_is_moving() {
             self.notify(SceneNotification::CameraMoved);
         }
         self.controller.update_data();
         if self.update.need_update {
+            if self.update.camera_update && new_state.get_widget_basis() == WidgetBasis::Screen {
+                // The widgets are oriented according to the camera
+                self.data.borrow_mut().notify_handle_movement();
+            }
             self.perform_update(dt);
         }
         self.data
@@ -859,6 +1005,7 @@ impl<S: AppState> Scene<S> {
         self.data
             .borrow_mut()
             .update_view(&new_state, &self.older_state);
+        self.data.borrow_mut().update_strand_trace(dt);
         let mut ret = new_state.draw_options_were_updated(&self.older_state);
         self.older_state = new_state;
         ret |= self.view.borrow().need_redraw();
@@ -883,7 +1030,7 @@ impl<S: AppState> Scene<S> {
             DrawType::Scene,
             self.area,
             is_stereographic,
-            app_state.get_draw_options(),
+            self.draw_options(app_state),
         );
     }
 
@@ -992,9 +1139,6 @@ impl<S: AppState> Scene<S> {
             Some("png"),
         );
         println!("3D PNG export to {:?}", path);
-        let device = self.element_selector.device.as_ref();
-        let queue = self.element_selector.queue.as_ref();
-        use ensnano_utils::BufferDimensions;
         use std::io::Write;
 
         let ratio = self.view.borrow().get_projection().borrow().get_ratio();
@@ -1008,6 +1152,44 @@ impl<S: AppState> Scene<S> {
         } else {
             (PNG_SIZE as f32 / ratio).floor() as u32
         };
+        let (pixels, buffer_dimensions) = self.render_to_pixels(width, height);
+        if let Ok(f_out) = std::fs::File::create(path) {
+            let mut png_encoder = png::Encoder::new(
+                f_out,
+                buffer_dimensions.width as u32,
+                buffer_dimensions.height as u32,
+            );
+            png_encoder.set_depth(png::BitDepth::Eight);
+            png_encoder.set_color(png::ColorType::Rgba);
+
+            let mut png_writer = png_encoder
+                .write_header()
+                .unwrap()
+                .into_stream_writer_with_size(buffer_dimensions.unpadded_bytes_per_row)
+                .unwrap();
+
+            for chunk in pixels.chunks(buffer_dimensions.padded_bytes_per_row) {
+                png_writer
+                    .write_all(&chunk[..buffer_dimensions.unpadded_bytes_per_row])
+                    .unwrap();
+            }
+            png_writer.finish().unwrap();
+            return;
+        }
+        println!("PNG export failed! Save our design first");
+    }
+
+    /// Render the 3D view in an offscreen texture of the given size and return its pixels in
+    /// Rgba format. Each row of pixels is padded to `buffer_dimensions.padded_bytes_per_row`
+    /// bytes.
+    fn render_to_pixels(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> (Vec<u8>, ensnano_utils::BufferDimensions) {
+        use ensnano_utils::BufferDimensions;
+        let device = self.element_selector.device.as_ref();
+        let queue = self.element_selector.queue.as_ref();
         let size = wgpu::Extent3d {
             width,
             height,
@@ -1017,14 +1199,14 @@ impl<S: AppState> Scene<S> {
         let (texture, texture_view) = self.create_png_export_texture(device, size);
 
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("3D PNG export"),
+            label: Some("3D offscreen rendering"),
         });
 
         // let draw_options =  DrawOptions {
         //     rendering_mode: RenderingMode::Cartoon,
         //     ..Default::default()
         // };
-        let draw_options = self.older_state.get_draw_options();
+        let draw_options = self.draw_options(&self.older_state);
 
         self.view.borrow_mut().draw(
             &mut encoder,
@@ -1099,30 +1281,28 @@ impl<S: AppState> Scene<S> {
             }
         };
         let pixels = futures::executor::block_on(pixels);
-        if let Ok(f_out) = std::fs::File::create(path) {
-            let mut png_encoder = png::Encoder::new(
-                f_out,
-                buffer_dimensions.width as u32,
-                buffer_dimensions.height as u32,
-            );
-            png_encoder.set_depth(png::BitDepth::Eight);
-            png_encoder.set_color(png::ColorType::Rgba);
-
-            let mut png_writer = png_encoder
-                .write_header()
-                .unwrap()
-                .into_stream_writer_with_size(buffer_dimensions.unpadded_bytes_per_row)
-                .unwrap();
+        (pixels, buffer_dimensions)
+    }
 
-            for chunk in pixels.chunks(buffer_dimensions.padded_bytes_per_row) {
-                png_writer
-                    .write_all(&chunk[..buffer_dimensions.unpadded_bytes_per_row])
-                    .unwrap();
-            }
-            png_writer.finish().unwrap();
-            return;
+    /// Render a small image of the current 3D view, used to preview the custom cameras.
+    fn render_thumbnail(&self) -> Thumbnail {
+        let ratio = self.view.borrow().get_projection().borrow().get_ratio();
+        let width = THUMBNAIL_WIDTH;
+        let height = ((THUMBNAIL_WIDTH as f32 / ratio).floor() as u32).max(1);
+        let (pixels, buffer_dimensions) = self.render_to_pixels(width, height);
+        let rgba = pixels
+            .chunks(buffer_dimensions.padded_bytes_per_row)
+            .flat_map(|row| {
+                row[..buffer_dimensions.unpadded_bytes_per_row]
+                    .iter()
+                    .cloned()
+            })
+            .collect();
+        Thumbnail {
+            width,
+            height,
+            rgba,
         }
-        println!("PNG export failed! Save our design first");
     }
 
     fn export_stl(&self, design_path: Option<Arc<Path>>, app_state: &S) {
@@ -1298,6 +1478,7 @@ impl<S: AppState> Application for Scene<S> {
                     }
                 }
             }
+            Notification::TeleportCamera2D(_) => (),
             Notification::FlipSplitViews => (),
             Notification::HorizonAligned => {
                 self.controller.align_horizon();
@@ -1321,6 +1502,17 @@ impl<S: AppState> Application for Scene<S> {
                     self.export_stl(design_path, &self.older_state);
                 }
             }
+            Notification::CameraThumbnail(camera_id) => {
+                if !self.is_stereographic() {
+                    self.view.borrow_mut().update(ViewUpdate::Camera);
+                    let thumbnail = self.render_thumbnail();
+                    self.requests
+                        .lock()
+                        .unwrap()
+                        .set_camera_thumbnail(camera_id, thumbnail);
+                }
+            }
+            Notification::TraceStrand(s_id) => self.data.borrow_mut().start_strand_trace(s_id),
         }
     }
 
@@ -1375,6 +1567,30 @@ impl<S: AppState> Application for Scene<S> {
         self.view.borrow().get_current_pivot()
     }
 
+    fn get_nb_instances(&self) -> Option<usize> {
+        Some(self.view.borrow().nb_dna_instances())
+    }
+
+    fn render_design_thumbnail(&self) -> Option<Vec<u8>> {
+        if self.is_stereographic() {
+            return None;
+        }
+        let thumbnail = self.render_thumbnail();
+        let mut png_bytes = Vec::new();
+        let mut png_encoder = png::Encoder::new(&mut png_bytes, thumbnail.width, thumbnail.height);
+        png_encoder.set_depth(png::BitDepth::Eight);
+        png_encoder.set_color(png::ColorType::Rgba);
+        let result = png_encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&thumbnail.rgba));
+        if let Err(e) = result {
+            log::error!("Could not encode design thumbnail: {e}");
+            None
+        } else {
+            Some(png_bytes)
+        }
+    }
+
     fn is_splited(&self) -> bool {
         false
     }
@@ -1393,15 +1609,30 @@ pub trait AppState: Clone + 'static {
     fn get_design_reader(&self) -> Self::DesignReader;
     fn get_strand_builders(&self) -> &[StrandBuilder];
     fn get_widget_basis(&self) -> WidgetBasis;
+    /// The scale factor applied to the translation and rotation widgets
+    fn get_gizmo_size(&self) -> f32;
+    fn get_gizmo_axis_constraint(&self) -> Option<GizmoAxis>;
+    fn get_ground_parameters(&self) -> GroundParameters;
+    fn get_scale_bar_parameters(&self) -> ScaleBarParameters;
+    fn get_color_vision_mode(&self) -> ColorVisionMode;
     fn is_changing_color(&self) -> bool;
     fn is_pasting(&self) -> bool;
     fn get_selected_element(&self) -> Option<CenterOfSelection>;
     fn get_current_group_pivot(&self) -> Option<ensnano_design::group_attributes::GroupPivot>;
     fn get_current_group_id(&self) -> Option<ensnano_design::GroupId>;
     fn suggestion_parameters_were_updated(&self, other: &Self) -> bool;
+    fn get_suggestion_parameters(&self) -> SuggestionParameters;
     fn get_check_xover_parameters(&self) -> CheckXoversParameter;
     fn follow_stereographic_camera(&self) -> bool;
+    /// True if the camera must be placed inside the designs with a wide field of view
+    fn inside_out_inspection(&self) -> bool;
+    /// The brush used to paint nucleotides when dragging the cursor
+    fn get_brush_parameters(&self) -> BrushParameters;
+    /// The global draw options. Each scene applies the overrides of its view layer to them.
     fn get_draw_options(&self) -> DrawOptions;
+    /// The settings of `view` that override the global rendering settings
+    fn get_view_layer(&self, view: ElementType) -> ViewLayer;
+    /// True if the draw options or the view layers were updated
     fn draw_options_were_updated(&self, other: &Self) -> bool;
     fn get_scroll_sensitivity(&self) -> f32;
     fn show_insertion_representents(&self) -> bool;
@@ -1419,8 +1650,13 @@ pub trait AppState: Clone + 'static {
     fn has_selected_a_bezier_grid(&self) -> bool;
 
     fn get_revolution_axis_position(&self) -> Option<f64>;
+    /// The axis around which free helices are twisted, if any
+    fn get_twist_axis(&self) -> Option<TwistAxis>;
     fn revolution_bezier_updated(&self, other: &Self) -> bool;
     fn get_current_unrooted_surface(&self) -> Option<UnrootedRevolutionSurfaceDescriptor>;
+    /// The differences with a reference design that must be shown, if any.
+    fn get_design_diff(&self) -> Option<&ensnano_design::diff::DesignDiff>;
+    fn design_diff_was_updated(&self, other: &Self) -> bool;
 }
 
 pub trait Requests {
@@ -1447,4 +1683,15 @@ pub trait Requests {
     fn translate_group_pivot(&mut self, translation: Vec3);
     fn rotate_group_pivot(&mut self, rotation: Rotor3);
     fn set_revolution_axis_position(&mut self, position: f32);
+    /// Set the axis around which free helices are twisted
+    fn set_twist_axis(&mut self, axis: TwistAxis);
+    /// Open a menu listing the actions that can be performed on `selection`
+    fn open_context_menu(&mut self, selection: Selection);
+    fn set_camera_thumbnail(&mut self, camera_id: ensnano_design::CameraId, thumbnail: Thumbnail);
+    /// Apply the effect of `brush` to all the nucleotides that were painted during a brush stroke
+    fn apply_brush(&mut self, nucls: Vec<Nucl>, brush: BrushParameters);
+    /// Give the keyboard focus to the input of the length of the selected insertion
+    fn edit_insertion_length(&mut self);
+    /// Set the position and length of the double strands created in helix building mode
+    fn set_double_strand_parameters(&mut self, position: isize, length: usize);
 }
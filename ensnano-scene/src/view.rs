@@ -32,6 +32,7 @@ use ensnano_utils::wgpu;
 use ensnano_utils::{bindgroup_manager, text, texture};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::ops::Range;
 use std::rc::Rc;
 use std::usize;
 use texture::Texture;
@@ -48,6 +49,10 @@ pub mod dna_obj;
 mod drawable;
 mod gltf_drawer;
 pub use gltf_drawer::ExternalObjects;
+/// Draw a grid and a shadow beneath the design.
+mod ground;
+pub use ground::Ground;
+use ground::{ContactShadow, GroundGrid};
 mod grid;
 mod grid_disc;
 /// A HandleDrawer draws the widget for translating objects
@@ -56,6 +61,9 @@ pub mod instances_drawer;
 mod letter;
 /// A RotationWidget draws the widget for rotating objects
 mod rotation_widget;
+mod scale_bar;
+pub use scale_bar::ScaleBar;
+use scale_bar::SCALE_BAR_CHARS;
 mod sheet_2d;
 
 use super::maths_3d::{self, distance_to_cursor_with_penalty};
@@ -66,7 +74,7 @@ pub use dna_obj::{
     SphereInstance, StereographicSphereAndPlane, TubeInstance, TubeLidInstance,
 };
 use drawable::{Drawable, Drawer, Vertex};
-pub use grid::{GridInstance, GridIntersection};
+pub use grid::{GridInstance, GridIntersection, GRID_LABEL_CHARS};
 use grid::{GridManager, GridTextures};
 pub use grid_disc::GridDisc;
 use handle_drawer::HandlesDrawer;
@@ -84,6 +92,13 @@ use text::Letter;
 //use plane_drawer::PlaneDrawer;
 //pub use plane_drawer::Plane;
 
+/// The number of samples per pixel used by the pipelines of the 3D scene.
+fn sample_count() -> u32 {
+    ensnano_interactor::graphics::render_quality()
+        .multisampling
+        .sample_count()
+}
+
 static MODEL_BG_ENTRY: &[wgpu::BindGroupLayoutEntry] = &[wgpu::BindGroupLayoutEntry {
     binding: 0,
     visibility: wgpu::ShaderStages::from_bits_truncate(wgpu::ShaderStages::VERTEX.bits()),
@@ -95,7 +110,9 @@ static MODEL_BG_ENTRY: &[wgpu::BindGroupLayoutEntry] = &[wgpu::BindGroupLayoutEn
     count: None,
 }];
 
-use ensnano_interactor::graphics::{Background3D, HBondDisplay, RenderingMode};
+use ensnano_interactor::graphics::{
+    Background3D, HBondDisplay, RenderingMode, StrandEndsParameters, Theme,
+};
 
 /// An object that handles the communication with the GPU to draw the scene.
 pub struct View {
@@ -119,6 +136,8 @@ pub struct View {
     /// The pipilines that draw the basis symbols
     letter_drawer: Vec<InstanceDrawer<LetterInstance>>,
     helix_letter_drawer: Vec<InstanceDrawer<LetterInstance>>,
+    /// The pipelines that draw the lengths of the insertions on their badges
+    insertion_badge_letter_drawer: Vec<InstanceDrawer<LetterInstance>>,
     device: Rc<Device>,
     /// A bind group associated to the uniform buffer containing the view and projection matrices.
     //TODO this is currently only passed to the widgets, it could be passed to the mesh pipeline as
@@ -142,16 +161,27 @@ pub struct View {
     sheets_drawer: InstanceDrawer<Sheet2D>,
     /// Cutting plane
     cut_plane_parameters: Option<CutPlaneParameters>,
+    ground: Option<Ground>,
+    ground_grid_drawer: Drawer<GroundGrid>,
+    contact_shadow_drawer: Drawer<ContactShadow>,
+    scale_bar: Option<ScaleBar>,
+    scale_bar_drawer: InstanceDrawer<PlainRectangleInstance>,
+    scale_bar_letter_drawer: Vec<InstanceDrawer<LetterInstance>>,
+    /// The size of the drawing area, used to give a constant size on screen to the scale bar.
+    area_size: PhySize,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DrawOptions {
     pub rendering_mode: RenderingMode,
     pub background3d: Background3D,
+    pub theme: Theme,
     pub show_stereographic_camera: bool,
     pub all_helices_on_axis: bool,
     pub h_bonds: HBondDisplay,
     pub show_bezier_planes: bool,
+    pub strand_ends: StrandEndsParameters,
+    pub insertion_badges: bool,
 }
 
 impl View {
@@ -211,7 +241,7 @@ impl View {
             })
             .collect();
         log::info!("Create helix letter drawer");
-        let helix_letter_drawer = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']
+        let helix_letter_drawer = GRID_LABEL_CHARS
             .iter()
             .map(|c| {
                 let letter = Letter::new(*c, device.clone(), queue.clone());
@@ -226,16 +256,56 @@ impl View {
                 )
             })
             .collect();
+        let insertion_badge_letter_drawer = GRID_LABEL_CHARS
+            .iter()
+            .map(|c| {
+                let letter = Letter::new(*c, device.clone(), queue.clone());
+                InstanceDrawer::new(
+                    device.clone(),
+                    queue.clone(),
+                    &viewer.get_layout_desc(),
+                    &model_bg_desc,
+                    letter,
+                    false,
+                    format!("insertion badge letter {c}"),
+                )
+            })
+            .collect();
+
+        let scale_bar_letter_drawer = SCALE_BAR_CHARS
+            .iter()
+            .map(|c| {
+                let letter = Letter::new(*c, device.clone(), queue.clone());
+                InstanceDrawer::new(
+                    device.clone(),
+                    queue.clone(),
+                    &viewer.get_layout_desc(),
+                    &model_bg_desc,
+                    letter,
+                    false,
+                    format!("scale bar letter {c}"),
+                )
+            })
+            .collect();
+        let scale_bar_drawer = InstanceDrawer::new(
+            device.clone(),
+            queue.clone(),
+            &viewer.get_layout_desc(),
+            &model_bg_desc,
+            (),
+            false,
+            "scale bar",
+        );
 
         let depth_texture =
-            texture::Texture::create_depth_texture(device.as_ref(), &area_size, SAMPLE_COUNT);
+            texture::Texture::create_depth_texture(device.as_ref(), &area_size, sample_count());
         let fake_depth_texture =
             texture::Texture::create_depth_texture(device.as_ref(), &window_size, 1);
-        let msaa_texture = if SAMPLE_COUNT > 1 {
+        let msaa_texture = if sample_count() > 1 {
             Some(ensnano_utils::texture::Texture::create_msaa_texture(
                 device.clone().as_ref(),
                 &area_size,
-                SAMPLE_COUNT,
+                sample_count(),
                 wgpu::TextureFormat::Bgra8UnormSrgb,
             ))
         } else {
@@ -339,9 +409,10 @@ impl View {
             models,
             handle_drawers: HandlesDrawer::new(device.clone()),
             all_frames_drawers,
-            rotation_widget: RotationWidget::new(device),
+            rotation_widget: RotationWidget::new(device.clone()),
             letter_drawer,
             helix_letter_drawer,
+            insertion_badge_letter_drawer,
             redraw_twice: false,
             need_redraw: true,
             need_redraw_fake: true,
@@ -357,6 +428,13 @@ impl View {
             stereography,
             sheets_drawer,
             cut_plane_parameters,
+            ground: None,
+            ground_grid_drawer: Drawer::new(device.clone()),
+            contact_shadow_drawer: Drawer::new(device),
+            scale_bar: None,
+            scale_bar_drawer,
+            scale_bar_letter_drawer,
+            area_size,
         }
     }
 
@@ -389,6 +467,8 @@ impl View {
             ViewUpdate::Size(size) => {
                 self.new_size = Some(size);
                 self.need_redraw_fake = true;
+                self.area_size = size;
+                self.update_scale_bar();
             }
             ViewUpdate::Camera => {
                 self.update_viewers();
@@ -397,6 +477,8 @@ impl View {
                 let dist = self.projection.borrow().cube_dist();
                 self.direction_cube
                     .new_instances(vec![DirectionCube::new(dist)]);
+                self.update_ground_grid();
+                self.update_scale_bar();
             }
             ViewUpdate::Fog(fog) => {
                 let fog_center = self.fog_parameters.alt_fog_center;
@@ -432,28 +514,18 @@ impl View {
                     self.helix_letter_drawer[i].new_instances(instance);
                 }
             }
+            ViewUpdate::InsertionBadgeLetter(letter) => {
+                for (i, instance) in letter.into_iter().enumerate() {
+                    self.insertion_badge_letter_drawer[i].new_instances(instance);
+                }
+            }
             ViewUpdate::Grids(grid) => self.grid_manager.new_instances(grid),
             ViewUpdate::GridDiscs(instances) => self.disc_drawer.new_instances(instances),
             ViewUpdate::RawDna(mesh, instances) => {
-                self.dna_drawers
-                    .get_mut(mesh)
-                    .new_instances_raw(instances.as_ref());
-                if let Some(_mesh) = mesh.to_fake() {
-                    let mut instances = instances.as_ref().clone();
-                    for i in instances.iter_mut() {
-                        if i.scale.z <= ensnano_interactor::consts::MIN_RADIUS_FOR_FAKE_UPSCALING {
-                            i.scale *= ensnano_interactor::consts::SELECT_SCALE_FACTOR;
-                        }
-                    }
-                    self.dna_drawers
-                        .get_mut(_mesh)
-                        .new_instances_raw(instances.as_ref());
-                }
-                if let Some(_mesh) = mesh.to_outline() {
-                    self.dna_drawers
-                        .get_mut(_mesh)
-                        .new_instances_raw(instances.as_ref());
-                }
+                self.update_raw_dna(mesh, instances.as_ref(), None)
+            }
+            ViewUpdate::RawDnaRanges(mesh, instances, dirty) => {
+                self.update_raw_dna(mesh, instances.as_ref(), Some(&dirty))
             }
             ViewUpdate::FogCenter(center) => {
                 self.fog_parameters.alt_fog_center = center;
@@ -481,9 +553,82 @@ impl View {
                 self.update_cut_plane(normal, dot_value);
                 self.need_redraw = true;
             }
+            ViewUpdate::Ground(ground) => {
+                self.ground = ground;
+                self.update_ground_grid();
+                self.contact_shadow_drawer
+                    .new_object(self.ground.and_then(|g| g.shadow()));
+            }
+            ViewUpdate::ScaleBar(scale_bar) => {
+                self.scale_bar = scale_bar;
+                self.update_scale_bar();
+            }
+        }
+    }
+
+    /// Write the instances of `mesh` and of the fake and outline meshes derived from it. If
+    /// `dirty` is `Some`, only the instances in these ranges differ from the previous ones.
+    fn update_raw_dna(
+        &mut self,
+        mesh: Mesh,
+        instances: &Vec<RawDnaInstance>,
+        dirty: Option<&[Range<usize>]>,
+    ) {
+        write_raw_dna(self.dna_drawers.get_mut(mesh), instances, dirty);
+        if let Some(mesh) = mesh.to_fake() {
+            use ensnano_interactor::consts::{
+                MIN_FAKE_TUBE_RADIUS, MIN_RADIUS_FOR_FAKE_UPSCALING, SELECT_SCALE_FACTOR,
+            };
+            let is_tube = mesh == Mesh::FakeTube || mesh == Mesh::FakePhantomTube;
+            let mut instances = instances.clone();
+            for i in instances.iter_mut() {
+                if i.scale.z <= MIN_RADIUS_FOR_FAKE_UPSCALING {
+                    i.scale *= SELECT_SCALE_FACTOR;
+                }
+                if is_tube {
+                    // scale.x is the length of the tube, the radius is unaffected
+                    i.scale.y = i.scale.y.max(MIN_FAKE_TUBE_RADIUS);
+                    i.scale.z = i.scale.z.max(MIN_FAKE_TUBE_RADIUS);
+                }
+            }
+            write_raw_dna(self.dna_drawers.get_mut(mesh), &instances, dirty);
+        }
+        if let Some(mesh) = mesh.to_outline() {
+            write_raw_dna(self.dna_drawers.get_mut(mesh), instances, dirty);
+        }
+    }
+
+    fn update_scale_bar(&mut self) {
+        let instances = self.scale_bar.and_then(|scale_bar| {
+            scale_bar.instances(
+                &self.camera.borrow(),
+                &self.projection.borrow(),
+                self.area_size,
+            )
+        });
+        if let Some(instances) = instances {
+            self.scale_bar_drawer.new_instances(vec![instances.bar]);
+            for (drawer, letters) in self
+                .scale_bar_letter_drawer
+                .iter_mut()
+                .zip(instances.letters.into_iter())
+            {
+                drawer.new_instances(letters);
+            }
+        } else {
+            self.scale_bar_drawer.new_instances(vec![]);
+            for drawer in self.scale_bar_letter_drawer.iter_mut() {
+                drawer.new_instances(vec![]);
+            }
         }
     }
 
+    fn update_ground_grid(&mut self) {
+        let camera_position = self.camera.borrow().position;
+        self.ground_grid_drawer
+            .new_object(self.ground.and_then(|g| g.grid(camera_position)));
+    }
+
     pub fn need_redraw_fake(&self) -> bool {
         self.need_redraw_fake
     }
@@ -513,20 +658,23 @@ impl View {
         let fake_color = draw_type.is_fake();
         if let Some(size) = self.new_size.take() {
             self.depth_texture =
-                Texture::create_depth_texture(self.device.as_ref(), &area.size, SAMPLE_COUNT);
+                Texture::create_depth_texture(self.device.as_ref(), &area.size, sample_count());
             self.fake_depth_texture = Texture::create_depth_texture(self.device.as_ref(), &size, 1);
-            self.msaa_texture = if SAMPLE_COUNT > 1 {
+            self.msaa_texture = if sample_count() > 1 {
                 Some(ensnano_utils::texture::Texture::create_msaa_texture(
                     self.device.clone().as_ref(),
                     &area.size,
-                    SAMPLE_COUNT,
+                    sample_count(),
                     wgpu::TextureFormat::Bgra8UnormSrgb,
                 ))
             } else {
                 None
             };
         }
-        let clear_color = if fake_color || draw_options.background3d == Background3D::White {
+        let clear_color = if fake_color
+            || (draw_options.background3d == Background3D::White
+                && draw_options.theme == Theme::Light)
+        {
             // 0xFF_FF_FF_FF is the "default" color for the fake texture
             wgpu::Color {
                 r: 1.,
@@ -534,6 +682,13 @@ impl View {
                 b: 1.,
                 a: 1.,
             }
+        } else if draw_options.background3d == Background3D::White {
+            wgpu::Color {
+                r: 0.12,
+                g: 0.12,
+                b: 0.13,
+                a: 1.,
+            }
         } else {
             // Clearing with black is a bit faster than with other colors, so that's what we do
             // when possible
@@ -562,12 +717,12 @@ impl View {
                 target
             }
         } else if let DrawType::Png { width, height } = draw_type {
-            png_msaa = if SAMPLE_COUNT > 1 {
+            png_msaa = if sample_count() > 1 {
                 let size = PhySize::new(width, height);
                 Some(ensnano_utils::texture::Texture::create_msaa_texture(
                     self.device.clone().as_ref(),
                     &size,
-                    SAMPLE_COUNT,
+                    sample_count(),
                     wgpu::TextureFormat::Bgra8UnormSrgb,
                 ))
             } else {
@@ -596,7 +751,7 @@ impl View {
             png_depth = Some(Texture::create_depth_texture(
                 self.device.as_ref(),
                 &size,
-                SAMPLE_COUNT,
+                sample_count(),
             ));
             png_depth.as_ref().unwrap()
         } else {
@@ -699,6 +854,16 @@ impl View {
                     viewer_bind_group,
                     self.models.get_bindgroup(),
                 );
+                self.dna_drawers.fake_helix_length_handle.draw(
+                    &mut render_pass,
+                    viewer_bind_group,
+                    self.models.get_bindgroup(),
+                );
+                self.dna_drawers.fake_insertion_badge.draw(
+                    &mut render_pass,
+                    viewer_bind_group,
+                    self.models.get_bindgroup(),
+                );
             }
 
             if !fake_color && !stereographic && self.draw_letter {
@@ -712,6 +877,18 @@ impl View {
             }
 
             if !fake_color && !stereographic {
+                self.ground_grid_drawer.draw(
+                    &mut render_pass,
+                    viewer_bind_group,
+                    viewer_bind_group_layout,
+                    false,
+                );
+                self.contact_shadow_drawer.draw(
+                    &mut render_pass,
+                    viewer_bind_group,
+                    viewer_bind_group_layout,
+                    false,
+                );
                 self.grid_manager.draw(
                     &mut render_pass,
                     viewer_bind_group,
@@ -730,11 +907,30 @@ impl View {
                         self.models.get_bindgroup(),
                     )
                 }
+                for drawer in self.insertion_badge_letter_drawer.iter_mut() {
+                    drawer.draw(
+                        &mut render_pass,
+                        viewer_bind_group,
+                        self.models.get_bindgroup(),
+                    )
+                }
                 self.sheets_drawer.draw(
                     &mut render_pass,
                     viewer_bind_group,
                     self.models.get_bindgroup(),
-                )
+                );
+                self.scale_bar_drawer.draw(
+                    &mut render_pass,
+                    viewer_bind_group,
+                    self.models.get_bindgroup(),
+                );
+                for drawer in self.scale_bar_letter_drawer.iter_mut() {
+                    drawer.draw(
+                        &mut render_pass,
+                        viewer_bind_group,
+                        self.models.get_bindgroup(),
+                    )
+                }
             }
 
             if draw_type.wants_widget() && !stereographic {
@@ -797,18 +993,8 @@ impl View {
                     }),
                 }),
             });
-            render_pass.set_viewport(
-                area.size.width as f32 / 20.,
-                0.,
-                (area.size.width as f32 / 10. * 1.5)
-                    .max(100.)
-                    .min(area.size.width as f32),
-                (area.size.height as f32 / 10. * 1.5)
-                    .max((100. * area.size.height as f32 / area.size.width as f32) as f32)
-                    .min(area.size.height as f32),
-                0.0,
-                1.0,
-            );
+            let (x, y, width, height) = DirectionCube::viewport(area.size);
+            render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
             log::trace!("draw direction cube...");
             self.direction_cube.draw(
                 &mut render_pass,
@@ -870,6 +1056,11 @@ impl View {
             .or_else(|| self.rotation_widget.get_pivot_position())
     }
 
+    /// The number of instances of the meshes that represent the design
+    pub fn nb_dna_instances(&self) -> usize {
+        self.dna_drawers.nb_instances()
+    }
+
     /// Get a pointer to the camera
     pub fn get_camera(&self) -> CameraPtr {
         self.camera.clone()
@@ -1009,6 +1200,18 @@ impl View {
         }
     }
 
+    /// Return the outward normal of the face of the direction cube that is at position `(x, y)`
+    /// of an area of size `area_size`.
+    pub fn direction_cube_face(&self, x: f32, y: f32, area_size: PhySize) -> Option<Vec3> {
+        DirectionCube::face_under_cursor(
+            x,
+            y,
+            area_size,
+            &self.camera.borrow(),
+            &self.projection.borrow(),
+        )
+    }
+
     pub fn grid_intersection(&self, x_ndc: f32, y_ndc: f32) -> Option<GridIntersection> {
         let ray = maths_3d::cast_ray(
             x_ndc,
@@ -1055,6 +1258,17 @@ impl View {
     }
 }
 
+fn write_raw_dna(
+    drawer: &mut dyn RawDrawer<RawInstance = RawDnaInstance>,
+    instances: &Vec<RawDnaInstance>,
+    dirty: Option<&[Range<usize>]>,
+) {
+    match dirty {
+        Some(dirty) => drawer.update_instances_raw(instances, dirty),
+        None => drawer.new_instances_raw(instances),
+    }
+}
+
 /// An notification to be given to the view
 #[derive(Debug)]
 pub enum ViewUpdate {
@@ -1069,9 +1283,14 @@ pub enum ViewUpdate {
     RotationWidget(Option<RotationWidgetDescriptor>),
     Letter(Vec<Vec<LetterInstance>>),
     GridLetter(Vec<Vec<LetterInstance>>),
+    /// The digits of the lengths of the insertions, indexed like `GRID_LABEL_CHARS`
+    InsertionBadgeLetter(Vec<Vec<LetterInstance>>),
     Grids(BTreeMap<GridId, GridInstance>),
     GridDiscs(Vec<GridDisc>),
     RawDna(Mesh, Rc<Vec<RawDnaInstance>>),
+    /// Same as `RawDna`, but only the instances in the given ranges differ from the ones of the
+    /// previous update of the mesh.
+    RawDnaRanges(Mesh, Rc<Vec<RawDnaInstance>>, Vec<Range<usize>>),
     Fog(FogParameters),
     FogCenter(Option<Vec3>),
     BezierSheets(Vec<Sheet2D>),
@@ -1079,6 +1298,10 @@ pub enum ViewUpdate {
     UnrootedSurface(Option<UnrootedRevolutionSurfaceDescriptor>),
     /// The cutting plane has been modified: normal and dot product
     CutPlane(Vec3, f32),
+    /// The ground beneath the design, or None if nothing must be drawn beneath the design
+    Ground(Option<Ground>),
+    /// The scale bar, or None if the scale bar must be hidden
+    ScaleBar(Option<ScaleBar>),
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, IntEnum)]
@@ -1118,6 +1341,17 @@ pub enum Mesh {
     HBond = 32,
     HBondOutline = 33,
     PlainRectangle = 34,
+    GhostSphere = 35,
+    GhostTube = 36,
+    HelixLengthHandle = 37,
+    FakeHelixLengthHandle = 38,
+    /// The glow moving along a strand that is being traced
+    TraceSphere = 39,
+    HelixRollDial = 40,
+    JointSphere = 41,
+    JointTube = 42,
+    InsertionBadge = 43,
+    FakeInsertionBadge = 44,
 }
 
 impl Mesh {
@@ -1129,6 +1363,8 @@ impl Mesh {
             Self::PhantomSphere => Some(Self::FakePhantomSphere),
             Self::PhantomTube => Some(Self::FakePhantomTube),
             Self::BezierControll => Some(Self::FakeBezierControl),
+            Self::HelixLengthHandle => Some(Self::FakeHelixLengthHandle),
+            Self::InsertionBadge => Some(Self::FakeInsertionBadge),
             _ => None,
         }
     }
@@ -1194,6 +1430,16 @@ struct DnaDrawers {
     outline_base_ellipsoid: InstanceDrawer<dna_obj::Ellipsoid>,
     hbond: InstanceDrawer<dna_obj::TubeInstance>,
     outline_hbond: InstanceDrawer<dna_obj::TubeInstance>,
+    ghost_sphere: InstanceDrawer<SphereInstance>,
+    ghost_tube: InstanceDrawer<TubeInstance>,
+    helix_length_handle: InstanceDrawer<SphereInstance>,
+    fake_helix_length_handle: InstanceDrawer<SphereInstance>,
+    trace_sphere: InstanceDrawer<SphereInstance>,
+    helix_roll_dial: InstanceDrawer<TubeInstance>,
+    joint_sphere: InstanceDrawer<SphereInstance>,
+    joint_tube: InstanceDrawer<TubeInstance>,
+    insertion_badge: InstanceDrawer<SphereInstance>,
+    fake_insertion_badge: InstanceDrawer<SphereInstance>,
 }
 
 impl DnaDrawers {
@@ -1233,6 +1479,16 @@ impl DnaDrawers {
             Mesh::BaseEllipsoid => &mut self.base_ellipsoid,
             Mesh::EllipsoidOutline => &mut self.outline_base_ellipsoid,
             Mesh::HBondOutline => &mut self.outline_hbond,
+            Mesh::GhostSphere => &mut self.ghost_sphere,
+            Mesh::GhostTube => &mut self.ghost_tube,
+            Mesh::HelixLengthHandle => &mut self.helix_length_handle,
+            Mesh::FakeHelixLengthHandle => &mut self.fake_helix_length_handle,
+            Mesh::TraceSphere => &mut self.trace_sphere,
+            Mesh::HelixRollDial => &mut self.helix_roll_dial,
+            Mesh::JointSphere => &mut self.joint_sphere,
+            Mesh::JointTube => &mut self.joint_tube,
+            Mesh::InsertionBadge => &mut self.insertion_badge,
+            Mesh::FakeInsertionBadge => &mut self.fake_insertion_badge,
         }
     }
 
@@ -1257,11 +1513,19 @@ impl DnaDrawers {
             &mut self.suggestion_tube,
             &mut self.pasted_tube,
             &mut self.pasted_sphere,
+            &mut self.ghost_tube,
+            &mut self.ghost_sphere,
             &mut self.pivot_sphere,
+            &mut self.trace_sphere,
             &mut self.xover_sphere,
             &mut self.xover_tube,
             &mut self.bezier_squelton,
             &mut self.bezier_controll_points,
+            &mut self.helix_length_handle,
+            &mut self.helix_roll_dial,
+            &mut self.joint_sphere,
+            &mut self.joint_tube,
+            &mut self.insertion_badge,
         ];
         let mut last_solid_item = 2;
         match draw_options.h_bonds {
@@ -1297,6 +1561,25 @@ impl DnaDrawers {
         ret
     }
 
+    /// The number of instances of the meshes that represent the design
+    pub fn nb_instances(&self) -> usize {
+        [
+            self.sphere.nb_instances(),
+            self.tube.nb_instances(),
+            self.tube_lid.nb_instances(),
+            self.sliced_tube.nb_instances(),
+            self.prime3_cones.nb_instances(),
+            self.phantom_sphere.nb_instances(),
+            self.phantom_tube.nb_instances(),
+            self.suggestion_sphere.nb_instances(),
+            self.suggestion_tube.nb_instances(),
+            self.hbond.nb_instances(),
+            self.base_ellipsoid.nb_instances(),
+        ]
+        .iter()
+        .sum()
+    }
+
     pub fn fakes(&mut self) -> Vec<&mut dyn RawDrawer<RawInstance = RawDnaInstance>> {
         vec![&mut self.fake_sphere, &mut self.fake_tube]
     }
@@ -1514,6 +1797,42 @@ impl DnaDrawers {
                 false,
                 "selected sphere",
             ),
+            ghost_sphere: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "ghost sphere",
+            ),
+            ghost_tube: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "ghost tube",
+            ),
+            helix_length_handle: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "helix length handle",
+            ),
+            fake_helix_length_handle: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                true,
+                "fake helix length handle",
+            ),
             selected_tube: InstanceDrawer::new(
                 device.clone(),
                 queue.clone(),
@@ -1532,6 +1851,60 @@ impl DnaDrawers {
                 false,
                 "pivot sphere",
             ),
+            trace_sphere: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "trace sphere",
+            ),
+            helix_roll_dial: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "helix roll dial",
+            ),
+            joint_sphere: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "joint sphere",
+            ),
+            joint_tube: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "joint tube",
+            ),
+            insertion_badge: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                false,
+                "insertion badge",
+            ),
+            fake_insertion_badge: InstanceDrawer::new(
+                device.clone(),
+                queue.clone(),
+                viewer_desc,
+                model_desc,
+                (),
+                true,
+                "fake insertion badge",
+            ),
             phantom_sphere: InstanceDrawer::new_wireframe(
                 device.clone(),
                 queue.clone(),
@@ -15,7 +15,9 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
+use super::camera::{Camera, Projection};
 use super::instances_drawer::{Instanciable, RessourceProvider, Vertexable};
+use crate::PhySize;
 use ensnano_design::ultraviolet::{Vec2, Vec3};
 use ensnano_utils::wgpu;
 use std::convert::TryInto;
@@ -73,6 +75,66 @@ impl DirectionCube {
     pub fn new(dist: f32) -> Self {
         Self { dist }
     }
+
+    /// The viewport, given as `(x, y, width, height)`, in which the cube is drawn in an area of
+    /// size `area_size`.
+    pub fn viewport(area_size: PhySize) -> (f32, f32, f32, f32) {
+        let width = area_size.width as f32;
+        let height = area_size.height as f32;
+        (
+            width / 20.,
+            0.,
+            (width / 10. * 1.5).max(100.).min(width),
+            (height / 10. * 1.5).max(100. * height / width).min(height),
+        )
+    }
+
+    /// Return the outward normal of the face of the cube that is under the cursor, if any.
+    ///
+    /// `x` and `y` are the coordinates of the cursor in pixels, relative to the top left corner
+    /// of the area.
+    pub fn face_under_cursor(
+        x: f32,
+        y: f32,
+        area_size: PhySize,
+        camera: &Camera,
+        projection: &Projection,
+    ) -> Option<Vec3> {
+        let (x_min, y_min, width, height) = Self::viewport(area_size);
+        if x < x_min || x > x_min + width || y < y_min || y > y_min + height {
+            return None;
+        }
+        let x_ndc = 2. * (x - x_min) / width - 1.;
+        let y_ndc = 1. - 2. * (y - y_min) / height;
+        let tan = (projection.get_fovy() / 2.).tan();
+        let ray: [f32; 3] = (camera.rotor.reversed()
+            * Vec3::new(x_ndc * tan * projection.get_ratio(), y_ndc * tan, -1.))
+        .into();
+
+        // The cube is centered on a point in front of the camera, see direction_cube.vert
+        let center: [f32; 3] = (camera.direction() * (projection.cube_dist() + 1.)).into();
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+        let mut normal = Vec3::zero();
+        for (i, axis) in [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()]
+            .iter()
+            .enumerate()
+        {
+            let t1 = (center[i] - 1.) / ray[i];
+            let t2 = (center[i] + 1.) / ray[i];
+            let (t_min, t_max) = (t1.min(t2), t1.max(t2));
+            if t_min > t_enter {
+                t_enter = t_min;
+                normal = if ray[i] > 0. { -*axis } else { *axis };
+            }
+            t_exit = t_exit.min(t_max);
+        }
+        if t_enter <= t_exit && t_exit > 0. {
+            Some(normal)
+        } else {
+            None
+        }
+    }
 }
 
 impl Instanciable for DirectionCube {
@@ -58,7 +58,7 @@ impl Vertexable for DnaVertex {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Copy, bytemuck::Zeroable, bytemuck::Pod)]
+#[derive(Clone, Debug, Copy, PartialEq, bytemuck::Zeroable, bytemuck::Pod)]
 pub struct RawDnaInstance {
     // must be aligned on 4 times f32 module 8 ?
     pub model: Mat4,          // 0
@@ -15,7 +15,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     You should have received a copy of the GNU General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
-use super::SAMPLE_COUNT;
+use super::sample_count;
 use ensnano_design::ultraviolet;
 use ensnano_interactor::consts::*;
 use ensnano_utils::create_buffer_with_data;
@@ -175,7 +175,7 @@ impl<D: Drawable> Drawer<D> {
             wgpu::BlendState::ALPHA_BLENDING
         };
 
-        let sample_count = if !fake { SAMPLE_COUNT } else { 1 };
+        let sample_count = if !fake { sample_count() } else { 1 };
 
         let targets = &[wgpu::ColorTargetState {
             format,
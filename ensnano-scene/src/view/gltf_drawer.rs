@@ -17,7 +17,6 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 */
 use super::wgpu;
 use ensnano_design::{External3DObject, External3DObjectId, PointOnSurface};
-use ensnano_interactor::consts;
 use ensnano_interactor::UnrootedRevolutionSurfaceDescriptor;
 use ensnano_utils::{create_buffer_with_data, obj_loader::*, texture::Texture, TEXTURE_FORMAT};
 use std::ffi::OsStr;
@@ -342,7 +341,7 @@ fn build_render_pipeline(
     let fragment_module = device.create_shader_module(&wgpu::include_spirv!("gltf_obj.frag.spv"));
     let format = TEXTURE_FORMAT;
     let blend_state = wgpu::BlendState::ALPHA_BLENDING;
-    let sample_count = consts::SAMPLE_COUNT;
+    let sample_count = super::sample_count();
 
     let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: Some("Gltf Drawer"),
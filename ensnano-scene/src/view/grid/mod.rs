@@ -26,6 +26,15 @@ use std::collections::BTreeMap;
 
 mod texture;
 
+/// The characters that can appear in the labels of the grids: helix numbers and lattice
+/// coordinates.
+pub const GRID_LABEL_CHARS: &[char] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '-', ','];
+
+/// Height of the lattice coordinates, relative to the helix numbers
+const COORDINATES_SCALE: f32 = 0.6;
+/// Distance, in nanometers, between the end of a helix and its number
+const HELIX_END_LABEL_GAP: f32 = 1.;
+
 #[derive(Debug, Clone)]
 pub struct GridInstance {
     pub grid: Grid,
@@ -38,6 +47,8 @@ pub struct GridInstance {
     pub id: GridId,
     pub fake: bool,
     pub visible: bool,
+    /// True if the lattice coordinates and helix numbers of the grid are labelled
+    pub labelled: bool,
 }
 
 impl GridInstance {
@@ -67,23 +78,89 @@ impl GridInstance {
         &self,
         x: isize,
         y: isize,
-        h_id: usize,
+        number: usize,
         instances: &mut Vec<Vec<LetterInstance>>,
         right: Vec3,
         up: Vec3,
     ) {
         let position = self.grid.position_helix(x, y);
-        for (c_idx, c) in h_id.to_string().chars().enumerate() {
-            let shift = 0.5 * up - 0.35 * h_id.to_string().len() as f32 * right;
-            let instance = LetterInstance {
-                position: position + 0.7 * c_idx as f32 * right + shift,
-                color: ultraviolet::Vec4::new(0., 0., 0., 1.),
-                design_id: self.design as u32,
-                scale: 3.,
-                shift: Vec3::zero(),
-            };
-            let idx = c.to_digit(10).unwrap();
-            instances[idx as usize].push(instance);
+        let label = number.to_string();
+        self.push_label(&label, position + 0.5 * up, 1., instances, right);
+    }
+
+    /// Add the coordinates of all the lattice points of the grid to `instances`. Hyperboloid
+    /// grids have no lattice and get no labels.
+    pub fn coordinates_letter_instances(
+        &self,
+        instances: &mut Vec<Vec<LetterInstance>>,
+        right: Vec3,
+        up: Vec3,
+    ) {
+        if let GridType::Hyperboloid(_) = self.grid.grid_type {
+            return;
+        }
+        for x in self.min_x..=self.max_x {
+            for y in self.min_y..=self.max_y {
+                let position = self.grid.position_helix(x as isize, y as isize);
+                let label = format!("{x},{y}");
+                self.push_label(
+                    &label,
+                    position - 0.5 * up,
+                    COORDINATES_SCALE,
+                    instances,
+                    right,
+                );
+            }
+        }
+    }
+
+    /// Add the display number of an helix next to both ends of the helix, whose axis goes from
+    /// `start` to `end`.
+    pub fn helix_end_letter_instances(
+        &self,
+        number: usize,
+        (start, end): (Vec3, Vec3),
+        instances: &mut Vec<Vec<LetterInstance>>,
+        right: Vec3,
+    ) {
+        let label = number.to_string();
+        let length = (end - start).mag();
+        let direction = if length > 1e-3 {
+            (end - start) / length
+        } else {
+            self.grid.axis_helix()
+        };
+        for position in [
+            start - HELIX_END_LABEL_GAP * direction,
+            end + HELIX_END_LABEL_GAP * direction,
+        ]
+        .iter()
+        {
+            self.push_label(&label, *position, 1., instances, right);
+        }
+    }
+
+    /// Add the letters of `label`, centered on `position`, to `instances`. Each letter is added
+    /// to the vector whose index is the position of the letter in `GRID_LABEL_CHARS`.
+    fn push_label(
+        &self,
+        label: &str,
+        position: Vec3,
+        scale: f32,
+        instances: &mut Vec<Vec<LetterInstance>>,
+        right: Vec3,
+    ) {
+        let shift = -0.35 * scale * label.len() as f32 * right;
+        for (c_idx, c) in label.chars().enumerate() {
+            if let Some(idx) = GRID_LABEL_CHARS.iter().position(|x| *x == c) {
+                instances[idx].push(LetterInstance {
+                    position: position + 0.7 * scale * c_idx as f32 * right + shift,
+                    color: ultraviolet::Vec4::new(0., 0., 0., 1.),
+                    design_id: self.design as u32,
+                    scale: 3. * scale,
+                    shift: Vec3::zero(),
+                });
+            }
         }
     }
 
@@ -20,8 +20,12 @@ use lyon::path::Path;
 use lyon::tessellation;
 use lyon::tessellation::{StrokeVertex, StrokeVertexConstructor};
 
-const TEXTURE_SIZE: u32 = 512;
-use ensnano_interactor::consts::*;
+use ensnano_interactor::graphics::render_quality;
+
+/// The width and height of the grid textures
+fn grid_texture_size() -> u32 {
+    render_quality().texture_resolution.grid_texture_size()
+}
 
 use ensnano_utils::wgpu;
 use wgpu::util::DeviceExt;
@@ -48,8 +52,8 @@ impl SquareTexture {
     pub fn new(device: &Device, encoder: &mut wgpu::CommandEncoder) -> Self {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             size: wgpu::Extent3d {
-                width: TEXTURE_SIZE,
-                height: TEXTURE_SIZE,
+                width: grid_texture_size(),
+                height: grid_texture_size(),
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -69,6 +73,7 @@ impl SquareTexture {
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
             mipmap_filter: wgpu::FilterMode::Nearest,
+            anisotropy_clamp: render_quality().anisotropic_filtering.clamp(),
             ..Default::default()
         });
 
@@ -102,15 +107,15 @@ fn fill_square_texture(target: &TextureView, device: &Device, encoder: &mut wgpu
     };
 
     let texture_size = ensnano_utils::winit::dpi::PhysicalSize {
-        width: TEXTURE_SIZE,
-        height: TEXTURE_SIZE,
+        width: grid_texture_size(),
+        height: grid_texture_size(),
     };
 
-    let msaa_texture = if SAMPLE_COUNT > 1 {
+    let msaa_texture = if super::super::sample_count() > 1 {
         Some(ensnano_utils::texture::Texture::create_msaa_texture(
             device,
             &texture_size,
-            SAMPLE_COUNT,
+            super::super::sample_count(),
             wgpu::TextureFormat::Bgra8UnormSrgb,
         ))
     } else {
@@ -144,8 +149,8 @@ fn fill_square_texture(target: &TextureView, device: &Device, encoder: &mut wgpu
     render_pass.set_viewport(
         0f32,
         0f32,
-        TEXTURE_SIZE as f32,
-        TEXTURE_SIZE as f32,
+        grid_texture_size() as f32,
+        grid_texture_size() as f32,
         0.0,
         1.0,
     );
@@ -190,8 +195,8 @@ impl HonneyTexture {
     pub fn new(device: &Device, encoder: &mut wgpu::CommandEncoder) -> Self {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             size: wgpu::Extent3d {
-                width: TEXTURE_SIZE,
-                height: TEXTURE_SIZE,
+                width: grid_texture_size(),
+                height: grid_texture_size(),
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -211,6 +216,7 @@ impl HonneyTexture {
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
             mipmap_filter: wgpu::FilterMode::Nearest,
+            anisotropy_clamp: render_quality().anisotropic_filtering.clamp(),
             ..Default::default()
         });
 
@@ -248,15 +254,15 @@ fn fill_honneycomb_texture(
     };
 
     let texture_size = ensnano_utils::winit::dpi::PhysicalSize {
-        width: TEXTURE_SIZE,
-        height: TEXTURE_SIZE,
+        width: grid_texture_size(),
+        height: grid_texture_size(),
     };
 
-    let msaa_texture = if SAMPLE_COUNT > 1 {
+    let msaa_texture = if super::super::sample_count() > 1 {
         Some(ensnano_utils::texture::Texture::create_msaa_texture(
             device,
             &texture_size,
-            SAMPLE_COUNT,
+            super::super::sample_count(),
             wgpu::TextureFormat::Bgra8UnormSrgb,
         ))
     } else {
@@ -290,8 +296,8 @@ fn fill_honneycomb_texture(
     render_pass.set_viewport(
         0f32,
         0f32,
-        TEXTURE_SIZE as f32,
-        TEXTURE_SIZE as f32,
+        grid_texture_size() as f32,
+        grid_texture_size() as f32,
         0.0,
         1.0,
     );
@@ -377,7 +383,7 @@ fn pipeline(device: &Device) -> wgpu::RenderPipeline {
         primitive,
         depth_stencil: None,
         multisample: wgpu::MultisampleState {
-            count: SAMPLE_COUNT,
+            count: super::super::sample_count(),
             mask: !0,
             alpha_to_coverage_enabled: false,
         },
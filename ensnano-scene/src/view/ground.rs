@@ -0,0 +1,187 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! Visual helpers drawn beneath the design: a ground grid and a contact shadow.
+
+use super::{Drawable, Vertex};
+use ensnano_design::ultraviolet::Vec3;
+use ensnano_interactor::graphics::GroundParameters;
+use ensnano_utils::wgpu;
+use std::f32::consts::PI;
+
+/// The number of lines of the grid on each side of its center, in each direction.
+const GRID_HALF_NB_LINES: u16 = 40;
+const GRID_COLOR: u32 = 0x80_80_80;
+/// The opacity of the lines at the center of the grid.
+const GRID_ALPHA: u32 = 0x90;
+const SHADOW_COLOR: u32 = 0x00_00_00;
+/// The opacity at the center of the shadow.
+const SHADOW_ALPHA: u32 = 0x60;
+const SHADOW_NB_SECTOR: u16 = 36;
+/// Distance between the grid and the shadow, to avoid z-fighting.
+const SHADOW_ELEVATION: f32 = 0.02;
+
+/// The bounding box of the design, and the parameters of the ground beneath it.
+#[derive(Debug, Clone, Copy)]
+pub struct Ground {
+    pub parameters: GroundParameters,
+    /// `[min_x, max_x, min_y, max_y, min_z, max_z]`
+    pub design_boundaries: [f32; 6],
+}
+
+impl Ground {
+    fn ground_y(&self) -> f32 {
+        self.design_boundaries[2] - self.parameters.height
+    }
+
+    /// The grid to be drawn if the camera is at `camera_position`.
+    ///
+    /// The grid has a finite number of lines but it is centered beneath the camera and fades
+    /// away from its center, so that it looks infinite.
+    pub fn grid(&self, camera_position: Vec3) -> Option<GroundGrid> {
+        self.parameters.show_grid.then(|| {
+            let spacing = self.parameters.spacing.max(0.1);
+            GroundGrid {
+                center: Vec3::new(
+                    (camera_position.x / spacing).round() * spacing,
+                    self.ground_y(),
+                    (camera_position.z / spacing).round() * spacing,
+                ),
+                spacing,
+            }
+        })
+    }
+
+    pub fn shadow(&self) -> Option<ContactShadow> {
+        let [min_x, max_x, _, _, min_z, max_z] = self.design_boundaries;
+        (self.parameters.show_shadow && min_x <= max_x && min_z <= max_z).then(|| ContactShadow {
+            center: Vec3::new(
+                (min_x + max_x) / 2.,
+                self.ground_y() + SHADOW_ELEVATION,
+                (min_z + max_z) / 2.,
+            ),
+            radius_x: (max_x - min_x) / 2. + 1.,
+            radius_z: (max_z - min_z) / 2. + 1.,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GroundGrid {
+    center: Vec3,
+    spacing: f32,
+}
+
+impl Drawable for GroundGrid {
+    fn vertices(&self, fake: bool) -> Vec<Vertex> {
+        let mut ret = Vec::new();
+        let n = GRID_HALF_NB_LINES as f32;
+        let half_length = n * self.spacing;
+        let width = self.spacing / 40.;
+        for (dir, normal) in [
+            (Vec3::unit_x(), Vec3::unit_z()),
+            (Vec3::unit_z(), Vec3::unit_x()),
+        ] {
+            for i in -(GRID_HALF_NB_LINES as i32)..=(GRID_HALF_NB_LINES as i32) {
+                let alpha = (GRID_ALPHA as f32 * (1. - i.abs() as f32 / n)) as u32;
+                let origin = self.center + normal * (i as f32 * self.spacing);
+                for (t, a) in [(-1., 0), (0., alpha), (1., 0)] {
+                    let color = (a << 24) | GRID_COLOR;
+                    let position = origin + dir * (t * half_length);
+                    ret.push(Vertex::new(position - normal * width, color, fake));
+                    ret.push(Vertex::new(position + normal * width, color, fake));
+                }
+            }
+        }
+        ret
+    }
+
+    fn indices() -> Vec<u16> {
+        let nb_lines = 2 * (2 * GRID_HALF_NB_LINES + 1);
+        let mut ret = Vec::with_capacity(12 * nb_lines as usize);
+        for line in 0..nb_lines {
+            for segment in 0..2 {
+                let i = 6 * line + 2 * segment;
+                ret.extend_from_slice(&[i, i + 1, i + 2, i + 1, i + 2, i + 3]);
+            }
+        }
+        ret
+    }
+
+    fn primitive_topology() -> wgpu::PrimitiveTopology {
+        wgpu::PrimitiveTopology::TriangleList
+    }
+
+    fn use_alpha() -> bool {
+        true
+    }
+}
+
+/// A soft elliptic shadow beneath the design.
+#[derive(Debug, Clone, Copy)]
+pub struct ContactShadow {
+    center: Vec3,
+    radius_x: f32,
+    radius_z: f32,
+}
+
+impl Drawable for ContactShadow {
+    fn vertices(&self, fake: bool) -> Vec<Vertex> {
+        let mut ret = Vec::with_capacity(1 + 2 * SHADOW_NB_SECTOR as usize);
+        ret.push(Vertex::new(
+            self.center,
+            (SHADOW_ALPHA << 24) | SHADOW_COLOR,
+            fake,
+        ));
+        // An inner ring where the shadow is still dark and an outer one where it vanishes.
+        for (scale, alpha) in [(0.8, 3 * SHADOW_ALPHA / 4), (1.3, 0)] {
+            for i in 0..SHADOW_NB_SECTOR {
+                let theta = 2. * PI * i as f32 / SHADOW_NB_SECTOR as f32;
+                let position = self.center
+                    + Vec3::new(
+                        scale * self.radius_x * theta.cos(),
+                        0.,
+                        scale * self.radius_z * theta.sin(),
+                    );
+                ret.push(Vertex::new(position, (alpha << 24) | SHADOW_COLOR, fake));
+            }
+        }
+        ret
+    }
+
+    fn indices() -> Vec<u16> {
+        let n = SHADOW_NB_SECTOR;
+        let mut ret = Vec::with_capacity(9 * n as usize);
+        for i in 0..n {
+            let inner = 1 + i;
+            let inner_next = 1 + (i + 1) % n;
+            ret.extend_from_slice(&[0, inner, inner_next]);
+            let outer = inner + n;
+            let outer_next = inner_next + n;
+            ret.extend_from_slice(&[inner, outer, inner_next, inner_next, outer, outer_next]);
+        }
+        ret
+    }
+
+    fn primitive_topology() -> wgpu::PrimitiveTopology {
+        wgpu::PrimitiveTopology::TriangleList
+    }
+
+    fn use_alpha() -> bool {
+        true
+    }
+}
@@ -18,7 +18,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use super::{CameraPtr, Drawable, Drawer, ProjectionPtr, Vertex};
 use ensnano_design::group_attributes::GroupPivot;
 use ensnano_design::ultraviolet;
-use ensnano_interactor::consts::*;
+use ensnano_interactor::{consts::*, GizmoAxis};
 use ensnano_utils::wgpu;
 use std::rc::Rc;
 use ultraviolet::{Rotor3, Vec3};
@@ -30,6 +30,8 @@ pub struct HandlesDescriptor {
     pub orientation: HandleOrientation,
     pub size: f32,
     pub colors: HandleColors,
+    /// If some, only the handle along that axis is displayed.
+    pub constraint: Option<GizmoAxis>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -55,14 +57,42 @@ impl HandlesDescriptor {
         let dist = (camera.borrow().position - self.origin).mag();
         let (right, up, dir) = self.make_axis();
         let length = self.size * dist * (projection.borrow().get_fovy() / 2.).tan();
+        let filter = |axis: GizmoAxis| {
+            if self.constraint.filter(|c| *c != axis).is_some() {
+                0.
+            } else {
+                1.
+            }
+        };
         let colors = match self.colors {
             HandleColors::Cym => ensnano_interactor::consts::CYM_HANDLE_COLORS,
             HandleColors::Rgb => ensnano_interactor::consts::RGB_HANDLE_COLORS,
         };
         [
-            Handle::new(self.origin, right, up, colors[0], RIGHT_HANDLE_ID, length),
-            Handle::new(self.origin, up, right, colors[1], UP_HANDLE_ID, length),
-            Handle::new(self.origin, dir, up, colors[2], DIR_HANDLE_ID, length),
+            Handle::new(
+                self.origin,
+                right,
+                up,
+                colors[0],
+                RIGHT_HANDLE_ID,
+                length * filter(GizmoAxis::X),
+            ),
+            Handle::new(
+                self.origin,
+                up,
+                right,
+                colors[1],
+                UP_HANDLE_ID,
+                length * filter(GizmoAxis::Y),
+            ),
+            Handle::new(
+                self.origin,
+                dir,
+                up,
+                colors[2],
+                DIR_HANDLE_ID,
+                length * filter(GizmoAxis::Z),
+            ),
         ]
     }
 
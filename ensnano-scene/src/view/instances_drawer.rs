@@ -19,7 +19,6 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! `Instanciable` trait can be turned into instances that can be drawn by an
 //! [InstanceDrawer](InstanceDrawer).
 
-use ensnano_interactor::consts::*;
 use ensnano_utils::bindgroup_manager::DynamicBindGroup;
 use ensnano_utils::create_buffer_with_data;
 use ensnano_utils::texture::Texture;
@@ -217,6 +216,11 @@ pub struct InstanceDrawer<D: Instanciable + ?Sized> {
 }
 
 impl<D: Instanciable> InstanceDrawer<D> {
+    /// The number of instances that are drawn
+    pub fn nb_instances(&self) -> usize {
+        self.nb_instances as usize
+    }
+
     pub fn new<S: AsRef<str>>(
         device: Rc<Device>,
         queue: Rc<Queue>,
@@ -449,7 +453,7 @@ impl<D: Instanciable> InstanceDrawer<D> {
             wgpu::BlendState::ALPHA_BLENDING
         };
 
-        let sample_count = if fake { 1 } else { SAMPLE_COUNT };
+        let sample_count = if fake { 1 } else { super::sample_count() };
 
         let instance_bind_group_layout_desc = BindGroupLayoutDescriptor {
             label: None,
@@ -558,6 +562,14 @@ pub trait RawDrawer {
     );
 
     fn new_instances_raw(&mut self, instances_raw: &Vec<Self::RawInstance>);
+
+    /// Same as `new_instances_raw`, but only the instances whose indices are in `dirty` differ
+    /// from the previous ones.
+    fn update_instances_raw(
+        &mut self,
+        instances_raw: &Vec<Self::RawInstance>,
+        dirty: &[std::ops::Range<usize>],
+    );
 }
 
 impl<D: Instanciable> RawDrawer for InstanceDrawer<D> {
@@ -568,6 +580,16 @@ impl<D: Instanciable> RawDrawer for InstanceDrawer<D> {
         self.instances.update(instances_raw.as_slice());
     }
 
+    fn update_instances_raw(
+        &mut self,
+        instances_raw: &Vec<D::RawInstance>,
+        dirty: &[std::ops::Range<usize>],
+    ) {
+        self.nb_instances = instances_raw.len() as u32;
+        self.instances
+            .update_ranges(instances_raw.as_slice(), dirty);
+    }
+
     fn draw<'a>(
         &'a mut self,
         render_pass: &mut RenderPass<'a>,
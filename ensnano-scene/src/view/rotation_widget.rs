@@ -20,7 +20,7 @@ use super::{
 };
 
 use ensnano_design::ultraviolet::{Rotor3, Vec3};
-use ensnano_interactor::consts::*;
+use ensnano_interactor::{consts::*, GizmoAxis};
 use ensnano_utils::wgpu;
 use std::f32::consts::PI;
 use std::rc::Rc;
@@ -248,6 +248,8 @@ pub struct RotationWidgetDescriptor {
     pub size: f32,
     pub available_rotation_axes: AvailableRotationAxes,
     pub colors: HandleColors,
+    /// If some, only the circle of the rotations around that axis is displayed.
+    pub constraint: Option<GizmoAxis>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -280,6 +282,13 @@ impl RotationWidgetDescriptor {
             AvailableRotationAxes::NoZ => (1., 0.),
             AvailableRotationAxes::OnlyZ => (0., 1.),
         };
+        let filter = |axis: GizmoAxis| {
+            if self.constraint.filter(|c| *c != axis).is_some() {
+                0.
+            } else {
+                1.
+            }
+        };
         let colors = match self.colors {
             HandleColors::Cym => ensnano_interactor::consts::CYM_HANDLE_COLORS,
             HandleColors::Rgb => ensnano_interactor::consts::RGB_HANDLE_COLORS,
@@ -287,7 +296,7 @@ impl RotationWidgetDescriptor {
         [
             Circle::new(
                 self.origin,
-                length * z_filter,
+                length * z_filter * filter(GizmoAxis::X),
                 up,
                 dir,
                 colors[0],
@@ -295,7 +304,7 @@ impl RotationWidgetDescriptor {
             ),
             Circle::new(
                 self.origin,
-                length * xy_filter,
+                length * xy_filter * filter(GizmoAxis::Y),
                 right,
                 dir,
                 colors[1],
@@ -303,7 +312,7 @@ impl RotationWidgetDescriptor {
             ),
             Circle::new(
                 self.origin,
-                length * 1.1 * xy_filter,
+                length * 1.1 * xy_filter * filter(GizmoAxis::Z),
                 right,
                 up,
                 colors[2],
@@ -0,0 +1,172 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A scale bar drawn in the bottom left corner of the 3D view.
+
+use super::camera::{Camera, Projection};
+use super::{LetterInstance, PlainRectangleInstance};
+use crate::PhySize;
+use ensnano_design::ultraviolet::{Vec3, Vec4};
+use ensnano_interactor::graphics::{scale_bar_length, LengthUnit};
+use ensnano_utils::instance::Instance;
+
+/// The characters that can appear in the label of the scale bar.
+pub const SCALE_BAR_CHARS: &[char] = &[
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', ' ', '/', 'n', 'm', 'b', 'p', 't', 'u',
+    'r', 's',
+];
+
+const SCALE_BAR_COLOR: u32 = 0xE0_70_10;
+const SCALE_BAR_MAX_WIDTH_PX: f32 = 150.;
+const SCALE_BAR_MARGIN_PX: f32 = 20.;
+const SCALE_BAR_THICKNESS_PX: f32 = 4.;
+const LABEL_HEIGHT_PX: f32 = 14.;
+const LABEL_SPACING_PX: f32 = 6.;
+
+/// The letter shader moves the letters towards the camera by this distance.
+const LETTER_DEPTH_SHIFT: f32 = 0.25;
+/// The distance between the camera and the label once the letters have been shifted. The label
+/// is drawn close to the camera so that it is not hidden by the design.
+const LABEL_DISTANCE: f32 = 1.;
+/// The height of a digit, and the advance of a glyph, for a letter instance of scale 1.
+const GLYPH_HEIGHT: f32 = 0.35;
+const GLYPH_ADVANCE: f32 = 0.235;
+
+/// The information needed to compute the length represented by the scale bar.
+#[derive(Debug, Clone, Copy)]
+pub struct ScaleBar {
+    /// The lengths are measured in the plane that contains this point and is orthogonal to the
+    /// direction of the camera.
+    pub reference_point: Vec3,
+    /// The distance in nanometers between two consecutive base pairs.
+    pub rise: f32,
+    /// The unit of the length written on the label
+    pub length_unit: LengthUnit,
+    /// The number of nanometers in one `length_unit`
+    pub nm_per_unit: f32,
+}
+
+pub struct ScaleBarInstances {
+    pub bar: PlainRectangleInstance,
+    /// The letters of the label, sorted by their index in `SCALE_BAR_CHARS`.
+    pub letters: Vec<Vec<LetterInstance>>,
+}
+
+impl ScaleBar {
+    pub fn instances(
+        &self,
+        camera: &Camera,
+        projection: &Projection,
+        area_size: PhySize,
+    ) -> Option<ScaleBarInstances> {
+        let depth = (self.reference_point - camera.position).dot(camera.direction());
+        if depth <= 0. || area_size.width == 0 || area_size.height == 0 {
+            return None;
+        }
+        let width_px = area_size.width as f32;
+        let height_px = area_size.height as f32;
+        let tan_half_fovy = (projection.get_fovy() / 2.).tan();
+        let nm_per_px = 2. * depth * tan_half_fovy / height_px;
+        let length = scale_bar_length(SCALE_BAR_MAX_WIDTH_PX * nm_per_px / self.nm_per_unit);
+        let length_nm = length * self.nm_per_unit;
+        let length_px = length_nm / nm_per_px;
+
+        let color = Instance::color_from_u32(SCALE_BAR_COLOR);
+        // (-1, -1) is the bottom left corner in normalized device coordinates
+        let ndc_per_px_x = 2. / width_px;
+        let ndc_per_px_y = 2. / height_px;
+        let bar = PlainRectangleInstance {
+            position: Vec3::new(
+                -1. + SCALE_BAR_MARGIN_PX * ndc_per_px_x,
+                -1. + SCALE_BAR_MARGIN_PX * ndc_per_px_y,
+                0.,
+            ),
+            color,
+            id: 0,
+            width: length_px * ndc_per_px_x,
+            height: SCALE_BAR_THICKNESS_PX * ndc_per_px_y,
+        };
+
+        let label = if let LengthUnit::Nanometer = self.length_unit {
+            format!(
+                "{} nm / {:.0} bp",
+                format_length(length_nm),
+                length_nm / self.rise
+            )
+        } else {
+            format!("{} {}", format_length(length), self.length_unit.symbol())
+        };
+        let letters = self.label_letters(
+            &label,
+            camera,
+            projection,
+            height_px,
+            color,
+            [
+                bar.position.x,
+                -1. + (SCALE_BAR_MARGIN_PX
+                    + SCALE_BAR_THICKNESS_PX
+                    + LABEL_SPACING_PX
+                    + LABEL_HEIGHT_PX)
+                    * ndc_per_px_y,
+            ],
+        );
+        Some(ScaleBarInstances { bar, letters })
+    }
+
+    /// The letters of `label`, with the top left corner of the label at position `top_left` in
+    /// normalized device coordinates.
+    fn label_letters(
+        &self,
+        label: &str,
+        camera: &Camera,
+        projection: &Projection,
+        height_px: f32,
+        color: Vec4,
+        top_left: [f32; 2],
+    ) -> Vec<Vec<LetterInstance>> {
+        let tan_half_fovy = (projection.get_fovy() / 2.).tan();
+        let world_per_px = 2. * LABEL_DISTANCE * tan_half_fovy / height_px;
+        let scale = LABEL_HEIGHT_PX * world_per_px / GLYPH_HEIGHT;
+        let right = camera.right_vec();
+        let origin = camera.position
+            + (LABEL_DISTANCE + LETTER_DEPTH_SHIFT) * camera.direction()
+            + LABEL_DISTANCE
+                * tan_half_fovy
+                * (top_left[0] * projection.get_ratio() * right + top_left[1] * camera.up_vec());
+
+        let mut letters = vec![Vec::new(); SCALE_BAR_CHARS.len()];
+        for (c_idx, c) in label.chars().enumerate() {
+            if let Some(idx) = SCALE_BAR_CHARS.iter().position(|x| *x == c) {
+                letters[idx].push(LetterInstance {
+                    position: origin + c_idx as f32 * GLYPH_ADVANCE * scale * right,
+                    color,
+                    design_id: 0,
+                    scale,
+                    shift: Vec3::zero(),
+                });
+            }
+        }
+        letters
+    }
+}
+
+/// Format `length` without the rounding errors introduced by `scale_bar_length`.
+fn format_length(length: f32) -> String {
+    let nb_decimals = (-length.log10().floor()).max(0.) as usize;
+    format!("{:.*}", nb_decimals, length)
+}
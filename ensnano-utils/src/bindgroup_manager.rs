@@ -16,6 +16,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 /// This modules contains structure that manipulate bind groups and their associated buffers.
+use std::ops::Range;
 use std::rc::Rc;
 
 use crate::create_buffer_with_data;
@@ -34,6 +35,8 @@ pub struct DynamicBindGroup {
 }
 
 const INITIAL_CAPACITY: u64 = 1024;
+/// Two dirty ranges separated by at most this number of elements are uploaded in a single write.
+const MERGE_GAP_ELEMENTS: usize = 4;
 
 impl DynamicBindGroup {
     pub fn new(device: Rc<Device>, queue: Rc<Queue>, label: &str) -> Self {
@@ -128,10 +131,44 @@ impl DynamicBindGroup {
         self.queue.write_buffer(&self.buffer, 0, bytes);
     }
 
+    /// Replace the data of the associated buffer, knowing that only the elements whose indices
+    /// are in `dirty` differ from the data of the previous update.
+    ///
+    /// If the length of the data changed, the whole buffer is written.
+    pub fn update_ranges<I: bytemuck::Pod>(&mut self, data: &[I], dirty: &[Range<usize>]) {
+        let stride = std::mem::size_of::<I>();
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        if self.length != bytes.len() as u64 || stride as u64 % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+            return self.update(data);
+        }
+        let ranges = merge_dirty_ranges(dirty, data.len());
+        let nb_dirty: usize = ranges.iter().map(|r| r.len()).sum();
+        if 2 * nb_dirty > data.len() {
+            // Most of the buffer changed, a single write is cheaper
+            self.queue.write_buffer(&self.buffer, 0, bytes);
+        } else {
+            for range in ranges {
+                self.queue.write_buffer(
+                    &self.buffer,
+                    (range.start * stride) as u64,
+                    bytemuck::cast_slice(&data[range]),
+                );
+            }
+        }
+    }
+
     #[allow(dead_code)]
     /// Write in the self.buffer with an offset
     pub fn update_offset(&mut self, offset: usize, bytes: &[u8]) {
-        debug_assert!(self.length as usize >= offset + bytes.len());
+        if offset + bytes.len() > self.length as usize {
+            log::error!(
+                "Writing {} bytes at offset {} in a buffer of length {}",
+                bytes.len(),
+                offset,
+                self.length
+            );
+            return;
+        }
         self.queue.write_buffer(&self.buffer, offset as u64, bytes);
     }
 
@@ -227,3 +264,41 @@ impl UniformBindGroup {
         }
     }
 }
+
+/// Sort the ranges of `dirty`, restrict them to `0..len` and merge the ones that overlap or are
+/// close to each other.
+fn merge_dirty_ranges(dirty: &[Range<usize>], len: usize) -> Vec<Range<usize>> {
+    let mut sorted: Vec<Range<usize>> = dirty
+        .iter()
+        .map(|r| r.start.min(len)..r.end.min(len))
+        .filter(|r| !r.is_empty())
+        .collect();
+    sorted.sort_by_key(|r| r.start);
+    let mut ret: Vec<Range<usize>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match ret.last_mut() {
+            Some(last) if range.start <= last.end + MERGE_GAP_ELEMENTS => {
+                last.end = last.end.max(range.end)
+            }
+            _ => ret.push(range),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dirty_ranges_are_sorted_and_merged() {
+        let merged = merge_dirty_ranges(&[20..22, 0..2, 1..3, 5..6, 100..101], 1000);
+        assert_eq!(merged, vec![0..6, 20..22, 100..101]);
+    }
+
+    #[test]
+    fn dirty_ranges_are_restricted_to_the_data() {
+        let merged = merge_dirty_ranges(&[8..12, 15..20, 3..3], 10);
+        assert_eq!(merged, vec![8..10]);
+    }
+}
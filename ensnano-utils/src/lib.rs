@@ -33,6 +33,7 @@ pub mod instance;
 pub mod light;
 pub mod mesh;
 pub mod obj_loader;
+pub mod spatial_index;
 pub mod text;
 pub mod texture;
 
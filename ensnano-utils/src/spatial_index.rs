@@ -0,0 +1,123 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A spatial index to find quickly the elements lying near a point of the 3D space.
+//!
+//! The space is divided in cubic cells, and the index maps each non-empty cell to the elements
+//! whose position is in that cell. A query only looks at the cells that intersect the ball of
+//! interest, so its cost does not depend on the total number of indexed elements.
+
+use ahash::RandomState;
+use std::collections::HashMap;
+use ultraviolet::Vec3;
+
+type Cell = (isize, isize, isize);
+
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<T> {
+    cell_size: f32,
+    cells: HashMap<Cell, Vec<(Vec3, T)>, RandomState>,
+    len: usize,
+}
+
+impl<T> SpatialIndex<T> {
+    /// Create an empty index.
+    ///
+    /// Queries are most efficient when `cell_size` is close to the radius of the balls that will
+    /// be queried.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: Default::default(),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, position: Vec3, value: T) {
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push((position, value));
+        self.len += 1;
+    }
+
+    /// Remove an element that was inserted at `position`. Return true if the element was found.
+    pub fn remove(&mut self, position: Vec3, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let cell = self.cell_of(position);
+        let removed = if let Some(elements) = self.cells.get_mut(&cell) {
+            let idx = elements.iter().position(|(_, v)| v == value);
+            if let Some(idx) = idx {
+                elements.swap_remove(idx);
+            }
+            if elements.is_empty() {
+                self.cells.remove(&cell);
+            }
+            idx.is_some()
+        } else {
+            false
+        };
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+    }
+
+    /// Return all the elements at distance strictly less than `radius` from `center`, together
+    /// with their distance to `center`.
+    pub fn within(&self, center: Vec3, radius: f32) -> impl Iterator<Item = (&T, f32)> {
+        let (min_x, min_y, min_z) = self.cell_of(center - Vec3::broadcast(radius));
+        let (max_x, max_y, max_z) = self.cell_of(center + Vec3::broadcast(radius));
+        (min_x..=max_x)
+            .flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+            .flat_map(move |(x, y)| (min_z..=max_z).map(move |z| (x, y, z)))
+            .filter_map(move |cell| self.cells.get(&cell))
+            .flatten()
+            .filter_map(move |(position, value)| {
+                let dist = (*position - center).mag();
+                (dist < radius).then_some((value, dist))
+            })
+    }
+
+    /// Return the element closest to `center` among those at distance strictly less than
+    /// `max_dist`, together with its distance to `center`.
+    pub fn nearest(&self, center: Vec3, max_dist: f32) -> Option<(&T, f32)> {
+        self.within(center, max_dist)
+            .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+    }
+
+    fn cell_of(&self, position: Vec3) -> Cell {
+        (
+            position.x.div_euclid(self.cell_size) as isize,
+            position.y.div_euclid(self.cell_size) as isize,
+            position.z.div_euclid(self.cell_size) as isize,
+        )
+    }
+}
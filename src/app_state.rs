@@ -24,36 +24,48 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //!
 //! Each component of ENSnano has specific needs and express them via its own `AppState` trait.
 
-use ensnano_design::{group_attributes::GroupPivot, BezierPathId};
+use ensnano_design::{group_attributes::GroupPivot, heat_map::HeatMapKind, BezierPathId};
 use ensnano_exports::{ExportResult, ExportType};
 use ensnano_gui::UiSize;
 use ensnano_interactor::{
-    graphics::{Background3D, HBondDisplay, RenderingMode},
-    UnrootedRevolutionSurfaceDescriptor,
+    consts::{DEFAULT_UNDO_LIMIT, MAX_GIZMO_SIZE, MAX_UNDO_LIMIT, MIN_GIZMO_SIZE, MIN_UNDO_LIMIT},
+    i18n::Language,
+    operation::Operation,
+    ActionMode, CenterOfSelection, CheckXoversParameter, GizmoAxis, Selection, SelectionMode,
+    TwistAxis, WidgetBasis,
 };
 use ensnano_interactor::{
-    operation::Operation, ActionMode, CenterOfSelection, CheckXoversParameter, Selection,
-    SelectionMode, WidgetBasis,
+    graphics::{
+        Background3D, BrushParameters, ColorVisionMode, ElementType, GpuPreference,
+        GroundParameters, HBondDisplay, NumberingParameters, RenderQuality, RenderingMode,
+        ScaleBarParameters, StrandEndsParameters, Theme, ViewLayer, XoverDrawingParameters,
+        XoverGuideParameters,
+    },
+    ShiftOptimizationParameters, UnrootedRevolutionSurfaceDescriptor,
 };
 
+use std::collections::HashSet;
 use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::sync::{mpsc, Arc, RwLock};
 mod address_pointer;
 mod design_interactor;
 mod transitions;
 use crate::apply_update;
 use crate::controller::{LoadDesignError, SaveDesignError, SimulationRequest};
 use address_pointer::AddressPointer;
-use ensnano_design::{Design, SavingInformation};
+use ensnano_design::{diff::DesignDiff, Design, Nucl, SavingInformation};
 use ensnano_interactor::consts::APP_NAME;
-use ensnano_interactor::{DesignOperation, RigidBodyConstants, SuggestionParameters};
+use ensnano_interactor::{
+    DesignBrowserContent, DesignFileInfo, DesignOperation, DesignTemplate, RigidBodyConstants,
+    SuggestionParameters,
+};
 use ensnano_organizer::GroupId;
 
 pub use design_interactor::controller::ErrOperation;
 pub use design_interactor::{
-    CopyOperation, DesignReader, InteractorNotification, PastePosition, PastingStatus,
-    ShiftOptimizationResult, ShiftOptimizerReader, SimulationInterface, SimulationReader,
-    SimulationTarget, SimulationUpdate,
+    CopyOperation, DesignReader, FileIoReader, FileIoResult, FileIoTask, InteractorNotification,
+    PastePosition, PastingStatus, ShiftOptimizationResult, ShiftOptimizerReader,
+    SimulationInterface, SimulationReader, SimulationTarget, SimulationUpdate,
 };
 use design_interactor::{DesignInteractor, InteractorResult};
 
@@ -102,6 +114,7 @@ impl AppState {
             parameters: confy::load(APP_NAME, APP_NAME)?,
             ..Default::default()
         };
+        ensnano_interactor::i18n::set_language(state.parameters.language);
         let mut ret = AppState(AddressPointer::new(state));
         log::trace!("call from default");
         // Synchronize all the pointers.
@@ -111,6 +124,16 @@ impl AppState {
         with_forgot_update.updated_once = false;
         Ok(AppState(AddressPointer::new(with_forgot_update)))
     }
+
+    /// Create a new state whose design is generated from a template.
+    pub fn from_template(template: DesignTemplate) -> Result<Self, ErrOperation> {
+        let mut ret = Self::default();
+        for op in template.operations() {
+            ret.apply_design_op(op)?;
+            ret.update();
+        }
+        Ok(ret.with_selection(vec![], None))
+    }
 }
 
 impl AppState {
@@ -185,6 +208,9 @@ impl AppState {
 
     pub fn with_action_mode(&self, action_mode: ActionMode) -> Self {
         let mut new_state = (*self.0).clone();
+        if new_state.action_mode != action_mode {
+            new_state.gizmo_axis_constraint = None;
+        }
         new_state.action_mode = action_mode;
         Self(AddressPointer::new(new_state))
     }
@@ -213,15 +239,96 @@ impl AppState {
         }
     }
 
+    pub fn with_design_diff(&self, diff: Option<DesignDiff>) -> Self {
+        let mut new_state = (*self.0).clone();
+        new_state.design_diff = diff.map(AddressPointer::new);
+        Self(AddressPointer::new(new_state))
+    }
+
     pub fn exporting(&self, exporting: bool) -> Self {
         let mut new_state = (*self.0).clone();
         new_state.exporting = exporting;
         Self(AddressPointer::new(new_state))
     }
 
+    /// Display the dialog listing design files with `content`, or close it if `content` is
+    /// `None`.
+    pub fn with_design_browser(&self, content: Option<DesignBrowserContent>) -> Self {
+        let mut new_state = (*self.0).clone();
+        new_state.design_browser = content.map(AddressPointer::new);
+        Self(AddressPointer::new(new_state))
+    }
+
+    pub fn get_design_browser(&self) -> Option<&DesignBrowserContent> {
+        self.0.design_browser.as_deref()
+    }
+
+    /// Return true if the translation or rotation widget is displayed in the 3D view.
+    pub fn shows_gizmo(&self) -> bool {
+        matches!(
+            self.0.action_mode,
+            ActionMode::Translate | ActionMode::Rotate
+        )
+    }
+
     pub fn with_toggled_widget_basis(&self) -> Self {
+        self.with_updated_parameters(|p| p.widget_basis.toggle())
+    }
+
+    pub fn with_widget_basis(&self, widget_basis: WidgetBasis) -> Self {
+        self.with_updated_parameters(|p| p.widget_basis = widget_basis)
+    }
+
+    pub fn with_gizmo_size(&self, size: f32) -> Self {
+        self.with_updated_parameters(|p| p.gizmo_size = size.clamp(MIN_GIZMO_SIZE, MAX_GIZMO_SIZE))
+    }
+
+    pub fn with_undo_limit(&self, limit: u32) -> Self {
+        self.with_updated_parameters(|p| p.undo_limit = limit.clamp(MIN_UNDO_LIMIT, MAX_UNDO_LIMIT))
+    }
+
+    /// The maximum number of operations that can be undone
+    pub fn undo_limit(&self) -> usize {
+        self.0.parameters.undo_limit as usize
+    }
+
+    pub fn with_binary_design_files(&self, binary: bool) -> Self {
+        self.with_updated_parameters(|p| p.binary_design_files = binary)
+    }
+
+    /// True if large deletions must be confirmed by the user
+    pub fn confirm_large_deletions(&self) -> bool {
+        self.0.parameters.confirm_large_deletions
+    }
+
+    pub fn with_confirm_large_deletions(&self, confirm: bool) -> Self {
+        self.with_updated_parameters(|p| p.confirm_large_deletions = confirm)
+    }
+
+    pub fn with_gpu_preference(&self, gpu_preference: GpuPreference) -> Self {
+        self.with_updated_parameters(|p| p.gpu_preference = gpu_preference)
+    }
+
+    pub fn with_render_quality(&self, render_quality: RenderQuality) -> Self {
+        self.with_updated_parameters(|p| p.render_quality = render_quality)
+    }
+
+    pub fn with_shift_optimization_parameters(
+        &self,
+        shift_optimization: ShiftOptimizationParameters,
+    ) -> Self {
+        self.with_updated_parameters(|p| p.shift_optimization = shift_optimization)
+    }
+
+    /// Constrain the widgets to `axis`, or release the constraint if the widgets were already
+    /// constrained to `axis`.
+    pub fn with_toggled_gizmo_axis(&self, axis: GizmoAxis) -> Self {
         let mut new_state = (*self.0).clone();
-        new_state.widget_basis.toggle();
+        if new_state.gizmo_axis_constraint == Some(axis) {
+            new_state.gizmo_axis_constraint = None;
+        } else {
+            new_state.gizmo_axis_constraint = Some(axis);
+        }
         Self(AddressPointer::new(new_state))
     }
 
@@ -230,7 +337,6 @@ impl AppState {
         apply_update(self, |s| s.with_updated_design(design))
     }
 
-    #[allow(dead_code)] //used in tests
     pub fn with_updated_design(&self, design: Design) -> Self {
         let mut new_state = self.0.clone_inner();
         let new_interactor = new_state.design.with_updated_design(design);
@@ -238,20 +344,43 @@ impl AppState {
         Self(AddressPointer::new(new_state))
     }
 
-    pub fn import_design(mut path: PathBuf) -> Result<Self, LoadDesignError> {
+    pub fn import_design(path: PathBuf) -> Result<Self, LoadDesignError> {
         let design_interactor = DesignInteractor::new_with_path(&path)?;
+        Ok(Self::with_loaded_interactor(path, design_interactor))
+    }
+
+    /// Start reading the design stored at `path` in a background thread.
+    ///
+    /// When the design has been read, `reader` receives a `FileIoResult::Loaded` whose design must
+    /// be passed to `from_loaded_design`.
+    pub fn start_loading_design(path: PathBuf, reader: &mut dyn FileIoReader) {
+        DesignInteractor::start_loading(path, reader)
+    }
+
+    /// Start reading summaries of the design files of `directory` in a background thread. The
+    /// channel is closed once all the files have been read.
+    pub fn start_scanning_design_directory(directory: PathBuf) -> mpsc::Receiver<DesignFileInfo> {
+        DesignInteractor::start_scanning_directory(directory)
+    }
+
+    pub fn from_loaded_design(path: PathBuf, design: Design) -> Self {
+        let design_interactor = DesignInteractor::from_loaded_design(design);
+        Self::with_loaded_interactor(path, design_interactor)
+    }
+
+    fn with_loaded_interactor(mut path: PathBuf, design_interactor: DesignInteractor) -> Self {
         if path.extension().map(|s| s.to_string_lossy())
             != Some(crate::consts::ENS_BACKUP_EXTENSION.into())
         {
             path.set_extension(crate::consts::ENS_EXTENSION);
         }
-        Ok(Self(AddressPointer::new(AppState_ {
+        Self(AddressPointer::new(AppState_ {
             design: AddressPointer::new(design_interactor),
             parameters: confy::load(APP_NAME, APP_NAME).unwrap_or_default(),
             path_to_current_design: Some(path.clone()),
             ..Default::default()
         }))
-        .updated())
+        .updated()
     }
 
     pub fn save_design(
@@ -259,11 +388,37 @@ impl AppState {
         path: &PathBuf,
         saving_info: SavingInformation,
     ) -> Result<(), SaveDesignError> {
-        self.get_design_reader().save_design(path, saving_info)?;
+        self.get_design_reader().save_design(
+            path,
+            saving_info,
+            self.0.parameters.binary_design_files,
+        )?;
         self.0.make_mut().path_to_current_design = Some(path.clone());
         Ok(())
     }
 
+    /// Start writing the design at `path` in a background thread.
+    ///
+    /// When the design has been written, `reader` receives a `FileIoResult::Saved`, after which
+    /// `set_path_to_current_design` should be called.
+    pub fn start_saving_design(
+        &self,
+        path: PathBuf,
+        saving_info: SavingInformation,
+        reader: &mut dyn FileIoReader,
+    ) {
+        self.get_design_reader().start_saving_design(
+            path,
+            saving_info,
+            self.0.parameters.binary_design_files,
+            reader,
+        )
+    }
+
+    pub fn set_path_to_current_design(&mut self, path: PathBuf) {
+        self.0.make_mut().path_to_current_design = Some(path);
+    }
+
     pub fn path_to_current_design(&self) -> Option<&PathBuf> {
         self.0.path_to_current_design.as_ref()
     }
@@ -273,6 +428,13 @@ impl AppState {
         apply_update(self, Self::updated)
     }
 
+    /// Return true if the next call to `update` will need to rebuild the presenter.
+    pub(super) fn design_need_update(&self) -> bool {
+        self.0
+            .design
+            .design_need_update(&self.0.parameters.suggestion_parameters)
+    }
+
     pub(super) fn apply_simulation_update(&mut self, update: Box<dyn SimulationUpdate>) {
         apply_update(self, |s| s.with_simualtion_update_applied(update))
     }
@@ -344,6 +506,19 @@ impl AppState {
         self.handle_operation_result(result)
     }
 
+    /// Start a simulation that runs in the background without blocking the edition of the
+    /// design. The simulation runs as long as `reader` keeps its interface alive.
+    pub(super) fn start_simulation_job(
+        &self,
+        parameters: RigidBodyConstants,
+        reader: &mut dyn SimulationReader,
+        target: SimulationTarget,
+    ) -> Result<(), ErrOperation> {
+        self.0
+            .design
+            .start_simulation_job(parameters, reader, target)
+    }
+
     pub(super) fn update_simulation(
         &mut self,
         request: SimulationRequest,
@@ -447,6 +622,14 @@ impl AppState {
         self.with_updated_parameters(|p| p.follow_stereography = follow)
     }
 
+    pub fn with_inside_out_inspection(&self, inside_out: bool) -> Self {
+        self.with_updated_parameters(|p| p.inside_out_inspection = inside_out)
+    }
+
+    pub fn with_brush_parameters(&self, brush: BrushParameters) -> Self {
+        self.with_updated_parameters(|p| p.brush = brush)
+    }
+
     pub fn with_show_stereographic_camera(&self, show: bool) -> Self {
         self.with_updated_parameters(|p| p.show_stereography = show)
     }
@@ -459,6 +642,10 @@ impl AppState {
         self.with_updated_parameters(|p| p.show_bezier_paths = show)
     }
 
+    pub fn with_show_insertion_badges(&self, show: bool) -> Self {
+        self.with_updated_parameters(|p| p.show_insertion_badges = show)
+    }
+
     pub fn all_helices_on_axis(&self, on_axis: bool) -> Self {
         self.with_updated_parameters(|p| p.all_helices_on_axis = on_axis)
     }
@@ -498,14 +685,84 @@ impl AppState {
         self.with_updated_parameters(|p| p.all_helices_on_axis ^= true)
     }
 
+    pub fn with_ground_parameters(&self, ground: GroundParameters) -> Self {
+        self.with_updated_parameters(|p| p.ground = ground)
+    }
+
+    pub fn with_scale_bar_parameters(&self, scale_bar: ScaleBarParameters) -> Self {
+        self.with_updated_parameters(|p| p.scale_bar = scale_bar)
+    }
+
+    pub fn with_numbering_parameters(&self, numbering: NumberingParameters) -> Self {
+        self.with_updated_parameters(|p| p.numbering = numbering)
+    }
+
+    pub fn with_xover_drawing_parameters(&self, xover_drawing: XoverDrawingParameters) -> Self {
+        self.with_updated_parameters(|p| p.xover_drawing = xover_drawing)
+    }
+
+    pub fn with_strand_ends_parameters(&self, strand_ends: StrandEndsParameters) -> Self {
+        self.with_updated_parameters(|p| p.strand_ends = strand_ends)
+    }
+
+    pub fn with_xover_guide_parameters(&self, xover_guides: XoverGuideParameters) -> Self {
+        self.with_updated_parameters(|p| p.xover_guides = xover_guides)
+    }
+
+    pub fn with_heat_map(&self, heat_map: Option<HeatMapKind>) -> Self {
+        self.with_updated_parameters(|p| p.heat_map = heat_map)
+    }
+
+    /// The twist axis is not saved in the preferences, so they are not stored again when it is
+    /// modified
+    pub fn with_twist_axis(&self, twist_axis: Option<TwistAxis>) -> Self {
+        let mut new_state = (*self.0).clone();
+        new_state.parameters.twist_axis = twist_axis;
+        Self(AddressPointer::new(new_state))
+    }
+
+    pub fn get_twist_axis(&self) -> Option<TwistAxis> {
+        self.0.parameters.twist_axis
+    }
+
     pub fn with_background3d(&self, bg: Background3D) -> Self {
         self.with_updated_parameters(|p| p.background3d = bg)
     }
 
+    pub fn with_theme(&self, theme: Theme) -> Self {
+        self.with_updated_parameters(|p| p.theme = theme)
+    }
+
+    pub fn with_color_vision_mode(&self, color_vision_mode: ColorVisionMode) -> Self {
+        self.with_updated_parameters(|p| p.color_vision_mode = color_vision_mode)
+    }
+
+    pub fn with_language(&self, language: Language) -> Self {
+        self.with_updated_parameters(|p| p.language = language)
+    }
+
     pub fn with_rendering_mode(&self, rendering_mode: RenderingMode) -> Self {
         self.with_updated_parameters(|p| p.rendering_mode = rendering_mode)
     }
 
+    /// Set the settings that override the global rendering settings in the 3D view `view`.
+    /// Only the cartesian and stereographic scenes have a view layer.
+    pub fn with_view_layer(&self, view: ElementType, layer: ViewLayer) -> Self {
+        self.with_updated_parameters(|p| match view {
+            ElementType::Scene => p.scene_layer = layer,
+            ElementType::StereographicScene => p.stereographic_scene_layer = layer,
+            _ => log::error!("No view layer for {:?}", view),
+        })
+    }
+
+    pub fn get_view_layer(&self, view: ElementType) -> ViewLayer {
+        match view {
+            ElementType::Scene => self.0.parameters.scene_layer,
+            ElementType::StereographicScene => self.0.parameters.stereographic_scene_layer,
+            _ => Default::default(),
+        }
+    }
+
     pub fn with_scroll_sensitivity(&self, sensitivity: f32) -> Self {
         self.with_updated_parameters(|p| p.scroll_sensitivity = sensitivity)
     }
@@ -538,7 +795,10 @@ impl AppState {
         &mut self,
         reader: &mut dyn ShiftOptimizerReader,
     ) -> Result<OkOperation, ErrOperation> {
-        let result = self.0.design.optimize_shift(reader);
+        let result = self
+            .0
+            .design
+            .optimize_shift(reader, self.0.parameters.shift_optimization);
         self.handle_operation_result(result)
     }
 
@@ -559,6 +819,19 @@ impl AppState {
         self.handle_operation_result(Ok(result))
     }
 
+    pub(super) fn set_invisible_nucls(
+        &mut self,
+        invisible_nucls: HashSet<Nucl>,
+        undoable: bool,
+    ) -> Result<OkOperation, ErrOperation> {
+        let result = self
+            .0
+            .design
+            .clone_inner()
+            .with_invisible_nucls(invisible_nucls, undoable);
+        self.handle_operation_result(Ok(result))
+    }
+
     pub fn design_was_modified(&self, other: &Self) -> bool {
         self.0.design.has_different_design_than(&other.0.design)
             && (self.0.updated_once || other.0.updated_once)
@@ -669,15 +942,55 @@ pub struct AppStateParameters {
     suggestion_parameters: SuggestionParameters,
     check_xover_parameters: CheckXoversParameter,
     follow_stereography: bool,
+    /// Not saved, the inspection mode is always left when the application is closed
+    #[serde(skip)]
+    inside_out_inspection: bool,
+    /// Not saved, the brush is never active when the application starts
+    #[serde(skip)]
+    brush: BrushParameters,
     show_stereography: bool,
     rendering_mode: RenderingMode,
     background3d: Background3D,
+    /// The overrides of the rendering settings in the cartesian 3D view
+    scene_layer: ViewLayer,
+    /// The overrides of the rendering settings in the stereographic view
+    stereographic_scene_layer: ViewLayer,
+    theme: Theme,
+    color_vision_mode: ColorVisionMode,
+    language: Language,
     all_helices_on_axis: bool,
     scroll_sensitivity: f32,
     inverted_y_scroll: bool,
     show_h_bonds: HBondDisplay,
     show_bezier_paths: bool,
+    /// If true, the length of the insertions is written on badges in the 3D view
+    show_insertion_badges: bool,
     pub ui_size: ensnano_gui::UiSize,
+    widget_basis: WidgetBasis,
+    gizmo_size: f32,
+    /// The maximum number of operations that can be undone
+    undo_limit: u32,
+    /// If true, designs are saved in binary format instead of json
+    binary_design_files: bool,
+    /// If true, a summary is shown before deleting a large number of nucleotides or
+    /// cross-overs
+    confirm_large_deletions: bool,
+    /// The graphics adapter requested at startup
+    pub gpu_preference: GpuPreference,
+    /// The quality settings of the 3D scene, applied at startup
+    pub render_quality: RenderQuality,
+    /// The settings of the optimization of the scaffold starting position
+    shift_optimization: ShiftOptimizationParameters,
+    ground: GroundParameters,
+    scale_bar: ScaleBarParameters,
+    numbering: NumberingParameters,
+    xover_drawing: XoverDrawingParameters,
+    xover_guides: XoverGuideParameters,
+    strand_ends: StrandEndsParameters,
+    heat_map: Option<HeatMapKind>,
+    /// The axis around which free helices are twisted. Not saved.
+    #[serde(skip)]
+    twist_axis: Option<TwistAxis>,
 }
 
 impl Default for AppStateParameters {
@@ -686,15 +999,39 @@ impl Default for AppStateParameters {
             suggestion_parameters: Default::default(),
             check_xover_parameters: Default::default(),
             follow_stereography: Default::default(),
+            inside_out_inspection: false,
+            brush: Default::default(),
             show_stereography: Default::default(),
             rendering_mode: Default::default(),
             background3d: Default::default(),
+            scene_layer: Default::default(),
+            stereographic_scene_layer: Default::default(),
+            theme: Default::default(),
+            color_vision_mode: Default::default(),
+            language: Default::default(),
             all_helices_on_axis: false,
             scroll_sensitivity: 0.0,
             inverted_y_scroll: false,
             show_h_bonds: HBondDisplay::No,
             show_bezier_paths: false,
+            show_insertion_badges: true,
             ui_size: ensnano_gui::UiSize::default(),
+            widget_basis: WidgetBasis::default(),
+            gizmo_size: 1.,
+            undo_limit: DEFAULT_UNDO_LIMIT,
+            binary_design_files: false,
+            confirm_large_deletions: true,
+            gpu_preference: Default::default(),
+            render_quality: Default::default(),
+            shift_optimization: Default::default(),
+            ground: Default::default(),
+            scale_bar: Default::default(),
+            numbering: Default::default(),
+            xover_drawing: Default::default(),
+            xover_guides: Default::default(),
+            strand_ends: Default::default(),
+            heat_map: None,
+            twist_axis: None,
         }
     }
 }
@@ -711,15 +1048,19 @@ struct AppState_ {
     /// replaced by a pointer to a modified `Design`.
     design: AddressPointer<DesignInteractor>,
     action_mode: ActionMode,
-    widget_basis: WidgetBasis,
+    gizmo_axis_constraint: Option<GizmoAxis>,
     strand_on_new_helix: Option<NewHelixStrand>,
     center_of_selection: Option<CenterOfSelection>,
     updated_once: bool,
     parameters: AppStateParameters,
     show_insertion_representents: bool,
     exporting: bool,
+    /// The content of the dialog listing design files, if it is open
+    design_browser: Option<AddressPointer<DesignBrowserContent>>,
     path_to_current_design: Option<PathBuf>,
     unrooted_surface: CurrentUnrootedSurface,
+    /// The differences with a reference design that are highlighted, if any.
+    design_diff: Option<AddressPointer<DesignDiff>>,
 }
 
 #[derive(Clone, Default)]
@@ -18,13 +18,17 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 
 use super::AddressPointer;
 use ensnano_design::{
-    grid::GridId, group_attributes::GroupAttribute, BezierPathId, BezierPlaneDescriptor, Design,
-    HelixCollection, HelixParameters, InstanciatedPiecewiseBezier,
+    elements::DesignElementKey,
+    grid::GridId,
+    group_attributes::{GroupAttribute, GroupPivot},
+    BezierPathId, BezierPlaneDescriptor, BundleId, Design, HelixBundle, HelixCollection,
+    HelixParameters, InstanciatedPiecewiseBezier, Joint, JointBody, JointId, Nucl,
 };
 use ensnano_exports::{ExportResult, ExportType};
 use ensnano_interactor::{
     operation::Operation, DesignOperation, RevolutionSurfaceSystemDescriptor, RigidBodyConstants,
-    Selection, SimulationState, StrandBuilder, SuggestionParameters,
+    Selection, ShiftOptimizationParameters, SimulationState, StrandBuilder, SuggestionParameters,
+    TwistAxis,
 };
 
 mod presenter;
@@ -42,8 +46,13 @@ use crate::{controller::SimulationRequest, gui::CurentOpState};
 pub(super) use controller::ErrOperation;
 use controller::{GridPresenter, HelixPresenter, OkOperation, RollPresenter, TwistPresenter};
 
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
+use ultraviolet::Vec3;
+mod binary_format;
+mod file_io;
 mod file_parsing;
+pub use file_io::{FileIoReader, FileIoResult, FileIoTask};
 
 /// The `DesignInteractor` handles all read/write operations on the design. It is a stateful struct
 /// so it is meant to be unexpansive to clone.
@@ -72,11 +81,12 @@ impl DesignInteractor {
     pub(super) fn optimize_shift(
         &self,
         reader: &mut dyn ShiftOptimizerReader,
+        parameters: ShiftOptimizationParameters,
     ) -> Result<InteractorResult, ErrOperation> {
         let nucl_map = self.presenter.get_owned_nucl_collection();
         let result = self
             .controller
-            .optimize_shift(reader, nucl_map, &self.design);
+            .optimize_shift(reader, nucl_map, &self.design, parameters);
         self.handle_operation_result(result)
     }
 
@@ -130,40 +140,92 @@ impl DesignInteractor {
         reader: &mut dyn SimulationReader,
         target: SimulationTarget,
     ) -> Result<InteractorResult, ErrOperation> {
-        let operation = match target {
+        let operation = self.simulation_operation(parameters, reader, target);
+        let result = self
+            .controller
+            .apply_simulation_operation(self.design.clone_inner(), operation);
+        self.handle_operation_result(result)
+    }
+
+    /// Start a simulation that runs in the background without changing the state of the
+    /// controller.
+    pub(super) fn start_simulation_job(
+        &self,
+        parameters: RigidBodyConstants,
+        reader: &mut dyn SimulationReader,
+        target: SimulationTarget,
+    ) -> Result<(), ErrOperation> {
+        let operation = self.simulation_operation(parameters, reader, target);
+        self.controller.start_simulation_job(operation)
+    }
+
+    fn simulation_operation<'a>(
+        &'a self,
+        parameters: RigidBodyConstants,
+        reader: &'a mut dyn SimulationReader,
+        target: SimulationTarget,
+    ) -> controller::SimulationOperation<'a, 'a> {
+        match target {
             SimulationTarget::Helices => controller::SimulationOperation::StartHelices {
                 presenter: self.presenter.as_ref(),
                 parameters,
                 reader,
+                free_helices: None,
+            },
+            SimulationTarget::Group(group_id) => controller::SimulationOperation::StartHelices {
+                presenter: self.presenter.as_ref(),
+                parameters,
+                reader,
+                free_helices: Some(self.get_design_reader().get_helices_of_group(group_id)),
             },
             SimulationTarget::Grids => controller::SimulationOperation::StartGrids {
                 presenter: self.presenter.as_ref(),
                 parameters,
                 reader,
+                rigid_bodies: controller::RigidBodyKind::Grids,
             },
-            SimulationTarget::Roll { target_helices } => {
+            SimulationTarget::Bundles => controller::SimulationOperation::StartGrids {
+                presenter: self.presenter.as_ref(),
+                parameters,
+                reader,
+                rigid_bodies: controller::RigidBodyKind::Bundles,
+            },
+            SimulationTarget::Roll {
+                target_helices,
+                target_xovers,
+            } => {
+                let springs = target_xovers.map(|xover_ids| {
+                    xover_ids
+                        .iter()
+                        .filter_map(|id| self.presenter.junctions_ids.get_element(*id))
+                        .collect()
+                });
                 controller::SimulationOperation::StartRoll {
                     presenter: self.presenter.as_ref(),
                     reader,
                     target_helices,
+                    springs,
                 }
             }
             SimulationTarget::Twist { grid_id } => controller::SimulationOperation::StartTwist {
                 presenter: self.presenter.as_ref(),
                 reader,
-                grid_id,
+                target: controller::TwistTarget::Grid(grid_id),
             },
+            SimulationTarget::TwistHelices { helices, axis } => {
+                controller::SimulationOperation::StartTwist {
+                    presenter: self.presenter.as_ref(),
+                    reader,
+                    target: controller::TwistTarget::FreeHelices { helices, axis },
+                }
+            }
             SimulationTarget::Revolution { desc } => {
                 controller::SimulationOperation::RevolutionRelaxation {
                     system: desc,
                     reader,
                 }
             }
-        };
-        let result = self
-            .controller
-            .apply_simulation_operation(self.design.clone_inner(), operation);
-        self.handle_operation_result(result)
+        }
     }
 
     pub(super) fn update_simulation(
@@ -191,14 +253,16 @@ impl DesignInteractor {
         result: Result<(OkOperation, Controller), ErrOperation>,
     ) -> Result<InteractorResult, ErrOperation> {
         match result {
-            Ok((OkOperation::Replace(design), mut controller)) => {
+            Ok((OkOperation::Replace(mut design), mut controller)) => {
+                design.assign_new_stable_ids();
                 let mut ret = self.clone();
                 ret.new_selection = controller.next_selection.take();
                 ret.controller = AddressPointer::new(controller);
                 ret.design = AddressPointer::new(design);
                 Ok(InteractorResult::Replace(ret))
             }
-            Ok((OkOperation::Push { design, label }, mut controller)) => {
+            Ok((OkOperation::Push { mut design, label }, mut controller)) => {
+                design.assign_new_stable_ids();
                 let mut ret = self.clone();
                 ret.current_operation = None;
                 ret.new_selection = controller.next_selection.take();
@@ -289,7 +353,6 @@ impl DesignInteractor {
         self
     }
 
-    #[allow(dead_code)] //used in tests
     pub(super) fn with_updated_design(&self, design: Design) -> Self {
         let mut new_interactor = self.clone();
         new_interactor.design = AddressPointer::new(design);
@@ -355,6 +418,27 @@ impl DesignInteractor {
         }
     }
 
+    /// Set the set of invisible nucleotides. If `undoable` is false, the change does not create
+    /// an undo entry (e.g. when moving between presentation steps).
+    pub(super) fn with_invisible_nucls(
+        mut self,
+        invisible_nucls: HashSet<Nucl>,
+        undoable: bool,
+    ) -> InteractorResult {
+        let mut presenter = self.presenter.clone_inner();
+        presenter.set_invisible_nucls(invisible_nucls);
+        self.presenter = AddressPointer::new(presenter);
+        self.design = AddressPointer::new(self.design.clone_inner());
+        if undoable {
+            InteractorResult::Push {
+                interactor: self,
+                label: crate::consts::UPDATE_VISIBILITY_SIEVE_LABEL.into(),
+            }
+        } else {
+            InteractorResult::Replace(self)
+        }
+    }
+
     pub(super) fn get_new_selection(&self) -> Option<Vec<Selection>> {
         self.controller.get_new_selection()
     }
@@ -407,16 +491,34 @@ impl DesignReader {
         &self,
         path: &PathBuf,
         saving_info: ensnano_design::SavingInformation,
+        binary: bool,
     ) -> Result<(), SaveDesignError> {
         use std::io::Write;
-        let mut design = self.presenter.current_design.clone_inner();
-        design.prepare_for_save(saving_info);
-        let json_content = serde_json::to_string_pretty(&design)?;
+        let design = self.design_to_save(saving_info);
+        let content = file_io::serialize_design(&design, binary)?;
         let mut f = std::fs::File::create(path)?;
-        f.write_all(json_content.as_bytes())?;
+        f.write_all(&content)?;
         Ok(())
     }
 
+    /// Start writing the design in a background thread.
+    pub(super) fn start_saving_design(
+        &self,
+        path: PathBuf,
+        saving_info: ensnano_design::SavingInformation,
+        binary: bool,
+        reader: &mut dyn FileIoReader,
+    ) {
+        let design = self.design_to_save(saving_info);
+        reader.attach_file_io_task(file_io::spawn_saving(path, design, binary))
+    }
+
+    fn design_to_save(&self, saving_info: ensnano_design::SavingInformation) -> Design {
+        let mut design = self.presenter.current_design.clone_inner();
+        design.prepare_for_save(saving_info);
+        design
+    }
+
     pub fn export(&self, export_path: &PathBuf, export_type: ExportType) -> ExportResult {
         self.presenter.export(export_path, export_type)
     }
@@ -433,6 +535,154 @@ impl DesignReader {
             .get(&group_id)
     }
 
+    pub fn get_helix_bundles(&self) -> &BTreeMap<BundleId, HelixBundle> {
+        &self.presenter.current_design.as_ref().helix_bundles
+    }
+
+    /// If all the helices of the selection belong to the same bundle, return the pivot of this
+    /// bundle.
+    pub fn get_bundle_pivot_of_selection(&self, selection: &[Selection]) -> Option<GroupPivot> {
+        let helices = ensnano_interactor::set_of_helices_containing_selection(selection, self)?;
+        let bundles = self.get_helix_bundles();
+        let mut bundle_ids = helices
+            .iter()
+            .map(|h| ensnano_design::bundle_of_helix(bundles, *h));
+        let bundle_id = bundle_ids.next()??;
+        bundle_ids
+            .all(|id| id == Some(bundle_id))
+            .then(|| bundles[&bundle_id].pivot)
+    }
+
+    pub fn get_joints(&self) -> &BTreeMap<JointId, Joint> {
+        &self.presenter.current_design.as_ref().joints
+    }
+
+    /// Return the bodies that can be linked by a joint and that contain an element of the
+    /// selection, in the order in which they appear in the selection. Helices that belong to a
+    /// bundle are seen as part of the bundle rather than of their grid.
+    pub fn get_joint_bodies_of_selection(&self, selection: &[Selection]) -> Vec<JointBody> {
+        let design = self.presenter.current_design.as_ref();
+        let mut ret = Vec::new();
+        for s in selection {
+            let bodies: Vec<JointBody> = if let Selection::Grid(_, g_id) = s {
+                vec![JointBody::Grid(*g_id)]
+            } else {
+                ensnano_interactor::set_of_helices_containing_selection(&[s.clone()], self)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|h_id| {
+                        ensnano_design::bundle_of_helix(&design.helix_bundles, h_id)
+                            .map(JointBody::Bundle)
+                            .or_else(|| {
+                                let grid = design.helices.get(&h_id)?.grid_position?.grid;
+                                matches!(grid, GridId::FreeGrid(_)).then(|| JointBody::Grid(grid))
+                            })
+                    })
+                    .collect()
+            };
+            for body in bodies {
+                if !ret.contains(&body) {
+                    ret.push(body);
+                }
+            }
+        }
+        ret
+    }
+
+    /// Return the identifiers of the helices that are in the group, or that hold a strand of the
+    /// group.
+    pub fn get_helices_of_group(&self, group_id: GroupId) -> BTreeSet<usize> {
+        let design = self.presenter.current_design.as_ref();
+        let elements = design
+            .organizer_tree
+            .as_ref()
+            .and_then(|tree| tree.get_elements_of_group(group_id))
+            .unwrap_or_default();
+        let mut ret = BTreeSet::new();
+        for element in elements {
+            match element {
+                DesignElementKey::Helix(h_id) => {
+                    ret.insert(h_id);
+                }
+                DesignElementKey::Strand(s_id) => {
+                    if let Some(strand) = design.strands.get(&s_id) {
+                        for d in strand.domains.iter() {
+                            if let ensnano_design::Domain::HelixDomain(interval) = d {
+                                ret.insert(interval.helix);
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    /// Return, for each element of the group, the positions of its nucleotides. Grids and empty
+    /// helices are represented by their origin.
+    pub fn get_positions_of_group_elements(&self, group_id: GroupId) -> Vec<Vec<Vec3>> {
+        use ensnano_design::{grid::FreeGridId, Domain, HasMap};
+        let design = self.presenter.current_design.as_ref();
+        let elements = design
+            .organizer_tree
+            .as_ref()
+            .and_then(|tree| tree.get_elements_of_group(group_id))
+            .unwrap_or_default();
+        fn positions_of_domains<'a>(
+            design: &Design,
+            domains: impl Iterator<Item = &'a Domain>,
+        ) -> Vec<Vec3> {
+            let mut ret = Vec::new();
+            for d in domains {
+                if let Domain::HelixDomain(interval) = d {
+                    for position in interval.start..interval.end {
+                        ret.extend(design.get_nucl_position(Nucl {
+                            helix: interval.helix,
+                            position,
+                            forward: interval.forward,
+                        }));
+                    }
+                }
+            }
+            ret
+        }
+        let mut ret = Vec::with_capacity(elements.len());
+        for element in elements {
+            let positions = match element {
+                DesignElementKey::Strand(s_id) => design
+                    .strands
+                    .get(&s_id)
+                    .map(|s| positions_of_domains(design, s.domains.iter()))
+                    .unwrap_or_default(),
+                DesignElementKey::Helix(h_id) => {
+                    let mut positions = positions_of_domains(
+                        design,
+                        design
+                            .strands
+                            .values()
+                            .flat_map(|s| s.domains.iter())
+                            .filter(|d| d.helix() == Some(h_id)),
+                    );
+                    if positions.is_empty() {
+                        positions.extend(design.helices.get(&h_id).map(|h| h.position));
+                    }
+                    positions
+                }
+                DesignElementKey::Grid(g_id) => design
+                    .free_grids
+                    .get(&FreeGridId(g_id))
+                    .map(|g| vec![g.position])
+                    .unwrap_or_default(),
+                _ => vec![],
+            };
+            if !positions.is_empty() {
+                ret.push(positions);
+            }
+        }
+        ret
+    }
+
     pub fn get_bezier_path_2d(&self, path_id: BezierPathId) -> Option<InstanciatedPiecewiseBezier> {
         self.presenter.get_bezier_path_2d(path_id)
     }
@@ -558,7 +808,7 @@ mod tests {
         let suggestion_parameters = Default::default();
         let interactor = interactor.with_updated_design_reader(&suggestion_parameters);
         let reader = interactor.get_design_reader();
-        assert_eq!(reader.get_all_visible_nucl_ids().len(), 24)
+        assert_eq!(reader.get_all_visible_nucl_ids(false).len(), 24)
     }
 
     #[test]
@@ -1307,6 +1557,184 @@ mod tests {
         assert_eq!(app_state.0.design.presenter.current_design.helices.len(), 1)
     }
 
+    #[test]
+    fn add_helix_block_with_scaffold_raster() {
+        let mut app_state = AppState::default();
+        app_state
+            .apply_design_op(DesignOperation::AddGrid(GridDescriptor {
+                position: Vec3::zero(),
+                orientation: Rotor3::identity(),
+                helix_parameters: None,
+                grid_type: ensnano_design::grid::GridTypeDescr::Square { twist: None },
+                invisible: false,
+                bezier_vertex: None,
+            }))
+            .unwrap();
+        app_state.update();
+        app_state
+            .apply_design_op(DesignOperation::AddHelixBlock(
+                ensnano_interactor::HelixBlockDescriptor {
+                    grid_id: GridId::FreeGrid(0),
+                    x: 0,
+                    y: 0,
+                    nb_rows: 2,
+                    nb_columns: 3,
+                    start: 0,
+                    length: 32,
+                    alternate_orientation: true,
+                    scaffold_raster: true,
+                },
+            ))
+            .unwrap();
+        app_state.update();
+        let design = &app_state.0.design.presenter.current_design;
+        assert_eq!(design.helices.len(), 6);
+        // One complementary strand per helix, and the raster
+        assert_eq!(design.strands.len(), 7);
+        let scaffold = design
+            .scaffold_id
+            .and_then(|s_id| design.strands.get(&s_id))
+            .expect("scaffold");
+        assert_eq!(scaffold.length(), 6 * 32);
+    }
+
+    #[test]
+    fn add_double_strand_on_existing_helix() {
+        let mut app_state = AppState::default();
+        app_state
+            .apply_design_op(DesignOperation::AddGrid(GridDescriptor {
+                position: Vec3::zero(),
+                orientation: Rotor3::identity(),
+                helix_parameters: None,
+                grid_type: ensnano_design::grid::GridTypeDescr::Square { twist: None },
+                invisible: false,
+                bezier_vertex: None,
+            }))
+            .unwrap();
+        app_state.update();
+        app_state
+            .apply_design_op(DesignOperation::AddGridHelix {
+                position: HelixGridPosition::from_grid_id_x_y(GridId::FreeGrid(0), 0, 0),
+                start: 0,
+                length: 10,
+            })
+            .unwrap();
+        app_state.update();
+        app_state
+            .apply_design_op(DesignOperation::AddDoubleStrand {
+                helix: 0,
+                start: 20,
+                length: 8,
+            })
+            .unwrap();
+        app_state.update();
+        let design = &app_state.0.design.presenter.current_design;
+        assert_eq!(design.strands.len(), 4);
+        assert!(design
+            .strands
+            .get_strand_nucl(&Nucl {
+                helix: 0,
+                position: 27,
+                forward: false,
+            })
+            .is_some());
+
+        // The double strand would overlap the existing one
+        assert!(app_state
+            .apply_design_op(DesignOperation::AddDoubleStrand {
+                helix: 0,
+                start: 5,
+                length: 8,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn delete_and_insert_base_pairs() {
+        let mut app_state = AppState::default();
+        app_state
+            .apply_design_op(DesignOperation::AddGrid(GridDescriptor {
+                position: Vec3::zero(),
+                orientation: Rotor3::identity(),
+                helix_parameters: None,
+                grid_type: ensnano_design::grid::GridTypeDescr::Square { twist: None },
+                invisible: false,
+                bezier_vertex: None,
+            }))
+            .unwrap();
+        app_state.update();
+        app_state
+            .apply_design_op(DesignOperation::AddGridHelix {
+                position: HelixGridPosition::from_grid_id_x_y(GridId::FreeGrid(0), 0, 0),
+                start: 0,
+                length: 10,
+            })
+            .unwrap();
+        app_state.update();
+        let base_pair = |position| {
+            vec![
+                Nucl {
+                    helix: 0,
+                    position,
+                    forward: true,
+                },
+                Nucl {
+                    helix: 0,
+                    position,
+                    forward: false,
+                },
+            ]
+        };
+        let strand_lengths = |app_state: &AppState| -> Vec<usize> {
+            app_state
+                .0
+                .design
+                .presenter
+                .current_design
+                .strands
+                .values()
+                .map(|s| s.length())
+                .collect()
+        };
+
+        app_state
+            .apply_design_op(DesignOperation::RmBasePairs {
+                nucls: base_pair(5),
+            })
+            .unwrap();
+        app_state.update();
+        assert_eq!(strand_lengths(&app_state), vec![9, 9]);
+
+        app_state
+            .apply_design_op(DesignOperation::InsertBasePairs {
+                nucls: base_pair(2),
+            })
+            .unwrap();
+        app_state.update();
+        assert_eq!(strand_lengths(&app_state), vec![10, 10]);
+
+        // Base pairs at the end of the strands cannot be deleted
+        assert!(app_state
+            .apply_design_op(DesignOperation::RmBasePairs {
+                nucls: base_pair(0),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn design_templates_are_generated() {
+        for (template, nb_helices) in [
+            (DesignTemplate::SingleHelix, 1),
+            (DesignTemplate::SixHelixBundle, 6),
+            (DesignTemplate::TwentyFourHelixRectangle, 24),
+            (DesignTemplate::TetrahedronWireframe, 6),
+        ] {
+            let app_state = AppState::from_template(template).unwrap();
+            let design = &app_state.0.design.presenter.current_design;
+            assert_eq!(design.helices.len(), nb_helices, "{}", template);
+        }
+    }
+
     #[ignore]
     #[test]
     fn copy_creates_clipboard() {
@@ -1904,13 +2332,24 @@ mod tests {
 #[allow(clippy::large_enum_variant)] // We don't create many instances of this type
 pub enum SimulationTarget {
     Grids,
+    /// Simulate the helix bundles as rigid bodies
+    Bundles,
     Helices,
+    /// Simulate the helices of a group, all other helices being locked
+    Group(GroupId),
     Roll {
         target_helices: Option<Vec<usize>>,
+        /// The identifiers of the cross-overs used as springs. If `None`, all cross-overs are used
+        target_xovers: Option<Vec<usize>>,
     },
     Twist {
         grid_id: GridId,
     },
+    /// Twist free helices around an axis
+    TwistHelices {
+        helices: Vec<usize>,
+        axis: TwistAxis,
+    },
     Revolution {
         desc: RevolutionSurfaceSystemDescriptor,
     },
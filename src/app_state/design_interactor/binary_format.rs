@@ -0,0 +1,49 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A compact binary encoding of designs.
+//!
+//! Binary design files start with [MAGIC_NUMBER], followed by the design encoded in MessagePack.
+//! They are detected automatically when loading a design, so they can use the same extension as
+//! json design files.
+
+use crate::controller::{LoadDesignError, SaveDesignError};
+use ensnano_design::Design;
+
+/// The first bytes of a binary design file. A json file cannot start with these bytes.
+const MAGIC_NUMBER: &[u8] = b"\0ENSNANO\x01";
+
+/// Return true if `content` is the content of a binary design file
+pub(super) fn is_binary_design(content: &[u8]) -> bool {
+    content.starts_with(MAGIC_NUMBER)
+}
+
+pub(super) fn encode_design(design: &Design) -> Result<Vec<u8>, SaveDesignError> {
+    let mut ret = MAGIC_NUMBER.to_vec();
+    // Field names are kept so that fields can be added to the design without breaking
+    // compatibility, as it is the case with json.
+    rmp_serde::encode::write_named(&mut ret, design)?;
+    Ok(ret)
+}
+
+pub(super) fn decode_design(content: &[u8]) -> Result<Design, LoadDesignError> {
+    let content = content
+        .strip_prefix(MAGIC_NUMBER)
+        .ok_or(LoadDesignError::NotABinaryDesign)?;
+    Ok(rmp_serde::from_slice(content)?)
+}
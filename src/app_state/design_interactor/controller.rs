@@ -19,16 +19,21 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use super::{NuclCollection, SimulationUpdate};
 use crate::app_state::AddressPointer;
 use ensnano_design::{
+    design_operations::{
+        ControlPointsEdit, HelixNumbering, InsertionPlacementTarget, NickMove, StrandSequences,
+        XoverRespacing,
+    },
     drawing_style::{DrawingAttribute, DrawingStyle},
     elements::{DesignElementKey, DnaAttribute},
     grid::{
         Edge, FreeGridId, GridDescriptor, GridId, GridObject, GridPosition, GridTypeDescr,
-        HelixGridPosition, Hyperboloid,
+        HelixGridPosition, Hyperboloid, PhantomParameters,
     },
     group_attributes::GroupPivot,
     mutate_in_arc, BezierEnd, BezierPathId, BezierPlaneDescriptor, BezierVertex, BezierVertexId,
-    CameraId, Collection, CurveDescriptor, Design, Domain, DomainJunction, Helices, Helix,
-    HelixCollection, Nucl, Strand, Strands, UpToDateDesign,
+    BundleId, CameraId, CameraRenderingSettings, Collection, CurveDescriptor, Design, Domain,
+    DomainJunction, Helices, Helix, HelixCollection, JointBody, JointId, JointKind, Nucl,
+    OligoPool, PresentationStep, Strand, Strands, UpToDateDesign,
 };
 use ensnano_gui::ClipboardContent;
 pub use ensnano_interactor::PastingStatus;
@@ -38,7 +43,8 @@ use ensnano_interactor::{
 };
 use ensnano_interactor::{
     BezierPlaneHomothethy, DesignOperation, DesignRotation, DesignTranslation, DomainIdentifier,
-    IsometryTarget, NeighbourDescriptor, NeighbourDescriptorGiver, Selection, StrandBuilder,
+    HelixBlockDescriptor, IsometryTarget, NeighbourDescriptor, NeighbourDescriptorGiver, Selection,
+    ShiftOptimizationParameters, StrandBuilder,
 };
 use ensnano_organizer::GroupId;
 use std::collections::BTreeMap;
@@ -66,8 +72,8 @@ pub use shift_optimization::{ShiftOptimizationResult, ShiftOptimizerReader};
 
 mod simulations;
 pub use simulations::{
-    GridPresenter, HelixPresenter, RigidHelixState, RollPresenter, ShakeTarget,
-    SimulationInterface, SimulationOperation, SimulationReader, TwistPresenter,
+    GridPresenter, HelixPresenter, RigidBodyKind, RigidHelixState, RollPresenter, ShakeTarget,
+    SimulationInterface, SimulationOperation, SimulationReader, TwistPresenter, TwistTarget,
 };
 
 mod update_insertion_length;
@@ -146,6 +152,16 @@ impl Controller {
             DesignOperation::SetSmallSpheres { grid_ids, small } => {
                 Ok(self.ok_apply(|c, d| c.set_small_spheres(d, grid_ids, small), design))
             }
+            DesignOperation::SetGridLabels { grid_ids, labelled } => {
+                Ok(self.ok_apply(|c, d| c.set_grid_labels(d, grid_ids, labelled), design))
+            }
+            DesignOperation::SetPhantomParameters {
+                grid_ids,
+                parameters,
+            } => Ok(self.ok_apply(
+                |c, d| c.set_phantom_parameters(d, grid_ids, parameters),
+                design,
+            )),
             DesignOperation::SnapHelices {
                 pivots,
                 translation,
@@ -186,6 +202,14 @@ impl Controller {
                 length,
                 start,
             } => self.apply(|c, d| c.add_grid_helix(d, position, start, length), design),
+            DesignOperation::AddDoubleStrand {
+                helix,
+                start,
+                length,
+            } => self.apply(|c, d| c.add_double_strand(d, helix, start, length), design),
+            DesignOperation::AddHelixBlock(block) => {
+                self.apply(|c, d| c.add_helix_block(d, block), design)
+            }
             DesignOperation::AddTwoPointsBezier { start, end } => {
                 self.apply(|c, d| c.add_two_points_bezier(d, start, end), design)
             }
@@ -227,6 +251,30 @@ impl Controller {
             DesignOperation::SetRollHelices { helices, roll } => {
                 self.apply(|c, d| c.set_roll_helices(d, helices, roll), design)
             }
+            DesignOperation::SetIdealRollHelices { helices } => {
+                self.apply(|c, d| c.set_ideal_roll_helices(d, helices), design)
+            }
+            DesignOperation::RenumberHelices(scheme) => {
+                Ok(self.ok_apply(|c, d| c.renumber_helices(d, scheme), design))
+            }
+            DesignOperation::SetHelixDisplayNumber { helix, number } => {
+                self.apply(|c, d| c.set_helix_display_number(d, helix, number), design)
+            }
+            DesignOperation::CreateHelixBundle { name, helices } => {
+                self.apply(|c, d| c.create_helix_bundle(d, name, helices), design)
+            }
+            DesignOperation::DeleteHelixBundle(bundle_id) => {
+                self.apply(|c, d| c.delete_helix_bundle(d, bundle_id), design)
+            }
+            DesignOperation::RenameHelixBundle { bundle_id, name } => {
+                self.apply(|c, d| c.rename_helix_bundle(d, bundle_id, name), design)
+            }
+            DesignOperation::AddJoint { bodies, kind } => {
+                self.apply(|c, d| c.add_joint(d, bodies, kind), design)
+            }
+            DesignOperation::DeleteJoint(joint_id) => {
+                self.apply(|c, d| c.delete_joint(d, joint_id), design)
+            }
             DesignOperation::SetVisibilityHelix { helix, visible } => {
                 self.apply(|c, d| c.set_visiblity_helix(d, helix, visible), design)
             }
@@ -259,6 +307,15 @@ impl Controller {
             DesignOperation::SetStrandName { s_id, name } => {
                 self.apply(|c, d| c.change_strand_name(d, s_id, name), design)
             }
+            DesignOperation::SetStrandPool { s_id, pool } => {
+                self.apply(|c, d| c.change_strand_pool(d, s_id, pool), design)
+            }
+            DesignOperation::SetStrandNote { s_id, note } => {
+                self.apply(|c, d| c.change_strand_note(d, s_id, note), design)
+            }
+            DesignOperation::SetStrandUrl { s_id, url } => {
+                self.apply(|c, d| c.change_strand_url(d, s_id, url), design)
+            }
             DesignOperation::SetGroupPivot { group_id, pivot } => {
                 self.apply(|c, d| c.set_group_pivot(d, group_id, pivot), design)
             }
@@ -270,6 +327,29 @@ impl Controller {
                 |c, d| c.create_camera(d, position, orientation, pivot_position),
                 design,
             )),
+            DesignOperation::AddPresentationStep {
+                camera_position,
+                camera_orientation,
+                pivot_position,
+                hidden_nucls,
+            } => Ok(self.ok_apply(
+                |c, d| {
+                    c.add_presentation_step(
+                        d,
+                        camera_position,
+                        camera_orientation,
+                        pivot_position,
+                        hidden_nucls,
+                    )
+                },
+                design,
+            )),
+            DesignOperation::DeletePresentationStep(step_id) => {
+                self.apply(|c, d| c.delete_presentation_step(d, step_id), design)
+            }
+            DesignOperation::SetPresentationStepName { step, name } => {
+                self.apply(|c, d| c.set_presentation_step_name(d, step, name), design)
+            }
             DesignOperation::DeleteCamera(cam_id) => {
                 self.apply(|c, d| c.delete_camera(d, cam_id), design)
             }
@@ -287,6 +367,16 @@ impl Controller {
             DesignOperation::SetCameraName { camera_id, name } => {
                 self.apply(|c, d| c.set_camera_name(d, camera_id, name), design)
             }
+            DesignOperation::SwapCameras(a, b) => {
+                self.apply(|c, d| c.swap_cameras(d, a, b), design)
+            }
+            DesignOperation::SetCameraRendering {
+                camera_id,
+                rendering,
+            } => self.apply(
+                |c, d| c.set_camera_rendering(d, camera_id, rendering),
+                design,
+            ),
             DesignOperation::SetGridPosition { grid_id, position } => {
                 self.apply(|c, d| c.set_grid_position(d, grid_id, position), design)
             }
@@ -308,6 +398,38 @@ impl Controller {
             DesignOperation::CheckXovers { xovers } => {
                 self.apply(|c, d| c.check_xovers(d, xovers), design)
             }
+            DesignOperation::RespaceXovers { moves } => {
+                self.apply(|c, d| c.respace_xovers(d, moves), design)
+            }
+            DesignOperation::ClearSequences { strand_ids } => {
+                self.apply(|c, d| c.clear_sequences(d, strand_ids), design)
+            }
+            DesignOperation::AssignScaffoldSequenceToDomains { domain_ids } => self.apply(
+                |c, d| c.assign_scaffold_sequence_to_domains(d, domain_ids),
+                design,
+            ),
+            DesignOperation::SetDomainSequences { sequences } => {
+                self.apply(|c, d| c.set_domain_sequences(d, sequences), design)
+            }
+            DesignOperation::MoveNicks { moves } => {
+                self.apply(|c, d| c.move_nicks(d, moves), design)
+            }
+            DesignOperation::PlaceInsertions { helices, target } => {
+                self.apply(|c, d| c.place_insertions(d, helices, target), design)
+            }
+            DesignOperation::RmBasePairs { nucls } => {
+                self.apply(|c, d| c.insert_base_pairs(d, nucls, -1), design)
+            }
+            DesignOperation::InsertBasePairs { nucls } => {
+                self.apply(|c, d| c.insert_base_pairs(d, nucls, 1), design)
+            }
+            DesignOperation::SetDesignMetadata(metadata) => Ok(self.ok_apply(
+                |_c, mut d| {
+                    d.metadata = metadata;
+                    d
+                },
+                design,
+            )),
             DesignOperation::SetRainbowScaffold(b) => Ok(self.ok_apply(
                 |_c, mut d| {
                     d.rainbow_scaffold = b;
@@ -351,6 +473,17 @@ impl Controller {
                 |c, d| c.set_bezier_vertex_position(d, vertex_id, position),
                 design,
             ),
+            DesignOperation::AddHelicesAlongBezierPath {
+                path_id,
+                with_strands,
+            } => self.apply(
+                |c, d| c.add_helices_along_bezier_path(d, path_id, with_strands),
+                design,
+            ),
+            DesignOperation::EditBezierPathControlPoints { path_id, edit } => self.apply(
+                |c, d| c.edit_bezier_path_control_points(d, path_id, edit),
+                design,
+            ),
             DesignOperation::TurnPathVerticesIntoGrid { path_id, grid_type } => self.apply(
                 |c, d| c.turn_bezier_path_into_grids(d, path_id, grid_type),
                 design,
@@ -490,6 +623,51 @@ impl Controller {
         }
     }
 
+    /// Start a simulation that runs in the background without changing the state of the
+    /// controller. The simulation is stopped when the reader drops its interface.
+    pub(super) fn start_simulation_job(
+        &self,
+        operation: SimulationOperation,
+    ) -> Result<(), ErrOperation> {
+        if self.is_in_persistant_state().is_transitory() {
+            return Err(ErrOperation::IncompatibleState(
+                "Cannot launch simulation while editing".into(),
+            ));
+        }
+        match operation {
+            SimulationOperation::StartGrids {
+                presenter,
+                parameters,
+                reader,
+                rigid_bodies,
+            } => {
+                GridsSystemThread::start_new(presenter, parameters, reader, rigid_bodies)?;
+            }
+            SimulationOperation::StartRoll {
+                presenter,
+                target_helices,
+                springs,
+                reader,
+            } => {
+                PhysicalSystem::start_new(presenter, target_helices, springs, reader);
+            }
+            SimulationOperation::StartTwist {
+                target,
+                presenter,
+                reader,
+            } => {
+                let err = twist_error(&target);
+                simulations::Twister::start_new(presenter, target, reader).ok_or(err)?;
+            }
+            _ => {
+                return Err(ErrOperation::IncompatibleState(
+                    "This simulation cannot run in the background".into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
     pub(super) fn apply_simulation_operation(
         &self,
         mut design: Design,
@@ -514,13 +692,15 @@ impl Controller {
                 presenter,
                 parameters,
                 reader,
+                free_helices,
             } => {
                 if self.is_in_persistant_state().is_transitory() {
                     return Err(ErrOperation::IncompatibleState(
                         "Cannot launch simulation while editing".into(),
                     ));
                 }
-                let interface = HelixSystemThread::start_new(presenter, parameters, reader)?;
+                let interface =
+                    HelixSystemThread::start_new(presenter, parameters, reader, free_helices)?;
                 ret.state = ControllerState::Simulating {
                     interface,
                     initial_design: AddressPointer::new(design.clone()),
@@ -530,21 +710,25 @@ impl Controller {
                 presenter,
                 parameters,
                 reader,
+                rigid_bodies,
             } => {
                 if self.is_in_persistant_state().is_transitory() {
                     return Err(ErrOperation::IncompatibleState(
                         "Cannot launch simulation while editing".into(),
                     ));
                 }
-                let interface = GridsSystemThread::start_new(presenter, parameters, reader)?;
+                let interface =
+                    GridsSystemThread::start_new(presenter, parameters, reader, rigid_bodies)?;
                 ret.state = ControllerState::SimulatingGrids {
                     interface,
                     _initial_design: AddressPointer::new(design.clone()),
+                    rigid_bodies,
                 }
             }
             SimulationOperation::StartRoll {
                 presenter,
                 target_helices,
+                springs,
                 reader,
             } => {
                 if self.is_in_persistant_state().is_transitory() {
@@ -552,14 +736,15 @@ impl Controller {
                         "Cannot launch simulation while editing".into(),
                     ));
                 }
-                let interface = PhysicalSystem::start_new(presenter, target_helices, reader);
+                let interface =
+                    PhysicalSystem::start_new(presenter, target_helices, springs, reader);
                 ret.state = ControllerState::Rolling {
                     _interface: interface,
                     _initial_design: AddressPointer::new(design.clone()),
                 };
             }
             SimulationOperation::StartTwist {
-                grid_id,
+                target,
                 presenter,
                 reader,
             } => {
@@ -568,8 +753,14 @@ impl Controller {
                         "Cannot launch simulation while editing".into(),
                     ));
                 }
-                let interface = simulations::Twister::start_new(presenter, grid_id, reader)
-                    .ok_or(ErrOperation::GridDoesNotExist(grid_id))?;
+                let err = twist_error(&target);
+                let grid_id = if let TwistTarget::Grid(grid_id) = target {
+                    Some(grid_id)
+                } else {
+                    None
+                };
+                let interface =
+                    simulations::Twister::start_new(presenter, target, reader).ok_or(err)?;
                 ret.state = ControllerState::Twisting {
                     _interface: interface,
                     _initial_design: AddressPointer::new(design.clone()),
@@ -650,6 +841,52 @@ impl Controller {
         Ok(design)
     }
 
+    fn change_strand_pool(
+        &mut self,
+        mut design: Design,
+        s_id: usize,
+        pool: Option<OligoPool>,
+    ) -> Result<Design, ErrOperation> {
+        let strand = design
+            .strands
+            .get_mut(&s_id)
+            .ok_or(ErrOperation::StrandDoesNotExist(s_id))?;
+        // Typing in the pool fields produces a single undoable operation, like typing a name.
+        self.state = ControllerState::ChangingStrandName { strand_id: s_id };
+        strand.pool = pool;
+        Ok(design)
+    }
+
+    fn change_strand_note(
+        &mut self,
+        mut design: Design,
+        s_id: usize,
+        note: Option<String>,
+    ) -> Result<Design, ErrOperation> {
+        let strand = design
+            .strands
+            .get_mut(&s_id)
+            .ok_or(ErrOperation::StrandDoesNotExist(s_id))?;
+        self.state = ControllerState::ChangingStrandName { strand_id: s_id };
+        strand.note = note;
+        Ok(design)
+    }
+
+    fn change_strand_url(
+        &mut self,
+        mut design: Design,
+        s_id: usize,
+        url: Option<String>,
+    ) -> Result<Design, ErrOperation> {
+        let strand = design
+            .strands
+            .get_mut(&s_id)
+            .ok_or(ErrOperation::StrandDoesNotExist(s_id))?;
+        self.state = ControllerState::ChangingStrandName { strand_id: s_id };
+        strand.url = url;
+        Ok(design)
+    }
+
     fn add_hyperboloid_helices(
         &mut self,
         design: &mut Design,
@@ -728,6 +965,105 @@ impl Controller {
         Ok(design)
     }
 
+    fn set_ideal_roll_helices(
+        &mut self,
+        mut design: Design,
+        helices: Vec<usize>,
+    ) -> Result<Design, ErrOperation> {
+        let mut rolls = Vec::with_capacity(helices.len());
+        for h in helices.iter() {
+            if design.helices.get(h).is_none() {
+                return Err(ErrOperation::HelixDoesNotExists(*h));
+            }
+            if let Some(roll) = ensnano_design::design_operations::ideal_roll(&design, *h) {
+                rolls.push((*h, roll));
+            }
+        }
+        let mut helices_mut = design.helices.make_mut();
+        for (h, roll) in rolls {
+            if let Some(helix) = helices_mut.get_mut(&h) {
+                helix.roll = roll;
+            }
+        }
+        drop(helices_mut);
+        Ok(design)
+    }
+
+    fn renumber_helices(&mut self, mut design: Design, scheme: HelixNumbering) -> Design {
+        let numbers = ensnano_design::design_operations::helix_display_numbers(&design, scheme);
+        let mut helices_mut = design.helices.make_mut();
+        for (h_id, number) in numbers {
+            if let Some(helix) = helices_mut.get_mut(&h_id) {
+                helix.display_number = number;
+            }
+        }
+        drop(helices_mut);
+        design
+    }
+
+    fn set_helix_display_number(
+        &mut self,
+        mut design: Design,
+        helix: usize,
+        number: Option<usize>,
+    ) -> Result<Design, ErrOperation> {
+        let mut helices_mut = design.helices.make_mut();
+        helices_mut
+            .get_mut(&helix)
+            .ok_or(ErrOperation::HelixDoesNotExists(helix))?
+            .display_number = number;
+        drop(helices_mut);
+        Ok(design)
+    }
+
+    fn create_helix_bundle(
+        &mut self,
+        mut design: Design,
+        name: String,
+        helices: Vec<usize>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::create_helix_bundle(&mut design, name, helices)?;
+        Ok(design)
+    }
+
+    fn delete_helix_bundle(
+        &mut self,
+        mut design: Design,
+        bundle_id: BundleId,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::delete_helix_bundle(&mut design, bundle_id)?;
+        Ok(design)
+    }
+
+    fn rename_helix_bundle(
+        &mut self,
+        mut design: Design,
+        bundle_id: BundleId,
+        name: String,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::rename_helix_bundle(&mut design, bundle_id, name)?;
+        Ok(design)
+    }
+
+    fn add_joint(
+        &mut self,
+        mut design: Design,
+        bodies: (JointBody, JointBody),
+        kind: JointKind,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::add_joint(&mut design, bodies, kind)?;
+        Ok(design)
+    }
+
+    fn delete_joint(
+        &mut self,
+        mut design: Design,
+        joint_id: JointId,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::delete_joint(&mut design, joint_id)?;
+        Ok(design)
+    }
+
     fn set_visiblity_helix(
         &mut self,
         mut design: Design,
@@ -826,7 +1162,7 @@ impl Controller {
                 let grid = grids_mut
                     .get_mut(&g_id)
                     .ok_or_else(|| ErrOperation::GridDoesNotExist(g_id.to_grid_id()))?;
-                grid.invisible = !grid.invisible;
+                grid.invisible = !visible;
                 drop(grids_mut);
             }
             _ => (),
@@ -961,6 +1297,7 @@ impl Controller {
         chanel_reader: &mut dyn ShiftOptimizerReader,
         nucl_collection: Arc<Nc>,
         design: &Design,
+        parameters: ShiftOptimizationParameters,
     ) -> Result<(OkOperation, Self), ErrOperation> {
         if let OperationCompatibility::Incompatible =
             self.check_compatibilty(&DesignOperation::SetScaffoldShift(0))
@@ -970,7 +1307,7 @@ impl Controller {
             ));
         }
         Ok(self.ok_no_op(
-            |c, d| c.start_shift_optimization(d, chanel_reader, nucl_collection),
+            |c, d| c.start_shift_optimization(d, chanel_reader, nucl_collection, parameters),
             design,
         ))
     }
@@ -980,12 +1317,14 @@ impl Controller {
         design: &Design,
         chanel_reader: &mut dyn ShiftOptimizerReader,
         nucl_collection: Arc<Nc>,
+        parameters: ShiftOptimizationParameters,
     ) {
         self.state = ControllerState::OptimizingScaffoldPosition;
         shift_optimization::optimize_shift(
             Arc::new(design.clone()),
             nucl_collection,
             chanel_reader,
+            parameters,
         );
     }
 
@@ -1063,6 +1402,20 @@ impl Controller {
                     OperationCompatibility::FinishFirst
                 }
             }
+            ControllerState::SettingScaffoldShift => {
+                if let DesignOperation::SetScaffoldShift(_) = operation {
+                    OperationCompatibility::Compatible
+                } else {
+                    OperationCompatibility::FinishFirst
+                }
+            }
+            ControllerState::SettingPhantomParameters => {
+                if let DesignOperation::SetPhantomParameters { .. } = operation {
+                    OperationCompatibility::Compatible
+                } else {
+                    OperationCompatibility::FinishFirst
+                }
+            }
             ControllerState::ApplyingOperation { .. } => OperationCompatibility::Compatible,
             ControllerState::BuildingStrand { initializing, .. } => {
                 if let DesignOperation::MoveBuilders(_) = operation {
@@ -1083,7 +1436,11 @@ impl Controller {
             ControllerState::ChangingStrandName {
                 strand_id: current_s_id,
             } => {
-                if let DesignOperation::SetStrandName { s_id, .. } = operation {
+                if let DesignOperation::SetStrandName { s_id, .. }
+                | DesignOperation::SetStrandPool { s_id, .. }
+                | DesignOperation::SetStrandNote { s_id, .. }
+                | DesignOperation::SetStrandUrl { s_id, .. } = operation
+                {
                     if current_s_id == *s_id {
                         OperationCompatibility::Compatible
                     } else {
@@ -1136,9 +1493,20 @@ impl Controller {
         match self.state {
             ControllerState::Simulating { .. } => SimulationState::RigidHelices,
             ControllerState::WithPausedSimulation { .. } => SimulationState::Paused,
-            ControllerState::SimulatingGrids { .. } => SimulationState::RigidGrid,
+            ControllerState::SimulatingGrids {
+                rigid_bodies: RigidBodyKind::Grids,
+                ..
+            } => SimulationState::RigidGrid,
+            ControllerState::SimulatingGrids {
+                rigid_bodies: RigidBodyKind::Bundles,
+                ..
+            } => SimulationState::RigidBundles,
             ControllerState::Rolling { .. } => SimulationState::Rolling,
-            ControllerState::Twisting { grid_id, .. } => SimulationState::Twisting { grid_id },
+            ControllerState::Twisting {
+                grid_id: Some(grid_id),
+                ..
+            } => SimulationState::Twisting { grid_id },
+            ControllerState::Twisting { grid_id: None, .. } => SimulationState::TwistingHelices,
             ControllerState::Relaxing { .. } => SimulationState::Relaxing,
             _ => SimulationState::None,
         }
@@ -1172,6 +1540,8 @@ impl Controller {
             ControllerState::WithPendingHelicesDuplication { .. } => StatePersitance::Persistant,
             ControllerState::WithPausedSimulation { .. } => StatePersitance::NeedFinish,
             ControllerState::SettingRollHelices { .. } => StatePersitance::NeedFinish,
+            ControllerState::SettingScaffoldShift => StatePersitance::NeedFinish,
+            ControllerState::SettingPhantomParameters => StatePersitance::NeedFinish,
             ControllerState::ChangingStrandName { .. } => StatePersitance::NeedFinish,
             _ => StatePersitance::Transitory,
         }
@@ -1565,6 +1935,52 @@ impl Controller {
         design
     }
 
+    fn add_presentation_step(
+        &mut self,
+        mut design: Design,
+        camera_position: Vec3,
+        camera_orientation: Rotor3,
+        pivot_position: Option<Vec3>,
+        hidden_nucls: Vec<Nucl>,
+    ) -> Design {
+        let name = format!("Step {}", design.presentation_steps.len() + 1);
+        design.presentation_steps.push(PresentationStep {
+            name,
+            camera_position,
+            camera_orientation,
+            pivot_position,
+            hidden_nucls,
+        });
+        design
+    }
+
+    fn delete_presentation_step(
+        &mut self,
+        mut design: Design,
+        step_id: usize,
+    ) -> Result<Design, ErrOperation> {
+        if step_id < design.presentation_steps.len() {
+            design.presentation_steps.remove(step_id);
+            Ok(design)
+        } else {
+            Err(ErrOperation::PresentationStepDoesNotExist(step_id))
+        }
+    }
+
+    fn set_presentation_step_name(
+        &mut self,
+        mut design: Design,
+        step_id: usize,
+        name: String,
+    ) -> Result<Design, ErrOperation> {
+        if let Some(step) = design.presentation_steps.get_mut(step_id) {
+            step.name = name;
+            Ok(design)
+        } else {
+            Err(ErrOperation::PresentationStepDoesNotExist(step_id))
+        }
+    }
+
     fn delete_camera(&mut self, mut design: Design, id: CameraId) -> Result<Design, ErrOperation> {
         if !design.rm_camera(id) {
             Err(ErrOperation::CameraDoesNotExist(id))
@@ -1615,6 +2031,35 @@ impl Controller {
         }
     }
 
+    fn swap_cameras(
+        &mut self,
+        mut design: Design,
+        a: CameraId,
+        b: CameraId,
+    ) -> Result<Design, ErrOperation> {
+        if design.swap_cameras(a, b) {
+            Ok(design)
+        } else if design.get_camera(a).is_none() {
+            Err(ErrOperation::CameraDoesNotExist(a))
+        } else {
+            Err(ErrOperation::CameraDoesNotExist(b))
+        }
+    }
+
+    fn set_camera_rendering(
+        &mut self,
+        mut design: Design,
+        id: CameraId,
+        rendering: Option<CameraRenderingSettings>,
+    ) -> Result<Design, ErrOperation> {
+        if let Some(camera) = design.get_camera_mut(id) {
+            camera.rendering = rendering;
+            Ok(design)
+        } else {
+            Err(ErrOperation::CameraDoesNotExist(id))
+        }
+    }
+
     pub(super) fn is_changing_color(&self) -> bool {
         matches!(self.state, ControllerState::ChangingColor)
     }
@@ -1950,9 +2395,14 @@ pub enum ErrOperation {
     HelixNotEmpty(usize),
     EmptyScaffoldSequence,
     NoScaffoldSet,
+    /// The range of positions searched by the scaffold shift optimization is empty
+    EmptyShiftSearchRange,
+    CouldNotCreateThreadPool(String),
     NoGrids,
+    NoHelixBundles,
     FinishFirst,
     CameraDoesNotExist(CameraId),
+    PresentationStepDoesNotExist(usize),
     GridIsNotHyperboloid(GridId),
     DesignOperationError(ensnano_design::design_operations::ErrOperation),
     NotPiecewiseBezier(usize),
@@ -1965,6 +2415,91 @@ pub enum ErrOperation {
     SvgImportError(ensnano_design::SvgImportError),
 }
 
+impl std::fmt::Display for ErrOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GroupHasNoPivot(_) => write!(f, "The group has no pivot"),
+            Self::NotImplemented => write!(f, "This operation is not implemented yet"),
+            Self::BadSelection => write!(f, "This operation cannot be applied on the selection"),
+            Self::IncompatibleState(state) => {
+                write!(f, "This operation is not possible now ({})", state)
+            }
+            Self::CannotBuildOn(nucl) => write!(f, "Cannot build on nucleotide {}", nucl),
+            Self::CutInexistingStrand => write!(f, "There is no strand to cut here"),
+            Self::GridDoesNotExist(g_id) => write!(f, "Grid {:?} does not exist", g_id),
+            Self::GridPositionAlreadyUsed => write!(f, "This grid position is already used"),
+            Self::StrandDoesNotExist(s_id) => write!(f, "Strand {} does not exist", s_id),
+            Self::HelixDoesNotExists(h_id) => write!(f, "Helix {} does not exist", h_id),
+            Self::HelixHasNoGridPosition(h_id) => write!(f, "Helix {} is not on a grid", h_id),
+            Self::CouldNotMakeEdge(_, _) | Self::CouldNotCreateEdges => {
+                write!(f, "The helices cannot be moved to these grid positions")
+            }
+            Self::MergingSameStrand => write!(f, "Cannot merge a strand with itself"),
+            Self::NuclDoesNotExist(nucl) => write!(f, "Nucleotide {} does not exist", nucl),
+            Self::XoverBetweenTwoPrime5 => {
+                write!(f, "Cannot make a cross-over between two 5' ends")
+            }
+            Self::XoverBetweenTwoPrime3 => {
+                write!(f, "Cannot make a cross-over between two 3' ends")
+            }
+            Self::EmptyOrigin => write!(f, "Nothing is selected"),
+            Self::EmptyClipboard => write!(f, "The clipboard is empty"),
+            Self::WrongClipboard => {
+                write!(f, "The clipboard does not contain what can be pasted here")
+            }
+            Self::CannotPasteHere => write!(f, "Cannot paste here"),
+            Self::HelixNotEmpty(h_id) => {
+                write!(
+                    f,
+                    "Helix {} cannot be deleted because it is not empty",
+                    h_id
+                )
+            }
+            Self::EmptyScaffoldSequence => write!(f, "The scaffold sequence is empty"),
+            Self::NoScaffoldSet => write!(f, "No strand is set as the scaffold"),
+            Self::EmptyShiftSearchRange => write!(f, "The range of scaffold shifts is empty"),
+            Self::CouldNotCreateThreadPool(e) => {
+                write!(f, "Could not start the computation: {}", e)
+            }
+            Self::NoGrids => write!(f, "The design has no grid"),
+            Self::NoHelixBundles => write!(f, "The design has no helix bundle"),
+            Self::FinishFirst => write!(f, "The current operation must be finished first"),
+            Self::CameraDoesNotExist(_) => write!(f, "This camera does not exist"),
+            Self::PresentationStepDoesNotExist(step) => {
+                write!(f, "Presentation step {} does not exist", step + 1)
+            }
+            Self::GridIsNotHyperboloid(g_id) => {
+                write!(f, "Grid {:?} is not a nanotube", g_id)
+            }
+            Self::DesignOperationError(e) => write!(f, "{}", e),
+            Self::NotPiecewiseBezier(h_id) => {
+                write!(f, "Helix {} is not a piecewise bezier curve", h_id)
+            }
+            Self::GridCopyError(e) => write!(f, "Could not copy the grid: {}", e),
+            Self::CouldNotGetPrime3of(s_id) => {
+                write!(f, "Could not find the 3' end of strand {}", s_id)
+            }
+            Self::PathDoesNotExist(path_id) => {
+                write!(f, "Bezier path {:?} does not exist", path_id)
+            }
+            Self::VertexDoesNotExist(path_id, vertex_id) => write!(
+                f,
+                "Vertex {} of bezier path {:?} does not exist",
+                vertex_id, path_id
+            ),
+            Self::GridIsNotEmpty(g_id) => {
+                write!(
+                    f,
+                    "Grid {:?} cannot be deleted because it is not empty",
+                    g_id
+                )
+            }
+            Self::CouldNotMake3DObject => write!(f, "Could not create the 3D object"),
+            Self::SvgImportError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
 impl From<ensnano_design::design_operations::ErrOperation> for ErrOperation {
     fn from(e: ensnano_design::design_operations::ErrOperation) -> Self {
         Self::DesignOperationError(e)
@@ -1979,8 +2514,8 @@ impl From<ensnano_design::SvgImportError> for ErrOperation {
 
 impl Controller {
     fn recolor_staples(&mut self, mut design: Design) -> Design {
-        for (s_id, strand) in design.strands.iter_mut() {
-            if Some(*s_id) != design.scaffold_id {
+        for s_id in Self::staple_ids(&design) {
+            if let Some(strand) = design.strands.get_mut(&s_id) {
                 let color = crate::utils::colors::new_color(&mut self.color_idx);
                 strand.color = color;
             }
@@ -1988,6 +2523,17 @@ impl Controller {
         design
     }
 
+    /// The identifiers of the non-scaffold strands. Iterating over them instead of calling
+    /// `design.strands.iter_mut()` avoids copying the scaffold.
+    fn staple_ids(design: &Design) -> Vec<usize> {
+        design
+            .strands
+            .keys()
+            .filter(|s_id| Some(**s_id) != design.scaffold_id)
+            .cloned()
+            .collect()
+    }
+
     fn fancy_recolor_staples(&mut self, mut design: Design) -> Design {
         let mut drawing_styles = HashMap::<DesignElementKey, DrawingStyle>::default();
 
@@ -2012,12 +2558,12 @@ impl Controller {
             }
         }
 
-        for (s_id, strand) in design.strands.iter_mut() {
-            // recoloring only concerns the non-scaffold strands
-            if Some(*s_id) != design.scaffold_id {
+        // recoloring only concerns the non-scaffold strands
+        for s_id in Self::staple_ids(&design) {
+            if let Some(strand) = design.strands.get_mut(&s_id) {
                 // Compute strand drawing style
                 let strand_style = drawing_styles
-                    .get(&DesignElementKey::Strand(*s_id))
+                    .get(&DesignElementKey::Strand(s_id))
                     .unwrap_or(&DrawingStyle::default())
                     .clone();
 
@@ -2047,6 +2593,9 @@ impl Controller {
     fn set_scaffold_shift(&mut self, mut design: Design, shift: usize) -> Design {
         if let ControllerState::OptimizingScaffoldPosition = self.state {
             self.state = ControllerState::Normal;
+        } else {
+            // Successive shifts set with the slider are undone at once
+            self.state = ControllerState::SettingScaffoldShift;
         }
         design.scaffold_shift = Some(shift);
         design
@@ -2099,6 +2648,40 @@ impl Controller {
         design
     }
 
+    fn set_grid_labels(
+        &mut self,
+        mut design: Design,
+        grid_ids: Vec<GridId>,
+        labelled: bool,
+    ) -> Design {
+        for g_id in grid_ids.into_iter() {
+            if labelled {
+                Arc::make_mut(&mut design.labelled_grids).insert(g_id);
+            } else {
+                Arc::make_mut(&mut design.labelled_grids).remove(&g_id);
+            }
+        }
+        design
+    }
+
+    fn set_phantom_parameters(
+        &mut self,
+        mut design: Design,
+        grid_ids: Vec<GridId>,
+        parameters: PhantomParameters,
+    ) -> Design {
+        // Successive parameters set with the sliders are undone at once
+        self.state = ControllerState::SettingPhantomParameters;
+        for g_id in grid_ids.into_iter() {
+            if parameters == PhantomParameters::default() {
+                Arc::make_mut(&mut design.phantom_parameters).remove(&g_id);
+            } else {
+                Arc::make_mut(&mut design.phantom_parameters).insert(g_id, parameters);
+            }
+        }
+        design
+    }
+
     fn snap_helices(
         &mut self,
         mut design: Design,
@@ -2580,6 +3163,10 @@ impl Controller {
             is_cyclic: false,
             sequence: seq_prim5,
             name: name.clone(),
+            uuid: None,
+            pool: strand.pool.clone(),
+            note: strand.note.clone(),
+            url: strand.url.clone(),
         };
 
         let mut strand_3prime = Strand {
@@ -2589,14 +3176,21 @@ impl Controller {
             junctions: prime3_junctions,
             sequence: seq_prim3,
             name,
+            uuid: None,
+            pool: strand.pool.clone(),
+            note: strand.note.clone(),
+            url: strand.url.clone(),
         };
         let new_id = (*strands.keys().max().unwrap_or(&0)).max(id) + 1;
         log::info!("new id {}, ; id {}", new_id, id);
+        // The half that keeps the original id also keeps the stable identifier of the strand.
         let (id_5prime, id_3prime) = if !on_3prime {
             strand_3prime.color = Self::new_color(color_idx);
+            strand_5prime.uuid = strand.uuid;
             (id, new_id)
         } else {
             strand_5prime.color = Self::new_color(color_idx);
+            strand_3prime.uuid = strand.uuid;
             (new_id, id)
         };
         if !strand_5prime.domains.is_empty() {
@@ -2684,6 +3278,63 @@ impl Controller {
         start: isize,
         length: usize,
     ) -> Result<Design, ErrOperation> {
+        let helix_id = Self::push_grid_helix(&mut design, position)?;
+        self.push_double_strand(&mut design, helix_id, start, length);
+        Ok(design)
+    }
+
+    /// Add a double strand going from `start` to `start + length` on the existing helix `helix`.
+    /// Fails if one of the nucleotides of the double strand is already used.
+    fn add_double_strand(
+        &mut self,
+        mut design: Design,
+        helix: usize,
+        start: isize,
+        length: usize,
+    ) -> Result<Design, ErrOperation> {
+        if !design.helices.contains_key(&helix) {
+            return Err(ErrOperation::HelixDoesNotExists(helix));
+        }
+        for position in start..(start + length as isize) {
+            for forward in [false, true] {
+                let nucl = Nucl {
+                    helix,
+                    position,
+                    forward,
+                };
+                if design.strands.get_strand_nucl(&nucl).is_some() {
+                    return Err(ErrOperation::CannotBuildOn(nucl));
+                }
+            }
+        }
+        self.push_double_strand(&mut design, helix, start, length);
+        Ok(design)
+    }
+
+    fn push_double_strand(
+        &mut self,
+        design: &mut Design,
+        helix: usize,
+        start: isize,
+        length: usize,
+    ) {
+        if length > 0 {
+            for b in [false, true].iter() {
+                let new_key = self.add_strand(design, helix, start, *b);
+                if let Domain::HelixDomain(ref mut dom) =
+                    design.strands.get_mut(&new_key).unwrap().domains[0]
+                {
+                    dom.end = dom.start + length as isize;
+                }
+            }
+        }
+    }
+
+    /// Add a new helix at a given grid position and return its identifier
+    fn push_grid_helix(
+        design: &mut Design,
+        position: HelixGridPosition,
+    ) -> Result<usize, ErrOperation> {
         let grid_manager = design.get_updated_grid_data();
         if grid_manager.pos_to_object(position.light()).is_some() {
             return Err(ErrOperation::GridPositionAlreadyUsed);
@@ -2705,19 +3356,160 @@ impl Controller {
         let mut new_helices = design.helices.make_mut();
         let helix_id = new_helices.push_helix(helix);
         drop(new_helices);
-        if length > 0 {
-            for b in [false, true].iter() {
-                let new_key = self.add_strand(&mut design, helix_id, start, *b);
-                if let Domain::HelixDomain(ref mut dom) =
-                    design.strands.get_mut(&new_key).unwrap().domains[0]
-                {
-                    dom.end = dom.start + length as isize;
+        Ok(helix_id)
+    }
+
+    /// Add a rectangular block of helices on a grid.
+    ///
+    /// If `block.scaffold_raster` is true, the helices are linked by a single strand that goes
+    /// through them in raster order, and each helix also receives a complementary strand.
+    /// Otherwise each helix receives a double strand, as with `add_grid_helix`.
+    fn add_helix_block(
+        &mut self,
+        mut design: Design,
+        block: HelixBlockDescriptor,
+    ) -> Result<Design, ErrOperation> {
+        if !matches!(block.grid_id, GridId::FreeGrid(_)) {
+            return Err(ErrOperation::NotImplemented);
+        }
+        let mut helix_ids = Vec::with_capacity(block.nb_rows * block.nb_columns);
+        for position in block.raster_positions() {
+            helix_ids.push(Self::push_grid_helix(&mut design, position)?);
+        }
+        if block.length == 0 {
+            return Ok(design);
+        }
+        let end = block.start + block.length as isize;
+        if block.scaffold_raster {
+            self.push_raster_strand(
+                &mut design,
+                &helix_ids,
+                block.start,
+                end,
+                block.alternate_orientation,
+            );
+        } else {
+            for h_id in helix_ids {
+                for forward in [false, true] {
+                    let new_key = self.add_strand(&mut design, h_id, block.start, forward);
+                    if let Domain::HelixDomain(ref mut dom) =
+                        design.strands.get_mut(&new_key).unwrap().domains[0]
+                    {
+                        dom.end = end;
+                    }
                 }
             }
         }
         Ok(design)
     }
 
+    /// Add a strand that goes through the helices `helix_ids` in raster order, from `start` to
+    /// `end` on each helix, and a complementary strand on each helix. The raster strand becomes
+    /// the scaffold if the design has none.
+    fn push_raster_strand(
+        &mut self,
+        design: &mut Design,
+        helix_ids: &[usize],
+        start: isize,
+        end: isize,
+        alternate_orientation: bool,
+    ) {
+        let mut raster_domains = Vec::with_capacity(helix_ids.len());
+        for (i, h_id) in helix_ids.iter().enumerate() {
+            let forward = !alternate_orientation || i % 2 == 0;
+            raster_domains.push(Domain::HelixDomain(HelixInterval {
+                helix: *h_id,
+                start,
+                end,
+                forward,
+                sequence: None,
+            }));
+            let new_key = self.add_strand(design, *h_id, start, !forward);
+            if let Domain::HelixDomain(ref mut dom) =
+                design.strands.get_mut(&new_key).unwrap().domains[0]
+            {
+                dom.end = end;
+            }
+        }
+        let raster_id = design.strands.keys().max().map(|k| k + 1).unwrap_or(0);
+        let junctions = ensnano_design::read_junctions(&raster_domains, false);
+        design.strands.insert(
+            raster_id,
+            Strand {
+                domains: raster_domains,
+                junctions,
+                color: crate::utils::colors::new_color(&mut self.color_idx),
+                ..Default::default()
+            },
+        );
+        if design.scaffold_id.is_none() {
+            design.scaffold_id = Some(raster_id);
+        }
+    }
+
+    /// Add a pair of helices along the bezier path `path_id`. The ends of the path are first
+    /// snapped onto the continuations of the closest existing helices.
+    ///
+    /// If `with_strands` is true, the helices receive a scaffold going forward on the first helix
+    /// and backward on the second one, and a staple on each helix. Their length is the number of
+    /// nucleotides that fit along the path with the current rise.
+    fn edit_bezier_path_control_points(
+        &mut self,
+        mut design: Design,
+        path_id: BezierPathId,
+        edit: ControlPointsEdit,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::edit_bezier_path_control_points(
+            &mut design,
+            path_id,
+            edit,
+        )?;
+        Ok(design)
+    }
+
+    fn add_helices_along_bezier_path(
+        &mut self,
+        mut design: Design,
+        path_id: BezierPathId,
+        with_strands: bool,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::snap_bezier_path_ends(&mut design, path_id)?;
+        let mut new_paths = design.bezier_paths.make_mut();
+        let path = new_paths
+            .get_mut(&path_id)
+            .ok_or(ErrOperation::PathDoesNotExist(path_id))?;
+        if path.grid_type.is_none() {
+            path.grid_type = Some(GridTypeDescr::Square { twist: None });
+        }
+        drop(new_paths);
+
+        let grid = GridId::BezierPathGrid(BezierVertexId {
+            path_id,
+            vertex_id: 0,
+        });
+        let mut helix_ids = Vec::with_capacity(2);
+        for x in 0..2 {
+            let position = HelixGridPosition {
+                grid,
+                x,
+                y: 0,
+                axis_pos: 0,
+                roll: 0.,
+            };
+            helix_ids.push(Self::push_grid_helix(&mut design, position)?);
+        }
+        let length = helix_ids
+            .iter()
+            .filter_map(|h_id| design.helices.get(h_id))
+            .map(|h| h.nb_bezier_nucls())
+            .min()
+            .unwrap_or(0);
+        if with_strands && length > 0 {
+            self.push_raster_strand(&mut design, &helix_ids, 0, length as isize, true);
+        }
+        Ok(design)
+    }
+
     fn add_two_points_bezier(
         &mut self,
         mut design: Design,
@@ -2900,6 +3692,10 @@ impl Controller {
                 junctions,
                 is_cyclic: false,
                 name,
+                uuid: strand5prime.uuid.or(strand3prime.uuid),
+                pool: strand5prime.pool.clone().or(strand3prime.pool.clone()),
+                note: strand5prime.note.clone().or(strand3prime.note.clone()),
+                url: strand5prime.url.clone().or(strand3prime.url.clone()),
             };
             new_strand.merge_consecutive_domains();
             strands.insert(prime5, new_strand);
@@ -3101,6 +3897,76 @@ impl Controller {
         Ok(design)
     }
 
+    fn respace_xovers(
+        &mut self,
+        mut design: Design,
+        moves: Vec<XoverRespacing>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::respace_xovers(&mut design, &moves)?;
+        Ok(design)
+    }
+
+    fn move_nicks(
+        &mut self,
+        mut design: Design,
+        moves: Vec<NickMove>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::move_nicks(&mut design, &moves)?;
+        Ok(design)
+    }
+
+    fn clear_sequences(
+        &mut self,
+        mut design: Design,
+        strand_ids: Vec<usize>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::clear_sequences(&mut design, &strand_ids)?;
+        Ok(design)
+    }
+
+    fn assign_scaffold_sequence_to_domains(
+        &mut self,
+        mut design: Design,
+        domain_ids: Vec<usize>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::assign_scaffold_sequence_to_domains(
+            &mut design,
+            &domain_ids,
+        )?;
+        Ok(design)
+    }
+
+    fn set_domain_sequences(
+        &mut self,
+        mut design: Design,
+        sequences: Vec<StrandSequences>,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::set_domain_sequences(&mut design, &sequences)?;
+        Ok(design)
+    }
+
+    fn place_insertions(
+        &mut self,
+        mut design: Design,
+        helices: Vec<usize>,
+        target: InsertionPlacementTarget,
+    ) -> Result<Design, ErrOperation> {
+        let insertions =
+            ensnano_design::design_operations::plan_insertions(&design, &helices, target);
+        ensnano_design::design_operations::apply_insertions(&mut design, &insertions)?;
+        Ok(design)
+    }
+
+    fn insert_base_pairs(
+        &mut self,
+        mut design: Design,
+        nucls: Vec<Nucl>,
+        length: isize,
+    ) -> Result<Design, ErrOperation> {
+        ensnano_design::design_operations::apply_base_pair_insertions(&mut design, &nucls, length)?;
+        Ok(design)
+    }
+
     fn twisted_pair(mut a1: Nucl, mut b1: Nucl, mut a2: Nucl, mut b2: Nucl) -> bool {
         if a1 > b1 {
             std::mem::swap(&mut a1, &mut b1);
@@ -3339,6 +4205,16 @@ impl Controller {
                 design.helices.make_mut().remove(h_id);
             }
         }
+        // Otherwise a helix pushed later with the same identifier would join the bundle
+        let deleted_bundles = ensnano_design::remove_helices_from_bundles(
+            &mut design.helix_bundles,
+            &helices_id.iter().cloned().collect(),
+        );
+        for bundle_id in deleted_bundles {
+            design
+                .joints
+                .retain(|_, joint| !joint.links_body(JointBody::Bundle(bundle_id)));
+        }
         Ok(design)
     }
 
@@ -3359,6 +4235,9 @@ impl Controller {
                 free_grids_mut
                     .remove(&g_id)
                     .ok_or(ErrOperation::GridDoesNotExist(g_id))?;
+                design
+                    .joints
+                    .retain(|_, joint| !joint.links_body(JointBody::Grid(g_id)));
             }
         }
 
@@ -3493,6 +4372,14 @@ fn nucl_pos_2d(helices: &Helices, nucl: &Nucl, segment: usize) -> Option<Vec2> {
     isometry.map(|i| i.into_homogeneous_matrix().transform_point2(local_position))
 }
 
+/// The error returned when a twist simulation cannot be started on `target`
+fn twist_error(target: &TwistTarget) -> ErrOperation {
+    match target {
+        TwistTarget::Grid(grid_id) => ErrOperation::GridDoesNotExist(*grid_id),
+        TwistTarget::FreeHelices { .. } => ErrOperation::BadSelection,
+    }
+}
+
 #[derive(Clone)]
 enum ControllerState {
     Normal,
@@ -3509,6 +4396,8 @@ enum ControllerState {
     },
     ChangingColor,
     SettingRollHelices,
+    SettingScaffoldShift,
+    SettingPhantomParameters,
     WithPendingOp {
         operation: Arc<dyn Operation>,
         design: AddressPointer<Design>,
@@ -3570,6 +4459,7 @@ enum ControllerState {
     SimulatingGrids {
         interface: Arc<Mutex<GridSystemInterface>>,
         _initial_design: AddressPointer<Design>,
+        rigid_bodies: RigidBodyKind,
     },
     Relaxing {
         interface: Arc<Mutex<RevolutionSystemInterface>>,
@@ -3585,7 +4475,8 @@ enum ControllerState {
     Twisting {
         _interface: Arc<Mutex<TwistInterface>>,
         _initial_design: AddressPointer<Design>,
-        grid_id: GridId,
+        /// The twisted grid, `None` if free helices are being twisted
+        grid_id: Option<GridId>,
     },
     ChangingStrandName {
         strand_id: usize,
@@ -3620,6 +4511,8 @@ impl ControllerState {
             Self::WithPausedSimulation { .. } => "WithPausedSimulation",
             Self::Rolling { .. } => "Rolling",
             Self::SettingRollHelices => "SettingRollHelices",
+            Self::SettingScaffoldShift => "SettingScaffoldShift",
+            Self::SettingPhantomParameters => "SettingPhantomParameters",
             Self::ChangingStrandName { .. } => "ChangingStrandName",
             Self::Twisting { .. } => "Twisting",
             Self::PositioningHelicesPastingPoint { .. } => "Positioning strand pasting point",
@@ -3772,6 +4665,8 @@ impl ControllerState {
             Self::WithPausedSimulation { .. } => Self::Normal,
             Self::Rolling { .. } => Self::Normal,
             Self::SettingRollHelices => Self::Normal,
+            Self::SettingScaffoldShift => Self::Normal,
+            Self::SettingPhantomParameters => Self::Normal,
             Self::Twisting { .. } => Self::Normal,
             Self::ChangingStrandName { .. } => Self::Normal,
             Self::PositioningHelicesPastingPoint { .. } => self.clone(),
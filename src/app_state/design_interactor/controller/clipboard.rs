@@ -603,6 +603,10 @@ impl Controller {
                     sequence: None,
                     is_cyclic: false,
                     name: None,
+                    uuid: None,
+                    pool: None,
+                    note: None,
+                    url: None,
                 };
                 let strand_id = if let Some(n) = design.strands.keys().max() {
                     n + 1
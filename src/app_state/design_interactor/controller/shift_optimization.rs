@@ -27,6 +27,8 @@ macro_rules! log_err {
 use crate::app_state::design_interactor::presenter::NuclCollection;
 
 use super::*;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
 
 fn read_scaffold_seq(
@@ -95,50 +97,110 @@ pub fn optimize_shift<Nc: NuclCollection>(
     design: Arc<Design>,
     nucl_collection: Arc<Nc>,
     chanel_reader: &mut dyn ShiftOptimizerReader,
+    parameters: ShiftOptimizationParameters,
 ) {
     let (progress_snd, progress_rcv) = std::sync::mpsc::channel();
     let (result_snd, result_rcv) = std::sync::mpsc::channel();
     chanel_reader.attach_result_chanel(result_rcv);
     chanel_reader.attach_progress_chanel(progress_rcv);
     std::thread::spawn(move || {
-        let result =
-            get_shift_optimization_result(design.as_ref(), progress_snd, nucl_collection.as_ref());
+        let result = get_shift_optimization_result(
+            design.as_ref(),
+            progress_snd,
+            nucl_collection.as_ref(),
+            parameters,
+        );
         log_err!(result_snd.send(result));
     });
 }
 
+/// A candidate position of the scaffold with its score
+struct ShiftCandidate {
+    score: usize,
+    shift: usize,
+    result: String,
+}
+
+impl ShiftCandidate {
+    /// Return the best of two candidates. When two positions have the same score, the smallest one
+    /// is kept so that the result does not depend on the order in which the threads finish.
+    fn best(self, other: Self) -> Self {
+        if (other.score, other.shift) < (self.score, self.shift) {
+            other
+        } else {
+            self
+        }
+    }
+}
+
 fn get_shift_optimization_result(
     design: &Design,
     progress_channel: std::sync::mpsc::Sender<f32>,
     nucl_collection: &dyn NuclCollection,
+    parameters: ShiftOptimizationParameters,
 ) -> ShiftOptimizationResult {
-    let mut best_score = usize::MAX;
-    let mut best_shfit = 0;
-    let mut best_result = String::new();
     let len = design
         .scaffold_sequence
         .as_ref()
         .map(|s| s.len())
         .ok_or(ErrOperation::NoScaffoldSet)?;
-    for shift in 0..len {
-        if shift % 100 == 0 {
-            log_err!(progress_channel.send(shift as f32 / len as f32))
-        }
-        let char_map = read_scaffold_seq(design, nucl_collection, shift)?;
-        let (score, result) = evaluate_shift(design, &char_map);
-        if score < best_score {
-            println!("shift {} score {}", shift, score);
-            best_score = score;
-            best_shfit = shift;
-            best_result = result;
-        }
-        if score == 0 {
-            break;
-        }
-    }
+    let range_end = parameters.range_end.unwrap_or(len).min(len);
+    let range_start = parameters.range_start.min(range_end);
+    let nb_shifts = range_end - range_start;
+
+    // With 0 threads, rayon uses as many threads as there are available cores.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(parameters.nb_threads)
+        .build()
+        .map_err(|e| ErrOperation::CouldNotCreateThreadPool(e.to_string()))?;
+
+    let perfect_score_found = AtomicBool::new(false);
+    let nb_evaluated = AtomicUsize::new(0);
+    let progress_channel = Mutex::new(progress_channel);
+
+    let best = pool.install(|| {
+        (range_start..range_end)
+            .into_par_iter()
+            .map(|shift| {
+                // Once a position without any bad pattern has been found, the remaining positions
+                // are skipped.
+                if perfect_score_found.load(Ordering::Relaxed) {
+                    return Ok(None);
+                }
+                let char_map = read_scaffold_seq(design, nucl_collection, shift)?;
+                let (score, result) = evaluate_shift(design, &char_map);
+                if score == 0 {
+                    perfect_score_found.store(true, Ordering::Relaxed);
+                }
+                let nb_evaluated = nb_evaluated.fetch_add(1, Ordering::Relaxed) + 1;
+                if nb_evaluated % 100 == 0 {
+                    log_err!(progress_channel
+                        .lock()
+                        .unwrap()
+                        .send(nb_evaluated as f32 / nb_shifts as f32))
+                }
+                Ok(Some(ShiftCandidate {
+                    score,
+                    shift,
+                    result,
+                }))
+            })
+            .try_reduce(
+                || None,
+                |a, b| {
+                    Ok(match (a, b) {
+                        (Some(a), Some(b)) => Some(a.best(b)),
+                        (a, b) => a.or(b),
+                    })
+                },
+            )
+    })?;
+
+    let best = best.ok_or(ErrOperation::EmptyShiftSearchRange)?;
+    log::info!("shift {} score {}", best.shift, best.score);
     Ok(ShiftOptimizationOk {
-        position: best_shfit,
-        score: best_result,
+        position: best.shift,
+        score: best.result,
     })
 }
 /// Evaluate a scaffold position. The score of the position is given by
@@ -22,8 +22,10 @@ pub use revolutions::*;
 
 use super::*;
 
-use ensnano_design::{grid::Grid, HelixParameters};
-use ensnano_interactor::{RevolutionSurfaceSystemDescriptor, RigidBodyConstants};
+use ensnano_design::{grid::Grid, HelixParameters, JointBody, JointKind};
+use ensnano_interactor::{
+    RevolutionRelaxationProgress, RevolutionSurfaceSystemDescriptor, RigidBodyConstants,
+};
 use mathru::algebra::linear::vector::vector::Vector;
 use mathru::analysis::differential_equation::ordinary::{
     solver::runge_kutta::{explicit::fixed::FixedStepper, ExplicitEuler, Kutta3},
@@ -33,18 +35,23 @@ use ordered_float::OrderedFloat;
 use rand::Rng;
 use rand_distr::{Exp, StandardNormal};
 use std::cmp::Reverse;
+use std::collections::BTreeSet;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, Weak};
+use std::sync::{mpsc, Arc, Mutex, Weak};
 use ultraviolet::{Bivec3, Mat3};
 
 mod roller;
 pub use roller::{PhysicalSystem, RollInterface, RollPresenter};
 mod twister;
-pub use twister::{TwistInterface, TwistPresenter, Twister};
+pub use twister::{TwistInterface, TwistPresenter, TwistTarget, Twister};
 mod revolutions;
 
 const MAX_DERIVATIVE_NORM: f32 = 1e4;
+/// The stiffness of the springs enforcing joints, relative to the stiffness of cross-overs
+const JOINT_STIFFNESS_FACTOR: f32 = 10.;
+/// The distance between the anchor of a joint and the other points attached by its springs
+const JOINT_ARM_LENGTH: f32 = 2.;
 
 macro_rules! bound_derivative {
     ($obj:ident) => {
@@ -865,10 +872,16 @@ impl HelixSystemThread {
         presenter: &dyn HelixPresenter,
         rigid_parameters: RigidBodyConstants,
         reader: &mut dyn SimulationReader,
+        free_helices: Option<BTreeSet<usize>>,
     ) -> Result<Arc<Mutex<HelixSystemInterface>>, ErrOperation> {
         let interval_results = read_intervals(presenter)?;
-        let helix_system =
-            make_flexible_helices_system((0., 1.), rigid_parameters, presenter, &interval_results)?;
+        let helix_system = make_flexible_helices_system(
+            (0., 1.),
+            rigid_parameters,
+            presenter,
+            &interval_results,
+            free_helices.as_ref(),
+        )?;
         let ret = Arc::new(Mutex::new(HelixSystemInterface::default()));
         let ret_dyn: Arc<Mutex<dyn SimulationInterface>> = ret.clone();
         reader.attach_state(&ret_dyn);
@@ -941,7 +954,22 @@ pub struct GridSystemState {
     positions: Vec<Vec3>,
     orientations: Vec<Rotor3>,
     center_of_mass_from_grid: Vec<Vec3>,
-    ids: Vec<GridId>,
+    ids: Vec<RigidBodyId>,
+}
+
+/// The objects of the design that are the rigid bodies of a `GridsSystem`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RigidBodyKind {
+    Grids,
+    /// The helix bundles of the design. Helices that are not in a bundle do not move.
+    Bundles,
+}
+
+/// The object of the design that is moved by a `RigidGrid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RigidBodyId {
+    Grid(GridId),
+    Bundle(BundleId),
 }
 
 pub(super) struct GridsSystemThread {
@@ -960,8 +988,12 @@ impl GridsSystemThread {
         presenter: &dyn GridPresenter,
         rigid_parameters: RigidBodyConstants,
         reader: &mut dyn SimulationReader,
+        rigid_bodies: RigidBodyKind,
     ) -> Result<Arc<Mutex<GridSystemInterface>>, ErrOperation> {
-        let grid_system = make_grid_system(presenter, (0., 1.), rigid_parameters)?;
+        let grid_system = match rigid_bodies {
+            RigidBodyKind::Grids => make_grid_system(presenter, (0., 1.), rigid_parameters)?,
+            RigidBodyKind::Bundles => make_bundle_system(presenter, (0., 1.), rigid_parameters)?,
+        };
         let ret = Arc::new(Mutex::new(GridSystemInterface::default()));
         let ret_dyn: Arc<Mutex<dyn SimulationInterface>> = ret.clone();
         reader.attach_state(&ret_dyn);
@@ -1014,6 +1046,7 @@ fn make_flexible_helices_system(
     rigid_parameters: RigidBodyConstants,
     presenter: &dyn HelixPresenter,
     interval_results: &IntervalResult,
+    free_helices: Option<&BTreeSet<usize>>,
 ) -> Result<HelixSystem, ErrOperation> {
     let helix_parameters = presenter
         .get_design()
@@ -1035,7 +1068,8 @@ fn make_flexible_helices_system(
             .helices
             .get(&h_id)
             .map(|h| h.locked_for_simulations)
-            .unwrap_or_default();
+            .unwrap_or_default()
+            || free_helices.map_or(false, |free| !free.contains(&h_id));
         rigid_helices.push(rigid_helix);
     }
     let xovers = presenter.get_xovers_list();
@@ -1318,11 +1352,14 @@ pub enum SimulationOperation<'pres, 'reader> {
         presenter: &'pres dyn HelixPresenter,
         parameters: RigidBodyConstants,
         reader: &'reader mut dyn SimulationReader,
+        /// If `Some`, only these helices are allowed to move during the simulation
+        free_helices: Option<BTreeSet<usize>>,
     },
     StartGrids {
         presenter: &'pres dyn GridPresenter,
         parameters: RigidBodyConstants,
         reader: &'reader mut dyn SimulationReader,
+        rigid_bodies: RigidBodyKind,
     },
     UpdateParameters {
         new_parameters: RigidBodyConstants,
@@ -1335,9 +1372,11 @@ pub enum SimulationOperation<'pres, 'reader> {
         presenter: &'pres dyn RollPresenter,
         reader: &'reader mut dyn SimulationReader,
         target_helices: Option<Vec<usize>>,
+        /// The cross-overs used as springs. If `None`, all cross-overs are used
+        springs: Option<Vec<(Nucl, Nucl)>>,
     },
     StartTwist {
-        grid_id: GridId,
+        target: TwistTarget,
         presenter: &'pres dyn TwistPresenter,
         reader: &'reader mut dyn SimulationReader,
     },
@@ -1350,6 +1389,12 @@ pub enum SimulationOperation<'pres, 'reader> {
 
 pub trait SimulationReader {
     fn attach_state(&mut self, state_chanel: &Arc<Mutex<dyn SimulationInterface>>);
+    /// Receive the advancement of a revolution surface relaxation.
+    fn attach_revolution_progress_chanel(
+        &mut self,
+        _chanel: mpsc::Receiver<RevolutionRelaxationProgress>,
+    ) {
+    }
 }
 
 pub trait SimulationInterface: Send {
@@ -1360,6 +1405,10 @@ pub trait SimulationInterface: Send {
     fn still_valid(&self) -> bool {
         true
     }
+    /// The advancement of the simulation between 0 and 1, if the simulation has a known end.
+    fn progress(&self) -> Option<f32> {
+        None
+    }
 }
 
 impl SimulationInterface for HelixSystemInterface {
@@ -1416,6 +1465,7 @@ impl SimulationUpdate for RigidHelixState {
 
 struct GridsSystem {
     springs: Vec<(ApplicationPoint, ApplicationPoint)>,
+    joint_springs: Vec<JointSpring>,
     grids: Vec<RigidGrid>,
     time_span: (f32, f32),
     last_state: Option<Vector<f32>>,
@@ -1434,12 +1484,12 @@ struct RigidGrid {
     orientation: Rotor3,
     inertia_inverse: Mat3,
     mass: f32,
-    id: GridId,
+    id: RigidBodyId,
 }
 
 impl RigidGrid {
     pub fn from_helices(
-        id: GridId,
+        id: RigidBodyId,
         helices: Vec<RigidHelix>,
         position_grid: Vec3,
         orientation: Rotor3,
@@ -1506,6 +1556,26 @@ impl GridsSystem {
             torques[spring.0.grid_id] += torque0;
             torques[spring.1.grid_id] += torque1;
         }
+
+        let k_joints = k_springs * JOINT_STIFFNESS_FACTOR;
+        for spring in self.joint_springs.iter() {
+            let (application_0, application_1) = &spring.points;
+            let point_0 = point_conversion(application_0);
+            let point_1 = point_conversion(application_1);
+            let mut delta = point_1 - point_0;
+            if let Some(axis) = spring.free_axis {
+                let axis = axis.rotated_by(orientations[application_0.grid_id]);
+                delta -= axis * delta.dot(axis);
+            }
+            let force = k_joints * delta;
+
+            forces[application_0.grid_id] += force;
+            forces[application_1.grid_id] -= force;
+            torques[application_0.grid_id] +=
+                (point_0 - positions[application_0.grid_id]).cross(force);
+            torques[application_1.grid_id] +=
+                (point_1 - positions[application_1.grid_id]).cross(-force);
+        }
         /*
         for i in 0..self.grids.len() {
             for j in (i + 1)..self.grids.len() {
@@ -1683,6 +1753,83 @@ struct ApplicationPoint {
     position_on_grid: Vec3,
 }
 
+/// A spring of rest length 0 that enforces a joint between two rigid bodies
+#[derive(Debug)]
+struct JointSpring {
+    points: (ApplicationPoint, ApplicationPoint),
+    /// If `Some`, the spring does not resist motions along this axis, given in the frame of the
+    /// first rigid body
+    free_axis: Option<Vec3>,
+}
+
+/// Make the springs enforcing the joints of the design. Joints linking a body that is not one of
+/// `rigid_bodies` are ignored.
+fn make_joint_springs(design: &Design, rigid_bodies: &[RigidGrid]) -> Vec<JointSpring> {
+    let rigid_body_idx = |body: JointBody| {
+        let id = match body {
+            JointBody::Grid(g_id) => RigidBodyId::Grid(g_id),
+            JointBody::Bundle(b_id) => RigidBodyId::Bundle(b_id),
+        };
+        rigid_bodies
+            .iter()
+            .position(|rigid_body| rigid_body.id == id)
+    };
+    let mut ret = Vec::new();
+    for joint in design.joints.values() {
+        let idx_0 = rigid_body_idx(joint.bodies.0);
+        let idx_1 = rigid_body_idx(joint.bodies.1);
+        let frame_0 = ensnano_design::body_frame(design, joint.bodies.0);
+        let frame_1 = ensnano_design::body_frame(design, joint.bodies.1);
+        if let Some(((idx_0, idx_1), ((position_0, orientation_0), (position_1, orientation_1)))) =
+            idx_0.zip(idx_1).zip(frame_0.zip(frame_1))
+        {
+            let anchor_0 = joint.anchors.0.rotated_by(orientation_0) + position_0;
+            let anchor_1 = joint.anchors.1.rotated_by(orientation_1) + position_1;
+            let axis = joint.axes.0.rotated_by(orientation_0);
+            let normal = if axis.cross(Vec3::unit_x()).mag() > 0.1 {
+                axis.cross(Vec3::unit_x()).normalized()
+            } else {
+                axis.cross(Vec3::unit_y()).normalized()
+            };
+            let binormal = axis.cross(normal);
+            let (offsets, free_axis) = match joint.kind {
+                JointKind::Ball => (vec![Vec3::zero()], None),
+                JointKind::Hinge => (
+                    vec![axis * JOINT_ARM_LENGTH, -axis * JOINT_ARM_LENGTH],
+                    None,
+                ),
+                JointKind::Slider => (
+                    vec![
+                        Vec3::zero(),
+                        normal * JOINT_ARM_LENGTH,
+                        binormal * JOINT_ARM_LENGTH,
+                    ],
+                    Some(axis),
+                ),
+            };
+            let application_point = |point: Vec3, idx: usize| {
+                let rigid_body = &rigid_bodies[idx];
+                ApplicationPoint {
+                    grid_id: idx,
+                    position_on_grid: (point - rigid_body.center_of_mass)
+                        .rotated_by(rigid_body.orientation.reversed()),
+                }
+            };
+            for offset in offsets {
+                ret.push(JointSpring {
+                    points: (
+                        application_point(anchor_0 + offset, idx_0),
+                        application_point(anchor_1 + offset, idx_1),
+                    ),
+                    free_axis: free_axis
+                        .map(|axis| axis.rotated_by(rigid_bodies[idx_0].orientation.reversed())),
+                });
+            }
+        }
+    }
+    ret
+}
+
 fn make_grid_system(
     presenter: &dyn GridPresenter,
     time_span: (f32, f32),
@@ -1756,8 +1903,10 @@ fn make_grid_system(
             }
         }
     }
+    let joint_springs = make_joint_springs(presenter.get_design(), &rigid_grids);
     let mut ret = GridsSystem {
         springs,
+        joint_springs,
         grids: rigid_grids,
         time_span,
         last_state: None,
@@ -1768,6 +1917,91 @@ fn make_grid_system(
     Ok(ret)
 }
 
+fn make_bundle_system(
+    presenter: &dyn GridPresenter,
+    time_span: (f32, f32),
+    rigid_paramaters: RigidBodyConstants,
+) -> Result<GridsSystem, ErrOperation> {
+    let design = presenter.get_design();
+    let intervals = design.strands.get_intervals();
+    let helix_parameters = design.helix_parameters.clone().unwrap_or_default();
+    // The index of the rigid body of each helix
+    let mut rigid_body_of_helix = HashMap::new();
+    let mut rigid_bundles = Vec::with_capacity(design.helix_bundles.len());
+    for (b_id, bundle) in design.helix_bundles.iter() {
+        let frame = bundle.pivot;
+        let mut rigid_helices = Vec::with_capacity(bundle.helices.len());
+        for h_id in bundle.helices.iter() {
+            if let Some(interval) = intervals
+                .get(h_id)
+                .filter(|_| design.helices.contains_key(h_id))
+            {
+                let mut rigid_helix = make_rigid_helix_world_pov_interval(
+                    design,
+                    *h_id,
+                    *interval,
+                    &helix_parameters,
+                );
+                rigid_helix.center_of_mass = (rigid_helix.center_of_mass - frame.position)
+                    .rotated_by(frame.orientation.reversed());
+                rigid_helices.push(rigid_helix);
+                rigid_body_of_helix.insert(*h_id, rigid_bundles.len());
+            }
+        }
+        if !rigid_helices.is_empty() {
+            rigid_bundles.push(RigidGrid::from_helices(
+                RigidBodyId::Bundle(*b_id),
+                rigid_helices,
+                frame.position,
+                frame.orientation,
+            ));
+        }
+    }
+    if rigid_bundles.is_empty() {
+        return Err(ErrOperation::NoHelixBundles);
+    }
+    let mut springs = Vec::new();
+    for (n1, n2) in presenter.get_xovers_list() {
+        let rigid_id1 = rigid_body_of_helix.get(&n1.helix).cloned();
+        let rigid_id2 = rigid_body_of_helix.get(&n2.helix).cloned();
+        if let Some((rigid_id1, rigid_id2)) = rigid_id1.zip(rigid_id2) {
+            if rigid_id1 != rigid_id2 {
+                let application_point = |nucl: Nucl, rigid_id: usize| {
+                    let helix = design
+                        .helices
+                        .get(&nucl.helix)
+                        .ok_or(ErrOperation::HelixDoesNotExists(nucl.helix))?;
+                    let rigid_bundle = &rigid_bundles[rigid_id];
+                    let position =
+                        (helix.space_pos(&helix_parameters, nucl.position, nucl.forward)
+                            - rigid_bundle.center_of_mass)
+                            .rotated_by(rigid_bundle.orientation.reversed());
+                    Ok(ApplicationPoint {
+                        position_on_grid: position,
+                        grid_id: rigid_id,
+                    })
+                };
+                springs.push((
+                    application_point(n1, rigid_id1)?,
+                    application_point(n2, rigid_id2)?,
+                ));
+            }
+        }
+    }
+    let joint_springs = make_joint_springs(design, &rigid_bundles);
+    let mut ret = GridsSystem {
+        springs,
+        joint_springs,
+        grids: rigid_bundles,
+        time_span,
+        last_state: None,
+        anchors: vec![],
+        parameters: rigid_paramaters.clone(),
+    };
+    ret.update_parameters(rigid_paramaters);
+    Ok(ret)
+}
+
 fn make_rigid_grid(
     presenter: &dyn GridPresenter,
     g_id: GridId,
@@ -1786,7 +2020,7 @@ fn make_rigid_grid(
     }
     if rigid_helices.len() > 0 {
         Some(RigidGrid::from_helices(
-            g_id,
+            RigidBodyId::Grid(g_id),
             rigid_helices,
             grid.position,
             grid.orientation,
@@ -1834,13 +2068,31 @@ impl SimulationInterface for GridSystemInterface {
 
 impl SimulationUpdate for GridSystemState {
     fn update_design(&self, design: &mut Design) {
+        let mut bundle_poses = Vec::new();
         let mut new_grids = design.free_grids.make_mut();
         for i in 0..self.ids.len() {
-            let position = self.positions[i];
             let orientation = self.orientations[i].normalized();
-            if let Some(grid) = new_grids.get_mut_g_id(&self.ids[i]) {
-                grid.position = position - self.center_of_mass_from_grid[i].rotated_by(orientation);
-                grid.orientation = orientation;
+            let position =
+                self.positions[i] - self.center_of_mass_from_grid[i].rotated_by(orientation);
+            match self.ids[i] {
+                RigidBodyId::Grid(g_id) => {
+                    if let Some(grid) = new_grids.get_mut_g_id(&g_id) {
+                        grid.position = position;
+                        grid.orientation = orientation;
+                    }
+                }
+                RigidBodyId::Bundle(b_id) => bundle_poses.push((b_id, position, orientation)),
+            }
+        }
+        drop(new_grids);
+        for (b_id, position, orientation) in bundle_poses {
+            if let Err(e) = ensnano_design::design_operations::set_helix_bundle_pose(
+                design,
+                b_id,
+                position,
+                orientation,
+            ) {
+                log::error!("Could not move bundle {:?}: {:?}", b_id, e);
             }
         }
     }
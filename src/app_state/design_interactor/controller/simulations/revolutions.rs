@@ -20,7 +20,7 @@ const MAX_ACCEL: f64 = 100.;
 
 use super::{SimulationInterface, SimulationReader, SimulationUpdate};
 use std::f64::consts::TAU;
-use std::sync::{Arc, Mutex, Weak};
+use std::sync::{mpsc, Arc, Mutex, Weak};
 
 use mathru::algebra::linear::vector::vector::Vector;
 use mathru::analysis::differential_equation::ordinary::{
@@ -33,8 +33,8 @@ use ensnano_design::{
     Similarity3,
 };
 use ensnano_interactor::{
-    EquadiffSolvingMethod, RevolutionSimulationParameters, RevolutionSurfaceRadius,
-    RevolutionSurfaceSystemDescriptor, RootedRevolutionSurface,
+    EquadiffSolvingMethod, RevolutionRelaxationProgress, RevolutionSimulationParameters,
+    RevolutionSurfaceRadius, RevolutionSurfaceSystemDescriptor, RootedRevolutionSurface,
 };
 
 use crate::app_state::ErrOperation;
@@ -146,6 +146,7 @@ impl RevolutionSurfaceSystem {
         &mut self,
         first: &mut bool,
         interface: Option<Arc<Mutex<RevolutionSystemInterface>>>,
+        progress: &mut RelaxationProgressSender,
     ) -> usize {
         let mut current_default;
         for _ in 0..10 {
@@ -156,12 +157,14 @@ impl RevolutionSurfaceSystem {
                     return 0;
                 }
                 if interface_lock.finished {
-                    break;
+                    // Do not rescale the radius, the current shape is accepted as it is
+                    return self.current_scaffold_length.unwrap_or(0);
                 }
                 interface_lock.new_state = Some(self.clone());
             }
             //std::thread::sleep_ms(20_000);
             current_default = self.one_simulation_step(first);
+            progress.send(current_default);
             if current_default < 1.01 {
                 break;
             }
@@ -495,6 +498,25 @@ impl ExplicitODE<f64> for RevolutionSurfaceSystem {
 pub struct RevolutionSystemThread {
     interface: Weak<Mutex<RevolutionSystemInterface>>,
     system: RevolutionSurfaceSystem,
+    progress: RelaxationProgressSender,
+}
+
+/// Counts the simulation steps and sends the advancement of the relaxation to the main thread.
+struct RelaxationProgressSender {
+    iteration: usize,
+    chanel: mpsc::Sender<RevolutionRelaxationProgress>,
+}
+
+impl RelaxationProgressSender {
+    fn send(&mut self, residual: f64) {
+        self.iteration += 1;
+        // The receiver may have been dropped if a new simulation was started, in which case
+        // there is nobody left to notify.
+        let _ = self.chanel.send(RevolutionRelaxationProgress {
+            iteration: self.iteration,
+            residual,
+        });
+    }
 }
 
 impl RevolutionSystemThread {
@@ -505,7 +527,9 @@ impl RevolutionSystemThread {
         let ret = Arc::new(Mutex::new(RevolutionSystemInterface::default()));
         let ret_dyn: Arc<Mutex<dyn SimulationInterface>> = ret.clone();
         reader.attach_state(&ret_dyn);
-        let simulation_thread = Self::new(system, &ret);
+        let (progress_snd, progress_rcv) = mpsc::channel();
+        reader.attach_revolution_progress_chanel(progress_rcv);
+        let simulation_thread = Self::new(system, &ret, progress_snd);
         simulation_thread.run();
         Ok(ret)
     }
@@ -513,11 +537,16 @@ impl RevolutionSystemThread {
     fn new(
         system_desc: RevolutionSurfaceSystemDescriptor,
         interface: &Arc<Mutex<RevolutionSystemInterface>>,
+        progress_chanel: mpsc::Sender<RevolutionRelaxationProgress>,
     ) -> Self {
         let system = RevolutionSurfaceSystem::new(system_desc);
         Self {
             interface: Arc::downgrade(interface),
             system,
+            progress: RelaxationProgressSender {
+                iteration: 0,
+                chanel: progress_chanel,
+            },
         }
     }
 
@@ -525,9 +554,11 @@ impl RevolutionSystemThread {
         std::thread::spawn(move || {
             let mut first = true;
             while let Some(interface_ptr) = self.interface.upgrade() {
-                let current_len = self
-                    .system
-                    .one_radius_optimisation_step(&mut first, Some(interface_ptr.clone()));
+                let current_len = self.system.one_radius_optimisation_step(
+                    &mut first,
+                    Some(interface_ptr.clone()),
+                    &mut self.progress,
+                );
                 if interface_ptr.lock().unwrap().finished
                     || current_len == self.system.scaffold_len_target
                 {
@@ -715,6 +746,10 @@ impl SimulationUpdate for HelicesRouting {
                     name: None,
                     is_cyclic: false,
                     sequence: None,
+                    uuid: None,
+                    pool: None,
+                    note: None,
+                    url: None,
                 });
             }
         }
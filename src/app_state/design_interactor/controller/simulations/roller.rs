@@ -40,6 +40,8 @@ pub struct PhysicalSystem {
     /// The structure that handles the simulation of the rotation springs.
     roller: RollSystem,
     interface: Weak<Mutex<RollInterface>>,
+    /// The helices that are rolled by the simulation, if it is not applied to all helices.
+    target_helices: Option<Vec<usize>>,
 }
 
 pub trait RollPresenter {
@@ -49,9 +51,13 @@ pub trait RollPresenter {
 }
 
 impl PhysicalSystem {
+    /// Start a simulation rolling the `target_helices`, or all helices if it is `None`. The
+    /// springs of the simulation are the cross-overs in `springs`, or all the cross-overs of the
+    /// design if it is `None`.
     pub fn start_new(
         presenter: &dyn RollPresenter,
         target_helices: Option<Vec<usize>>,
+        springs: Option<Vec<(Nucl, Nucl)>>,
         reader: &mut dyn SimulationReader,
     ) -> Arc<Mutex<RollInterface>> {
         let intervals_map = presenter.get_design().strands.get_intervals();
@@ -62,14 +68,14 @@ impl PhysicalSystem {
             .helix_parameters
             .clone()
             .unwrap_or_default();
-        let xovers = presenter.get_xovers_list();
+        let xovers = springs.unwrap_or_else(|| presenter.get_xovers_list());
         let mut helix_map = HashMap::new();
         let mut intervals = Vec::with_capacity(helices.len());
         for (n, k) in keys.iter().enumerate() {
             helix_map.insert(*k, n);
             intervals.push(intervals_map.get(k).cloned());
         }
-        let roller = RollSystem::new(helices.len(), target_helices, &helix_map);
+        let roller = RollSystem::new(helices.len(), target_helices.clone(), &helix_map);
         let data = DesignData {
             helices,
             helix_map,
@@ -85,6 +91,7 @@ impl PhysicalSystem {
             data,
             roller,
             interface: Arc::downgrade(&interface),
+            target_helices,
         };
         system.run();
         interface
@@ -98,7 +105,8 @@ impl PhysicalSystem {
                 let grad = self.roller.solve_one_step(&mut self.data, 1e-3);
                 log::trace!("grad {}", grad);
                 interface_ptr.lock().unwrap().stabilized = grad < 0.1;
-                interface_ptr.lock().unwrap().new_state = Some(self.data.get_roll_state())
+                interface_ptr.lock().unwrap().new_state =
+                    Some(self.data.get_roll_state(self.target_helices.as_ref()))
             }
         });
     }
@@ -370,10 +378,14 @@ pub struct DesignData {
 }
 
 impl DesignData {
-    fn get_roll_state(&self) -> RollState {
+    /// The state of the helices. If `target_helices` is not `None`, only the target helices are
+    /// included so that the other helices can be modified while the simulation is running.
+    fn get_roll_state(&self, target_helices: Option<&Vec<usize>>) -> RollState {
         let mut ret = HashMap::new();
         for (k, n) in self.helix_map.iter() {
-            ret.insert(*k, self.helices[*n].clone());
+            if target_helices.map(|t| t.contains(k)).unwrap_or(true) {
+                ret.insert(*k, self.helices[*n].clone());
+            }
         }
         RollState(ret)
     }
@@ -21,10 +21,13 @@ use ensnano_design::{
     grid::*, Collection, CurveDescriptor, HelixCollection, HelixParameters, Twist,
 };
 
+use ensnano_interactor::TwistAxis;
+
 use super::roller::{DesignData, RollPresenter, RollSystem};
 use super::{Design, Helix, SimulationReader};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, Weak};
+use ultraviolet::Vec3;
 
 pub trait TwistPresenter: RollPresenter {}
 
@@ -46,11 +49,23 @@ const MIN_OMEGA: f64 = -0.2;
 const MAX_OMEGA: f64 = 0.2;
 const NB_STEP_OMEGA: usize = 300;
 
+/// The helices on which the twist is applied
+#[derive(Clone, Debug)]
+pub enum TwistTarget {
+    /// All the helices of a grid, the twist is a parameter of the grid
+    Grid(GridId),
+    /// Free helices, twisted around an arbitrary axis
+    FreeHelices {
+        helices: Vec<usize>,
+        axis: TwistAxis,
+    },
+}
+
 #[derive(Clone)]
 pub struct TwistState {
-    grid_id: GridId,
+    /// The twisted grid, `None` if free helices are being twisted
+    grid: Option<(GridId, GridDescriptor)>,
     helices: HashMap<usize, Helix>,
-    grid: GridDescriptor,
 }
 
 impl super::SimulationUpdate for TwistState {
@@ -60,13 +75,15 @@ impl super::SimulationUpdate for TwistState {
             new_helices.insert(*i, h.clone())
         }
 
-        let mut grids_mut = design.free_grids.make_mut();
-        if let Some(grid) =
-            FreeGridId::try_from_grid_id(self.grid_id).and_then(|g_id| grids_mut.get_mut(&g_id))
-        {
-            *grid = self.grid.clone()
-        } else {
-            log::error!("COULD NOT UPDATE GRID {:?}", self.grid_id)
+        if let Some((grid_id, grid_desc)) = self.grid.as_ref() {
+            let mut grids_mut = design.free_grids.make_mut();
+            if let Some(grid) =
+                FreeGridId::try_from_grid_id(*grid_id).and_then(|g_id| grids_mut.get_mut(&g_id))
+            {
+                *grid = grid_desc.clone()
+            } else {
+                log::error!("COULD NOT UPDATE GRID {:?}", grid_id)
+            }
         }
     }
 }
@@ -75,30 +92,54 @@ impl super::SimulationUpdate for TwistState {
 pub struct TwistInterface {
     pub new_state: Option<TwistState>,
     stabilized: bool,
+    /// The fraction of the tested twist values
+    progress: f32,
 }
 
 impl Twister {
     pub fn start_new(
         presenter: &dyn TwistPresenter,
-        target_grid: GridId,
+        target: TwistTarget,
         reader: &mut dyn SimulationReader,
     ) -> Option<Arc<Mutex<TwistInterface>>> {
         let intervals_map = presenter.get_design().strands.get_intervals();
-        let mut helices: Vec<Helix> = Vec::new();
-        let mut keys: Vec<usize> = Vec::new();
-        for (key, helix) in presenter.get_helices().iter().filter(|(_, h)| {
-            h.grid_position
-                .filter(|pos| pos.grid == target_grid)
-                .is_some()
-        }) {
-            keys.push(key.clone());
-            helices.push(helix.clone());
-        }
         let helix_parameters = presenter
             .get_design()
             .helix_parameters
             .clone()
             .unwrap_or_default();
+        let mut helices: Vec<Helix> = Vec::new();
+        let mut keys: Vec<usize> = Vec::new();
+        match &target {
+            TwistTarget::Grid(target_grid) => {
+                for (key, helix) in presenter.get_helices().iter().filter(|(_, h)| {
+                    h.grid_position
+                        .filter(|pos| pos.grid == *target_grid)
+                        .is_some()
+                }) {
+                    keys.push(key.clone());
+                    helices.push(helix.clone());
+                }
+            }
+            TwistTarget::FreeHelices {
+                helices: target_helices,
+                axis,
+            } => {
+                let all_helices = presenter.get_helices();
+                for h_id in target_helices.iter() {
+                    let helix = all_helices.get(h_id)?;
+                    if helix.grid_position.is_some() {
+                        log::error!("Helix {} is on a grid", h_id);
+                        return None;
+                    }
+                    keys.push(*h_id);
+                    helices.push(helix_with_twist_curve(helix, axis, &helix_parameters)?);
+                }
+                if keys.is_empty() {
+                    return None;
+                }
+            }
+        }
         let mut xovers = presenter.get_xovers_list();
         xovers.retain(|(n1, n2)| keys.contains(&n1.helix) && keys.contains(&n2.helix));
         let mut helix_map = HashMap::new();
@@ -125,22 +166,34 @@ impl Twister {
         let interface_dyn: Arc<Mutex<dyn super::SimulationInterface>> = interface.clone();
         reader.attach_state(&interface_dyn);
 
-        let initial_state = if let Some(grid) = FreeGridId::try_from_grid_id(target_grid)
-            .and_then(|target_grid| presenter.get_design().free_grids.get(&target_grid))
-        {
-            TwistState {
-                grid_id: target_grid,
-                grid: grid.clone(),
-                helices: presenter
-                    .get_design()
-                    .helices
+        let initial_state = match target {
+            TwistTarget::Grid(target_grid) => {
+                if let Some(grid) = FreeGridId::try_from_grid_id(target_grid)
+                    .and_then(|target_grid| presenter.get_design().free_grids.get(&target_grid))
+                {
+                    TwistState {
+                        grid: Some((target_grid, grid.clone())),
+                        helices: presenter
+                            .get_design()
+                            .helices
+                            .iter()
+                            .filter(|(k, _)| keys.contains(*k))
+                            .map(|(k, h)| (k.clone(), h.clone()))
+                            .collect(),
+                    }
+                } else {
+                    log::error!("Could not get grid {:?}", target_grid);
+                    return None;
+                }
+            }
+            TwistTarget::FreeHelices { .. } => TwistState {
+                grid: None,
+                helices: keys
                     .iter()
-                    .map(|(k, h)| (k.clone(), h.clone()))
+                    .cloned()
+                    .zip(data.helices.iter().cloned())
                     .collect(),
-            }
-        } else {
-            log::error!("Could not get grid {:?}", target_grid);
-            return None;
+            },
         };
 
         let twister = Self {
@@ -157,7 +210,14 @@ impl Twister {
 
 impl TwistState {
     fn set_twist(&mut self, twist: f64, helix_parameters: &HelixParameters) {
-        let omega = match &mut self.grid.grid_type {
+        let grid_type = if let Some((_, grid)) = self.grid.as_mut() {
+            &mut grid.grid_type
+        } else {
+            let omega = ensnano_design::nb_turn_per_100_nt_to_omega(twist, helix_parameters);
+            self.set_omega(omega);
+            return;
+        };
+        let omega = match grid_type {
             GridTypeDescr::Hyperboloid {
                 nb_turn_per_100_nt, ..
             } => {
@@ -173,7 +233,10 @@ impl TwistState {
                 ensnano_design::twist_to_omega(twist, helix_parameters)
             }
         };
+        self.set_omega(omega);
+    }
 
+    fn set_omega(&mut self, omega: Option<f64>) {
         if let Some(new_omega) = omega {
             for h in self.helices.values_mut() {
                 if let Some(CurveDescriptor::Twist(Twist { omega, .. })) =
@@ -190,6 +253,27 @@ impl TwistState {
     }
 }
 
+/// A copy of `helix` whose curve turns around `axis`. The twist of the curve is initially null.
+fn helix_with_twist_curve(
+    helix: &Helix,
+    axis: &TwistAxis,
+    helix_parameters: &HelixParameters,
+) -> Option<Helix> {
+    if !matches!(
+        helix.curve.as_ref().map(Arc::as_ref),
+        None | Some(CurveDescriptor::Twist(_))
+    ) {
+        log::error!("Cannot twist a helix that follows a curve");
+        return None;
+    }
+    let direction = Vec3::unit_x().rotated_by(helix.orientation);
+    let twist = Twist::around_axis(helix.position, direction, axis.origin, axis.direction, 0.0)?;
+    let mut ret = helix.clone();
+    ret.curve = Some(Arc::new(CurveDescriptor::Twist(twist)));
+    ret.try_update_curve(helix_parameters);
+    Some(ret)
+}
+
 impl Twister {
     fn evaluate_twist(&mut self, twist: f64) -> f64 {
         self.data.update_twist(twist);
@@ -219,6 +303,9 @@ impl Twister {
             while let Some(interface_ptr) = self.interface.upgrade() {
                 self.solve_one_step();
                 interface_ptr.lock().unwrap().stabilized = self.system.current_omega >= MAX_OMEGA;
+                interface_ptr.lock().unwrap().progress = ((self.system.current_omega - MIN_OMEGA)
+                    / (MAX_OMEGA - MIN_OMEGA))
+                    .min(1.) as f32;
                 interface_ptr.lock().unwrap().new_state = Some(self.state.clone());
             }
         });
@@ -234,6 +321,10 @@ impl super::SimulationInterface for TwistInterface {
     fn still_valid(&self) -> bool {
         !self.stabilized
     }
+
+    fn progress(&self) -> Option<f32> {
+        Some(self.progress)
+    }
 }
 
 impl DesignData {
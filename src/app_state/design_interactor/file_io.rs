@@ -0,0 +1,259 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Reading and writing design files in a background thread, so that the event loop stays
+//! responsive while large designs are loaded or saved.
+
+use super::binary_format::encode_design;
+use super::file_parsing::parse_design;
+use crate::controller::{LoadDesignError, SaveDesignError};
+use ensnano_design::Design;
+use ensnano_interactor::{consts::ENS_EXTENSION, DesignFileInfo};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::SystemTime;
+
+/// The size of the chunks in which files are read and written. The progress is reported and
+/// cancellation requests are checked after each chunk.
+const CHUNK_SIZE: usize = 1 << 16;
+
+pub enum FileIoResult {
+    Loaded {
+        path: PathBuf,
+        design: Result<Design, LoadDesignError>,
+    },
+    Saved {
+        path: PathBuf,
+        result: Result<(), SaveDesignError>,
+    },
+    /// The thread performing the task stopped before sending its result
+    Crashed { path: PathBuf },
+}
+
+/// A design file being read or written in a background thread.
+pub struct FileIoTask {
+    /// A description of the task, displayed next to its progress
+    pub name: String,
+    pub path: PathBuf,
+    pub progress: mpsc::Receiver<f32>,
+    pub result: mpsc::Receiver<FileIoResult>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl FileIoTask {
+    /// Ask the background thread to stop. The task still sends a result, which will be a
+    /// "cancelled" error if the request arrived early enough.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+}
+
+pub trait FileIoReader {
+    fn attach_file_io_task(&mut self, task: FileIoTask);
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Read and parse the design stored at `path` in a background thread.
+pub(super) fn spawn_loading(path: PathBuf) -> FileIoTask {
+    let (progress_snd, progress_rcv) = mpsc::channel();
+    let (result_snd, result_rcv) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = FileIoTask {
+        name: format!("Loading {}", file_name(&path)),
+        path: path.clone(),
+        progress: progress_rcv,
+        result: result_rcv,
+        cancelled: cancelled.clone(),
+    };
+    std::thread::spawn(move || {
+        let design = read_with_progress(&path, &progress_snd, &cancelled)
+            .and_then(|content| parse_design(&content, &path))
+            .and_then(|design| {
+                if cancelled.load(Ordering::Relaxed) {
+                    Err(LoadDesignError::Cancelled)
+                } else {
+                    Ok(design)
+                }
+            });
+        let _ = result_snd.send(FileIoResult::Loaded { path, design });
+    });
+    task
+}
+
+/// Read summaries of the design files of `directory` in a background thread.
+///
+/// The summaries are sent one by one, most recently modified file first. The channel is closed
+/// once all the files have been read.
+pub(super) fn spawn_directory_scan(directory: PathBuf) -> mpsc::Receiver<DesignFileInfo> {
+    let (snd, rcv) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut files: Vec<(PathBuf, Option<SystemTime>)> = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension() == Some(ENS_EXTENSION.as_ref()))
+                .map(|entry| {
+                    let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                    (entry.path(), modified)
+                })
+                .collect(),
+            Err(e) => {
+                log::error!("Could not read {}: {e}", directory.display());
+                Vec::new()
+            }
+        };
+        files.sort_by(|a, b| b.1.cmp(&a.1));
+        for (path, modified) in files {
+            if snd.send(read_design_file_info(path, modified)).is_err() {
+                // The dialog was closed
+                return;
+            }
+        }
+    });
+    rcv
+}
+
+fn read_design_file_info(path: PathBuf, modified: Option<SystemTime>) -> DesignFileInfo {
+    let modified = modified.map(|time| {
+        chrono::DateTime::<chrono::Local>::from(time)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    });
+    let design = std::fs::read(&path)
+        .map_err(LoadDesignError::from)
+        .and_then(|content| parse_design(&content, &path));
+    match design {
+        Ok(design) => DesignFileInfo {
+            modified,
+            nb_helices: design.helices.len(),
+            nb_strands: design.strands.len(),
+            nb_nucleotides: design.strands.values().map(|s| s.length()).sum(),
+            thumbnail_png: design.get_thumbnail_png(),
+            metadata: design.metadata.clone(),
+            error: None,
+            path,
+        },
+        Err(e) => DesignFileInfo {
+            modified,
+            nb_helices: 0,
+            nb_strands: 0,
+            nb_nucleotides: 0,
+            thumbnail_png: None,
+            metadata: Default::default(),
+            error: Some(e.to_string()),
+            path,
+        },
+    }
+}
+
+/// Read the content of the file, reporting progress between 0 and 0.5. The remaining half is
+/// spent parsing the design.
+fn read_with_progress(
+    path: &Path,
+    progress: &mpsc::Sender<f32>,
+    cancelled: &AtomicBool,
+) -> Result<Vec<u8>, LoadDesignError> {
+    let mut file = std::fs::File::open(path)?;
+    let total = file.metadata()?.len().max(1) as f32;
+    let mut content = Vec::new();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(LoadDesignError::Cancelled);
+        }
+        let nb_read = file.read(&mut buffer)?;
+        if nb_read == 0 {
+            break;
+        }
+        content.extend_from_slice(&buffer[..nb_read]);
+        let _ = progress.send(0.5 * (content.len() as f32 / total).min(1.));
+    }
+    Ok(content)
+}
+
+/// Serialize `design` and write it at `path` in a background thread. If `binary` is true, the
+/// design is written in binary format, otherwise it is written in json.
+///
+/// The file is first written next to its destination and then renamed, so that cancelling the
+/// task or failing to write does not corrupt a previous version of the file.
+pub(super) fn spawn_saving(path: PathBuf, design: Design, binary: bool) -> FileIoTask {
+    let (progress_snd, progress_rcv) = mpsc::channel();
+    let (result_snd, result_rcv) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = FileIoTask {
+        name: format!("Saving {}", file_name(&path)),
+        path: path.clone(),
+        progress: progress_rcv,
+        result: result_rcv,
+        cancelled: cancelled.clone(),
+    };
+    std::thread::spawn(move || {
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        let result = write_with_progress(&tmp_path, &design, binary, &progress_snd, &cancelled)
+            .and_then(|()| std::fs::rename(&tmp_path, &path).map_err(SaveDesignError::from));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        let _ = result_snd.send(FileIoResult::Saved { path, result });
+    });
+    task
+}
+
+/// Write the design, reporting progress between 0.5 and 1. The first half is spent serializing the
+/// design.
+fn write_with_progress(
+    path: &Path,
+    design: &Design,
+    binary: bool,
+    progress: &mpsc::Sender<f32>,
+    cancelled: &AtomicBool,
+) -> Result<(), SaveDesignError> {
+    let content = serialize_design(design, binary)?;
+    let total = content.len().max(1) as f32;
+    let _ = progress.send(0.5);
+    let mut file = std::fs::File::create(path)?;
+    let mut written = 0;
+    for chunk in content.chunks(CHUNK_SIZE) {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(SaveDesignError::cancelled());
+        }
+        file.write_all(chunk)?;
+        written += chunk.len();
+        let _ = progress.send(0.5 + 0.5 * written as f32 / total);
+    }
+    file.sync_all()?;
+    Ok(())
+}
+
+/// The content of a file storing `design` in binary format if `binary` is true, or in json
+/// otherwise.
+pub(super) fn serialize_design(design: &Design, binary: bool) -> Result<Vec<u8>, SaveDesignError> {
+    if binary {
+        encode_design(design)
+    } else {
+        Ok(serde_json::to_vec_pretty(design)?)
+    }
+}
@@ -16,6 +16,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use super::binary_format;
 use super::*;
 use crate::controller::LoadDesignError;
 use crate::utils::id_generator::IdGenerator;
@@ -31,10 +32,29 @@ impl DesignInteractor {
     /// * codenano
     /// * icednano
     pub fn new_with_path(json_path: &PathBuf) -> Result<Self, LoadDesignError> {
-        let mut xover_ids: IdGenerator<(Nucl, Nucl)> = Default::default();
-        let mut design = read_file(json_path)?;
+        let design = read_file(json_path)?;
         println!("Design read");
+        Ok(Self::from_loaded_design(design))
+    }
+
+    /// Start reading the design stored at `path` in a background thread. Once received by
+    /// `reader`, the design must be passed to `from_loaded_design`.
+    pub fn start_loading(path: PathBuf, reader: &mut dyn super::FileIoReader) {
+        reader.attach_file_io_task(super::file_io::spawn_loading(path))
+    }
+
+    /// Start reading summaries of the design files of `directory` in a background thread.
+    pub fn start_scanning_directory(
+        directory: PathBuf,
+    ) -> std::sync::mpsc::Receiver<ensnano_interactor::DesignFileInfo> {
+        super::file_io::spawn_directory_scan(directory)
+    }
+
+    /// Create a new data from a design that was read from a file
+    pub fn from_loaded_design(mut design: Design) -> Self {
+        let mut xover_ids: IdGenerator<(Nucl, Nucl)> = Default::default();
         design.strands.remove_empty_domains();
+        design.assign_stable_ids();
 
         /*
                 /// vvv added
@@ -51,54 +71,80 @@ impl DesignInteractor {
                 /// ^^^ Added
         */
 
-        for s in design.strands.values_mut() {
-            s.read_junctions(&mut xover_ids, true);
+        // Only the strands whose junctions are modified are given mutably, so that they are
+        // not copied needlessly.
+        let strand_ids: Vec<usize> = design.strands.keys().cloned().collect();
+        for s_id in strand_ids.iter() {
+            let missing_junctions = design
+                .strands
+                .get(s_id)
+                .map(|s| s.junctions.is_empty() && !s.domains.is_empty())
+                .unwrap_or(false);
+            if missing_junctions {
+                if let Some(s) = design.strands.get_mut(s_id) {
+                    s.init_junctions();
+                }
+            }
         }
-        for s in design.strands.values_mut() {
-            s.read_junctions(&mut xover_ids, false);
+        for identified in [true, false] {
+            for s_id in strand_ids.iter() {
+                let new_junctions = design
+                    .strands
+                    .get(s_id)
+                    .map(|s| s.identify_junctions(&mut xover_ids, identified))
+                    .unwrap_or_default();
+                if !new_junctions.is_empty() {
+                    if let Some(s) = design.strands.get_mut(s_id) {
+                        for (i, junction) in new_junctions {
+                            s.junctions[i] = junction;
+                        }
+                    }
+                }
+            }
         }
         //let file_name = real_name(json_path);
         let suggestion_parameters = SuggestionParameters::default();
         let (presenter, design_ptr) =
             Presenter::from_new_design(design, &xover_ids, suggestion_parameters);
-        let ret = Self {
+        Self {
             design: design_ptr,
             presenter: AddressPointer::new(presenter),
             ..Default::default()
-        };
-        Ok(ret)
+        }
     }
 }
 
 /// Create a design by parsing a file
 use cadnano::{Cadnano, FromCadnano};
 fn read_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> Result<Design, LoadDesignError> {
-    let json_str =
-        std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("File not found {:?}", path));
+    let content = std::fs::read(&path).unwrap_or_else(|_| panic!("File not found {:?}", path));
+    parse_design(&content, path)
+}
 
-    let design: Result<Design, _> = serde_json::from_str(&json_str);
+/// Create a design from the content of a file, trying all the supported formats
+pub(super) fn parse_design<P: AsRef<Path> + std::fmt::Debug>(
+    content: &[u8],
+    path: P,
+) -> Result<Design, LoadDesignError> {
+    if binary_format::is_binary_design(content) {
+        log::info!("ok binary");
+        return binary_format::decode_design(content).and_then(check_version);
+    }
+    let json_str = std::str::from_utf8(content)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let design: Result<Design, _> = serde_json::from_str(json_str);
     // First try to read icednano format
     match design {
-        Ok(mut design) => {
-            design.update_version();
-            use version_compare::Cmp;
+        Ok(design) => {
             log::info!("ok icednano");
-            let required_version = design.ensnano_version.clone();
-            let current_version = ensnano_design::ensnano_version();
-            match version_compare::compare(&required_version, &current_version) {
-                Ok(Cmp::Lt) | Ok(Cmp::Eq) => Ok(design),
-                _ => Err(LoadDesignError::IncompatibleVersion {
-                    current: current_version,
-                    required: required_version,
-                }),
-            }
+            check_version(design)
         }
         Err(e) => {
             // If the file is not in icednano format, try the other supported format
-            let cdn_design: Result<codenano::Design<(), ()>, _> = serde_json::from_str(&json_str);
+            let cdn_design: Result<codenano::Design<(), ()>, _> = serde_json::from_str(json_str);
 
             let scadnano_design: Result<scadnano::ScadnanoDesign, _> =
-                serde_json::from_str(&json_str);
+                serde_json::from_str(json_str);
 
             // Try codenano format
             if let Ok(scadnano) = scadnano_design {
@@ -121,6 +167,22 @@ fn read_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> Result<Design, LoadDe
     }
 }
 
+/// Update the design to the current version of the data structures, or return an error if the
+/// design was created by a more recent version of ENSnano.
+fn check_version(mut design: Design) -> Result<Design, LoadDesignError> {
+    use version_compare::Cmp;
+    design.update_version();
+    let required_version = design.ensnano_version.clone();
+    let current_version = ensnano_design::ensnano_version();
+    match version_compare::compare(&required_version, &current_version) {
+        Ok(Cmp::Lt) | Ok(Cmp::Eq) => Ok(design),
+        _ => Err(LoadDesignError::IncompatibleVersion {
+            current: current_version,
+            required: required_version,
+        }),
+    }
+}
+
 use scadnano::ScadnanoImportError;
 
 impl std::convert::From<ScadnanoImportError> for LoadDesignError {
@@ -129,6 +191,18 @@ impl std::convert::From<ScadnanoImportError> for LoadDesignError {
     }
 }
 
+impl std::convert::From<rmp_serde::decode::Error> for LoadDesignError {
+    fn from(error: rmp_serde::decode::Error) -> Self {
+        Self::BinaryFormatError(error)
+    }
+}
+
+impl std::convert::From<std::io::Error> for LoadDesignError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ensnano_design::HelixCollection;
@@ -149,4 +223,38 @@ mod tests {
         let design = interactor.design.as_ref();
         assert_eq!(design.helices.len(), 1);
     }
+
+    #[test]
+    fn binary_design_is_detected() {
+        let path = one_helix_path();
+        let design = read_file(&path).ok().unwrap();
+        let content = binary_format::encode_design(&design).ok().unwrap();
+        assert!(binary_format::is_binary_design(&content));
+        let decoded = parse_design(&content, &path).ok().unwrap();
+        assert_eq!(decoded.helices.len(), 1);
+        assert_eq!(decoded.strands.len(), design.strands.len());
+    }
+
+    #[derive(Default)]
+    struct DummyReader(Option<FileIoTask>);
+
+    impl FileIoReader for DummyReader {
+        fn attach_file_io_task(&mut self, task: FileIoTask) {
+            self.0 = Some(task)
+        }
+    }
+
+    #[test]
+    fn parse_one_helix_in_background() {
+        let mut reader = DummyReader::default();
+        DesignInteractor::start_loading(one_helix_path(), &mut reader);
+        let task = reader.0.expect("No task attached");
+        match task.result.recv().unwrap() {
+            FileIoResult::Loaded { design, .. } => {
+                let interactor = DesignInteractor::from_loaded_design(design.ok().unwrap());
+                assert_eq!(interactor.design.as_ref().helices.len(), 1);
+            }
+            _ => panic!("Expected a loaded design"),
+        }
+    }
 }
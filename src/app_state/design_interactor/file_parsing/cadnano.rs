@@ -137,6 +137,10 @@ fn make_strand(
         is_cyclic: cyclic,
         color: crate::consts::SCAFFOLD_COLOR,
         name: None,
+        uuid: None,
+        pool: None,
+        note: None,
+        url: None,
     };
 
     let mut insertions = Vec::new();
@@ -29,19 +29,48 @@ pub trait StrandJunction {
     /// * Its length is equal to self.domains.length
     /// * All the junctions are appropriate.
     fn read_junctions(&mut self, xover_ids: &mut IdGenerator<(Nucl, Nucl)>, identified: bool);
+
+    /// Infer the junctions of self from its domains. This must be done before calling
+    /// `identify_junctions` if self.junctions is empty.
+    fn init_junctions(&mut self);
+
+    /// Same as `read_junctions`, but instead of modifying self, return the indices of the
+    /// junctions that must be modified together with their new value.
+    ///
+    /// Assumes that self.junctions is not empty if self.domains is not empty.
+    fn identify_junctions(
+        &self,
+        xover_ids: &mut IdGenerator<(Nucl, Nucl)>,
+        identified: bool,
+    ) -> Vec<(usize, DomainJunction)>;
 }
 
 impl StrandJunction for Strand {
     fn read_junctions(&mut self, xover_ids: &mut IdGenerator<(Nucl, Nucl)>, identified: bool) {
         //TODO check validity of self.junctions
         if self.junctions.is_empty() {
-            let sane_domains = sanitize_domains(&self.domains, self.is_cyclic);
-            self.domains = sane_domains;
-            let junctions = read_junctions(&self.domains, self.is_cyclic);
-            self.junctions = junctions;
+            self.init_junctions();
+        }
+        for (i, junction) in self.identify_junctions(xover_ids, identified) {
+            self.junctions[i] = junction;
         }
+    }
+
+    fn init_junctions(&mut self) {
+        let sane_domains = sanitize_domains(&self.domains, self.is_cyclic);
+        self.domains = sane_domains;
+        let junctions = read_junctions(&self.domains, self.is_cyclic);
+        self.junctions = junctions;
+    }
+
+    fn identify_junctions(
+        &self,
+        xover_ids: &mut IdGenerator<(Nucl, Nucl)>,
+        identified: bool,
+    ) -> Vec<(usize, DomainJunction)> {
+        let mut ret = Vec::new();
         if self.domains.is_empty() {
-            return;
+            return ret;
         }
         let mut previous_domain = self.domains.last().unwrap();
         for i in 0..(self.domains.len()) {
@@ -55,22 +84,22 @@ impl StrandJunction for Strand {
             } else {
                 &self.domains[i + 1]
             };
-            match &mut self.junctions[i] {
-                s @ DomainJunction::UnindentifiedXover => {
+            match &self.junctions[i] {
+                DomainJunction::UnindentifiedXover => {
                     if !identified {
                         if let (Domain::HelixDomain(d1), Domain::HelixDomain(d2)) = (current, next)
                         {
                             let prime5 = d1.prime3();
                             let prime3 = d2.prime5();
                             let id = xover_ids.insert((prime5, prime3));
-                            *s = DomainJunction::IdentifiedXover(id);
+                            ret.push((i, DomainJunction::IdentifiedXover(id)));
                         } else if let (Domain::HelixDomain(d1), Domain::HelixDomain(d2)) =
                             (previous_domain, next)
                         {
                             let prime5 = d1.prime3();
                             let prime3 = d2.prime5();
                             let id = xover_ids.insert((prime5, prime3));
-                            *s = DomainJunction::IdentifiedXover(id);
+                            ret.push((i, DomainJunction::IdentifiedXover(id)));
                         } else if let Domain::Insertion { .. } = next {
                             panic!("UnindentifiedXover before an insertion");
                         } else if let Domain::Insertion { .. } = previous_domain {
@@ -108,6 +137,7 @@ impl StrandJunction for Strand {
                 previous_domain = current;
             }
         }
+        ret
     }
 }
 
@@ -25,8 +25,8 @@ use ensnano_design::{
 };
 
 use ensnano_interactor::{
-    application::Camera3D, NeighbourDescriptor, NeighbourDescriptorGiver, ScaffoldInfo, Selection,
-    SuggestionParameters,
+    application::Camera3D, DesignStatistics, NeighbourDescriptor, NeighbourDescriptorGiver,
+    ScaffoldInfo, Selection, SuggestionParameters,
 };
 
 use ultraviolet::Mat4;
@@ -34,12 +34,16 @@ use ultraviolet::Mat4;
 use crate::utils::id_generator::IdGenerator;
 type JunctionsIds = IdGenerator<(Nucl, Nucl)>;
 pub mod design_content;
+#[cfg(feature = "http_api")]
+mod impl_http_api;
 mod impl_main_reader;
+mod impl_plugin;
 mod impl_reader2d;
 mod impl_reader3d;
 mod impl_readergui;
 use crate::scene::{HBond, HalfHBond};
 use design_content::DesignContent;
+use ensnano_exports::nucleotides::NucleotideRecord;
 use std::collections::{BTreeMap, HashSet};
 
 #[derive(Clone)]
@@ -61,6 +65,8 @@ pub(super) struct Presenter {
     visibility_sieve: Option<VisibilitySieve>,
     invisible_nucls: HashSet<Nucl>,
     h_bonds: AddressPointer<Vec<HBond>>,
+    /// Counts of the elements of `current_design`, recomputed only when the design is read.
+    design_statistics: DesignStatistics,
 }
 
 impl Default for Presenter {
@@ -74,6 +80,7 @@ impl Default for Presenter {
             visibility_sieve: None,
             invisible_nucls: Default::default(),
             h_bonds: Default::default(),
+            design_statistics: Default::default(),
         }
     }
 }
@@ -126,7 +133,8 @@ impl Presenter {
         log::info!("new design presenter");
         let model_matrix = Mat4::identity();
         let (content, design, junctions_ids) =
-            DesignContent::make_hash_maps(design, old_junctions_ids, &suggestion_parameters);
+            DesignContent::make_hash_maps(design, old_junctions_ids, &suggestion_parameters, None);
+        let design_statistics = compute_design_statistics(&design);
         let design = AddressPointer::new(design);
         let mut ret = Self {
             current_design: design.clone(),
@@ -137,6 +145,7 @@ impl Presenter {
             visibility_sieve: None,
             invisible_nucls: Default::default(),
             h_bonds: Default::default(),
+            design_statistics,
         };
         // Strand sequence are not read
         ret.read_scaffold_seq();
@@ -163,7 +172,9 @@ impl Presenter {
             design.clone_inner(),
             self.junctions_ids.as_ref(),
             suggestion_parameters,
+            Some(self.content.as_ref()),
         );
+        self.design_statistics = compute_design_statistics(&new_design);
         self.current_design = AddressPointer::new(new_design);
         log::trace!("Presenter design <- {:p}", self.current_design);
         self.content = AddressPointer::new(content);
@@ -450,6 +461,23 @@ impl Presenter {
         self.update_visibility();
     }
 
+    /// Hide exactly the nucleotides of `invisible_nucls`.
+    pub fn set_invisible_nucls(&mut self, invisible_nucls: HashSet<Nucl>) {
+        if invisible_nucls.is_empty() {
+            self.visibility_sieve = None;
+        } else {
+            // A sieve with an empty selection preserves the visibility of all nucleotides when
+            // the design is updated.
+            self.visibility_sieve = Some(VisibilitySieve {
+                selection: vec![],
+                compl: false,
+                visible: true,
+            });
+        }
+        self.invisible_nucls = invisible_nucls;
+        self.update_visibility();
+    }
+
     pub fn get_checked_xovers_ids(&self) -> Vec<u32> {
         self.current_design
             .checked_xovers
@@ -510,12 +538,48 @@ impl Presenter {
     }
 
     pub fn export(&self, export_path: &PathBuf, export_type: ExportType) -> ExportResult {
-        ensnano_exports::export(
-            &self.current_design,
-            export_type,
-            Some(self.content.letter_map.as_ref()),
-            export_path,
-        )
+        match export_type {
+            ExportType::NucleotidesCsv | ExportType::NucleotidesJson => {
+                ensnano_exports::nucleotides::export_nucleotides(
+                    &self.get_nucleotide_records(),
+                    export_type,
+                    export_path,
+                )
+            }
+            _ => ensnano_exports::export(
+                &self.current_design,
+                export_type,
+                Some(self.content.letter_map.as_ref()),
+                export_path,
+            ),
+        }
+    }
+
+    /// Collect the data of each nucleotide of the design, sorted by identifier.
+    fn get_nucleotide_records(&self) -> Vec<NucleotideRecord> {
+        let content = self.content.as_ref();
+        let mut ret: Vec<NucleotideRecord> = content
+            .nucleotide
+            .iter()
+            .filter_map(|(id, nucl)| {
+                Some(NucleotideRecord {
+                    id: *id,
+                    strand: *content.strand_map.get(id)?,
+                    helix: nucl.helix,
+                    position: nucl.position,
+                    forward: nucl.forward,
+                    space_position: *content.space_position.get(id)?,
+                    color: content.color_map.get(id).cloned().unwrap_or_default(),
+                    base: content.letter_map.get(nucl).cloned(),
+                    paired_with: content
+                        .nucl_collection
+                        .get_identifier(&nucl.compl())
+                        .cloned(),
+                })
+            })
+            .collect();
+        ret.sort_by_key(|r| r.id);
+        ret
     }
 
     pub fn get_bezier_path_2d(&self, path_id: BezierPathId) -> Option<InstanciatedPiecewiseBezier> {
@@ -527,6 +591,20 @@ impl Presenter {
     }
 }
 
+fn compute_design_statistics(design: &Design) -> DesignStatistics {
+    let scaffold_id = design.scaffold_id;
+    DesignStatistics {
+        nb_helices: design.helices.len(),
+        nb_strands: design.strands.len(),
+        nb_staples: design
+            .strands
+            .keys()
+            .filter(|s_id| Some(**s_id) != scaffold_id)
+            .count(),
+        nb_nucleotides: design.strands.values().map(|s| s.length()).sum(),
+    }
+}
+
 pub(super) fn design_need_update(
     presenter: &AddressPointer<Presenter>,
     design: &AddressPointer<Design>,
@@ -700,6 +778,19 @@ impl DesignReader {
         })
     }
 
+    pub fn get_nb_grids(&self) -> usize {
+        self.presenter.current_design.free_grids.len()
+    }
+
+    /// The design that is currently edited.
+    pub fn get_design(&self) -> &Design {
+        self.presenter.current_design.as_ref()
+    }
+
+    pub fn get_design_statistics(&self) -> DesignStatistics {
+        self.presenter.design_statistics
+    }
+
     pub fn get_camera_with_id(&self, cam_id: ensnano_design::CameraId) -> Option<Camera3D> {
         self.presenter
             .current_design
@@ -712,6 +803,44 @@ impl DesignReader {
             })
     }
 
+    pub fn get_nb_presentation_steps(&self) -> usize {
+        self.presenter.current_design.presentation_steps.len()
+    }
+
+    /// Return the camera and the set of hidden nucleotides of the `n`-th step of the presentation
+    pub fn get_presentation_step(&self, n: usize) -> Option<(Camera3D, HashSet<Nucl>)> {
+        self.presenter
+            .current_design
+            .presentation_steps
+            .get(n)
+            .map(|step| {
+                (
+                    Camera3D {
+                        position: step.camera_position,
+                        orientation: step.camera_orientation,
+                        pivot_position: step.pivot_position,
+                    },
+                    step.hidden_nucls.iter().cloned().collect(),
+                )
+            })
+    }
+
+    pub fn get_invisible_nucls(&self) -> Vec<Nucl> {
+        self.presenter.invisible_nucls.iter().cloned().collect()
+    }
+
+    /// Return the identifiers of the strands containing at least one of `nucls`, without
+    /// repetition.
+    pub fn get_ids_of_strands_containing_nucls(&self, nucls: &[Nucl]) -> Vec<usize> {
+        let mut ret: Vec<usize> = nucls
+            .iter()
+            .filter_map(|n| self.get_id_of_strand_containing_nucl(n))
+            .collect();
+        ret.sort_unstable();
+        ret.dedup();
+        ret
+    }
+
     pub fn get_nth_camera(&self, n: u32) -> Option<Camera3D> {
         self.presenter
             .current_design
@@ -22,13 +22,15 @@ use ahash::RandomState;
 use cadnano_format::color;
 use ensnano_design::drawing_style::{ColorType, DrawingAttribute, DrawingStyle};
 use ensnano_design::elements::{DesignElement, DesignElementKey};
-use ensnano_design::grid::{GridId, GridObject, GridPosition, HelixGridPosition};
+use ensnano_design::grid::{
+    GridId, GridObject, GridPosition, HelixGridPosition, PhantomParameters,
+};
 use ensnano_design::*;
 use ensnano_interactor::consts::{
     BOND_RADIUS, CLONE_OPACITY, HELIX_CYLINDER_COLOR, HELIX_CYLINDER_RADIUS, SPHERE_RADIUS,
 };
 use ensnano_interactor::{
-    graphics::{LoopoutBond, LoopoutNucl},
+    graphics::{LoopoutBond, LoopoutNucl, StrandEnd},
     ObjectType,
 };
 use ensnano_utils::clic_counter::ClicCounter;
@@ -132,6 +134,8 @@ pub(super) struct DesignContent {
     pub helix_color_map: HashMap<u32, u32, RandomState>,
     pub letter_map: Arc<HashMap<Nucl, char, RandomState>>,
     pub prime3_set: Vec<Prime3End>,
+    /// The ends of the non-cyclic strands, on which the end glyphs are drawn
+    pub strand_ends: Vec<StrandEnd>,
     pub elements: Vec<DesignElement>,
     pub suggestions: Vec<(Nucl, Nucl)>,
     pub(super) grid_manager: GridData,
@@ -145,8 +149,14 @@ pub(super) struct DesignContent {
     pub xover_coloring_map: HashMap<u32, bool, RandomState>,
     pub clone_transformations: Vec<Isometry3>,
     pub with_cones_map: HashMap<u32, bool, RandomState>,
+    /// Maps bonds identifier to the cone spacing required by their drawing style (if any)
+    pub cone_spacing_map: HashMap<u32, usize, RandomState>,
+    /// Maps bonds identifier to their rank along their strand
+    pub bond_rank_map: HashMap<u32, usize, RandomState>,
     // min value, max value and rainow function(t, min, max)->color
     pub scalebar: Option<(f32, f32, fn(f32, f32, f32) -> u32)>,
+    /// Kept from one version of the design to the next to update the suggestions incrementally
+    suggestion_maker: XoverSuggestions,
 }
 
 impl DesignContent {
@@ -231,14 +241,22 @@ impl DesignContent {
             .map(|obj| obj.helix())
     }
 
-    pub(super) fn get_persistent_phantom_helices_id(&self) -> HashSet<u32> {
+    pub(super) fn get_persistent_phantom_helices_id(&self) -> HashMap<u32, PhantomParameters> {
         self.grid_manager.get_persistent_phantom_helices_id()
     }
 
+    pub(super) fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters {
+        self.grid_manager.get_phantom_parameters(g_id)
+    }
+
     pub(super) fn grid_has_small_spheres(&self, g_id: GridId) -> bool {
         self.grid_manager.small_spheres.contains(&g_id)
     }
 
+    pub(super) fn grid_has_labels(&self, g_id: GridId) -> bool {
+        self.grid_manager.labelled_grids.contains(&g_id)
+    }
+
     pub(super) fn grid_has_persistent_phantom(&self, g_id: GridId) -> bool {
         !self.grid_manager.no_phantoms.contains(&g_id)
     }
@@ -370,9 +388,9 @@ impl DesignContent {
             let key = if let Some((prim5, prim3)) = strand.get_5prime().zip(strand.get_3prime()) {
                 (
                     group_names,
-                    prim5.helix,
+                    design.helices.display_number(prim5.helix),
                     prim5.position,
-                    prim3.helix,
+                    design.helices.display_number(prim3.helix),
                     prim3.position,
                 )
             } else {
@@ -390,6 +408,10 @@ impl DesignContent {
                     color: strand.color & 0xFFFFFF,
                     group_names: presenter.get_name_of_group_having_strand(*s_id),
                     intervals,
+                    uuid: strand.uuid,
+                    pool: strand.pool.clone(),
+                    note: strand.note.clone(),
+                    url: strand.url.clone(),
                 },
             );
         }
@@ -432,6 +454,20 @@ impl DesignContent {
                     .map(|split| split.1.to_string())
                     .unwrap_or(staple_info.domain_decomposition.clone()),
                 intervals: staple_info.intervals.clone(),
+                uuid: staple_info.uuid,
+                pool_name: staple_info
+                    .pool
+                    .as_ref()
+                    .map(|pool| pool.name.clone())
+                    .unwrap_or_default(),
+                concentration_str: staple_info
+                    .pool
+                    .as_ref()
+                    .and_then(|pool| pool.concentration)
+                    .map(|c| c.to_string())
+                    .unwrap_or_default(),
+                note: staple_info.note.clone().unwrap_or_default(),
+                url: staple_info.url.clone().unwrap_or_default(),
             });
         }
         ret
@@ -512,6 +548,16 @@ pub struct Staple {
     pub domain_decomposition: String,
     pub length_str: String,
     pub intervals: StapleIntervals,
+    /// The stable identifier of the strand
+    pub uuid: Option<Uuid>,
+    /// The name of the oligo pool of the staple, empty if it has none
+    pub pool_name: String,
+    /// The concentration of the staple in its pool, in nanomolars, empty if it is not set
+    pub concentration_str: String,
+    /// The note attached to the staple, empty if it has none
+    pub note: String,
+    /// The link to an external description of the staple, empty if it has none
+    pub url: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -529,6 +575,10 @@ struct StapleInfo {
     domain_decomposition: String,
     length: usize,
     intervals: StapleIntervals,
+    uuid: Option<Uuid>,
+    pool: Option<OligoPool>,
+    note: Option<String>,
+    url: Option<String>,
 }
 
 #[derive(Clone)]
@@ -539,10 +589,14 @@ pub struct Prime3End {
 
 impl DesignContent {
     /// Update all the hash maps - called after every edit operation
+    ///
+    /// The content of the previous version of the design, if any, is used to avoid recomputing
+    /// the crossover suggestions from scratch.
     pub(super) fn make_hash_maps(
         mut design: Design,
         xover_ids: &JunctionsIds,
         suggestion_parameters: &SuggestionParameters,
+        previous_content: Option<&Self>,
     ) -> (Self, Design, JunctionsIds) {
         let groups = design.groups.clone();
         let mut object_type = HashMap::default();
@@ -561,6 +615,9 @@ impl DesignContent {
         let mut helix_map = HashMap::default();
         let mut letter_map = HashMap::default();
         let mut with_cones_map = HashMap::default();
+        let mut cone_spacing_map = HashMap::default();
+        let mut bond_rank_map = HashMap::default();
+        let mut strand_ends = Vec::new();
         let mut loopout_bonds = Vec::new();
         let mut loopout_nucls = Vec::new();
         let mut id_TMP = 0u32;
@@ -571,7 +628,9 @@ impl DesignContent {
         let mut elements = Vec::new();
         let mut prime3_set = Vec::new();
         let mut new_junctions: JunctionsIds = Default::default();
-        let mut suggestion_maker = XoverSuggestions::default();
+        let mut suggestion_maker = previous_content
+            .map(|c| c.suggestion_maker.begin_update())
+            .unwrap_or_default();
         let mut insertion_length = HashMap::default();
         let mut drawing_styles = HashMap::default();
         let mut xover_coloring_map = HashMap::default();
@@ -631,14 +690,29 @@ impl DesignContent {
         }
 
         // Scanning strands
-        for (s_id, strand) in design.strands.iter_mut() {
+        // Strands are only copied if their insertions or their junctions must be updated, so that
+        // the other ones stay shared with the previous versions of the design.
+        let parameters = design.helix_parameters.unwrap_or_default();
+        let strand_ids: Vec<usize> = design.strands.keys().cloned().collect();
+        let mut updated_junctions = Vec::new();
+        for s_id in strand_ids.iter() {
+            let insertions_need_update = design
+                .strands
+                .get(s_id)
+                .map(|s| s.insertions_need_update(&design.helices, &parameters))
+                .unwrap_or(false);
+            if insertions_need_update {
+                if let Some(strand) = design.strands.get_mut(s_id) {
+                    strand.update_insertions(&design.helices, &parameters);
+                }
+            }
+            let strand = design.strands.get(s_id).unwrap();
+            let mut junctions = strand.junctions.clone();
             elements.push(elements::DesignElement::StrandElement {
                 id: *s_id, // the key in design.strands btreemap
                 length: strand.length(),
                 domain_lengths: strand.domain_lengths(),
             });
-            let parameters = design.helix_parameters.unwrap_or_default();
-            strand.update_insertions(&design.helices, &parameters);
             let mut strand_position = 0;
             let strand_seq = strand.sequence.as_ref().filter(|s| s.is_ascii());
             let strand_color = strand.color;
@@ -677,6 +751,7 @@ impl DesignContent {
             let mut last_xover_junction: Option<&mut DomainJunction> = None;
             let mut prev_loopout_pos = None;
             let mut prev_style = strand_style; // style of the previous domain, only used for cyclic strand outside the domain loop
+            let mut first_style: Option<DrawingStyle> = None; // style of the first domain, used for the 5' end glyph
             let bond_coloring = strand_style.xover_coloring.unwrap_or(true);
 
             let strand_on_axis = strand_style.on_axis.unwrap_or(false);
@@ -730,6 +805,7 @@ impl DesignContent {
                     let bond_radius = domain_style.bond_radius.unwrap_or(BOND_RADIUS);
                     let nucl_radius = domain_style.sphere_radius.unwrap_or(SPHERE_RADIUS);
                     prev_style = domain_style;
+                    first_style.get_or_insert(domain_style);
                     // Get the sequence if any
                     let dom_seq = domain.sequence.as_ref().filter(|s| s.is_ascii());
 
@@ -788,6 +864,7 @@ impl DesignContent {
                             let bond = (prev_nucl, nucl);
                             object_type
                                 .insert(bond_id, ObjectType::Bond(prev_nucl_id.unwrap(), id_TMP)); // To be overwritten by a sliced bond later
+                            bond_rank_map.insert(bond_id, bond_ids_sequence.len());
                             bond_ids_sequence.push(bond_id);
                             identifier_bond.insert(bond, bond_id);
                             nucleotides_involved.insert(bond_id, bond);
@@ -799,6 +876,9 @@ impl DesignContent {
                             if Some(false) == strand_style.with_cones {
                                 with_cones_map.insert(bond_id, false);
                             }
+                            if let Some(spacing) = domain_style.cone_spacing {
+                                cone_spacing_map.insert(bond_id, spacing);
+                            }
                             id_TMP
                         } else {
                             id_TMP
@@ -826,7 +906,7 @@ impl DesignContent {
                             letter_map.remove(&nucl);
                         }
                         strand_position += 1;
-                        suggestion_maker.add_nucl(nucl, position, groups.as_ref());
+                        suggestion_maker.add_nucl(nucl, position);
                         let position = [position[0] as f32, position[1] as f32, position[2] as f32];
                         space_position.insert(nucl_id, position);
                         axis_space_position.insert(nucl_id, axis_position);
@@ -836,10 +916,10 @@ impl DesignContent {
                         prev_nucl = Some(nucl);
                         prev_nucl_id = Some(nucl_id);
                     }
-                    if strand.junctions.len() <= i {
-                        log::debug!("{:?}", strand.junctions);
+                    if junctions.len() <= i {
+                        log::debug!("{:?}", junctions);
                     }
-                    last_xover_junction = Some(&mut strand.junctions[i]);
+                    last_xover_junction = Some(&mut junctions[i]);
                 } else if let Domain::Insertion {
                     nb_nucl,
                     instanciation,
@@ -880,7 +960,7 @@ impl DesignContent {
                         }
                     }
                     insertion_length.insert(id_TMP, *nb_nucl);
-                    last_xover_junction = Some(&mut strand.junctions[i]);
+                    last_xover_junction = Some(&mut junctions[i]);
                 }
             }
             if strand.is_cyclic {
@@ -900,6 +980,7 @@ impl DesignContent {
                 id_TMP += 1;
                 let bond = (prev_nucl.unwrap(), nucl);
                 object_type.insert(bond_id, ObjectType::Bond(prev_nucl_id.unwrap(), *prime5_id)); // to be overwritten by a sliced bond later
+                bond_rank_map.insert(bond_id, bond_ids_sequence.len());
                 bond_ids_sequence.push(bond_id);
                 identifier_bond.insert(bond, bond_id);
                 nucleotides_involved.insert(bond_id, bond);
@@ -911,12 +992,14 @@ impl DesignContent {
                 if Some(false) == strand_style.with_cones {
                     with_cones_map.insert(bond_id, false);
                 }
+                if let Some(spacing) = prev_style.cone_spacing {
+                    cone_spacing_map.insert(bond_id, spacing);
+                }
 
                 log::debug!("adding {:?}, {:?}", bond.0, bond.1);
                 Self::update_junction(
                     &mut new_junctions,
-                    strand
-                        .junctions
+                    junctions
                         .last_mut()
                         .expect("Broke Invariant [LastXoverJunction]"),
                     (bond.0, bond.1),
@@ -1009,12 +1092,33 @@ impl DesignContent {
                         unreachable!("The sliced bond is not a sliced bond");
                     };
                     object_type.insert(last_id, ObjectType::SlicedBond(*prev_id, *id1, *id2, *id2));
+
+                    strand_ends.push(StrandEnd {
+                        nucl_id: nucl1_ids[0],
+                        neighbour_id: nucl2_ids[0],
+                        prime5: true,
+                        glyph: first_style.unwrap_or(strand_style).prime5_glyph,
+                    });
+                    strand_ends.push(StrandEnd {
+                        nucl_id: nucl2_ids[n - 1],
+                        neighbour_id: nucl1_ids[n - 1],
+                        prime5: false,
+                        glyph: prev_style.prime3_glyph,
+                    });
                 }
             }
+            if junctions != strand.junctions {
+                updated_junctions.push((*s_id, junctions));
+            }
             // next iteration
             prev_nucl = None;
             prev_nucl_id = None;
         } // Scanning strands
+        for (s_id, junctions) in updated_junctions {
+            if let Some(strand) = design.strands.get_mut(&s_id) {
+                strand.junctions = junctions;
+            }
+        }
 
         // Scanning grids
         for g_id in grid_manager.grids.keys() {
@@ -1287,6 +1391,12 @@ impl DesignContent {
                     if let Some(wc) = with_cones_map.get(&bond_id) {
                         with_cones_map.insert(clone_bond_id, *wc);
                     }
+                    if let Some(spacing) = cone_spacing_map.get(&bond_id) {
+                        cone_spacing_map.insert(clone_bond_id, *spacing);
+                    }
+                    if let Some(rank) = bond_rank_map.get(&bond_id) {
+                        bond_rank_map.insert(clone_bond_id, *rank);
+                    }
                 }
                 // Cloned cylinders
                 for bond_id in &helix_cylinders {
@@ -1329,6 +1439,7 @@ impl DesignContent {
             helix_color_map,
             letter_map: Arc::new(letter_map),
             prime3_set,
+            strand_ends,
             elements,
             grid_manager,
             suggestions: vec![],
@@ -1339,10 +1450,14 @@ impl DesignContent {
             xover_coloring_map,
             clone_transformations,
             with_cones_map,
+            cone_spacing_map,
+            bond_rank_map,
             scalebar,
+            suggestion_maker: Default::default(),
         };
         let suggestions = suggestion_maker.get_suggestions(&design, suggestion_parameters);
         ret.suggestions = suggestions;
+        ret.suggestion_maker = suggestion_maker;
 
         drop(groups);
 
@@ -1522,6 +1637,7 @@ impl GridInstancesMaker for GridData {
                 id: *g_id,
                 fake: false,
                 visible: !g.invisible,
+                labelled: self.labelled_grids.contains(g_id),
             };
             ret.insert(*g_id, grid);
         }
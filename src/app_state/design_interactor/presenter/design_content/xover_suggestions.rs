@@ -16,83 +16,156 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use super::{Design, Nucl, SuggestionParameters};
+use super::{Design, HelixCollection, Nucl, SuggestionParameters};
 use ahash::RandomState;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use ensnano_utils::spatial_index::SpatialIndex;
+use std::collections::{HashMap, HashSet};
 use ultraviolet::Vec3;
 
-type CubeMap = HashMap<(isize, isize, isize), Vec<Nucl>, RandomState>;
+const LEN_CRIT: f32 = SuggestionParameters::LARGEST_MAX_DISTANCE;
+/// When a larger proportion of the nucleotides moved, the neighbourhoods are recomputed from
+/// scratch instead of being updated.
+const MAX_INCREMENTAL_RATIO: f32 = 0.5;
 
-const LEN_CRIT: f32 = 1.2;
-
-#[derive(Default, Debug, Clone)]
+/// Computes the suggested crossovers of a design.
+///
+/// The pairs of close nucleotides are remembered from one version of the design to the next so
+/// that only the neighbourhoods of the nucleotides that moved need to be recomputed.
+#[derive(Debug, Clone)]
 pub(super) struct XoverSuggestions {
-    helices_groups: BTreeMap<usize, Vec<Nucl>>,
-    helices_cubes: BTreeMap<usize, CubeMap>,
-    blue_nucl: Vec<Nucl>,
-    red_cubes: CubeMap,
+    /// The position of each nucleotide when the neighbourhoods were last updated
+    positions: HashMap<Nucl, Vec3, RandomState>,
+    index: SpatialIndex<Nucl>,
+    /// Maps each nucleotide to the nucleotides of other helices that are at distance less than
+    /// `LEN_CRIT` from it, and to their distance.
+    neighbours: HashMap<Nucl, Vec<(Nucl, f32)>, RandomState>,
+    /// The nucleotides of the current version of the design, in the order in which they were
+    /// added.
+    nucls: Vec<(Nucl, Vec3)>,
+}
+
+impl Default for XoverSuggestions {
+    fn default() -> Self {
+        Self {
+            positions: Default::default(),
+            index: SpatialIndex::new(LEN_CRIT),
+            neighbours: Default::default(),
+            nucls: Vec::new(),
+        }
+    }
 }
 
 impl XoverSuggestions {
-    pub(super) fn add_nucl(&mut self, nucl: Nucl, space_pos: Vec3, groups: &BTreeMap<usize, bool>) {
-        let cube = space_to_cube(space_pos.x, space_pos.y, space_pos.z);
-
-        self.helices_groups
-            .entry(nucl.helix)
-            .or_default()
-            .push(nucl.clone());
-        self.helices_cubes
-            .entry(nucl.helix)
-            .or_default()
-            .entry(cube)
-            .or_default()
-            .push(nucl);
-
-        match groups.get(&nucl.helix) {
-            Some(true) => {
-                self.blue_nucl.push(nucl);
-            }
-            Some(false) => {
-                self.red_cubes
-                    .entry(cube)
-                    .or_insert(vec![])
-                    .push(nucl.clone());
-            }
-            None => (),
+    /// Return an engine that remembers the neighbourhoods computed by `self`, to which the
+    /// nucleotides of a new version of the design must be added.
+    pub(super) fn begin_update(&self) -> Self {
+        Self {
+            nucls: Vec::new(),
+            ..self.clone()
         }
     }
 
+    pub(super) fn add_nucl(&mut self, nucl: Nucl, space_pos: Vec3) {
+        self.nucls.push((nucl, space_pos));
+    }
+
     /// Return the list of all suggested crossovers
     pub(super) fn get_suggestions(
-        &self,
+        &mut self,
         design: &Design,
         suggestion_parameters: &SuggestionParameters,
     ) -> Vec<(Nucl, Nucl)> {
+        self.update_neighbourhoods();
         let mut ret = vec![];
-        if suggestion_parameters.ignore_groups {
-            self.get_suggestions_all_helices(&mut ret, design, suggestion_parameters);
-        } else {
-            self.get_suggestions_groups(&mut ret, design, suggestion_parameters);
+        for (nucl, _) in self.nucls.iter() {
+            let group = design.groups.get(&nucl.helix);
+            if !suggestion_parameters.ignore_groups && group != Some(&true) {
+                continue;
+            }
+            for (other, dist) in self.neighbours.get(nucl).into_iter().flatten() {
+                if *dist > suggestion_parameters.max_distance {
+                    continue;
+                }
+                if suggestion_parameters.same_group_only
+                    && !Self::same_group(design, nucl.helix, other.helix)
+                {
+                    continue;
+                }
+                let candidate = if suggestion_parameters.ignore_groups {
+                    other.helix > nucl.helix
+                } else {
+                    design.groups.get(&other.helix) == Some(&false)
+                };
+                if candidate && Self::can_be_suggested(design, nucl, other, suggestion_parameters) {
+                    ret.push((*nucl, *other, *dist));
+                }
+            }
         }
         ret.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
         self.trimm_suggestion(&ret, design, suggestion_parameters)
     }
 
-    /// Return the list of all suggested crossovers
-    fn get_suggestions_groups(
-        &self,
-        ret: &mut Vec<(Nucl, Nucl, f32)>,
-        design: &Design,
-        suggestion_parameters: &SuggestionParameters,
-    ) {
-        for blue_nucl in self.blue_nucl.iter() {
-            let neighbour = self
-                .get_possible_cross_over_groups(design, blue_nucl, suggestion_parameters)
-                .unwrap_or_default();
-            for (red_nucl, dist) in neighbour {
-                ret.push((*blue_nucl, red_nucl, dist))
+    /// Bring the neighbourhoods up to date with the nucleotides added since the last call to
+    /// `begin_update`.
+    fn update_neighbourhoods(&mut self) {
+        let new_positions: HashMap<Nucl, Vec3, RandomState> = self.nucls.iter().cloned().collect();
+        let removed: Vec<Nucl> = self
+            .positions
+            .keys()
+            .filter(|n| !new_positions.contains_key(n))
+            .cloned()
+            .collect();
+        let mut moved: Vec<Nucl> = new_positions
+            .iter()
+            .filter(|(n, p)| self.positions.get(n) != Some(p))
+            .map(|(n, _)| *n)
+            .collect();
+        if removed.is_empty() && moved.is_empty() {
+            return;
+        }
+
+        if (removed.len() + moved.len()) as f32 > MAX_INCREMENTAL_RATIO * new_positions.len() as f32
+        {
+            log::debug!("Recomputing all crossover suggestions");
+            self.positions.clear();
+            self.index.clear();
+            self.neighbours.clear();
+            moved = new_positions.keys().cloned().collect();
+        }
+
+        for n in removed.iter().chain(moved.iter()) {
+            if let Some(old_position) = self.positions.remove(n) {
+                self.index.remove(old_position, n);
+            }
+            for (m, _) in self.neighbours.remove(n).into_iter().flatten() {
+                if let Some(neighbours) = self.neighbours.get_mut(&m) {
+                    neighbours.retain(|(x, _)| x != n);
+                }
             }
         }
+        for n in moved.iter() {
+            let position = new_positions[n];
+            self.index.insert(position, *n);
+            self.positions.insert(*n, position);
+        }
+
+        let moved_set: HashSet<Nucl, RandomState> = moved.iter().cloned().collect();
+        for n in moved.iter() {
+            let close: Vec<(Nucl, f32)> = self
+                .index
+                .within(self.positions[n], LEN_CRIT)
+                .filter(|(m, _)| m.helix != n.helix)
+                .map(|(m, dist)| (*m, dist))
+                .collect();
+            for (m, dist) in close.iter() {
+                // The neighbourhoods of the nucleotides that moved are computed entirely in this
+                // loop.
+                if !moved_set.contains(m) {
+                    self.neighbours.entry(*m).or_default().push((*n, *dist));
+                }
+            }
+            self.neighbours.insert(*n, close);
+        }
     }
 
     /// Trimm a list of crossovers so that each nucleotide appears at most once in the suggestion
@@ -123,127 +196,34 @@ impl XoverSuggestions {
         ret
     }
 
-    fn get_suggestions_all_helices(
-        &self,
-        ret: &mut Vec<(Nucl, Nucl, f32)>,
-        design: &Design,
-        suggestion_parameters: &SuggestionParameters,
-    ) {
-        for nucls in self.helices_groups.values() {
-            for n in nucls.iter() {
-                let neighbour = self
-                    .get_possible_cross_over_all_helices(design, n, suggestion_parameters)
-                    .unwrap_or_default();
-                for (red_nucl, dist) in neighbour {
-                    ret.push((*n, red_nucl, dist))
-                }
-            }
+    /// Return true iff helices `h1` and `h2` belong to the same helix bundle or, if neither of them
+    /// belongs to a bundle, lie on the same grid.
+    fn same_group(design: &Design, h1: usize, h2: usize) -> bool {
+        let bundle_1 = ensnano_design::bundle_of_helix(&design.helix_bundles, h1);
+        let bundle_2 = ensnano_design::bundle_of_helix(&design.helix_bundles, h2);
+        if bundle_1.is_some() || bundle_2.is_some() {
+            return bundle_1 == bundle_2;
         }
+        let grid = |h_id| {
+            design
+                .helices
+                .get(&h_id)
+                .and_then(|h| h.grid_position.map(|pos| pos.grid))
+        };
+        grid(h1).is_some() && grid(h1) == grid(h2)
     }
 
-    fn get_possible_cross_over_all_helices(
-        &self,
+    fn can_be_suggested(
         design: &Design,
         nucl: &Nucl,
+        red_nucl: &Nucl,
         suggestion_parameters: &SuggestionParameters,
-    ) -> Option<Vec<(Nucl, f32)>> {
-        let mut ret = Vec::new();
-        let positions = design.get_nucl_position(*nucl)?;
-        let cube0 = space_to_cube(positions[0], positions[1], positions[2]);
-        for i in vec![-1, 0, 1].iter() {
-            for j in vec![-1, 0, 1].iter() {
-                for k in vec![-1, 0, 1].iter() {
-                    let cube = (cube0.0 + i, cube0.1 + j, cube0.2 + k);
-
-                    for (_, cubes) in self.helices_cubes.iter().filter(|(h, _)| **h > nucl.helix) {
-                        if let Some(v) = cubes.get(&cube) {
-                            for red_nucl in v {
-                                if red_nucl.helix != nucl.helix {
-                                    if let Some(red_position) = design.get_nucl_position(*red_nucl)
-                                    {
-                                        let dist = (0..3)
-                                            .map(|i| (positions[i], red_position[i]))
-                                            .map(|(x, y)| (x - y) * (x - y))
-                                            .sum::<f32>()
-                                            .sqrt();
-                                        if dist < LEN_CRIT
-                                            && (suggestion_parameters.include_scaffold
-                                                || design.strands.get_strand_nucl(nucl)
-                                                    != design.scaffold_id)
-                                            && (suggestion_parameters.include_scaffold
-                                                || design.strands.get_strand_nucl(red_nucl)
-                                                    != design.scaffold_id)
-                                            && (suggestion_parameters.include_intra_strand
-                                                || design.strands.get_strand_nucl(nucl)
-                                                    != design.strands.get_strand_nucl(red_nucl))
-                                        {
-                                            ret.push((*red_nucl, dist));
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Some(ret)
-    }
-
-    /// Return all the crossovers of length less than `len_crit` involving `nucl`, and their length.
-    fn get_possible_cross_over_groups(
-        &self,
-        design: &Design,
-        nucl: &Nucl,
-        suggestion_parameters: &SuggestionParameters,
-    ) -> Option<Vec<(Nucl, f32)>> {
-        let mut ret = Vec::new();
-        let positions = design.get_nucl_position(*nucl)?;
-        let cube0 = space_to_cube(positions[0], positions[1], positions[2]);
-
-        for i in vec![-1, 0, 1].iter() {
-            for j in vec![-1, 0, 1].iter() {
-                for k in vec![-1, 0, 1].iter() {
-                    let cube = (cube0.0 + i, cube0.1 + j, cube0.2 + k);
-
-                    if let Some(v) = self.red_cubes.get(&cube) {
-                        for red_nucl in v {
-                            if red_nucl.helix != nucl.helix {
-                                if let Some(red_position) = design.get_nucl_position(*red_nucl) {
-                                    let dist = (0..3)
-                                        .map(|i| (positions[i], red_position[i]))
-                                        .map(|(x, y)| (x - y) * (x - y))
-                                        .sum::<f32>()
-                                        .sqrt();
-                                    if dist < LEN_CRIT
-                                        && (suggestion_parameters.include_scaffold
-                                            || design.strands.get_strand_nucl(nucl)
-                                                != design.scaffold_id)
-                                        && (suggestion_parameters.include_scaffold
-                                            || design.strands.get_strand_nucl(red_nucl)
-                                                != design.scaffold_id)
-                                        && (suggestion_parameters.include_intra_strand
-                                            || design.strands.get_strand_nucl(nucl)
-                                                != design.strands.get_strand_nucl(red_nucl))
-                                    {
-                                        ret.push((*red_nucl, dist));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Some(ret)
+    ) -> bool {
+        (suggestion_parameters.include_scaffold
+            || design.strands.get_strand_nucl(nucl) != design.scaffold_id)
+            && (suggestion_parameters.include_scaffold
+                || design.strands.get_strand_nucl(red_nucl) != design.scaffold_id)
+            && (suggestion_parameters.include_intra_strand
+                || design.strands.get_strand_nucl(nucl) != design.strands.get_strand_nucl(red_nucl))
     }
 }
-
-fn space_to_cube(x: f32, y: f32, z: f32) -> (isize, isize, isize) {
-    let cube_len = 1.2;
-    (
-        x.div_euclid(cube_len) as isize,
-        y.div_euclid(cube_len) as isize,
-        z.div_euclid(cube_len) as isize,
-    )
-}
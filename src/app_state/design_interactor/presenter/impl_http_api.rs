@@ -0,0 +1,92 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::*;
+use crate::http_api::{
+    DesignGeometry, HelixGeometry, HttpApiReader, StapleDescription, StrandGeometry,
+};
+use ensnano_design::{Collection, Domain};
+use ensnano_interactor::{DesignStatistics, Referential};
+
+impl HttpApiReader for DesignReader {
+    fn staples(&self) -> Vec<StapleDescription> {
+        self.presenter
+            .content
+            .get_staples(&self.presenter.current_design, &self.presenter)
+            .into_iter()
+            .map(|staple| StapleDescription {
+                id: staple.intervals.staple_id,
+                name: staple.name.into_owned(),
+                sequence: staple.sequence,
+                plate: staple.plate,
+                well: staple.well,
+                intervals: staple.intervals.intervals,
+                uuid: staple.uuid,
+            })
+            .collect()
+    }
+
+    fn statistics(&self) -> DesignStatistics {
+        self.get_design_statistics()
+    }
+
+    fn geometry(&self) -> DesignGeometry {
+        let design = self.presenter.current_design.as_ref();
+        let helices = design
+            .helices
+            .iter()
+            .map(|(id, helix)| HelixGeometry {
+                id: *id,
+                position: helix.position,
+                orientation: helix.orientation,
+            })
+            .collect();
+        let strands = design
+            .strands
+            .iter()
+            .map(|(id, strand)| StrandGeometry {
+                id: *id,
+                nucleotides: strand
+                    .domains
+                    .iter()
+                    .filter_map(|d| {
+                        if let Domain::HelixDomain(interval) = d {
+                            Some(interval)
+                        } else {
+                            None
+                        }
+                    })
+                    .flat_map(|interval| {
+                        interval.iter().filter_map(move |position| {
+                            self.get_position_of_nucl_on_helix(
+                                Nucl {
+                                    helix: interval.helix,
+                                    position,
+                                    forward: interval.forward,
+                                },
+                                Referential::World,
+                                false,
+                            )
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+        DesignGeometry { helices, strands }
+    }
+}
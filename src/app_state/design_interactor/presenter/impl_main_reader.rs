@@ -69,7 +69,7 @@ impl StaplesDownloader for DesignReader {
         Ok(DownloadStapleOk { warnings })
     }
 
-    fn write_staples_xlsx(&self, xlsx_path: &PathBuf) {
+    fn write_staples_xlsx(&self, xlsx_path: &PathBuf) -> Result<(), XlsxError> {
         // use simple_excel_writer::{row, Row, Workbook};
 
         let all_group_names: Vec<String> = self.presenter.get_names_of_all_groups();
@@ -96,6 +96,10 @@ impl StaplesDownloader for DesignReader {
                 }
             })
             .collect();
+        let uuid_strs: Vec<_> = staples
+            .iter()
+            .map(|staple| staple.uuid.map(|uuid| uuid.to_string()).unwrap_or_default())
+            .collect();
 
         let mut first_row_content = vec![
             "Well Position",
@@ -106,6 +110,11 @@ impl StaplesDownloader for DesignReader {
             "Domain Length",
             "Color",
             "Groups",
+            "UUID",
+            "Pool",
+            "Concentration (nM)",
+            "Note",
+            "URL",
         ];
         first_row_content.extend(all_group_names.iter().map(|s| &**s));
 
@@ -129,6 +138,11 @@ impl StaplesDownloader for DesignReader {
                 &staple.domain_decomposition,
                 &staple.color_str,
                 &staple.group_names_string,
+                &uuid_strs[i],
+                &staple.pool_name,
+                &staple.concentration_str,
+                &staple.note,
+                &staple.url,
             ];
             row.extend(group_vec.iter());
             sheet.push(row)
@@ -138,18 +152,14 @@ impl StaplesDownloader for DesignReader {
 
         // Add one sheet per plate
         for (sheet_id, rows) in sheets.iter() {
-            let mut sheet: &mut rust_xlsxwriter::Worksheet = wb
-                .add_worksheet()
-                .set_name(&format!("Plate {sheet_id}"))
-                .expect("Excel error: cannot create worksheet");
+            let mut sheet: &mut rust_xlsxwriter::Worksheet =
+                wb.add_worksheet().set_name(&format!("Plate {sheet_id}"))?;
 
             for (i, row) in rows.iter().enumerate() {
                 if i == 0 {
                     for (j, data) in row.iter().enumerate() {
                         let bold = Format::new().set_bold();
-                        sheet
-                            .write_with_format(0, j as u16, data.to_string(), &bold)
-                            .expect("error write cell");
+                        sheet.write_with_format(0, j as u16, data.to_string(), &bold)?;
                     }
                     continue;
                 }
@@ -158,9 +168,7 @@ impl StaplesDownloader for DesignReader {
                     if j == 4 {
                         // length
                         if let Ok(length) = row[j].parse::<f64>() {
-                            sheet
-                                .write(i as u32, j as u16, length)
-                                .expect("error write cell");
+                            sheet.write(i as u32, j as u16, length)?;
                             continue;
                         }
                     }
@@ -179,25 +187,24 @@ impl StaplesDownloader for DesignReader {
                             let format = Format::new()
                                 .set_background_color(Color::RGB(color))
                                 .set_font_color(font_color);
-                            sheet
-                                .write_with_format(i as u32, j as u16, row[j].to_string(), &format)
-                                .expect("error write cell");
+                            sheet.write_with_format(
+                                i as u32,
+                                j as u16,
+                                row[j].to_string(),
+                                &format,
+                            )?;
                             continue;
                         }
                     }
-                    sheet
-                        .write(i as u32, j as u16, row[j].to_string())
-                        .expect("error write cell");
+                    sheet.write(i as u32, j as u16, row[j].to_string())?;
                 }
             }
 
             sheet.autofit();
         }
 
-        let mut sheet: &mut rust_xlsxwriter::Worksheet = wb
-            .add_worksheet()
-            .set_name(&format!("All staples"))
-            .expect("Excel error: cannot create worksheet");
+        let mut sheet: &mut rust_xlsxwriter::Worksheet =
+            wb.add_worksheet().set_name(&format!("All staples"))?;
         let mut write_once = true;
         let mut all_i = 0;
         for (_, rows) in sheets.iter() {
@@ -206,9 +213,7 @@ impl StaplesDownloader for DesignReader {
                     if write_once {
                         for (j, data) in row.iter().enumerate() {
                             let bold = Format::new().set_bold();
-                            sheet
-                                .write_with_format(0, j as u16, data.to_string(), &bold)
-                                .expect("error write cell");
+                            sheet.write_with_format(0, j as u16, data.to_string(), &bold)?;
                         }
                         write_once = false;
                         all_i += 1;
@@ -220,9 +225,7 @@ impl StaplesDownloader for DesignReader {
                     if j == 4 {
                         // length
                         if let Ok(length) = row[j].parse::<f64>() {
-                            sheet
-                                .write(all_i, j as u16, length)
-                                .expect("error write cell");
+                            sheet.write(all_i, j as u16, length)?;
                             continue;
                         }
                     }
@@ -240,15 +243,16 @@ impl StaplesDownloader for DesignReader {
                             let format = Format::new()
                                 .set_background_color(Color::RGB(color))
                                 .set_font_color(font_color);
-                            sheet
-                                .write_with_format(all_i, j as u16, row[j].to_string(), &format)
-                                .expect("error write cell");
+                            sheet.write_with_format(
+                                all_i,
+                                j as u16,
+                                row[j].to_string(),
+                                &format,
+                            )?;
                             continue;
                         }
                     }
-                    sheet
-                        .write(all_i as u32, j as u16, row[j].to_string())
-                        .expect("error write cell");
+                    sheet.write(all_i as u32, j as u16, row[j].to_string())?;
                 }
                 all_i += 1;
             }
@@ -257,8 +261,38 @@ impl StaplesDownloader for DesignReader {
         }
 
         // close the excel file
-        wb.save(xlsx_path).expect("save excel error!");
-        // wb.close().expect("close excel error!");
+        wb.save(xlsx_path)
+    }
+
+    fn write_staples_csv(&self, csv_path: &PathBuf) {
+        let staples = self
+            .presenter
+            .content
+            .get_staples(&self.presenter.current_design, &self.presenter);
+        let mut content = String::from(STAPLES_CSV_HEADER);
+        content.push('\n');
+        for staple in staples.iter() {
+            let fields = [
+                staple.plate.to_string(),
+                staple.well.clone(),
+                staple.name.to_string(),
+                staple.sequence.clone(),
+                staple.length_str.clone(),
+                staple.pool_name.clone(),
+                staple.concentration_str.clone(),
+                staple.color_str.clone(),
+                staple.group_names_string.clone(),
+                staple.uuid.map(|uuid| uuid.to_string()).unwrap_or_default(),
+                staple.note.clone(),
+                staple.url.clone(),
+            ];
+            let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            content.push_str(&line.join(","));
+            content.push('\n');
+        }
+        if let Err(e) = std::fs::write(csv_path, content) {
+            log::error!("Could not write to file {}", e);
+        }
     }
 
     fn write_intervals(&self, origami_path: &PathBuf) {
@@ -298,6 +332,18 @@ impl StaplesDownloader for DesignReader {
     }
 }
 
+const STAPLES_CSV_HEADER: &str =
+    "Plate,Well Position,Name,Sequence,Length,Pool,Concentration (nM),Color,Groups,UUID,Note,URL";
+
+/// Quote a field of a CSV line if it contains a separator or a quote.
+fn csv_field(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 fn warn_all_staples_not_paired(first_unpaired: Nucl) -> String {
     format!(
         "All staptes are not paired. First unpaired nucleotide: {}",
@@ -353,6 +399,16 @@ impl MainReader for DesignReader {
             .get(&s_id)
             .map(|s| s.domain_ends())
     }
+
+    fn get_paired_nucl(&self, nucl: Nucl) -> Option<Nucl> {
+        let virtual_compl =
+            Nucl::map_to_virtual_nucl(nucl, &self.presenter.current_design.helices)?.compl();
+        self.presenter
+            .content
+            .nucl_collection
+            .virtual_to_real(&virtual_compl)
+            .cloned()
+    }
 }
 
 use std::collections::BTreeMap;
@@ -0,0 +1,89 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+use super::*;
+use ensnano_design::Domain;
+use ensnano_interactor::plugin::DesignReader as PluginReader;
+use ensnano_interactor::{DesignStatistics, Referential};
+
+impl PluginReader for DesignReader {
+    fn get_all_helix_ids(&self) -> Vec<usize> {
+        self.presenter
+            .current_design
+            .helices
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn get_all_strand_ids(&self) -> Vec<usize> {
+        self.presenter
+            .current_design
+            .strands
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn get_scaffold_id(&self) -> Option<usize> {
+        self.presenter.current_design.scaffold_id
+    }
+
+    fn get_strand_name(&self, s_id: usize) -> Option<String> {
+        self.presenter
+            .current_design
+            .strands
+            .get(&s_id)
+            .and_then(|s| s.name.as_ref())
+            .map(|name| name.to_string())
+    }
+
+    fn get_strand_color(&self, s_id: usize) -> Option<u32> {
+        self.presenter
+            .current_design
+            .strands
+            .get(&s_id)
+            .map(|s| s.color)
+    }
+
+    fn get_strand_nucls(&self, s_id: usize) -> Option<Vec<Nucl>> {
+        let strand = self.presenter.current_design.strands.get(&s_id)?;
+        let mut ret = Vec::with_capacity(strand.length());
+        for domain in strand.domains.iter() {
+            if let Domain::HelixDomain(interval) = domain {
+                ret.extend(interval.iter().map(|position| Nucl {
+                    helix: interval.helix,
+                    position,
+                    forward: interval.forward,
+                }));
+            }
+        }
+        Some(ret)
+    }
+
+    fn get_base(&self, nucl: Nucl) -> Option<char> {
+        self.presenter.content.letter_map.get(&nucl).cloned()
+    }
+
+    fn get_nucl_position(&self, nucl: Nucl) -> Option<Vec3> {
+        self.get_position_of_nucl_on_helix(nucl, Referential::World, false)
+    }
+
+    fn get_design_statistics(&self) -> DesignStatistics {
+        DesignReader::get_design_statistics(self)
+    }
+}
@@ -151,6 +151,10 @@ impl Reader2D for DesignReader {
             .cloned()
     }
 
+    fn get_helix_display_number(&self, h_id: usize) -> usize {
+        self.presenter.current_design.helices.display_number(h_id)
+    }
+
     fn get_visibility_helix(&self, h_id: usize) -> Option<bool> {
         self.presenter
             .current_design
@@ -233,6 +237,36 @@ impl Reader2D for DesignReader {
             .map(|data| data.grid_data.get_abscissa_converter(h_id))
             .unwrap_or_default()
     }
+
+    fn get_helix_parameters(&self) -> ensnano_design::HelixParameters {
+        self.presenter
+            .current_design
+            .helix_parameters
+            .unwrap_or_default()
+    }
+
+    fn get_helix_grid_type(&self, h_id: usize) -> Option<ensnano_design::grid::GridTypeDescr> {
+        let grid_id = self
+            .presenter
+            .current_design
+            .helices
+            .get(&h_id)
+            .and_then(|h| h.grid_position)
+            .map(|position| position.grid)?;
+        self.presenter
+            .content
+            .grid_manager
+            .grids
+            .get(&grid_id)
+            .map(|grid| grid.grid_type.descr())
+    }
+
+    fn get_heat_map(
+        &self,
+        kind: ensnano_design::heat_map::HeatMapKind,
+    ) -> BTreeMap<usize, Vec<(isize, f32)>> {
+        ensnano_design::heat_map::heat_map(&self.presenter.current_design, kind)
+    }
 }
 
 impl crate::flatscene::NuclCollection for super::design_content::NuclCollection {
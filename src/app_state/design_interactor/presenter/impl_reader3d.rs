@@ -19,12 +19,12 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use super::*;
 use ahash::RandomState;
 use ensnano_design::{
-    grid::{GridId, GridObject, GridPosition, HelixGridPosition},
+    grid::{GridId, GridObject, GridPosition, HelixGridPosition, PhantomParameters},
     BezierPlaneDescriptor, BezierPlaneId, BezierVertexId, Collection, CurveDescriptor, Domain,
-    Nucl,
+    JointKind, Nucl,
 };
 use ensnano_interactor::{
-    graphics::{LoopoutBond, LoopoutNucl},
+    graphics::{LoopoutBond, LoopoutNucl, StrandEnd},
     BezierControlPoint, ObjectType, Referential,
 };
 use std::collections::HashSet;
@@ -55,6 +55,33 @@ impl Reader3D for DesignReader {
         self.presenter.content.with_cones_map.get(&e_id).cloned()
     }
 
+    fn get_cone_spacing(&self, e_id: u32) -> Option<usize> {
+        self.presenter.content.cone_spacing_map.get(&e_id).cloned()
+    }
+
+    fn get_bond_rank(&self, e_id: u32) -> Option<usize> {
+        self.presenter.content.bond_rank_map.get(&e_id).cloned()
+    }
+
+    fn get_strand_ends(&self, ignore_visibility_sieve: bool) -> Vec<StrandEnd> {
+        self.presenter
+            .content
+            .strand_ends
+            .iter()
+            .filter(|end| {
+                ignore_visibility_sieve
+                    || self
+                        .presenter
+                        .content
+                        .nucleotide
+                        .get(&end.nucl_id)
+                        .map(|nucl| !self.presenter.invisible_nucls.contains(nucl))
+                        .unwrap_or(false)
+            })
+            .cloned()
+            .collect()
+    }
+
     fn get_basis(&self) -> Rotor3 {
         self.presenter.model_matrix.extract_rotation()
     }
@@ -99,6 +126,14 @@ impl Reader3D for DesignReader {
             .map(|h| h.orientation)
     }
 
+    fn get_helix_roll(&self, h_id: usize) -> Option<f32> {
+        self.presenter
+            .current_design
+            .helices
+            .get(&h_id)
+            .map(|h| h.roll)
+    }
+
     fn get_all_nucl_ids(&self) -> Vec<u32> {
         self.presenter.content.nucleotide.keys().cloned().collect()
     }
@@ -174,7 +209,7 @@ impl Reader3D for DesignReader {
         self.presenter.content.get_helices_on_grid(g_id)
     }
 
-    fn get_all_prime3_nucl(&self) -> Vec<(Vec3, Vec3, u32)> {
+    fn get_all_prime3_nucl(&self, ignore_visibility_sieve: bool) -> Vec<(Vec3, Vec3, u32)> {
         let locate_nucl = |nucl| {
             let pos_start_opt = self
                 .get_identifier_nucl(&nucl)
@@ -186,7 +221,9 @@ impl Reader3D for DesignReader {
             .content
             .prime3_set
             .iter()
-            .filter(|prime3| !self.presenter.invisible_nucls.contains(&prime3.nucl))
+            .filter(|prime3| {
+                ignore_visibility_sieve || !self.presenter.invisible_nucls.contains(&prime3.nucl)
+            })
             .filter_map(|prime3| {
                 let start = locate_nucl(prime3.nucl)?;
                 let end = locate_nucl(prime3.nucl.prime3())?;
@@ -219,11 +256,16 @@ impl Reader3D for DesignReader {
             .get_helix_grid_position(h_id as usize)
     }
 
-    fn get_all_visible_nucl_ids(&self) -> Vec<u32> {
-        self.presenter.content.get_all_visible_nucl_ids(
-            &self.presenter.current_design,
-            &self.presenter.invisible_nucls,
-        )
+    fn get_all_visible_nucl_ids(&self, ignore_visibility_sieve: bool) -> Vec<u32> {
+        let no_invisible_nucls = HashSet::new();
+        let invisible_nucls = if ignore_visibility_sieve {
+            &no_invisible_nucls
+        } else {
+            &self.presenter.invisible_nucls
+        };
+        self.presenter
+            .content
+            .get_all_visible_nucl_ids(&self.presenter.current_design, invisible_nucls)
     }
 
     fn get_grid_latice_position(&self, position: GridPosition) -> Option<Vec3> {
@@ -239,11 +281,16 @@ impl Reader3D for DesignReader {
             .map(|t| t.0))
     }
 
-    fn get_all_visible_bond_ids(&self) -> Vec<u32> {
-        self.presenter.content.get_all_visible_bonds(
-            &self.presenter.current_design,
-            &self.presenter.invisible_nucls,
-        )
+    fn get_all_visible_bond_ids(&self, ignore_visibility_sieve: bool) -> Vec<u32> {
+        let no_invisible_nucls = HashSet::new();
+        let invisible_nucls = if ignore_visibility_sieve {
+            &no_invisible_nucls
+        } else {
+            &self.presenter.invisible_nucls
+        };
+        self.presenter
+            .content
+            .get_all_visible_bonds(&self.presenter.current_design, invisible_nucls)
     }
 
     fn get_scalebar(&self) -> Option<(f32, f32, fn(f32, f32, f32) -> u32)> {
@@ -306,14 +353,54 @@ impl Reader3D for DesignReader {
         self.presenter.content.strand_map.get(&e_id).cloned()
     }
 
+    fn get_helices_axis_ends(&self) -> BTreeMap<usize, (Vec3, Vec3)> {
+        let design = self.presenter.current_design.as_ref();
+        let helix_parameters = design.helix_parameters.unwrap_or_default();
+        design
+            .strands
+            .get_intervals()
+            .into_iter()
+            .filter_map(|(h_id, (start, end))| {
+                let helix = design.helices.get(&h_id)?;
+                Some((
+                    h_id,
+                    (
+                        helix.axis_position(&helix_parameters, start, true),
+                        helix.axis_position(&helix_parameters, end, true),
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    fn get_helix_display_number(&self, h_id: usize) -> usize {
+        self.presenter.current_design.helices.display_number(h_id)
+    }
+
+    fn get_joints(&self) -> Vec<(JointKind, Vec3, Vec3)> {
+        let design = self.presenter.current_design.as_ref();
+        design
+            .joints
+            .values()
+            .filter_map(|joint| {
+                let (anchor, axis) = joint.world_anchor_and_axis(design)?;
+                Some((joint.kind, anchor, axis))
+            })
+            .collect()
+    }
+
     fn get_used_coordinates_on_grid(&self, g_id: GridId) -> Option<Vec<(isize, isize)>> {
         Some(self.presenter.content.get_used_coordinates_on_grid(g_id))
     }
 
-    fn get_persistent_phantom_helices_id(&self) -> HashSet<u32> {
+    fn get_persistent_phantom_helices_id(&self) -> HashMap<u32, PhantomParameters> {
         self.presenter.content.get_persistent_phantom_helices_id()
     }
 
+    fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters {
+        self.presenter.content.get_grid_phantom_parameters(g_id)
+    }
+
     fn get_ids_of_elements_belonging_to_helix(&self, h_id: usize) -> Vec<u32> {
         let nucls = self
             .presenter
@@ -392,6 +479,23 @@ impl Reader3D for DesignReader {
             .unwrap_or(0)
     }
 
+    fn get_insertion_lengths(&self, ignore_visibility_sieve: bool) -> Vec<(u32, usize)> {
+        self.presenter
+            .content
+            .insertion_length
+            .iter()
+            .filter(|(id, len)| {
+                **len > 0
+                    && (ignore_visibility_sieve
+                        || self
+                            .get_nucl_with_id_relaxed(**id)
+                            .map(|nucl| !self.presenter.invisible_nucls.contains(&nucl))
+                            .unwrap_or(false))
+            })
+            .map(|(id, len)| (*id, *len))
+            .collect()
+    }
+
     fn get_expected_bond_length(&self) -> f32 {
         self.presenter
             .current_design
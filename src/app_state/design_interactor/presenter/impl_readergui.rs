@@ -16,11 +16,13 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use ensnano_design::{elements::DesignElement, CameraId, Collection};
+use ensnano_design::{
+    elements::DesignElement, grid::PhantomParameters, CameraId, Collection, Domain, OligoPool,
+};
 
 use super::*;
-use crate::gui::DesignReader as ReaderGui;
-use ensnano_interactor::InsertionPoint;
+use crate::gui::{DesignReader as ReaderGui, StapleRow, WellPosition};
+use ensnano_interactor::{DesignStatistics, InsertionPoint};
 use ultraviolet::Rotor3;
 
 impl ReaderGui for DesignReader {
@@ -28,10 +30,18 @@ impl ReaderGui for DesignReader {
         self.presenter.content.grid_has_small_spheres(g_id)
     }
 
+    fn grid_has_labels(&self, g_id: GridId) -> bool {
+        self.presenter.content.grid_has_labels(g_id)
+    }
+
     fn grid_has_persistent_phantom(&self, g_id: GridId) -> bool {
         self.presenter.content.grid_has_persistent_phantom(g_id)
     }
 
+    fn get_grid_phantom_parameters(&self, g_id: GridId) -> PhantomParameters {
+        self.presenter.content.get_grid_phantom_parameters(g_id)
+    }
+
     fn get_grid_shift(&self, g_id: GridId) -> Option<f32> {
         self.presenter.content.get_grid_shift(g_id)
     }
@@ -79,6 +89,62 @@ impl ReaderGui for DesignReader {
             .unwrap_or_else(|| String::from("Unamed strand"))
     }
 
+    fn strand_pool(&self, s_id: usize) -> Option<OligoPool> {
+        self.presenter
+            .current_design
+            .strands
+            .get(&s_id)
+            .and_then(|s| s.pool.clone())
+    }
+
+    fn strand_note(&self, s_id: usize) -> Option<String> {
+        self.presenter
+            .current_design
+            .strands
+            .get(&s_id)
+            .and_then(|s| s.note.clone())
+    }
+
+    fn strand_url(&self, s_id: usize) -> Option<String> {
+        self.presenter
+            .current_design
+            .strands
+            .get(&s_id)
+            .and_then(|s| s.url.clone())
+    }
+
+    fn get_staple_rows(&self) -> Vec<StapleRow> {
+        let design = &self.presenter.current_design;
+        design
+            .strands
+            .iter()
+            .filter(|(s_id, _)| design.scaffold_id != Some(**s_id))
+            .map(|(s_id, s)| StapleRow {
+                s_id: *s_id,
+                name: s.name.as_ref().map(|n| n.to_string()).unwrap_or_default(),
+                length: s.length(),
+                color: s.color,
+                pool: s.pool.as_ref().map(|p| p.name.clone()),
+                note: s.note.clone(),
+                url: s.url.clone(),
+            })
+            .collect()
+    }
+
+    fn get_strands_at_wells(&self, wells: &[WellPosition]) -> Vec<usize> {
+        self.presenter
+            .content
+            .get_staples(&self.presenter.current_design, &self.presenter)
+            .into_iter()
+            .filter(|staple| {
+                wells.iter().any(|w| {
+                    w.well == staple.well && w.plate.map(|p| p == staple.plate).unwrap_or(true)
+                })
+            })
+            .map(|staple| staple.intervals.staple_id)
+            .collect()
+    }
+
     fn get_all_cameras(&self) -> Vec<(CameraId, &str)> {
         //TODO this obviously needs to be updated to relate to the real content
         self.presenter
@@ -93,6 +159,26 @@ impl ReaderGui for DesignReader {
         self.presenter.current_design.get_favourite_camera_id()
     }
 
+    fn camera_has_rendering_settings(&self, cam_id: CameraId) -> bool {
+        self.presenter
+            .current_design
+            .get_camera(cam_id)
+            .map_or(false, |c| c.rendering.is_some())
+    }
+
+    fn get_presentation_step_names(&self) -> Vec<&str> {
+        self.presenter
+            .current_design
+            .presentation_steps
+            .iter()
+            .map(|step| step.name.as_str())
+            .collect()
+    }
+
+    fn get_design_statistics(&self) -> DesignStatistics {
+        DesignReader::get_design_statistics(self)
+    }
+
     fn get_grid_position_and_orientation(&self, g_id: GridId) -> Option<(Vec3, Rotor3)> {
         self.presenter
             .current_design
@@ -137,6 +223,10 @@ impl ReaderGui for DesignReader {
         self.presenter.current_design.rainbow_scaffold
     }
 
+    fn get_design_metadata(&self) -> ensnano_design::DesignMetadata {
+        self.presenter.current_design.metadata.clone()
+    }
+
     fn get_insertion_length(&self, selection: &Selection) -> Option<usize> {
         match selection {
             Selection::Bond(_, n1, n2) => {
@@ -230,6 +320,28 @@ impl ReaderGui for DesignReader {
             .map(|p| p.is_cyclic)
     }
 
+    fn get_min_radius_of_curvature_along_path(
+        &self,
+        path_id: ensnano_design::BezierPathId,
+    ) -> Option<f64> {
+        ensnano_design::design_operations::min_radius_of_curvature_along_path(
+            &self.presenter.current_design,
+            path_id,
+        )
+    }
+
+    fn get_helix_bundles(
+        &self,
+    ) -> std::collections::BTreeMap<ensnano_design::BundleId, ensnano_design::HelixBundle> {
+        self.presenter.current_design.helix_bundles.clone()
+    }
+
+    fn get_joints(
+        &self,
+    ) -> std::collections::BTreeMap<ensnano_design::JointId, ensnano_design::Joint> {
+        self.presenter.current_design.joints.clone()
+    }
+
     fn get_bezier_vertex_position(
         &self,
         vertex_id: ensnano_design::BezierVertexId,
@@ -246,6 +358,36 @@ impl ReaderGui for DesignReader {
         self.presenter.current_design.scaffold_sequence.as_deref()
     }
 
+    fn get_strand_sequence(&self, s_id: usize) -> Option<String> {
+        let strand = self.presenter.current_design.strands.get(&s_id)?;
+        let letter_map = self.presenter.content.letter_map.as_ref();
+        let mut ret = String::with_capacity(strand.length());
+        for domain in strand.domains.iter() {
+            match domain {
+                Domain::HelixDomain(dom) => {
+                    for position in dom.iter() {
+                        let nucl = Nucl {
+                            position,
+                            forward: dom.forward,
+                            helix: dom.helix,
+                        };
+                        ret.push(letter_map.get(&nucl).cloned().unwrap_or('?'));
+                    }
+                }
+                Domain::Insertion {
+                    nb_nucl, sequence, ..
+                } => {
+                    if let Some(sequence) = sequence {
+                        ret.push_str(sequence)
+                    } else {
+                        ret.extend(std::iter::repeat('?').take(*nb_nucl))
+                    }
+                }
+            }
+        }
+        Some(ret)
+    }
+
     fn get_current_length_of_relaxed_shape(&self) -> Option<usize> {
         self.presenter
             .current_design
@@ -253,4 +395,8 @@ impl ReaderGui for DesignReader {
             .as_ref()
             .and_then(|s| s.current_length())
     }
+
+    fn get_helix_strains(&self) -> Vec<ensnano_design::xover_strain::HelixStrain> {
+        ensnano_design::xover_strain::helix_strains(&self.presenter.current_design)
+    }
 }
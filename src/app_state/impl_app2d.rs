@@ -65,6 +65,42 @@ impl App2D for AppState {
     fn get_building_state(&self) -> Option<ensnano_interactor::StrandBuildingStatus> {
         self.get_strand_building_state()
     }
+
+    fn get_theme(&self) -> ensnano_interactor::graphics::Theme {
+        self.0.parameters.theme
+    }
+
+    fn get_scale_bar_parameters(&self) -> ensnano_interactor::graphics::ScaleBarParameters {
+        self.0.parameters.scale_bar
+    }
+
+    fn get_color_vision_mode(&self) -> ensnano_interactor::graphics::ColorVisionMode {
+        self.0.parameters.color_vision_mode
+    }
+
+    fn get_numbering_parameters(&self) -> ensnano_interactor::graphics::NumberingParameters {
+        self.0.parameters.numbering
+    }
+
+    fn get_xover_drawing_parameters(&self) -> ensnano_interactor::graphics::XoverDrawingParameters {
+        self.0.parameters.xover_drawing
+    }
+
+    fn get_xover_guide_parameters(&self) -> ensnano_interactor::graphics::XoverGuideParameters {
+        self.0.parameters.xover_guides
+    }
+
+    fn get_heat_map(&self) -> Option<ensnano_design::heat_map::HeatMapKind> {
+        self.0.parameters.heat_map
+    }
+
+    fn get_suggestion_parameters(&self) -> ensnano_interactor::SuggestionParameters {
+        self.0.parameters.suggestion_parameters.clone()
+    }
+
+    fn get_action_mode(&self) -> ActionMode {
+        self.0.action_mode
+    }
 }
 
 #[cfg(test)]
@@ -57,7 +57,7 @@ impl App3D for AppState {
     }
 
     fn get_action_mode(&self) -> (ActionMode, WidgetBasis) {
-        (self.0.action_mode, self.0.widget_basis)
+        (self.0.action_mode, self.0.parameters.widget_basis)
     }
 
     fn get_design_reader(&self) -> Self::DesignReader {
@@ -75,10 +75,30 @@ impl App3D for AppState {
         if self.has_selected_a_bezier_grid() {
             WidgetBasis::Object
         } else {
-            self.0.widget_basis
+            self.0.parameters.widget_basis
         }
     }
 
+    fn get_gizmo_size(&self) -> f32 {
+        self.0.parameters.gizmo_size
+    }
+
+    fn get_gizmo_axis_constraint(&self) -> Option<GizmoAxis> {
+        self.0.gizmo_axis_constraint
+    }
+
+    fn get_ground_parameters(&self) -> GroundParameters {
+        self.0.parameters.ground
+    }
+
+    fn get_scale_bar_parameters(&self) -> ScaleBarParameters {
+        self.0.parameters.scale_bar
+    }
+
+    fn get_color_vision_mode(&self) -> ColorVisionMode {
+        self.0.parameters.color_vision_mode
+    }
+
     fn is_changing_color(&self) -> bool {
         self.is_changing_color()
     }
@@ -99,6 +119,7 @@ impl App3D for AppState {
             .and_then(|g_id| reader.get_group_attributes(g_id))
             .and_then(|attributes| attributes.pivot)
             .or(*self.0.selection.pivot.read().as_deref().unwrap())
+            .or_else(|| reader.get_bundle_pivot_of_selection(&self.0.selection.selection))
     }
 
     fn get_current_group_id(&self) -> Option<ensnano_design::GroupId> {
@@ -109,6 +130,10 @@ impl App3D for AppState {
         self.0.parameters.suggestion_parameters != other.0.parameters.suggestion_parameters
     }
 
+    fn get_suggestion_parameters(&self) -> SuggestionParameters {
+        self.0.parameters.suggestion_parameters.clone()
+    }
+
     fn get_check_xover_parameters(&self) -> CheckXoversParameter {
         self.0.parameters.check_xover_parameters
     }
@@ -117,19 +142,37 @@ impl App3D for AppState {
         self.0.parameters.follow_stereography
     }
 
+    fn inside_out_inspection(&self) -> bool {
+        self.0.parameters.inside_out_inspection
+    }
+
+    fn get_brush_parameters(&self) -> BrushParameters {
+        self.0.parameters.brush
+    }
+
     fn get_draw_options(&self) -> DrawOptions {
         DrawOptions {
             background3d: self.0.parameters.background3d,
+            theme: self.0.parameters.theme,
             rendering_mode: self.0.parameters.rendering_mode,
             show_stereographic_camera: self.0.parameters.show_stereography,
             all_helices_on_axis: self.0.parameters.all_helices_on_axis,
             h_bonds: self.0.parameters.show_h_bonds,
             show_bezier_planes: self.0.parameters.show_bezier_paths,
+            strand_ends: self.0.parameters.strand_ends,
+            insertion_badges: self.0.parameters.show_insertion_badges,
         }
     }
 
+    fn get_view_layer(&self, view: ElementType) -> ViewLayer {
+        AppState::get_view_layer(self, view)
+    }
+
     fn draw_options_were_updated(&self, other: &Self) -> bool {
         self.get_draw_options() != other.get_draw_options()
+            || self.0.parameters.scene_layer != other.0.parameters.scene_layer
+            || self.0.parameters.stereographic_scene_layer
+                != other.0.parameters.stereographic_scene_layer
     }
 
     fn get_scroll_sensitivity(&self) -> f32 {
@@ -176,6 +219,18 @@ impl App3D for AppState {
         self.0.unrooted_surface.descriptor.clone()
     }
 
+    fn get_design_diff(&self) -> Option<&DesignDiff> {
+        self.0.design_diff.as_deref()
+    }
+
+    fn design_diff_was_updated(&self, other: &Self) -> bool {
+        self.0.design_diff != other.0.design_diff
+    }
+
+    fn get_twist_axis(&self) -> Option<ensnano_interactor::TwistAxis> {
+        self.0.parameters.twist_axis
+    }
+
     fn get_revolution_axis_position(&self) -> Option<f64> {
         Some(
             self.0
@@ -20,7 +20,9 @@ use super::*;
 use crate::gui::AppState as GuiState;
 use ensnano_design::{elements::DesignElementKey, HelixParameters};
 use ensnano_gui::ClipboardContent;
-use ensnano_interactor::{ScaffoldInfo, SelectionConversion, SimulationState};
+use ensnano_interactor::{
+    ScaffoldInfo, SelectionConversion, ShiftOptimizationParameters, SimulationState, TwistAxis,
+};
 
 mod curve_builders;
 use curve_builders::*;
@@ -38,13 +40,81 @@ impl GuiState for AppState {
     }
 
     fn get_widget_basis(&self) -> WidgetBasis {
-        self.0.widget_basis
+        self.0.parameters.widget_basis
+    }
+
+    fn get_gizmo_size(&self) -> f32 {
+        self.0.parameters.gizmo_size
+    }
+
+    fn get_undo_limit(&self) -> u32 {
+        self.0.parameters.undo_limit
+    }
+
+    fn get_binary_design_files(&self) -> bool {
+        self.0.parameters.binary_design_files
+    }
+
+    fn get_confirm_large_deletions(&self) -> bool {
+        self.0.parameters.confirm_large_deletions
+    }
+
+    fn get_gpu_preference(&self) -> GpuPreference {
+        self.0.parameters.gpu_preference
+    }
+
+    fn get_render_quality(&self) -> RenderQuality {
+        self.0.parameters.render_quality
+    }
+
+    fn get_shift_optimization_parameters(&self) -> ShiftOptimizationParameters {
+        self.0.parameters.shift_optimization
+    }
+
+    fn get_design_diff_summary(&self) -> Option<String> {
+        self.0.design_diff.as_ref().map(|diff| diff.to_string())
+    }
+
+    fn get_ground_parameters(&self) -> GroundParameters {
+        self.0.parameters.ground
+    }
+
+    fn get_view_layer(&self, view: ElementType) -> ViewLayer {
+        AppState::get_view_layer(self, view)
+    }
+
+    fn get_scale_bar_parameters(&self) -> ScaleBarParameters {
+        self.0.parameters.scale_bar
+    }
+
+    fn get_numbering_parameters(&self) -> NumberingParameters {
+        self.0.parameters.numbering
+    }
+
+    fn get_xover_drawing_parameters(&self) -> XoverDrawingParameters {
+        self.0.parameters.xover_drawing
+    }
+
+    fn get_strand_ends_parameters(&self) -> StrandEndsParameters {
+        self.0.parameters.strand_ends
+    }
+
+    fn get_xover_guide_parameters(&self) -> XoverGuideParameters {
+        self.0.parameters.xover_guides
+    }
+
+    fn get_heat_map(&self) -> Option<HeatMapKind> {
+        self.0.parameters.heat_map
     }
 
     fn get_simulation_state(&self) -> SimulationState {
         self.0.design.get_simulation_state()
     }
 
+    fn get_twist_axis(&self) -> Option<TwistAxis> {
+        self.0.parameters.twist_axis
+    }
+
     fn get_dna_parameters(&self) -> HelixParameters {
         self.0.design.get_dna_parameters()
     }
@@ -118,6 +188,16 @@ impl GuiState for AppState {
         self.0.selection.selected_group.clone()
     }
 
+    fn get_selected_group_pivot_position(&self) -> Option<ultraviolet::Vec3> {
+        let reader = self.get_design_reader();
+        self.0
+            .selection
+            .selected_group
+            .and_then(|g_id| reader.get_group_attributes(g_id))
+            .and_then(|attributes| attributes.pivot)
+            .map(|pivot| pivot.position)
+    }
+
     fn get_suggestion_parameters(&self) -> &SuggestionParameters {
         &self.0.parameters.suggestion_parameters
     }
@@ -130,6 +210,14 @@ impl GuiState for AppState {
         self.0.parameters.follow_stereography
     }
 
+    fn inside_out_inspection(&self) -> bool {
+        self.0.parameters.inside_out_inspection
+    }
+
+    fn get_brush_parameters(&self) -> BrushParameters {
+        self.0.parameters.brush
+    }
+
     fn show_stereographic_camera(&self) -> bool {
         self.0.parameters.show_stereography
     }
@@ -146,6 +234,18 @@ impl GuiState for AppState {
         self.0.parameters.inverted_y_scroll
     }
 
+    fn get_theme(&self) -> ensnano_interactor::graphics::Theme {
+        self.0.parameters.theme
+    }
+
+    fn get_color_vision_mode(&self) -> ColorVisionMode {
+        self.0.parameters.color_vision_mode
+    }
+
+    fn get_language(&self) -> ensnano_interactor::i18n::Language {
+        self.0.parameters.language
+    }
+
     fn want_all_helices_on_axis(&self) -> bool {
         self.0.parameters.all_helices_on_axis
     }
@@ -158,6 +258,10 @@ impl GuiState for AppState {
         self.0.parameters.show_bezier_paths
     }
 
+    fn get_show_insertion_badges(&self) -> bool {
+        self.0.parameters.show_insertion_badges
+    }
+
     fn get_selected_bezier_path(&self) -> Option<ensnano_design::BezierPathId> {
         if let Some(Selection::BezierVertex(vertex)) = self.0.selection.selection.get(0) {
             Some(vertex.path_id)
@@ -170,6 +274,10 @@ impl GuiState for AppState {
         self.0.exporting
     }
 
+    fn get_design_browser(&self) -> Option<&DesignBrowserContent> {
+        self.0.design_browser.as_deref()
+    }
+
     fn is_transitory(&self) -> bool {
         !self.is_in_stable_state()
     }
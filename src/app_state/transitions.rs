@@ -29,6 +29,8 @@ pub struct AppStateTransition {
     pub label: TransitionLabel,
     /// The position of the 3d scene's camera at the moment the operation was performed
     pub camera_3d: Camera3D,
+    /// The last time at which an operation was merged in this transition
+    pub date: std::time::Instant,
 }
 
 /// A label describing an operation.
@@ -42,6 +44,31 @@ impl<T: Into<Cow<'static, str>>> From<T> for TransitionLabel {
     }
 }
 
+/// Beginning of the labels of the operations that are typically repeated many times in a row
+/// (nudges, parameter tweaks...). Consecutive such operations can share a single undo entry.
+const COALESCABLE_LABELS: &[&str] = &[
+    "Rotation of",
+    "Translation of",
+    "Translate 2D helices",
+    "Move 2D helices",
+    "Set roll of helix",
+    "Set isometry of helices",
+    "Set grid position",
+    "Set grid orientation",
+    "Set phantom helices parameters",
+    "Color modification",
+    "Update design metadata",
+];
+
+impl TransitionLabel {
+    /// Return true if consecutive transitions with this label may be merged in the undo stack
+    pub fn is_coalescable(&self) -> bool {
+        COALESCABLE_LABELS
+            .iter()
+            .any(|prefix| self.0.starts_with(prefix))
+    }
+}
+
 impl AsRef<str> for TransitionLabel {
     fn as_ref(&self) -> &str {
         &self.0.as_ref()
@@ -0,0 +1,204 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Applying the same treatment to all the designs of a directory, without opening a window.
+//!
+//! `ensnano batch DIRECTORY ACTION` processes every `.ens` file of `DIRECTORY` and prints one
+//! line per file. The exit code is non-zero if at least one file could not be processed.
+//!
+//! The available actions are
+//! * `export-staples`: write the staples of the design in a `.xlsx` file next to it,
+//! * `export-cadnano`, `export-oxdna`, `export-pdb`: export the design next to it,
+//! * `export-print`: write a PDF printout of the 2D view of the design next to it,
+//! * `recolor-staples`: pick a new color for all the staples and save the design,
+//! * `backup`: write a backup of the design in the current file format next to it,
+//! * `script=FILE`: apply the operations listed in the JSON file `FILE` and save the design. The
+//!   file contains an array of [RemoteOperation].
+
+use crate::app_state::AppState;
+use crate::controller::{export_extenstion, DownloadStapleError, StaplesDownloader};
+use crate::remote_operation::RemoteOperation;
+use ensnano_design::SavingInformation;
+use ensnano_exports::ExportType;
+use ensnano_interactor::{
+    consts::{ENS_BACKUP_EXTENSION, ENS_EXTENSION},
+    DesignOperation,
+};
+use std::path::{Path, PathBuf};
+
+/// The name of the subcommand.
+pub const BATCH_SUBCOMMAND: &str = "batch";
+
+enum BatchAction {
+    ExportStaples,
+    Export(ExportType),
+    RecolorStaples,
+    Backup,
+    Script(Vec<RemoteOperation>),
+}
+
+impl BatchAction {
+    fn parse(action: &str) -> Result<Self, String> {
+        match action {
+            "export-staples" => Ok(Self::ExportStaples),
+            "export-cadnano" => Ok(Self::Export(ExportType::Cadnano)),
+            "export-oxdna" => Ok(Self::Export(ExportType::Oxdna)),
+            "export-pdb" => Ok(Self::Export(ExportType::Pdb)),
+            "export-print" => Ok(Self::Export(ExportType::Print2d(Default::default()))),
+            "recolor-staples" => Ok(Self::RecolorStaples),
+            "backup" => Ok(Self::Backup),
+            _ => {
+                if let Some(script) = action.strip_prefix("script=") {
+                    let content = std::fs::read_to_string(script)
+                        .map_err(|e| format!("Could not read {}: {}", script, e))?;
+                    serde_json::from_str(&content)
+                        .map(Self::Script)
+                        .map_err(|e| format!("Could not parse {}: {}", script, e))
+                } else {
+                    Err(format!("Unknown action {}", action))
+                }
+            }
+        }
+    }
+
+    /// Apply the action to the design stored at `path`. On success, return a description of what
+    /// was done.
+    fn apply(&self, path: &Path) -> Result<String, String> {
+        let mut app_state =
+            AppState::import_design(path.to_path_buf()).map_err(|e| e.to_string())?;
+        match self {
+            Self::ExportStaples => {
+                let reader = app_state.get_design_reader();
+                let ok = reader.download_staples().map_err(|e| match e {
+                    DownloadStapleError::NoScaffoldSet => "No scaffold set".to_string(),
+                    DownloadStapleError::ScaffoldSequenceNotSet => {
+                        "No sequence set for the scaffold".to_string()
+                    }
+                    DownloadStapleError::SeveralDesignNoneSelected => {
+                        "No design selected".to_string()
+                    }
+                })?;
+                let xlsx_path = path.with_extension("xlsx");
+                reader
+                    .write_staples_xlsx(&xlsx_path)
+                    .map_err(|e| e.to_string())?;
+                let mut message = format!("wrote {}", xlsx_path.to_string_lossy());
+                for warning in ok.warnings {
+                    message.push_str(&format!(" (warning: {})", warning));
+                }
+                Ok(message)
+            }
+            Self::Export(export_type) => {
+                let export_path = path.with_extension(export_extenstion(export_type.clone()));
+                app_state
+                    .export(&export_path, export_type.clone())
+                    .map(|success| success.message().replace('\n', " "))
+                    .map_err(|e| e.to_string())
+            }
+            Self::RecolorStaples => {
+                apply_operations(&mut app_state, vec![DesignOperation::RecolorStaples])?;
+                save(&mut app_state, path.to_path_buf())
+            }
+            Self::Backup => save(&mut app_state, path.with_extension(ENS_BACKUP_EXTENSION)),
+            Self::Script(operations) => {
+                let operations = operations
+                    .iter()
+                    .cloned()
+                    .map(RemoteOperation::to_operation)
+                    .collect();
+                apply_operations(&mut app_state, operations)?;
+                save(&mut app_state, path.to_path_buf())
+            }
+        }
+    }
+}
+
+fn apply_operations(
+    app_state: &mut AppState,
+    operations: Vec<DesignOperation>,
+) -> Result<(), String> {
+    for operation in operations {
+        app_state
+            .apply_design_op(operation)
+            .map_err(|e| e.to_string())?;
+        app_state.update();
+    }
+    Ok(())
+}
+
+fn save(app_state: &mut AppState, path: PathBuf) -> Result<String, String> {
+    let saving_info = SavingInformation {
+        camera: app_state
+            .get_design_reader()
+            .get_design()
+            .get_saved_camera()
+            .cloned(),
+        provenance: None,
+        thumbnail_png: None,
+    };
+    app_state
+        .save_design(&path, saving_info)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("saved {}", path.to_string_lossy()))
+}
+
+/// Run the batch subcommand with the arguments that follow it and return the exit code.
+pub fn run(args: &[String]) -> i32 {
+    let (directory, action) = match args {
+        [directory, action] => (directory, action),
+        _ => {
+            eprintln!("Usage: ensnano {} DIRECTORY ACTION", BATCH_SUBCOMMAND);
+            return 2;
+        }
+    };
+    let action = match BatchAction::parse(action) {
+        Ok(action) => action,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension() == Some(ENS_EXTENSION.as_ref()))
+            .collect(),
+        Err(e) => {
+            eprintln!("Could not read {}: {}", directory, e);
+            return 2;
+        }
+    };
+    paths.sort();
+
+    let mut nb_failures = 0;
+    for path in paths.iter() {
+        match action.apply(path) {
+            Ok(message) => println!("OK     {}: {}", path.to_string_lossy(), message),
+            Err(e) => {
+                nb_failures += 1;
+                println!("FAILED {}: {}", path.to_string_lossy(), e)
+            }
+        }
+    }
+    println!("{} files processed, {} failed", paths.len(), nb_failures);
+    if nb_failures > 0 {
+        1
+    } else {
+        0
+    }
+}
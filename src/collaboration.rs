@@ -0,0 +1,265 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Sharing the edition of a design between two instances of ENSnano.
+//!
+//! One instance hosts the session with `--host=PORT` and the other one joins it with
+//! `--join=ws://ADDRESS:PORT`. The design operations applied on one side are sent over a
+//! WebSocket and applied on the other side as soon as they are received. There is no conflict
+//! resolution: when both users modify the same element, the last operation to be applied wins.
+//!
+//! By default the session only accepts connections from the same machine. To accept remote
+//! collaborators, the host must give the address to listen on with `--host=ADDRESS:PORT` and a
+//! shared secret with `--token=SECRET`. The collaborator must give the same `--token`, which is
+//! sent as the first message of the connection.
+//!
+//! Only the operations that can be described independently of the state of the user interface
+//! are shared, see [RemoteOperation].
+
+use crate::remote_operation::RemoteOperation;
+use ensnano_interactor::DesignOperation;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::time::Duration;
+use tungstenite::{Message, WebSocket};
+
+/// How long the networking thread waits for an incoming message before sending the pending
+/// outgoing ones.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long the host waits for the token of a collaborator that just connected.
+const AUTHENTICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The connection with the other instance of ENSnano, if any.
+#[derive(Default)]
+pub struct Collaboration {
+    session: Option<Session>,
+}
+
+/// The channels used to communicate with the networking thread.
+struct Session {
+    outgoing: mpsc::Sender<String>,
+    incoming: mpsc::Receiver<RemoteOperation>,
+}
+
+impl Collaboration {
+    /// Host or join a session if requested by the command line arguments.
+    pub fn from_args(args: &[String]) -> Self {
+        let token = args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--token="))
+            .unwrap_or_default()
+            .to_string();
+        if let Some(address) = args.iter().find_map(|arg| arg.strip_prefix("--host=")) {
+            match listening_address(address) {
+                Ok(address) if !address.ip().is_loopback() && token.is_empty() => {
+                    log::error!(
+                        "Refusing to host a session on {} without a --token",
+                        address
+                    );
+                    Default::default()
+                }
+                Ok(address) => Self::host(address, token),
+                Err(e) => {
+                    log::error!("Invalid address {}: {}", address, e);
+                    Default::default()
+                }
+            }
+        } else if let Some(url) = args.iter().find_map(|arg| arg.strip_prefix("--join=")) {
+            Self::join(url.to_string(), token)
+        } else {
+            Default::default()
+        }
+    }
+
+    /// Wait for another instance that knows `token` to connect on `address`.
+    fn host(address: SocketAddr, token: String) -> Self {
+        Self::start(move || {
+            let listener = TcpListener::bind(address).map_err(|e| e.to_string())?;
+            log::info!("Waiting for a collaborator on {}", address);
+            loop {
+                let (stream, peer) = listener.accept().map_err(|e| e.to_string())?;
+                log::info!("Collaborator connected from {}", peer);
+                match authenticate(stream, &token) {
+                    Ok(socket) => break Ok(socket),
+                    Err(e) => log::warn!("Rejected connection from {}: {}", peer, e),
+                }
+            }
+        })
+    }
+
+    /// Connect to the instance hosting a session at `url`.
+    fn join(url: String, token: String) -> Self {
+        Self::start(move || {
+            let stream = TcpStream::connect(host_and_port(&url)?).map_err(|e| e.to_string())?;
+            let (mut socket, _) =
+                tungstenite::client(url.as_str(), stream).map_err(|e| e.to_string())?;
+            socket
+                .send(Message::Text(token))
+                .map_err(|e| e.to_string())?;
+            socket
+                .get_ref()
+                .set_read_timeout(Some(POLL_INTERVAL))
+                .map_err(|e| e.to_string())?;
+            log::info!("Joined collaboration session at {}", url);
+            Ok(socket)
+        })
+    }
+
+    fn start<F>(connect: F) -> Self
+    where
+        F: FnOnce() -> Result<WebSocket<TcpStream>, String> + Send + 'static,
+    {
+        let (outgoing_snd, outgoing_rcv) = mpsc::channel();
+        let (incoming_snd, incoming_rcv) = mpsc::channel();
+        std::thread::spawn(move || match connect() {
+            Ok(socket) => run_session(socket, incoming_snd, outgoing_rcv),
+            Err(e) => log::error!("Could not start collaboration session: {}", e),
+        });
+        Self {
+            session: Some(Session {
+                outgoing: outgoing_snd,
+                incoming: incoming_rcv,
+            }),
+        }
+    }
+
+    /// Send `operation` to the other instance if it can be shared.
+    pub fn broadcast(&self, operation: &DesignOperation) {
+        if let Some(session) = self.session.as_ref() {
+            if let Some(remote) = RemoteOperation::from_operation(operation) {
+                match serde_json::to_string(&remote) {
+                    Ok(message) => {
+                        if session.outgoing.send(message).is_err() {
+                            log::warn!("Collaboration session is closed");
+                        }
+                    }
+                    Err(e) => log::error!("Could not serialize {:?}: {}", remote, e),
+                }
+            }
+        }
+    }
+
+    /// The operations received from the other instance since the last call.
+    pub fn received_operations(&self) -> Vec<DesignOperation> {
+        self.session
+            .as_ref()
+            .map(|session| {
+                session
+                    .incoming
+                    .try_iter()
+                    .map(RemoteOperation::to_operation)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The address to listen on, given either as a port, in which case only local connections are
+/// accepted, or as `ADDRESS:PORT`.
+fn listening_address(address: &str) -> Result<SocketAddr, String> {
+    if let Ok(port) = address.parse::<u16>() {
+        Ok(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+    } else {
+        address
+            .parse()
+            .map_err(|e: std::net::AddrParseError| e.to_string())
+    }
+}
+
+/// Accept the WebSocket connection on `stream` if its first message is `token`.
+fn authenticate(stream: TcpStream, token: &str) -> Result<WebSocket<TcpStream>, String> {
+    stream
+        .set_read_timeout(Some(AUTHENTICATION_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let mut socket = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+    match socket.read().map_err(|e| e.to_string())? {
+        Message::Text(received) if received == token => (),
+        _ => {
+            let _ = socket.close(None);
+            return Err("wrong token".to_string());
+        }
+    }
+    socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .map_err(|e| e.to_string())?;
+    Ok(socket)
+}
+
+/// Extract the address to connect to from a `ws://host:port/path` url.
+fn host_and_port(url: &str) -> Result<String, String> {
+    let without_scheme = url
+        .strip_prefix("ws://")
+        .ok_or_else(|| format!("{} is not a ws:// url", url))?;
+    Ok(without_scheme
+        .split('/')
+        .next()
+        .unwrap_or(without_scheme)
+        .to_string())
+}
+
+/// Forward the messages between the socket and the main thread until one side closes the
+/// connection.
+fn run_session<S: Read + Write>(
+    mut socket: WebSocket<S>,
+    incoming: mpsc::Sender<RemoteOperation>,
+    outgoing: mpsc::Receiver<String>,
+) {
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(operation) => {
+                    if incoming.send(operation).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => log::error!("Could not read remote operation: {}", e),
+            },
+            Ok(Message::Close(_)) => {
+                log::info!("Collaborator left the session");
+                break;
+            }
+            Ok(_) => (),
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => {
+                log::error!("Collaboration session ended: {}", e);
+                break;
+            }
+        }
+        loop {
+            match outgoing.try_recv() {
+                Ok(message) => {
+                    if let Err(e) = socket.send(Message::Text(message)) {
+                        log::error!("Could not send operation: {}", e);
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    return;
+                }
+            }
+        }
+    }
+}
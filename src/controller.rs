@@ -18,6 +18,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 
 //! Handles windows and dialog (Alert, and file pickers) interactions.
 
+use crate::app_state::TransitionLabel;
 use crate::PastePosition;
 mod download_intervals;
 mod download_staples;
@@ -33,7 +34,10 @@ use ensnano_exports::{ExportResult, ExportType};
 use ensnano_interactor::{
     application::Notification, DesignOperation, RevolutionSurfaceSystemDescriptor,
 };
-use ensnano_interactor::{DesignReader, RigidBodyConstants, Selection};
+use ensnano_interactor::{
+    DesignBrowserChoice, DesignReader, DesignTemplate, RigidBodyConstants, Selection,
+};
+pub(crate) use quit::export_extenstion;
 use quit::*;
 mod set_scaffold_sequence;
 use set_scaffold_sequence::*;
@@ -194,12 +198,23 @@ pub(crate) trait MainState: ScaffoldSetter {
     fn pop_action(&mut self) -> Option<Action>;
     fn exit_control_flow(&mut self);
     fn new_design(&mut self);
+    fn new_design_from_template(&mut self, template: DesignTemplate);
+    /// Start loading a design in a background thread. The completion of the loading is reported
+    /// by `poll_file_io`.
     fn load_design(&mut self, path: PathBuf) -> Result<(), LoadDesignError>;
+    /// Start saving the design in a background thread. The completion of the saving is reported
+    /// by `poll_file_io`.
     fn save_design(&mut self, path: &PathBuf) -> Result<(), SaveDesignError>;
+    /// If the background loading or saving of a design is finished, apply its result.
+    fn poll_file_io(&mut self) -> Option<FileIoOutcome>;
     fn save_backup(&mut self) -> Result<(), SaveDesignError>;
     fn get_chanel_reader(&mut self) -> &mut ChannelReader;
     fn apply_operation(&mut self, operation: DesignOperation);
     fn apply_silent_operation(&mut self, operation: DesignOperation);
+    /// Start recording a batch of operations that will produce a single undo entry
+    fn begin_batch(&mut self, label: TransitionLabel);
+    /// Stop recording the current batch of operations
+    fn commit_batch(&mut self);
     fn undo(&mut self);
     fn redo(&mut self);
     fn get_staple_downloader(&self) -> Box<dyn StaplesDownloader>;
@@ -218,43 +233,191 @@ pub(crate) trait MainState: ScaffoldSetter {
     fn apply_paste(&mut self);
     fn duplicate(&mut self);
     fn delete_selection(&mut self);
+    /// Delete the base pairs of the selected nucleotides
+    fn delete_base_pairs(&mut self);
+    /// Insert a base pair next to the base pairs of the selected nucleotides
+    fn insert_base_pairs(&mut self);
+    /// Remove the sequences of the selected strands, or of all the strands if no strand is
+    /// selected
+    fn clear_sequences(&mut self);
+    /// Write the bases given by the scaffold sequence only in the selected domains of the
+    /// scaffold
+    fn assign_scaffold_sequence_to_selected_domains(&mut self);
+    /// What would be removed from the design by `delete_selection`, if it deletes strands or
+    /// cross-overs
+    fn deletion_summary(&mut self) -> Option<DeletionSummary>;
+    /// True if large deletions must be confirmed by the user
+    fn confirm_large_deletions(&self) -> bool;
     fn scaffold_to_selection(&mut self);
     fn start_helix_simulation(&mut self, parameters: RigidBodyConstants);
     fn start_grid_simulation(&mut self, parameters: RigidBodyConstants);
+    fn start_bundle_simulation(&mut self, parameters: RigidBodyConstants);
     fn start_revolution_simulation(&mut self, desc: RevolutionSurfaceSystemDescriptor);
-    fn start_roll_simulation(&mut self, target_helices: Option<Vec<usize>>);
+    fn start_roll_simulation(
+        &mut self,
+        target_helices: Option<Vec<usize>>,
+        target_xovers: Option<Vec<usize>>,
+    );
     fn update_simulation(&mut self, request: SimulationRequest);
     fn set_roll_of_selected_helices(&mut self, roll: f32);
+    fn set_ideal_roll_of_selected_helices(&mut self);
+    fn place_insertions_on_selected_helices(
+        &mut self,
+        target: ensnano_design::design_operations::InsertionPlacementTarget,
+    );
     fn turn_selection_into_anchor(&mut self);
     fn set_visibility_sieve(&mut self, compl: bool);
     fn clear_visibility_sieve(&mut self);
     fn need_save(&self) -> Option<Option<PathBuf>>;
     fn get_current_design_directory(&self) -> Option<&Path>;
     fn get_current_file_name(&self) -> Option<&Path>;
+    /// Record the provenance that must be written in the design when it is saved at `path` as a
+    /// variant of the current file
+    fn record_variant_provenance(&mut self, path: PathBuf);
+    /// Open the dialog listing the design files of `directory`, or of the user's documents folder
+    /// if `directory` is `None`
+    fn open_design_browser(&mut self, directory: Option<PathBuf>);
+    /// Update the list of files displayed by the open dialog, and return the answer of the user
+    /// if there is one
+    fn poll_design_browser(&mut self) -> Option<DesignBrowserChoice>;
+    fn close_design_browser(&mut self);
     fn set_current_group_pivot(&mut self, pivot: GroupPivot);
     fn translate_group_pivot(&mut self, translation: Vec3);
     fn rotate_group_pivot(&mut self, rotation: Rotor3);
     fn create_new_camera(&mut self);
     fn select_camera(&mut self, camera_id: ensnano_design::CameraId);
     fn select_favorite_camera(&mut self, n_camera: u32);
+    /// Record the current camera and visibility as a new step of the presentation
+    fn add_presentation_step(&mut self);
+    /// Display the next (if `forward` is true) or previous step of the presentation
+    fn go_to_presentation_step(&mut self, forward: bool);
     fn update_camera(&mut self, camera_id: ensnano_design::CameraId);
+    fn copy_to_clipboard(&mut self, content: String);
+    fn set_read_only(&mut self, read_only: bool);
+    fn toggle_detached_left_panel(&mut self);
+    fn open_overlay(&mut self, overlay_type: OverlayType);
+    fn close_overlay(&mut self, overlay_type: OverlayType);
+    fn open_context_menu(&mut self, selection: Selection);
     fn toggle_2d(&mut self);
     fn make_all_suggested_xover(&mut self, doubled: bool);
+    /// The moves that would bring the selected cross-overs, or the cross-overs of the selected
+    /// strands, to the closest positions at which they are the shortest
+    fn plan_xover_respacing(&mut self) -> Vec<ensnano_design::design_operations::XoverRespacing>;
+    /// The moves of the nicks of the selected strands, or of all the nicks if no strand is
+    /// selected, that strengthen the seed domains of the staples
+    fn plan_nick_optimization(&mut self) -> Vec<ensnano_design::design_operations::NickMove>;
+    /// Random sequences, satisfying `parameters`, for the nucleotides of unknown basis of the
+    /// selected strands, or of all the strands if no strand is selected
+    fn plan_random_sequences(
+        &mut self,
+        parameters: &ensnano_design::design_operations::RandomSequenceParameters,
+    ) -> Vec<ensnano_design::design_operations::StrandSequences>;
+    /// The strands that are empty, shorter than `min_length` or unpaired
+    fn plan_strand_cleanup(
+        &mut self,
+        min_length: usize,
+    ) -> Vec<ensnano_design::design_operations::StrandCleanup>;
+    /// The scaffold nucleotides that are not paired with exactly one staple nucleotide, or
+    /// `None` if there is no scaffold
+    fn audit_scaffold_pairing(
+        &mut self,
+    ) -> Option<Vec<ensnano_design::pairing_audit::PairingIssue>>;
     fn need_backup(&self) -> bool;
     fn check_backup(&mut self);
     fn flip_split_views(&mut self);
     fn start_twist(&mut self, g_id: GridId);
+    fn start_helices_twist(&mut self);
     fn set_expand_insertions(&mut self, expand: bool);
     fn set_exporting(&mut self, exporting: bool);
     fn load_3d_object(&mut self, path: PathBuf);
     fn load_svg(&mut self, path: PathBuf);
+    /// Highlight the differences between the design stored at `path` and the current design.
+    fn compare_with_design_file(&mut self, path: PathBuf) -> Result<(), LoadDesignError>;
     fn get_design_path_and_notify(&mut self, notificator: fn(Option<Arc<Path>>) -> Notification);
 }
 
+/// The elements that are removed from the design by the deletion of the selection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeletionSummary {
+    pub nb_strands: usize,
+    pub nb_nucleotides: usize,
+    pub nb_xovers: usize,
+}
+
+impl DeletionSummary {
+    /// Deletions of at least this number of nucleotides must be confirmed
+    const LARGE_DELETION_NB_NUCLEOTIDES: usize = 100;
+    /// Deletions of at least this number of cross-overs must be confirmed
+    const LARGE_DELETION_NB_XOVERS: usize = 20;
+
+    pub fn is_large(&self) -> bool {
+        self.nb_nucleotides >= Self::LARGE_DELETION_NB_NUCLEOTIDES
+            || self.nb_xovers >= Self::LARGE_DELETION_NB_XOVERS
+    }
+}
+
+/// The outcome of the loading or saving of a design in a background thread
+pub enum FileIoOutcome {
+    Loaded,
+    Saved,
+    Cancelled,
+    Failed(String),
+}
+
+/// Wait for the loading or saving of a design to finish, and transition to `on_success` or
+/// `on_error` depending on the result.
+struct WaitFileIo {
+    on_success: Box<dyn State>,
+    on_error: Box<dyn State>,
+    /// A message to display before transitioning to `on_success`
+    success_message: Option<&'static str>,
+}
+
+impl WaitFileIo {
+    fn new(
+        on_success: Box<dyn State>,
+        on_error: Box<dyn State>,
+        success_message: Option<&'static str>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            on_success,
+            on_error,
+            success_message,
+        })
+    }
+}
+
+impl State for WaitFileIo {
+    fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
+        match main_state.poll_file_io() {
+            None => self,
+            Some(FileIoOutcome::Loaded) | Some(FileIoOutcome::Saved) => {
+                if let Some(msg) = self.success_message {
+                    TransitionMessage::new(msg, rfd::MessageLevel::Info, self.on_success)
+                } else {
+                    self.on_success
+                }
+            }
+            Some(FileIoOutcome::Cancelled) => self.on_error,
+            Some(FileIoOutcome::Failed(msg)) => {
+                TransitionMessage::new(msg, rfd::MessageLevel::Error, self.on_error)
+            }
+        }
+    }
+}
+
 pub enum LoadDesignError {
     JsonError(serde_json::Error),
     ScadnanoImportError(ensnano_design::scadnano::ScadnanoImportError),
-    IncompatibleVersion { current: String, required: String },
+    IncompatibleVersion {
+        current: String,
+        required: String,
+    },
+    IoError(std::io::Error),
+    BinaryFormatError(rmp_serde::decode::Error),
+    NotABinaryDesign,
+    /// The loading was cancelled by the user
+    Cancelled,
 }
 
 impl std::fmt::Display for LoadDesignError {
@@ -277,6 +440,10 @@ impl std::fmt::Display for LoadDesignError {
                 Requiered version: {required}"
                 )
             }
+            Self::IoError(e) => write!(f, "Could not read file: {e}"),
+            Self::BinaryFormatError(e) => write!(f, "Invalid binary design file: {e}"),
+            Self::NotABinaryDesign => write!(f, "Not a binary design file"),
+            Self::Cancelled => write!(f, "Loading cancelled"),
         }
     }
 }
@@ -284,6 +451,12 @@ impl std::fmt::Display for LoadDesignError {
 #[derive(Debug)]
 pub struct SaveDesignError(String);
 
+impl std::fmt::Display for SaveDesignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl<E: std::error::Error> From<E> for SaveDesignError {
     fn from(e: E) -> Self {
         Self(format!("{}", e))
@@ -294,6 +467,18 @@ impl SaveDesignError {
     pub fn cannot_open_default_dir() -> Self {
         Self(crate::consts::CANNOT_OPEN_DEFAULT_DIR.to_string())
     }
+
+    pub fn cancelled() -> Self {
+        Self(crate::consts::SAVE_CANCELLED.to_string())
+    }
+
+    pub fn file_operation_in_progress() -> Self {
+        Self(crate::consts::FILE_OPERATION_IN_PROGRESS.to_string())
+    }
+
+    pub fn is_cancellation(&self) -> bool {
+        self.0 == crate::consts::SAVE_CANCELLED
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -22,15 +22,19 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex, Weak};
 
+use ensnano_interactor::RevolutionRelaxationProgress;
+
 use crate::app_state::{
-    ShiftOptimizationResult, ShiftOptimizerReader, SimulationInterface, SimulationReader,
-    SimulationUpdate,
+    FileIoReader, FileIoResult, FileIoTask, ShiftOptimizationResult, ShiftOptimizerReader,
+    SimulationInterface, SimulationReader, SimulationUpdate,
 };
 #[derive(Default)]
 pub struct ChannelReader {
     scaffold_shift_optimization_progress: Option<mpsc::Receiver<f32>>,
     scaffold_shift_optimization_result: Option<mpsc::Receiver<ShiftOptimizationResult>>,
     simulation_interface: Option<Weak<Mutex<dyn SimulationInterface>>>,
+    revolution_relaxation_progress: Option<mpsc::Receiver<RevolutionRelaxationProgress>>,
+    file_io_task: Option<FileIoTask>,
 }
 
 pub enum ChannelReaderUpdate {
@@ -40,6 +44,15 @@ pub enum ChannelReaderUpdate {
     ScaffoldShiftOptimizationResult(ShiftOptimizationResult),
     SimulationUpdate(Box<dyn SimulationUpdate>),
     SimulationExpired,
+    /// Progress has been made in the relaxation of a revolution surface
+    RevolutionRelaxationProgress(RevolutionRelaxationProgress),
+    /// Progress has been made in the loading or saving of a design
+    FileIoProgress {
+        task_name: String,
+        progress: f32,
+    },
+    /// A design has been loaded or saved
+    FileIoFinished(FileIoResult),
 }
 
 impl ChannelReader {
@@ -70,15 +83,66 @@ impl ChannelReader {
         if invalidated {
             self.simulation_interface = None;
         }
+        if let Some(progress) = self.get_revolution_relaxation_progress() {
+            updates.push(ChannelReaderUpdate::RevolutionRelaxationProgress(progress));
+        }
+        self.get_file_io_updates(&mut updates);
         updates
     }
 
+    fn get_file_io_updates(&mut self, updates: &mut Vec<ChannelReaderUpdate>) {
+        let finished = if let Some(task) = self.file_io_task.as_ref() {
+            if let Some(progress) = task.progress.try_iter().last() {
+                updates.push(ChannelReaderUpdate::FileIoProgress {
+                    task_name: task.name.clone(),
+                    progress,
+                });
+            }
+            match task.result.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => Some(FileIoResult::Crashed {
+                    path: task.path.clone(),
+                }),
+            }
+        } else {
+            None
+        };
+        if let Some(result) = finished {
+            self.file_io_task = None;
+            updates.push(ChannelReaderUpdate::FileIoFinished(result));
+        }
+    }
+
+    /// Return true if a design is being loaded or saved
+    pub fn is_doing_file_io(&self) -> bool {
+        self.file_io_task.is_some()
+    }
+
+    /// Ask the thread that is loading or saving a design to stop
+    pub fn cancel_file_io(&self) {
+        if let Some(task) = self.file_io_task.as_ref() {
+            task.cancel()
+        }
+    }
+
     fn get_scaffold_shift_optimization_progress(&self) -> Option<f32> {
         self.scaffold_shift_optimization_progress
             .as_ref()
             .and_then(|chanel| chanel.try_recv().ok())
     }
 
+    fn get_revolution_relaxation_progress(&mut self) -> Option<RevolutionRelaxationProgress> {
+        let chanel = self.revolution_relaxation_progress.as_ref()?;
+        let ret = chanel.try_iter().last();
+        if ret.is_none() {
+            if let Err(mpsc::TryRecvError::Disconnected) = chanel.try_recv() {
+                self.revolution_relaxation_progress = None;
+            }
+        }
+        ret
+    }
+
     fn get_scaffold_shift_optimization_result(&self) -> Option<ShiftOptimizationResult> {
         self.scaffold_shift_optimization_result
             .as_ref()
@@ -96,8 +160,22 @@ impl ShiftOptimizerReader for ChannelReader {
     }
 }
 
+impl FileIoReader for ChannelReader {
+    fn attach_file_io_task(&mut self, task: FileIoTask) {
+        self.file_io_task = Some(task);
+    }
+}
+
 impl SimulationReader for ChannelReader {
     fn attach_state(&mut self, state_chanel: &std::sync::Arc<Mutex<dyn SimulationInterface>>) {
         self.simulation_interface = Some(Arc::downgrade(state_chanel));
+        self.revolution_relaxation_progress = None;
+    }
+
+    fn attach_revolution_progress_chanel(
+        &mut self,
+        chanel: mpsc::Receiver<RevolutionRelaxationProgress>,
+    ) {
+        self.revolution_relaxation_progress = Some(chanel);
     }
 }
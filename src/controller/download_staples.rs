@@ -20,6 +20,7 @@ use super::{messages, MainState, NormalState, State, TransitionMessage};
 
 use crate::dialog;
 use dialog::{MustAckMessage, PathInput};
+use rust_xlsxwriter::XlsxError;
 use std::path::PathBuf;
 
 #[derive(Default)]
@@ -164,14 +165,22 @@ fn download_staples(
     _design_id: usize,
     path: PathBuf,
 ) -> Box<dyn State> {
-    downlader.write_staples_xlsx(&path);
+    if path.extension().map_or(false, |ext| ext == "csv") {
+        downlader.write_staples_csv(&path);
+    } else {
+        if let Err(e) = downlader.write_staples_xlsx(&path) {
+            let msg = messages::failed_staples_export_msg(&e);
+            return TransitionMessage::new(msg, rfd::MessageLevel::Error, Box::new(NormalState));
+        }
+    }
     let msg = messages::successfull_staples_export_msg(&path);
     TransitionMessage::new(msg, rfd::MessageLevel::Error, Box::new(NormalState))
 }
 
 pub trait StaplesDownloader {
     fn download_staples(&self) -> Result<DownloadStapleOk, DownloadStapleError>;
-    fn write_staples_xlsx(&self, xlsx_path: &PathBuf);
+    fn write_staples_xlsx(&self, xlsx_path: &PathBuf) -> Result<(), XlsxError>;
+    fn write_staples_csv(&self, csv_path: &PathBuf);
     fn write_intervals(&self, origami_path: &PathBuf);
     fn default_shift(&self) -> Option<usize>;
 }
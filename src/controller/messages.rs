@@ -16,6 +16,8 @@ ENSnano, a 3d graphical application for DNA nanostructures.
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use ensnano_design::design_operations::{CleanupReason, NickMove, StrandCleanup, XoverRespacing};
+use ensnano_design::pairing_audit::{PairingDefect, PairingIssue};
 use std::path::Path;
 pub const NO_FILE_RECIEVED_LOAD: &'static str = "Open canceled";
 pub const NO_FILE_RECIEVED_SAVE: &'static str = "Save canceled";
@@ -44,6 +46,10 @@ pub fn successfull_staples_export_msg<P: AsRef<Path>>(file: P) -> String {
     )
 }
 
+pub fn failed_staples_export_msg<E: std::fmt::Display>(reason: &E) -> String {
+    format!("Could not write staples: {reason}")
+}
+
 pub const OXDNA_EXPORT_FAILED: &'static str = "OxDNA export failed";
 pub const SAVE_DESIGN_FAILED: &'static str = "Could not save design";
 pub const SAVE_BEFORE_EXIT: &'static str = "Do you want to save your design before exiting?";
@@ -95,6 +101,165 @@ pub const DESIGN_WRITE_FILTER: Filters = &[("ENSnano files", &[crate::consts::EN
 
 pub const SEQUENCE_FILTERS: Filters = &[("Text files", &["txt"])];
 
+pub const NO_XOVER_TO_RESPACE: &'static str =
+    "The selected cross-overs are already at their best positions.\n
+Select cross-overs or strands to respace their cross-overs.";
+
+/// The number of moves listed in the preview of a cross-over respacing
+const MAX_LISTED_XOVER_RESPACINGS: usize = 20;
+
+pub fn xover_respacing_preview(moves: &[XoverRespacing]) -> String {
+    let mut ret = format!("{} cross-overs will be moved:\n", moves.len());
+    for respacing in moves.iter().take(MAX_LISTED_XOVER_RESPACINGS) {
+        let (prime3, prime5) = respacing.from;
+        ret.push_str(&format!(
+            "\nStrand {}: helix {} -> helix {} at position {}, moved by {:+}",
+            respacing.strand_id,
+            prime3.helix,
+            prime5.helix,
+            prime3.position,
+            respacing.shift()
+        ));
+    }
+    if moves.len() > MAX_LISTED_XOVER_RESPACINGS {
+        ret.push_str(&format!(
+            "\n... and {} more",
+            moves.len() - MAX_LISTED_XOVER_RESPACINGS
+        ));
+    }
+    ret.push_str(
+        "\n\nThe lengths of the domains joined by these cross-overs will be adjusted. Apply?",
+    );
+    ret
+}
+
+pub const NO_NICK_TO_OPTIMIZE: &'static str =
+    "No nick can be moved to strengthen the seed domains of the staples.\n
+The breakpoints of the staples can only be optimized once a scaffold sequence is set.";
+
+/// The number of moves listed in the preview of a nick optimization
+const MAX_LISTED_NICK_MOVES: usize = 20;
+
+pub fn nick_optimization_preview(moves: &[NickMove]) -> String {
+    let mut ret = format!("{} nicks will be moved:\n", moves.len());
+    for nick_move in moves.iter().take(MAX_LISTED_NICK_MOVES) {
+        ret.push_str(&format!(
+            "\nStrands {} / {}: helix {} at position {}, moved by {:+}, seed Tm {:.1}°C -> {:.1}°C",
+            nick_move.prime3_strand,
+            nick_move.prime5_strand,
+            nick_move.from.helix,
+            nick_move.from.position,
+            nick_move.shift,
+            nick_move.seed_tm_before,
+            nick_move.seed_tm_after,
+        ));
+    }
+    if moves.len() > MAX_LISTED_NICK_MOVES {
+        ret.push_str(&format!(
+            "\n... and {} more",
+            moves.len() - MAX_LISTED_NICK_MOVES
+        ));
+    }
+    ret.push_str("\n\nThe lengths of the staples will be adjusted. Apply?");
+    ret
+}
+
+pub const NO_SEQUENCE_TO_FILL: &'static str =
+    "All the nucleotides of the selected strands already have a basis.\n
+Select strands to fill their sequences, or select nothing to fill the sequences of all the strands.";
+
+pub const NO_STRAND_TO_CLEAN: &'static str =
+    "No empty, short or unpaired strand was found in the design.";
+
+/// The number of strands listed in the preview of a strand cleanup
+const MAX_LISTED_STRAND_CLEANUPS: usize = 20;
+
+pub fn strand_cleanup_preview(cleanup: &[StrandCleanup]) -> String {
+    let mut ret = format!("{} strands will be removed:\n", cleanup.len());
+    for c in cleanup.iter().take(MAX_LISTED_STRAND_CLEANUPS) {
+        let reason = match c.reason {
+            CleanupReason::Empty => "empty",
+            CleanupReason::Short => "too short",
+            CleanupReason::Unpaired => "unpaired",
+        };
+        ret.push_str(&format!(
+            "\nStrand {}: {} nt, {}",
+            c.strand_id, c.length, reason
+        ));
+    }
+    if cleanup.len() > MAX_LISTED_STRAND_CLEANUPS {
+        ret.push_str(&format!(
+            "\n... and {} more",
+            cleanup.len() - MAX_LISTED_STRAND_CLEANUPS
+        ));
+    }
+    ret.push_str("\n\nRemove these strands?");
+    ret
+}
+
+pub const SCAFFOLD_FULLY_PAIRED: &'static str =
+    "Each nucleotide of the scaffold is paired with exactly one staple nucleotide.";
+
+/// The number of nucleotides listed in the report of a pairing audit
+const MAX_LISTED_PAIRING_ISSUES: usize = 20;
+
+pub fn pairing_audit_report(issues: &[PairingIssue]) -> String {
+    let nb_unpaired = issues
+        .iter()
+        .filter(|i| i.defect == PairingDefect::Unpaired)
+        .count();
+    let mut ret = format!(
+        "{} scaffold nucleotides are unpaired and {} are paired with several staple nucleotides:\n",
+        nb_unpaired,
+        issues.len() - nb_unpaired
+    );
+    for issue in issues.iter().take(MAX_LISTED_PAIRING_ISSUES) {
+        let nucl = issue.scaffold_nucl;
+        let defect = match &issue.defect {
+            PairingDefect::Unpaired => String::from("unpaired"),
+            PairingDefect::MultiplyPaired(partners) => format!(
+                "paired with {}",
+                partners
+                    .iter()
+                    .map(|n| format!("h{}:nt{}", n.helix, n.position))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        ret.push_str(&format!(
+            "\nh{}:nt{} {}: {}",
+            nucl.helix,
+            nucl.position,
+            if nucl.forward { "→" } else { "←" },
+            defect
+        ));
+    }
+    if issues.len() > MAX_LISTED_PAIRING_ISSUES {
+        ret.push_str(&format!(
+            "\n... and {} more",
+            issues.len() - MAX_LISTED_PAIRING_ISSUES
+        ));
+    }
+    ret
+}
+
+pub fn deletion_summary(summary: &super::DeletionSummary) -> String {
+    let mut ret = String::from("The following elements will be removed:\n");
+    if summary.nb_strands > 0 {
+        ret.push_str(&format!(
+            "\n{} strands ({} nucleotides)",
+            summary.nb_strands, summary.nb_nucleotides
+        ));
+    }
+    if summary.nb_xovers > 0 {
+        ret.push_str(&format!("\n{} cross-overs", summary.nb_xovers));
+    }
+    ret.push_str(
+        "\n\nThis confirmation can be disabled in the parameters tab. Delete the selection?",
+    );
+    ret
+}
+
 pub const CHANGING_DNA_PARAMETERS_WARNING: &'static str =
     "Are you sure that you want to change DNA parameters?";
 
@@ -102,12 +267,15 @@ pub const OXDNA_CONFIG_EXTENSTION: &str = "oxdna";
 
 pub const OXDNA_CONFIG_FILTERS: Filters = &[("Oxdna config files", &[OXDNA_CONFIG_EXTENSTION])];
 
-pub const STAPLES_FILTER: Filters = &[("Excel files", &["xlsx"])];
+pub const STAPLES_FILTER: Filters = &[("Excel files", &["xlsx"]), ("Csv files", &["csv"])];
 
 pub const ORIGAMI_FLTER: Filters = &[("Origami files", &[crate::consts::ORIGAMI_EXTENSION])];
 
 pub const PDB_FILTER: Filters = &[("Pdb files", &["pdb"])];
 pub const CADNANO_FILTER: Filters = &[("Cadnano files", &["json"])];
+pub const NUCLEOTIDES_CSV_FILTER: Filters = &[("Csv files", &["csv"])];
+pub const NUCLEOTIDES_JSON_FILTER: Filters = &[("Json files", &["json"])];
+pub const PDF_FILTER: Filters = &[("Pdf files", &["pdf"])];
 
 pub const STL_FILTER: Filters = &[("Stl files", &["stl"])];
 
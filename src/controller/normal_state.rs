@@ -22,7 +22,13 @@ use super::download_intervals::DownloadIntervals;
 use super::messages::CHANGING_DNA_PARAMETERS_WARNING;
 use super::*;
 use ensnano_design::group_attributes::GroupPivot;
-use ensnano_design::{grid::GridId, HelixParameters};
+use ensnano_design::{
+    design_operations::{
+        InsertionPlacementTarget, NickMove, RandomSequenceParameters, XoverRespacing,
+    },
+    grid::{GridId, PhantomParameters},
+    HelixParameters,
+};
 use ensnano_interactor::{
     graphics::FogParameters, HyperboloidOperation, RevolutionSurfaceSystemDescriptor,
 };
@@ -36,7 +42,10 @@ impl State for NormalState {
     fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
         if let Some(action) = main_state.pop_action() {
             match action {
-                Action::NewDesign => Box::new(NewDesign::init(main_state.need_save())),
+                Action::NewDesign => Box::new(NewDesign::init(main_state.need_save(), None)),
+                Action::NewDesignFromTemplate(template) => {
+                    Box::new(NewDesign::init(main_state.need_save(), Some(template)))
+                }
                 Action::SaveAs => save_as(),
                 Action::QuickSave => {
                     if let Some(path) = main_state
@@ -48,6 +57,22 @@ impl State for NormalState {
                         save_as()
                     }
                 }
+                Action::SaveIncrementalCopy => {
+                    if let Some(path) = main_state.get_current_file_name() {
+                        quicksave(incremental_copy_path(path))
+                    } else {
+                        save_as()
+                    }
+                }
+                Action::SaveVariant => {
+                    if let Some(path) = main_state.get_current_file_name() {
+                        let variant_path = incremental_copy_path(path);
+                        main_state.record_variant_provenance(variant_path.clone());
+                        quicksave(variant_path)
+                    } else {
+                        save_as()
+                    }
+                }
                 Action::DownloadStaplesRequest => Box::new(DownloadStaples::default()),
                 Action::DownloadOrigamiRequest => Box::new(DownloadIntervals::default()),
                 Action::SetScaffoldSequence { shift } => Box::new(SetScaffoldSequence::init(shift)),
@@ -57,8 +82,16 @@ impl State for NormalState {
                     self
                 }
                 Action::Export(export_type) => export(export_type),
-                Action::CloseOverlay(_) | Action::OpenOverlay(_) => {
-                    println!("unexpected action");
+                Action::OpenOverlay(overlay_type) => {
+                    main_state.open_overlay(overlay_type);
+                    self
+                }
+                Action::CloseOverlay(overlay_type) => {
+                    main_state.close_overlay(overlay_type);
+                    self
+                }
+                Action::OpenContextMenu(selection) => {
+                    main_state.open_context_menu(selection);
                     self
                 }
                 Action::ChangeUiSize(size) => {
@@ -76,6 +109,14 @@ impl State for NormalState {
                     main_state.apply_silent_operation(op);
                     self.make_progress(main_state)
                 }
+                Action::DesignOperationBatch { label, operations } => {
+                    main_state.begin_batch(label.into());
+                    for op in operations {
+                        main_state.apply_operation(op);
+                    }
+                    main_state.commit_batch();
+                    self.make_progress(main_state)
+                }
                 Action::Undo => {
                     main_state.undo();
                     self
@@ -103,6 +144,14 @@ impl State for NormalState {
                     self.toggle_helices_persistance(main_state, persistant)
                 }
                 Action::ToggleSmallSphere(small) => self.toggle_small_spheres(main_state, small),
+                Action::SetGridLabels(labelled) => self.set_grid_labels(main_state, labelled),
+                Action::SetPhantomParameters(parameters) => {
+                    self.set_phantom_parameters(main_state, parameters)
+                }
+                Action::FinishSettingPhantomParameters => {
+                    main_state.finish_operation();
+                    self
+                }
                 Action::LoadDesign(Some(path)) => Box::new(Load::known_path(path)),
                 Action::LoadDesign(None) => Load::load(main_state.need_save(), LoadType::Design),
                 Action::Import3DObject => {
@@ -117,6 +166,7 @@ impl State for NormalState {
                     }
                 }
                 Action::ImportSvg => Load::load(None, LoadType::SvgPath),
+                Action::CompareWithDesignFile => Load::load(None, LoadType::ComparedDesign),
                 Action::SuspendOp => {
                     log::info!("Suspending operation");
                     main_state.finish_operation();
@@ -144,7 +194,34 @@ impl State for NormalState {
                     self
                 }
                 Action::DeleteSelection => {
-                    main_state.delete_selection();
+                    let summary = main_state
+                        .deletion_summary()
+                        .filter(|s| s.is_large() && main_state.confirm_large_deletions());
+                    if let Some(summary) = summary {
+                        Box::new(YesNo::new(
+                            messages::deletion_summary(&summary),
+                            Box::new(DeletingSelection),
+                            self,
+                        ))
+                    } else {
+                        main_state.delete_selection();
+                        self
+                    }
+                }
+                Action::DeleteBasePairs => {
+                    main_state.delete_base_pairs();
+                    self
+                }
+                Action::ClearSequences => {
+                    main_state.clear_sequences();
+                    self
+                }
+                Action::AssignScaffoldSequenceToSelection => {
+                    main_state.assign_scaffold_sequence_to_selected_domains();
+                    self
+                }
+                Action::InsertBasePairs => {
+                    main_state.insert_base_pairs();
                     self
                 }
                 Action::ScaffoldToSelection => {
@@ -184,6 +261,10 @@ impl State for NormalState {
                     main_state.start_grid_simulation(parameters);
                     self
                 }
+                Action::RigidBundleSimulation { parameters } => {
+                    main_state.start_bundle_simulation(parameters);
+                    self
+                }
                 Action::RevolutionSimulation { desc } => {
                     main_state.start_revolution_simulation(desc);
                     self
@@ -196,10 +277,18 @@ impl State for NormalState {
                     main_state.update_simulation(SimulationRequest::FinishRelaxation);
                     self
                 }
+                Action::PlaceInsertions(target) => {
+                    main_state.place_insertions_on_selected_helices(target);
+                    self
+                }
                 Action::RollHelices(roll) => {
                     main_state.set_roll_of_selected_helices(roll);
                     self
                 }
+                Action::SetIdealRoll => {
+                    main_state.set_ideal_roll_of_selected_helices();
+                    self
+                }
                 Action::ResetSimulation => {
                     main_state.update_simulation(SimulationRequest::Reset);
                     self
@@ -209,7 +298,7 @@ impl State for NormalState {
                     self
                 }
                 Action::RollRequest(request) => {
-                    main_state.start_roll_simulation(request.target_helices);
+                    main_state.start_roll_simulation(request.target_helices, request.target_xovers);
                     self
                 }
                 Action::Fog(fog) => {
@@ -264,6 +353,26 @@ impl State for NormalState {
                     main_state.select_favorite_camera(n);
                     self
                 }
+                Action::AddPresentationStep => {
+                    main_state.add_presentation_step();
+                    self
+                }
+                Action::PresentationStep { forward } => {
+                    main_state.go_to_presentation_step(forward);
+                    self
+                }
+                Action::SetReadOnly(read_only) => {
+                    main_state.set_read_only(read_only);
+                    self
+                }
+                Action::ToggleDetachedLeftPanel => {
+                    main_state.toggle_detached_left_panel();
+                    self
+                }
+                Action::CopyToClipboard(content) => {
+                    main_state.copy_to_clipboard(content);
+                    self
+                }
                 Action::UpdateCamera(camera_id) => {
                     main_state.update_camera(camera_id);
                     self
@@ -286,6 +395,10 @@ impl State for NormalState {
                     main_state.start_twist(g_id);
                     self
                 }
+                Action::TwistHelices => {
+                    main_state.start_helices_twist();
+                    self
+                }
                 Action::SetDnaParameters(param) => Box::new(YesNo::new(
                     CHANGING_DNA_PARAMETERS_WARNING,
                     Box::new(ChangingDnaParameters(param)),
@@ -304,6 +417,87 @@ impl State for NormalState {
                     self
                 }
                 Action::OptimizeShift => Box::new(SetScaffoldSequence::optimize_shift()),
+                Action::RespaceXovers => {
+                    let moves = main_state.plan_xover_respacing();
+                    if moves.is_empty() {
+                        TransitionMessage::new(
+                            messages::NO_XOVER_TO_RESPACE,
+                            rfd::MessageLevel::Info,
+                            self,
+                        )
+                    } else {
+                        Box::new(YesNo::new(
+                            messages::xover_respacing_preview(&moves),
+                            Box::new(RespacingXovers(moves)),
+                            self,
+                        ))
+                    }
+                }
+                Action::OptimizeNicks => {
+                    let moves = main_state.plan_nick_optimization();
+                    if moves.is_empty() {
+                        TransitionMessage::new(
+                            messages::NO_NICK_TO_OPTIMIZE,
+                            rfd::MessageLevel::Info,
+                            self,
+                        )
+                    } else {
+                        Box::new(YesNo::new(
+                            messages::nick_optimization_preview(&moves),
+                            Box::new(OptimizingNicks(moves)),
+                            self,
+                        ))
+                    }
+                }
+                Action::FillRandomSequences { parameters } => {
+                    let sequences = main_state.plan_random_sequences(&parameters);
+                    if sequences.is_empty() {
+                        TransitionMessage::new(
+                            messages::NO_SEQUENCE_TO_FILL,
+                            rfd::MessageLevel::Info,
+                            self,
+                        )
+                    } else {
+                        main_state
+                            .apply_operation(DesignOperation::SetDomainSequences { sequences });
+                        self
+                    }
+                }
+                Action::CleanupStrands { min_length } => {
+                    let cleanup = main_state.plan_strand_cleanup(min_length);
+                    if cleanup.is_empty() {
+                        TransitionMessage::new(
+                            messages::NO_STRAND_TO_CLEAN,
+                            rfd::MessageLevel::Info,
+                            self,
+                        )
+                    } else {
+                        Box::new(YesNo::new(
+                            messages::strand_cleanup_preview(&cleanup),
+                            Box::new(CleaningStrands(
+                                cleanup.iter().map(|c| c.strand_id).collect(),
+                            )),
+                            self,
+                        ))
+                    }
+                }
+                Action::AuditScaffoldPairing => match main_state.audit_scaffold_pairing() {
+                    None => TransitionMessage::new(
+                        messages::NO_SCAFFOLD_SET,
+                        rfd::MessageLevel::Warning,
+                        self,
+                    ),
+                    Some(issues) if issues.is_empty() => TransitionMessage::new(
+                        messages::SCAFFOLD_FULLY_PAIRED,
+                        rfd::MessageLevel::Info,
+                        self,
+                    ),
+                    Some(issues) => TransitionMessage::new(
+                        messages::pairing_audit_report(&issues),
+                        rfd::MessageLevel::Warning,
+                        self,
+                    ),
+                },
                 // Defaults
                 action => {
                     println!("Not implemented {:?}", action);
@@ -327,6 +521,48 @@ impl State for ChangingDnaParameters {
     }
 }
 
+/// The user has accepted the removal of the strands proposed by a cleanup
+struct CleaningStrands(Vec<usize>);
+
+impl State for CleaningStrands {
+    fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
+        main_state.begin_batch("Strand cleanup".into());
+        main_state.apply_operation(DesignOperation::RmStrands { strand_ids: self.0 });
+        main_state.commit_batch();
+        Box::new(NormalState)
+    }
+}
+
+/// The user has confirmed the deletion of the selection
+struct DeletingSelection;
+
+impl State for DeletingSelection {
+    fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
+        main_state.delete_selection();
+        Box::new(NormalState)
+    }
+}
+
+/// The user has accepted the moves of cross-overs that were proposed to them
+struct RespacingXovers(Vec<XoverRespacing>);
+
+impl State for RespacingXovers {
+    fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
+        main_state.apply_operation(DesignOperation::RespaceXovers { moves: self.0 });
+        Box::new(NormalState)
+    }
+}
+
+/// The user has accepted the moves of nicks that were proposed to them
+struct OptimizingNicks(Vec<NickMove>);
+
+impl State for OptimizingNicks {
+    fn make_progress(self: Box<Self>, main_state: &mut dyn MainState) -> Box<dyn State> {
+        main_state.apply_operation(DesignOperation::MoveNicks { moves: self.0 });
+        Box::new(NormalState)
+    }
+}
+
 impl NormalState {
     fn turn_selection_into_grid(self: Box<Self>, main_state: &mut dyn MainState) -> Box<Self> {
         let selection = main_state.get_selection();
@@ -381,6 +617,35 @@ impl NormalState {
         self
     }
 
+    fn set_grid_labels(
+        self: Box<Self>,
+        main_state: &mut dyn MainState,
+        labelled: bool,
+    ) -> Box<Self> {
+        let grid_ids =
+            ensnano_interactor::extract_grids(main_state.get_selection().as_ref().as_ref());
+        if !grid_ids.is_empty() {
+            main_state.apply_operation(DesignOperation::SetGridLabels { grid_ids, labelled });
+        }
+        self
+    }
+
+    fn set_phantom_parameters(
+        self: Box<Self>,
+        main_state: &mut dyn MainState,
+        parameters: PhantomParameters,
+    ) -> Box<Self> {
+        let grid_ids =
+            ensnano_interactor::extract_grids(main_state.get_selection().as_ref().as_ref());
+        if !grid_ids.is_empty() {
+            main_state.apply_operation(DesignOperation::SetPhantomParameters {
+                grid_ids,
+                parameters,
+            });
+        }
+        self
+    }
+
     fn toggle_helices_persistance(
         self: Box<Self>,
         main_state: &mut dyn MainState,
@@ -420,6 +685,37 @@ fn quicksave<P: AsRef<Path>>(starting_path: P) -> Box<dyn State> {
     })
 }
 
+/// The path of the next incremental copy of the design stored at `path`.
+///
+/// `origami.ens` is followed by `origami_v001.ens`, and `origami_v002.ens` by `origami_v003.ens`.
+/// Versions for which a file already exists are skipped.
+fn incremental_copy_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let (base, mut version) = split_version_suffix(stem);
+    loop {
+        version += 1;
+        let candidate = path.with_file_name(format!(
+            "{base}_v{version:03}.{}",
+            crate::consts::ENS_EXTENSION
+        ));
+        if !candidate.exists() {
+            return candidate;
+        }
+    }
+}
+
+/// Split a file stem of the form `name_vXXX` into `name` and `XXX`. If the stem does not end
+/// with a version number, return the whole stem and version 0.
+fn split_version_suffix(stem: &str) -> (&str, usize) {
+    stem.rsplit_once("_v")
+        .filter(|(_, version)| !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|(base, version)| Some((base, version.parse().ok()?)))
+        .unwrap_or((stem, 0))
+}
+
 fn export(export_type: ExportType) -> Box<dyn State> {
     let on_success = Box::new(NormalState);
     let on_error = TransitionMessage::new(
@@ -434,7 +730,8 @@ use ensnano_design::grid::{GridDescriptor, GridTypeDescr};
 
 use ensnano_interactor::HyperboloidRequest;
 use ensnano_interactor::{
-    application::Notification, DesignOperation, RigidBodyConstants, RollRequest,
+    application::Notification, DesignOperation, DesignTemplate, RigidBodyConstants, RollRequest,
+    Selection,
 };
 /// An action to be performed at the end of an event loop iteration, and that will have an effect
 /// on the main application state, e.g. Closing the window, or toggling between 3D/2D views.
@@ -442,8 +739,14 @@ use ensnano_interactor::{
 pub enum Action {
     LoadDesign(Option<PathBuf>),
     NewDesign,
+    /// Replace the current design by one generated from a template
+    NewDesignFromTemplate(DesignTemplate),
     SaveAs,
     QuickSave,
+    /// Save the design next to the current file, with an incremented version number
+    SaveIncrementalCopy,
+    /// Save the design as the next incremental copy, recording the current file as its parent
+    SaveVariant,
     DownloadStaplesRequest,
     DownloadOrigamiRequest,
     /// Trigger the sequence of action that will set the scaffold of the sequence.
@@ -455,11 +758,18 @@ pub enum Action {
     Export(ExportType),
     CloseOverlay(OverlayType),
     OpenOverlay(OverlayType),
+    /// Open a menu listing the actions that can be performed on the selected element
+    OpenContextMenu(Selection),
     ChangeUiSize(UiSize),
     InvertScrollY(bool),
     ErrorMsg(String),
     DesignOperation(DesignOperation),
     SilentDesignOperation(DesignOperation),
+    /// Apply several operations that can be undone all at once
+    DesignOperationBatch {
+        label: String,
+        operations: Vec<DesignOperation>,
+    },
     Undo,
     Redo,
     NotifyApps(Notification),
@@ -472,9 +782,18 @@ pub enum Action {
     FinishChangingColor,
     ToggleHelicesPersistance(bool),
     ToggleSmallSphere(bool),
+    /// Show or hide the labels of the selected grids in the 3D view
+    SetGridLabels(bool),
+    SetPhantomParameters(PhantomParameters),
+    FinishSettingPhantomParameters,
     RollRequest(RollRequest),
     StopSimulation,
     RollHelices(f32),
+    /// Set the roll of the selected helices to the one that minimizes the length of their
+    /// cross-overs
+    SetIdealRoll,
+    /// Place insertions and skips on the selected helices
+    PlaceInsertions(InsertionPlacementTarget),
     Copy,
     PasteCandidate(Option<PastePosition>),
     InitPaste,
@@ -483,6 +802,9 @@ pub enum Action {
     RigidGridSimulation {
         parameters: RigidBodyConstants,
     },
+    RigidBundleSimulation {
+        parameters: RigidBodyConstants,
+    },
     RevolutionSimulation {
         desc: RevolutionSurfaceSystemDescriptor,
     },
@@ -499,6 +821,15 @@ pub enum Action {
         compl: bool,
     },
     DeleteSelection,
+    /// Delete the base pairs of the selected nucleotides
+    DeleteBasePairs,
+    /// Insert a base pair next to the base pairs of the selected nucleotides
+    InsertBasePairs,
+    /// Remove the sequences of the selected strands, or of all the strands
+    ClearSequences,
+    /// Write the bases given by the scaffold sequence only in the selected domains of the
+    /// scaffold
+    AssignScaffoldSequenceToSelection,
     ScaffoldToSelection,
     /// Save the nucleotides 3D positions by strand as a json file in the design directory
     GetDesignPathAndNotify(fn(Option<Arc<Path>>) -> Notification),
@@ -515,18 +846,68 @@ pub enum Action {
     NewCamera,
     SelectCamera(ensnano_design::CameraId),
     SelectFavoriteCamera(u32),
+    AddPresentationStep,
+    /// Go to the next or previous step of the presentation
+    PresentationStep {
+        forward: bool,
+    },
     UpdateCamera(ensnano_design::CameraId),
+    CopyToClipboard(String),
+    /// Enable or disable the viewer mode
+    SetReadOnly(bool),
+    /// Display the left panel in its own window, or put it back in the main window
+    ToggleDetachedLeftPanel,
     Toggle2D,
     MakeAllSuggestedXover {
         doubled: bool,
     },
     FlipSplitViews,
     Twist(GridId),
+    /// Twist the selected free helices around the current twist axis
+    TwistHelices,
     SetDnaParameters(HelixParameters),
     SetExpandInsertions(bool),
     AddBezierPlane,
     SetExporting(bool),
     Import3DObject,
     ImportSvg,
+    CompareWithDesignFile,
     OptimizeShift,
+    /// Move the selected cross-overs, or the cross-overs of the selected strands, to the closest
+    /// positions at which they are the shortest, after the user has accepted the moves
+    RespaceXovers,
+    /// Move the nicks of the selected strands, or all the nicks, to strengthen the seed domains
+    /// of the staples, after the user has accepted the moves
+    OptimizeNicks,
+    /// Give random bases to the nucleotides of unknown basis of the selected strands, or of all
+    /// the strands
+    FillRandomSequences {
+        parameters: RandomSequenceParameters,
+    },
+    /// Propose to remove the empty, short and unpaired strands
+    CleanupStrands {
+        min_length: usize,
+    },
+    /// List the scaffold nucleotides that are not paired with exactly one staple nucleotide
+    AuditScaffoldPairing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_suffix_is_recognized() {
+        assert_eq!(split_version_suffix("origami_v003"), ("origami", 3));
+        assert_eq!(
+            split_version_suffix("my_very_v2_origami_v12"),
+            ("my_very_v2_origami", 12)
+        );
+        assert_eq!(split_version_suffix("origami"), ("origami", 0));
+        assert_eq!(split_version_suffix("origami_v"), ("origami_v", 0));
+        assert_eq!(
+            split_version_suffix("origami_vfinal"),
+            ("origami_vfinal", 0)
+        );
+    }
 }
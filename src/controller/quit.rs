@@ -19,10 +19,11 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 use crate::controller::normal_state::NormalState;
 use crate::dialog::Filters;
 
-use super::{dialog, messages, MainState, State, TransitionMessage, YesNo};
+use super::{dialog, messages, MainState, State, TransitionMessage, WaitFileIo, YesNo};
 
 use dialog::PathInput;
 use ensnano_exports::ExportType;
+use ensnano_interactor::{DesignBrowserChoice, DesignTemplate};
 use std::path::Path;
 
 pub(super) struct Quit {
@@ -121,8 +122,17 @@ impl Load {
 
 use std::path::PathBuf;
 enum LoadStep {
-    Init { need_save: Option<Option<PathBuf>> },
-    AskPath { path_input: Option<PathInput> },
+    Init {
+        need_save: Option<Option<PathBuf>>,
+    },
+    AskPath {
+        path_input: Option<PathInput>,
+    },
+    /// The design files of a folder are listed in the open dialog
+    Browse {
+        /// The folder whose files must be listed next, while the user is picking it
+        folder_input: Option<PathInput>,
+    },
     GotPath(PathBuf),
 }
 
@@ -131,6 +141,8 @@ pub(super) enum LoadType {
     Design,
     Object3D,
     SvgPath,
+    /// A design that is compared with the current one
+    ComparedDesign,
 }
 
 impl Load {
@@ -158,10 +170,12 @@ impl State for Load {
                 state.get_current_design_directory(),
                 self.load_type,
             ),
+            LoadStep::Browse { folder_input } => browse(folder_input, state),
             LoadStep::GotPath(path) => match self.load_type {
                 LoadType::Design => load_design(path, state),
                 LoadType::Object3D => load_3d_object(path, state),
                 LoadType::SvgPath => load_svg(path, state),
+                LoadType::ComparedDesign => compare_with_design(path, state),
             },
         }
     }
@@ -233,10 +247,14 @@ fn ask_path<P: AsRef<Path>>(
             })
         }
     } else {
+        if let LoadType::Design = load_type {
+            return Load::start_browsing(starting_directory.map(|p| p.as_ref().to_path_buf()));
+        }
         let filters = match load_type {
             LoadType::Object3D => messages::OBJECT3D_FILTERS,
             LoadType::Design => messages::DESIGN_LOAD_FILTER,
             LoadType::SvgPath => messages::SVG_FILTERS,
+            LoadType::ComparedDesign => messages::DESIGN_LOAD_FILTER,
         };
         let path_input = dialog::load(starting_directory, filters);
         Box::new(Load {
@@ -248,6 +266,67 @@ fn ask_path<P: AsRef<Path>>(
     }
 }
 
+impl Load {
+    fn start_browsing(directory: Option<PathBuf>) -> Box<dyn State> {
+        Box::new(OpenDesignBrowser { directory })
+    }
+
+    fn browsing(folder_input: Option<PathInput>) -> Box<Self> {
+        Box::new(Self {
+            step: LoadStep::Browse { folder_input },
+            load_type: LoadType::Design,
+        })
+    }
+}
+
+/// Open the dialog listing the design files of `directory`.
+struct OpenDesignBrowser {
+    directory: Option<PathBuf>,
+}
+
+impl State for OpenDesignBrowser {
+    fn make_progress(self: Box<Self>, state: &mut dyn MainState) -> Box<dyn State> {
+        state.open_design_browser(self.directory);
+        Load::browsing(None)
+    }
+}
+
+fn browse(folder_input: Option<PathInput>, state: &mut dyn MainState) -> Box<dyn State> {
+    if let Some(folder_input) = folder_input {
+        return match folder_input.get() {
+            None => Load::browsing(Some(folder_input)),
+            Some(Some(directory)) => Load::start_browsing(Some(directory)),
+            // The user did not pick another folder, keep listing the current one
+            Some(None) => Load::browsing(None),
+        };
+    }
+    match state.poll_design_browser() {
+        None => Load::browsing(None),
+        Some(DesignBrowserChoice::Open(path)) => {
+            state.close_design_browser();
+            Box::new(Load::known_path(path))
+        }
+        Some(DesignBrowserChoice::ChooseFolder) => Load::browsing(Some(dialog::get_dir())),
+        Some(DesignBrowserChoice::OtherFile) => {
+            state.close_design_browser();
+            let path_input = dialog::load(
+                state.get_current_design_directory(),
+                messages::DESIGN_LOAD_FILTER,
+            );
+            Box::new(Load {
+                step: LoadStep::AskPath {
+                    path_input: Some(path_input),
+                },
+                load_type: LoadType::Design,
+            })
+        }
+        Some(DesignBrowserChoice::Cancel) => {
+            state.close_design_browser();
+            Box::new(super::NormalState)
+        }
+    }
+}
+
 fn load_design(path: PathBuf, state: &mut dyn MainState) -> Box<dyn State> {
     if let Err(err) = state.load_design(path) {
         TransitionMessage::new(
@@ -256,7 +335,11 @@ fn load_design(path: PathBuf, state: &mut dyn MainState) -> Box<dyn State> {
             Box::new(super::NormalState),
         )
     } else {
-        Box::new(super::NormalState)
+        WaitFileIo::new(
+            Box::new(super::NormalState),
+            Box::new(super::NormalState),
+            None,
+        )
     }
 }
 
@@ -270,8 +353,22 @@ fn load_svg(path: PathBuf, state: &mut dyn MainState) -> Box<dyn State> {
     Box::new(super::NormalState)
 }
 
+fn compare_with_design(path: PathBuf, state: &mut dyn MainState) -> Box<dyn State> {
+    if let Err(err) = state.compare_with_design_file(path) {
+        TransitionMessage::new(
+            format!("Error when loading design:\n{err}"),
+            rfd::MessageLevel::Error,
+            Box::new(super::NormalState),
+        )
+    } else {
+        Box::new(super::NormalState)
+    }
+}
+
 pub(super) struct NewDesign {
     step: NewStep,
+    /// If not None, the new design is generated from this template instead of being empty
+    template: Option<DesignTemplate>,
 }
 
 enum NewStep {
@@ -280,15 +377,17 @@ enum NewStep {
 }
 
 impl NewDesign {
-    pub fn init(need_save: Option<Option<PathBuf>>) -> Self {
+    pub fn init(need_save: Option<Option<PathBuf>>, template: Option<DesignTemplate>) -> Self {
         Self {
             step: NewStep::Init { need_save },
+            template,
         }
     }
 
-    fn make_new_design() -> Box<dyn State> {
+    fn make_new_design(template: Option<DesignTemplate>) -> Box<dyn State> {
         Box::new(Self {
             step: NewStep::MakeNewDesign,
+            template,
         })
     }
 }
@@ -298,29 +397,39 @@ impl State for NewDesign {
         match self.step {
             NewStep::Init { need_save } => {
                 if let Some(path) = need_save {
-                    init_new_design(path)
+                    init_new_design(path, self.template)
                 } else {
-                    new_design(main_state)
+                    new_design(main_state, self.template)
                 }
             }
-            NewStep::MakeNewDesign => new_design(main_state),
+            NewStep::MakeNewDesign => new_design(main_state, self.template),
         }
     }
 }
 
-fn init_new_design(path_to_save: Option<PathBuf>) -> Box<dyn State> {
-    let yes = save_before_new(path_to_save);
-    let no = NewDesign::make_new_design();
+fn init_new_design(
+    path_to_save: Option<PathBuf>,
+    template: Option<DesignTemplate>,
+) -> Box<dyn State> {
+    let yes = save_before_new(path_to_save, template);
+    let no = NewDesign::make_new_design(template);
     Box::new(YesNo::new(messages::SAVE_BEFORE_NEW, yes, no))
 }
 
-fn new_design(main_state: &mut dyn MainState) -> Box<dyn State> {
-    main_state.new_design();
+fn new_design(main_state: &mut dyn MainState, template: Option<DesignTemplate>) -> Box<dyn State> {
+    if let Some(template) = template {
+        main_state.new_design_from_template(template);
+    } else {
+        main_state.new_design();
+    }
     Box::new(super::NormalState)
 }
 
-fn save_before_new(path_to_save: Option<PathBuf>) -> Box<dyn State> {
-    let on_success = NewDesign::make_new_design();
+fn save_before_new(
+    path_to_save: Option<PathBuf>,
+    template: Option<DesignTemplate>,
+) -> Box<dyn State> {
+    let on_success = NewDesign::make_new_design(template);
     let on_error = Box::new(super::NormalState);
     if let Some(path) = path_to_save {
         Box::new(SaveWithPath {
@@ -361,11 +470,7 @@ impl State for SaveAs {
                             self.on_error,
                         )
                     } else {
-                        TransitionMessage::new(
-                            "Saved successfully".to_string(),
-                            rfd::MessageLevel::Info,
-                            self.on_success,
-                        )
+                        WaitFileIo::new(self.on_success, self.on_error, Some("Saved successfully"))
                     }
                 } else {
                     TransitionMessage::new(
@@ -404,11 +509,7 @@ impl State for SaveWithPath {
                 self.on_error,
             )
         } else {
-            TransitionMessage::new(
-                "Saved successfully".to_string(),
-                rfd::MessageLevel::Info,
-                self.on_success,
-            )
+            WaitFileIo::new(self.on_success, self.on_error, Some("Saved successfully"))
         }
     }
 }
@@ -479,12 +580,15 @@ impl State for Exporting {
     }
 }
 
-fn export_extenstion(export_type: ExportType) -> &'static str {
+pub(crate) fn export_extenstion(export_type: ExportType) -> &'static str {
     match export_type {
         ExportType::Oxdna => messages::OXDNA_CONFIG_EXTENSTION,
         ExportType::Pdb => "pdb",
         ExportType::Cadnano => "json",
         ExportType::Cando => "cndo",
+        ExportType::NucleotidesCsv => "csv",
+        ExportType::NucleotidesJson => "json",
+        ExportType::Print2d(_) => "pdf",
     }
 }
 
@@ -494,5 +598,8 @@ fn export_filters(export_type: ExportType) -> &'static Filters {
         ExportType::Pdb => &messages::PDB_FILTER,
         ExportType::Cadnano => &messages::CADNANO_FILTER,
         ExportType::Cando => todo!(),
+        ExportType::NucleotidesCsv => &messages::NUCLEOTIDES_CSV_FILTER,
+        ExportType::NucleotidesJson => &messages::NUCLEOTIDES_JSON_FILTER,
+        ExportType::Print2d(_) => &messages::PDF_FILTER,
     }
 }
@@ -32,6 +32,7 @@ use std::sync::mpsc;
 use std::thread;
 pub type Filters = &'static [(&'static str, &'static [&'static str])];
 
+use ensnano_interactor::i18n::tr;
 use std::borrow::Cow;
 /// A question to which the user must answer yes or no
 pub struct YesNoQuestion(mpsc::Receiver<bool>);
@@ -43,7 +44,7 @@ impl YesNoQuestion {
 
 pub fn yes_no_dialog(message: Cow<'static, str>) -> YesNoQuestion {
     let msg = rfd::AsyncMessageDialog::new()
-        .set_description(message.as_ref())
+        .set_description(tr(message.as_ref()))
         .set_buttons(rfd::MessageButtons::YesNo)
         .show();
     let (snd, rcv) = mpsc::channel();
@@ -70,7 +71,7 @@ impl MustAckMessage {
 pub fn blocking_message(message: Cow<'static, str>, level: rfd::MessageLevel) -> MustAckMessage {
     let msg = rfd::AsyncMessageDialog::new()
         .set_level(level)
-        .set_description(message.as_ref())
+        .set_description(tr(message.as_ref()))
         .show();
     let (snd, rcv) = mpsc::channel();
     thread::spawn(move || {
@@ -170,7 +171,7 @@ pub fn get_file_to_write<P1: AsRef<Path>, P2: AsRef<Path>>(
     PathInput(rcv)
 }
 
-#[allow(dead_code)]
+/// Ask the user to pick a directory.
 pub fn get_dir() -> PathInput {
     let dialog = rfd::AsyncFileDialog::new().pick_folder();
     let (snd, rcv) = mpsc::channel();
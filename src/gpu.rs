@@ -0,0 +1,233 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Creation of the graphics device and recovery from graphics errors.
+//!
+//! At startup, the backends are tried in order until one of them provides a device. If none
+//! does, a dialog listing the available adapters is shown to help diagnosing the issue.
+//! When the device is lost at runtime, a new device is created and the applications that draw on
+//! it are recreated.
+
+use ensnano_interactor::graphics::GpuPreference;
+use iced_wgpu::wgpu;
+use iced_winit::winit::window::Window;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Determine wgpu backends.
+///
+/// On some windows machine, only the DX12 backends will work. So the `dx12_only` feature forces
+/// its use.
+#[cfg(not(feature = "dx12_only"))]
+const BACKENDS: &[wgpu::Backends] = &[wgpu::Backends::PRIMARY, wgpu::Backends::SECONDARY];
+#[cfg(feature = "dx12_only")]
+const BACKENDS: &[wgpu::Backends] = &[wgpu::Backends::DX12];
+
+/// Determine if wgpu validation errors should panic.
+///
+/// By default the errors are logged. They panic if the `panic_on_wgpu_errors` feature is enabled.
+#[cfg(not(feature = "panic_on_wgpu_errors"))]
+const PANIC_ON_WGPU_ERRORS: bool = false;
+#[cfg(feature = "panic_on_wgpu_errors")]
+const PANIC_ON_WGPU_ERRORS: bool = true;
+
+pub struct GpuContext {
+    pub instance: wgpu::Instance,
+    pub surface: wgpu::Surface,
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+    pub adapter_info: wgpu::AdapterInfo,
+}
+
+impl GpuContext {
+    /// Create a device that can draw on `window`, trying the available backends in order.
+    pub fn new(window: &Window, preference: GpuPreference) -> Result<Self, GpuSetupError> {
+        for backends in BACKENDS.iter().cloned() {
+            match iced_winit::futures::executor::block_on(Self::with_backends(
+                window, backends, preference,
+            )) {
+                Ok(ret) => {
+                    log::info!(
+                        "Using adapter {} ({:?})",
+                        ret.adapter_info.name,
+                        ret.adapter_info.backend
+                    );
+                    return Ok(ret);
+                }
+                Err(e) => log::warn!("Could not use backends {:?}: {}", backends, e),
+            }
+        }
+        let instance = wgpu::Instance::new(wgpu::Backends::all());
+        Err(GpuSetupError {
+            adapters: instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .map(|adapter| adapter.get_info())
+                .collect(),
+        })
+    }
+
+    async fn with_backends(
+        window: &Window,
+        backends: wgpu::Backends,
+        preference: GpuPreference,
+    ) -> Result<Self, String> {
+        let instance = wgpu::Instance::new(backends);
+        let surface = unsafe { instance.create_surface(window) };
+        let adapter = if let Some(adapter) =
+            preferred_device_type(preference).and_then(|device_type| {
+                instance.enumerate_adapters(backends).find(|a| {
+                    a.get_info().device_type == device_type && a.is_surface_supported(&surface)
+                })
+            }) {
+            adapter
+        } else {
+            instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: power_preference(preference),
+                    compatible_surface: Some(&surface),
+                    force_fallback_adapter: false,
+                })
+                .await
+                .ok_or_else(|| String::from("no compatible adapter"))?
+        };
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    features: wgpu::Features::empty(),
+                    limits: wgpu::Limits::default(),
+                    label: None,
+                },
+                None,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            instance,
+            surface,
+            device,
+            queue,
+            adapter_info: adapter.get_info(),
+        })
+    }
+}
+
+/// The type of adapter that must be picked if there is one, regardless of the power preference.
+fn preferred_device_type(preference: GpuPreference) -> Option<wgpu::DeviceType> {
+    match preference {
+        GpuPreference::Integrated => Some(wgpu::DeviceType::IntegratedGpu),
+        GpuPreference::Discrete => Some(wgpu::DeviceType::DiscreteGpu),
+        GpuPreference::LowPower | GpuPreference::HighPerformance => None,
+    }
+}
+
+fn power_preference(preference: GpuPreference) -> wgpu::PowerPreference {
+    match preference {
+        GpuPreference::LowPower | GpuPreference::Integrated => wgpu::PowerPreference::LowPower,
+        GpuPreference::HighPerformance | GpuPreference::Discrete => {
+            wgpu::PowerPreference::HighPerformance
+        }
+    }
+}
+
+/// The error returned when no backend could provide a device.
+#[derive(Debug)]
+pub struct GpuSetupError {
+    /// All the adapters that were found on the system
+    adapters: Vec<wgpu::AdapterInfo>,
+}
+
+impl GpuSetupError {
+    /// Show a dialog describing the error and listing the available adapters.
+    pub fn show_diagnostic(&self) {
+        let adapters = if self.adapters.is_empty() {
+            String::from("No adapter was found.")
+        } else {
+            self.adapters
+                .iter()
+                .map(|info| {
+                    format!(
+                        "- {} ({:?}, {:?}, vendor {:#x}, device {:#x})",
+                        info.name, info.backend, info.device_type, info.vendor, info.device
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let description = format!(
+            "Could not get a graphics device.\n\
+             This might be because gpu drivers are missing.\n\
+             You need Vulkan, Metal (for MacOS) or DirectX (for Windows) drivers to run this software.\n\n\
+             Available adapters:\n{}",
+            adapters
+        );
+        log::error!("{}", description);
+        rfd::MessageDialog::new()
+            .set_title("ENSnano")
+            .set_level(rfd::MessageLevel::Error)
+            .set_description(&description)
+            .show();
+    }
+}
+
+/// A flag that is raised when the device is lost.
+#[derive(Clone, Default)]
+pub struct DeviceStatus(Arc<AtomicBool>);
+
+impl DeviceStatus {
+    /// Log the uncaptured errors of `device` and raise the flag if the device is lost.
+    pub fn watch(device: &wgpu::Device) -> Self {
+        let ret = Self::default();
+        let lost = ret.0.clone();
+        device.on_uncaptured_error(move |e| match e {
+            wgpu::Error::OutOfMemory { .. } => log::error!("wgpu error {}", e),
+            _ if is_device_loss(&e) => {
+                log::error!("wgpu device lost: {}", e);
+                lost.store(true, Ordering::Relaxed);
+            }
+            wgpu::Error::Validation { .. } => {
+                if PANIC_ON_WGPU_ERRORS {
+                    panic!("wgpu error {}", e);
+                } else {
+                    log::error!("wgpu error {}", e);
+                }
+            }
+        });
+        ret
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The message of the error with which wgpu reports that an operation used a lost device.
+const DEVICE_LOST_MESSAGE: &str = "device is lost";
+
+/// True if `error` was caused by the loss of the device.
+///
+/// wgpu reports device losses as validation errors, so the sources of the error are inspected.
+fn is_device_loss(error: &wgpu::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(e) = source {
+        if e.to_string().contains(DEVICE_LOST_MESSAGE) {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
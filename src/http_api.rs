@@ -0,0 +1,373 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A local HTTP server that lets external tools read and modify the current design.
+//!
+//! The server is started with `--http-api=PORT --http-api-token=SECRET` and only listens on
+//! `127.0.0.1`. Every request must carry the secret in an `Authorization: Bearer SECRET` header.
+//! Requests whose `Host` or `Origin` is not local are rejected, so that web pages cannot reach the
+//! API through the user's browser. All the bodies are JSON documents, and the requests with a
+//! body must be sent with the `application/json` content type.
+//!
+//! | Method | Path          | Content                                                     |
+//! |--------|---------------|-------------------------------------------------------------|
+//! | GET    | `/staples`    | The staples, as they would be exported                      |
+//! | GET    | `/statistics` | The number of helices, strands and nucleotides              |
+//! | GET    | `/geometry`   | The position of the helices and of the strands' nucleotides |
+//! | POST   | `/operations` | Apply a [RemoteOperation], or an array of them              |
+//!
+//! The requests are forwarded to the main thread which answers them between two frames. The
+//! operations of a POST request are applied as one undoable batch. They are applied in order
+//! until one of them fails, and the answer gives the number of operations that were applied and
+//! the error of the one that failed, if any.
+
+use crate::remote_operation::RemoteOperation;
+use ensnano_interactor::{DesignOperation, DesignStatistics};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+use ultraviolet::{Rotor3, Vec3};
+
+/// How long the server waits for the main thread to answer a request.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The data that the main thread must provide to answer the read requests.
+pub trait HttpApiReader {
+    fn staples(&self) -> Vec<StapleDescription>;
+    fn statistics(&self) -> DesignStatistics;
+    fn geometry(&self) -> DesignGeometry;
+}
+
+#[derive(Debug, Serialize)]
+pub struct StapleDescription {
+    pub id: usize,
+    pub name: String,
+    pub sequence: String,
+    pub plate: usize,
+    pub well: String,
+    pub intervals: Vec<(isize, isize)>,
+    pub uuid: Option<ensnano_design::Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DesignGeometry {
+    pub helices: Vec<HelixGeometry>,
+    pub strands: Vec<StrandGeometry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HelixGeometry {
+    pub id: usize,
+    pub position: Vec3,
+    pub orientation: Rotor3,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrandGeometry {
+    pub id: usize,
+    /// The position of the nucleotides on helices, from 5' to 3'.
+    pub nucleotides: Vec<Vec3>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OperationsBody {
+    One(RemoteOperation),
+    Several(Vec<RemoteOperation>),
+}
+
+enum Endpoint {
+    Staples,
+    Statistics,
+    Geometry,
+    Operations(Vec<RemoteOperation>),
+}
+
+/// A request that must be answered by the main thread.
+pub struct ApiRequest {
+    endpoint: Endpoint,
+    answer: mpsc::Sender<ApiAnswer>,
+}
+
+struct ApiAnswer {
+    status: u16,
+    body: String,
+}
+
+impl ApiAnswer {
+    fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status: 200, body },
+            Err(e) => Self::error(500, e.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: String) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+impl ApiRequest {
+    /// Answer the read requests using `reader`. The requests that modify the design are returned
+    /// so that they can be answered once their operations have been applied.
+    pub fn answer<R: HttpApiReader>(
+        self,
+        reader: &R,
+        read_only: bool,
+    ) -> Option<OperationsRequest> {
+        let answer = match self.endpoint {
+            Endpoint::Staples => ApiAnswer::json(&reader.staples()),
+            Endpoint::Statistics => ApiAnswer::json(&reader.statistics()),
+            Endpoint::Geometry => ApiAnswer::json(&reader.geometry()),
+            Endpoint::Operations(_) if read_only => {
+                ApiAnswer::error(403, "The design is in read-only mode".into())
+            }
+            Endpoint::Operations(operations) => {
+                return Some(OperationsRequest {
+                    operations: operations
+                        .into_iter()
+                        .map(RemoteOperation::to_operation)
+                        .collect(),
+                    answer: self.answer,
+                })
+            }
+        };
+        let _ = self.answer.send(answer);
+        None
+    }
+}
+
+/// A request whose operations must be applied before it is answered.
+pub struct OperationsRequest {
+    pub operations: Vec<DesignOperation>,
+    answer: mpsc::Sender<ApiAnswer>,
+}
+
+impl OperationsRequest {
+    /// Answer with the outcome of the operations: the number of operations that were applied,
+    /// and the error of the operation that failed, if any.
+    pub fn answer(self, applied: usize, error: Option<String>) {
+        let answer = if let Some(error) = error {
+            ApiAnswer {
+                status: 422,
+                body: serde_json::json!({ "applied": applied, "error": error }).to_string(),
+            }
+        } else {
+            ApiAnswer::json(&serde_json::json!({ "applied": applied }))
+        };
+        let _ = self.answer.send(answer);
+    }
+}
+
+/// The handle on the HTTP server, if it was started.
+#[derive(Default)]
+pub struct HttpApi {
+    requests: Option<mpsc::Receiver<ApiRequest>>,
+}
+
+impl HttpApi {
+    /// Start the server if requested by the command line arguments.
+    pub fn from_args(args: &[String]) -> Self {
+        let port = args.iter().find_map(|arg| arg.strip_prefix("--http-api="));
+        let token = args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--http-api-token="))
+            .unwrap_or_default()
+            .to_string();
+        match port.map(|p| p.parse::<u16>().map_err(|e| (p, e))) {
+            Some(Ok(_)) if token.is_empty() => {
+                log::error!("Refusing to start the HTTP API without an --http-api-token");
+                Default::default()
+            }
+            Some(Ok(port)) => Self::start(port, token),
+            Some(Err((port, e))) => {
+                log::error!("Invalid port {}: {}", port, e);
+                Default::default()
+            }
+            None => Default::default(),
+        }
+    }
+
+    fn start(port: u16, token: String) -> Self {
+        let server = match Server::http(("127.0.0.1", port)) {
+            Ok(server) => server,
+            Err(e) => {
+                log::error!("Could not start HTTP API: {}", e);
+                return Default::default();
+            }
+        };
+        log::info!("HTTP API listening on 127.0.0.1:{}", port);
+        let (request_snd, request_rcv) = mpsc::channel();
+        std::thread::spawn(move || serve(server, &token, request_snd));
+        Self {
+            requests: Some(request_rcv),
+        }
+    }
+
+    /// The requests received since the last call.
+    pub fn pending_requests(&self) -> Vec<ApiRequest> {
+        self.requests
+            .as_ref()
+            .map(|rcv| rcv.try_iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The value of the header `name` of `request`, if any.
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Return true if the host part of `authority` (`host[:port]`) is a loopback address.
+fn is_local_authority(authority: &str) -> bool {
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed.split(']').next().unwrap_or_default()
+    } else {
+        authority.split(':').next().unwrap_or_default()
+    };
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .map_or(false, is_local_authority)
+}
+
+fn is_json_content_type(content_type: &str) -> bool {
+    content_type.split(';').next().map_or(false, |mime| {
+        mime.trim().eq_ignore_ascii_case("application/json")
+    })
+}
+
+/// Reject the requests that could come from a web page, that do not know the token, or whose
+/// body is not declared as JSON.
+fn check_request(request: &Request, token: &str) -> Result<(), ApiAnswer> {
+    if !header(request, "Host").map_or(false, is_local_authority) {
+        return Err(ApiAnswer::error(403, "The Host must be local".into()));
+    }
+    if let Some(origin) = header(request, "Origin") {
+        if !is_local_origin(origin) {
+            return Err(ApiAnswer::error(
+                403,
+                format!("Origin {} is not allowed", origin),
+            ));
+        }
+    }
+    let authorization = header(request, "Authorization").and_then(|h| h.strip_prefix("Bearer "));
+    if authorization != Some(token) {
+        return Err(ApiAnswer::error(401, "Missing or wrong token".into()));
+    }
+    if *request.method() == Method::Post
+        && !header(request, "Content-Type").map_or(false, is_json_content_type)
+    {
+        return Err(ApiAnswer::error(
+            415,
+            "The body must be sent as application/json".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Parse the request into an endpoint of the API.
+fn route(request: &mut Request) -> Result<Endpoint, ApiAnswer> {
+    match (request.method(), request.url()) {
+        (Method::Get, "/staples") => Ok(Endpoint::Staples),
+        (Method::Get, "/statistics") => Ok(Endpoint::Statistics),
+        (Method::Get, "/geometry") => Ok(Endpoint::Geometry),
+        (Method::Post, "/operations") => {
+            match serde_json::from_reader::<_, OperationsBody>(request.as_reader()) {
+                Ok(OperationsBody::One(op)) => Ok(Endpoint::Operations(vec![op])),
+                Ok(OperationsBody::Several(ops)) => Ok(Endpoint::Operations(ops)),
+                Err(e) => Err(ApiAnswer::error(400, e.to_string())),
+            }
+        }
+        (method, url) => Err(ApiAnswer::error(
+            404,
+            format!("No route for {} {}", method, url),
+        )),
+    }
+}
+
+fn serve(server: Server, token: &str, requests: mpsc::Sender<ApiRequest>) {
+    for mut request in server.incoming_requests() {
+        let endpoint = check_request(&request, token).and_then(|()| route(&mut request));
+        let answer = endpoint.and_then(|endpoint| {
+            let (answer_snd, answer_rcv) = mpsc::channel();
+            requests
+                .send(ApiRequest {
+                    endpoint,
+                    answer: answer_snd,
+                })
+                .map_err(|_| ApiAnswer::error(503, "ENSnano is closing".into()))?;
+            answer_rcv
+                .recv_timeout(ANSWER_TIMEOUT)
+                .map_err(|_| ApiAnswer::error(504, "ENSnano did not answer in time".into()))
+        });
+        let answer = answer.unwrap_or_else(|err| err);
+        let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("valid header");
+        let response = Response::from_string(answer.body)
+            .with_status_code(answer.status)
+            .with_header(content_type);
+        if let Err(e) = request.respond(response) {
+            log::warn!("Could not answer HTTP request: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_loopback_authorities_are_local() {
+        assert!(is_local_authority("127.0.0.1:8080"));
+        assert!(is_local_authority("localhost:8080"));
+        assert!(is_local_authority("[::1]:8080"));
+        assert!(is_local_authority("localhost"));
+        assert!(!is_local_authority("evil.example:8080"));
+        assert!(!is_local_authority("127.0.0.1.evil.example:8080"));
+        assert!(!is_local_authority("localhost.evil.example"));
+    }
+
+    #[test]
+    fn only_local_origins_are_allowed() {
+        assert!(is_local_origin("http://localhost:3000"));
+        assert!(is_local_origin("https://127.0.0.1"));
+        assert!(!is_local_origin("null"));
+        assert!(!is_local_origin("http://evil.example"));
+    }
+
+    #[test]
+    fn json_content_type_may_have_parameters() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("Application/JSON; charset=utf-8"));
+        assert!(!is_json_content_type("text/plain"));
+        assert!(!is_json_content_type("application/x-www-form-urlencoded"));
+    }
+}
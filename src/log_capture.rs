@@ -0,0 +1,84 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! A logger that prints records like `pretty_env_logger` does, and keeps the warnings and errors
+//! so that they can be displayed in the console of the GUI.
+
+use crate::gui::{LogRecord, MAX_NB_RECORDS};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// The warnings and errors that have been logged but not yet forwarded to the GUI. When there are
+/// more than `MAX_NB_RECORDS` of them, the oldest ones are dropped.
+static CAPTURED_RECORDS: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+struct CapturingLogger {
+    printer: env_logger::Logger,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::Level::Warn || self.printer.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.printer.enabled(record.metadata()) {
+            self.printer.log(record)
+        }
+        if record.level() <= log::Level::Warn {
+            if let Ok(mut captured) = CAPTURED_RECORDS.lock() {
+                if captured.len() >= MAX_NB_RECORDS {
+                    captured.pop_front();
+                }
+                captured.push_back(LogRecord {
+                    level: record.level(),
+                    module: record.module_path().unwrap_or(record.target()).to_string(),
+                    message: record.args().to_string(),
+                })
+            }
+        }
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+/// Install the logger. Printed records are filtered according to the `RUST_LOG` environment
+/// variable, but warnings and errors are always captured.
+pub fn init() {
+    let mut builder = pretty_env_logger::formatted_builder();
+    if let Ok(filters) = std::env::var("RUST_LOG") {
+        builder.parse_filters(&filters);
+    }
+    let printer = builder.build();
+    let max_level = printer.filter().max(LevelFilter::Warn);
+    if let Err(e) = log::set_boxed_logger(Box::new(CapturingLogger { printer })) {
+        eprintln!("Could not initialize logger: {e}");
+    } else {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Return the records that were captured since the last call to this function.
+pub fn take_records() -> Vec<LogRecord> {
+    CAPTURED_RECORDS
+        .lock()
+        .map(|mut records| records.drain(..).collect())
+        .unwrap_or_default()
+}
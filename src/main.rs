@@ -75,7 +75,7 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //!  permitted by the current state of the program. For example an error is returned if the user
 //!  try to modify the design durring a simulation.
 //!
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -83,15 +83,22 @@ use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use controller::{ChannelReader, ChannelReaderUpdate, SimulationRequest};
-use ensnano_design::{grid::GridId, Camera};
+use ensnano_design::{
+    design_operations::InsertionPlacementTarget,
+    diff::DesignDiff,
+    elements::{DesignElementKey, DnaAttribute},
+    grid::GridId,
+    Camera, GroupId, JointKind, Nucl,
+};
 use ensnano_exports::{ExportResult, ExportType};
 use ensnano_interactor::{
     application::{Application, Notification},
     RevolutionSurfaceSystemDescriptor, UnrootedRevolutionSurfaceDescriptor,
 };
 use ensnano_interactor::{
-    CenterOfSelection, CursorIcon, DesignOperation, DesignReader, RigidBodyConstants,
-    SuggestionParameters,
+    CenterOfSelection, CursorIcon, DesignBrowserChoice, DesignBrowserContent, DesignFileInfo,
+    DesignOperation, DesignReader, DesignTemplate, GizmoAxis, GroupOperation, RigidBodyConstants,
+    SuggestionParameters, TwistAxis, WidgetBasis,
 };
 use iced_native::Event as IcedEvent;
 use iced_wgpu::{wgpu, Settings, Viewport};
@@ -103,10 +110,10 @@ use futures::task::SpawnExt;
 use rand::random;
 use ultraviolet::{Rotor3, Vec3};
 use winit::{
-    dpi::{PhysicalPosition, PhysicalSize},
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
     event::{Event, ModifiersState, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::Window,
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    window::{Window, WindowBuilder},
 };
 
 #[allow(unused_imports)]
@@ -130,7 +137,7 @@ use ensnano_interactor::consts;
 mod multiplexer;
 use ensnano_flatscene as flatscene;
 use ensnano_interactor::{
-    graphics::{ElementType, SplitMode},
+    graphics::{BrushEffect, BrushParameters, ElementType, SplitMode},
     operation::Operation,
     ActionMode, CheckXoversParameter, Selection, SelectionMode,
 };
@@ -145,6 +152,7 @@ mod main_tests;
 // mod grid_panel; We don't use the grid panel atm
 
 mod app_state;
+mod batch;
 mod controller;
 use app_state::{
     AppState, AppStateTransition, CopyOperation, ErrOperation, OkOperation, PastePosition,
@@ -156,10 +164,24 @@ use controller::Controller;
 mod requests;
 pub use requests::Requests;
 
+#[cfg(feature = "collaboration")]
+mod collaboration;
 mod dialog;
-
-use flatscene::FlatScene;
-use gui::{ColorOverlay, Gui, IcedMessages, OverlayType, UiSize};
+mod gpu;
+#[cfg(feature = "http_api")]
+mod http_api;
+mod log_capture;
+mod performance;
+mod plugins;
+mod remote_operation;
+mod session;
+mod simulation_jobs;
+mod tutorial;
+
+use flatscene::{CrossSection, FlatScene};
+use gui::left_panel::ColorMessage;
+use gui::{ColorOverlay, ContextMenu, ContextMenuMessage};
+use gui::{Gui, IcedMessages, OverlayType, ToastSeverity, UiSize};
 use multiplexer::{Multiplexer, Overlay};
 use scene::Scene;
 use utils::{PhySize, TEXTURE_FORMAT};
@@ -184,21 +206,11 @@ const EARLY_LOG: bool = true;
 #[cfg(feature = "log_after_renderer_setup")]
 const EARLY_LOG: bool = false;
 
-/// Determine wgpu backends.
-///
-/// On some windows machine, only the DX12 backends will work. So the `dx12_only` feature forces
-/// its use.
-#[cfg(not(feature = "dx12_only"))]
-const BACKEND: wgpu::Backends = wgpu::Backends::PRIMARY;
-#[cfg(feature = "dx12_only")]
-const BACKEND: wgpu::Backends = wgpu::Backends::DX12;
-
-/// Determine if wgpu errors should panic.
-///
-/// Set to true because there should not be any "false-positive" in wgpu errors.
-///
-/// TODO: Make a feature that would set this constant to `false`.
-const PANIC_ON_WGPU_ERRORS: bool = true;
+/// The delay between two updates of the memory usage displayed in the console.
+const MEMORY_REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The delay between two updates of the performance overlay.
+const PERFORMANCE_REPORT_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Main function. Runs the event loop and holds the framebuffer.
 ///
@@ -229,15 +241,40 @@ const PANIC_ON_WGPU_ERRORS: bool = true;
 ///
 fn main() {
     if EARLY_LOG {
-        pretty_env_logger::init();
+        log_capture::init();
     }
     // parse arugments, if an argument was given it is treated as a file to open
-    let args: Vec<String> = env::args().collect();
-    let path = if args.len() >= 2 {
-        Some(PathBuf::from(&args[1]))
-    } else {
-        None
-    };
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(batch::BATCH_SUBCOMMAND) {
+        std::process::exit(batch::run(&args[1..]));
+    }
+    let viewer_mode = args.iter().any(|arg| arg == "--viewer");
+    let path = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map(PathBuf::from);
+    let gpu_argument = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--gpu="))
+        .and_then(|preference| {
+            preference
+                .parse::<ensnano_interactor::graphics::GpuPreference>()
+                .map_err(|e| log::error!("{}", e))
+                .ok()
+        });
+
+    use consts::APP_NAME;
+    let preferences: Option<AppStateParameters> = confy::load(APP_NAME, APP_NAME).ok();
+    let ui_size = preferences.as_ref().map(|p| p.ui_size).unwrap_or_default();
+    let gpu_preference = gpu_argument
+        .or_else(|| preferences.as_ref().map(|p| p.gpu_preference))
+        .unwrap_or_default();
+    ensnano_interactor::graphics::set_render_quality(
+        preferences
+            .as_ref()
+            .map(|p| p.render_quality)
+            .unwrap_or_default(),
+    );
 
     // Initialize winit
     let event_loop = EventLoop::new();
@@ -251,37 +288,21 @@ fn main() {
     // Represents the current state of the keyboard modifiers (Shift, Ctrl, etc.)
     let kbd_modifiers = ModifiersState::default();
 
-    let gpu = wgpu::Instance::new(BACKEND);
-    let surface = unsafe { gpu.create_surface(&window) };
     // Initialize WGPU
-    let (device, queue) = futures::executor::block_on(async {
-        let adapter = gpu
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::LowPower,
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .expect("Could not get adapter\n\
-                     This might be because gpu drivers are missing.\n\
-                     You need Vulkan, Metal (for MacOS) or DirectX (for Windows) drivers to run this software");
-
-        adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    features: wgpu::Features::empty(),
-                    limits: wgpu::Limits::default(),
-                    label: None,
-                },
-                None,
-            )
-            .await
-            .expect("Request device")
-    });
-
-    if !PANIC_ON_WGPU_ERRORS {
-        device.on_uncaptured_error(|e| log::error!("wgpu error {:?}", e));
-    }
+    let gpu::GpuContext {
+        instance: mut gpu,
+        mut surface,
+        device,
+        queue,
+        adapter_info,
+    } = match gpu::GpuContext::new(&window, gpu_preference) {
+        Ok(context) => context,
+        Err(e) => {
+            e.show_diagnostic();
+            std::process::exit(1)
+        }
+    };
+    let mut device_status = gpu::DeviceStatus::watch(&device);
 
     {
         let size = window.inner_size();
@@ -298,21 +319,14 @@ fn main() {
         )
     }
 
-    use consts::APP_NAME;
-    let ui_size = confy::load(APP_NAME, APP_NAME)
-        .map(|p: AppStateParameters| p.ui_size)
-        .unwrap_or_default();
-
     let settings = Settings {
         antialiasing: Some(iced_graphics::Antialiasing::MSAAx4),
         default_text_size: ui_size.main_text(),
         default_font: Some(include_bytes!("../font/ensnano2.ttf")),
         ..Default::default()
     };
-    let mut renderer =
-        iced_wgpu::Renderer::new(iced_wgpu::Backend::new(&device, settings, TEXTURE_FORMAT));
-    let device = Rc::new(device);
-    let queue = Rc::new(queue);
+    let mut device = Rc::new(device);
+    let mut queue = Rc::new(queue);
     let mut resized = false;
     let mut scale_factor_changed = false;
     let mut staging_belt = wgpu::util::StagingBelt::new(5 * 1024);
@@ -321,7 +335,13 @@ fn main() {
     // Initialize the mediator
     let requests = Arc::new(Mutex::new(Requests::default()));
     let messages = Arc::new(Mutex::new(IcedMessages::new()));
-    let mut scheduler = Scheduler::new();
+    messages.lock().unwrap().set_system_info(format!(
+        "ENSnano {}\nOS: {} {}\nGPU adapter: {:?}",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        adapter_info
+    ));
 
     // Initialize the layout
     let mut multiplexer = Multiplexer::new(
@@ -333,54 +353,32 @@ fn main() {
     );
     multiplexer.change_split(SplitMode::Both);
 
-    // Initialize the scenes
-    //
-    // The `encoder` encodes a series of GPU operations.
-    let mut encoder =
-        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-    let scene_area = multiplexer.get_element_area(ElementType::Scene).unwrap();
-    let scene = Arc::new(Mutex::new(Scene::new(
-        device.clone(),
-        queue.clone(),
-        window.inner_size(),
-        scene_area,
-        requests.clone(),
-        &mut encoder,
-        Default::default(),
-        scene::SceneKind::Cartesian,
-    )));
-    let stereographic_scene = Arc::new(Mutex::new(Scene::new(
-        device.clone(),
-        queue.clone(),
-        window.inner_size(),
-        scene_area,
-        requests.clone(),
-        &mut encoder,
-        Default::default(),
-        scene::SceneKind::Stereographic,
-    )));
-
-    queue.submit(Some(encoder.finish()));
-    scheduler.add_application(scene.clone(), ElementType::Scene);
-    scheduler.add_application(stereographic_scene.clone(), ElementType::StereographicScene);
-
-    let flat_scene = Arc::new(Mutex::new(FlatScene::new(
-        device.clone(),
-        queue.clone(),
-        window.inner_size(),
-        scene_area,
-        requests.clone(),
-        Default::default(),
-    )));
-    scheduler.add_application(flat_scene.clone(), ElementType::FlatScene);
-
     // Initialize the UI
     //
     let main_state_constructor = MainStateConstructor {
         messages: messages.clone(),
+        #[cfg(feature = "collaboration")]
+        collaboration: collaboration::Collaboration::from_args(&args),
+        #[cfg(feature = "http_api")]
+        http_api: http_api::HttpApi::from_args(&args),
     };
 
     let mut main_state = MainState::new(main_state_constructor);
+    main_state.read_only = viewer_mode;
+
+    // Initialize the scenes
+    let mut scheduler = create_applications(
+        &device,
+        &queue,
+        &window,
+        &multiplexer,
+        &requests,
+        &mut main_state,
+    );
+    messages
+        .lock()
+        .unwrap()
+        .set_plugins(main_state.plugins.descriptions());
 
     let mut gui = gui::Gui::new(
         device.clone(),
@@ -392,20 +390,15 @@ fn main() {
         Default::default(),
     );
 
-    let mut overlay_manager = OverlayManager::new(requests.clone(), &window, &mut renderer);
+    let mut overlay_manager =
+        OverlayManager::new(requests.clone(), &window, &device, settings, ui_size);
 
     // Run event loop
     let mut last_render_time = std::time::Instant::now();
+    let mut last_memory_report = std::time::Instant::now();
+    let mut last_performance_report = std::time::Instant::now();
     let mut mouse_interaction = iced::mouse::Interaction::Pointer;
 
-    main_state.applications.insert(ElementType::Scene, scene);
-    main_state
-        .applications
-        .insert(ElementType::FlatScene, flat_scene);
-    main_state
-        .applications
-        .insert(ElementType::StereographicScene, stereographic_scene);
-
     // Add a design to the scene if one was given as a command line arguement
     if path.is_some() {
         main_state.push_action(Action::LoadDesign(path))
@@ -417,7 +410,7 @@ fn main() {
 
     println!("{}", consts::WELCOME_MSG);
     if !EARLY_LOG {
-        pretty_env_logger::init();
+        log_capture::init();
     }
 
     let mut first_iteration = true;
@@ -431,7 +424,9 @@ fn main() {
         .unwrap()
         .push_application_state(main_state.get_app_state(), last_gui_state.1.clone());
 
-    event_loop.run(move |event, _, control_flow| {
+    let mut detached_panel: Option<DetachedPanelWindow> = None;
+
+    event_loop.run(move |event, event_loop_target, control_flow| {
         // Wait for event or redraw a frame every 33 ms (30 frame per seconds)
         *control_flow = ControlFlow::WaitUntil(Instant::now() + Duration::from_millis(33));
 
@@ -441,11 +436,45 @@ fn main() {
             multiplexer: &mut multiplexer,
             gui: &mut gui,
             scheduler: &mut scheduler,
+            overlay_manager: &mut overlay_manager,
             window: &window,
             resized: false,
         };
 
         match event {
+            Event::WindowEvent { window_id, event }
+                if detached_panel.as_ref().map(|panel| panel.window.id()) == Some(window_id) =>
+            {
+                let panel = detached_panel.as_ref().unwrap();
+                match event {
+                    WindowEvent::CloseRequested => main_state.wants_detach_toggle = true,
+                    WindowEvent::Resized(size) => {
+                        if multiplexer.resize_detached_left_pannel(size) {
+                            panel.configure(&device);
+                            resized = true;
+                            window.request_redraw();
+                        }
+                    }
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        multiplexer.update_modifiers(modifiers);
+                        messages.lock().unwrap().update_modifiers(modifiers);
+                    }
+                    event => {
+                        if let Some(event) = event.to_static() {
+                            multiplexer.detached_left_pannel_event(&event);
+                            main_state.focused_element = Some(ElementType::LeftPanel);
+                            let event = iced_winit::conversion::window_event(
+                                &event,
+                                panel.window.scale_factor(),
+                                kbd_modifiers,
+                            );
+                            if let Some(event) = event {
+                                gui.forward_event(ElementType::LeftPanel, event);
+                            }
+                        }
+                    }
+                }
+            }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
@@ -473,6 +502,21 @@ fn main() {
             {
                 window.set_fullscreen(None)
             }
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { input, .. },
+                ..
+            } if input.virtual_keycode == Some(VirtualKeyCode::F12)
+                && input.state == winit::event::ElementState::Pressed =>
+            {
+                let main_state = &mut main_state_view.main_state;
+                main_state.performance.toggle();
+                let stats = if main_state.performance.is_enabled() {
+                    Some(main_state.performance_stats())
+                } else {
+                    None
+                };
+                messages.lock().unwrap().set_performance_stats(stats);
+            }
             Event::WindowEvent {
                 event: WindowEvent::KeyboardInput { .. },
                 ..
@@ -497,6 +541,7 @@ fn main() {
             Event::WindowEvent { event, .. } => {
                 //let modifiers = multiplexer.modifiers();
                 if let Some(event) = event.to_static() {
+                    multiplexer.set_gizmo_shortcuts(main_state.app_state.shows_gizmo());
                     // Feed the event to the multiplexer
                     let event = multiplexer.event(event, &mut resized, &mut scale_factor_changed);
 
@@ -514,6 +559,13 @@ fn main() {
                             main_state.update_candidates(vec![]);
                         }
                         main_state.applications_cursor = None;
+                        if let winit::event::WindowEvent::MouseInput {
+                            state: winit::event::ElementState::Pressed,
+                            ..
+                        } = event
+                        {
+                            overlay_manager.dismiss_context_menu(area, &mut multiplexer);
+                        }
                         match area {
                             area if area.is_gui() => {
                                 let event = iced_winit::conversion::window_event(
@@ -549,6 +601,69 @@ fn main() {
                     }
                 }
             }
+            Event::MainEventsCleared if device_status.is_lost() => {
+                // Recreate the device and everything that was created with it. The design, the
+                // undo history and the camera are kept.
+                let context = match gpu::GpuContext::new(&window, gpu_preference) {
+                    Ok(context) => context,
+                    Err(e) => {
+                        e.show_diagnostic();
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                };
+                let camera = main_state
+                    .applications
+                    .get(&ElementType::Scene)
+                    .and_then(|s| s.lock().unwrap().get_camera());
+                gpu = context.instance;
+                surface = context.surface;
+                device = Rc::new(context.device);
+                queue = Rc::new(context.queue);
+                device_status = gpu::DeviceStatus::watch(&device);
+                staging_belt = wgpu::util::StagingBelt::new(5 * 1024);
+                multiplexer.set_device(device.clone());
+                scheduler = create_applications(
+                    &device,
+                    &queue,
+                    &window,
+                    &multiplexer,
+                    &requests,
+                    &mut main_state,
+                );
+                if let Some(camera) = camera {
+                    for app in main_state.applications.values() {
+                        app.lock()
+                            .unwrap()
+                            .on_notify(Notification::TeleportCamera(camera.0.clone()));
+                    }
+                }
+                gui = gui::Gui::new(
+                    device.clone(),
+                    &window,
+                    &multiplexer,
+                    requests.clone(),
+                    ui_size,
+                    &main_state.app_state,
+                    main_state.gui_state(&multiplexer),
+                );
+                overlay_manager =
+                    OverlayManager::new(requests.clone(), &window, &device, settings, ui_size);
+                if let Some(panel) = detached_panel.as_mut() {
+                    panel.surface = unsafe { gpu.create_surface(&panel.window) };
+                    panel.configure(&device);
+                }
+                messages
+                    .lock()
+                    .unwrap()
+                    .push_application_state(main_state.get_app_state(), last_gui_state.1.clone());
+                main_state.push_toast(
+                    ToastSeverity::Warning,
+                    "The graphics device was lost and has been recreated".to_string(),
+                );
+                resized = true;
+                window.request_redraw();
+            }
             Event::MainEventsCleared => {
                 scale_factor_changed |= multiplexer.check_scale_factor(&window);
                 let mut redraw = resized || scale_factor_changed;
@@ -564,6 +679,7 @@ fn main() {
                     multiplexer: &mut multiplexer,
                     gui: &mut gui,
                     scheduler: &mut scheduler,
+                    overlay_manager: &mut overlay_manager,
                     window: &window,
                     resized: false,
                 };
@@ -577,6 +693,20 @@ fn main() {
                 resized |= first_iteration;
                 first_iteration = false;
 
+                if main_state.wants_detach_toggle {
+                    main_state.wants_detach_toggle = false;
+                    if detached_panel.take().is_some() {
+                        multiplexer.reattach_left_pannel();
+                    } else if let Some(area) = multiplexer.get_element_area(ElementType::LeftPanel)
+                    {
+                        let panel =
+                            DetachedPanelWindow::new(event_loop_target, &gpu, &device, area.size);
+                        multiplexer.detach_left_pannel(panel.window.inner_size());
+                        detached_panel = Some(panel);
+                    }
+                    resized = true;
+                }
+
                 for update in main_state.channel_reader.get_updates() {
                     if let ChannelReaderUpdate::ScaffoldShiftOptimizationProgress(x) = update {
                         main_state
@@ -599,22 +729,66 @@ fn main() {
                             main_state.pending_actions.push_back(Action::ErrorMsg(msg));
                         } else {
                             // unwrap because in this block, result is necessarilly an Err
-                            log::warn!("{:?}", result.err().unwrap());
+                            let err = result.err().unwrap();
+                            log::warn!("{:?}", err);
+                            main_state.push_toast(
+                                ToastSeverity::Error,
+                                format!("Scaffold shift optimization failed: {}", err),
+                            );
                         }
                     } else if let ChannelReaderUpdate::SimulationUpdate(update) = update {
                         main_state.app_state.apply_simulation_update(update)
                     } else if let ChannelReaderUpdate::SimulationExpired = update {
+                        main_state.push_toast(
+                            ToastSeverity::Info,
+                            "The simulation has expired and was stopped".to_string(),
+                        );
                         main_state.update_simulation(SimulationRequest::Stop)
+                    } else if let ChannelReaderUpdate::RevolutionRelaxationProgress(progress) =
+                        update
+                    {
+                        main_state
+                            .messages
+                            .lock()
+                            .unwrap()
+                            .set_revolution_relaxation_progress(progress);
+                    } else if let ChannelReaderUpdate::FileIoProgress {
+                        task_name,
+                        progress,
+                    } = update
+                    {
+                        main_state
+                            .messages
+                            .lock()
+                            .unwrap()
+                            .push_cancellable_progress(task_name, progress);
+                    } else if let ChannelReaderUpdate::FileIoFinished(result) = update {
+                        main_state.messages.lock().unwrap().finish_progess();
+                        main_state.file_io_result = Some(result);
                     }
                 }
 
+                main_state.poll_simulation_jobs();
+                #[cfg(feature = "collaboration")]
+                main_state.apply_remote_operations();
+                #[cfg(feature = "http_api")]
+                main_state.answer_http_requests();
+
                 log::trace!("call update from main");
                 main_state.update();
+                let statistics = main_state
+                    .app_state
+                    .get_design_reader()
+                    .get_design_statistics();
                 let new_title = if let Some(path) = main_state.get_current_file_name() {
                     let path_str = formated_path_end(path);
-                    format!("ENSnano {}", path_str)
+                    format!("ENSnano {} ({})", path_str, statistics)
                 } else {
-                    format!("ENSnano {}", crate::consts::NO_DESIGN_TITLE)
+                    format!(
+                        "ENSnano {} ({})",
+                        crate::consts::NO_DESIGN_TITLE,
+                        statistics
+                    )
                 };
 
                 if windows_title != new_title {
@@ -623,10 +797,25 @@ fn main() {
                 }
 
                 // Treat eventual event that happenend in the gui left panel.
-                let _overlay_change =
-                    overlay_manager.fetch_change(&multiplexer, &window, &mut renderer);
+                redraw |= overlay_manager.fetch_change(&multiplexer, &window);
+                redraw |= multiplexer.is_handling_overlay();
                 {
                     let mut messages = messages.lock().unwrap();
+                    messages.expire_toasts();
+                    let log_records = log_capture::take_records();
+                    if !log_records.is_empty() {
+                        messages.push_log_records(log_records);
+                    }
+                    if last_memory_report.elapsed() > MEMORY_REPORT_INTERVAL {
+                        messages.set_memory_usage(main_state.memory_usage());
+                        last_memory_report = std::time::Instant::now();
+                    }
+                    if main_state.performance.is_enabled()
+                        && last_performance_report.elapsed() > PERFORMANCE_REPORT_INTERVAL
+                    {
+                        messages.set_performance_stats(Some(main_state.performance_stats()));
+                        last_performance_report = std::time::Instant::now();
+                    }
                     gui.forward_messages(&mut messages);
                     overlay_manager.forward_messages(&mut messages);
                 }
@@ -708,19 +897,40 @@ fn main() {
                 // If there are events pending
                 gui.update(&multiplexer, &window);
 
-                overlay_manager.process_event(&mut renderer, resized, &multiplexer, &window);
+                overlay_manager.process_event(resized, &multiplexer, &window);
 
                 resized = false;
                 scale_factor_changed = false;
 
-                if let Ok(frame) = surface.get_current_texture() {
+                let frame = match surface.get_current_texture() {
+                    Ok(frame) => Some(frame),
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        log::error!("Out of memory while getting next frame");
+                        None
+                    }
+                    Err(wgpu::SurfaceError::Lost) => {
+                        log::warn!("Surface lost, recreating it");
+                        surface = unsafe { gpu.create_surface(&window) };
+                        resized = true;
+                        None
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Error getting next frame: {:?}, attempt to recreate swap chain",
+                            e
+                        );
+                        resized = true;
+                        None
+                    }
+                };
+                if let Some(frame) = frame {
                     let mut encoder = device
                         .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
                     // We draw the applications first
                     let now = std::time::Instant::now();
                     let dt = now - last_render_time;
-                    scheduler.draw_apps(&mut encoder, &multiplexer, dt);
+                    let draw_times = scheduler.draw_apps(&mut encoder, &multiplexer, dt);
 
                     gui.render(
                         &mut encoder,
@@ -735,6 +945,13 @@ fn main() {
                         window.request_redraw();
                         return;
                     }
+                    overlay_manager.render(
+                        &device,
+                        &mut staging_belt,
+                        &mut encoder,
+                        &multiplexer,
+                        &window,
+                    );
                     log::trace!("window size {:?}", window.inner_size());
                     multiplexer.draw(
                         &mut encoder,
@@ -743,27 +960,41 @@ fn main() {
                             .create_view(&wgpu::TextureViewDescriptor::default()),
                         &window,
                     );
-                    //overlay_manager.render(&device, &mut staging_belt, &mut encoder, &frame.output.view, &multiplexer, &window, &mut renderer);
+                    let panel_frame = detached_panel
+                        .as_ref()
+                        .and_then(|panel| panel.surface.get_current_texture().ok());
+                    if let Some(panel_frame) = panel_frame.as_ref() {
+                        multiplexer.draw_detached_left_pannel(
+                            &mut encoder,
+                            &panel_frame
+                                .texture
+                                .create_view(&wgpu::TextureViewDescriptor::default()),
+                        );
+                    }
 
                     // Then we submit the work
                     staging_belt.finish();
                     queue.submit(Some(encoder.finish()));
                     frame.present();
+                    main_state.performance.frame_presented(draw_times);
+                    if let Some(panel_frame) = panel_frame {
+                        panel_frame.present();
+                    }
 
                     // And update the mouse cursor
                     main_state.gui_cursor =
                         iced_winit::conversion::mouse_interaction(mouse_interaction);
                     main_state.update_cursor(&multiplexer);
                     window.set_cursor_icon(main_state.cursor);
+                    if let Some(panel) = detached_panel.as_ref() {
+                        panel.window.set_cursor_icon(main_state.cursor);
+                    }
                     local_pool
                         .spawner()
                         .spawn(staging_belt.recall())
                         .expect("Recall staging buffers");
 
                     local_pool.run_until_stalled();
-                } else {
-                    log::warn!("Error getting next frame, attempt to recreate swap chain");
-                    resized = true;
                 }
             }
             _ => {}
@@ -771,35 +1002,176 @@ fn main() {
     })
 }
 
+/// A window in which the left panel is displayed when it is detached from the main window.
+/// Create the applications that draw on `device`, register them in `main_state` and return a
+/// scheduler that drives them.
+fn create_applications(
+    device: &Rc<wgpu::Device>,
+    queue: &Rc<wgpu::Queue>,
+    window: &Window,
+    multiplexer: &Multiplexer,
+    requests: &Arc<Mutex<Requests>>,
+    main_state: &mut MainState,
+) -> Scheduler {
+    let mut scheduler = Scheduler::new();
+    // The `encoder` encodes a series of GPU operations.
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let scene_area = multiplexer.get_element_area(ElementType::Scene).unwrap();
+    let scene = Arc::new(Mutex::new(Scene::new(
+        device.clone(),
+        queue.clone(),
+        window.inner_size(),
+        scene_area,
+        requests.clone(),
+        &mut encoder,
+        Default::default(),
+        scene::SceneKind::Cartesian,
+    )));
+    let stereographic_scene = Arc::new(Mutex::new(Scene::new(
+        device.clone(),
+        queue.clone(),
+        window.inner_size(),
+        scene_area,
+        requests.clone(),
+        &mut encoder,
+        Default::default(),
+        scene::SceneKind::Stereographic,
+    )));
+
+    queue.submit(Some(encoder.finish()));
+    scheduler.add_application(scene.clone(), ElementType::Scene);
+    scheduler.add_application(stereographic_scene.clone(), ElementType::StereographicScene);
+
+    let flat_scene = Arc::new(Mutex::new(FlatScene::new(
+        device.clone(),
+        queue.clone(),
+        window.inner_size(),
+        scene_area,
+        requests.clone(),
+        Default::default(),
+    )));
+    scheduler.add_application(flat_scene.clone(), ElementType::FlatScene);
+
+    let cross_section = Arc::new(Mutex::new(CrossSection::new(
+        device.clone(),
+        queue.clone(),
+        scene_area,
+        requests.clone(),
+        Default::default(),
+    )));
+    scheduler.add_application(cross_section.clone(), ElementType::CrossSection);
+
+    main_state.applications.insert(ElementType::Scene, scene);
+    main_state
+        .applications
+        .insert(ElementType::FlatScene, flat_scene);
+    main_state
+        .applications
+        .insert(ElementType::StereographicScene, stereographic_scene);
+    main_state
+        .applications
+        .insert(ElementType::CrossSection, cross_section);
+    scheduler
+}
+
+struct DetachedPanelWindow {
+    window: Window,
+    surface: wgpu::Surface,
+}
+
+impl DetachedPanelWindow {
+    fn new(
+        event_loop: &EventLoopWindowTarget<()>,
+        gpu: &wgpu::Instance,
+        device: &wgpu::Device,
+        size: PhySize,
+    ) -> Self {
+        let window = WindowBuilder::new()
+            .with_title("ENSnano")
+            .with_inner_size(size)
+            .with_min_inner_size(PhySize::new(100, 100))
+            .build(event_loop)
+            .unwrap();
+        let surface = unsafe { gpu.create_surface(&window) };
+        let ret = Self { window, surface };
+        ret.configure(device);
+        ret
+    }
+
+    fn configure(&self, device: &wgpu::Device) {
+        let size = self.window.inner_size();
+        if size.width > 0 && size.height > 0 {
+            self.surface.configure(
+                device,
+                &wgpu::SurfaceConfiguration {
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    format: TEXTURE_FORMAT,
+                    width: size.width,
+                    height: size.height,
+                    present_mode: wgpu::PresentMode::Mailbox,
+                },
+            )
+        }
+    }
+}
+
 pub struct OverlayManager {
     color_state: iced_native::program::State<ColorOverlay<Requests>>,
     color_debug: Debug,
+    color_renderer: iced_wgpu::Renderer,
+    context_menu_state: iced_native::program::State<ContextMenu<Requests>>,
+    context_menu_debug: Debug,
+    context_menu_renderer: iced_wgpu::Renderer,
     overlay_types: Vec<OverlayType>,
-    overlays: Vec<Overlay>,
+    /// The *physical* size of each overlay the last time that its program was updated.
+    overlay_sizes: Vec<PhySize>,
+    ui_size: UiSize,
 }
 
 impl OverlayManager {
     pub fn new(
         requests: Arc<Mutex<Requests>>,
         window: &Window,
-        renderer: &mut iced_wgpu::Renderer,
+        device: &wgpu::Device,
+        settings: Settings,
+        ui_size: UiSize,
     ) -> Self {
+        let mut color_renderer =
+            iced_wgpu::Renderer::new(iced_wgpu::Backend::new(device, settings, TEXTURE_FORMAT));
         let color = ColorOverlay::new(
-            requests,
+            requests.clone(),
             PhysicalSize::new(250., 250.).to_logical(window.scale_factor()),
         );
         let mut color_debug = Debug::new();
         let color_state = program::State::new(
             color,
             convert_size(PhysicalSize::new(250, 250)),
-            renderer,
+            &mut color_renderer,
             &mut color_debug,
         );
+        let mut context_menu_renderer =
+            iced_wgpu::Renderer::new(iced_wgpu::Backend::new(device, settings, TEXTURE_FORMAT));
+        let mut context_menu_debug = Debug::new();
+        let context_menu_state = program::State::new(
+            ContextMenu::new(requests, ui_size),
+            convert_size(PhysicalSize::new(
+                gui::CONTEXT_MENU_WIDTH as u32,
+                ContextMenu::<Requests>::height(0, ui_size) as u32,
+            )),
+            &mut context_menu_renderer,
+            &mut context_menu_debug,
+        );
         Self {
             color_state,
             color_debug,
+            color_renderer,
+            context_menu_state,
+            context_menu_debug,
+            context_menu_renderer,
             overlay_types: Vec::new(),
-            overlays: Vec::new(),
+            overlay_sizes: Vec::new(),
+            ui_size,
         }
     }
 
@@ -810,146 +1182,205 @@ impl OverlayManager {
                 unreachable!();
             }
             Some(OverlayType::Color) => self.color_state.queue_event(event),
+            Some(OverlayType::ContextMenu) => self.context_menu_state.queue_event(event),
         }
     }
 
-    #[allow(dead_code)]
     fn add_overlay(&mut self, overlay_type: OverlayType, multiplexer: &mut Multiplexer) {
-        match overlay_type {
-            OverlayType::Color => self.overlays.push(Overlay {
-                position: PhysicalPosition::new(500, 500),
-                size: PhysicalSize::new(250, 250),
-            }),
+        if let Some(n) = self.overlay_types.iter().position(|t| *t == overlay_type) {
+            multiplexer.raise_overlay(n);
+            return;
         }
+        let overlay = match overlay_type {
+            OverlayType::Color => Overlay {
+                position: PhysicalPosition::new(500, 500),
+                size: PhysicalSize::new(250, 300),
+                movable: true,
+            },
+            OverlayType::ContextMenu => {
+                log::error!("Context menus must be opened with open_context_menu");
+                return;
+            }
+        };
+        self.push_overlay(overlay_type, overlay, multiplexer);
+    }
+
+    fn push_overlay(
+        &mut self,
+        overlay_type: OverlayType,
+        overlay: Overlay,
+        multiplexer: &mut Multiplexer,
+    ) {
+        multiplexer.push_overlay(overlay);
         self.overlay_types.push(overlay_type);
-        self.update_multiplexer(multiplexer);
+        // The program will be updated with the actual size of the overlay
+        self.overlay_sizes.push(PhysicalSize::new(0, 0));
+    }
+
+    /// Open a context menu for `selection` at the position of the mouse cursor.
+    fn open_context_menu(&mut self, selection: Selection, multiplexer: &mut Multiplexer) {
+        self.rm_overlay(OverlayType::ContextMenu, multiplexer);
+        let nb_actions = gui::ContextMenuAction::actions_for(&selection).len();
+        if nb_actions == 0 {
+            return;
+        }
+        self.context_menu_state
+            .queue_message(ContextMenuMessage::NewSelection(selection));
+        let size = LogicalSize::new(
+            gui::CONTEXT_MENU_WIDTH as f64,
+            ContextMenu::<Requests>::height(nb_actions, self.ui_size) as f64,
+        )
+        .to_physical(multiplexer.scale_factor);
+        let overlay = Overlay {
+            position: multiplexer.get_mouse_position().cast::<u32>(),
+            size,
+            movable: false,
+        };
+        self.push_overlay(OverlayType::ContextMenu, overlay, multiplexer);
     }
 
-    fn process_event(
+    /// Close the context menu if the user clicked outside of it.
+    fn dismiss_context_menu(
         &mut self,
-        renderer: &mut iced_wgpu::Renderer,
-        resized: bool,
-        multiplexer: &Multiplexer,
-        window: &Window,
+        clicked_element: ElementType,
+        multiplexer: &mut Multiplexer,
     ) {
-        for (n, overlay) in self.overlay_types.iter().enumerate() {
-            let cursor_position = if multiplexer.foccused_element() == Some(ElementType::Overlay(n))
-            {
-                multiplexer.get_cursor_position()
-            } else {
-                PhysicalPosition::new(-1., -1.)
-            };
-            let mut clipboard = iced_native::clipboard::Null;
-            match overlay {
-                OverlayType::Color => {
-                    if !self.color_state.is_queue_empty() || resized {
-                        let _ = self.color_state.update(
-                            convert_size(PhysicalSize::new(250, 250)),
-                            conversion::cursor_position(cursor_position, window.scale_factor()),
-                            renderer,
-                            &mut clipboard,
-                            &mut self.color_debug,
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    #[allow(dead_code)]
-    fn render(
-        &self,
-        device: &wgpu::Device,
-        staging_belt: &mut wgpu::util::StagingBelt,
-        encoder: &mut wgpu::CommandEncoder,
-        target: &wgpu::TextureView,
-        multiplexer: &Multiplexer,
-        window: &Window,
-        renderer: &mut iced_wgpu::Renderer,
-    ) {
-        for overlay_type in self.overlay_types.iter() {
-            match overlay_type {
-                OverlayType::Color => {
-                    let color_viewport = Viewport::with_physical_size(
-                        convert_size_u32(multiplexer.window_size),
-                        window.scale_factor(),
-                    );
-                    renderer.with_primitives(|backend, primitives| {
-                        backend.present(
-                            device,
-                            staging_belt,
-                            encoder,
-                            target,
-                            primitives,
-                            &color_viewport,
-                            &self.color_debug.overlay(),
-                        )
-                    });
-                }
+        let context_menu = self
+            .overlay_types
+            .iter()
+            .position(|t| *t == OverlayType::ContextMenu);
+        if let Some(n) = context_menu {
+            if clicked_element != ElementType::Overlay(n) {
+                self.rm_overlay(OverlayType::ContextMenu, multiplexer)
             }
         }
     }
 
-    #[allow(dead_code)]
     fn rm_overlay(&mut self, overlay_type: OverlayType, multiplexer: &mut Multiplexer) {
-        let mut rm_idx = Vec::new();
-        for (idx, overlay_type_) in self.overlay_types.iter().rev().enumerate() {
-            if *overlay_type_ == overlay_type {
-                rm_idx.push(idx);
-            }
+        while let Some(n) = self.overlay_types.iter().position(|t| *t == overlay_type) {
+            self.overlay_types.remove(n);
+            self.overlay_sizes.remove(n);
+            multiplexer.remove_overlay(n);
         }
-        for idx in rm_idx.iter() {
-            self.overlays.remove(*idx);
-            self.overlay_types.remove(*idx);
-        }
-        self.update_multiplexer(multiplexer);
     }
 
-    #[allow(dead_code)]
-    fn update_multiplexer(&self, multiplexer: &mut Multiplexer) {
-        multiplexer.set_overlays(self.overlays.clone())
+    fn process_event(&mut self, resized: bool, multiplexer: &Multiplexer, window: &Window) {
+        self.update_programs(resized, multiplexer, window);
     }
 
-    fn forward_messages(&mut self, _messages: &mut IcedMessages<AppState>) {
-        ()
-        /*
-        for m in messages.color_overlay.drain(..) {
-            self.color_state.queue_message(m);
-        }*/
-    }
-
-    fn fetch_change(
-        &mut self,
-        multiplexer: &Multiplexer,
-        window: &Window,
-        renderer: &mut iced_wgpu::Renderer,
-    ) -> bool {
+    /// Update the programs of the overlays that have pending events or that have been resized.
+    ///
+    /// Return true if at least one program was updated.
+    fn update_programs(&mut self, force: bool, multiplexer: &Multiplexer, window: &Window) -> bool {
         let mut ret = false;
         for (n, overlay) in self.overlay_types.iter().enumerate() {
+            let size = if let Some(area) = multiplexer.get_draw_area(ElementType::Overlay(n)) {
+                area.size
+            } else {
+                continue;
+            };
             let cursor_position = if multiplexer.foccused_element() == Some(ElementType::Overlay(n))
             {
                 multiplexer.get_cursor_position()
             } else {
                 PhysicalPosition::new(-1., -1.)
             };
+            let cursor_position =
+                conversion::cursor_position(cursor_position, window.scale_factor());
+            let size_changed = self.overlay_sizes[n] != size;
+            self.overlay_sizes[n] = size;
             let mut clipboard = iced_native::clipboard::Null;
             match overlay {
                 OverlayType::Color => {
-                    if !self.color_state.is_queue_empty() {
+                    if size_changed {
+                        self.color_state.queue_message(ColorMessage::Resized(
+                            size.to_logical(window.scale_factor()),
+                        ));
+                    }
+                    if !self.color_state.is_queue_empty() || force || size_changed {
                         ret = true;
                         let _ = self.color_state.update(
-                            convert_size(PhysicalSize::new(250, 250)),
-                            conversion::cursor_position(cursor_position, window.scale_factor()),
-                            renderer,
+                            convert_size(size),
+                            cursor_position,
+                            &mut self.color_renderer,
                             &mut clipboard,
                             &mut self.color_debug,
                         );
                     }
                 }
+                OverlayType::ContextMenu => {
+                    if !self.context_menu_state.is_queue_empty() || force || size_changed {
+                        ret = true;
+                        let _ = self.context_menu_state.update(
+                            convert_size(size),
+                            cursor_position,
+                            &mut self.context_menu_renderer,
+                            &mut clipboard,
+                            &mut self.context_menu_debug,
+                        );
+                    }
+                }
             }
         }
         ret
     }
+
+    fn render(
+        &mut self,
+        device: &wgpu::Device,
+        staging_belt: &mut wgpu::util::StagingBelt,
+        encoder: &mut wgpu::CommandEncoder,
+        multiplexer: &Multiplexer,
+        window: &Window,
+    ) {
+        for (n, overlay_type) in self.overlay_types.iter().enumerate() {
+            let area = multiplexer.get_draw_area(ElementType::Overlay(n));
+            let target = multiplexer.get_texture_view(ElementType::Overlay(n));
+            if let Some((area, target)) = area.zip(target) {
+                let viewport = Viewport::with_physical_size(
+                    convert_size_u32(area.size),
+                    window.scale_factor(),
+                );
+                let (renderer, debug) = match overlay_type {
+                    OverlayType::Color => (&mut self.color_renderer, &self.color_debug),
+                    OverlayType::ContextMenu => {
+                        (&mut self.context_menu_renderer, &self.context_menu_debug)
+                    }
+                };
+                renderer.with_primitives(|backend, primitives| {
+                    backend.present(
+                        device,
+                        staging_belt,
+                        encoder,
+                        target,
+                        primitives,
+                        &viewport,
+                        &debug.overlay(),
+                    )
+                });
+            }
+        }
+    }
+
+    fn forward_messages(&mut self, _messages: &mut IcedMessages<AppState>) {
+        ()
+        /*
+        for m in messages.color_overlay.drain(..) {
+            self.color_state.queue_message(m);
+        }*/
+    }
+
+    fn fetch_change(&mut self, multiplexer: &Multiplexer, window: &Window) -> bool {
+        self.update_programs(false, multiplexer, window)
+    }
+}
+
+/// The resident memory of the process in bytes. Only available on Linux.
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
 }
 
 fn formated_path_end<P: AsRef<Path>>(path: P) -> String {
@@ -972,6 +1403,12 @@ fn formated_path_end<P: AsRef<Path>>(path: P) -> String {
     ret.join("/")
 }
 
+/// A named snapshot of the application state, created by the user.
+struct Checkpoint {
+    name: String,
+    state: AppState,
+}
+
 /// The state of the main event loop.
 pub(crate) struct MainState {
     app_state: AppState,
@@ -983,6 +1420,8 @@ pub(crate) struct MainState {
     applications: HashMap<ElementType, Arc<Mutex<dyn Application<AppState = AppState>>>>,
     focused_element: Option<ElementType>,
     last_saved_state: AppState,
+    /// Named snapshots of the application state created by the user during the session.
+    checkpoints: Vec<Checkpoint>,
 
     /// The name of the file containing the current design.
     ///
@@ -991,16 +1430,65 @@ pub(crate) struct MainState {
     file_name: Option<PathBuf>,
 
     wants_fit: bool,
+    /// Set when the left panel must be detached from, or put back in, the main window.
+    wants_detach_toggle: bool,
     last_backup_date: Instant,
     last_backed_up_state: AppState,
     simulation_cursor: Option<CursorIcon>,
     applications_cursor: Option<CursorIcon>,
     gui_cursor: CursorIcon,
     cursor: CursorIcon,
+    /// The presentation step that is currently displayed, if any.
+    presentation_step: Option<usize>,
+    /// If true, the design cannot be modified.
+    read_only: bool,
+    /// The progression in the interactive tutorial, if it is running.
+    tutorial: Option<tutorial::Tutorial>,
+    /// The batch of operations that is being recorded, if any.
+    batch: Option<OperationBatch>,
+    /// The state that is being saved in a background thread, if it is stable.
+    state_being_saved: Option<AppState>,
+    /// The number of operations that were applied to the design since it was created or opened.
+    nb_operations: usize,
+    /// The path of the last variant of the design that was saved, and its provenance. The
+    /// provenance is written again each time the design is saved at this path.
+    variant_provenance: Option<(PathBuf, ensnano_design::DesignProvenance)>,
+    /// The result of the last background file operation, waiting to be handled by the controller.
+    file_io_result: Option<app_state::FileIoResult>,
+    /// The design files of the folder listed by the open dialog that are still being read.
+    design_browser_scan: Option<std::sync::mpsc::Receiver<DesignFileInfo>>,
+    /// The answer of the user to the open dialog, waiting to be handled by the controller.
+    design_browser_choice: Option<DesignBrowserChoice>,
+    performance: performance::PerformanceMonitor,
+    /// The simulations that run in the background.
+    simulation_jobs: simulation_jobs::SimulationJobs,
+    /// The description of the jobs that was last sent to the GUI.
+    last_simulation_job_infos: Vec<ensnano_interactor::SimulationJobInfo>,
+    /// The connection with the instance of ENSnano with which the design is shared.
+    #[cfg(feature = "collaboration")]
+    collaboration: collaboration::Collaboration,
+    /// The server through which external tools can access the design.
+    #[cfg(feature = "http_api")]
+    http_api: http_api::HttpApi,
+    plugins: plugins::PluginManager,
+}
+
+/// A sequence of operations that produce a single undo entry.
+struct OperationBatch {
+    /// The state before the first operation of the batch
+    old_state: AppState,
+    label: TransitionLabel,
+    /// The number of calls to `begin_batch` that have not been matched by a call to
+    /// `commit_batch` yet.
+    depth: usize,
 }
 
 struct MainStateConstructor {
     messages: Arc<Mutex<IcedMessages<AppState>>>,
+    #[cfg(feature = "collaboration")]
+    collaboration: collaboration::Collaboration,
+    #[cfg(feature = "http_api")]
+    http_api: http_api::HttpApi,
 }
 
 use controller::SaveDesignError;
@@ -1023,14 +1511,34 @@ impl MainState {
             applications: Default::default(),
             focused_element: None,
             last_saved_state: app_state.clone(),
+            checkpoints: Vec::new(),
             file_name: None,
             wants_fit: false,
+            wants_detach_toggle: false,
             last_backup_date: Instant::now(),
             last_backed_up_state: app_state,
             simulation_cursor: None,
             applications_cursor: None,
             gui_cursor: Default::default(),
             cursor: Default::default(),
+            presentation_step: None,
+            read_only: false,
+            tutorial: None,
+            batch: None,
+            state_being_saved: None,
+            nb_operations: 0,
+            variant_provenance: None,
+            file_io_result: None,
+            design_browser_scan: None,
+            design_browser_choice: None,
+            performance: performance::PerformanceMonitor::new(),
+            simulation_jobs: Default::default(),
+            last_simulation_job_infos: Vec::new(),
+            #[cfg(feature = "collaboration")]
+            collaboration: constructor.collaboration,
+            #[cfg(feature = "http_api")]
+            http_api: constructor.http_api,
+            plugins: plugins::PluginManager::load(),
         }
     }
 
@@ -1087,6 +1595,13 @@ impl MainState {
         self.redo_stack.clear();
         self.app_state = new_state.clone();
         self.last_saved_state = new_state;
+        self.nb_operations = 0;
+        self.variant_provenance = None;
+        self.presentation_step = None;
+        if !self.checkpoints.is_empty() {
+            self.checkpoints.clear();
+            self.update_checkpoints_list();
+        }
     }
 
     fn update(&mut self) {
@@ -1104,7 +1619,71 @@ impl MainState {
                 .unwrap()
                 .on_notify(Notification::NewStereographicCamera(camera_ptr));
         }
-        self.app_state.update()
+        if self.batch.is_some() {
+            // The design will be updated once, when the batch is committed.
+            return;
+        }
+        if self.app_state.design_need_update() {
+            let update_start = Instant::now();
+            self.app_state.update();
+            self.performance.presenter_updated(update_start.elapsed());
+        } else {
+            self.app_state.update();
+        }
+        self.update_tutorial();
+    }
+
+    fn handle_tutorial_request(&mut self, request: tutorial::TutorialRequest) {
+        use tutorial::TutorialRequest;
+        match request {
+            TutorialRequest::Start => {
+                self.tutorial = Some(tutorial::Tutorial::new(self.app_state.clone()));
+                self.show_tutorial_step();
+            }
+            TutorialRequest::SkipStep => {
+                let app_state = self.app_state.clone();
+                if let Some(tutorial) = self.tutorial.as_mut() {
+                    if tutorial.next_step(app_state) {
+                        self.show_tutorial_step();
+                    } else {
+                        self.finish_tutorial();
+                    }
+                }
+            }
+            TutorialRequest::Stop => self.tutorial = None,
+        }
+    }
+
+    fn update_tutorial(&mut self) {
+        let need_save = self.need_save();
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            let step = tutorial.status().step;
+            if !tutorial.update(&self.app_state, need_save) {
+                self.finish_tutorial();
+            } else if tutorial.status().step != step {
+                self.show_tutorial_step();
+            }
+        }
+    }
+
+    /// Make sure that the region of the window refered to by the current step is visible.
+    fn show_tutorial_step(&mut self) {
+        use gui::TutorialHighlight;
+        if let Some(tutorial) = self.tutorial.as_ref() {
+            if let TutorialHighlight::Scene3D | TutorialHighlight::FlatScene =
+                tutorial.status().highlight
+            {
+                self.push_action(Action::ToggleSplit(SplitMode::Both));
+            }
+        }
+    }
+
+    fn finish_tutorial(&mut self) {
+        self.tutorial = None;
+        self.push_toast(
+            ToastSeverity::Info,
+            "Congratulations, you have completed the tutorial!".to_string(),
+        );
     }
 
     fn update_candidates(&mut self, candidates: Vec<Selection>) {
@@ -1124,9 +1703,26 @@ impl MainState {
 
     fn update_selection(
         &mut self,
-        selection: Vec<Selection>,
+        mut selection: Vec<Selection>,
         group_id: Option<ensnano_organizer::GroupId>,
     ) {
+        if self.app_state.get_selection_mode() == SelectionMode::BasePair {
+            // Select the nucleotides together with the nucleotide they are paired with
+            let reader = self.app_state.get_design_reader();
+            let paired: Vec<Selection> = selection
+                .iter()
+                .filter_map(|s| {
+                    if let Selection::Nucleotide(d_id, nucl) = s {
+                        reader
+                            .get_paired_nucl(*nucl)
+                            .map(|paired| Selection::Nucleotide(*d_id, paired))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            selection.extend(paired);
+        }
         self.modify_state(
             |s| s.with_selection(selection, group_id),
             Some("Selection".into()),
@@ -1137,12 +1733,34 @@ impl MainState {
         self.modify_state(|s| s.with_center_of_selection(center), None)
     }
 
+    /// Return true and notify the user if the design cannot be modified.
+    fn reject_if_read_only(&self) -> bool {
+        if self.read_only {
+            self.push_toast(
+                ToastSeverity::Warning,
+                "The design cannot be modified in viewer mode".to_string(),
+            );
+        }
+        self.read_only
+    }
+
     fn apply_copy_operation(&mut self, operation: CopyOperation) {
         let result = self.app_state.apply_copy_operation(operation);
         self.apply_operation_result(result);
     }
 
     fn apply_operation(&mut self, operation: DesignOperation) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        // The error, if any, is already shown to the user
+        let _ = self.try_apply_operation(operation);
+    }
+
+    /// Apply `operation` on the design and return the error it caused, if any. The error is also
+    /// shown to the user. The caller is responsible for checking that the design is not
+    /// read-only.
+    fn try_apply_operation(&mut self, operation: DesignOperation) -> Result<(), String> {
         log::debug!("Applying operation {:?}", operation);
         let result = self.app_state.apply_design_op(operation.clone());
         if let Err(ErrOperation::FinishFirst) = result {
@@ -1150,13 +1768,22 @@ impl MainState {
                 |s| s.notified(app_state::InteractorNotification::FinishOperation),
                 None,
             );
-            self.apply_operation(operation);
+            self.try_apply_operation(operation)
         } else {
+            #[cfg(feature = "collaboration")]
+            if result.is_ok() {
+                self.collaboration.broadcast(&operation);
+            }
+            let error = result.as_ref().err().map(|e| e.to_string());
             self.apply_operation_result(result);
+            error.map_or(Ok(()), Err)
         }
     }
 
     fn start_helix_simulation(&mut self, parameters: RigidBodyConstants) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.start_simulation(
             parameters,
             &mut self.channel_reader,
@@ -1165,7 +1792,22 @@ impl MainState {
         self.apply_operation_result(result)
     }
 
+    fn start_group_simulation(&mut self, group_id: GroupId, parameters: RigidBodyConstants) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        let result = self.app_state.start_simulation(
+            parameters,
+            &mut self.channel_reader,
+            SimulationTarget::Group(group_id),
+        );
+        self.apply_operation_result(result)
+    }
+
     fn start_grid_simulation(&mut self, parameters: RigidBodyConstants) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.start_simulation(
             parameters,
             &mut self.channel_reader,
@@ -1174,7 +1816,22 @@ impl MainState {
         self.apply_operation_result(result)
     }
 
+    fn start_bundle_simulation(&mut self, parameters: RigidBodyConstants) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        let result = self.app_state.start_simulation(
+            parameters,
+            &mut self.channel_reader,
+            SimulationTarget::Bundles,
+        );
+        self.apply_operation_result(result)
+    }
+
     fn start_revolution_simulation(&mut self, desc: RevolutionSurfaceSystemDescriptor) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.start_simulation(
             Default::default(),
             &mut self.channel_reader,
@@ -1184,6 +1841,9 @@ impl MainState {
     }
 
     fn start_twist(&mut self, grid_id: GridId) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.start_simulation(
             Default::default(),
             &mut self.channel_reader,
@@ -1192,11 +1852,93 @@ impl MainState {
         self.apply_operation_result(result)
     }
 
-    fn start_roll_simulation(&mut self, target_helices: Option<Vec<usize>>) {
+    /// Twist the selected free helices around the current twist axis. If there is no twist
+    /// axis, it is first set from the selection.
+    fn start_helices_twist(&mut self) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        let helices = ensnano_interactor::extract_helices(self.app_state.get_selection().as_ref());
+        if self.app_state.get_twist_axis().is_none() {
+            self.set_twist_axis_from_selection();
+        }
+        if let Some(axis) = self.app_state.get_twist_axis() {
+            let result = self.app_state.start_simulation(
+                Default::default(),
+                &mut self.channel_reader,
+                SimulationTarget::TwistHelices { helices, axis },
+            );
+            self.apply_operation_result(result)
+        }
+    }
+
+    fn set_twist_axis(&mut self, axis: Option<TwistAxis>) {
+        self.modify_state(|s| s.with_twist_axis(axis), None)
+    }
+
+    /// Set the twist axis to the mean axis of the selected helices
+    fn set_twist_axis_from_selection(&mut self) {
+        let helices = ensnano_interactor::extract_helices(self.app_state.get_selection().as_ref());
+        let reader = self.app_state.get_design_reader();
+        let design = reader.get_design();
+        let mut origin = Vec3::zero();
+        let mut direction = Vec3::zero();
+        let mut nb_helices = 0;
+        for helix in helices.iter().filter_map(|h_id| design.helices.get(h_id)) {
+            let helix_direction = Vec3::unit_x().rotated_by(helix.orientation);
+            // Antiparallel helices must not cancel each other
+            if helix_direction.dot(direction) < 0. {
+                direction -= helix_direction;
+            } else {
+                direction += helix_direction;
+            }
+            origin += helix.position;
+            nb_helices += 1;
+        }
+        if nb_helices > 0 {
+            origin /= nb_helices as f32;
+            let axis = TwistAxis::from_origin_and_tip(origin, origin + direction);
+            self.set_twist_axis(axis)
+        }
+    }
+
+    /// Add a joint between the two bodies (grids or helix bundles) that contain the selection
+    fn add_joint_between_selected_bodies(&mut self, kind: JointKind) {
+        let bodies = self
+            .app_state
+            .get_design_reader()
+            .get_joint_bodies_of_selection(self.app_state.get_selection().as_ref());
+        if let [body_0, body_1] = bodies[..] {
+            self.apply_operation(DesignOperation::AddJoint {
+                bodies: (body_0, body_1),
+                kind,
+            })
+        } else {
+            self.push_toast(
+                ToastSeverity::Warning,
+                format!(
+                    "A joint links exactly two grids or bundles, the selection contains {}",
+                    bodies.len()
+                ),
+            );
+        }
+    }
+
+    fn start_roll_simulation(
+        &mut self,
+        target_helices: Option<Vec<usize>>,
+        target_xovers: Option<Vec<usize>>,
+    ) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.start_simulation(
             Default::default(),
             &mut self.channel_reader,
-            SimulationTarget::Roll { target_helices },
+            SimulationTarget::Roll {
+                target_helices,
+                target_xovers,
+            },
         );
         self.apply_operation_result(result)
     }
@@ -1207,8 +1949,14 @@ impl MainState {
     }
 
     fn apply_silent_operation(&mut self, operation: DesignOperation) {
+        if self.reject_if_read_only() {
+            return;
+        }
         match self.app_state.apply_design_op(operation.clone()) {
-            Ok(_) => (),
+            Ok(_) => {
+                #[cfg(feature = "collaboration")]
+                self.collaboration.broadcast(&operation);
+            }
             Err(ErrOperation::FinishFirst) => {
                 self.modify_state(
                     |s| s.notified(app_state::InteractorNotification::FinishOperation),
@@ -1221,13 +1969,118 @@ impl MainState {
     }
 
     fn save_old_state(&mut self, old_state: AppState, label: TransitionLabel) {
-        let camera_3d = self.get_camera_3d();
-        self.undo_stack.push(AppStateTransition {
-            state: old_state,
+        self.push_undo_entry(old_state, label);
+    }
+
+    /// Push `old_state` on the undo stack and clear the redo stack.
+    ///
+    /// If the label is coalescable and the previous entry of the undo stack has the same label
+    /// and was pushed less than `UNDO_COALESCING_DELAY` ago, the two operations are merged in a
+    /// single entry.
+    fn push_undo_entry(&mut self, old_state: AppState, label: TransitionLabel) {
+        if self.batch.is_some() {
+            log::trace!("{} is part of the current batch", label.as_ref());
+            self.redo_stack.clear();
+            return;
+        }
+        self.nb_operations += 1;
+        let now = Instant::now();
+        if let Some(last) = self.undo_stack.last_mut().filter(|t| {
+            label.is_coalescable()
+                && t.label.as_ref() == label.as_ref()
+                && now.duration_since(t.date) < consts::UNDO_COALESCING_DELAY
+        }) {
+            log::debug!("Merging {} in the previous undo entry", label.as_ref());
+            last.date = now;
+        } else {
+            let camera_3d = self.get_camera_3d();
+            self.undo_stack.push(AppStateTransition {
+                state: old_state,
+                label,
+                camera_3d,
+                date: now,
+            });
+        }
+        self.redo_stack.clear();
+        self.trim_undo_stack();
+    }
+
+    /// Start recording a batch of operations.
+    ///
+    /// Until the matching call to `commit_batch`, the operations that are applied do not push
+    /// entries on the undo stack and the design is not updated. Batches can be nested, in which
+    /// case only the outermost one is recorded.
+    fn begin_batch(&mut self, label: TransitionLabel) {
+        if let Some(batch) = self.batch.as_mut() {
+            batch.depth += 1;
+            return;
+        }
+        if !self.app_state.is_in_stable_state() {
+            self.modify_state(
+                |s| s.notified(app_state::InteractorNotification::FinishOperation),
+                None,
+            );
+        }
+        self.batch = Some(OperationBatch {
+            old_state: self.app_state.clone(),
             label,
-            camera_3d,
+            depth: 1,
         });
-        self.redo_stack.clear();
+    }
+
+    /// Stop recording the current batch of operations.
+    ///
+    /// When the outermost batch is committed, a single undo entry is pushed for all the
+    /// operations of the batch and the design is updated once.
+    fn commit_batch(&mut self) {
+        if let Some(batch) = self.batch.as_mut() {
+            batch.depth -= 1;
+            if batch.depth > 0 {
+                return;
+            }
+        } else {
+            log::warn!("commit_batch called without begin_batch");
+            return;
+        }
+        if let Some(batch) = self.batch.take() {
+            self.modify_state(
+                |s| s.notified(app_state::InteractorNotification::FinishOperation),
+                None,
+            );
+            if batch.old_state != self.app_state {
+                self.push_undo_entry(batch.old_state, batch.label);
+            }
+            self.app_state.update();
+        }
+    }
+
+    /// Forget the oldest entries of the undo stack that exceed the undo limit
+    fn trim_undo_stack(&mut self) {
+        let limit = self.app_state.undo_limit();
+        if self.undo_stack.len() > limit {
+            let nb_removed = self.undo_stack.len() - limit;
+            self.undo_stack.drain(..nb_removed);
+        }
+    }
+
+    /// The statistics displayed by the performance overlay
+    fn performance_stats(&self) -> gui::PerformanceStats {
+        let nb_instances = self
+            .applications
+            .values()
+            .filter_map(|app| app.lock().unwrap().get_nb_instances())
+            .sum();
+        self.performance.stats(nb_instances)
+    }
+
+    /// The length of the undo stacks and the memory used by the application
+    fn memory_usage(&self) -> gui::MemoryUsage {
+        gui::MemoryUsage {
+            undo_entries: self.undo_stack.len(),
+            redo_entries: self.redo_stack.len(),
+            undo_limit: self.app_state.undo_limit(),
+            resident_bytes: resident_memory(),
+        }
     }
 
     fn set_roll_of_selected_helices(&mut self, roll: f32) {
@@ -1238,7 +2091,30 @@ impl MainState {
         }
     }
 
+    fn set_ideal_roll_of_selected_helices(&mut self) {
+        if let Some((_, helices)) =
+            ensnano_interactor::list_of_helices(self.app_state.get_selection().as_ref())
+        {
+            self.apply_operation(DesignOperation::SetIdealRollHelices { helices })
+        }
+    }
+
+    fn place_insertions_on_selected_helices(&mut self, target: InsertionPlacementTarget) {
+        if let Some((_, helices)) =
+            ensnano_interactor::list_of_helices(self.app_state.get_selection().as_ref())
+        {
+            self.apply_operation(DesignOperation::PlaceInsertions { helices, target })
+        }
+    }
+
     fn undo(&mut self) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        if self.batch.is_some() {
+            log::warn!("Cannot undo while a batch of operations is being recorded");
+            return;
+        }
         if let Some(mut transition) = self.undo_stack.pop() {
             transition.state.prepare_for_replacement(&self.app_state);
             let mut redo_state = std::mem::replace(&mut self.app_state, transition.state);
@@ -1253,12 +2129,20 @@ impl MainState {
                     state: redo_state,
                     label: transition.label,
                     camera_3d: transition.camera_3d,
+                    date: transition.date,
                 });
             }
         }
     }
 
     fn redo(&mut self) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        if self.batch.is_some() {
+            log::warn!("Cannot redo while a batch of operations is being recorded");
+            return;
+        }
         if let Some(mut transition) = self.redo_stack.pop() {
             transition.state.prepare_for_replacement(&self.app_state);
             let undo_state = std::mem::replace(&mut self.app_state, transition.state);
@@ -1271,6 +2155,7 @@ impl MainState {
                 state: undo_state,
                 camera_3d: transition.camera_3d,
                 label: transition.label,
+                date: transition.date,
             });
         }
     }
@@ -1284,18 +2169,15 @@ impl MainState {
         self.app_state = modification(state);
         if let Some(label) = undo_label {
             if old_state != self.app_state && old_state.is_in_stable_state() {
-                let camera_3d = self.get_camera_3d();
-                self.undo_stack.push(AppStateTransition {
-                    state: old_state,
-                    label,
-                    camera_3d,
-                });
-                self.redo_stack.clear();
+                self.push_undo_entry(old_state, label);
             }
         }
     }
 
     fn update_pending_operation(&mut self, operation: Arc<dyn Operation>) {
+        if self.reject_if_read_only() {
+            return;
+        }
         let result = self.app_state.update_pending_operation(operation.clone());
         if let Err(ErrOperation::FinishFirst) = result {
             self.modify_state(
@@ -1307,17 +2189,306 @@ impl MainState {
         self.apply_operation_result(result);
     }
 
+    fn push_simulation_job(
+        &mut self,
+        kind: ensnano_interactor::SimulationJobKind,
+        parameters: RigidBodyConstants,
+    ) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        self.simulation_jobs.push(kind, parameters);
+    }
+
+    /// Apply the results of the simulation jobs, start the queued jobs and notify the GUI of the
+    /// changes in the list of jobs.
+    fn poll_simulation_jobs(&mut self) {
+        for update in self.simulation_jobs.get_updates() {
+            self.app_state.apply_simulation_update(update)
+        }
+        for (job, err) in self.simulation_jobs.start_queued_jobs(&self.app_state) {
+            log::warn!("{:?}", err);
+            self.push_toast(
+                ToastSeverity::Error,
+                format!("Could not start job {}: {}", job, err),
+            );
+        }
+        let infos = self.simulation_jobs.infos();
+        if infos != self.last_simulation_job_infos {
+            self.messages
+                .lock()
+                .unwrap()
+                .set_simulation_jobs(infos.clone());
+            self.last_simulation_job_infos = infos;
+        }
+    }
+
+    /// Apply the operations received from the collaborator. They are not broadcast back.
+    #[cfg(feature = "collaboration")]
+    fn apply_remote_operations(&mut self) {
+        for operation in self.collaboration.received_operations() {
+            if self.read_only {
+                continue;
+            }
+            log::debug!("Applying remote operation {:?}", operation);
+            let mut result = self.app_state.apply_design_op(operation.clone());
+            if let Err(ErrOperation::FinishFirst) = result {
+                self.modify_state(
+                    |s| s.notified(app_state::InteractorNotification::FinishOperation),
+                    None,
+                );
+                result = self.app_state.apply_design_op(operation);
+            }
+            self.apply_operation_result(result);
+        }
+    }
+
+    /// Answer the requests received by the HTTP API, applying the operations that they contain.
+    #[cfg(feature = "http_api")]
+    fn answer_http_requests(&mut self) {
+        for request in self.http_api.pending_requests() {
+            let reader = self.app_state.get_design_reader();
+            if let Some(mut request) = request.answer(&reader, self.read_only) {
+                let operations = std::mem::take(&mut request.operations);
+                let mut applied = 0;
+                let mut error = None;
+                self.begin_batch("HTTP API operations".to_string().into());
+                for operation in operations {
+                    if let Err(e) = self.try_apply_operation(operation) {
+                        error = Some(e);
+                        break;
+                    }
+                    applied += 1;
+                }
+                self.commit_batch();
+                request.answer(applied, error);
+            }
+        }
+    }
+
+    /// Highlight the differences between `reference` and the current design.
+    fn show_design_diff(&mut self, reference: &AppState) {
+        let diff = DesignDiff::new(
+            reference.get_design_reader().get_design(),
+            self.app_state.get_design_reader().get_design(),
+        );
+        self.push_toast(ToastSeverity::Info, diff.to_string());
+        self.modify_state(|s| s.with_design_diff(Some(diff)), None);
+    }
+
+    fn compare_with_last_save(&mut self) {
+        let reference = self.last_saved_state.clone();
+        self.show_design_diff(&reference);
+    }
+
+    fn stop_design_comparison(&mut self) {
+        self.modify_state(|s| s.with_design_diff(None), None);
+    }
+
+    fn create_checkpoint(&mut self, name: String) {
+        let name = if name.trim().is_empty() {
+            format!("Checkpoint {}", self.checkpoints.len() + 1)
+        } else {
+            name.trim().to_string()
+        };
+        self.checkpoints.push(Checkpoint {
+            name,
+            state: self.app_state.clone(),
+        });
+        self.update_checkpoints_list();
+    }
+
+    /// Replace the current design by the one of a checkpoint. This can be undone and does not
+    /// modify the file on disk.
+    fn restore_checkpoint(&mut self, checkpoint_id: usize) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        if let Some(checkpoint) = self.checkpoints.get(checkpoint_id) {
+            let design = checkpoint.state.get_design_reader().get_design().clone();
+            let label = format!("Restore checkpoint \"{}\"", checkpoint.name);
+            self.modify_state(|s| s.with_updated_design(design), Some(label.into()));
+        }
+    }
+
+    fn compare_with_checkpoint(&mut self, checkpoint_id: usize) {
+        if let Some(reference) = self.checkpoints.get(checkpoint_id).map(|c| c.state.clone()) {
+            self.show_design_diff(&reference);
+        }
+    }
+
+    fn delete_checkpoint(&mut self, checkpoint_id: usize) {
+        if checkpoint_id < self.checkpoints.len() {
+            self.checkpoints.remove(checkpoint_id);
+            self.update_checkpoints_list();
+        }
+    }
+
+    fn move_group_pivot(&mut self, group_id: GroupId, operation: GroupOperation) {
+        use ensnano_design::group_attributes::GroupPivot;
+        let reader = self.app_state.get_design_reader();
+        let current_pivot = reader
+            .get_group_attributes(group_id)
+            .and_then(|attributes| attributes.pivot);
+        let elements = reader.get_positions_of_group_elements(group_id);
+        let bounding_box_center = || {
+            let mut positions = elements.iter().flatten();
+            let first = *positions.next()?;
+            let (min, max) = positions.fold((first, first), |(min, max), p| {
+                (min.min_by_component(*p), max.max_by_component(*p))
+            });
+            Some((min + max) / 2.)
+        };
+        let position = match operation {
+            GroupOperation::SetPivotPosition(position) => Some(position),
+            GroupOperation::ResetPivot => bounding_box_center(),
+            GroupOperation::SnapPivotToCentroid => {
+                let reference = current_pivot
+                    .map(|p| p.position)
+                    .or_else(bounding_box_center);
+                reference.and_then(|reference| {
+                    elements
+                        .iter()
+                        .map(|positions| {
+                            positions.iter().fold(Vec3::zero(), |acc, p| acc + *p)
+                                / positions.len() as f32
+                        })
+                        .min_by(|a, b| {
+                            (*a - reference)
+                                .mag_sq()
+                                .total_cmp(&(*b - reference).mag_sq())
+                        })
+                })
+            }
+            _ => None,
+        };
+        if let Some(position) = position {
+            let pivot = GroupPivot {
+                position,
+                orientation: current_pivot
+                    .map(|p| p.orientation)
+                    .unwrap_or_else(Rotor3::identity),
+            };
+            self.apply_operation(DesignOperation::SetGroupPivot { group_id, pivot })
+        } else {
+            self.push_toast(
+                ToastSeverity::Warning,
+                "This group has no element with a position".into(),
+            );
+        }
+    }
+
+    fn update_checkpoints_list(&mut self) {
+        let names = self.checkpoints.iter().map(|c| c.name.clone()).collect();
+        self.messages.lock().unwrap().set_checkpoints(names);
+    }
+
+    fn apply_group_operation(&mut self, group_id: GroupId, operation: GroupOperation) {
+        if self.reject_if_read_only() {
+            return;
+        }
+        if let GroupOperation::SetPivotPosition(_)
+        | GroupOperation::SnapPivotToCentroid
+        | GroupOperation::ResetPivot = operation
+        {
+            self.move_group_pivot(group_id, operation);
+            return;
+        }
+        let elements = self
+            .app_state
+            .get_design_reader()
+            .get_design()
+            .organizer_tree
+            .as_ref()
+            .and_then(|tree| tree.get_elements_of_group(group_id));
+        let elements = if let Some(elements) = elements {
+            elements
+        } else {
+            self.push_toast(ToastSeverity::Error, "This group does not exist".into());
+            return;
+        };
+        let operation = match operation {
+            GroupOperation::SetVisibility(visible) => DesignOperation::UpdateAttribute {
+                attribute: DnaAttribute::Visible(visible),
+                elements: elements
+                    .into_iter()
+                    .filter(|e| matches!(e, DesignElementKey::Helix(_) | DesignElementKey::Grid(_)))
+                    .collect(),
+            },
+            GroupOperation::SetLocked(locked) => DesignOperation::UpdateAttribute {
+                attribute: DnaAttribute::LockedForSimulations(locked),
+                elements: elements
+                    .into_iter()
+                    .filter(|e| matches!(e, DesignElementKey::Helix(_)))
+                    .collect(),
+            },
+            GroupOperation::SetPivotPosition(_)
+            | GroupOperation::SnapPivotToCentroid
+            | GroupOperation::ResetPivot => unreachable!("handled by move_group_pivot"),
+            GroupOperation::Recolor => {
+                let strands: Vec<usize> = elements
+                    .into_iter()
+                    .filter_map(|e| {
+                        if let DesignElementKey::Strand(s_id) = e {
+                            Some(s_id)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                if strands.is_empty() {
+                    self.push_toast(ToastSeverity::Warning, "This group has no strand".into());
+                    return;
+                }
+                let mut color_idx = random::<u8>() as usize;
+                DesignOperation::ChangeColor {
+                    color: utils::colors::new_color(&mut color_idx),
+                    strands,
+                }
+            }
+        };
+        self.apply_operation(operation)
+    }
+
+    fn run_plugin(&mut self, plugin_id: usize, values: Vec<String>) {
+        let reader = self.app_state.get_design_reader();
+        let selection = self.app_state.get_selection();
+        let context = plugins::Context {
+            design: &reader,
+            selection: selection.as_ref(),
+        };
+        if let Some(output) = self.plugins.run(plugin_id, &context, &values) {
+            if !output.operations.is_empty() && !self.reject_if_read_only() {
+                self.push_action(Action::DesignOperationBatch {
+                    label: "Run plugin".to_string(),
+                    operations: output.operations,
+                });
+            }
+            if let Some(message) = output.message {
+                self.push_toast(ToastSeverity::Info, message);
+            }
+        }
+    }
+
     fn optimize_shift(&mut self) {
         let reader = &mut self.channel_reader;
         let result = self.app_state.optimize_shift(reader);
         self.apply_operation_result(result);
     }
 
+    fn push_toast(&self, severity: ToastSeverity, message: String) {
+        let message = ensnano_interactor::i18n::tr(&message).to_string();
+        self.messages.lock().unwrap().push_toast(severity, message)
+    }
+
     fn apply_operation_result(&mut self, result: Result<OkOperation, ErrOperation>) {
         match result {
             Ok(OkOperation::Undoable { state, label }) => self.save_old_state(state, label),
             Ok(OkOperation::NotUndoable) => (),
-            Err(e) => log::warn!("{:?}", e),
+            Err(e) => {
+                log::warn!("{:?}", e);
+                self.push_toast(ToastSeverity::Warning, e.to_string());
+            }
         }
         if let Some(new_selection) = self.app_state.get_new_selection() {
             self.modify_state(|s| s.with_selection(new_selection, None), None)
@@ -1345,6 +2516,9 @@ impl MainState {
 
     fn apply_paste(&mut self) {
         log::info!("apply paste");
+        if self.reject_if_read_only() {
+            return;
+        }
         match self.app_state.get_pasting_status() {
             PastingStatus::Copy => self.apply_copy_operation(CopyOperation::Paste),
             PastingStatus::Duplication => self.apply_copy_operation(CopyOperation::Duplicate),
@@ -1353,6 +2527,9 @@ impl MainState {
     }
 
     fn request_duplication(&mut self) {
+        if self.reject_if_read_only() {
+            return;
+        }
         if self.app_state.can_iterate_duplication() {
             self.apply_copy_operation(CopyOperation::Duplicate)
         } else if let Some((_, nucl_pairs)) = ensnano_interactor::list_of_xover_as_nucl_pairs(
@@ -1372,7 +2549,12 @@ impl MainState {
         }
     }
 
+    /// Start saving the design in a background thread. `finish_saving_design` must be called once
+    /// the design has been written.
     fn save_design(&mut self, path: &PathBuf) -> Result<(), SaveDesignError> {
+        if self.channel_reader.is_doing_file_io() {
+            return Err(SaveDesignError::file_operation_in_progress());
+        }
         let camera = self
             .applications
             .get(&ElementType::Scene)
@@ -1383,18 +2565,51 @@ impl MainState {
                 position: camera.0.position,
                 orientation: camera.0.orientation,
                 pivot_position: camera.0.pivot_position,
+                rendering: None,
             });
-        let save_info = ensnano_design::SavingInformation { camera };
-        self.app_state.save_design(path, save_info)?;
+        let provenance = self
+            .variant_provenance
+            .as_ref()
+            .filter(|(variant_path, _)| variant_path == path)
+            .map(|(_, provenance)| provenance.clone());
+        let thumbnail_png = self
+            .applications
+            .get(&ElementType::Scene)
+            .and_then(|s| s.lock().unwrap().render_design_thumbnail());
+        let save_info = ensnano_design::SavingInformation {
+            camera,
+            provenance,
+            thumbnail_png,
+        };
+        self.app_state
+            .start_saving_design(path.clone(), save_info, &mut self.channel_reader);
+        self.state_being_saved = Some(self.app_state.clone()).filter(AppState::is_in_stable_state);
+        Ok(())
+    }
 
-        if self.app_state.is_in_stable_state() {
-            self.last_saved_state = self.app_state.clone();
+    fn finish_saving_design(&mut self, path: PathBuf) {
+        self.app_state.set_path_to_current_design(path);
+        if let Some(saved_state) = self.state_being_saved.take() {
+            self.last_saved_state = saved_state;
         }
         self.update_current_file_name();
-        Ok(())
     }
 
     fn save_backup(&mut self) -> Result<(), SaveDesignError> {
+        let path = self.backup_path()?;
+        if self.app_state.is_in_stable_state() {
+            let save_info = self.saving_information();
+            self.app_state.save_design(&path, save_info)?;
+            self.last_backed_up_state = self.app_state.clone();
+            println!("Saved backup to {}", path.to_string_lossy());
+        } else {
+            // Do nothing. We do not want to save backup in transitory states.
+        }
+
+        Ok(())
+    }
+
+    fn saving_information(&self) -> ensnano_design::SavingInformation {
         let camera = self
             .applications
             .get(&ElementType::Scene)
@@ -1405,11 +2620,86 @@ impl MainState {
                 position: camera.0.position,
                 orientation: camera.0.orientation,
                 pivot_position: camera.0.pivot_position,
+                rendering: None,
             });
-        let save_info = ensnano_design::SavingInformation { camera };
-        let path = if let Some(mut path) = self.app_state.path_to_current_design().cloned() {
+        ensnano_design::SavingInformation {
+            camera,
+            provenance: None,
+            thumbnail_png: None,
+        }
+    }
+
+    /// Record that the design is about to be saved at `path` as a variant of the current file
+    fn record_variant_provenance(&mut self, path: PathBuf) {
+        let parent = self
+            .app_state
+            .path_to_current_design()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let provenance = ensnano_design::DesignProvenance {
+            parent,
+            date: chrono::Local::now().to_rfc3339(),
+            operation_count: self.nb_operations,
+        };
+        self.variant_provenance = Some((path, provenance));
+    }
+
+    /// List the design files of `directory` in the open dialog. If `directory` is `None`, the
+    /// files of the user's documents folder are listed.
+    fn open_design_browser(&mut self, directory: Option<PathBuf>) {
+        let directory = directory
+            .or_else(dirs::document_dir)
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        self.design_browser_scan =
+            Some(AppState::start_scanning_design_directory(directory.clone()));
+        self.design_browser_choice = None;
+        let content = DesignBrowserContent {
+            directory,
+            files: Vec::new(),
+            scanning: true,
+        };
+        self.modify_state(|app| app.with_design_browser(Some(content)), None);
+    }
+
+    /// Add the design files that were read since the last call to the open dialog.
+    fn poll_design_browser_scan(&mut self) {
+        use std::sync::mpsc::TryRecvError;
+        let receiver = if let Some(receiver) = self.design_browser_scan.as_ref() {
+            receiver
+        } else {
+            return;
+        };
+        let mut new_files = Vec::new();
+        let finished = loop {
+            match receiver.try_recv() {
+                Ok(info) => new_files.push(info),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if finished {
+            self.design_browser_scan = None;
+        } else if new_files.is_empty() {
+            return;
+        }
+        if let Some(mut content) = self.app_state.get_design_browser().cloned() {
+            content.files.extend(new_files);
+            content.scanning = !finished;
+            self.modify_state(|app| app.with_design_browser(Some(content)), None);
+        }
+    }
+
+    fn close_design_browser(&mut self) {
+        self.design_browser_scan = None;
+        self.design_browser_choice = None;
+        self.modify_state(|app| app.with_design_browser(None), None);
+    }
+
+    fn backup_path(&mut self) -> Result<PathBuf, SaveDesignError> {
+        if let Some(mut path) = self.app_state.path_to_current_design().cloned() {
             path.set_extension(crate::consts::ENS_BACKUP_EXTENSION);
-            path
+            Ok(path)
         } else {
             let mut ret = dirs::document_dir()
                 .or_else(dirs::home_dir)
@@ -1420,17 +2710,8 @@ impl MainState {
                 })?;
             ret.push(crate::consts::ENS_UNNAMED_FILE_NAME);
             ret.set_extension(crate::consts::ENS_BACKUP_EXTENSION);
-            ret
-        };
-        if self.app_state.is_in_stable_state() {
-            self.app_state.save_design(&path, save_info)?;
-            self.last_backed_up_state = self.app_state.clone();
-            println!("Saved backup to {}", path.to_string_lossy());
-        } else {
-            // Do nothing. We do not want to save backup in transitory states.
+            Ok(ret)
         }
-
-        Ok(())
     }
 
     fn change_selection_mode(&mut self, mode: SelectionMode) {
@@ -1449,11 +2730,127 @@ impl MainState {
         self.modify_state(|s| s.with_toggled_widget_basis(), None)
     }
 
+    fn set_widget_basis(&mut self, widget_basis: WidgetBasis) {
+        self.modify_state(|s| s.with_widget_basis(widget_basis), None)
+    }
+
+    fn set_gizmo_size(&mut self, size: f32) {
+        self.modify_state(|s| s.with_gizmo_size(size), None)
+    }
+
+    fn set_binary_design_files(&mut self, binary: bool) {
+        self.modify_state(|s| s.with_binary_design_files(binary), None)
+    }
+
+    fn set_confirm_large_deletions(&mut self, confirm: bool) {
+        self.modify_state(|s| s.with_confirm_large_deletions(confirm), None)
+    }
+
+    fn set_gpu_preference(&mut self, gpu_preference: ensnano_interactor::graphics::GpuPreference) {
+        self.modify_state(|s| s.with_gpu_preference(gpu_preference), None)
+    }
+
+    fn set_render_quality(&mut self, render_quality: ensnano_interactor::graphics::RenderQuality) {
+        self.modify_state(|s| s.with_render_quality(render_quality), None)
+    }
+
+    fn set_shift_optimization_parameters(
+        &mut self,
+        parameters: ensnano_interactor::ShiftOptimizationParameters,
+    ) {
+        self.modify_state(|s| s.with_shift_optimization_parameters(parameters), None)
+    }
+
+    fn set_undo_limit(&mut self, limit: u32) {
+        self.modify_state(|s| s.with_undo_limit(limit), None);
+        self.trim_undo_stack();
+    }
+
+    fn set_ground_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::GroundParameters,
+    ) {
+        self.modify_state(|s| s.with_ground_parameters(parameters), None)
+    }
+
+    fn set_view_layer(
+        &mut self,
+        view: ElementType,
+        layer: ensnano_interactor::graphics::ViewLayer,
+    ) {
+        self.modify_state(|s| s.with_view_layer(view, layer), None)
+    }
+
+    fn set_scale_bar_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::ScaleBarParameters,
+    ) {
+        self.modify_state(|s| s.with_scale_bar_parameters(parameters), None)
+    }
+
+    fn set_numbering_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::NumberingParameters,
+    ) {
+        self.modify_state(|s| s.with_numbering_parameters(parameters), None)
+    }
+
+    fn set_xover_drawing_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::XoverDrawingParameters,
+    ) {
+        self.modify_state(|s| s.with_xover_drawing_parameters(parameters), None)
+    }
+
+    fn set_strand_ends_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::StrandEndsParameters,
+    ) {
+        self.modify_state(|s| s.with_strand_ends_parameters(parameters), None)
+    }
+
+    fn set_xover_guide_parameters(
+        &mut self,
+        parameters: ensnano_interactor::graphics::XoverGuideParameters,
+    ) {
+        self.modify_state(|s| s.with_xover_guide_parameters(parameters), None)
+    }
+
+    fn set_heat_map(&mut self, heat_map: Option<ensnano_design::heat_map::HeatMapKind>) {
+        self.modify_state(|s| s.with_heat_map(heat_map), None)
+    }
+
+    fn toggle_gizmo_axis(&mut self, axis: GizmoAxis) {
+        self.modify_state(|s| s.with_toggled_gizmo_axis(axis), None)
+    }
+
     fn set_visibility_sieve(&mut self, selection: Vec<Selection>, compl: bool) {
         let result = self.app_state.set_visibility_sieve(selection, compl);
         self.apply_operation_result(result)
     }
 
+    /// Select the strands `strands`. If `hide_others` is true, everything else is hidden.
+    fn highlight_strands(&mut self, strands: Vec<usize>, hide_others: bool) {
+        let selection: Vec<Selection> = strands
+            .into_iter()
+            .map(|s_id| Selection::Strand(0, s_id as u32))
+            .collect();
+        self.update_selection(selection.clone(), None);
+        if hide_others && !selection.is_empty() {
+            // Start from a fully visible design so that the sieve hides the other elements
+            // instead of toggling their visibility.
+            self.set_visibility_sieve(vec![], true);
+            self.set_visibility_sieve(selection, true);
+        }
+    }
+
+    fn set_invisible_nucls(&mut self, invisible_nucls: HashSet<Nucl>, undoable: bool) {
+        let result = self
+            .app_state
+            .set_invisible_nucls(invisible_nucls, undoable);
+        self.apply_operation_result(result)
+    }
+
     fn need_save(&self) -> bool {
         self.app_state.design_was_modified(&self.last_saved_state)
     }
@@ -1483,6 +2880,76 @@ impl MainState {
         self.modify_state(|s| s.with_follow_stereographic_camera(follow), None)
     }
 
+    fn set_inside_out_inspection(&mut self, inside_out: bool) {
+        self.modify_state(|s| s.with_inside_out_inspection(inside_out), None)
+    }
+
+    fn set_brush_parameters(&mut self, parameters: BrushParameters) {
+        self.modify_state(|s| s.with_brush_parameters(parameters), None)
+    }
+
+    /// Apply the effect of `brush` to the nucleotides painted during a brush stroke, in a single
+    /// undoable operation.
+    fn apply_brush(&mut self, nucls: Vec<Nucl>, brush: BrushParameters) {
+        if nucls.is_empty() {
+            return;
+        }
+        let reader = self.app_state.get_design_reader();
+        match brush.effect {
+            BrushEffect::Hide | BrushEffect::Show => {
+                let mut invisible_nucls: HashSet<Nucl> =
+                    reader.get_invisible_nucls().into_iter().collect();
+                if brush.effect == BrushEffect::Hide {
+                    invisible_nucls.extend(nucls)
+                } else {
+                    for n in nucls.iter() {
+                        invisible_nucls.remove(n);
+                    }
+                }
+                self.set_invisible_nucls(invisible_nucls, true)
+            }
+            BrushEffect::Color => {
+                let strands = reader.get_ids_of_strands_containing_nucls(&nucls);
+                if !strands.is_empty() {
+                    self.apply_operation(DesignOperation::ChangeColor {
+                        color: brush.color,
+                        strands,
+                    })
+                }
+            }
+            BrushEffect::Group => {
+                let group_id = if let Some(group_id) = self.app_state.get_current_group_id() {
+                    group_id
+                } else {
+                    self.push_toast(
+                        ToastSeverity::Warning,
+                        "Select a group in the organizer to add nucleotides to it".into(),
+                    );
+                    return;
+                };
+                let mut tree = if let Some(tree) = reader.get_design().organizer_tree.as_ref() {
+                    tree.as_ref().clone()
+                } else {
+                    self.push_toast(ToastSeverity::Error, "This group does not exist".into());
+                    return;
+                };
+                let elements: Vec<DesignElementKey> = nucls
+                    .into_iter()
+                    .map(|n| DesignElementKey::Nucleotide {
+                        helix: n.helix,
+                        position: n.position,
+                        forward: n.forward,
+                    })
+                    .collect();
+                if tree.add_elements_to_group(group_id, &elements) {
+                    self.apply_operation(DesignOperation::SetOrganizerTree(tree))
+                } else {
+                    self.push_toast(ToastSeverity::Error, "This group does not exist".into());
+                }
+            }
+        }
+    }
+
     fn set_show_stereographic_camera(&mut self, show: bool) {
         self.modify_state(|s| s.with_show_stereographic_camera(show), None)
     }
@@ -1495,6 +2962,10 @@ impl MainState {
         self.modify_state(|s| s.with_show_bezier_paths(show), None)
     }
 
+    fn set_show_insertion_badges(&mut self, show: bool) {
+        self.modify_state(|s| s.with_show_insertion_badges(show), None)
+    }
+
     fn set_all_helices_on_axis(&mut self, off_axis: bool) {
         self.modify_state(|s| s.all_helices_on_axis(off_axis), None)
     }
@@ -1550,12 +3021,73 @@ impl MainState {
         self.modify_state(|s| s.with_toggled_all_helices_on_axis(), None)
     }
 
-    fn set_background_3d(&mut self, bg: ensnano_interactor::graphics::Background3D) {
-        self.modify_state(|s| s.with_background3d(bg), None)
+    fn set_background_3d(&mut self, bg: ensnano_interactor::graphics::Background3D) {
+        self.modify_state(|s| s.with_background3d(bg), None)
+    }
+
+    fn set_theme(&mut self, theme: ensnano_interactor::graphics::Theme) {
+        self.modify_state(|s| s.with_theme(theme), None)
+    }
+
+    fn set_color_vision_mode(&mut self, mode: ensnano_interactor::graphics::ColorVisionMode) {
+        self.modify_state(|s| s.with_color_vision_mode(mode), None)
+    }
+
+    fn set_language(&mut self, language: ensnano_interactor::i18n::Language) {
+        ensnano_interactor::i18n::set_language(language);
+        self.modify_state(|s| s.with_language(language), None)
+    }
+
+    fn set_rendering_mode(&mut self, rendering_mode: ensnano_interactor::graphics::RenderingMode) {
+        self.modify_state(|s| s.with_rendering_mode(rendering_mode), None)
+    }
+
+    fn apply_camera_rendering(&mut self, rendering: ensnano_design::CameraRenderingSettings) {
+        use ensnano_interactor::graphics::{Background3D, RenderingMode};
+        let rendering_mode = if rendering.cartoon {
+            RenderingMode::Cartoon
+        } else {
+            RenderingMode::Normal
+        };
+        let background = if rendering.white_background {
+            Background3D::White
+        } else {
+            Background3D::Sky
+        };
+        // Going through the gui keeps the settings displayed in the camera tab up to date.
+        self.messages
+            .lock()
+            .unwrap()
+            .set_3d_rendering(rendering_mode, background);
+    }
+
+    /// Store the current rendering settings in a camera, or forget the settings stored in it.
+    fn store_camera_rendering(&mut self, camera_id: ensnano_design::CameraId, store: bool) {
+        use ensnano_interactor::graphics::{Background3D, RenderingMode};
+        let rendering = if store {
+            let draw_options = scene::AppState::get_draw_options(&self.app_state);
+            Some(ensnano_design::CameraRenderingSettings {
+                cartoon: draw_options.rendering_mode == RenderingMode::Cartoon,
+                white_background: draw_options.background3d == Background3D::White,
+            })
+        } else {
+            None
+        };
+        self.apply_operation(DesignOperation::SetCameraRendering {
+            camera_id,
+            rendering,
+        })
     }
 
-    fn set_rendering_mode(&mut self, rendering_mode: ensnano_interactor::graphics::RenderingMode) {
-        self.modify_state(|s| s.with_rendering_mode(rendering_mode), None)
+    fn set_camera_thumbnail(
+        &mut self,
+        camera_id: ensnano_design::CameraId,
+        thumbnail: ensnano_interactor::graphics::Thumbnail,
+    ) {
+        self.messages
+            .lock()
+            .unwrap()
+            .set_camera_thumbnail(camera_id, thumbnail)
     }
 
     fn set_scroll_sensitivity(&mut self, sensitivity: f32) {
@@ -1579,7 +3111,15 @@ impl MainState {
                 .map(|app| app.lock().unwrap().is_splited())
                 .unwrap_or(false),
             can_toggle_2d: multiplexer.is_showing(&ElementType::FlatScene)
-                || multiplexer.is_showing(&ElementType::StereographicScene),
+                || multiplexer.is_showing(&ElementType::StereographicScene)
+                || multiplexer.is_showing(&ElementType::CrossSection),
+            last_operation: self
+                .undo_stack
+                .last()
+                .map(|transition| transition.label.as_ref().to_string()),
+            read_only: self.read_only,
+            left_panel_detached: multiplexer.left_pannel_is_detached(),
+            tutorial: self.tutorial.as_ref().map(|t| t.status()),
         }
     }
 
@@ -1613,11 +3153,103 @@ struct MainStateView<'a> {
     multiplexer: &'a mut Multiplexer,
     scheduler: &'a mut Scheduler,
     gui: &'a mut Gui<Requests, AppState>,
+    overlay_manager: &'a mut OverlayManager,
     window: &'a Window,
     resized: bool,
 }
 
-use controller::{LoadDesignError, MainState as MainStateInterface, StaplesDownloader};
+impl<'a> MainStateView<'a> {
+    fn get_session(&self) -> session::Session {
+        let selection = self.main_state.app_state.get_selection();
+        session::Session {
+            split_mode: self.multiplexer.get_split_mode(),
+            left_pannel_proportion: self.multiplexer.get_left_pannel_proportion(),
+            selected_tab: self.gui.get_selected_tab(),
+            selection: selection
+                .as_ref()
+                .iter()
+                .filter_map(session::SessionSelection::from_selection)
+                .collect(),
+            camera_3d: Some(self.main_state.get_camera_3d()),
+            camera_2d: self
+                .main_state
+                .applications
+                .get(&ElementType::FlatScene)
+                .and_then(|s| s.lock().unwrap().get_camera_2d()),
+        }
+    }
+
+    /// Replace the current design by a design that was read in a background thread
+    fn finish_loading_design(&mut self, path: PathBuf, mut design: ensnano_design::Design) {
+        let session = session::Session::load(&path);
+        let nb_new_ids = design.assign_stable_ids();
+        let state = AppState::from_loaded_design(path, design);
+        self.notify_apps(Notification::ClearDesigns);
+        self.main_state.clear_app_state(state);
+        if let Some((position, orientation)) = self
+            .main_state
+            .app_state
+            .get_design_reader()
+            .get_favourite_camera()
+        {
+            self.notify_apps(Notification::TeleportCamera(
+                ensnano_interactor::application::Camera3D {
+                    position,
+                    orientation,
+                    pivot_position: None,
+                },
+            ));
+        } else {
+            self.main_state.wants_fit = true;
+        }
+        if let Some(session) = session {
+            self.restore_session(session);
+        }
+        self.main_state.update_current_file_name();
+        if nb_new_ids > 0 {
+            self.main_state.push_toast(
+                ToastSeverity::Info,
+                format!(
+                    "{} strands and helices were given a stable identifier. \
+                    Save the design to keep these identifiers.",
+                    nb_new_ids
+                ),
+            );
+        }
+    }
+
+    fn restore_session(&mut self, session: session::Session) {
+        if let Some(proportion) = session.left_pannel_proportion {
+            self.multiplexer.set_left_pannel_proportion(proportion);
+        }
+        self.toggle_split_mode(session.split_mode);
+        if let Some(tab) = session.selected_tab {
+            self.main_state
+                .messages
+                .lock()
+                .unwrap()
+                .push_selected_tab(tab);
+        }
+        let selection = session
+            .selection
+            .into_iter()
+            .map(session::SessionSelection::to_selection)
+            .collect();
+        self.main_state
+            .modify_state(|s| s.with_selection(selection, None), None);
+        if let Some(camera) = session.camera_3d {
+            self.main_state.wants_fit = false;
+            self.notify_apps(Notification::TeleportCamera(camera));
+        }
+        if let Some(camera) = session.camera_2d {
+            self.notify_apps(Notification::TeleportCamera2D(camera));
+        }
+    }
+}
+
+use controller::{
+    DeletionSummary, LoadDesignError, MainState as MainStateInterface, StaplesDownloader,
+};
 impl<'a> MainStateInterface for MainStateView<'a> {
     fn pop_action(&mut self) -> Option<Action> {
         if !self.main_state.pending_actions.is_empty() {
@@ -1654,6 +3286,18 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.new_design()
     }
 
+    fn new_design_from_template(&mut self, template: DesignTemplate) {
+        match AppState::from_template(template) {
+            Ok(state) => {
+                self.notify_apps(Notification::ClearDesigns);
+                self.main_state.clear_app_state(state);
+                self.main_state.wants_fit = true;
+                self.main_state.update_current_file_name();
+            }
+            Err(e) => log::error!("Could not generate template {}: {:?}", template, e),
+        }
+    }
+
     fn export(&mut self, path: &PathBuf, export_type: ExportType) -> ExportResult {
         let ret = self.main_state.app_state.export(path, export_type);
         self.set_exporting(false);
@@ -1661,29 +3305,59 @@ impl<'a> MainStateInterface for MainStateView<'a> {
     }
 
     fn load_design(&mut self, path: PathBuf) -> Result<(), LoadDesignError> {
-        let state = AppState::import_design(path)?;
-        self.notify_apps(Notification::ClearDesigns);
-        self.main_state.clear_app_state(state);
-        if let Some((position, orientation)) = self
-            .main_state
-            .app_state
-            .get_design_reader()
-            .get_favourite_camera()
-        {
-            self.notify_apps(Notification::TeleportCamera(
-                ensnano_interactor::application::Camera3D {
-                    position,
-                    orientation,
-                    pivot_position: None,
-                },
-            ));
-        } else {
-            self.main_state.wants_fit = true;
+        if self.main_state.channel_reader.is_doing_file_io() {
+            return Err(LoadDesignError::IoError(std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                crate::consts::FILE_OPERATION_IN_PROGRESS,
+            )));
         }
-        self.main_state.update_current_file_name();
+        AppState::start_loading_design(path, &mut self.main_state.channel_reader);
         Ok(())
     }
 
+    fn poll_file_io(&mut self) -> Option<controller::FileIoOutcome> {
+        use app_state::FileIoResult;
+        use controller::FileIoOutcome;
+        let outcome = match self.main_state.file_io_result.take()? {
+            FileIoResult::Loaded {
+                path,
+                design: Ok(design),
+            } => {
+                self.finish_loading_design(path, design);
+                FileIoOutcome::Loaded
+            }
+            FileIoResult::Loaded {
+                design: Err(LoadDesignError::Cancelled),
+                ..
+            } => FileIoOutcome::Cancelled,
+            FileIoResult::Loaded {
+                design: Err(err), ..
+            } => FileIoOutcome::Failed(format!("Error when loading design:\n{err}")),
+            FileIoResult::Saved {
+                path,
+                result: Ok(()),
+            } => {
+                self.main_state.finish_saving_design(path);
+                FileIoOutcome::Saved
+            }
+            FileIoResult::Saved {
+                result: Err(err), ..
+            } => {
+                self.main_state.state_being_saved = None;
+                if err.is_cancellation() {
+                    FileIoOutcome::Cancelled
+                } else {
+                    FileIoOutcome::Failed(format!("Failed to save: {:?}", err.0))
+                }
+            }
+            FileIoResult::Crashed { path } => {
+                self.main_state.state_being_saved = None;
+                FileIoOutcome::Failed(format!("Could not read or write {}", path.display()))
+            }
+        };
+        Some(outcome)
+    }
+
     fn get_chanel_reader(&mut self) -> &mut ChannelReader {
         &mut self.main_state.channel_reader
     }
@@ -1696,6 +3370,14 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.apply_silent_operation(operation)
     }
 
+    fn begin_batch(&mut self, label: TransitionLabel) {
+        self.main_state.begin_batch(label)
+    }
+
+    fn commit_batch(&mut self) {
+        self.main_state.commit_batch()
+    }
+
     fn undo(&mut self) {
         self.main_state.undo();
     }
@@ -1711,6 +3393,9 @@ impl<'a> MainStateInterface for MainStateView<'a> {
     fn save_design(&mut self, path: &PathBuf) -> Result<(), SaveDesignError> {
         self.main_state.save_design(path)?;
         self.main_state.last_backup_date = Instant::now();
+        if let Err(e) = self.get_session().save(path) {
+            log::error!("Could not save session: {e}");
+        }
         Ok(())
     }
 
@@ -1801,6 +3486,10 @@ impl<'a> MainStateInterface for MainStateView<'a> {
     }
 
     fn delete_selection(&mut self) {
+        if self.main_state.app_state.get_selection_mode() == SelectionMode::BasePair {
+            self.delete_base_pairs();
+            return;
+        }
         let selection = self.get_selection();
         if let Some((_, nucl_pairs)) = ensnano_interactor::list_of_xover_as_nucl_pairs(
             selection.as_ref().as_ref(),
@@ -1836,6 +3525,103 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         }
     }
 
+    fn delete_base_pairs(&mut self) {
+        let selection = self.get_selection();
+        if let Some((_, nucls)) = ensnano_interactor::list_of_base_pairs(
+            selection.as_ref().as_ref(),
+            self.get_design_reader().as_ref(),
+        ) {
+            self.main_state.update_selection(vec![], None);
+            self.main_state
+                .apply_operation(DesignOperation::RmBasePairs { nucls })
+        }
+    }
+
+    fn insert_base_pairs(&mut self) {
+        let selection = self.get_selection();
+        if let Some((_, nucls)) = ensnano_interactor::list_of_base_pairs(
+            selection.as_ref().as_ref(),
+            self.get_design_reader().as_ref(),
+        ) {
+            self.main_state
+                .apply_operation(DesignOperation::InsertBasePairs { nucls })
+        }
+    }
+
+    fn clear_sequences(&mut self) {
+        let selection = self.get_selection();
+        let strand_ids = if let Some((_, strand_ids)) =
+            ensnano_interactor::list_of_strands(selection.as_ref().as_ref())
+        {
+            strand_ids
+        } else {
+            let reader = self.main_state.app_state.get_design_reader();
+            reader.get_design().strands.keys().cloned().collect()
+        };
+        self.main_state
+            .apply_operation(DesignOperation::ClearSequences { strand_ids })
+    }
+
+    fn assign_scaffold_sequence_to_selected_domains(&mut self) {
+        let selection = self.get_selection();
+        let nucls = ensnano_interactor::extract_nucls_and_xover_ends(
+            selection.as_ref().as_ref(),
+            self.get_design_reader().as_ref(),
+        );
+        let reader = self.main_state.app_state.get_design_reader();
+        let design = reader.get_design();
+        let domain_ids: Vec<usize> = design
+            .scaffold_id
+            .and_then(|s_id| design.strands.get(&s_id))
+            .map(|scaffold| {
+                scaffold
+                    .domains
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, d)| nucls.iter().any(|n| d.has_nucl(n).is_some()))
+                    .map(|(d_id, _)| d_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !domain_ids.is_empty() {
+            self.main_state
+                .apply_operation(DesignOperation::AssignScaffoldSequenceToDomains { domain_ids })
+        }
+    }
+
+    fn deletion_summary(&mut self) -> Option<DeletionSummary> {
+        let selection = self.get_selection();
+        if let Some((_, nucl_pairs)) = ensnano_interactor::list_of_xover_as_nucl_pairs(
+            selection.as_ref().as_ref(),
+            self.get_design_reader().as_ref(),
+        ) {
+            Some(DeletionSummary {
+                nb_xovers: nucl_pairs.len(),
+                ..Default::default()
+            })
+        } else if let Some((_, strand_ids)) =
+            ensnano_interactor::list_of_strands(selection.as_ref().as_ref())
+        {
+            let reader = self.main_state.app_state.get_design_reader();
+            let design = reader.get_design();
+            let strands: Vec<_> = strand_ids
+                .iter()
+                .filter_map(|s_id| design.strands.get(s_id))
+                .collect();
+            Some(DeletionSummary {
+                nb_strands: strands.len(),
+                nb_nucleotides: strands.iter().map(|s| s.length()).sum(),
+                nb_xovers: strands.iter().map(|s| s.xovers().len()).sum(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn confirm_large_deletions(&self) -> bool {
+        self.main_state.app_state.confirm_large_deletions()
+    }
+
     fn scaffold_to_selection(&mut self) {
         let scaffold_id = self
             .main_state
@@ -1857,12 +3643,21 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.start_grid_simulation(parameters);
     }
 
+    fn start_bundle_simulation(&mut self, parameters: RigidBodyConstants) {
+        self.main_state.start_bundle_simulation(parameters);
+    }
+
     fn start_revolution_simulation(&mut self, desc: RevolutionSurfaceSystemDescriptor) {
         self.main_state.start_revolution_simulation(desc)
     }
 
-    fn start_roll_simulation(&mut self, target_helices: Option<Vec<usize>>) {
-        self.main_state.start_roll_simulation(target_helices);
+    fn start_roll_simulation(
+        &mut self,
+        target_helices: Option<Vec<usize>>,
+        target_xovers: Option<Vec<usize>>,
+    ) {
+        self.main_state
+            .start_roll_simulation(target_helices, target_xovers);
     }
 
     fn update_simulation(&mut self, request: SimulationRequest) {
@@ -1873,6 +3668,14 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.set_roll_of_selected_helices(roll)
     }
 
+    fn set_ideal_roll_of_selected_helices(&mut self) {
+        self.main_state.set_ideal_roll_of_selected_helices()
+    }
+
+    fn place_insertions_on_selected_helices(&mut self, target: InsertionPlacementTarget) {
+        self.main_state.place_insertions_on_selected_helices(target)
+    }
+
     fn turn_selection_into_anchor(&mut self) {
         let selection = self.get_selection();
         let nucls = ensnano_interactor::extract_nucls_from_selection(selection.as_ref().as_ref());
@@ -1922,6 +3725,23 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.get_current_file_name()
     }
 
+    fn record_variant_provenance(&mut self, path: PathBuf) {
+        self.main_state.record_variant_provenance(path)
+    }
+
+    fn open_design_browser(&mut self, directory: Option<PathBuf>) {
+        self.main_state.open_design_browser(directory)
+    }
+
+    fn poll_design_browser(&mut self) -> Option<DesignBrowserChoice> {
+        self.main_state.poll_design_browser_scan();
+        self.main_state.design_browser_choice.take()
+    }
+
+    fn close_design_browser(&mut self) {
+        self.main_state.close_design_browser()
+    }
+
     fn get_design_path_and_notify(&mut self, notificator: fn(Option<Arc<Path>>) -> Notification) {
         if let Some(filename) = self.get_current_file_name() {
             self.main_state
@@ -1980,7 +3800,18 @@ impl<'a> MainStateInterface for MainStateView<'a> {
                     position: camera.0.position,
                     orientation: camera.0.orientation,
                     pivot_position: camera.0.pivot_position,
-                })
+                });
+            let new_camera_id = self
+                .main_state
+                .app_state
+                .get_design_reader()
+                .get_design()
+                .get_cameras()
+                .map(|(id, _)| *id)
+                .max();
+            if let Some(camera_id) = new_camera_id {
+                self.notify_apps(Notification::CameraThumbnail(camera_id))
+            }
         } else {
             log::error!("Could not get current camera position");
         }
@@ -1989,7 +3820,15 @@ impl<'a> MainStateInterface for MainStateView<'a> {
     fn select_camera(&mut self, camera_id: ensnano_design::CameraId) {
         let reader = self.main_state.app_state.get_design_reader();
         if let Some(camera) = reader.get_camera_with_id(camera_id) {
-            self.notify_apps(Notification::TeleportCamera(camera))
+            let rendering = reader
+                .get_design()
+                .get_camera(camera_id)
+                .and_then(|c| c.rendering);
+            if let Some(rendering) = rendering {
+                self.main_state.apply_camera_rendering(rendering);
+            }
+            self.notify_apps(Notification::TeleportCamera(camera));
+            self.notify_apps(Notification::CameraThumbnail(camera_id))
         } else {
             log::error!("Could not get camera {:?}", camera_id)
         }
@@ -2007,12 +3846,81 @@ impl<'a> MainStateInterface for MainStateView<'a> {
                     camera_id,
                     position: camera.0.position,
                     orientation: camera.0.orientation,
+                });
+            self.notify_apps(Notification::CameraThumbnail(camera_id))
+        } else {
+            log::error!("Could not get current camera position");
+        }
+    }
+
+    fn add_presentation_step(&mut self) {
+        if let Some(camera) = self
+            .main_state
+            .applications
+            .get(&ElementType::Scene)
+            .and_then(|s| s.lock().unwrap().get_camera())
+        {
+            let hidden_nucls = self
+                .main_state
+                .app_state
+                .get_design_reader()
+                .get_invisible_nucls();
+            self.main_state
+                .apply_operation(DesignOperation::AddPresentationStep {
+                    camera_position: camera.0.position,
+                    camera_orientation: camera.0.orientation,
+                    pivot_position: camera.0.pivot_position,
+                    hidden_nucls,
                 })
         } else {
             log::error!("Could not get current camera position");
         }
     }
 
+    fn go_to_presentation_step(&mut self, forward: bool) {
+        let reader = self.main_state.app_state.get_design_reader();
+        let nb_steps = reader.get_nb_presentation_steps();
+        if nb_steps == 0 {
+            self.main_state.push_toast(
+                ToastSeverity::Info,
+                "The design has no presentation step".into(),
+            );
+            return;
+        }
+        let step = match self.main_state.presentation_step {
+            None => 0,
+            Some(n) if forward => n + 1,
+            Some(n) => n.saturating_sub(1),
+        }
+        .min(nb_steps - 1);
+        if let Some((camera, hidden_nucls)) = reader.get_presentation_step(step) {
+            self.main_state.presentation_step = Some(step);
+            self.main_state.set_invisible_nucls(hidden_nucls, false);
+            self.notify_apps(Notification::TeleportCamera(camera));
+            self.main_state.push_toast(
+                ToastSeverity::Info,
+                format!("Presentation step {}/{}", step + 1, nb_steps),
+            );
+        }
+    }
+
+    fn set_read_only(&mut self, read_only: bool) {
+        self.main_state.read_only = read_only;
+        let message = if read_only {
+            "Viewer mode: the design cannot be modified"
+        } else {
+            "Edit mode: the design can be modified"
+        };
+        self.main_state
+            .push_toast(ToastSeverity::Info, message.into());
+    }
+
+    fn copy_to_clipboard(&mut self, content: String) {
+        iced_winit::Clipboard::connect(self.window).write(content);
+        self.main_state
+            .push_toast(ToastSeverity::Info, "Copied to clipboard".into());
+    }
+
     fn select_favorite_camera(&mut self, n_camera: u32) {
         let reader = self.main_state.app_state.get_design_reader();
         if let Some(camera) = reader.get_nth_camera(n_camera) {
@@ -2022,6 +3930,25 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         }
     }
 
+    fn open_overlay(&mut self, overlay_type: OverlayType) {
+        self.overlay_manager
+            .add_overlay(overlay_type, self.multiplexer);
+    }
+
+    fn close_overlay(&mut self, overlay_type: OverlayType) {
+        self.overlay_manager
+            .rm_overlay(overlay_type, self.multiplexer);
+    }
+
+    fn open_context_menu(&mut self, selection: Selection) {
+        self.overlay_manager
+            .open_context_menu(selection, self.multiplexer);
+    }
+
+    fn toggle_detached_left_panel(&mut self) {
+        self.main_state.wants_detach_toggle = true;
+    }
+
     fn toggle_2d(&mut self) {
         self.multiplexer.toggle_2d();
         self.scheduler
@@ -2035,6 +3962,87 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.apply_operation(DesignOperation::MakeSeveralXovers { xovers, doubled })
     }
 
+    fn plan_xover_respacing(&mut self) -> Vec<ensnano_design::design_operations::XoverRespacing> {
+        let selection = self.get_selection();
+        let reader = self.main_state.app_state.get_design_reader();
+        let design = reader.get_design();
+        let xovers = if let Some((_, xovers)) = ensnano_interactor::list_of_xover_as_nucl_pairs(
+            selection.as_ref().as_ref(),
+            self.get_design_reader().as_ref(),
+        ) {
+            xovers
+        } else if let Some((_, strand_ids)) =
+            ensnano_interactor::list_of_strands(selection.as_ref().as_ref())
+        {
+            strand_ids
+                .iter()
+                .filter_map(|s_id| design.strands.get(s_id))
+                .flat_map(|s| s.xovers())
+                .collect()
+        } else {
+            vec![]
+        };
+        ensnano_design::design_operations::plan_xover_respacing(design, &xovers)
+    }
+
+    fn plan_nick_optimization(&mut self) -> Vec<ensnano_design::design_operations::NickMove> {
+        use flatscene::DesignReader;
+        let selection = self.get_selection();
+        let reader = self.main_state.app_state.get_design_reader();
+        let design = reader.get_design();
+        let strand_ids = if let Some((_, strand_ids)) =
+            ensnano_interactor::list_of_strands(selection.as_ref().as_ref())
+        {
+            strand_ids
+        } else {
+            design.strands.keys().cloned().collect()
+        };
+        ensnano_design::design_operations::plan_nick_optimization(
+            design,
+            reader.get_basis_map().as_ref(),
+            &strand_ids,
+        )
+    }
+
+    fn plan_random_sequences(
+        &mut self,
+        parameters: &ensnano_design::design_operations::RandomSequenceParameters,
+    ) -> Vec<ensnano_design::design_operations::StrandSequences> {
+        use flatscene::DesignReader;
+        let selection = self.get_selection();
+        let reader = self.main_state.app_state.get_design_reader();
+        let design = reader.get_design();
+        let strand_ids = if let Some((_, strand_ids)) =
+            ensnano_interactor::list_of_strands(selection.as_ref().as_ref())
+        {
+            strand_ids
+        } else {
+            design.strands.keys().cloned().collect()
+        };
+        ensnano_design::design_operations::plan_random_sequences(
+            design,
+            reader.get_basis_map().as_ref(),
+            &strand_ids,
+            parameters,
+            random(),
+        )
+    }
+
+    fn plan_strand_cleanup(
+        &mut self,
+        min_length: usize,
+    ) -> Vec<ensnano_design::design_operations::StrandCleanup> {
+        let reader = self.main_state.app_state.get_design_reader();
+        ensnano_design::design_operations::plan_strand_cleanup(reader.get_design(), min_length)
+    }
+
+    fn audit_scaffold_pairing(
+        &mut self,
+    ) -> Option<Vec<ensnano_design::pairing_audit::PairingIssue>> {
+        let reader = self.main_state.app_state.get_design_reader();
+        ensnano_design::pairing_audit::audit_scaffold_pairing(reader.get_design())
+    }
+
     fn flip_split_views(&mut self) {
         self.notify_apps(Notification::FlipSplitViews)
     }
@@ -2043,6 +4051,10 @@ impl<'a> MainStateInterface for MainStateView<'a> {
         self.main_state.start_twist(g_id);
     }
 
+    fn start_helices_twist(&mut self) {
+        self.main_state.start_helices_twist();
+    }
+
     fn set_expand_insertions(&mut self, expand: bool) {
         self.main_state
             .modify_state(|app| app.with_expand_insertion_set(expand), None);
@@ -2068,6 +4080,12 @@ impl<'a> MainStateInterface for MainStateView<'a> {
     fn load_svg(&mut self, path: PathBuf) {
         self.apply_operation(DesignOperation::ImportSvgPath { path });
     }
+
+    fn compare_with_design_file(&mut self, path: PathBuf) -> Result<(), LoadDesignError> {
+        let reference = AppState::import_design(path)?;
+        self.main_state.show_design_diff(&reference);
+        Ok(())
+    }
 }
 
 use controller::{SetScaffoldSequenceError, SetScaffoldSequenceOk};
@@ -2089,7 +4107,7 @@ impl<'a> controller::ScaffoldSetter for MainStateView<'a> {
                 self.main_state.save_old_state(state, label)
             }
             Ok(OkOperation::NotUndoable) => (),
-            Err(e) => return Err(SetScaffoldSequenceError(format!("{:?}", e))),
+            Err(e) => return Err(SetScaffoldSequenceError(e.to_string())),
         };
         let default_shift = self.get_staple_downloader().default_shift();
         let scaffold_length = self.get_scaffold_length().unwrap_or(0);
@@ -462,3 +462,86 @@ fn no_need_to_save_after_new_design() {
     main_state.update();
     assert!(!main_state.need_save(), "Need save after update");
 }
+
+#[test]
+fn tutorial_advances_when_grid_is_added() {
+    use ensnano_design::grid::{GridDescriptor, GridTypeDescr};
+    let mut main_state = new_state();
+    main_state.handle_tutorial_request(tutorial::TutorialRequest::Start);
+    main_state.update();
+    assert_eq!(
+        main_state.tutorial.as_ref().map(|t| t.status().step),
+        Some(0)
+    );
+    main_state.apply_operation(DesignOperation::AddGrid(GridDescriptor {
+        position: Vec3::zero(),
+        orientation: ultraviolet::Rotor3::identity(),
+        helix_parameters: None,
+        grid_type: GridTypeDescr::Square { twist: None },
+        invisible: false,
+        bezier_vertex: None,
+    }));
+    main_state.update();
+    assert_eq!(
+        main_state.tutorial.as_ref().map(|t| t.status().step),
+        Some(1)
+    );
+    main_state.handle_tutorial_request(tutorial::TutorialRequest::Stop);
+    assert!(main_state.tutorial.is_none());
+}
+
+fn new_grid_operation(x: f32) -> DesignOperation {
+    use ensnano_design::grid::{GridDescriptor, GridTypeDescr};
+    DesignOperation::AddGrid(GridDescriptor {
+        position: Vec3::unit_x() * x,
+        orientation: ultraviolet::Rotor3::identity(),
+        helix_parameters: None,
+        grid_type: GridTypeDescr::Square { twist: None },
+        invisible: false,
+        bezier_vertex: None,
+    })
+}
+
+#[test]
+fn batch_produces_one_undo_entry() {
+    let mut main_state = new_state();
+    main_state.begin_batch("Grids creation".into());
+    main_state.apply_operation(new_grid_operation(0.));
+    main_state.apply_operation(new_grid_operation(10.));
+    main_state.apply_operation(new_grid_operation(20.));
+    assert!(main_state.undo_stack.is_empty());
+    main_state.commit_batch();
+    assert_eq!(main_state.undo_stack.len(), 1);
+    main_state.undo();
+    assert!(main_state.undo_stack.is_empty());
+    assert_eq!(main_state.redo_stack.len(), 1);
+}
+
+#[test]
+fn undo_and_redo_are_rejected_in_viewer_mode() {
+    let mut main_state = new_state();
+    main_state.apply_operation(new_grid_operation(0.));
+    main_state.apply_operation(new_grid_operation(10.));
+    main_state.undo();
+    assert_eq!(main_state.undo_stack.len(), 1);
+    assert_eq!(main_state.redo_stack.len(), 1);
+    main_state.read_only = true;
+    main_state.undo();
+    main_state.redo();
+    assert_eq!(main_state.undo_stack.len(), 1);
+    assert_eq!(main_state.redo_stack.len(), 1);
+}
+
+#[test]
+fn nested_batches_produce_one_undo_entry() {
+    let mut main_state = new_state();
+    main_state.begin_batch("Outer".into());
+    main_state.apply_operation(new_grid_operation(0.));
+    main_state.begin_batch("Inner".into());
+    main_state.apply_operation(new_grid_operation(10.));
+    main_state.commit_batch();
+    assert!(main_state.undo_stack.is_empty());
+    main_state.commit_batch();
+    assert_eq!(main_state.undo_stack.len(), 1);
+    assert_eq!(main_state.undo_stack[0].label.as_ref(), "Outer");
+}
@@ -31,10 +31,10 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //!
 //! The multiplexer is also in charge of drawing to the frame.
 use super::{Action, Requests};
-use crate::gui::UiSize;
+use crate::gui::{UiSize, OVERLAY_TITLE_BAR_HEIGHT};
 use crate::utils::texture::SampledTexture;
 use crate::PhySize;
-use ensnano_interactor::{ActionMode, SelectionMode};
+use ensnano_interactor::{ActionMode, GizmoAxis, SelectionMode};
 use iced_wgpu::wgpu;
 use iced_winit::winit;
 use iced_winit::winit::event::*;
@@ -67,6 +67,8 @@ pub struct Multiplexer {
     cursor_position: PhysicalPosition<f64>,
     /// The area that are drawn on top of the application.
     overlays: Vec<Overlay>,
+    /// The indices of the overlays, from the bottom-most to the top-most one.
+    overlay_order: Vec<usize>,
     /// The texture on which the scene is rendered.
     scene_texture: Option<MultiplexerTexture>,
     /// The texture on which the top bar gui is rendered.
@@ -79,6 +81,8 @@ pub struct Multiplexer {
     grid_panel_texture: Option<MultiplexerTexture>,
     /// The texture on which the stereographic scene is rendered.
     stereographic_scene_texture: Option<MultiplexerTexture>,
+    /// The texture on which the cross-section view is rendered.
+    cross_section_texture: Option<MultiplexerTexture>,
     /// The texture on which the status bar gui is rendered.
     status_bar_texture: Option<MultiplexerTexture>,
     /// The texutre on which the flat scene is rendered.
@@ -87,6 +91,8 @@ pub struct Multiplexer {
     top_bar_split: usize,
     /// The pointer to the node that separtate the status bar from the scene.
     status_bar_split: usize,
+    /// The pointer to the node that separate the left pannel from the scene.
+    left_pannel_split: usize,
     device: Rc<Device>,
     pipeline: Option<wgpu::RenderPipeline>,
     split_mode: SplitMode,
@@ -97,12 +103,28 @@ pub struct Multiplexer {
     pub icon: Option<CursorIcon>,
     element_3d: ElementType,
     element_2d: ElementType,
+    /// Set if the left pannel is displayed in its own window.
+    detached_left_pannel: Option<DetachedPannel>,
+    /// Set if the X, Y and Z keys constrain the 3D widgets to an axis.
+    gizmo_shortcuts: bool,
+}
+
+/// A GUI pannel that is rendered in a separate window.
+struct DetachedPannel {
+    /// The *physical* size of the pannel's window.
+    size: PhySize,
+    /// The proportion that the pannel occupied in the main window before being detached.
+    old_proportion: f64,
 }
 
 /// Maximum width of the left pannel.
 const MAX_LEFT_PANNEL_WIDTH: f64 = 200.;
 /// Maximum height of the status bar.
 const MAX_STATUS_BAR_HEIGHT: f64 = 50.;
+/// Width of the borders by which overlays can be resized.
+const OVERLAY_BORDER_WIDTH: f64 = 5.;
+/// Minimum width and height of an overlay.
+const MIN_OVERLAY_SIZE: f64 = 100.;
 
 impl Multiplexer {
     /// Create a new multiplexer for a window with size `window_size`.
@@ -137,12 +159,16 @@ impl Multiplexer {
         let (top_bar, scene) = layout.hsplit(0, top_pannel_prop, false);
         let left_pannel_prop = proportion(
             0.2,
-            MAX_LEFT_PANNEL_WIDTH * scale_factor,
+            MAX_LEFT_PANNEL_WIDTH * ui_size.scale() * scale_factor,
             window_size.width as f64,
         );
+        let left_pannel_split = scene;
         let (left_pannel, scene) = layout.vsplit(scene, left_pannel_prop, true);
         let scene_height = (1. - top_pannel_prop) * window_size.height as f64;
-        let status_bar_prop = exact_proportion(MAX_STATUS_BAR_HEIGHT * scale_factor, scene_height);
+        let status_bar_prop = exact_proportion(
+            MAX_STATUS_BAR_HEIGHT * ui_size.scale() * scale_factor,
+            scene_height,
+        );
         let status_bar_split = scene;
         let (scene, status_bar) = layout.hsplit(scene, 1. - status_bar_prop, false);
         //let (scene, grid_panel) = layout_manager.hsplit(scene, 0.8);
@@ -164,7 +190,9 @@ impl Multiplexer {
             grid_panel_texture: None,
             status_bar_texture: None,
             stereographic_scene_texture: None,
+            cross_section_texture: None,
             overlays: Vec::new(),
+            overlay_order: Vec::new(),
             overlays_textures: Vec::new(),
             device,
             pipeline: None,
@@ -172,6 +200,7 @@ impl Multiplexer {
             requests,
             status_bar_split,
             top_bar_split,
+            left_pannel_split,
             state: State::Normal {
                 mouse_position: PhysicalPosition::new(-1., -1.),
             },
@@ -180,6 +209,8 @@ impl Multiplexer {
             icon: None,
             element_2d: ElementType::FlatScene,
             element_3d: ElementType::Scene,
+            detached_left_pannel: None,
+            gizmo_shortcuts: false,
         };
         ret.generate_textures();
         ret
@@ -192,6 +223,9 @@ impl Multiplexer {
                 .stereographic_scene_texture
                 .as_ref()
                 .map(|t| &t.texture.view),
+            ElementType::CrossSection => {
+                self.cross_section_texture.as_ref().map(|t| &t.texture.view)
+            }
             ElementType::Scene => self.scene_texture.as_ref().map(|t| &t.texture.view),
             ElementType::LeftPanel => self.left_pannel_texture.as_ref().map(|t| &t.texture.view),
             ElementType::TopBar => self.top_bar_texture.as_ref().map(|t| &t.texture.view),
@@ -215,6 +249,7 @@ impl Multiplexer {
             ElementType::StereographicScene => {
                 self.stereographic_scene_texture.as_ref().map(|t| t.area)
             }
+            ElementType::CrossSection => self.cross_section_texture.as_ref().map(|t| t.area),
             ElementType::Unattributed => unreachable!(),
         }
     }
@@ -267,17 +302,24 @@ impl Multiplexer {
             depth_stencil_attachment: None,
         });
         if self.window_size.width > 0 && self.window_size.height > 0 {
-            for element in [
+            let overlays = self.overlay_order.iter().map(|n| ElementType::Overlay(*n));
+            let elements: Vec<ElementType> = [
                 ElementType::TopBar,
                 ElementType::LeftPanel,
                 ElementType::GridPanel,
                 ElementType::Scene,
                 ElementType::FlatScene,
                 ElementType::StereographicScene,
+                ElementType::CrossSection,
                 ElementType::StatusBar,
             ]
-            .iter()
-            {
+            .into_iter()
+            .chain(overlays)
+            .collect();
+            for element in elements.iter() {
+                if *element == ElementType::LeftPanel && self.detached_left_pannel.is_some() {
+                    continue;
+                }
                 log::trace!("Draw {:?}", element);
                 if let Some(area) = self.get_texture_size(*element) {
                     render_pass.set_bind_group(0, self.get_bind_group(element), &[]);
@@ -330,6 +372,14 @@ impl Multiplexer {
                     .texture
                     .bind_group
             }
+            ElementType::CrossSection => {
+                &self
+                    .cross_section_texture
+                    .as_ref()
+                    .unwrap()
+                    .texture
+                    .bind_group
+            }
             ElementType::Unattributed => unreachable!(),
         }
     }
@@ -339,6 +389,12 @@ impl Multiplexer {
         use ElementType::Overlay;
         let (position, size) = if let Overlay(n) = element_type {
             (self.overlays[n].position, self.overlays[n].size)
+        } else if let Some(detached) = self
+            .detached_left_pannel
+            .as_ref()
+            .filter(|_| element_type == ElementType::LeftPanel)
+        {
+            (PhysicalPosition::new(0, 0), detached.size)
         } else {
             let (left, top, right, bottom) = self.layout.get_area(element_type)?;
             let top = top * self.window_size.height as f64;
@@ -399,6 +455,29 @@ impl Multiplexer {
                     self.icon = Some(CursorIcon::EwResize);
                     captured = true;
                 }
+                State::HandlingOverlay {
+                    mouse_position,
+                    clicked_position,
+                    overlay,
+                    handle,
+                    old_overlay,
+                } => {
+                    *mouse_position = *position;
+                    let delta = PhysicalPosition::new(
+                        position.x - clicked_position.x,
+                        position.y - clicked_position.y,
+                    );
+                    let new_overlay = old_overlay.handled(
+                        *handle,
+                        delta,
+                        self.window_size,
+                        MIN_OVERLAY_SIZE * self.scale_factor,
+                    );
+                    let n = *overlay;
+                    self.icon = Some(handle.cursor_icon());
+                    self.set_overlay_area(n, new_overlay);
+                    captured = true;
+                }
 
                 State::Normal { mouse_position, .. } => {
                     *mouse_position = *position;
@@ -418,6 +497,9 @@ impl Multiplexer {
                             PixelRegion::Area(_) => unreachable!(),
                         }
                         .or(self.focus.and_then(|e| self.get_draw_area(e)));
+                        if let Some((_, handle)) = self.overlay_handle(*position) {
+                            self.icon = Some(handle.cursor_icon());
+                        }
 
                         if let Some(area) = area {
                             self.cursor_position.x = position.x - area.position.cast::<f64>().x;
@@ -459,9 +541,40 @@ impl Multiplexer {
                     self.generate_textures();
                 }
             }
+            WindowEvent::MouseInput { state, .. }
+                if matches!(self.state, State::HandlingOverlay { .. }) =>
+            {
+                if *state == ElementState::Released {
+                    self.state = State::Normal {
+                        mouse_position: self.state.mouse_position(),
+                    };
+                }
+                captured = true;
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                ..
+            } if self.overlay_handle(self.state.mouse_position()).is_some() => {
+                let mouse_position = self.state.mouse_position();
+                let (n, handle) = self.overlay_handle(mouse_position).unwrap();
+                self.raise_overlay(n);
+                self.state = State::HandlingOverlay {
+                    mouse_position,
+                    clicked_position: mouse_position,
+                    overlay: n,
+                    handle,
+                    old_overlay: self.overlays[n].clone(),
+                };
+                captured = true;
+            }
             WindowEvent::MouseInput { state, .. } => {
                 let element = self.pixel_to_element(self.state.mouse_position());
                 let mouse_position = self.state.mouse_position();
+                if let PixelRegion::Element(ElementType::Overlay(n)) = element {
+                    if *state == ElementState::Pressed {
+                        self.raise_overlay(n);
+                    }
+                }
                 match element {
                     PixelRegion::Resize(n) if *state == ElementState::Pressed => {
                         let mut clicked_position = mouse_position.clone();
@@ -516,6 +629,19 @@ impl Multiplexer {
                     VirtualKeyCode::Escape => {
                         self.requests.lock().unwrap().action_mode = Some(ActionMode::Normal)
                     }
+                    VirtualKeyCode::X | VirtualKeyCode::Y | VirtualKeyCode::Z
+                        if self.gizmo_shortcuts
+                            && self.focus == Some(ElementType::Scene)
+                            && !ctrl(&self.modifiers)
+                            && !self.modifiers.alt() =>
+                    {
+                        let axis = match *key {
+                            VirtualKeyCode::X => GizmoAxis::X,
+                            VirtualKeyCode::Y => GizmoAxis::Y,
+                            _ => GizmoAxis::Z,
+                        };
+                        self.requests.lock().unwrap().toggle_gizmo_axis = Some(axis);
+                    }
                     VirtualKeyCode::X if self.modifiers.alt() => {
                         self.requests.lock().unwrap().keep_proceed.push_back(
                             Action::MakeAllSuggestedXover {
@@ -561,6 +687,16 @@ impl Multiplexer {
                     VirtualKeyCode::H => {
                         self.requests.lock().unwrap().selection_mode = Some(SelectionMode::Helix)
                     }
+                    VirtualKeyCode::B => {
+                        self.requests.lock().unwrap().selection_mode = Some(SelectionMode::BasePair)
+                    }
+                    VirtualKeyCode::S if ctrl(&self.modifiers) && self.modifiers.shift() => {
+                        self.requests
+                            .lock()
+                            .unwrap()
+                            .keep_proceed
+                            .push_back(Action::SaveIncrementalCopy);
+                    }
                     VirtualKeyCode::S if ctrl(&self.modifiers) => {
                         self.requests.lock().unwrap().save_shortcut = Some(());
                     }
@@ -592,6 +728,13 @@ impl Multiplexer {
                     VirtualKeyCode::K => {
                         self.requests.lock().unwrap().recolor_staples = Some(());
                     }
+                    VirtualKeyCode::PageDown | VirtualKeyCode::PageUp => {
+                        self.requests.lock().unwrap().keep_proceed.push_back(
+                            Action::PresentationStep {
+                                forward: *key == VirtualKeyCode::PageDown,
+                            },
+                        );
+                    }
                     VirtualKeyCode::Delete | VirtualKeyCode::Back => {
                         self.requests.lock().unwrap().delete_selection = Some(());
                     }
@@ -609,6 +752,10 @@ impl Multiplexer {
     }
 
     pub fn change_ui_size(&mut self, ui_size: UiSize, window: &iced_winit::winit::window::Window) {
+        if let Some(proportion) = self.get_left_pannel_proportion() {
+            let ratio = ui_size.scale() / self.ui_size.scale();
+            self.set_left_pannel_proportion((proportion * ratio).min(0.5));
+        }
         self.ui_size = ui_size;
         self.resize(window.inner_size(), window.scale_factor());
         self.generate_textures();
@@ -650,11 +797,11 @@ impl Multiplexer {
             self.layout.log_tree();
         }
         let old_element_2d = self.element_2d;
-        if self.element_2d == ElementType::FlatScene {
-            self.element_2d = ElementType::StereographicScene;
-        } else {
-            self.element_2d = ElementType::FlatScene;
-        }
+        self.element_2d = match self.element_2d {
+            ElementType::FlatScene => ElementType::StereographicScene,
+            ElementType::StereographicScene => ElementType::CrossSection,
+            _ => ElementType::FlatScene,
+        };
         if let Some(id) = self.layout.get_area_id(old_element_2d) {
             self.layout.attribute_element(id, self.element_2d)
         }
@@ -674,6 +821,138 @@ impl Multiplexer {
         self.generate_textures();
     }
 
+    pub fn get_split_mode(&self) -> SplitMode {
+        self.split_mode
+    }
+
+    /// The proportion of the window's width that is occupied by the left pannel.
+    ///
+    /// If the left pannel is detached, this is the proportion that it will occupy once put back
+    /// in the main window.
+    pub fn get_left_pannel_proportion(&self) -> Option<f64> {
+        if let Some(detached) = self.detached_left_pannel.as_ref() {
+            Some(detached.old_proportion)
+        } else {
+            self.layout.get_proportion(self.left_pannel_split)
+        }
+    }
+
+    /// Set the proportion of the window's width that is occupied by the left pannel.
+    ///
+    /// The textures are not regenerated, `self.generate_textures()` must be called afterwards.
+    pub fn set_left_pannel_proportion(&mut self, proportion: f64) {
+        if let Some(detached) = self.detached_left_pannel.as_mut() {
+            detached.old_proportion = proportion
+        } else {
+            self.layout.resize(self.left_pannel_split, proportion)
+        }
+    }
+
+    /// Render the left pannel in its own window of *physical* size `size`.
+    ///
+    /// The area that the pannel occupied in the main window is given to the scenes.
+    pub fn detach_left_pannel(&mut self, size: PhySize) {
+        if self.detached_left_pannel.is_none() {
+            let old_proportion = self.get_left_pannel_proportion().unwrap_or(0.2);
+            self.layout.resize(self.left_pannel_split, 0.);
+            self.layout.set_resizable(self.left_pannel_split, false);
+            self.detached_left_pannel = Some(DetachedPannel {
+                size,
+                old_proportion,
+            });
+            self.generate_textures();
+        }
+    }
+
+    /// Put the left pannel back in the main window, with the width it had before being detached.
+    pub fn reattach_left_pannel(&mut self) {
+        if let Some(detached) = self.detached_left_pannel.take() {
+            self.layout
+                .resize(self.left_pannel_split, detached.old_proportion);
+            self.layout.set_resizable(self.left_pannel_split, true);
+            self.generate_textures();
+        }
+    }
+
+    pub fn set_gizmo_shortcuts(&mut self, enabled: bool) {
+        self.gizmo_shortcuts = enabled;
+    }
+
+    pub fn left_pannel_is_detached(&self) -> bool {
+        self.detached_left_pannel.is_some()
+    }
+
+    /// Update the *physical* size of the left pannel's window.
+    ///
+    /// Return true if the size has changed, in which case `self.generate_textures()` must be
+    /// called afterwards.
+    pub fn resize_detached_left_pannel(&mut self, size: PhySize) -> bool {
+        if let Some(detached) = self.detached_left_pannel.as_mut() {
+            let ret = detached.size != size;
+            detached.size = size;
+            ret
+        } else {
+            false
+        }
+    }
+
+    /// Update the focus and cursor position according to an event that happened on the left
+    /// pannel's window.
+    pub fn detached_left_pannel_event(&mut self, event: &WindowEvent<'static>) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.focus = Some(ElementType::LeftPanel);
+                self.cursor_position = *position;
+                self.icon = None;
+            }
+            WindowEvent::CursorLeft { .. } => {
+                if self.focus == Some(ElementType::LeftPanel) {
+                    self.focus = None;
+                }
+                self.cursor_position = PhysicalPosition::new(-1., -1.);
+            }
+            _ => (),
+        }
+    }
+
+    /// Draw the left pannel on the frame of its own window.
+    pub fn draw_detached_left_pannel(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+    ) {
+        let size = if let Some(detached) = self.detached_left_pannel.as_ref() {
+            detached.size
+        } else {
+            return;
+        };
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        if self.pipeline.is_none() {
+            let bg_layout = &self.top_bar_texture.as_ref().unwrap().texture.bg_layout;
+            self.pipeline = Some(create_pipeline(self.device.as_ref(), bg_layout));
+        }
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Detached pannel render pass"),
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        if self.left_pannel_texture.is_some() {
+            render_pass.set_bind_group(0, self.get_bind_group(&ElementType::LeftPanel), &[]);
+            render_pass.set_viewport(0., 0., size.width as f32, size.height as f32, 0.0, 1.0);
+            render_pass.set_pipeline(self.pipeline.as_ref().unwrap());
+            render_pass.draw(0..4, 0..1);
+        }
+    }
+
     pub fn resize(&mut self, window_size: PhySize, scale_factor: f64) -> bool {
         let ret = self.window_size != window_size;
         let top_pannel_prop = exact_proportion(
@@ -681,7 +960,10 @@ impl Multiplexer {
             window_size.height as f64,
         );
         let scene_height = (1. - top_pannel_prop) * window_size.height as f64;
-        let status_bar_prop = exact_proportion(MAX_STATUS_BAR_HEIGHT * scale_factor, scene_height);
+        let status_bar_prop = exact_proportion(
+            MAX_STATUS_BAR_HEIGHT * self.ui_size.scale() * scale_factor,
+            scene_height,
+        );
         self.layout.resize(self.top_bar_split, top_pannel_prop);
         self.layout
             .resize(self.status_bar_split, 1. - status_bar_prop);
@@ -696,6 +978,13 @@ impl Multiplexer {
         Some(MultiplexerTexture { area, texture })
     }
 
+    /// Replace the device on which the textures are created, after the previous one was lost.
+    pub fn set_device(&mut self, device: Rc<Device>) {
+        self.device = device;
+        self.pipeline = None;
+        self.generate_textures();
+    }
+
     pub fn generate_textures(&mut self) {
         self.scene_texture = self.texture(ElementType::Scene);
         self.top_bar_texture = self.texture(ElementType::TopBar);
@@ -704,7 +993,11 @@ impl Multiplexer {
         self.flat_scene_texture = self.texture(ElementType::FlatScene);
         self.status_bar_texture = self.texture(ElementType::StatusBar);
         self.stereographic_scene_texture = self.texture(ElementType::StereographicScene);
+        self.cross_section_texture = self.texture(ElementType::CrossSection);
 
+        for overlay in self.overlays.iter_mut() {
+            overlay.fit_in(self.window_size);
+        }
         self.overlays_textures.clear();
         for overlay in self.overlays.iter() {
             let size = overlay.size;
@@ -723,9 +1016,9 @@ impl Multiplexer {
     /// Maps *physical* pixels to an element
     fn pixel_to_element(&self, pixel: PhysicalPosition<f64>) -> PixelRegion {
         let pixel_u32 = pixel.cast::<u32>();
-        for (n, overlay) in self.overlays.iter().enumerate() {
-            if overlay.contains_pixel(pixel_u32) {
-                return PixelRegion::Element(ElementType::Overlay(n));
+        for n in self.overlay_order.iter().rev() {
+            if self.overlays[*n].contains_pixel(pixel_u32) {
+                return PixelRegion::Element(ElementType::Overlay(*n));
             }
         }
         self.layout.get_area_pixel(
@@ -739,6 +1032,11 @@ impl Multiplexer {
         self.get_draw_area(element)
     }
 
+    /// Return the *physical* position of the cursor, in the window coordinates
+    pub fn get_mouse_position(&self) -> PhysicalPosition<f64> {
+        self.state.mouse_position()
+    }
+
     /// Return the *physical* position of the cursor, in the foccused element coordinates
     pub fn get_cursor_position(&self) -> PhysicalPosition<f64> {
         self.cursor_position
@@ -749,20 +1047,85 @@ impl Multiplexer {
         self.focus
     }
 
-    pub fn set_overlays(&mut self, overlays: Vec<Overlay>) {
-        self.overlays = overlays;
-        self.overlays_textures.clear();
-        for overlay in self.overlays.iter_mut() {
-            let size = overlay.size;
-            let texture = SampledTexture::create_target_texture(self.device.as_ref(), &size);
-            self.overlays_textures.push(MultiplexerTexture {
-                texture,
-                area: DrawArea {
-                    size,
-                    position: overlay.position,
-                },
-            });
+    /// Add an overlay on top of the others and return its index.
+    pub fn push_overlay(&mut self, mut overlay: Overlay) -> usize {
+        overlay.fit_in(self.window_size);
+        let n = self.overlays.len();
+        self.overlays_textures.push(self.overlay_texture(&overlay));
+        self.overlays.push(overlay);
+        self.overlay_order.push(n);
+        n
+    }
+
+    /// Remove the `n`-th overlay. The overlays with a greater index are shifted down by one.
+    pub fn remove_overlay(&mut self, n: usize) {
+        if n >= self.overlays.len() {
+            return;
+        }
+        self.overlays.remove(n);
+        self.overlays_textures.remove(n);
+        self.overlay_order.retain(|m| *m != n);
+        for m in self.overlay_order.iter_mut() {
+            if *m > n {
+                *m -= 1;
+            }
+        }
+        match self.focus {
+            Some(ElementType::Overlay(m)) if m == n => self.focus = None,
+            Some(ElementType::Overlay(m)) if m > n => {
+                self.focus = Some(ElementType::Overlay(m - 1))
+            }
+            _ => (),
         }
+        if let State::HandlingOverlay { mouse_position, .. } = self.state {
+            self.state = State::Normal { mouse_position };
+        }
+    }
+
+    /// Put the `n`-th overlay on top of the others.
+    pub fn raise_overlay(&mut self, n: usize) {
+        self.overlay_order.retain(|m| *m != n);
+        self.overlay_order.push(n);
+    }
+
+    /// True if an overlay is being moved or resized by the user.
+    pub fn is_handling_overlay(&self) -> bool {
+        matches!(self.state, State::HandlingOverlay { .. })
+    }
+
+    fn set_overlay_area(&mut self, n: usize, overlay: Overlay) {
+        let size_changed = self.overlays[n].size != overlay.size;
+        if size_changed {
+            self.overlays_textures[n] = self.overlay_texture(&overlay);
+        } else {
+            self.overlays_textures[n].area.position = overlay.position;
+        }
+        self.overlays[n] = overlay;
+    }
+
+    fn overlay_texture(&self, overlay: &Overlay) -> MultiplexerTexture {
+        let texture = SampledTexture::create_target_texture(self.device.as_ref(), &overlay.size);
+        MultiplexerTexture {
+            texture,
+            area: DrawArea {
+                size: overlay.size,
+                position: overlay.position,
+            },
+        }
+    }
+
+    /// Return the top-most overlay containing `pixel`, and the handle under `pixel` if any.
+    fn overlay_handle(&self, pixel: PhysicalPosition<f64>) -> Option<(usize, OverlayHandle)> {
+        let title_bar_height = OVERLAY_TITLE_BAR_HEIGHT as f64 * self.scale_factor;
+        let border_width = OVERLAY_BORDER_WIDTH * self.scale_factor;
+        let n = *self
+            .overlay_order
+            .iter()
+            .rev()
+            .find(|n| self.overlays[**n].contains_pixel(pixel.cast::<u32>()))?;
+        self.overlays[n]
+            .handle(pixel, title_bar_height, border_width)
+            .map(|handle| (n, handle))
     }
 
     pub fn is_showing(&self, area: &ElementType) -> bool {
@@ -783,6 +1146,8 @@ impl Multiplexer {
 pub struct Overlay {
     pub position: PhysicalPosition<u32>,
     pub size: PhysicalSize<u32>,
+    /// If false, the overlay cannot be moved or resized by the user.
+    pub movable: bool,
 }
 
 impl Overlay {
@@ -792,6 +1157,134 @@ impl Overlay {
             && pixel.x < self.position.x + self.size.width
             && pixel.y < self.position.y + self.size.height
     }
+
+    /// Return the handle of the overlay under `pixel`.
+    ///
+    /// `pixel` must be a pixel of the overlay.
+    fn handle(
+        &self,
+        pixel: PhysicalPosition<f64>,
+        title_bar_height: f64,
+        border_width: f64,
+    ) -> Option<OverlayHandle> {
+        if !self.movable {
+            return None;
+        }
+        let x = pixel.x - self.position.x as f64;
+        let y = pixel.y - self.position.y as f64;
+        let width = self.size.width as f64;
+        let height = self.size.height as f64;
+        let left = x < border_width;
+        let right = x >= width - border_width;
+        let top = y < border_width;
+        let bottom = y >= height - border_width;
+        if left || right || top || bottom {
+            Some(OverlayHandle::Border {
+                left,
+                right,
+                top,
+                bottom,
+            })
+        } else if y < title_bar_height {
+            Some(OverlayHandle::TitleBar)
+        } else {
+            None
+        }
+    }
+
+    /// Return the overlay obtained by dragging `handle` by `delta` *physical* pixels.
+    fn handled(
+        &self,
+        handle: OverlayHandle,
+        delta: PhysicalPosition<f64>,
+        window_size: PhySize,
+        min_size: f64,
+    ) -> Self {
+        let window_width = window_size.width as f64;
+        let window_height = window_size.height as f64;
+        let mut left = self.position.x as f64;
+        let mut top = self.position.y as f64;
+        let mut right = left + self.size.width as f64;
+        let mut bottom = top + self.size.height as f64;
+        match handle {
+            OverlayHandle::TitleBar => {
+                let width = right - left;
+                let height = bottom - top;
+                left = (left + delta.x).min(window_width - width).max(0.);
+                top = (top + delta.y).min(window_height - height).max(0.);
+                right = left + width;
+                bottom = top + height;
+            }
+            OverlayHandle::Border {
+                left: drag_left,
+                right: drag_right,
+                top: drag_top,
+                bottom: drag_bottom,
+            } => {
+                if drag_left {
+                    left = (left + delta.x).min(right - min_size).max(0.);
+                }
+                if drag_right {
+                    right = (right + delta.x).max(left + min_size).min(window_width);
+                }
+                if drag_top {
+                    top = (top + delta.y).min(bottom - min_size).max(0.);
+                }
+                if drag_bottom {
+                    bottom = (bottom + delta.y).max(top + min_size).min(window_height);
+                }
+            }
+        }
+        Self {
+            position: PhysicalPosition::new(left, top).cast::<u32>(),
+            size: PhysicalSize::new(right - left, bottom - top).cast::<u32>(),
+            movable: self.movable,
+        }
+    }
+
+    /// Move and shrink the overlay so that it fits in a window of size `window_size`.
+    fn fit_in(&mut self, window_size: PhySize) {
+        if window_size.width == 0 || window_size.height == 0 {
+            return;
+        }
+        self.size.width = self.size.width.min(window_size.width);
+        self.size.height = self.size.height.min(window_size.height);
+        self.position.x = self.position.x.min(window_size.width - self.size.width);
+        self.position.y = self.position.y.min(window_size.height - self.size.height);
+    }
+}
+
+/// A part of an overlay by which it can be moved or resized.
+#[derive(Clone, Copy, Debug)]
+enum OverlayHandle {
+    /// The top of the overlay, dragging it moves the overlay.
+    TitleBar,
+    /// The edges of the overlay that are moved when resizing it.
+    Border {
+        left: bool,
+        right: bool,
+        top: bool,
+        bottom: bool,
+    },
+}
+
+impl OverlayHandle {
+    fn cursor_icon(&self) -> CursorIcon {
+        match *self {
+            Self::TitleBar => CursorIcon::Move,
+            Self::Border {
+                left,
+                right,
+                top,
+                bottom,
+            } => match (left || right, top || bottom) {
+                (true, false) => CursorIcon::EwResize,
+                (false, true) => CursorIcon::NsResize,
+                _ if (left && top) || (right && bottom) => CursorIcon::NwseResize,
+                _ => CursorIcon::NeswResize,
+            },
+        }
+    }
 }
 
 fn create_pipeline(device: &Device, bg_layout: &wgpu::BindGroupLayout) -> wgpu::RenderPipeline {
@@ -868,6 +1361,15 @@ enum State {
         mouse_position: PhysicalPosition<f64>,
         element: ElementType,
     },
+    /// An overlay is being moved or resized.
+    HandlingOverlay {
+        mouse_position: PhysicalPosition<f64>,
+        clicked_position: PhysicalPosition<f64>,
+        overlay: usize,
+        handle: OverlayHandle,
+        /// The overlay as it was when the user clicked on it.
+        old_overlay: Overlay,
+    },
 }
 
 impl State {
@@ -875,7 +1377,8 @@ impl State {
         match self {
             Self::Resizing { mouse_position, .. }
             | Self::Normal { mouse_position }
-            | Self::Interacting { mouse_position, .. } => *mouse_position,
+            | Self::Interacting { mouse_position, .. }
+            | Self::HandlingOverlay { mouse_position, .. } => *mouse_position,
         }
     }
 }
@@ -282,6 +282,13 @@ impl LayoutTree {
         self.area.get(region).and_then(|a| a.borrow().proportion())
     }
 
+    /// Allow or forbid the user to resize the split node `node_id` by dragging its separation.
+    pub fn set_resizable(&mut self, node_id: usize, resizable: bool) {
+        self.area[node_id]
+            .borrow_mut()
+            .set_resizable(Some(node_id).filter(|_| resizable))
+    }
+
     pub fn log_tree(&self) {
         println!("{:#?}", self.root);
     }
@@ -566,6 +573,15 @@ impl LayoutNode {
     }
 
     /// Resize a split layout according to the new proportien given.
+    pub fn set_resizable(&mut self, new_resizable: Option<usize>) {
+        match self {
+            LayoutNode::VSplit { resizable, .. } | LayoutNode::HSplit { resizable, .. } => {
+                *resizable = new_resizable
+            }
+            LayoutNode::Area { .. } => (),
+        }
+    }
+
     pub fn resize(&mut self, new_proportion: f64) {
         match self {
             LayoutNode::VSplit {
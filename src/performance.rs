@@ -0,0 +1,109 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Measures of the time spent in the different parts of the event loop, displayed by the
+//! performance overlay.
+
+use crate::gui::PerformanceStats;
+use ensnano_interactor::graphics::ElementType;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The number of frames over which the frame time is averaged
+const NB_AVERAGED_FRAMES: usize = 30;
+
+pub struct PerformanceMonitor {
+    launch_date: Instant,
+    /// The time between the launch of the application and the first frame
+    startup_time: Option<Duration>,
+    last_frame_date: Option<Instant>,
+    frame_times: VecDeque<Duration>,
+    draw_times: Vec<(ElementType, Duration)>,
+    presenter_update_time: Option<Duration>,
+    /// True if the performance overlay is displayed
+    enabled: bool,
+}
+
+impl PerformanceMonitor {
+    pub fn new() -> Self {
+        Self {
+            launch_date: Instant::now(),
+            startup_time: None,
+            last_frame_date: None,
+            frame_times: VecDeque::with_capacity(NB_AVERAGED_FRAMES),
+            draw_times: Vec::new(),
+            presenter_update_time: None,
+            enabled: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled ^= true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Must be called each time a frame is presented
+    pub fn frame_presented(&mut self, draw_times: Vec<(ElementType, Duration)>) {
+        let now = Instant::now();
+        if self.startup_time.is_none() {
+            let startup_time = now - self.launch_date;
+            log::info!("Startup time: {:.2} s", startup_time.as_secs_f64());
+            self.startup_time = Some(startup_time);
+        }
+        if let Some(last_frame_date) = self.last_frame_date {
+            if self.frame_times.len() == NB_AVERAGED_FRAMES {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(now - last_frame_date);
+        }
+        self.last_frame_date = Some(now);
+        for (app, time) in draw_times {
+            if let Some(entry) = self.draw_times.iter_mut().find(|(a, _)| *a == app) {
+                entry.1 = time;
+            } else {
+                self.draw_times.push((app, time));
+            }
+        }
+    }
+
+    pub fn presenter_updated(&mut self, duration: Duration) {
+        self.presenter_update_time = Some(duration);
+    }
+
+    pub fn stats(&self, nb_instances: usize) -> PerformanceStats {
+        let frame_time = if self.frame_times.is_empty() {
+            Duration::ZERO
+        } else {
+            self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32
+        };
+        PerformanceStats {
+            frame_time,
+            draw_times: self
+                .draw_times
+                .iter()
+                .map(|(app, time)| (format!("{:?}", app), *time))
+                .collect(),
+            presenter_update_time: self.presenter_update_time,
+            nb_instances,
+            startup_time: self.startup_time,
+        }
+    }
+}
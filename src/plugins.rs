@@ -0,0 +1,207 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Loading and running the plugins.
+//!
+//! Plugins are only loaded when ENSnano is built with the `plugins` feature. Otherwise the list
+//! of plugins is always empty.
+
+use ensnano_interactor::{
+    consts::APP_NAME,
+    plugin::{DesignReader, Plugin, PluginContext, PluginDescription, PluginOutput},
+    Selection,
+};
+use std::path::PathBuf;
+
+/// The plugins that were loaded at start up.
+#[derive(Default)]
+pub struct PluginManager {
+    plugins: Vec<imp::LoadedPlugin>,
+}
+
+impl PluginManager {
+    pub fn load() -> Self {
+        let plugins = plugins_directory()
+            .map(|dir| imp::load_plugins_in(&dir))
+            .unwrap_or_default();
+        Self { plugins }
+    }
+
+    pub fn descriptions(&self) -> Vec<PluginDescription> {
+        self.plugins
+            .iter()
+            .enumerate()
+            .map(|(id, loaded)| {
+                let plugin = loaded.plugin();
+                PluginDescription {
+                    id,
+                    name: plugin.name(),
+                    description: plugin.description(),
+                    parameters: plugin.parameters(),
+                }
+            })
+            .collect()
+    }
+
+    /// Run the plugin with identifier `id`, or return `None` if there is no such plugin.
+    pub fn run(
+        &mut self,
+        id: usize,
+        context: &dyn PluginContext,
+        values: &[String],
+    ) -> Option<PluginOutput> {
+        let loaded = self.plugins.get_mut(id)?;
+        log::info!("Running plugin {}", loaded.plugin().name());
+        Some(loaded.plugin_mut().run(context, values))
+    }
+}
+
+/// The state of the application that is given to the plugins.
+pub struct Context<'a> {
+    pub design: &'a dyn DesignReader,
+    pub selection: &'a [Selection],
+}
+
+impl<'a> PluginContext for Context<'a> {
+    fn design(&self) -> &dyn DesignReader {
+        self.design
+    }
+
+    fn selection(&self) -> &[Selection] {
+        self.selection
+    }
+}
+
+fn plugins_directory() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(APP_NAME).join("plugins"))
+}
+
+#[cfg(feature = "plugins")]
+mod imp {
+    use super::*;
+    use ensnano_interactor::plugin::{
+        PluginConstructor, PluginRustcVersion, PLUGIN_API_VERSION, PLUGIN_API_VERSION_SYMBOL,
+        PLUGIN_CONSTRUCTOR_SYMBOL, PLUGIN_RUSTC_VERSION_SYMBOL, RUSTC_VERSION,
+    };
+    use libloading::Library;
+    use std::ffi::CStr;
+    use std::path::Path;
+
+    pub struct LoadedPlugin {
+        // Declared before `_library` so that the plugin is dropped before its code is unloaded.
+        plugin: Box<dyn Plugin>,
+        _library: Library,
+    }
+
+    impl LoadedPlugin {
+        pub fn plugin(&self) -> &dyn Plugin {
+            self.plugin.as_ref()
+        }
+
+        pub fn plugin_mut(&mut self) -> &mut dyn Plugin {
+            self.plugin.as_mut()
+        }
+
+        fn load(path: &Path) -> Result<Self, String> {
+            // Safety: the plugin is trusted by the user who put it in the plugins directory. The
+            // compiler and API versions are read through a C ABI and checked before any Rust
+            // function it exports is called.
+            unsafe {
+                let library = Library::new(path).map_err(|e| e.to_string())?;
+                let rustc_version = library
+                    .get::<PluginRustcVersion>(PLUGIN_RUSTC_VERSION_SYMBOL)
+                    .map_err(|e| e.to_string())?;
+                let rustc_version = CStr::from_ptr(rustc_version()).to_string_lossy();
+                if rustc_version != RUSTC_VERSION {
+                    return Err(format!(
+                        "built with {}, expected {}",
+                        rustc_version, RUSTC_VERSION
+                    ));
+                }
+                let version = library
+                    .get::<*const u32>(PLUGIN_API_VERSION_SYMBOL)
+                    .map_err(|e| e.to_string())?;
+                if **version != PLUGIN_API_VERSION {
+                    return Err(format!(
+                        "built for plugin API version {}, expected {}",
+                        **version, PLUGIN_API_VERSION
+                    ));
+                }
+                let constructor = library
+                    .get::<PluginConstructor>(PLUGIN_CONSTRUCTOR_SYMBOL)
+                    .map_err(|e| e.to_string())?;
+                let plugin = constructor();
+                Ok(Self {
+                    plugin,
+                    _library: library,
+                })
+            }
+        }
+    }
+
+    pub fn load_plugins_in(dir: &Path) -> Vec<LoadedPlugin> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension().and_then(|ext| ext.to_str())
+                    == Some(std::env::consts::DLL_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .filter_map(|path| match LoadedPlugin::load(&path) {
+                Ok(plugin) => {
+                    log::info!("Loaded plugin {:?}", path);
+                    Some(plugin)
+                }
+                Err(e) => {
+                    log::error!("Could not load plugin {:?}: {}", path, e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+mod imp {
+    use super::*;
+    use std::path::Path;
+
+    /// Never constructed: plugins cannot be loaded without the `plugins` feature.
+    pub enum LoadedPlugin {}
+
+    impl LoadedPlugin {
+        pub fn plugin(&self) -> &dyn Plugin {
+            match *self {}
+        }
+
+        pub fn plugin_mut(&mut self) -> &mut dyn Plugin {
+            match *self {}
+        }
+    }
+
+    pub fn load_plugins_in(_dir: &Path) -> Vec<LoadedPlugin> {
+        Vec::new()
+    }
+}
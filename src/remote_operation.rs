@@ -0,0 +1,247 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A serializable description of the design operations that can be sent to, or received from,
+//! another program.
+
+use ensnano_design::{
+    grid::{GridDescriptor, HelixGridPosition},
+    group_attributes::GroupPivot,
+    CameraId, HelixParameters, Nucl,
+};
+use ensnano_interactor::DesignOperation;
+use ensnano_organizer::GroupId;
+use serde::{Deserialize, Serialize};
+
+/// A design operation that can be sent to another instance of ENSnano or to an external tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RemoteOperation {
+    Cut {
+        nucl: Nucl,
+        s_id: usize,
+    },
+    GeneralXover {
+        source: Nucl,
+        target: Nucl,
+    },
+    Xover {
+        prime5_id: usize,
+        prime3_id: usize,
+    },
+    CrossCut {
+        target_3prime: bool,
+        source_id: usize,
+        target_id: usize,
+        nucl: Nucl,
+    },
+    MakeSeveralXovers {
+        xovers: Vec<(Nucl, Nucl)>,
+        doubled: bool,
+    },
+    RmXovers {
+        xovers: Vec<(Nucl, Nucl)>,
+    },
+    RmStrands {
+        strand_ids: Vec<usize>,
+    },
+    AddGridHelix {
+        position: HelixGridPosition,
+        start: isize,
+        length: usize,
+    },
+    RmHelices {
+        h_ids: Vec<usize>,
+    },
+    AddGrid(GridDescriptor),
+    RmGrid(usize),
+    ChangeSequence {
+        sequence: String,
+        strands: Vec<usize>,
+    },
+    ChangeColor {
+        color: u32,
+        strands: Vec<usize>,
+    },
+    SetScaffoldId(Option<usize>),
+    SetScaffoldShift(usize),
+    SetScaffoldSequence {
+        sequence: String,
+        shift: usize,
+    },
+    SetStrandName {
+        s_id: usize,
+        name: String,
+    },
+    SetRollHelices {
+        helices: Vec<usize>,
+        roll: f32,
+    },
+    SetVisibilityHelix {
+        helix: usize,
+        visible: bool,
+    },
+    FlipHelixGroup {
+        helix: usize,
+    },
+    FlipAnchors {
+        nucls: Vec<Nucl>,
+    },
+    SetRainbowScaffold(bool),
+    SetGlobalHelixParameters {
+        helix_parameters: HelixParameters,
+    },
+    SetGroupPivot {
+        group_id: GroupId,
+        pivot: GroupPivot,
+    },
+    DeleteCamera(CameraId),
+    SetFavouriteCamera(CameraId),
+}
+
+impl RemoteOperation {
+    /// Return the remote version of `operation`, or `None` if it cannot be shared.
+    #[cfg_attr(not(feature = "collaboration"), allow(dead_code))]
+    pub fn from_operation(operation: &DesignOperation) -> Option<Self> {
+        use DesignOperation as Op;
+        Some(match operation.clone() {
+            Op::Cut { nucl, s_id } => Self::Cut { nucl, s_id },
+            Op::GeneralXover { source, target } => Self::GeneralXover { source, target },
+            Op::Xover {
+                prime5_id,
+                prime3_id,
+            } => Self::Xover {
+                prime5_id,
+                prime3_id,
+            },
+            Op::CrossCut {
+                target_3prime,
+                source_id,
+                target_id,
+                nucl,
+            } => Self::CrossCut {
+                target_3prime,
+                source_id,
+                target_id,
+                nucl,
+            },
+            Op::MakeSeveralXovers { xovers, doubled } => {
+                Self::MakeSeveralXovers { xovers, doubled }
+            }
+            Op::RmXovers { xovers } => Self::RmXovers { xovers },
+            Op::RmStrands { strand_ids } => Self::RmStrands { strand_ids },
+            Op::AddGridHelix {
+                position,
+                start,
+                length,
+            } => Self::AddGridHelix {
+                position,
+                start,
+                length,
+            },
+            Op::RmHelices { h_ids } => Self::RmHelices { h_ids },
+            Op::AddGrid(desc) => Self::AddGrid(desc),
+            Op::RmGrid(g_id) => Self::RmGrid(g_id),
+            Op::ChangeSequence { sequence, strands } => Self::ChangeSequence { sequence, strands },
+            Op::ChangeColor { color, strands } => Self::ChangeColor { color, strands },
+            Op::SetScaffoldId(s_id) => Self::SetScaffoldId(s_id),
+            Op::SetScaffoldShift(shift) => Self::SetScaffoldShift(shift),
+            Op::SetScaffoldSequence { sequence, shift } => {
+                Self::SetScaffoldSequence { sequence, shift }
+            }
+            Op::SetStrandName { s_id, name } => Self::SetStrandName { s_id, name },
+            Op::SetRollHelices { helices, roll } => Self::SetRollHelices { helices, roll },
+            Op::SetVisibilityHelix { helix, visible } => {
+                Self::SetVisibilityHelix { helix, visible }
+            }
+            Op::FlipHelixGroup { helix } => Self::FlipHelixGroup { helix },
+            Op::FlipAnchors { nucls } => Self::FlipAnchors { nucls },
+            Op::SetRainbowScaffold(b) => Self::SetRainbowScaffold(b),
+            Op::SetGlobalHelixParameters { helix_parameters } => {
+                Self::SetGlobalHelixParameters { helix_parameters }
+            }
+            Op::SetGroupPivot { group_id, pivot } => Self::SetGroupPivot { group_id, pivot },
+            Op::DeleteCamera(camera_id) => Self::DeleteCamera(camera_id),
+            Op::SetFavouriteCamera(camera_id) => Self::SetFavouriteCamera(camera_id),
+            _ => return None,
+        })
+    }
+
+    pub fn to_operation(self) -> DesignOperation {
+        use DesignOperation as Op;
+        match self {
+            Self::Cut { nucl, s_id } => Op::Cut { nucl, s_id },
+            Self::GeneralXover { source, target } => Op::GeneralXover { source, target },
+            Self::Xover {
+                prime5_id,
+                prime3_id,
+            } => Op::Xover {
+                prime5_id,
+                prime3_id,
+            },
+            Self::CrossCut {
+                target_3prime,
+                source_id,
+                target_id,
+                nucl,
+            } => Op::CrossCut {
+                target_3prime,
+                source_id,
+                target_id,
+                nucl,
+            },
+            Self::MakeSeveralXovers { xovers, doubled } => {
+                Op::MakeSeveralXovers { xovers, doubled }
+            }
+            Self::RmXovers { xovers } => Op::RmXovers { xovers },
+            Self::RmStrands { strand_ids } => Op::RmStrands { strand_ids },
+            Self::AddGridHelix {
+                position,
+                start,
+                length,
+            } => Op::AddGridHelix {
+                position,
+                start,
+                length,
+            },
+            Self::RmHelices { h_ids } => Op::RmHelices { h_ids },
+            Self::AddGrid(desc) => Op::AddGrid(desc),
+            Self::RmGrid(g_id) => Op::RmGrid(g_id),
+            Self::ChangeSequence { sequence, strands } => Op::ChangeSequence { sequence, strands },
+            Self::ChangeColor { color, strands } => Op::ChangeColor { color, strands },
+            Self::SetScaffoldId(s_id) => Op::SetScaffoldId(s_id),
+            Self::SetScaffoldShift(shift) => Op::SetScaffoldShift(shift),
+            Self::SetScaffoldSequence { sequence, shift } => {
+                Op::SetScaffoldSequence { sequence, shift }
+            }
+            Self::SetStrandName { s_id, name } => Op::SetStrandName { s_id, name },
+            Self::SetRollHelices { helices, roll } => Op::SetRollHelices { helices, roll },
+            Self::SetVisibilityHelix { helix, visible } => {
+                Op::SetVisibilityHelix { helix, visible }
+            }
+            Self::FlipHelixGroup { helix } => Op::FlipHelixGroup { helix },
+            Self::FlipAnchors { nucls } => Op::FlipAnchors { nucls },
+            Self::SetRainbowScaffold(b) => Op::SetRainbowScaffold(b),
+            Self::SetGlobalHelixParameters { helix_parameters } => {
+                Op::SetGlobalHelixParameters { helix_parameters }
+            }
+            Self::SetGroupPivot { group_id, pivot } => Op::SetGroupPivot { group_id, pivot },
+            Self::DeleteCamera(camera_id) => Op::DeleteCamera(camera_id),
+            Self::SetFavouriteCamera(camera_id) => Op::SetFavouriteCamera(camera_id),
+        }
+    }
+}
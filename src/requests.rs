@@ -27,22 +27,31 @@ mod poll;
 
 use super::gui::UiSize;
 use super::*;
-use ensnano_interactor::{application::AppId, RollRequest, Selection};
+use ensnano_interactor::{application::AppId, RollRequest, Selection, TwistAxis};
 use ensnano_interactor::{graphics::HBondDisplay, UnrootedRevolutionSurfaceDescriptor};
-use ensnano_interactor::{CenterOfSelection, CheckXoversParameter};
+use ensnano_interactor::{i18n::Language, CenterOfSelection, CheckXoversParameter};
 pub(crate) use poll::poll_all;
 use ultraviolet::Vec3;
 
 use super::gui::OrganizerTree;
 use super::scene::FogParameters;
-use ensnano_design::grid::{GridId, GridPosition, GridTypeDescr};
+use super::tutorial::TutorialRequest;
+use ensnano_design::grid::{GridId, GridPosition, GridTypeDescr, PhantomParameters};
 use ensnano_design::{
+    design_operations::{HelixNumbering, InsertionPlacementTarget},
     elements::{DesignElementKey, DnaAttribute},
-    Nucl,
+    heat_map::HeatMapKind,
+    BundleId, JointId, JointKind, Nucl,
 };
 use ensnano_interactor::{
-    graphics::{Background3D, RenderingMode},
-    HyperboloidRequest, RigidBodyConstants, SuggestionParameters,
+    graphics::{
+        Background3D, BrushParameters, ColorVisionMode, ElementType, GpuPreference,
+        GroundParameters, NumberingParameters, RenderQuality, RenderingMode, ScaleBarParameters,
+        StrandEndsParameters, Theme, Thumbnail, ViewLayer, XoverDrawingParameters,
+        XoverGuideParameters,
+    },
+    DesignBrowserChoice, GizmoAxis, GroupOperation, HyperboloidRequest, RigidBodyConstants,
+    ShiftOptimizationParameters, SimulationJobKind, SuggestionParameters, WidgetBasis,
 };
 
 use std::collections::VecDeque;
@@ -78,6 +87,8 @@ pub struct Requests {
     pub camera_rotation: Option<(f32, f32, f32)>,
     pub camera_target: Option<(Vec3, Vec3)>,
     pub small_spheres: Option<bool>,
+    pub grid_labels: Option<bool>,
+    pub phantom_parameters: Option<PhantomParameters>,
     pub set_scaffold_id: Option<Option<usize>>,
     pub recolor_staples: Option<()>,
     pub roll_request: Option<RollRequest>,
@@ -92,6 +103,7 @@ pub struct Requests {
     pub paste: Option<()>,
     pub duplication: Option<()>,
     pub rigid_grid_simulation: Option<RigidBodyConstants>,
+    pub rigid_bundle_simulation: Option<RigidBodyConstants>,
     pub rigid_helices_simulation: Option<RigidBodyConstants>,
     pub anchor: Option<()>,
     pub rigid_body_parameters: Option<RigidBodyConstants>,
@@ -109,11 +121,34 @@ pub struct Requests {
     pub toggle_visibility: Option<bool>,
     pub all_visible: Option<()>,
     pub redim_2d_helices: Option<bool>,
+    pub insertion_placement: Option<InsertionPlacementTarget>,
+    pub ideal_roll: Option<()>,
+    pub renumber_helices: Option<HelixNumbering>,
+    pub helix_display_number: Option<(usize, Option<usize>)>,
+    pub new_helix_bundle: Option<(String, Vec<usize>)>,
+    pub delete_helix_bundle: Option<BundleId>,
+    pub rename_helix_bundle: Option<(BundleId, String)>,
+    /// A request to add a joint of the given kind between the two bodies of the selection
+    pub new_joint: Option<JointKind>,
+    pub delete_joint: Option<JointId>,
+    /// A request to select some strands, and to hide everything else if the boolean is true
+    pub highlighted_strands: Option<(Vec<usize>, bool)>,
     pub delete_selection: Option<()>,
     pub select_scaffold: Option<()>,
     pub scaffold_shift: Option<usize>,
     pub rendering_mode: Option<RenderingMode>,
     pub background3d: Option<Background3D>,
+    pub ground_parameters: Option<GroundParameters>,
+    pub view_layer: Option<(ElementType, ViewLayer)>,
+    pub scale_bar_parameters: Option<ScaleBarParameters>,
+    pub numbering_parameters: Option<NumberingParameters>,
+    pub xover_drawing_parameters: Option<XoverDrawingParameters>,
+    pub strand_ends_parameters: Option<StrandEndsParameters>,
+    pub xover_guide_parameters: Option<XoverGuideParameters>,
+    pub heat_map: Option<Option<HeatMapKind>>,
+    pub theme: Option<Theme>,
+    pub language: Option<Language>,
+    pub color_vision_mode: Option<ColorVisionMode>,
     pub undo: Option<()>,
     pub redo: Option<()>,
     pub save_shortcut: Option<()>,
@@ -125,8 +160,21 @@ pub struct Requests {
     pub new_selection: Option<Vec<Selection>>,
     pub suspend_op: Option<()>,
     pub center_selection: Option<(Selection, AppId)>,
+    pub trace_strand: Option<usize>,
+    pub context_menu: Option<Selection>,
     pub centering_on_nucl: Option<(Nucl, usize)>,
     pub toggle_widget_basis: Option<()>,
+    pub widget_basis: Option<WidgetBasis>,
+    pub gizmo_size: Option<f32>,
+    pub undo_limit: Option<u32>,
+    pub cancel_file_io: Option<()>,
+    pub design_browser_choice: Option<DesignBrowserChoice>,
+    pub binary_design_files: Option<bool>,
+    pub confirm_large_deletions: Option<bool>,
+    pub gpu_preference: Option<GpuPreference>,
+    pub render_quality: Option<RenderQuality>,
+    pub shift_optimization_parameters: Option<ShiftOptimizationParameters>,
+    pub toggle_gizmo_axis: Option<GizmoAxis>,
     pub stop_roll: Option<()>,
     pub new_paste_candiate: Option<Option<Nucl>>,
     pub new_grid_paste_candidate: Option<GridPosition>,
@@ -135,17 +183,41 @@ pub struct Requests {
     pub new_suggestion_parameters: Option<SuggestionParameters>,
     pub check_xover_parameters: Option<CheckXoversParameter>,
     pub follow_stereographic_camera: Option<bool>,
+    pub inside_out_inspection: Option<bool>,
+    pub brush_parameters: Option<BrushParameters>,
+    pub brush_stroke: Option<(Vec<Nucl>, BrushParameters)>,
+    pub edit_insertion_length: Option<()>,
     pub set_show_stereographic_camera: Option<bool>,
     pub set_show_h_bonds: Option<HBondDisplay>,
     pub set_show_bezier_paths: Option<bool>,
+    pub set_show_insertion_badges: Option<bool>,
     pub set_invert_y_scroll: Option<bool>,
     pub set_all_helices_on_axis: Option<bool>,
     pub toggle_all_helices_on_axis: Option<()>,
     pub twist_simulation: Option<GridId>,
+    /// A request to set or remove the axis around which free helices are twisted
+    pub twist_axis: Option<Option<TwistAxis>>,
+    pub twist_axis_from_selection: Option<()>,
+    pub helices_twist: Option<()>,
+    pub simulation_job: Option<(SimulationJobKind, RigidBodyConstants)>,
+    pub pause_simulation_job: Option<(usize, bool)>,
+    pub stop_simulation_job: Option<usize>,
+    pub run_plugin: Option<(usize, Vec<String>)>,
+    pub compare_with_last_save: Option<()>,
+    pub stop_design_comparison: Option<()>,
+    pub create_checkpoint: Option<String>,
+    pub restore_checkpoint: Option<usize>,
+    pub compare_with_checkpoint: Option<usize>,
+    pub delete_checkpoint: Option<usize>,
+    pub group_operation: Option<(ensnano_organizer::GroupId, GroupOperation)>,
+    pub group_simulation: Option<(ensnano_organizer::GroupId, RigidBodyConstants)>,
+    pub camera_thumbnail: Option<(ensnano_design::CameraId, Thumbnail)>,
+    pub store_camera_rendering: Option<(ensnano_design::CameraId, bool)>,
     pub horizon_targeted: Option<()>,
     pub new_bezier_revolution_id: Option<Option<usize>>,
     pub new_bezier_revolution_radius: Option<f64>,
     pub new_bezier_revolution_axis_position: Option<f64>,
     pub new_unrooted_surface: Option<Option<UnrootedRevolutionSurfaceDescriptor>>,
     pub switched_to_revolution_tab: Option<()>,
+    pub tutorial_request: Option<TutorialRequest>,
 }
@@ -95,4 +95,12 @@ impl FlatSceneRequests for Requests {
     fn set_paste_candidate(&mut self, candidate: Option<Nucl>) {
         self.new_paste_candiate = Some(candidate);
     }
+
+    fn open_context_menu(&mut self, selection: Selection) {
+        self.context_menu = Some(selection);
+    }
+
+    fn set_double_strand_parameters(&mut self, position: isize, length: usize) {
+        self.new_double_strand_parameters = Some(Some((position, length)));
+    }
 }
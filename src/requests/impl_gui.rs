@@ -19,8 +19,16 @@ ENSnano, a 3d graphical application for DNA nanostructures.
 //! Implements the [Requests](`crate::gui::Requests`) trait for [Requests](`super::Requests`).
 
 use crate::gui::{Requests as GuiRequests, RigidBodyParametersRequest};
-use ensnano_design::grid::GridId;
-use ensnano_interactor::{InsertionPoint, RigidBodyConstants, RollRequest};
+use ensnano_design::{
+    design_operations::RandomSequenceParameters,
+    grid::{GridId, PhantomParameters},
+    heat_map::HeatMapKind,
+    BundleId, JointId, JointKind, OligoPool,
+};
+use ensnano_interactor::{
+    DesignBrowserChoice, DesignTemplate, GroupOperation, HelixBlockDescriptor, InsertionPoint,
+    RigidBodyConstants, RollRequest, SimulationJobKind,
+};
 use std::collections::BTreeSet;
 
 use super::*;
@@ -40,10 +48,54 @@ impl GuiRequests for Requests {
         self.strand_color_change = Some(color);
     }
 
+    fn set_ground_parameters(&mut self, parameters: GroundParameters) {
+        self.ground_parameters = Some(parameters)
+    }
+
+    fn set_view_layer(&mut self, view: ElementType, layer: ViewLayer) {
+        self.view_layer = Some((view, layer))
+    }
+
+    fn set_scale_bar_parameters(&mut self, parameters: ScaleBarParameters) {
+        self.scale_bar_parameters = Some(parameters)
+    }
+
+    fn set_numbering_parameters(&mut self, parameters: NumberingParameters) {
+        self.numbering_parameters = Some(parameters)
+    }
+
+    fn set_xover_drawing_parameters(&mut self, parameters: XoverDrawingParameters) {
+        self.xover_drawing_parameters = Some(parameters)
+    }
+
+    fn set_strand_ends_parameters(&mut self, parameters: StrandEndsParameters) {
+        self.strand_ends_parameters = Some(parameters)
+    }
+
+    fn set_xover_guide_parameters(&mut self, parameters: XoverGuideParameters) {
+        self.xover_guide_parameters = Some(parameters)
+    }
+
+    fn set_heat_map(&mut self, heat_map: Option<HeatMapKind>) {
+        self.heat_map = Some(heat_map)
+    }
+
     fn change_3d_background(&mut self, bg: Background3D) {
         self.background3d = Some(bg);
     }
 
+    fn set_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+    }
+
+    fn set_color_vision_mode(&mut self, mode: ColorVisionMode) {
+        self.color_vision_mode = Some(mode);
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = Some(language);
+    }
+
     fn change_3d_rendering_mode(&mut self, mode: RenderingMode) {
         self.rendering_mode = Some(mode);
     }
@@ -64,6 +116,58 @@ impl GuiRequests for Requests {
         self.redim_2d_helices = Some(all);
     }
 
+    fn place_insertions(&mut self, target: InsertionPlacementTarget) {
+        self.insertion_placement = Some(target);
+    }
+
+    fn set_ideal_roll(&mut self) {
+        self.ideal_roll = Some(());
+    }
+
+    fn renumber_helices(&mut self, scheme: HelixNumbering) {
+        self.renumber_helices = Some(scheme);
+    }
+
+    fn set_helix_display_number(&mut self, helix: usize, number: Option<usize>) {
+        self.helix_display_number = Some((helix, number));
+    }
+
+    fn create_helix_bundle(&mut self, name: String, helices: Vec<usize>) {
+        self.new_helix_bundle = Some((name, helices));
+    }
+
+    fn delete_helix_bundle(&mut self, bundle_id: BundleId) {
+        self.delete_helix_bundle = Some(bundle_id);
+    }
+
+    fn rename_helix_bundle(&mut self, bundle_id: BundleId, name: String) {
+        self.rename_helix_bundle = Some((bundle_id, name));
+    }
+
+    fn add_joint_between_selected_bodies(&mut self, kind: JointKind) {
+        self.new_joint = Some(kind);
+    }
+
+    fn delete_joint(&mut self, joint_id: JointId) {
+        self.delete_joint = Some(joint_id);
+    }
+
+    fn set_twist_axis_from_selection(&mut self) {
+        self.twist_axis_from_selection = Some(());
+    }
+
+    fn clear_twist_axis(&mut self) {
+        self.twist_axis = Some(None);
+    }
+
+    fn start_helices_twist(&mut self) {
+        self.helices_twist = Some(());
+    }
+
+    fn highlight_strands(&mut self, strands: Vec<usize>, hide_others: bool) {
+        self.highlighted_strands = Some((strands, hide_others));
+    }
+
     fn make_all_elements_visible(&mut self) {
         self.all_visible = Some(());
     }
@@ -72,6 +176,18 @@ impl GuiRequests for Requests {
         self.toggle_visibility = Some(compl);
     }
 
+    fn delete_selection(&mut self) {
+        self.delete_selection = Some(());
+    }
+
+    fn center_selection(&mut self, selection: Selection, app_id: AppId) {
+        self.center_selection = Some((selection, app_id));
+    }
+
+    fn trace_strand(&mut self, s_id: usize) {
+        self.trace_strand = Some(s_id);
+    }
+
     fn remove_empty_domains(&mut self) {
         self.clean_requests = Some(());
     }
@@ -88,6 +204,42 @@ impl GuiRequests for Requests {
         self.toggle_widget_basis = Some(())
     }
 
+    fn set_widget_basis(&mut self, widget_basis: WidgetBasis) {
+        self.widget_basis = Some(widget_basis)
+    }
+
+    fn set_gizmo_size(&mut self, size: f32) {
+        self.gizmo_size = Some(size)
+    }
+
+    fn set_undo_limit(&mut self, limit: u32) {
+        self.undo_limit = Some(limit)
+    }
+
+    fn cancel_file_io(&mut self) {
+        self.cancel_file_io = Some(())
+    }
+
+    fn set_binary_design_files(&mut self, binary: bool) {
+        self.binary_design_files = Some(binary)
+    }
+
+    fn set_confirm_large_deletions(&mut self, confirm: bool) {
+        self.confirm_large_deletions = Some(confirm)
+    }
+
+    fn set_gpu_preference(&mut self, gpu_preference: GpuPreference) {
+        self.gpu_preference = Some(gpu_preference)
+    }
+
+    fn set_render_quality(&mut self, render_quality: RenderQuality) {
+        self.render_quality = Some(render_quality)
+    }
+
+    fn set_shift_optimization_parameters(&mut self, parameters: ShiftOptimizationParameters) {
+        self.shift_optimization_parameters = Some(parameters)
+    }
+
     fn set_dna_sequences_visibility(&mut self, visible: bool) {
         self.toggle_text = Some(visible);
     }
@@ -139,6 +291,11 @@ impl GuiRequests for Requests {
         self.rigid_grid_simulation = Some(rigid_body_parameters);
     }
 
+    fn update_rigid_bundles_simulation(&mut self, parameters: RigidBodyParametersRequest) {
+        let rigid_body_parameters = rigid_parameters(parameters);
+        self.rigid_bundle_simulation = Some(rigid_body_parameters);
+    }
+
     fn update_rigid_body_simulation_parameters(&mut self, parameters: RigidBodyParametersRequest) {
         let rigid_body_parameters = rigid_parameters(parameters);
         self.rigid_body_parameters = Some(rigid_body_parameters);
@@ -241,6 +398,23 @@ impl GuiRequests for Requests {
         self.keep_proceed.push_back(Action::NewDesign)
     }
 
+    fn start_tutorial(&mut self) {
+        self.tutorial_request = Some(TutorialRequest::Start);
+    }
+
+    fn skip_tutorial_step(&mut self) {
+        self.tutorial_request = Some(TutorialRequest::SkipStep);
+    }
+
+    fn stop_tutorial(&mut self) {
+        self.tutorial_request = Some(TutorialRequest::Stop);
+    }
+
+    fn new_design_from_template(&mut self, template: DesignTemplate) {
+        self.keep_proceed
+            .push_back(Action::NewDesignFromTemplate(template))
+    }
+
     fn save_as(&mut self) {
         self.keep_proceed.push_back(Action::SaveAs);
     }
@@ -249,6 +423,22 @@ impl GuiRequests for Requests {
         self.keep_proceed.push_back(Action::QuickSave);
     }
 
+    fn save_incremental_copy(&mut self) {
+        self.keep_proceed.push_back(Action::SaveIncrementalCopy);
+    }
+
+    fn save_variant(&mut self) {
+        self.keep_proceed.push_back(Action::SaveVariant);
+    }
+
+    fn set_read_only(&mut self, read_only: bool) {
+        self.keep_proceed.push_back(Action::SetReadOnly(read_only));
+    }
+
+    fn toggle_detached_left_panel(&mut self) {
+        self.keep_proceed.push_back(Action::ToggleDetachedLeftPanel);
+    }
+
     fn open_file(&mut self) {
         self.keep_proceed.push_back(Action::LoadDesign(None));
     }
@@ -282,6 +472,19 @@ impl GuiRequests for Requests {
         self.small_spheres = Some(small);
     }
 
+    fn set_grid_labels(&mut self, labelled: bool) {
+        self.grid_labels = Some(labelled);
+    }
+
+    fn set_phantom_parameters(&mut self, parameters: PhantomParameters) {
+        self.phantom_parameters = Some(parameters);
+    }
+
+    fn finish_setting_phantom_parameters(&mut self) {
+        self.keep_proceed
+            .push_back(Action::FinishSettingPhantomParameters);
+    }
+
     fn finish_changing_color(&mut self) {
         self.keep_proceed.push_back(Action::FinishChangingColor);
     }
@@ -310,6 +513,38 @@ impl GuiRequests for Requests {
             }));
     }
 
+    fn set_strand_pool(&mut self, s_id: usize, pool: Option<OligoPool>) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::SetStrandPool {
+                s_id,
+                pool,
+            }));
+    }
+
+    fn set_strand_note(&mut self, s_id: usize, note: Option<String>) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::SetStrandNote {
+                s_id,
+                note,
+            }));
+    }
+
+    fn set_strand_url(&mut self, s_id: usize, url: Option<String>) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::SetStrandUrl {
+                s_id,
+                url,
+            }));
+    }
+
+    fn set_strand_color(&mut self, s_id: usize, color: u32) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::ChangeColor {
+                color,
+                strands: vec![s_id],
+            }));
+    }
+
     fn create_new_camera(&mut self) {
         self.keep_proceed.push_back(Action::NewCamera);
     }
@@ -325,6 +560,27 @@ impl GuiRequests for Requests {
         self.keep_proceed.push_back(Action::SelectCamera(cam_id))
     }
 
+    fn add_presentation_step(&mut self) {
+        self.keep_proceed.push_back(Action::AddPresentationStep)
+    }
+
+    fn delete_presentation_step(&mut self, step: usize) {
+        self.keep_proceed.push_back(Action::DesignOperation(
+            DesignOperation::DeletePresentationStep(step),
+        ))
+    }
+
+    fn set_presentation_step_name(&mut self, step: usize, name: String) {
+        self.keep_proceed.push_back(Action::DesignOperation(
+            DesignOperation::SetPresentationStepName { step, name },
+        ))
+    }
+
+    fn go_to_presentation_step(&mut self, forward: bool) {
+        self.keep_proceed
+            .push_back(Action::PresentationStep { forward })
+    }
+
     fn set_favourite_camera(&mut self, cam_id: ensnano_design::CameraId) {
         self.keep_proceed.push_back(Action::DesignOperation(
             DesignOperation::SetFavouriteCamera(cam_id),
@@ -343,6 +599,15 @@ impl GuiRequests for Requests {
             }))
     }
 
+    fn swap_cameras(&mut self, a: ensnano_design::CameraId, b: ensnano_design::CameraId) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::SwapCameras(a, b)))
+    }
+
+    fn store_camera_rendering(&mut self, cam_id: ensnano_design::CameraId, store: bool) {
+        self.store_camera_rendering = Some((cam_id, store));
+    }
+
     fn set_suggestion_parameters(&mut self, param: SuggestionParameters) {
         self.new_suggestion_parameters = Some(param);
     }
@@ -376,6 +641,13 @@ impl GuiRequests for Requests {
             }))
     }
 
+    fn add_helix_block(&mut self, block: HelixBlockDescriptor) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::AddHelixBlock(
+                block,
+            )))
+    }
+
     fn set_check_xover_parameters(&mut self, parameters: CheckXoversParameter) {
         self.check_xover_parameters = Some(parameters);
     }
@@ -384,6 +656,14 @@ impl GuiRequests for Requests {
         self.follow_stereographic_camera = Some(follow);
     }
 
+    fn set_inside_out_inspection(&mut self, inside_out: bool) {
+        self.inside_out_inspection = Some(inside_out);
+    }
+
+    fn set_brush_parameters(&mut self, parameters: BrushParameters) {
+        self.brush_parameters = Some(parameters);
+    }
+
     fn flip_split_views(&mut self) {
         self.keep_proceed.push_back(Action::FlipSplitViews);
     }
@@ -394,6 +674,13 @@ impl GuiRequests for Requests {
         ))
     }
 
+    fn set_design_metadata(&mut self, metadata: ensnano_design::DesignMetadata) {
+        self.keep_proceed
+            .push_back(Action::DesignOperation(DesignOperation::SetDesignMetadata(
+                metadata,
+            )))
+    }
+
     fn set_show_stereographic_camera(&mut self, show: bool) {
         self.set_show_stereographic_camera = Some(show);
     }
@@ -406,6 +693,10 @@ impl GuiRequests for Requests {
         self.set_show_bezier_paths = Some(show);
     }
 
+    fn set_show_insertion_badges(&mut self, show: bool) {
+        self.set_show_insertion_badges = Some(show);
+    }
+
     fn set_all_helices_on_axis(&mut self, off_axis: bool) {
         // thick helices = normal helices; thin helices = only axis
         self.set_all_helices_on_axis = Some(off_axis)
@@ -415,6 +706,70 @@ impl GuiRequests for Requests {
         self.twist_simulation = Some(grid_id);
     }
 
+    fn start_simulation_job(
+        &mut self,
+        kind: SimulationJobKind,
+        parameters: RigidBodyParametersRequest,
+    ) {
+        self.simulation_job = Some((kind, rigid_parameters(parameters)));
+    }
+
+    fn pause_simulation_job(&mut self, job_id: usize, paused: bool) {
+        self.pause_simulation_job = Some((job_id, paused));
+    }
+
+    fn stop_simulation_job(&mut self, job_id: usize) {
+        self.stop_simulation_job = Some(job_id);
+    }
+
+    fn run_plugin(&mut self, plugin_id: usize, values: Vec<String>) {
+        self.run_plugin = Some((plugin_id, values));
+    }
+
+    fn compare_with_last_save(&mut self) {
+        self.compare_with_last_save = Some(());
+    }
+
+    fn compare_with_design_file(&mut self) {
+        self.keep_proceed.push_back(Action::CompareWithDesignFile)
+    }
+
+    fn stop_design_comparison(&mut self) {
+        self.stop_design_comparison = Some(());
+    }
+
+    fn create_checkpoint(&mut self, name: String) {
+        self.create_checkpoint = Some(name);
+    }
+
+    fn restore_checkpoint(&mut self, checkpoint_id: usize) {
+        self.restore_checkpoint = Some(checkpoint_id);
+    }
+
+    fn compare_with_checkpoint(&mut self, checkpoint_id: usize) {
+        self.compare_with_checkpoint = Some(checkpoint_id);
+    }
+
+    fn delete_checkpoint(&mut self, checkpoint_id: usize) {
+        self.delete_checkpoint = Some(checkpoint_id);
+    }
+
+    fn apply_group_operation(
+        &mut self,
+        group_id: ensnano_organizer::GroupId,
+        operation: GroupOperation,
+    ) {
+        self.group_operation = Some((group_id, operation));
+    }
+
+    fn start_group_simulation(
+        &mut self,
+        group_id: ensnano_organizer::GroupId,
+        parameters: RigidBodyParametersRequest,
+    ) {
+        self.group_simulation = Some((group_id, rigid_parameters(parameters)));
+    }
+
     fn align_horizon(&mut self) {
         self.horizon_targeted = Some(());
     }
@@ -451,6 +806,29 @@ impl GuiRequests for Requests {
         ))
     }
 
+    fn add_helices_along_path(
+        &mut self,
+        path_id: ensnano_design::BezierPathId,
+        with_strands: bool,
+    ) {
+        self.keep_proceed.push_back(Action::DesignOperation(
+            DesignOperation::AddHelicesAlongBezierPath {
+                path_id,
+                with_strands,
+            },
+        ))
+    }
+
+    fn edit_bezier_path_control_points(
+        &mut self,
+        path_id: ensnano_design::BezierPathId,
+        edit: ensnano_design::design_operations::ControlPointsEdit,
+    ) {
+        self.keep_proceed.push_back(Action::DesignOperation(
+            DesignOperation::EditBezierPathControlPoints { path_id, edit },
+        ))
+    }
+
     fn make_bezier_path_cyclic(&mut self, path_id: ensnano_design::BezierPathId, cyclic: bool) {
         self.keep_proceed.push_back(Action::DesignOperation(
             DesignOperation::MakeBezierPathCyclic { path_id, cyclic },
@@ -461,6 +839,10 @@ impl GuiRequests for Requests {
         self.keep_proceed.push_back(Action::SetExporting(exporting))
     }
 
+    fn choose_in_design_browser(&mut self, choice: DesignBrowserChoice) {
+        self.design_browser_choice = Some(choice)
+    }
+
     fn import_3d_object(&mut self) {
         self.keep_proceed.push_back(Action::Import3DObject)
     }
@@ -482,6 +864,45 @@ impl GuiRequests for Requests {
         self.keep_proceed.push_back(Action::OptimizeShift)
     }
 
+    fn respace_xovers(&mut self) {
+        self.keep_proceed.push_back(Action::RespaceXovers)
+    }
+
+    fn delete_base_pairs(&mut self) {
+        self.keep_proceed.push_back(Action::DeleteBasePairs)
+    }
+
+    fn insert_base_pairs(&mut self) {
+        self.keep_proceed.push_back(Action::InsertBasePairs)
+    }
+
+    fn optimize_nicks(&mut self) {
+        self.keep_proceed.push_back(Action::OptimizeNicks)
+    }
+
+    fn clear_sequences(&mut self) {
+        self.keep_proceed.push_back(Action::ClearSequences)
+    }
+
+    fn assign_scaffold_sequence_to_selection(&mut self) {
+        self.keep_proceed
+            .push_back(Action::AssignScaffoldSequenceToSelection)
+    }
+
+    fn fill_random_sequences(&mut self, parameters: RandomSequenceParameters) {
+        self.keep_proceed
+            .push_back(Action::FillRandomSequences { parameters })
+    }
+
+    fn cleanup_strands(&mut self, min_length: usize) {
+        self.keep_proceed
+            .push_back(Action::CleanupStrands { min_length })
+    }
+
+    fn audit_scaffold_pairing(&mut self) {
+        self.keep_proceed.push_back(Action::AuditScaffoldPairing)
+    }
+
     fn start_revolution_relaxation(&mut self, desc: RevolutionSurfaceSystemDescriptor) {
         self.keep_proceed
             .push_back(Action::RevolutionSimulation { desc })
@@ -522,6 +943,11 @@ impl GuiRequests for Requests {
         // .push_back(Action::NotifyApps(Notification::ScreenShot3D))
     }
 
+    fn copy_to_clipboard(&mut self, content: String) {
+        self.keep_proceed
+            .push_back(Action::CopyToClipboard(content))
+    }
+
     fn request_save_nucleotides_positions(&mut self) {
         self.keep_proceed
             .push_back(Action::GetDesignPathAndNotify(|path| {
@@ -129,4 +129,36 @@ impl SceneRequests for Requests {
     fn set_revolution_axis_position(&mut self, position: f32) {
         self.new_bezier_revolution_axis_position = Some(position as f64);
     }
+
+    fn set_twist_axis(&mut self, axis: TwistAxis) {
+        self.twist_axis = Some(Some(axis));
+    }
+
+    fn open_context_menu(&mut self, selection: Selection) {
+        self.context_menu = Some(selection);
+    }
+
+    fn set_camera_thumbnail(
+        &mut self,
+        camera_id: ensnano_design::CameraId,
+        thumbnail: ensnano_interactor::graphics::Thumbnail,
+    ) {
+        self.camera_thumbnail = Some((camera_id, thumbnail));
+    }
+
+    fn apply_brush(
+        &mut self,
+        nucls: Vec<Nucl>,
+        brush: ensnano_interactor::graphics::BrushParameters,
+    ) {
+        self.brush_stroke = Some((nucls, brush));
+    }
+
+    fn edit_insertion_length(&mut self) {
+        self.edit_insertion_length = Some(());
+    }
+
+    fn set_double_strand_parameters(&mut self, position: isize, length: usize) {
+        self.new_double_strand_parameters = Some(Some((position, length)));
+    }
 }
@@ -85,6 +85,14 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.push_action(Action::ToggleSmallSphere(b))
     }
 
+    if let Some(labelled) = requests.grid_labels.take() {
+        main_state.push_action(Action::SetGridLabels(labelled))
+    }
+
+    if let Some(parameters) = requests.phantom_parameters.take() {
+        main_state.push_action(Action::SetPhantomParameters(parameters))
+    }
+
     if let Some(target) = requests.camera_target.take() {
         main_state.push_action(Action::NotifyApps(Notification::CameraTarget(target)))
     }
@@ -158,10 +166,82 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.push_action(Action::RigidGridSimulation { parameters })
     }
 
+    if let Some(parameters) = requests.rigid_bundle_simulation.take() {
+        main_state.push_action(Action::RigidBundleSimulation { parameters })
+    }
+
     if let Some(g_id) = requests.twist_simulation.take() {
         main_state.push_action(Action::Twist(g_id))
     }
 
+    if let Some(axis) = requests.twist_axis.take() {
+        main_state.set_twist_axis(axis)
+    }
+
+    if requests.twist_axis_from_selection.take().is_some() {
+        main_state.set_twist_axis_from_selection()
+    }
+
+    if requests.helices_twist.take().is_some() {
+        main_state.push_action(Action::TwistHelices)
+    }
+
+    if let Some((kind, parameters)) = requests.simulation_job.take() {
+        main_state.push_simulation_job(kind, parameters);
+    }
+
+    if let Some((job_id, paused)) = requests.pause_simulation_job.take() {
+        main_state.simulation_jobs.set_paused(job_id, paused);
+    }
+
+    if let Some(job_id) = requests.stop_simulation_job.take() {
+        main_state.simulation_jobs.stop(job_id);
+    }
+
+    if let Some((plugin_id, values)) = requests.run_plugin.take() {
+        main_state.run_plugin(plugin_id, values);
+    }
+
+    if requests.compare_with_last_save.take().is_some() {
+        main_state.compare_with_last_save();
+    }
+
+    if requests.stop_design_comparison.take().is_some() {
+        main_state.stop_design_comparison();
+    }
+
+    if let Some(name) = requests.create_checkpoint.take() {
+        main_state.create_checkpoint(name);
+    }
+
+    if let Some(checkpoint_id) = requests.restore_checkpoint.take() {
+        main_state.restore_checkpoint(checkpoint_id);
+    }
+
+    if let Some(checkpoint_id) = requests.compare_with_checkpoint.take() {
+        main_state.compare_with_checkpoint(checkpoint_id);
+    }
+
+    if let Some(checkpoint_id) = requests.delete_checkpoint.take() {
+        main_state.delete_checkpoint(checkpoint_id);
+    }
+
+    if let Some((group_id, operation)) = requests.group_operation.take() {
+        main_state.apply_group_operation(group_id, operation);
+    }
+
+    if let Some((group_id, parameters)) = requests.group_simulation.take() {
+        main_state.start_group_simulation(group_id, parameters);
+    }
+
+    if let Some((camera_id, thumbnail)) = requests.camera_thumbnail.take() {
+        main_state.set_camera_thumbnail(camera_id, thumbnail);
+    }
+
+    if let Some((camera_id, store)) = requests.store_camera_rendering.take() {
+        main_state.store_camera_rendering(camera_id, store);
+    }
+
     if let Some(parameters) = requests.rigid_helices_simulation.take() {
         main_state.push_action(Action::RigidHelicesSimulation { parameters })
     }
@@ -251,6 +331,18 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.set_background_3d(bg);
     }
 
+    if let Some(theme) = requests.theme.take() {
+        main_state.set_theme(theme);
+    }
+
+    if let Some(mode) = requests.color_vision_mode.take() {
+        main_state.set_color_vision_mode(mode);
+    }
+
+    if let Some(language) = requests.language.take() {
+        main_state.set_language(language);
+    }
+
     if requests.undo.take().is_some() {
         main_state.push_action(Action::Undo);
     }
@@ -267,6 +359,10 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.messages.lock().unwrap().push_show_tutorial()
     }
 
+    if let Some(request) = requests.tutorial_request.take() {
+        main_state.handle_tutorial_request(request);
+    }
+
     if requests.force_help.take().is_some() {
         main_state.messages.lock().unwrap().show_help()
     }
@@ -286,6 +382,82 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.toggle_widget_basis()
     }
 
+    if let Some(widget_basis) = requests.widget_basis.take() {
+        main_state.set_widget_basis(widget_basis)
+    }
+
+    if let Some(size) = requests.gizmo_size.take() {
+        main_state.set_gizmo_size(size)
+    }
+
+    if let Some(limit) = requests.undo_limit.take() {
+        main_state.set_undo_limit(limit)
+    }
+
+    if let Some(binary) = requests.binary_design_files.take() {
+        main_state.set_binary_design_files(binary)
+    }
+
+    if let Some(confirm) = requests.confirm_large_deletions.take() {
+        main_state.set_confirm_large_deletions(confirm)
+    }
+
+    if let Some(gpu_preference) = requests.gpu_preference.take() {
+        main_state.set_gpu_preference(gpu_preference)
+    }
+
+    if let Some(render_quality) = requests.render_quality.take() {
+        main_state.set_render_quality(render_quality)
+    }
+
+    if let Some(parameters) = requests.shift_optimization_parameters.take() {
+        main_state.set_shift_optimization_parameters(parameters)
+    }
+
+    if requests.cancel_file_io.take().is_some() {
+        main_state.channel_reader.cancel_file_io()
+    }
+
+    if let Some(choice) = requests.design_browser_choice.take() {
+        main_state.design_browser_choice = Some(choice)
+    }
+
+    if let Some(parameters) = requests.ground_parameters.take() {
+        main_state.set_ground_parameters(parameters)
+    }
+
+    if let Some((view, layer)) = requests.view_layer.take() {
+        main_state.set_view_layer(view, layer)
+    }
+
+    if let Some(parameters) = requests.scale_bar_parameters.take() {
+        main_state.set_scale_bar_parameters(parameters)
+    }
+
+    if let Some(parameters) = requests.numbering_parameters.take() {
+        main_state.set_numbering_parameters(parameters)
+    }
+
+    if let Some(parameters) = requests.xover_drawing_parameters.take() {
+        main_state.set_xover_drawing_parameters(parameters)
+    }
+
+    if let Some(parameters) = requests.strand_ends_parameters.take() {
+        main_state.set_strand_ends_parameters(parameters)
+    }
+
+    if let Some(parameters) = requests.xover_guide_parameters.take() {
+        main_state.set_xover_guide_parameters(parameters)
+    }
+
+    if let Some(heat_map) = requests.heat_map.take() {
+        main_state.set_heat_map(heat_map)
+    }
+
+    if let Some(axis) = requests.toggle_gizmo_axis.take() {
+        main_state.toggle_gizmo_axis(axis)
+    }
+
     if requests.stop_roll.take().is_some() {
         main_state.pending_actions.push_back(Action::StopSimulation)
     }
@@ -308,6 +480,58 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
             )))
     }
 
+    if let Some((strands, hide_others)) = requests.highlighted_strands.take() {
+        main_state.highlight_strands(strands, hide_others)
+    }
+
+    if let Some(target) = requests.insertion_placement.take() {
+        main_state.push_action(Action::PlaceInsertions(target))
+    }
+
+    if requests.ideal_roll.take().is_some() {
+        main_state.push_action(Action::SetIdealRoll)
+    }
+
+    if let Some(scheme) = requests.renumber_helices.take() {
+        main_state.push_action(Action::DesignOperation(DesignOperation::RenumberHelices(
+            scheme,
+        )))
+    }
+
+    if let Some((helix, number)) = requests.helix_display_number.take() {
+        main_state.push_action(Action::DesignOperation(
+            DesignOperation::SetHelixDisplayNumber { helix, number },
+        ))
+    }
+
+    if let Some((name, helices)) = requests.new_helix_bundle.take() {
+        main_state.push_action(Action::DesignOperation(
+            DesignOperation::CreateHelixBundle { name, helices },
+        ))
+    }
+
+    if let Some(bundle_id) = requests.delete_helix_bundle.take() {
+        main_state.push_action(Action::DesignOperation(DesignOperation::DeleteHelixBundle(
+            bundle_id,
+        )))
+    }
+
+    if let Some((bundle_id, name)) = requests.rename_helix_bundle.take() {
+        main_state.push_action(Action::DesignOperation(
+            DesignOperation::RenameHelixBundle { bundle_id, name },
+        ))
+    }
+
+    if let Some(kind) = requests.new_joint.take() {
+        main_state.add_joint_between_selected_bodies(kind)
+    }
+
+    if let Some(joint_id) = requests.delete_joint.take() {
+        main_state.push_action(Action::DesignOperation(DesignOperation::DeleteJoint(
+            joint_id,
+        )))
+    }
+
     if let Some((selection, app_id)) = requests.center_selection.take() {
         main_state
             .pending_actions
@@ -316,6 +540,16 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
             )))
     }
 
+    if let Some(s_id) = requests.trace_strand.take() {
+        main_state
+            .pending_actions
+            .push_back(Action::NotifyApps(Notification::TraceStrand(s_id)))
+    }
+
+    if let Some(selection) = requests.context_menu.take() {
+        main_state.push_action(Action::OpenContextMenu(selection))
+    }
+
     if let Some(candidate) = requests.new_paste_candiate.take() {
         main_state
             .pending_actions
@@ -346,6 +580,22 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.set_follow_stereographic_camera(b);
     }
 
+    if let Some(b) = requests.inside_out_inspection.take() {
+        main_state.set_inside_out_inspection(b);
+    }
+
+    if let Some(parameters) = requests.brush_parameters.take() {
+        main_state.set_brush_parameters(parameters);
+    }
+
+    if let Some((nucls, brush)) = requests.brush_stroke.take() {
+        main_state.apply_brush(nucls, brush);
+    }
+
+    if requests.edit_insertion_length.take().is_some() {
+        main_state.messages.lock().unwrap().focus_insertion_length()
+    }
+
     if let Some(b) = requests.set_show_stereographic_camera.take() {
         main_state.set_show_stereographic_camera(b);
     }
@@ -358,6 +608,10 @@ pub(crate) fn poll_all<R: DerefMut<Target = Requests>>(
         main_state.set_show_bezier_paths(b);
     }
 
+    if let Some(b) = requests.set_show_insertion_badges.take() {
+        main_state.set_show_insertion_badges(b);
+    }
+
     if let Some(b) = requests.set_all_helices_on_axis.take() {
         main_state.set_all_helices_on_axis(b);
     }
@@ -26,7 +26,7 @@ use iced_winit::winit::{
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// The scheduler is responsible for running the different applications
 pub struct Scheduler {
@@ -86,18 +86,24 @@ impl Scheduler {
     }
 
     /// Request an application to draw on a texture
+    ///
+    /// Return the time spent by each application that was redrawn.
     pub fn draw_apps(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         multiplexer: &Multiplexer,
         dt: Duration,
-    ) {
+    ) -> Vec<(ElementType, Duration)> {
+        let mut draw_times = Vec::with_capacity(self.needs_redraw.len());
         for area in self.needs_redraw.iter() {
             let app = self.applications.get_mut(area).unwrap();
             if let Some(target) = multiplexer.get_texture_view(*area) {
+                let start = Instant::now();
                 app.lock().unwrap().on_redraw_request(encoder, target, dt);
+                draw_times.push((*area, start.elapsed()));
             }
         }
+        draw_times
     }
 
     /// Notify all applications that the size of the window has been modified
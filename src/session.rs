@@ -0,0 +1,139 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+//! The session of a design is the state of the user interface when the design was last saved:
+//! layout of the window, opened tab, current selection and cameras.
+//!
+//! It is stored next to the design in a file with the same name and the
+//! [ENS_SESSION_EXTENSION](crate::consts::ENS_SESSION_EXTENSION) extension, and restored when the
+//! design is opened.
+
+use ensnano_design::Nucl;
+use ensnano_interactor::application::{Camera2DState, Camera3D};
+use ensnano_interactor::graphics::SplitMode;
+use ensnano_interactor::Selection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub split_mode: SplitMode,
+    pub left_pannel_proportion: Option<f64>,
+    pub selected_tab: Option<usize>,
+    pub selection: Vec<SessionSelection>,
+    pub camera_3d: Option<Camera3D>,
+    pub camera_2d: Option<Camera2DState>,
+}
+
+/// The selected elements that can be restored when the design is re-opened.
+///
+/// Elements that only exist while the application is running (phantom helices, bezier control
+/// points...) are not saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionSelection {
+    Nucleotide(Nucl),
+    Bond(Nucl, Nucl),
+    Xover(usize),
+    Strand(u32),
+    Helix { helix_id: usize, segment_id: usize },
+}
+
+impl SessionSelection {
+    pub fn from_selection(selection: &Selection) -> Option<Self> {
+        match selection {
+            Selection::Nucleotide(_, nucl) => Some(Self::Nucleotide(*nucl)),
+            Selection::Bond(_, n1, n2) => Some(Self::Bond(*n1, *n2)),
+            Selection::Xover(_, xover_id) => Some(Self::Xover(*xover_id)),
+            Selection::Strand(_, strand_id) => Some(Self::Strand(*strand_id)),
+            Selection::Helix {
+                helix_id,
+                segment_id,
+                ..
+            } => Some(Self::Helix {
+                helix_id: *helix_id,
+                segment_id: *segment_id,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn to_selection(self) -> Selection {
+        match self {
+            Self::Nucleotide(nucl) => Selection::Nucleotide(0, nucl),
+            Self::Bond(n1, n2) => Selection::Bond(0, n1, n2),
+            Self::Xover(xover_id) => Selection::Xover(0, xover_id),
+            Self::Strand(strand_id) => Selection::Strand(0, strand_id),
+            Self::Helix {
+                helix_id,
+                segment_id,
+            } => Selection::Helix {
+                design_id: 0,
+                helix_id,
+                segment_id,
+            },
+        }
+    }
+}
+
+/// Return the path of the session file associated to a design.
+pub fn session_path(design_path: &Path) -> PathBuf {
+    let mut ret = design_path.to_path_buf();
+    ret.set_extension(crate::consts::ENS_SESSION_EXTENSION);
+    ret
+}
+
+impl Session {
+    pub fn save(&self, design_path: &Path) -> Result<(), std::io::Error> {
+        let json_content = serde_json::to_string_pretty(self)?;
+        std::fs::write(session_path(design_path), json_content)
+    }
+
+    /// Read the session associated to a design, if there is one.
+    pub fn load(design_path: &Path) -> Option<Self> {
+        let json_content = std::fs::read_to_string(session_path(design_path)).ok()?;
+        match serde_json::from_str(&json_content) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                log::warn!("Could not read session file: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_file_is_next_to_design() {
+        assert_eq!(
+            session_path(Path::new("/tmp/designs/origami.ens")),
+            PathBuf::from("/tmp/designs/origami.enssession")
+        );
+    }
+
+    #[test]
+    fn transient_selections_are_not_saved() {
+        let strand = Selection::Strand(0, 3);
+        assert_eq!(
+            SessionSelection::from_selection(&strand).map(SessionSelection::to_selection),
+            Some(strand)
+        );
+        assert_eq!(SessionSelection::from_selection(&Selection::Nothing), None);
+    }
+}
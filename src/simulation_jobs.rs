@@ -0,0 +1,178 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Simulations that run in the background, simultaneously with each other and with the edition of
+//! the design.
+//!
+//! Each job is started on the current state of the design and only applies its results to the
+//! part of the design that it simulates, so several jobs can run on different groups of
+//! helices or grids. At most [MAX_RUNNING_JOBS] jobs run at the same time, the other ones are
+//! queued.
+
+use crate::app_state::{AppState, SimulationInterface, SimulationReader, SimulationTarget};
+use crate::app_state::{ErrOperation, SimulationUpdate};
+use ensnano_interactor::{
+    RigidBodyConstants, SimulationJobInfo, SimulationJobKind, SimulationJobStatus,
+};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// The maximum number of jobs that run at the same time
+const MAX_RUNNING_JOBS: usize = 3;
+
+#[derive(Default)]
+pub struct SimulationJobs {
+    next_id: usize,
+    running: Vec<RunningJob>,
+    queued: VecDeque<QueuedJob>,
+}
+
+struct QueuedJob {
+    id: usize,
+    kind: SimulationJobKind,
+    parameters: RigidBodyConstants,
+}
+
+struct RunningJob {
+    id: usize,
+    kind: SimulationJobKind,
+    /// The simulation thread stops when this pointer is dropped.
+    interface: Arc<Mutex<dyn SimulationInterface>>,
+    paused: bool,
+    nb_steps: usize,
+}
+
+/// Receives the interface of a simulation that is started as a job.
+#[derive(Default)]
+struct JobInterfaceReceiver(Option<Arc<Mutex<dyn SimulationInterface>>>);
+
+impl SimulationReader for JobInterfaceReceiver {
+    fn attach_state(&mut self, state_chanel: &Arc<Mutex<dyn SimulationInterface>>) {
+        self.0 = Some(state_chanel.clone())
+    }
+}
+
+impl SimulationJobs {
+    /// Add a job to the queue and return its identifier.
+    pub fn push(&mut self, kind: SimulationJobKind, parameters: RigidBodyConstants) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queued.push_back(QueuedJob {
+            id,
+            kind,
+            parameters,
+        });
+        id
+    }
+
+    /// Start the queued jobs while there are less than [MAX_RUNNING_JOBS] running jobs. Return
+    /// the errors that prevented some jobs from starting.
+    pub fn start_queued_jobs(&mut self, app_state: &AppState) -> Vec<(String, ErrOperation)> {
+        let mut errors = Vec::new();
+        while self.running.len() < MAX_RUNNING_JOBS {
+            let job = if let Some(job) = self.queued.pop_front() {
+                job
+            } else {
+                break;
+            };
+            let mut receiver = JobInterfaceReceiver::default();
+            let target = match job.kind.clone() {
+                SimulationJobKind::Roll {
+                    target_helices,
+                    target_xovers,
+                } => SimulationTarget::Roll {
+                    target_helices,
+                    target_xovers,
+                },
+                SimulationJobKind::Twist { grid_id } => SimulationTarget::Twist { grid_id },
+                SimulationJobKind::RelaxGrids => SimulationTarget::Grids,
+            };
+            match app_state.start_simulation_job(job.parameters, &mut receiver, target) {
+                Ok(()) => {
+                    if let Some(interface) = receiver.0 {
+                        self.running.push(RunningJob {
+                            id: job.id,
+                            kind: job.kind,
+                            interface,
+                            paused: false,
+                            nb_steps: 0,
+                        })
+                    } else {
+                        log::error!("Job {} did not provide an interface", job.kind);
+                    }
+                }
+                Err(e) => errors.push((job.kind.to_string(), e)),
+            }
+        }
+        errors
+    }
+
+    /// Return the new states computed by the running jobs that are not paused, and remove the
+    /// jobs that are finished.
+    pub fn get_updates(&mut self) -> Vec<Box<dyn SimulationUpdate>> {
+        let mut updates = Vec::new();
+        for job in self.running.iter_mut().filter(|j| !j.paused) {
+            if let Some(update) = job.interface.lock().unwrap().get_simulation_state() {
+                job.nb_steps += 1;
+                updates.push(update);
+            }
+        }
+        self.running.retain(|job| {
+            let still_valid = job.interface.lock().unwrap().still_valid();
+            if !still_valid {
+                log::info!("Job {} is finished", job.kind);
+            }
+            still_valid
+        });
+        updates
+    }
+
+    pub fn set_paused(&mut self, id: usize, paused: bool) {
+        if let Some(job) = self.running.iter_mut().find(|j| j.id == id) {
+            job.paused = paused;
+        }
+    }
+
+    /// Stop a running job or remove a queued job from the queue.
+    pub fn stop(&mut self, id: usize) {
+        self.running.retain(|j| j.id != id);
+        self.queued.retain(|j| j.id != id);
+    }
+
+    pub fn infos(&self) -> Vec<SimulationJobInfo> {
+        let running = self.running.iter().map(|job| SimulationJobInfo {
+            id: job.id,
+            name: job.kind.to_string(),
+            status: if job.paused {
+                SimulationJobStatus::Paused
+            } else {
+                SimulationJobStatus::Running
+            },
+            progress: job.interface.lock().unwrap().progress(),
+            nb_steps: job.nb_steps,
+        });
+        let queued = self.queued.iter().map(|job| SimulationJobInfo {
+            id: job.id,
+            name: job.kind.to_string(),
+            status: SimulationJobStatus::Queued,
+            progress: None,
+            nb_steps: 0,
+        });
+        running.chain(queued).collect()
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ENSnano, a 3d graphical application for DNA nanostructures.
+    Copyright (C) 2021  Nicolas Levy <nicolaspierrelevy@gmail.com> and Nicolas Schabanel <nicolas.schabanel@ens-lyon.fr>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! This module defines the interactive tutorial.
+//!
+//! The tutorial is a sequence of steps. Each step gives an instruction to the user and refers to
+//! a region of the window that the GUI highlights. The tutorial advances to the next step as soon
+//! as the user has done what was expected, which is detected by comparing the current state of the
+//! application with the one it had when the step started.
+
+use super::gui::{TutorialHighlight, TutorialStatus};
+use super::AppState;
+use ensnano_gui::AppState as GuiAppState;
+use ensnano_interactor::{DesignStatistics, Selection};
+
+/// A request made by the user through the GUI to control the tutorial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialRequest {
+    Start,
+    SkipStep,
+    Stop,
+}
+
+/// The information needed to decide whether a step is completed.
+pub(crate) struct TutorialContext<'a> {
+    /// The state of the application when the step started
+    pub initial_state: &'a AppState,
+    pub current_state: &'a AppState,
+    pub need_save: bool,
+}
+
+impl<'a> TutorialContext<'a> {
+    fn initial_statistics(&self) -> DesignStatistics {
+        self.initial_state
+            .get_design_reader()
+            .get_design_statistics()
+    }
+
+    fn current_statistics(&self) -> DesignStatistics {
+        self.current_state
+            .get_design_reader()
+            .get_design_statistics()
+    }
+}
+
+struct TutorialStep {
+    instruction: &'static str,
+    highlight: TutorialHighlight,
+    is_completed: fn(&TutorialContext) -> bool,
+}
+
+const GRID_TAB: usize = 0;
+
+const STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        instruction: "Add a grid to the design by clicking on one of the grid buttons of the grid \
+            tab.",
+        highlight: TutorialHighlight::LeftPanelTab(GRID_TAB),
+        is_completed: grid_added,
+    },
+    TutorialStep {
+        instruction: "Select the helix building mode with the corresponding button of the top bar.",
+        highlight: TutorialHighlight::TopBar,
+        is_completed: building_helices,
+    },
+    TutorialStep {
+        instruction: "Click on two neighbouring positions of the grid to add two helices.",
+        highlight: TutorialHighlight::Scene3D,
+        is_completed: two_helices_added,
+    },
+    TutorialStep {
+        instruction: "Drag the end of a strand onto the neighbouring helix to make a cross-over \
+            between the two helices.",
+        highlight: TutorialHighlight::FlatScene,
+        is_completed: strands_linked,
+    },
+    TutorialStep {
+        instruction: "Choose the strand selection mode in the top bar and click on a strand to \
+            select it.",
+        highlight: TutorialHighlight::TopBar,
+        is_completed: strand_selected,
+    },
+    TutorialStep {
+        instruction: "Make the selected strand the scaffold of the design with the \
+            \"Scaffold\" checkbox.",
+        highlight: TutorialHighlight::ContextualPanel,
+        is_completed: scaffold_set,
+    },
+    TutorialStep {
+        instruction: "Save your design with the save button of the top bar.",
+        highlight: TutorialHighlight::TopBar,
+        is_completed: design_saved,
+    },
+];
+
+fn grid_added(ctx: &TutorialContext) -> bool {
+    ctx.current_state.get_design_reader().get_nb_grids()
+        > ctx.initial_state.get_design_reader().get_nb_grids()
+}
+
+fn building_helices(ctx: &TutorialContext) -> bool {
+    ctx.current_state.get_action_mode().is_build()
+}
+
+fn two_helices_added(ctx: &TutorialContext) -> bool {
+    ctx.current_statistics().nb_helices >= ctx.initial_statistics().nb_helices + 2
+}
+
+/// Making a cross-over between two strands merges them
+fn strands_linked(ctx: &TutorialContext) -> bool {
+    ctx.current_statistics().nb_strands < ctx.initial_statistics().nb_strands
+}
+
+fn strand_selected(ctx: &TutorialContext) -> bool {
+    ctx.current_state
+        .get_selection()
+        .as_ref()
+        .iter()
+        .any(|s| matches!(s, Selection::Strand(_, _)))
+}
+
+fn scaffold_set(ctx: &TutorialContext) -> bool {
+    ctx.current_state.get_scaffold_info().is_some()
+}
+
+fn design_saved(ctx: &TutorialContext) -> bool {
+    !ctx.need_save
+}
+
+/// The progression of the user in the tutorial.
+pub(crate) struct Tutorial {
+    step: usize,
+    /// The state of the application when the current step started
+    initial_state: AppState,
+}
+
+impl Tutorial {
+    pub fn new(app_state: AppState) -> Self {
+        Self {
+            step: 0,
+            initial_state: app_state,
+        }
+    }
+
+    pub fn status(&self) -> TutorialStatus {
+        let step = &STEPS[self.step];
+        TutorialStatus {
+            step: self.step,
+            nb_steps: STEPS.len(),
+            instruction: step.instruction,
+            highlight: step.highlight,
+        }
+    }
+
+    /// Go to the next step. Return false if the current step was the last one.
+    pub fn next_step(&mut self, app_state: AppState) -> bool {
+        self.step += 1;
+        self.initial_state = app_state;
+        self.step < STEPS.len()
+    }
+
+    /// Go to the next step if the current one is completed. Return false if the tutorial is
+    /// finished.
+    pub fn update(&mut self, app_state: &AppState, need_save: bool) -> bool {
+        let context = TutorialContext {
+            initial_state: &self.initial_state,
+            current_state: app_state,
+            need_save,
+        };
+        if (STEPS[self.step].is_completed)(&context) {
+            self.next_step(app_state.clone())
+        } else {
+            true
+        }
+    }
+}